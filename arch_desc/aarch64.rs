@@ -6,9 +6,21 @@ mod inst_operand;
 pub use inst_operand::*;
 mod register;
 pub use register::*;
+mod disassemble;
+pub use disassemble::*;
+mod assemble;
+pub use assemble::*;
 mod compiler;
 pub use compiler::*;
 pub(crate) mod compiler_prelude;
+pub(crate) mod exception;
+pub(crate) mod mmu;
+pub(crate) mod sysreg;
 
 mod inst_decode;
 pub(crate) use inst_decode::*;
+mod inst_encode;
+pub(crate) use inst_encode::*;
+
+#[cfg(test)]
+mod testing;
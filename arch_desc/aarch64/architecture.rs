@@ -1,6 +1,9 @@
-use core::{ir::BasicBlock, Architecture, Interrupt, Primitive, RegisterFileDesc};
+use core::{
+    ir::BasicBlock, Abort, AccessType, Architecture, Interrupt, PageTranslation, Primitive,
+    RegisterFileDesc, RegisterId, TranslationRegime, ViewWrite,
+};
 
-use super::{AArch64Inst, AArch64MnemonicHint, AArch64Register, AArch64RegisterId};
+use super::{exception, mmu, AArch64Inst, AArch64MnemonicHint, AArch64Register, AArch64RegisterId};
 
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
 pub struct AArch64Architecture;
@@ -17,8 +20,47 @@ impl Architecture for AArch64Architecture {
         match name {
             // handle special registers
             "sp" => return AArch64RegisterId::Sp,
+            "wsp" => return AArch64RegisterId::Wsp,
+            "xzr" => return AArch64RegisterId::Xzr,
+            "wzr" => return AArch64RegisterId::Wzr,
             "pc" => return AArch64RegisterId::Pc,
             "pstate" => return AArch64RegisterId::Pstate,
+            "nzcv" => return AArch64RegisterId::Nzcv,
+            "fpcr" => return AArch64RegisterId::Fpcr,
+            "fpsr" => return AArch64RegisterId::Fpsr,
+            "tpidr_el0" => return AArch64RegisterId::TpidrEl0,
+            "tpidrro_el0" => return AArch64RegisterId::TpidrroEl0,
+            "cntvct_el0" => return AArch64RegisterId::CntvctEl0,
+            "sp_el0" => return AArch64RegisterId::SpEl0,
+            "sp_el1" => return AArch64RegisterId::SpEl1,
+            "sctlr_el1" => return AArch64RegisterId::SctlrEl1,
+            "cpacr_el1" => return AArch64RegisterId::CpacrEl1,
+            "ttbr0_el1" => return AArch64RegisterId::Ttbr0El1,
+            "ttbr1_el1" => return AArch64RegisterId::Ttbr1El1,
+            "tcr_el1" => return AArch64RegisterId::TcrEl1,
+            "mair_el1" => return AArch64RegisterId::MairEl1,
+            "vbar_el1" => return AArch64RegisterId::VbarEl1,
+            "elr_el1" => return AArch64RegisterId::ElrEl1,
+            "spsr_el1" => return AArch64RegisterId::SpsrEl1,
+            "esr_el1" => return AArch64RegisterId::EsrEl1,
+            "far_el1" => return AArch64RegisterId::FarEl1,
+            "contextidr_el1" => return AArch64RegisterId::ContextidrEl1,
+            "tpidr_el1" => return AArch64RegisterId::TpidrEl1,
+            "cntkctl_el1" => return AArch64RegisterId::CntkctlEl1,
+            "sp_el2" => return AArch64RegisterId::SpEl2,
+            "hcr_el2" => return AArch64RegisterId::HcrEl2,
+            "vbar_el2" => return AArch64RegisterId::VbarEl2,
+            "elr_el2" => return AArch64RegisterId::ElrEl2,
+            "spsr_el2" => return AArch64RegisterId::SpsrEl2,
+            "esr_el2" => return AArch64RegisterId::EsrEl2,
+            "far_el2" => return AArch64RegisterId::FarEl2,
+            "sp_el3" => return AArch64RegisterId::SpEl3,
+            "scr_el3" => return AArch64RegisterId::ScrEl3,
+            "vbar_el3" => return AArch64RegisterId::VbarEl3,
+            "elr_el3" => return AArch64RegisterId::ElrEl3,
+            "spsr_el3" => return AArch64RegisterId::SpsrEl3,
+            "esr_el3" => return AArch64RegisterId::EsrEl3,
+            "far_el3" => return AArch64RegisterId::FarEl3,
             _ => {}
         }
 
@@ -27,6 +69,9 @@ impl Architecture for AArch64Architecture {
 
         assert!(reg_number < 32, "invalid register number {}", reg_number);
         match reg_prefix {
+            // Register 31 is sp or the zero register, it has no number
+            "x" | "w" if reg_number == 31 => unreachable!("invalid register name {}", name),
+
             // Handle scalar registers
             "x" => AArch64RegisterId::X(reg_number),
             "w" => AArch64RegisterId::W(reg_number),
@@ -63,16 +108,148 @@ impl Architecture for AArch64Architecture {
     }
 
     fn get_flag_register() -> Self::RegId {
-        AArch64RegisterId::Pstate
+        AArch64RegisterId::Nzcv
     }
 
     fn get_register_file_desc() -> RegisterFileDesc {
-        todo!()
+        let mut desc = RegisterFileDesc::new();
+
+        let storage = (0..31)
+            .map(AArch64RegisterId::X)
+            .chain([
+                AArch64RegisterId::Sp,
+                AArch64RegisterId::Pc,
+                AArch64RegisterId::Pstate,
+                AArch64RegisterId::Nzcv,
+            ])
+            .chain(AArch64RegisterId::SYSTEM)
+            .chain((0..32).map(AArch64RegisterId::V));
+        for reg in storage {
+            desc.add_register(reg.raw(), reg.ty().size_in_bytes(), false);
+        }
+        desc.add_zero_register(AArch64RegisterId::Xzr.raw(), 8);
+
+        let views = (0..31)
+            .map(AArch64RegisterId::W)
+            .chain([AArch64RegisterId::Wsp, AArch64RegisterId::Wzr])
+            .chain((0..32).flat_map(|v| {
+                [
+                    AArch64RegisterId::Q(v),
+                    AArch64RegisterId::D(v),
+                    AArch64RegisterId::S(v),
+                    AArch64RegisterId::H(v),
+                    AArch64RegisterId::B(v),
+                ]
+            }));
+        for reg in views {
+            desc.add_view(
+                reg.raw(),
+                reg.parent().raw(),
+                0,
+                reg.ty().size_in_bytes(),
+                ViewWrite::ZeroExtend,
+            );
+        }
+
+        desc
     }
 
     fn interrupt(interrupt: Interrupt) -> BasicBlock {
         let mut bb = BasicBlock::new(0);
+        exception::gen_take(&mut bb, interrupt);
+        bb
+    }
+
+    fn translation_regime(registers: &[u8]) -> Option<TranslationRegime> {
+        mmu::translation_regime(registers)
+    }
+
+    fn unprivileged_regime(regime: TranslationRegime) -> TranslationRegime {
+        mmu::unprivileged_regime(regime)
+    }
+
+    fn translate(
+        registers: &[u8],
+        regime: TranslationRegime,
+        address: u64,
+        access: AccessType,
+        read: &mut dyn FnMut(u64) -> Option<u64>,
+    ) -> Result<PageTranslation, Abort> {
+        mmu::translate(registers, regime, address, access, read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::Register;
+
+    use super::*;
+
+    #[test]
+    fn register_file_covers_every_register() {
+        let desc = AArch64Architecture::get_register_file_desc();
+
+        // X0-X30, SP, PC, PSTATE, NZCV, the system registers and V0-V31
+        assert_eq!(desc.total_size(), 70 * 8 + 32 * 16);
+        assert_eq!(desc.storage_registers().count(), 70 + 32);
+
+        for name in [
+            "x0",
+            "w30",
+            "sp",
+            "wsp",
+            "xzr",
+            "wzr",
+            "pc",
+            "nzcv",
+            "tpidr_el0",
+            "sp_el0",
+            "vbar_el1",
+        ] {
+            let id = AArch64Architecture::get_register_by_name(name);
+            let reg = AArch64Register(id);
+
+            assert_eq!(desc.register(id.raw()).size, reg.size(), "{name}");
+        }
+        for prefix in ["v", "q", "d", "s", "h", "b"] {
+            let id = AArch64Architecture::get_register_by_name(format!("{prefix}31"));
+            assert_eq!(desc.register(id.raw()).size, AArch64Register(id).size());
+        }
+    }
+
+    #[test]
+    fn views_alias_the_low_bits_of_their_parent() {
+        let desc = AArch64Architecture::get_register_file_desc();
+
+        for (view, parent) in [
+            (AArch64RegisterId::W(3), AArch64RegisterId::X(3)),
+            (AArch64RegisterId::Wsp, AArch64RegisterId::Sp),
+            (AArch64RegisterId::Q(7), AArch64RegisterId::V(7)),
+            (AArch64RegisterId::B(7), AArch64RegisterId::V(7)),
+        ] {
+            let view_desc = desc.register(view.raw());
+            let parent_desc = desc.register(parent.raw());
+
+            assert_eq!(AArch64Register(view).parent(), parent);
+            assert_eq!(view_desc.parent, Some(parent.raw()));
+            assert_eq!(view_desc.offset, parent_desc.offset);
+            assert_eq!(
+                desc.write_extent(view.raw()),
+                parent_desc.offset..parent_desc.offset + parent_desc.size
+            );
+        }
+    }
 
-        todo!()
+    #[test]
+    fn zero_register_has_no_storage() {
+        let desc = AArch64Architecture::get_register_file_desc();
+
+        assert!(desc.register(AArch64RegisterId::Xzr.raw()).is_zero);
+        assert!(desc.register(AArch64RegisterId::Wzr.raw()).is_zero);
+        assert!(desc.write_extent(AArch64RegisterId::Xzr.raw()).is_empty());
+        assert_eq!(
+            AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, 31u8),
+            AArch64RegisterId::Xzr
+        );
     }
 }
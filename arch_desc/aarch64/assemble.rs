@@ -0,0 +1,1603 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::compiler_prelude::decode_bit_masks;
+use super::decode_aarch64_inst;
+use super::disassemble::{BarrierOption, CONDITIONS, EXTENDS, SHIFTS};
+use super::sysreg::SYSREGS;
+use super::{
+    AArch64Inst, AArch64RegisterId, B5B40Imm14Rt, Barriers, Bitfield, CondCmpImm, CondCmpReg,
+    DataProc2Src, DataProc3Src, ExceptionGen, ExtractImm, Hints, HwImm16Rd, Imm16, Imm19Cond,
+    Imm19Rt, Imm26, LdStRegUnscaledImm, LoadStoreRegPair, LoadStoreRegRegOffset, LogicalImm,
+    OpcSizeImm12RnRt, PcRelAddressing, RmCondRnRd, RnRd, RsRt2RnRt, ShImm12RnRd, ShiftRmImm6RnRd,
+    SysRegMov, UncondBranchReg,
+};
+use Reject::{OutOfRange, Unsupported};
+
+/// The error of a line that couldn't be assembled, with the text of the line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AssembleError {
+    /// An operand isn't a register, an immediate, a memory operand or a name.
+    InvalidOperand(String),
+    /// The instruction isn't supported by the assembler, or not with these operands.
+    Unsupported(String),
+    /// An immediate doesn't fit in its field or can't be encoded.
+    OutOfRange(String),
+}
+
+impl fmt::Display for AssembleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssembleError::InvalidOperand(line) => write!(f, "invalid operand in `{line}`"),
+            AssembleError::Unsupported(line) => write!(f, "unsupported instruction `{line}`"),
+            AssembleError::OutOfRange(line) => write!(f, "immediate out of range in `{line}`"),
+        }
+    }
+}
+
+impl std::error::Error for AssembleError {}
+
+/// Assemble the instructions of `source` into their words. Instructions are separated by new
+/// lines or `;` and comments start with `//`.
+///
+/// Only these general-purpose instructions are supported:
+/// - ADD, SUB, CMP, CMN, NEG and the logical instructions with immediates or shifted registers,
+///   MOV, MOVZ, MOVN and MOVK
+/// - the shifts, EXTR, the bitfield instructions and their aliases, SXT* and UXT*
+/// - RBIT, REV, REV16, REV32, CLZ and CLS
+/// - MADD, MSUB, MUL, MNEG, SMULH, UMULH, SDIV, UDIV and the long multiplications SMADDL,
+///   UMADDL, SMSUBL, UMSUBL, SMULL, UMULL, SMNEGL and UMNEGL
+/// - the conditional selects and their aliases, CCMP and CCMN
+/// - LDR, STR and their byte, halfword and signed forms with an immediate or register offset,
+///   LDUR and STUR, literal loads, LDP, STP and LDPSW
+/// - the exclusive loads and stores, LDAR and STLR
+/// - B, BL, B.cond, CBZ, CBNZ, TBZ, TBNZ, BR, BLR, RET, ERET, ADR and ADRP
+/// - SVC, HVC, SMC, BRK, HLT, UDF, the hints, DMB, DSB, ISB, CLREX, MRS and MSR
+///
+/// The unprivileged, RCpc and atomic memory instructions, PRFM, SYS and its aliases like TLBI
+/// and DC, and the SIMD&FP instructions aren't. The syntax is the one printed by the
+/// disassembler, branch targets are offsets like `#-8`, see `assemble_at` for absolute targets.
+pub fn assemble(source: &str) -> Result<Vec<u32>, AssembleError> {
+    assemble_lines(source, None)
+}
+
+/// Assemble the instructions of `source` to be placed from `address`. The pc-relative targets
+/// can also be the absolute addresses printed by `display_at`, like `b.ne 0x40010`.
+pub fn assemble_at(source: &str, address: u64) -> Result<Vec<u32>, AssembleError> {
+    assemble_lines(source, Some(address))
+}
+
+fn assemble_lines(source: &str, address: Option<u64>) -> Result<Vec<u32>, AssembleError> {
+    source
+        .lines()
+        .map(|line| line.split("//").next().unwrap_or_default())
+        .flat_map(|line| line.split(';'))
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            let pc = address.map(|address| address.wrapping_add(4 * i as u64));
+            parse_line(line, pc).map(|inst| inst.encode())
+        })
+        .collect()
+}
+
+/// Parse a single instruction in the syntax of the disassembler, see `assemble`. The operands get
+/// the same registers as when the instruction is decoded.
+impl FromStr for AArch64Inst {
+    type Err = AssembleError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        parse_line(line, None)
+    }
+}
+
+impl AArch64Inst {
+    /// Parse the instruction fetched from `pc`, the pc-relative targets can be absolute
+    /// addresses as printed by `display_at`.
+    pub fn parse_at(line: &str, pc: u64) -> Result<Self, AssembleError> {
+        parse_line(line, Some(pc))
+    }
+}
+
+fn parse_line(line: &str, pc: Option<u64>) -> Result<AArch64Inst, AssembleError> {
+    let line = line.trim();
+    let (mnemonic, operands) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let mnemonic = mnemonic.to_ascii_lowercase();
+    let mut operands = split_operands(operands)
+        .map(|operand| {
+            Operand::parse(operand).ok_or_else(|| AssembleError::InvalidOperand(line.into()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // An absolute target becomes the offset from the instruction, or from its page for ADRP
+    let pc_relative = PC_RELATIVE.contains(&mnemonic.as_str()) || mnemonic.starts_with("b.");
+    if let (Some(pc), Some(Operand::Addr(target)), true) = (pc, operands.last(), pc_relative) {
+        let base = if mnemonic == "adrp" { pc & !0xfff } else { pc };
+        let offset = Operand::Imm(target.wrapping_sub(base) as i64);
+        *operands.last_mut().unwrap() = offset;
+    }
+
+    let error = |reject| match reject {
+        Reject::Unsupported => AssembleError::Unsupported(line.into()),
+        Reject::OutOfRange => AssembleError::OutOfRange(line.into()),
+    };
+    let inst = parse_inst(&mnemonic, &operands).map_err(error)?;
+    // The decoder picks the register views of the operands, e.g. `x1` for a 32-bit MOVZ
+    Ok(decode_aarch64_inst(&inst.encode().to_le_bytes()))
+}
+
+/// The instructions with a pc-relative target as their last operand, besides `b.cond`.
+const PC_RELATIVE: [&str; 10] = [
+    "b", "bl", "cbz", "cbnz", "tbz", "tbnz", "adr", "adrp", "ldr", "ldrsw",
+];
+
+/// Why the operands were rejected, the line is added to make an `AssembleError`.
+enum Reject {
+    Unsupported,
+    OutOfRange,
+}
+
+type Assembled = Result<AArch64Inst, Reject>;
+
+// Operands
+
+/// The operands separated by the commas outside of the brackets.
+fn split_operands(operands: &str) -> impl Iterator<Item = &str> {
+    let mut depth = 0;
+    operands
+        .split(move |c| {
+            match c {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                _ => {}
+            }
+            c == ',' && depth == 0
+        })
+        .map(str::trim)
+        .filter(|operand| !operand.is_empty())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operand<'a> {
+    Reg(AArch64RegisterId),
+    Imm(i64),
+    /// An absolute address, the target of a pc-relative instruction, e.g. `0x40010`.
+    Addr(u64),
+    /// A shift by an immediate, e.g. `lsl #12`, the kind is the index in `SHIFTS`.
+    Shift(u8, u8),
+    /// A base register with an immediate offset, `[xn]`, `[xn, #imm]` or pre-indexed
+    /// `[xn, #imm]!`.
+    Mem(AArch64RegisterId, i64, bool),
+    /// A base register with a register offset, extended and optionally shifted,
+    /// e.g. `[xn, wm, sxtw #2]`. The extend is the option field, `lsl` is `uxtx`.
+    MemReg(AArch64RegisterId, AArch64RegisterId, u8, Option<u8>),
+    /// A condition, a barrier option or a system register.
+    Name(&'a str),
+}
+
+impl<'a> Operand<'a> {
+    fn parse(text: &'a str) -> Option<Self> {
+        if let Some(imm) = text.strip_prefix('#') {
+            return parse_int(imm).map(Operand::Imm);
+        }
+        if let Some(address) = text.strip_prefix('[') {
+            return parse_address(address);
+        }
+        if text.starts_with(|c: char| c.is_ascii_digit()) {
+            return parse_int(text).map(|address| Operand::Addr(address as u64));
+        }
+        if let Some(id) = parse_gpr(text) {
+            return Some(Operand::Reg(id));
+        }
+        if let Some((shift, amount)) = text.split_once(char::is_whitespace) {
+            let kind = SHIFTS.iter().position(|s| s.eq_ignore_ascii_case(shift))?;
+            let amount = amount.trim().strip_prefix('#')?.parse().ok()?;
+            return Some(Operand::Shift(kind as u8, amount));
+        }
+        text.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+            .then_some(Operand::Name(text))
+    }
+}
+
+/// A decimal or `0x` hexadecimal integer, optionally negative.
+fn parse_int(text: &str) -> Option<i64> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+    let value = match digits.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok()? as i64,
+        None => digits.parse().ok()?,
+    };
+    Some(if negative {
+        value.wrapping_neg()
+    } else {
+        value
+    })
+}
+
+/// A general-purpose register, `x0`-`x30`, `w0`-`w30`, the zero registers or the stack pointer.
+fn parse_gpr(text: &str) -> Option<AArch64RegisterId> {
+    let text = text.to_ascii_lowercase();
+    let id = match text.as_str() {
+        "sp" => AArch64RegisterId::Sp,
+        "wsp" => AArch64RegisterId::Wsp,
+        "xzr" => AArch64RegisterId::Xzr,
+        "wzr" => AArch64RegisterId::Wzr,
+        _ => {
+            let (view, n_text) = text.split_at_checked(1)?;
+            let n = n_text
+                .parse()
+                .ok()
+                .filter(|&n: &u8| n < 31 && n.to_string() == n_text)?;
+            match view {
+                "x" => AArch64RegisterId::X(n),
+                "w" => AArch64RegisterId::W(n),
+                _ => return None,
+            }
+        }
+    };
+    Some(id)
+}
+
+/// The memory operand after the opening bracket.
+fn parse_address(text: &str) -> Option<Operand<'_>> {
+    let (inner, writeback) = match text.strip_suffix("]!") {
+        Some(inner) => (inner, true),
+        None => (text.strip_suffix(']')?, false),
+    };
+    let mut parts = inner.split(',').map(str::trim);
+    let base = parse_gpr(parts.next()?).filter(|id| is_64(*id))?;
+    let Some(offset) = parts.next() else {
+        return (!writeback).then_some(Operand::Mem(base, 0, false));
+    };
+
+    if let Some(imm) = offset.strip_prefix('#') {
+        return match parts.next() {
+            None => Some(Operand::Mem(base, parse_int(imm)?, writeback)),
+            Some(_) => None,
+        };
+    }
+
+    let index = parse_gpr(offset)?;
+    if writeback {
+        return None;
+    }
+    let (option, amount) = match parts.next() {
+        None => (0b011, None),
+        Some(extend) => {
+            let (extend, amount) = match extend.split_once(char::is_whitespace) {
+                Some((extend, amount)) => {
+                    (extend, Some(amount.trim().strip_prefix('#')?.parse().ok()?))
+                }
+                None => (extend, None),
+            };
+            let option = match extend.to_ascii_lowercase().as_str() {
+                "lsl" => 0b011,
+                extend => EXTENDS.iter().position(|e| *e == extend)? as u8,
+            };
+            (option, amount)
+        }
+    };
+    parts
+        .next()
+        .is_none()
+        .then_some(Operand::MemReg(base, index, option, amount))
+}
+
+fn is_64(id: AArch64RegisterId) -> bool {
+    matches!(
+        id,
+        AArch64RegisterId::X(_) | AArch64RegisterId::Sp | AArch64RegisterId::Xzr
+    )
+}
+
+fn is_sp(id: AArch64RegisterId) -> bool {
+    matches!(id, AArch64RegisterId::Sp | AArch64RegisterId::Wsp)
+}
+
+/// The width of the registers, `true` for 64 bits, if they all have the same width and only
+/// those allowed to be `sp` are.
+fn width(regs: &[(AArch64RegisterId, bool)]) -> Result<bool, Reject> {
+    let sf = is_64(regs[0].0);
+    let valid = regs
+        .iter()
+        .all(|&(id, allow_sp)| is_64(id) == sf && (allow_sp || !is_sp(id)));
+    valid.then_some(sf).ok_or(Unsupported)
+}
+
+fn zr(sf: bool) -> AArch64RegisterId {
+    match sf {
+        true => AArch64RegisterId::Xzr,
+        false => AArch64RegisterId::Wzr,
+    }
+}
+
+/// `value` as an unsigned field of `bits` bits.
+fn unsigned(value: i64, bits: u32) -> Result<u32, Reject> {
+    (0..1 << bits)
+        .contains(&value)
+        .then_some(value as u32)
+        .ok_or(OutOfRange)
+}
+
+/// `value` as a two's complement field of `bits` bits.
+fn signed(value: i64, bits: u32) -> Result<u32, Reject> {
+    let half = 1 << (bits - 1);
+    (-half..half)
+        .contains(&value)
+        .then_some(value as u32 & ((1 << bits) - 1))
+        .ok_or(OutOfRange)
+}
+
+/// `value` divided by `scale`, it must be a multiple of it.
+fn scaled(value: i64, scale: i64) -> Result<i64, Reject> {
+    match value % scale {
+        0 => Ok(value / scale),
+        _ => Err(OutOfRange),
+    }
+}
+
+fn condition(name: &str) -> Result<u8, Reject> {
+    let name = name.to_ascii_lowercase();
+    let cond = match name.as_str() {
+        "cs" => 2,
+        "cc" => 3,
+        name => CONDITIONS
+            .iter()
+            .position(|c| *c == name)
+            .ok_or(Unsupported)?,
+    };
+    Ok(cond as u8)
+}
+
+// Instructions
+
+fn parse_inst(m: &str, ops: &[Operand]) -> Assembled {
+    use Operand::*;
+
+    match (m, ops) {
+        ("add" | "adds" | "sub" | "subs", [Reg(rd), Reg(rn), Imm(imm)]) => {
+            add_sub_imm(m, *rd, *rn, *imm, 0)
+        }
+        ("add" | "adds" | "sub" | "subs", [Reg(rd), Reg(rn), Imm(imm), Shift(0, 12)]) => {
+            add_sub_imm(m, *rd, *rn, *imm, 1)
+        }
+        ("cmp" | "cmn", [Reg(rn), Imm(imm)]) => {
+            add_sub_imm(flag_setting(m), zr(is_64(*rn)), *rn, *imm, 0)
+        }
+        ("cmp" | "cmn", [Reg(rn), Imm(imm), Shift(0, 12)]) => {
+            add_sub_imm(flag_setting(m), zr(is_64(*rn)), *rn, *imm, 1)
+        }
+        ("mov", [Reg(rd), Reg(rn)]) if is_sp(*rd) || is_sp(*rn) => {
+            add_sub_imm("add", *rd, *rn, 0, 0)
+        }
+        ("mov", [Reg(rd), Reg(rm)]) => shifted_reg("orr", *rd, zr(is_64(*rd)), *rm, 0, 0),
+        ("mov", [Reg(rd), Imm(imm)]) => mov_imm(*rd, *imm),
+
+        (_, [Reg(rd), Reg(rn), Reg(rm)]) if SHIFTED_REG_OPS.contains(&m) => {
+            shifted_reg(m, *rd, *rn, *rm, 0, 0)
+        }
+        (_, [Reg(rd), Reg(rn), Reg(rm), Shift(shift, amount)]) if SHIFTED_REG_OPS.contains(&m) => {
+            shifted_reg(m, *rd, *rn, *rm, *shift, *amount)
+        }
+        ("cmp" | "cmn" | "tst", [Reg(rn), Reg(rm)]) => {
+            shifted_reg(flag_setting(m), zr(is_64(*rn)), *rn, *rm, 0, 0)
+        }
+        ("cmp" | "cmn" | "tst", [Reg(rn), Reg(rm), Shift(shift, amount)]) => {
+            shifted_reg(flag_setting(m), zr(is_64(*rn)), *rn, *rm, *shift, *amount)
+        }
+        ("neg" | "negs" | "mvn", [Reg(rd), Reg(rm)]) => {
+            shifted_reg(negated(m), *rd, zr(is_64(*rd)), *rm, 0, 0)
+        }
+        ("neg" | "negs" | "mvn", [Reg(rd), Reg(rm), Shift(shift, amount)]) => {
+            shifted_reg(negated(m), *rd, zr(is_64(*rd)), *rm, *shift, *amount)
+        }
+
+        ("and" | "orr" | "eor" | "ands", [Reg(rd), Reg(rn), Imm(imm)]) => {
+            logical_imm(m, *rd, *rn, *imm)
+        }
+        ("tst", [Reg(rn), Imm(imm)]) => logical_imm("ands", zr(is_64(*rn)), *rn, *imm),
+
+        ("movz" | "movn" | "movk", [Reg(rd), Imm(imm)]) => move_wide(m, *rd, *imm, 0),
+        ("movz" | "movn" | "movk", [Reg(rd), Imm(imm), Shift(0, shift)]) => {
+            move_wide(m, *rd, *imm, *shift)
+        }
+
+        ("lsl" | "lsr" | "asr" | "ror", [Reg(rd), Reg(rn), Imm(shift)]) => {
+            shift_imm(m, *rd, *rn, *shift)
+        }
+        ("lsl" | "lsr" | "asr" | "ror", [Reg(rd), Reg(rn), Reg(rm)]) => {
+            data_proc_2src(&format!("{m}v"), *rd, *rn, *rm)
+        }
+        ("lslv" | "lsrv" | "asrv" | "rorv" | "udiv" | "sdiv", [Reg(rd), Reg(rn), Reg(rm)]) => {
+            data_proc_2src(m, *rd, *rn, *rm)
+        }
+        ("extr", [Reg(rd), Reg(rn), Reg(rm), Imm(lsb)]) => extract(*rd, *rn, *rm, *lsb),
+        ("sbfm" | "bfm" | "ubfm", [Reg(rd), Reg(rn), Imm(immr), Imm(imms)]) => {
+            let sf = width(&[(*rd, false), (*rn, false)])?;
+            let bits = if sf { 6 } else { 5 };
+            let (immr, imms) = (unsigned(*immr, bits)?, unsigned(*imms, bits)?);
+            bitfield(m, *rd, *rn, immr as u8, imms as u8)
+        }
+        (_, [Reg(rd), Reg(rn), Imm(lsb), Imm(width)]) if BITFIELD_ALIASES.contains(&m) => {
+            bitfield_alias(m, *rd, *rn, *lsb, *width)
+        }
+        ("bfc", [Reg(rd), Imm(lsb), Imm(width)]) => {
+            bitfield_alias("bfi", *rd, zr(is_64(*rd)), *lsb, *width)
+        }
+        ("sxtb" | "sxth" | "sxtw" | "uxtb" | "uxth", [Reg(rd), Reg(rn)]) => extend(m, *rd, *rn),
+
+        ("madd" | "msub", [Reg(rd), Reg(rn), Reg(rm), Reg(ra)]) => {
+            data_proc_3src(m, *rd, *rn, *rm, *ra)
+        }
+        ("mul" | "mneg", [Reg(rd), Reg(rn), Reg(rm)]) => {
+            let m = if m == "mul" { "madd" } else { "msub" };
+            data_proc_3src(m, *rd, *rn, *rm, zr(is_64(*rd)))
+        }
+        ("smulh" | "umulh", [Reg(rd), Reg(rn), Reg(rm)]) => {
+            data_proc_3src(m, *rd, *rn, *rm, AArch64RegisterId::Xzr)
+        }
+        ("smaddl" | "smsubl" | "umaddl" | "umsubl", [Reg(rd), Reg(rn), Reg(rm), Reg(ra)]) => {
+            multiply_long(m, *rd, *rn, *rm, *ra)
+        }
+        ("smull" | "smnegl" | "umull" | "umnegl", [Reg(rd), Reg(rn), Reg(rm)]) => {
+            let m = match m {
+                "smull" => "smaddl",
+                "smnegl" => "smsubl",
+                "umull" => "umaddl",
+                _ => "umsubl",
+            };
+            multiply_long(m, *rd, *rn, *rm, AArch64RegisterId::Xzr)
+        }
+        ("rbit" | "rev16" | "rev32" | "rev" | "rev64" | "clz" | "cls", [Reg(rd), Reg(rn)]) => {
+            data_proc_1src(m, *rd, *rn)
+        }
+
+        ("csel" | "csinc" | "csinv" | "csneg", [Reg(rd), Reg(rn), Reg(rm), Name(cond)]) => {
+            cond_select(m, *rd, *rn, *rm, condition(cond)?)
+        }
+        ("cset" | "csetm", [Reg(rd), Name(cond)]) => {
+            let m = if m == "cset" { "csinc" } else { "csinv" };
+            let zr = zr(is_64(*rd));
+            cond_select(m, *rd, zr, zr, inverted(condition(cond)?)?)
+        }
+        ("cinc" | "cinv" | "cneg", [Reg(rd), Reg(rn), Name(cond)]) => {
+            let m = match m {
+                "cinc" => "csinc",
+                "cinv" => "csinv",
+                _ => "csneg",
+            };
+            cond_select(m, *rd, *rn, *rn, inverted(condition(cond)?)?)
+        }
+        ("ccmp" | "ccmn", [Reg(rn), Reg(rm), Imm(nzcv), Name(cond)]) => {
+            let sf = width(&[(*rn, false), (*rm, false)])?;
+            let variant = match (m, sf) {
+                ("ccmn", false) => AArch64Inst::CcmnRegVar32,
+                ("ccmp", false) => AArch64Inst::CcmpRegVar32,
+                ("ccmn", true) => AArch64Inst::CcmnRegVar64,
+                _ => AArch64Inst::CcmpRegVar64,
+            };
+            Ok(variant(CondCmpReg {
+                rm: *rm,
+                cond: condition(cond)?,
+                rn: *rn,
+                nzcv: unsigned(*nzcv, 4)? as u8,
+            }))
+        }
+        ("ccmp" | "ccmn", [Reg(rn), Imm(imm), Imm(nzcv), Name(cond)]) => {
+            let sf = width(&[(*rn, false)])?;
+            let variant = match (m, sf) {
+                ("ccmn", false) => AArch64Inst::CcmnImmVar32,
+                ("ccmp", false) => AArch64Inst::CcmpImmVar32,
+                ("ccmn", true) => AArch64Inst::CcmnImmVar64,
+                _ => AArch64Inst::CcmpImmVar64,
+            };
+            Ok(variant(CondCmpImm {
+                imm5: unsigned(*imm, 5)? as u8,
+                cond: condition(cond)?,
+                rn: *rn,
+                nzcv: unsigned(*nzcv, 4)? as u8,
+            }))
+        }
+
+        (_, [Reg(rt), Mem(rn, offset, true)]) if LOADS_AND_STORES.contains(&m) => {
+            load_store_imm(m, *rt, *rn, *offset, Indexing::Pre)
+        }
+        (_, [Reg(rt), Mem(rn, offset, false)]) if LOADS_AND_STORES.contains(&m) => {
+            // Negative and unaligned offsets fall back to the unscaled form, like `ldur`
+            match load_store_imm(m, *rt, *rn, *offset, Indexing::Offset) {
+                Err(OutOfRange) if signed(*offset, 9).is_ok() => {
+                    load_store_unscaled(m, *rt, *rn, *offset)
+                }
+                assembled => assembled,
+            }
+        }
+        (_, [Reg(rt), Mem(rn, 0, false), Imm(offset)]) if LOADS_AND_STORES.contains(&m) => {
+            load_store_imm(m, *rt, *rn, *offset, Indexing::Post)
+        }
+        (_, [Reg(rt), MemReg(rn, rm, option, amount)]) if LOADS_AND_STORES.contains(&m) => {
+            load_store_reg(m, *rt, *rn, *rm, *option, *amount)
+        }
+        ("ldr" | "ldrsw", [Reg(rt), Imm(offset)]) => load_literal(m, *rt, *offset),
+        (_, [Reg(rt), Mem(rn, offset, false)]) if UNSCALED_LOADS_AND_STORES.contains(&m) => {
+            load_store_unscaled(&m.replacen('u', "", 1), *rt, *rn, *offset)
+        }
+        ("ldp" | "stp" | "ldpsw", [Reg(rt), Reg(rt2), Mem(rn, offset, writeback)]) => {
+            let o = if *writeback { 0b011 } else { 0b010 };
+            load_store_pair(m, *rt, *rt2, *rn, *offset, o)
+        }
+        ("ldp" | "stp" | "ldpsw", [Reg(rt), Reg(rt2), Mem(rn, 0, false), Imm(offset)]) => {
+            load_store_pair(m, *rt, *rt2, *rn, *offset, 0b001)
+        }
+        (_, [Reg(rt), Mem(rn, 0, false)]) if ORDERED_LOADS_AND_STORES.contains(&m) => {
+            exclusive(m, AArch64RegisterId::Xzr, *rt, *rn)
+        }
+        (_, [Reg(rs), Reg(rt), Mem(rn, 0, false)]) if EXCLUSIVE_STORES.contains(&m) => {
+            exclusive(m, *rs, *rt, *rn)
+        }
+
+        ("b" | "bl", [Imm(offset)]) => {
+            let imm26 = signed(scaled(*offset, 4)?, 26)?;
+            let variant = if m == "b" {
+                AArch64Inst::BImm
+            } else {
+                AArch64Inst::BlImm
+            };
+            Ok(variant(Imm26 { imm26 }))
+        }
+        (_, [Imm(offset)]) if m.starts_with("b.") => {
+            let imm19 = signed(scaled(*offset, 4)?, 19)?;
+            let cond = condition(&m[2..])?;
+            Ok(AArch64Inst::BCond(Imm19Cond { imm19, cond }))
+        }
+        ("cbz" | "cbnz", [Reg(rt), Imm(offset)]) => {
+            let sf = width(&[(*rt, false)])?;
+            let imm19 = signed(scaled(*offset, 4)?, 19)?;
+            let variant = match (m, sf) {
+                ("cbz", false) => AArch64Inst::Cbz32,
+                ("cbnz", false) => AArch64Inst::Cbnz32,
+                ("cbz", true) => AArch64Inst::Cbz64,
+                _ => AArch64Inst::Cbnz64,
+            };
+            Ok(variant(Imm19Rt { imm19, rt: *rt }))
+        }
+        ("tbz" | "tbnz", [Reg(rt), Imm(bit), Imm(offset)]) => {
+            let sf = width(&[(*rt, false)])?;
+            let bit = unsigned(*bit, if sf { 6 } else { 5 })? as u8;
+            let data = B5B40Imm14Rt {
+                b5: bit >> 5,
+                b40: bit & 0b11111,
+                imm14: signed(scaled(*offset, 4)?, 14)? as u16,
+                rt: *rt,
+            };
+            let variant = if m == "tbz" {
+                AArch64Inst::Tbz
+            } else {
+                AArch64Inst::Tbnz
+            };
+            Ok(variant(data))
+        }
+        ("br" | "blr" | "ret", [Reg(rn)]) => branch_reg(m, *rn),
+        ("ret", []) => branch_reg(m, AArch64RegisterId::X(30)),
+        ("adr", [Reg(rd), Imm(offset)]) => pc_rel(AArch64Inst::Adr, *rd, *offset),
+        ("adrp", [Reg(rd), Imm(offset)]) => {
+            pc_rel(AArch64Inst::Adrp, *rd, scaled(*offset, 1 << 12)?)
+        }
+
+        ("svc" | "hvc" | "smc" | "brk" | "hlt", [Imm(imm)]) => exception(m, *imm),
+        ("udf", [Imm(imm)]) => Ok(AArch64Inst::Udf(Imm16 {
+            imm16: unsigned(*imm, 16)? as u16,
+        })),
+        ("nop", []) => Ok(AArch64Inst::Nop),
+        ("hint", [Imm(imm)]) => {
+            let imm = unsigned(*imm, 7)? as u8;
+            Ok(AArch64Inst::Hint(Hints {
+                crm: imm >> 3,
+                op2: imm & 0b111,
+            }))
+        }
+        ("yield", []) => Ok(AArch64Inst::Yield),
+        ("wfe", []) => Ok(AArch64Inst::Wfe),
+        ("wfi", []) => Ok(AArch64Inst::Wfi),
+        ("sev", []) => Ok(AArch64Inst::Sev),
+        ("sevl", []) => Ok(AArch64Inst::Sevl),
+        ("eret", []) => Ok(AArch64Inst::ERet(UncondBranchReg {
+            z: 0,
+            op: 0,
+            a: 0,
+            rn: AArch64RegisterId::Xzr,
+            rm: AArch64RegisterId::X(0),
+        })),
+        ("isb" | "clrex", []) => barrier(m, 0b1111),
+        ("dmb" | "dsb" | "isb" | "clrex", [Imm(crm)]) => barrier(m, unsigned(*crm, 4)? as u8),
+        ("dmb" | "dsb", [Name(option)]) => {
+            let option = option.to_ascii_lowercase();
+            let crm = (0..16)
+                .find(|&crm| BarrierOption(crm).to_string() == option)
+                .ok_or(Unsupported)?;
+            barrier(m, crm)
+        }
+        ("mrs", [Reg(rt), Name(sysreg)]) => {
+            let rt = rt_64(*rt)?;
+            Ok(AArch64Inst::Mrs(sysreg_mov(sysreg, rt)?))
+        }
+        ("msr", [Name(sysreg), Reg(rt)]) => {
+            let rt = rt_64(*rt)?;
+            Ok(AArch64Inst::MsrReg(sysreg_mov(sysreg, rt)?))
+        }
+        _ => Err(Unsupported),
+    }
+}
+
+fn flag_setting(m: &str) -> &'static str {
+    match m {
+        "cmp" => "subs",
+        "cmn" => "adds",
+        _ => "ands",
+    }
+}
+
+fn negated(m: &str) -> &'static str {
+    match m {
+        "neg" => "sub",
+        "negs" => "subs",
+        _ => "orn",
+    }
+}
+
+/// The inverted condition used by the conditional select aliases, `al` and `nv` can't be
+/// inverted.
+fn inverted(cond: u8) -> Result<u8, Reject> {
+    match cond {
+        0b1110 | 0b1111 => Err(Unsupported),
+        cond => Ok(cond ^ 1),
+    }
+}
+
+fn rt_64(rt: AArch64RegisterId) -> Result<AArch64RegisterId, Reject> {
+    width(&[(rt, false)])?.then_some(rt).ok_or(Unsupported)
+}
+
+fn add_sub_imm(
+    m: &str,
+    rd: AArch64RegisterId,
+    rn: AArch64RegisterId,
+    imm: i64,
+    sh: u8,
+) -> Assembled {
+    // The flag setting forms write to the zero register instead of sp
+    let set_flags = m.ends_with('s');
+    let sf = width(&[(rd, !set_flags), (rn, true)])?;
+    let variant = match (m, sf) {
+        ("add", false) => AArch64Inst::AddImm32,
+        ("adds", false) => AArch64Inst::AddsImm32,
+        ("sub", false) => AArch64Inst::SubImm32,
+        ("subs", false) => AArch64Inst::SubsImm32,
+        ("add", true) => AArch64Inst::AddImm64,
+        ("adds", true) => AArch64Inst::AddsImm64,
+        ("sub", true) => AArch64Inst::SubImm64,
+        _ => AArch64Inst::SubsImm64,
+    };
+    let imm12 = unsigned(imm, 12)? as u16;
+    Ok(variant(ShImm12RnRd { sh, imm12, rn, rd }))
+}
+
+/// The instructions with a shifted register operand.
+const SHIFTED_REG_OPS: [&str; 12] = [
+    "add", "adds", "sub", "subs", "and", "bic", "orr", "orn", "eor", "eon", "ands", "bics",
+];
+
+fn shifted_reg(
+    m: &str,
+    rd: AArch64RegisterId,
+    rn: AArch64RegisterId,
+    rm: AArch64RegisterId,
+    shift: u8,
+    amount: u8,
+) -> Assembled {
+    let sf = width(&[(rd, false), (rn, false), (rm, false)])?;
+    let variant = match (m, sf) {
+        ("add", false) => AArch64Inst::AddShiftedReg32,
+        ("adds", false) => AArch64Inst::AddsShiftedReg32,
+        ("sub", false) => AArch64Inst::SubShiftedReg32,
+        ("subs", false) => AArch64Inst::SubsShiftedReg32,
+        ("add", true) => AArch64Inst::AddShiftedReg64,
+        ("adds", true) => AArch64Inst::AddsShiftedReg64,
+        ("sub", true) => AArch64Inst::SubShiftedReg64,
+        ("subs", true) => AArch64Inst::SubsShiftedReg64,
+        ("and", false) => AArch64Inst::AndShiftedReg32,
+        ("bic", false) => AArch64Inst::BicShiftedReg32,
+        ("orr", false) => AArch64Inst::OrrShiftedReg32,
+        ("orn", false) => AArch64Inst::OrnShiftedReg32,
+        ("eor", false) => AArch64Inst::EorShiftedReg32,
+        ("eon", false) => AArch64Inst::EonShiftedReg32,
+        ("ands", false) => AArch64Inst::AndsShiftedReg32,
+        ("bics", false) => AArch64Inst::BicsShiftedReg32,
+        ("and", true) => AArch64Inst::AndShiftedReg64,
+        ("bic", true) => AArch64Inst::BicShiftedReg64,
+        ("orr", true) => AArch64Inst::OrrShiftedReg64,
+        ("orn", true) => AArch64Inst::OrnShiftedReg64,
+        ("eor", true) => AArch64Inst::EorShiftedReg64,
+        ("eon", true) => AArch64Inst::EonShiftedReg64,
+        ("ands", true) => AArch64Inst::AndsShiftedReg64,
+        _ => AArch64Inst::BicsShiftedReg64,
+    };
+    // The arithmetic instructions can't rotate
+    if shift == 0b11 && m.starts_with(['a', 's']) && !m.starts_with("and") {
+        return Err(Unsupported);
+    }
+    let imm6 = unsigned(amount as i64, if sf { 6 } else { 5 })? as u8;
+    Ok(variant(ShiftRmImm6RnRd {
+        shift,
+        rm,
+        imm6,
+        rn,
+        rd,
+    }))
+}
+
+/// The `N:immr:imms` encoding of a logical immediate, `None` if `imm` isn't a repeated rotated
+/// run of ones.
+fn encode_bit_masks(imm: u64, sf: bool) -> Option<(u8, u8, u8)> {
+    let n_values = if sf { 0..2 } else { 0..1 };
+    n_values
+        .flat_map(|n| (0..64).flat_map(move |immr| (0..64).map(move |imms| (n, immr, imms))))
+        .find(|&(n, immr, imms)| {
+            let bits = if sf { 64 } else { 32 };
+            // Only the canonical encoding with immr below the element size is accepted
+            let len = (((n as u32) << 6) | (!imms as u32 & 0x3f)).checked_ilog2();
+            len.is_some_and(|len| immr < 1 << len)
+                && decode_bit_masks(n, imms, immr, true, bits).map(|masks| masks.0) == Some(imm)
+        })
+}
+
+fn logical_imm(m: &str, rd: AArch64RegisterId, rn: AArch64RegisterId, imm: i64) -> Assembled {
+    // The non flag setting forms write to sp instead of the zero register
+    let sf = width(&[(rd, m != "ands"), (rn, false)])?;
+    let imm = match sf {
+        true => imm as u64,
+        false => u32::try_from(imm as u64)
+            .or(i32::try_from(imm).map(|imm| imm as u32))
+            .map_err(|_| OutOfRange)? as u64,
+    };
+    let (n, immr, imms) = encode_bit_masks(imm, sf).ok_or(OutOfRange)?;
+    let variant = match (m, sf) {
+        ("and", false) => AArch64Inst::AndImm32,
+        ("orr", false) => AArch64Inst::OrrImm32,
+        ("eor", false) => AArch64Inst::EorImm32,
+        ("ands", false) => AArch64Inst::AndsImm32,
+        ("and", true) => AArch64Inst::AndImm64,
+        ("orr", true) => AArch64Inst::OrrImm64,
+        ("eor", true) => AArch64Inst::EorImm64,
+        _ => AArch64Inst::AndsImm64,
+    };
+    Ok(variant(LogicalImm {
+        n,
+        immr,
+        imms,
+        rn,
+        rd,
+    }))
+}
+
+fn move_wide(m: &str, rd: AArch64RegisterId, imm: i64, shift: u8) -> Assembled {
+    let sf = width(&[(rd, false)])?;
+    if !shift.is_multiple_of(16) || shift >= if sf { 64 } else { 32 } {
+        return Err(OutOfRange);
+    }
+    let variant = match (m, sf) {
+        ("movn", false) => AArch64Inst::MovnVar32,
+        ("movz", false) => AArch64Inst::MovzVar32,
+        ("movk", false) => AArch64Inst::MovkVar32,
+        ("movn", true) => AArch64Inst::MovnVar64,
+        ("movz", true) => AArch64Inst::MovzVar64,
+        _ => AArch64Inst::MovkVar64,
+    };
+    Ok(variant(HwImm16Rd {
+        hw: shift / 16,
+        imm16: unsigned(imm, 16)? as u16,
+        rd,
+    }))
+}
+
+/// `mov` of an immediate, with MOVZ, MOVN or ORR in that order of preference.
+fn mov_imm(rd: AArch64RegisterId, imm: i64) -> Assembled {
+    let sf = width(&[(rd, true)])?;
+    let (imm, bits) = match sf {
+        true => (imm as u64, 64),
+        false => {
+            let imm = u32::try_from(imm as u64)
+                .or(i32::try_from(imm).map(|imm| imm as u32))
+                .map_err(|_| OutOfRange)?;
+            (imm as u64, 32)
+        }
+    };
+    let mask = u64::MAX >> (64 - bits);
+    // A single 16-bit chunk with the others all zeros, or all ones for MOVN
+    let chunk = |value: u64| {
+        (0..bits / 16)
+            .map(|hw| (hw, value >> (hw * 16) & 0xffff))
+            .find(|&(hw, chunk)| chunk << (hw * 16) == value)
+    };
+    if !is_sp(rd) {
+        if let Some((hw, chunk)) = chunk(imm) {
+            return move_wide("movz", rd, chunk as i64, hw as u8 * 16);
+        }
+        if let Some((hw, chunk)) = chunk(!imm & mask) {
+            return move_wide("movn", rd, chunk as i64, hw as u8 * 16);
+        }
+    }
+    logical_imm("orr", rd, zr(sf), imm as i64)
+}
+
+fn shift_imm(m: &str, rd: AArch64RegisterId, rn: AArch64RegisterId, shift: i64) -> Assembled {
+    let sf = width(&[(rd, false), (rn, false)])?;
+    let bits = if sf { 64 } else { 32 };
+    let shift = unsigned(shift, if sf { 6 } else { 5 })? as u8;
+    match m {
+        "lsl" => bitfield("ubfm", rd, rn, (bits - shift) % bits, bits - 1 - shift),
+        "lsr" => bitfield("ubfm", rd, rn, shift, bits - 1),
+        "asr" => bitfield("sbfm", rd, rn, shift, bits - 1),
+        _ => extract(rd, rn, rn, shift as i64),
+    }
+}
+
+fn bitfield(
+    m: &str,
+    rd: AArch64RegisterId,
+    rn: AArch64RegisterId,
+    immr: u8,
+    imms: u8,
+) -> Assembled {
+    let sf = width(&[(rd, false), (rn, false)])?;
+    let variant = match (m, sf) {
+        ("sbfm", false) => AArch64Inst::Sbfm32,
+        ("bfm", false) => AArch64Inst::Bfm32,
+        ("ubfm", false) => AArch64Inst::Ubfm32,
+        ("sbfm", true) => AArch64Inst::Sbfm64,
+        ("bfm", true) => AArch64Inst::Bfm64,
+        _ => AArch64Inst::Ubfm64,
+    };
+    Ok(variant(Bitfield {
+        n: sf as u8,
+        immr,
+        imms,
+        rn,
+        rd,
+    }))
+}
+
+/// The bitfield aliases taking a least significant bit and a width.
+const BITFIELD_ALIASES: [&str; 6] = ["sbfiz", "sbfx", "bfi", "bfxil", "ubfiz", "ubfx"];
+
+fn bitfield_alias(
+    m: &str,
+    rd: AArch64RegisterId,
+    rn: AArch64RegisterId,
+    lsb: i64,
+    width: i64,
+) -> Assembled {
+    let bits = if is_64(rd) { 64 } else { 32 };
+    if !(0..bits).contains(&lsb) || !(1..=bits - lsb).contains(&width) {
+        return Err(OutOfRange);
+    }
+    let (lsb, width) = (lsb as u8, width as u8);
+    let bits = bits as u8;
+    // The insert forms rotate the field into place, the extract forms take it from lsb
+    let (immr, imms) = match m {
+        "sbfiz" | "bfi" | "ubfiz" => ((bits - lsb) % bits, width - 1),
+        _ => (lsb, lsb + width - 1),
+    };
+    let m = match m {
+        "sbfiz" | "sbfx" => "sbfm",
+        "bfi" | "bfxil" => "bfm",
+        _ => "ubfm",
+    };
+    bitfield(m, rd, rn, immr, imms)
+}
+
+fn extend(m: &str, rd: AArch64RegisterId, rn: AArch64RegisterId) -> Assembled {
+    let imms = match &m[3..] {
+        "b" => 7,
+        "h" => 15,
+        _ => 31,
+    };
+    let rd_64 = is_64(rd);
+    // The unsigned extensions and the 32-bit forms read a w register, sxtw writes an x register
+    let valid = !is_64(rn) && (m.starts_with('s') || !rd_64) && (m != "sxtw" || rd_64);
+    if !valid {
+        return Err(Unsupported);
+    }
+    let rn = if rd_64 { rn.parent() } else { rn };
+    let m = if m.starts_with('s') { "sbfm" } else { "ubfm" };
+    bitfield(m, rd, rn, 0, imms)
+}
+
+fn extract(
+    rd: AArch64RegisterId,
+    rn: AArch64RegisterId,
+    rm: AArch64RegisterId,
+    lsb: i64,
+) -> Assembled {
+    let sf = width(&[(rd, false), (rn, false), (rm, false)])?;
+    let imms = unsigned(lsb, if sf { 6 } else { 5 })? as u8;
+    let variant = if sf {
+        AArch64Inst::Extr64
+    } else {
+        AArch64Inst::Extr32
+    };
+    Ok(variant(ExtractImm { rm, imms, rn, rd }))
+}
+
+fn data_proc_2src(
+    m: &str,
+    rd: AArch64RegisterId,
+    rn: AArch64RegisterId,
+    rm: AArch64RegisterId,
+) -> Assembled {
+    let sf = width(&[(rd, false), (rn, false), (rm, false)])?;
+    let variant = match (m, sf) {
+        ("udiv", false) => AArch64Inst::UdivVar32,
+        ("sdiv", false) => AArch64Inst::SdivVar32,
+        ("lslv", false) => AArch64Inst::LslvVar32,
+        ("lsrv", false) => AArch64Inst::LsrvVar32,
+        ("asrv", false) => AArch64Inst::AsrvVar32,
+        ("rorv", false) => AArch64Inst::RorvVar32,
+        ("udiv", true) => AArch64Inst::UdivVar64,
+        ("sdiv", true) => AArch64Inst::SdivVar64,
+        ("lslv", true) => AArch64Inst::LslvVar64,
+        ("lsrv", true) => AArch64Inst::LsrvVar64,
+        ("asrv", true) => AArch64Inst::AsrvVar64,
+        _ => AArch64Inst::RorvVar64,
+    };
+    Ok(variant(DataProc2Src { rm, rn, rd }))
+}
+
+fn data_proc_3src(
+    m: &str,
+    rd: AArch64RegisterId,
+    rn: AArch64RegisterId,
+    rm: AArch64RegisterId,
+    ra: AArch64RegisterId,
+) -> Assembled {
+    let sf = width(&[(rd, false), (rn, false), (rm, false), (ra, false)])?;
+    let variant = match (m, sf) {
+        ("madd", false) => AArch64Inst::Madd32,
+        ("msub", false) => AArch64Inst::Msub32,
+        ("madd", true) => AArch64Inst::Madd64,
+        ("msub", true) => AArch64Inst::Msub64,
+        ("smulh", true) => AArch64Inst::Smulh,
+        ("umulh", true) => AArch64Inst::Umulh,
+        _ => return Err(Unsupported),
+    };
+    Ok(variant(DataProc3Src { rm, ra, rn, rd }))
+}
+
+fn multiply_long(
+    m: &str,
+    rd: AArch64RegisterId,
+    rn: AArch64RegisterId,
+    rm: AArch64RegisterId,
+    ra: AArch64RegisterId,
+) -> Assembled {
+    // The 32-bit sources are multiplied into a 64-bit product
+    let valid = width(&[(rd, false), (ra, false)])? && !width(&[(rn, false), (rm, false)])?;
+    if !valid {
+        return Err(Unsupported);
+    }
+    let variant = match m {
+        "smaddl" => AArch64Inst::Smaddl,
+        "smsubl" => AArch64Inst::Smsubl,
+        "umaddl" => AArch64Inst::Umaddl,
+        _ => AArch64Inst::Umsubl,
+    };
+    Ok(variant(DataProc3Src { rm, ra, rn, rd }))
+}
+
+fn data_proc_1src(m: &str, rd: AArch64RegisterId, rn: AArch64RegisterId) -> Assembled {
+    let sf = width(&[(rd, false), (rn, false)])?;
+    let variant = match (m, sf) {
+        ("rbit", false) => AArch64Inst::RbitVar32,
+        ("rev16", false) => AArch64Inst::Rev16Var32,
+        ("rev", false) => AArch64Inst::RevVar32,
+        ("clz", false) => AArch64Inst::ClzVar32,
+        ("cls", false) => AArch64Inst::ClsVar32,
+        ("rbit", true) => AArch64Inst::RbitVar64,
+        ("rev16", true) => AArch64Inst::Rev16Var64,
+        ("rev32", true) => AArch64Inst::Rev32,
+        ("rev" | "rev64", true) => AArch64Inst::RevVar64,
+        ("clz", true) => AArch64Inst::ClzVar64,
+        ("cls", true) => AArch64Inst::ClsVar64,
+        _ => return Err(Unsupported),
+    };
+    Ok(variant(RnRd { rn, rd }))
+}
+
+fn cond_select(
+    m: &str,
+    rd: AArch64RegisterId,
+    rn: AArch64RegisterId,
+    rm: AArch64RegisterId,
+    cond: u8,
+) -> Assembled {
+    let sf = width(&[(rd, false), (rn, false), (rm, false)])?;
+    let variant = match (m, sf) {
+        ("csel", false) => AArch64Inst::Csel32,
+        ("csinc", false) => AArch64Inst::Csinc32,
+        ("csinv", false) => AArch64Inst::Csinv32,
+        ("csneg", false) => AArch64Inst::Csneg32,
+        ("csel", true) => AArch64Inst::Csel64,
+        ("csinc", true) => AArch64Inst::Csinc64,
+        ("csinv", true) => AArch64Inst::Csinv64,
+        _ => AArch64Inst::Csneg64,
+    };
+    Ok(variant(RmCondRnRd { rm, cond, rn, rd }))
+}
+
+// Loads and stores
+
+/// The loads and stores of a general-purpose register with an immediate or register offset.
+const LOADS_AND_STORES: [&str; 9] = [
+    "strb", "ldrb", "ldrsb", "strh", "ldrh", "ldrsh", "str", "ldr", "ldrsw",
+];
+
+/// The loads and stores with an unscaled offset.
+const UNSCALED_LOADS_AND_STORES: [&str; 9] = [
+    "sturb", "ldurb", "ldursb", "sturh", "ldurh", "ldursh", "stur", "ldur", "ldursw",
+];
+
+#[derive(Clone, Copy)]
+enum Indexing {
+    Offset,
+    Pre,
+    Post,
+}
+
+/// The `size` and `opc` fields of a load or store of `rt`, by the mnemonic without its
+/// addressing mode, e.g. `ldrsb` for `ldursb`.
+fn size_opc(m: &str, rt: AArch64RegisterId) -> Result<(u8, u8), Reject> {
+    let sf = width(&[(rt, false)])?;
+    let (size, opc) = match (m, sf) {
+        ("strb", false) => (0b00, 0b00),
+        ("ldrb", false) => (0b00, 0b01),
+        ("ldrsb", true) => (0b00, 0b10),
+        ("ldrsb", false) => (0b00, 0b11),
+        ("strh", false) => (0b01, 0b00),
+        ("ldrh", false) => (0b01, 0b01),
+        ("ldrsh", true) => (0b01, 0b10),
+        ("ldrsh", false) => (0b01, 0b11),
+        ("str", false) => (0b10, 0b00),
+        ("ldr", false) => (0b10, 0b01),
+        ("ldrsw", true) => (0b10, 0b10),
+        ("str", true) => (0b11, 0b00),
+        ("ldr", true) => (0b11, 0b01),
+        _ => return Err(Unsupported),
+    };
+    Ok((size, opc))
+}
+
+fn load_store_imm(
+    m: &str,
+    rt: AArch64RegisterId,
+    rn: AArch64RegisterId,
+    offset: i64,
+    indexing: Indexing,
+) -> Assembled {
+    let (size, opc) = size_opc(m, rt)?;
+    let (idxt, imm12) = match indexing {
+        Indexing::Offset => (0b01, unsigned(scaled(offset, 1 << size)?, 12)?),
+        Indexing::Pre => (0b00, signed(offset, 9)? << 2 | 0b11),
+        Indexing::Post => (0b00, signed(offset, 9)? << 2 | 0b01),
+    };
+    let variant = match (size, opc) {
+        (0b00, 0b00) => AArch64Inst::StrbImm,
+        (0b00, 0b01) => AArch64Inst::LdrbImm,
+        (0b00, 0b10) => AArch64Inst::LdrsbImm64,
+        (0b00, 0b11) => AArch64Inst::LdrsbImm32,
+        (0b01, 0b00) => AArch64Inst::StrhImm,
+        (0b01, 0b01) => AArch64Inst::LdrhImm,
+        (0b01, 0b10) => AArch64Inst::LdrshImm64,
+        (0b01, 0b11) => AArch64Inst::LdrshImm32,
+        (0b10, 0b00) => AArch64Inst::StrImm32,
+        (0b10, 0b01) => AArch64Inst::LdrImm32,
+        (0b10, 0b10) => AArch64Inst::LdrswImm,
+        (0b11, 0b00) => AArch64Inst::StrImm64,
+        _ => AArch64Inst::LdrImm64,
+    };
+    Ok(variant(OpcSizeImm12RnRt {
+        idxt,
+        opc,
+        size,
+        imm12: imm12 as u16,
+        rn,
+        rt,
+    }))
+}
+
+/// The unscaled form of a load or store, by the mnemonic of the scaled form, e.g. `ldrsb` for
+/// `ldursb`.
+fn load_store_unscaled(
+    m: &str,
+    rt: AArch64RegisterId,
+    rn: AArch64RegisterId,
+    offset: i64,
+) -> Assembled {
+    let (size, opc) = size_opc(m, rt)?;
+    let variant = match (size, opc) {
+        (0b00, 0b00) => AArch64Inst::Sturb,
+        (0b00, 0b01) => AArch64Inst::Ldurb,
+        (0b00, 0b10) => AArch64Inst::Ldursb64,
+        (0b00, 0b11) => AArch64Inst::Ldursb32,
+        (0b01, 0b00) => AArch64Inst::Sturh,
+        (0b01, 0b01) => AArch64Inst::Ldurh,
+        (0b01, 0b10) => AArch64Inst::Ldursh64,
+        (0b01, 0b11) => AArch64Inst::Ldursh32,
+        (0b10, 0b00) => AArch64Inst::Stur32,
+        (0b10, 0b01) => AArch64Inst::Ldur32,
+        (0b10, 0b10) => AArch64Inst::Ldursw,
+        (0b11, 0b00) => AArch64Inst::Stur64,
+        _ => AArch64Inst::Ldur64,
+    };
+    Ok(variant(LdStRegUnscaledImm {
+        imm9: signed(offset, 9)? as u16,
+        rn,
+        rt,
+    }))
+}
+
+fn load_store_reg(
+    m: &str,
+    rt: AArch64RegisterId,
+    rn: AArch64RegisterId,
+    rm: AArch64RegisterId,
+    option: u8,
+    amount: Option<u8>,
+) -> Assembled {
+    let (size, opc) = size_opc(m, rt)?;
+    // option<1> must be set, option<0> selects a 64-bit offset register
+    if option & 0b010 == 0 || is_64(rm) != (option & 1 == 1) || is_sp(rm) {
+        return Err(Unsupported);
+    }
+    let s = match amount {
+        None => 0,
+        Some(amount) if amount == size => 1,
+        Some(_) => return Err(OutOfRange),
+    };
+    let shifted = option == 0b011;
+    let variant = match (size, opc) {
+        (0b00, 0b00) if shifted => AArch64Inst::StrbRegShiftedReg,
+        (0b00, 0b00) => AArch64Inst::StrbRegExtReg,
+        (0b00, 0b01) if shifted => AArch64Inst::LdrbRegShiftedReg,
+        (0b00, 0b01) => AArch64Inst::LdrbRegExtReg,
+        (0b00, 0b10) if shifted => AArch64Inst::LdrsbRegShiftedReg64,
+        (0b00, 0b10) => AArch64Inst::LdrsbRegExtReg64,
+        (0b00, 0b11) if shifted => AArch64Inst::LdrsbRegShiftedReg32,
+        (0b00, 0b11) => AArch64Inst::LdrsbRegExtReg32,
+        (0b01, 0b00) => AArch64Inst::StrhReg,
+        (0b01, 0b01) => AArch64Inst::LdrhReg,
+        (0b01, 0b10) => AArch64Inst::LdrshReg64,
+        (0b01, 0b11) => AArch64Inst::LdrshReg32,
+        (0b10, 0b00) => AArch64Inst::StrReg32,
+        (0b10, 0b01) => AArch64Inst::LdrReg32,
+        (0b10, 0b10) => AArch64Inst::LdrswReg,
+        (0b11, 0b00) => AArch64Inst::StrReg64,
+        _ => AArch64Inst::LdrReg64,
+    };
+    Ok(variant(LoadStoreRegRegOffset {
+        size,
+        v: 0,
+        opc,
+        rm,
+        option,
+        s,
+        rn,
+        rt,
+    }))
+}
+
+fn load_literal(m: &str, rt: AArch64RegisterId, offset: i64) -> Assembled {
+    let sf = width(&[(rt, false)])?;
+    let variant = match (m, sf) {
+        ("ldr", false) => AArch64Inst::LdrLitVar32,
+        ("ldr", true) => AArch64Inst::LdrLitVar64,
+        ("ldrsw", true) => AArch64Inst::LdrswLit,
+        _ => return Err(Unsupported),
+    };
+    let imm19 = signed(scaled(offset, 4)?, 19)?;
+    Ok(variant(Imm19Rt { imm19, rt }))
+}
+
+fn load_store_pair(
+    m: &str,
+    rt: AArch64RegisterId,
+    rt2: AArch64RegisterId,
+    rn: AArch64RegisterId,
+    offset: i64,
+    o: u8,
+) -> Assembled {
+    let sf = width(&[(rt, false), (rt2, false)])?;
+    let (opc, variant): (u8, fn(_) -> _) = match (m, sf) {
+        ("stp", false) => (0b00, AArch64Inst::StpVar32),
+        ("ldp", false) => (0b00, AArch64Inst::LdpVar32),
+        ("ldpsw", true) => (0b01, AArch64Inst::Ldpsw),
+        ("stp", true) => (0b10, AArch64Inst::StpVar64),
+        ("ldp", true) => (0b10, AArch64Inst::LdpVar64),
+        _ => return Err(Unsupported),
+    };
+    let scale = if opc == 0b10 { 8 } else { 4 };
+    let imm7 = signed(scaled(offset, scale)?, 7)? as u8;
+    Ok(variant(LoadStoreRegPair {
+        opc,
+        imm7,
+        o,
+        rt2,
+        rn,
+        rt,
+    }))
+}
+
+/// The exclusive loads and the load-acquires and store-releases.
+const ORDERED_LOADS_AND_STORES: [&str; 12] = [
+    "ldxrb", "ldxrh", "ldxr", "ldaxrb", "ldaxrh", "ldaxr", "stlrb", "stlrh", "stlr", "ldarb",
+    "ldarh", "ldar",
+];
+
+const EXCLUSIVE_STORES: [&str; 6] = ["stxrb", "stxrh", "stxr", "stlxrb", "stlxrh", "stlxr"];
+
+fn exclusive(
+    m: &str,
+    rs: AArch64RegisterId,
+    rt: AArch64RegisterId,
+    rn: AArch64RegisterId,
+) -> Assembled {
+    let sf = width(&[(rt, false)])?;
+    // The byte and halfword forms only take w registers
+    if m.ends_with(['b', 'h']) && sf || !is_64(rn) || is_64(rs) && rs != AArch64RegisterId::Xzr {
+        return Err(Unsupported);
+    }
+    let variant = match (m, sf) {
+        ("stxrb", _) => AArch64Inst::Stxrb,
+        ("ldxrb", _) => AArch64Inst::Ldxrb,
+        ("stxrh", _) => AArch64Inst::Stxrh,
+        ("ldxrh", _) => AArch64Inst::Ldxrh,
+        ("stxr", false) => AArch64Inst::StxrVar32,
+        ("ldxr", false) => AArch64Inst::LdxrVar32,
+        ("stxr", true) => AArch64Inst::StxrVar64,
+        ("ldxr", true) => AArch64Inst::LdxrVar64,
+        ("stlxrb", _) => AArch64Inst::Stlxrb,
+        ("ldaxrb", _) => AArch64Inst::Ldaxrb,
+        ("stlxrh", _) => AArch64Inst::Stlxrh,
+        ("ldaxrh", _) => AArch64Inst::Ldaxrh,
+        ("stlxr", false) => AArch64Inst::StlxrVar32,
+        ("ldaxr", false) => AArch64Inst::LdaxrVar32,
+        ("stlxr", true) => AArch64Inst::StlxrVar64,
+        ("ldaxr", true) => AArch64Inst::LdaxrVar64,
+        ("stlrb", _) => AArch64Inst::Stlrb,
+        ("ldarb", _) => AArch64Inst::Ldarb,
+        ("stlrh", _) => AArch64Inst::Stlrh,
+        ("ldarh", _) => AArch64Inst::Ldarh,
+        ("stlr", false) => AArch64Inst::StlrVar32,
+        ("ldar", false) => AArch64Inst::LdarVar32,
+        ("stlr", true) => AArch64Inst::StlrVar64,
+        _ => AArch64Inst::LdarVar64,
+    };
+    // Rt2 is unused and must be all ones, like Rs for everything but the exclusive stores
+    Ok(variant(RsRt2RnRt {
+        rs,
+        rt2: 0b11111,
+        rn,
+        rt,
+    }))
+}
+
+// Branches, exceptions and system instructions
+
+fn branch_reg(m: &str, rn: AArch64RegisterId) -> Assembled {
+    width(&[(rn, false)])?.then_some(()).ok_or(Unsupported)?;
+    let (op, variant): (u8, fn(_) -> _) = match m {
+        "br" => (0b00, AArch64Inst::Br),
+        "blr" => (0b01, AArch64Inst::Blr),
+        _ => (0b10, AArch64Inst::Ret),
+    };
+    Ok(variant(UncondBranchReg {
+        z: 0,
+        op,
+        a: 0,
+        rn,
+        rm: AArch64RegisterId::X(0),
+    }))
+}
+
+fn pc_rel(
+    variant: fn(PcRelAddressing) -> AArch64Inst,
+    rd: AArch64RegisterId,
+    offset: i64,
+) -> Assembled {
+    width(&[(rd, false)])?.then_some(()).ok_or(Unsupported)?;
+    let imm21 = signed(offset, 21)?;
+    Ok(variant(PcRelAddressing {
+        immlo: (imm21 & 0b11) as u8,
+        immhi: imm21 >> 2,
+        rd,
+    }))
+}
+
+fn exception(m: &str, imm: i64) -> Assembled {
+    let (opc, ll, variant): (u8, u8, fn(_) -> _) = match m {
+        "svc" => (0b000, 0b01, AArch64Inst::Svc),
+        "hvc" => (0b000, 0b10, AArch64Inst::Hvc),
+        "smc" => (0b000, 0b11, AArch64Inst::Smc),
+        "brk" => (0b001, 0b00, AArch64Inst::Brk),
+        _ => (0b010, 0b00, AArch64Inst::Hlt),
+    };
+    Ok(variant(ExceptionGen {
+        opc,
+        imm16: unsigned(imm, 16)? as u16,
+        op2: 0,
+        ll,
+    }))
+}
+
+fn barrier(m: &str, crm: u8) -> Assembled {
+    let variant = match m {
+        "clrex" => AArch64Inst::Clrex,
+        "dsb" => AArch64Inst::DsbEncoding,
+        "dmb" => AArch64Inst::Dmb,
+        _ => AArch64Inst::Isb,
+    };
+    Ok(variant(Barriers { crm }))
+}
+
+/// MRS and MSR of a system register by name or as `S<op0>_<op1>_C<n>_C<m>_<op2>`.
+fn sysreg_mov(name: &str, rt: AArch64RegisterId) -> Result<SysRegMov, Reject> {
+    let encoding = match SYSREGS
+        .iter()
+        .find(|sysreg| sysreg.name.eq_ignore_ascii_case(name))
+    {
+        Some(sysreg) => sysreg.encoding,
+        None => {
+            let fields = name
+                .strip_prefix(['S', 's'])
+                .ok_or(Unsupported)?
+                .split('_')
+                .map(|field| field.trim_start_matches(['C', 'c']).parse::<u8>().ok())
+                .collect::<Option<Vec<_>>>()
+                .ok_or(Unsupported)?;
+            let [op0, op1, crn, crm, op2] = fields[..] else {
+                return Err(Unsupported);
+            };
+            if !(2..4).contains(&op0) || op1 > 7 || crn > 15 || crm > 15 || op2 > 7 {
+                return Err(OutOfRange);
+            }
+            super::sysreg::SysRegEncoding::new(op0, op1, crn, crm, op2)
+        }
+    };
+    Ok(SysRegMov {
+        o0: encoding.op0 & 1,
+        op1: encoding.op1,
+        crn: encoding.crn,
+        crm: encoding.crm,
+        op2: encoding.op2,
+        rt,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::decode_aarch64_inst;
+    use super::*;
+    use utility::SplitMix64;
+
+    #[test]
+    fn assemble_instructions() {
+        let cases = [
+            ("add x0, x1, #3", 0x9100_0c20),
+            ("add sp, sp, #16", 0x9100_43ff),
+            ("sub w2, w3, #1, lsl #12", 0x5140_0462),
+            ("cmp x1, #4", 0xf100_103f),
+            ("mov x29, sp", 0x9100_03fd),
+            ("mov w0, w1", 0x2a01_03e0),
+            ("mov x0, #0x10000", 0xd2a0_0020),
+            ("mov w1, #-2", 0x1280_0021),
+            ("mov x2, #0xff00ff00ff00ff00", 0xb208_9fe2),
+            ("movk x3, #0xbeef, lsl #16", 0xf2b7_dde3),
+            ("and x0, x1, #0xff", 0x9240_1c20),
+            ("orr w5, w6, w7, lsl #3", 0x2a07_0cc5),
+            ("tst w0, #0x8", 0x721d_001f),
+            ("neg x1, x2", 0xcb02_03e1),
+            ("mvn w3, w4", 0x2a24_03e3),
+            ("lsl x0, x1, #3", 0xd37d_f020),
+            ("lsr w2, w3, #31", 0x531f_7c62),
+            ("asr x4, x5, x6", 0x9ac6_28a4),
+            ("ror w7, w8, #5", 0x1388_1507),
+            ("ubfx x0, x1, #4, #8", 0xd344_2c20),
+            ("bfi w2, w3, #8, #4", 0x3318_0c62),
+            ("sxtw x0, w1", 0x9340_7c20),
+            ("uxtb w2, w3", 0x5300_1c62),
+            ("mul x0, x1, x2", 0x9b02_7c20),
+            ("madd w3, w4, w5, w6", 0x1b05_1883),
+            ("udiv x7, x8, x9", 0x9ac9_0907),
+            ("smull x0, w1, w2", 0x9b22_7c20),
+            ("umaddl x3, w4, w5, x6", 0x9ba5_1883),
+            ("smnegl x7, w8, w9", 0x9b29_fd07),
+            ("rev x0, x1", 0xdac0_0c20),
+            ("rev16 w2, w3", 0x5ac0_0462),
+            ("rev32 x4, x5", 0xdac0_08a4),
+            ("clz w6, w7", 0x5ac0_10e6),
+            ("cls x8, x9", 0xdac0_1528),
+            ("rbit w10, w11", 0x5ac0_016a),
+            ("csel x0, x1, x2, ne", 0x9a82_1020),
+            ("cset w3, eq", 0x1a9f_17e3),
+            ("cinc x4, x5, lt", 0x9a85_a4a4),
+            ("ccmp x0, x1, #4, ne", 0xfa41_1004),
+            ("ccmn w2, #5, #8, ge", 0x3a45_a848),
+            ("ldr x0, [sp, #8]", 0xf940_07e0),
+            ("str w1, [x2, #-4]!", 0xb81f_cc41),
+            ("ldrb w3, [x4], #1", 0x3840_1483),
+            ("ldrsw x5, [x6, x7, lsl #2]", 0xb8a7_78c5),
+            ("strh w8, [x9, w10, sxtw]", 0x782a_c928),
+            ("ldur x11, [x12, #-3]", 0xf85f_d18b),
+            ("ldrsb x0, [x1, #-3]", 0x389f_d020),
+            ("ldr x0, [x1, #3]", 0xf840_3020),
+            ("strh w2, [x3, #-1]", 0x781f_f062),
+            ("ldr x0, #16", 0x5800_0080),
+            ("ldp x29, x30, [sp], #16", 0xa8c1_7bfd),
+            ("stp x29, x30, [sp, #-16]!", 0xa9bf_7bfd),
+            ("ldxr w0, [x1]", 0x885f_7c20),
+            ("stlxr w2, x3, [x4]", 0xc802_fc83),
+            ("ldar x5, [x6]", 0xc8df_fcc5),
+            ("b #8", 0x1400_0002),
+            ("bl #-4", 0x97ff_ffff),
+            ("b.ne #-8", 0x54ff_ffc1),
+            ("cbz x0, #12", 0xb400_0060),
+            ("tbnz w1, #3, #16", 0x3718_0081),
+            ("ret", 0xd65f_03c0),
+            ("br x16", 0xd61f_0200),
+            ("adr x0, #4", 0x1000_0020),
+            ("adrp x1, #4096", 0xb000_0001),
+            ("svc #0", 0xd400_0001),
+            ("brk #0x3e8", 0xd420_7d00),
+            ("eret", 0xd69f_03e0),
+            ("nop", 0xd503_201f),
+            ("hint #127", 0xd503_2fff),
+            ("hint #0", 0xd503_201f),
+            ("dmb ish", 0xd503_3bbf),
+            ("dsb sy", 0xd503_3f9f),
+            ("isb", 0xd503_3fdf),
+            ("mrs x0, tpidr_el0", 0xd53b_d040),
+            ("msr nzcv, x1", 0xd51b_4201),
+            ("mrs x2, s3_0_c15_c2_0", 0xd538_f202),
+        ];
+        for (text, word) in cases {
+            let inst: AArch64Inst = text.parse().unwrap();
+            assert_eq!(inst.encode(), word, "{text}");
+        }
+    }
+
+    #[test]
+    fn assemble_source() {
+        let source = "
+            // Add the numbers from 1 to x0
+            mov x1, #0
+            add x1, x1, x0; subs x0, x0, #1
+            b.ne #-8 // Loop
+            ret
+        ";
+        let words = assemble(source).unwrap();
+        assert_eq!(
+            words,
+            [
+                0xd280_0001,
+                0x8b00_0021,
+                0xf100_0400,
+                0x54ff_ffc1,
+                0xd65f_03c0
+            ]
+        );
+    }
+
+    #[test]
+    fn assemble_errors() {
+        let error = |text: &str| text.parse::<AArch64Inst>().unwrap_err();
+        assert_eq!(
+            error("fadd d0, d1, d2"),
+            AssembleError::Unsupported("fadd d0, d1, d2".into())
+        );
+        assert_eq!(
+            error("add x0, w1, #1"),
+            AssembleError::Unsupported("add x0, w1, #1".into())
+        );
+        assert_eq!(
+            error("add x0, x1, #4096"),
+            AssembleError::OutOfRange("add x0, x1, #4096".into())
+        );
+        assert_eq!(
+            error("and x0, x1, #0"),
+            AssembleError::OutOfRange("and x0, x1, #0".into())
+        );
+        assert_eq!(
+            error("ldr x0, [x1, #-257]"),
+            AssembleError::OutOfRange("ldr x0, [x1, #-257]".into())
+        );
+        assert_eq!(
+            error("ldr x0, [w1]"),
+            AssembleError::InvalidOperand("ldr x0, [w1]".into())
+        );
+        assert_eq!(
+            assemble("nop\nb #2"),
+            Err(AssembleError::OutOfRange("b #2".into()))
+        );
+        // The address of the instruction is needed to resolve an absolute target
+        assert_eq!(
+            error("b 0x40010"),
+            AssembleError::Unsupported("b 0x40010".into())
+        );
+    }
+
+    #[test]
+    fn assemble_absolute_targets() {
+        let source = "
+            b.ne 0x40010
+            bl 0x3fffc
+            cbnz x0, 0x40004
+            tbz w0, #3, 0x40014
+            adr x0, 0x4001c
+            adrp x0, 0x41000
+            ldr x1, 0x40000
+        ";
+        let words = assemble_at(source, 0x40000).unwrap();
+        assert_eq!(
+            words,
+            [
+                0x5400_0081,
+                0x97ff_fffe,
+                0xb5ff_ffe0,
+                0x3618_0040,
+                0x1000_0060,
+                0xb000_0000,
+                0x58ff_ff41
+            ]
+        );
+    }
+
+    /// The text of the decoded random words is assembled again. The decoder accepts some
+    /// encodings with unused fields set, those are assembled to the canonical word, which must
+    /// round trip exactly.
+    #[test]
+    fn disassemble_round_trip() {
+        let mut rng = SplitMix64::new(0x2545_f491_4f6c_dd1d);
+        let (mut assembled, mut exact) = (0, 0);
+        for _ in 0..200_000 {
+            let word = rng.next_u64() as u32;
+            // The SIMD&FP instructions, including their loads and stores, aren't supported
+            if word & 0x0e00_0000 == 0x0e00_0000 || word & 0x0e00_0000 == 0x0c00_0000 {
+                continue;
+            }
+            let Ok(inst) = std::panic::catch_unwind(|| decode_aarch64_inst(&word.to_le_bytes()))
+            else {
+                continue;
+            };
+            let Ok(parsed) = inst.to_string().parse::<AArch64Inst>() else {
+                continue;
+            };
+            assembled += 1;
+            exact += (parsed.encode() == word) as usize;
+
+            let canonical = parsed.encode();
+            let text = parsed.to_string();
+            let reparsed: AArch64Inst = text.parse().unwrap();
+            assert_eq!(reparsed.encode(), canonical, "{text}");
+
+            // The same with the targets resolved by the disassembler
+            let pc = rng.next_u64() & !0b11;
+            let text = parsed.display_at(pc).to_string();
+            let reparsed = AArch64Inst::parse_at(&text, pc).unwrap();
+            assert_eq!(reparsed.encode(), canonical, "{text} at {pc:#x}");
+        }
+        assert!(assembled > 40_000, "{assembled}");
+        assert!(exact > assembled * 9 / 10, "{exact} of {assembled}");
+    }
+}
@@ -1,307 +1,11372 @@
 use core::{
-    ir::{BasicBlock, BasicBlockTerminator, IrConstant, IrInst, IrType, IrValue, VecTy},
-    RegisterId,
+    ir::{
+        AtomicOp, BasicBlock, BasicBlockTerminator, FloatOp, FloatRounding, IrInst, IrOrdering,
+        IrType, IrValue, TypeOf,
+    },
+    Architecture, ExceptionKind, Interrupt,
 };
 
+use super::compiler_prelude::{
+    self, bits, gen_add, gen_add_with_carry, gen_and, gen_assign, gen_div, gen_imm, gen_mul,
+    gen_not, gen_or, gen_select, gen_sext, gen_shift, gen_shl, gen_shr, gen_sub, gen_value,
+    gen_xor, gen_zext, imm, reg, sign_extend, signed, uint,
+};
+use super::exception;
+use super::sysreg::{self, SysReg, SysRegEncoding};
 use super::{
-    compiler_prelude, AArch64Inst, AddSubtractExtReg, AdvSimdModifiedImm, AdvancedSimdCopy,
-    B5B40Imm14Rt, Bitfield, CondCmpImm, CondCmpReg, DataProc2Src, DataProc3Src, ExceptionGen,
-    HwImm16Rd, Imm19Cond, Imm19Rt, Imm26, LdStRegUnscaledImm, LoadStoreRegPair,
-    LoadStoreRegRegOffset, LogicalImm, OpcSizeImm12RnRt, PcRelAddressing, PstateOp, RmCondRnRd,
-    RnRd, RsRt2RnRt, ShImm12RnRd, ShiftRmImm6RnRd, SysRegMov, UncondBranchReg,
+    AArch64Architecture, AArch64Inst, AArch64MnemonicHint, AArch64RegisterId, AddSubtractExtReg,
+    AdvSimdLdStMultiStructures, AdvSimdLdStMultiStructuresPostIndexed, AdvSimdLdStSingleStructure,
+    AdvSimdLdStSingleStructurePostIndexed, AdvSimdModifiedImm, AdvSimdScalarPairwise,
+    AdvSimdScalarThreeSame, AdvSimdScalarTwoRegMisc, AdvSimdScalarXIndexedElem, AdvSimdShiftByImm,
+    AdvSimdTableLookup, AdvSimdXIndexedElem, AdvancedSimdCopy, AdvancedSimdExtract, B5B40Imm14Rt,
+    Barriers, Bitfield, CompareAndSwapPair, CondCmpImm, CondCmpReg, ConvBetweenFloatAndFixedPoint,
+    DataProc2Src, DataProc3Src, ExceptionGen, ExtractImm, FloatingPointCompare,
+    FloatingPointImmediate, HwImm16Rd, Imm16, Imm19Cond, Imm19Rt, Imm26, Imm9RnRt,
+    LdStNoAllocPairOffset, LdStRegUnscaledImm, LoadStoreRegPair, LoadStoreRegRegOffset, LogicalImm,
+    OpcSizeImm12RnRt, PcRelAddressing, PstateOp, QSizeRmRnRd, QSizeRnRd, RmCondRnRd, RmRaRnRd,
+    RmRnRd, Rn, RnRd, RotateRightIntoFlags, RsRnRt, ShImm12RnRd, ShiftRmImm6RnRd, SysRegMov,
+    SystemInstructions, UncondBranchReg,
 };
 
 pub(crate) fn compile_aarch64_to_ir(inst: &AArch64Inst, basic_block: &mut BasicBlock) {
-    assert!(basic_block.terminator() != BasicBlockTerminator::None);
-
     match inst {
-        AArch64Inst::MovzVar32(operand) | AArch64Inst::MovzVar64(operand) => {
-            compile_movz(basic_block, operand)
-        }
+        AArch64Inst::MovzVar32(operand) => compile_movz(basic_block, operand, IrType::U32),
+        AArch64Inst::MovzVar64(operand) => compile_movz(basic_block, operand, IrType::U64),
         AArch64Inst::MovnVar32(operand) => compile_movn(basic_block, operand, IrType::U32),
         AArch64Inst::MovnVar64(operand) => compile_movn(basic_block, operand, IrType::U64),
         AArch64Inst::MovkVar32(operand) => compile_movk(basic_block, operand, IrType::U32),
         AArch64Inst::MovkVar64(operand) => compile_movk(basic_block, operand, IrType::U64),
-        AArch64Inst::MoviVectorVar64(operand) => compile_movi(basic_block, operand),
         AArch64Inst::Adr(operand) => compile_adr(basic_block, operand),
         AArch64Inst::Adrp(operand) => compile_adrp(basic_block, operand),
 
-        AArch64Inst::RevVar32(operand) => compile_rev_var(basic_block, operand, IrType::U32),
-        AArch64Inst::RevVar64(operand) => compile_rev_var(basic_block, operand, IrType::U64),
+        AArch64Inst::RbitVar32(operand) => compile_rbit(basic_block, operand, IrType::U32),
+        AArch64Inst::RbitVar64(operand) => compile_rbit(basic_block, operand, IrType::U64),
+        AArch64Inst::Rev16Var32(operand) => compile_rev(basic_block, operand, IrType::U32, 16),
+        AArch64Inst::Rev16Var64(operand) => compile_rev(basic_block, operand, IrType::U64, 16),
+        AArch64Inst::Rev32(operand) => compile_rev(basic_block, operand, IrType::U64, 32),
+        AArch64Inst::RevVar32(operand) => compile_rev(basic_block, operand, IrType::U32, 32),
+        AArch64Inst::RevVar64(operand) => compile_rev(basic_block, operand, IrType::U64, 64),
+        AArch64Inst::ClzVar32(operand) => compile_clz(basic_block, operand, IrType::U32),
+        AArch64Inst::ClzVar64(operand) => compile_clz(basic_block, operand, IrType::U64),
+        AArch64Inst::ClsVar32(operand) => compile_cls(basic_block, operand, IrType::U32),
+        AArch64Inst::ClsVar64(operand) => compile_cls(basic_block, operand, IrType::U64),
 
         // Load and Stores
-        AArch64Inst::LdrImm32(operand) => compile_ldr_imm(basic_block, operand, IrType::U32),
-        AArch64Inst::LdrImm64(operand) => compile_ldr_imm(basic_block, operand, IrType::U64),
+        AArch64Inst::StrbImm(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::Store, IrType::U8)
+        }
+        AArch64Inst::LdrbImm(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::Load, IrType::U8)
+        }
+        AArch64Inst::LdrsbImm32(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::LoadSigned32, IrType::U8)
+        }
+        AArch64Inst::LdrsbImm64(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::LoadSigned64, IrType::U8)
+        }
+        AArch64Inst::StrhImm(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::Store, IrType::U16)
+        }
+        AArch64Inst::LdrhImm(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::Load, IrType::U16)
+        }
+        AArch64Inst::LdrshImm32(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::LoadSigned32, IrType::U16)
+        }
+        AArch64Inst::LdrshImm64(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::LoadSigned64, IrType::U16)
+        }
+        AArch64Inst::StrImm32(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::Store, IrType::U32)
+        }
+        AArch64Inst::LdrImm32(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::Load, IrType::U32)
+        }
+        AArch64Inst::LdrswImm(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::LoadSigned64, IrType::U32)
+        }
+        AArch64Inst::StrImm64(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::Store, IrType::U64)
+        }
+        AArch64Inst::LdrImm64(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::Load, IrType::U64)
+        }
+        AArch64Inst::StrImmSimdFP8(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::Store, IrType::U8)
+        }
+        AArch64Inst::LdrImmSimdFP8(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::Load, IrType::U8)
+        }
+        AArch64Inst::StrImmSimdFP16(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::Store, IrType::U16)
+        }
+        AArch64Inst::LdrImmSimdFP16(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::Load, IrType::U16)
+        }
+        AArch64Inst::StrImmSimdFP32(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::Store, IrType::U32)
+        }
+        AArch64Inst::LdrImmSimdFP32(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::Load, IrType::U32)
+        }
+        AArch64Inst::StrImmSimdFP64(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::Store, IrType::U64)
+        }
         AArch64Inst::LdrImmSimdFP64(operand) => {
-            compile_ldr_imm_simd_fp(basic_block, operand, IrType::U64)
+            compile_ld_st_imm(basic_block, operand, MemOp::Load, IrType::U64)
+        }
+        AArch64Inst::StrImmSimdFP128(operand) => {
+            compile_ld_st_imm(basic_block, operand, MemOp::Store, IrType::U128)
         }
         AArch64Inst::LdrImmSimdFP128(operand) => {
-            compile_ldr_imm_simd_fp(basic_block, operand, IrType::Vector(VecTy::U64, 2))
+            compile_ld_st_imm(basic_block, operand, MemOp::Load, IrType::U128)
+        }
+        AArch64Inst::StrbRegExtReg(operand) => {
+            compile_ld_st_reg(basic_block, operand, MemOp::Store, IrType::U8)
+        }
+        AArch64Inst::StrbRegShiftedReg(operand) => {
+            compile_ld_st_reg(basic_block, operand, MemOp::Store, IrType::U8)
+        }
+        AArch64Inst::LdrbRegExtReg(operand) => {
+            compile_ld_st_reg(basic_block, operand, MemOp::Load, IrType::U8)
         }
-        AArch64Inst::LdrLitVar64(operand) => compile_ldr_lit_var64(basic_block, operand),
-        AArch64Inst::LdrhImm(operand) => compile_ldrh_imm(basic_block, operand),
-        AArch64Inst::LdrbImm(operand) => compile_ldrb_imm(basic_block, operand),
-        AArch64Inst::LdrReg32(operand) => compile_ldr_reg(basic_block, operand, IrType::U32),
-        AArch64Inst::LdrReg64(operand) => compile_ldr_reg(basic_block, operand, IrType::U64),
         AArch64Inst::LdrbRegShiftedReg(operand) => {
-            compile_ldrb_reg_shifted_reg(basic_block, operand)
-        }
-        AArch64Inst::LdpVar64(operand) => compile_ldp(basic_block, operand, IrType::U64),
-        AArch64Inst::LdpVar32(operand) => compile_ldp(basic_block, operand, IrType::U32),
-        AArch64Inst::LdrshReg64(operand) => compile_ldrsh_reg(basic_block, operand, IrType::U64),
-        AArch64Inst::LdrshReg32(operand) => compile_ldrsh_reg(basic_block, operand, IrType::U32),
-        AArch64Inst::LdaxrVar32(operand) => compile_ldaxr(basic_block, operand, IrType::U32),
-        AArch64Inst::LdarVar64(operand) => compile_ldar(basic_block, operand, IrType::U64),
-        AArch64Inst::Ldur64(operand) => compile_ldur(basic_block, operand, IrType::U64),
-        AArch64Inst::LdpSimdFpVar128(operand) => {
-            compile_ldp_simd_fp(basic_block, operand, IrType::Vector(VecTy::U64, 2))
-        }
-        AArch64Inst::LdrRegSimdFP(operand) => compile_ldr_reg_simd_fp(basic_block, operand),
-        AArch64Inst::LdxrVar64(operand) => compile_ldxr(basic_block, operand, IrType::U64),
-
-        AArch64Inst::StrImm32(operand) => compile_str_imm(basic_block, operand, IrType::U32),
-        AArch64Inst::StrImm64(operand) => compile_str_imm(basic_block, operand, IrType::U64),
-        AArch64Inst::StpVar64(operand) => compile_stp_var(basic_block, operand, IrType::U64),
-        AArch64Inst::StpVar32(operand) => compile_stp_var(basic_block, operand, IrType::U32),
-        AArch64Inst::StrbImm(operand) => compile_strb_imm(basic_block, operand),
-        AArch64Inst::Sturb(operand) => compile_sturb_imm(basic_block, operand),
-        AArch64Inst::StrReg32(operand) => compile_str_reg(basic_block, operand, IrType::U32),
-        AArch64Inst::StrReg64(operand) => compile_str_reg(basic_block, operand, IrType::U64),
-        AArch64Inst::Stur32(operand) => compile_stur(basic_block, operand, IrType::U32),
-        AArch64Inst::Stur64(operand) => compile_stur(basic_block, operand, IrType::U64),
+            compile_ld_st_reg(basic_block, operand, MemOp::Load, IrType::U8)
+        }
+        AArch64Inst::LdrsbRegExtReg32(operand) => {
+            compile_ld_st_reg(basic_block, operand, MemOp::LoadSigned32, IrType::U8)
+        }
+        AArch64Inst::LdrsbRegShiftedReg32(operand) => {
+            compile_ld_st_reg(basic_block, operand, MemOp::LoadSigned32, IrType::U8)
+        }
+        AArch64Inst::LdrsbRegExtReg64(operand) => {
+            compile_ld_st_reg(basic_block, operand, MemOp::LoadSigned64, IrType::U8)
+        }
+        AArch64Inst::LdrsbRegShiftedReg64(operand) => {
+            compile_ld_st_reg(basic_block, operand, MemOp::LoadSigned64, IrType::U8)
+        }
+        AArch64Inst::StrhReg(operand) => {
+            compile_ld_st_reg(basic_block, operand, MemOp::Store, IrType::U16)
+        }
+        AArch64Inst::LdrhReg(operand) => {
+            compile_ld_st_reg(basic_block, operand, MemOp::Load, IrType::U16)
+        }
+        AArch64Inst::LdrshReg32(operand) => {
+            compile_ld_st_reg(basic_block, operand, MemOp::LoadSigned32, IrType::U16)
+        }
+        AArch64Inst::LdrshReg64(operand) => {
+            compile_ld_st_reg(basic_block, operand, MemOp::LoadSigned64, IrType::U16)
+        }
+        AArch64Inst::StrReg32(operand) => {
+            compile_ld_st_reg(basic_block, operand, MemOp::Store, IrType::U32)
+        }
+        AArch64Inst::LdrReg32(operand) => {
+            compile_ld_st_reg(basic_block, operand, MemOp::Load, IrType::U32)
+        }
+        AArch64Inst::LdrswReg(operand) => {
+            compile_ld_st_reg(basic_block, operand, MemOp::LoadSigned64, IrType::U32)
+        }
+        AArch64Inst::StrReg64(operand) => {
+            compile_ld_st_reg(basic_block, operand, MemOp::Store, IrType::U64)
+        }
+        AArch64Inst::LdrReg64(operand) => {
+            compile_ld_st_reg(basic_block, operand, MemOp::Load, IrType::U64)
+        }
+        AArch64Inst::StrRegSimdFP(operand) => {
+            compile_ld_st_reg_simd_fp(basic_block, operand, MemOp::Store)
+        }
+        AArch64Inst::LdrRegSimdFP(operand) => {
+            compile_ld_st_reg_simd_fp(basic_block, operand, MemOp::Load)
+        }
+        AArch64Inst::Sturb(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Store, IrType::U8)
+        }
+        AArch64Inst::Ldurb(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Load, IrType::U8)
+        }
+        AArch64Inst::Ldursb32(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::LoadSigned32, IrType::U8)
+        }
+        AArch64Inst::Ldursb64(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::LoadSigned64, IrType::U8)
+        }
+        AArch64Inst::Sturh(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Store, IrType::U16)
+        }
+        AArch64Inst::Ldurh(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Load, IrType::U16)
+        }
+        AArch64Inst::Ldursh32(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::LoadSigned32, IrType::U16)
+        }
+        AArch64Inst::Ldursh64(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::LoadSigned64, IrType::U16)
+        }
+        AArch64Inst::Stur32(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Store, IrType::U32)
+        }
+        AArch64Inst::Ldur32(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Load, IrType::U32)
+        }
+        AArch64Inst::Ldursw(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::LoadSigned64, IrType::U32)
+        }
+        AArch64Inst::Stur64(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Store, IrType::U64)
+        }
+        AArch64Inst::Ldur64(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Load, IrType::U64)
+        }
+        AArch64Inst::SturSimdFP8(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Store, IrType::U8)
+        }
+        AArch64Inst::LdurSimdFP8(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Load, IrType::U8)
+        }
+        AArch64Inst::SturSimdFP16(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Store, IrType::U16)
+        }
+        AArch64Inst::LdurSimdFP16(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Load, IrType::U16)
+        }
+        AArch64Inst::SturSimdFP32(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Store, IrType::U32)
+        }
+        AArch64Inst::LdurSimdFP32(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Load, IrType::U32)
+        }
         AArch64Inst::SturSimdFP64(operand) => {
-            compile_stur_simd_fp(basic_block, operand, IrType::U64)
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Store, IrType::U64)
+        }
+        AArch64Inst::LdurSimdFP64(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Load, IrType::U64)
         }
         AArch64Inst::SturSimdFP128(operand) => {
-            compile_stur_simd_fp(basic_block, operand, IrType::Vector(VecTy::U64, 2))
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Store, IrType::U128)
+        }
+        AArch64Inst::LdurSimdFP128(operand) => {
+            compile_ld_st_unscaled(basic_block, operand, MemOp::Load, IrType::U128)
+        }
+        AArch64Inst::Sttrb(operand) => {
+            compile_ld_st_unprivileged(basic_block, operand, MemOp::Store, IrType::U8)
+        }
+        AArch64Inst::Ldtrb(operand) => {
+            compile_ld_st_unprivileged(basic_block, operand, MemOp::Load, IrType::U8)
+        }
+        AArch64Inst::LdtrsbVar32(operand) => {
+            compile_ld_st_unprivileged(basic_block, operand, MemOp::LoadSigned32, IrType::U8)
+        }
+        AArch64Inst::LdtrsbVar64(operand) => {
+            compile_ld_st_unprivileged(basic_block, operand, MemOp::LoadSigned64, IrType::U8)
+        }
+        AArch64Inst::Sttrh(operand) => {
+            compile_ld_st_unprivileged(basic_block, operand, MemOp::Store, IrType::U16)
+        }
+        AArch64Inst::Ldtrh(operand) => {
+            compile_ld_st_unprivileged(basic_block, operand, MemOp::Load, IrType::U16)
+        }
+        AArch64Inst::LdtrshVar32(operand) => {
+            compile_ld_st_unprivileged(basic_block, operand, MemOp::LoadSigned32, IrType::U16)
+        }
+        AArch64Inst::LdtrshVar64(operand) => {
+            compile_ld_st_unprivileged(basic_block, operand, MemOp::LoadSigned64, IrType::U16)
+        }
+        AArch64Inst::SttrVar32(operand) => {
+            compile_ld_st_unprivileged(basic_block, operand, MemOp::Store, IrType::U32)
+        }
+        AArch64Inst::LdtrVar32(operand) => {
+            compile_ld_st_unprivileged(basic_block, operand, MemOp::Load, IrType::U32)
+        }
+        AArch64Inst::Ldtrsw(operand) => {
+            compile_ld_st_unprivileged(basic_block, operand, MemOp::LoadSigned64, IrType::U32)
+        }
+        AArch64Inst::SttrVar64(operand) => {
+            compile_ld_st_unprivileged(basic_block, operand, MemOp::Store, IrType::U64)
+        }
+        AArch64Inst::LdtrVar64(operand) => {
+            compile_ld_st_unprivileged(basic_block, operand, MemOp::Load, IrType::U64)
+        }
+        AArch64Inst::Stlurb(operand) => {
+            compile_ld_st_ordered_unscaled(basic_block, operand, MemOp::Store, IrType::U8)
+        }
+        AArch64Inst::Ldapurb(operand) => {
+            compile_ld_st_ordered_unscaled(basic_block, operand, MemOp::Load, IrType::U8)
+        }
+        AArch64Inst::LdapursbVar32(operand) => {
+            compile_ld_st_ordered_unscaled(basic_block, operand, MemOp::LoadSigned32, IrType::U8)
+        }
+        AArch64Inst::LdapursbVar64(operand) => {
+            compile_ld_st_ordered_unscaled(basic_block, operand, MemOp::LoadSigned64, IrType::U8)
+        }
+        AArch64Inst::Stlurh(operand) => {
+            compile_ld_st_ordered_unscaled(basic_block, operand, MemOp::Store, IrType::U16)
+        }
+        AArch64Inst::Ldapurh(operand) => {
+            compile_ld_st_ordered_unscaled(basic_block, operand, MemOp::Load, IrType::U16)
+        }
+        AArch64Inst::LdapurshVar32(operand) => {
+            compile_ld_st_ordered_unscaled(basic_block, operand, MemOp::LoadSigned32, IrType::U16)
+        }
+        AArch64Inst::LdapurshVar64(operand) => {
+            compile_ld_st_ordered_unscaled(basic_block, operand, MemOp::LoadSigned64, IrType::U16)
+        }
+        AArch64Inst::StlurVar32(operand) => {
+            compile_ld_st_ordered_unscaled(basic_block, operand, MemOp::Store, IrType::U32)
+        }
+        AArch64Inst::LdapurVar32(operand) => {
+            compile_ld_st_ordered_unscaled(basic_block, operand, MemOp::Load, IrType::U32)
+        }
+        AArch64Inst::Ldapursw(operand) => {
+            compile_ld_st_ordered_unscaled(basic_block, operand, MemOp::LoadSigned64, IrType::U32)
+        }
+        AArch64Inst::StlurVar64(operand) => {
+            compile_ld_st_ordered_unscaled(basic_block, operand, MemOp::Store, IrType::U64)
+        }
+        AArch64Inst::LdapurVar64(operand) => {
+            compile_ld_st_ordered_unscaled(basic_block, operand, MemOp::Load, IrType::U64)
+        }
+        AArch64Inst::StpVar32(operand) => {
+            compile_ld_st_pair(basic_block, operand, MemOp::Store, IrType::U32)
+        }
+        AArch64Inst::LdpVar32(operand) => {
+            compile_ld_st_pair(basic_block, operand, MemOp::Load, IrType::U32)
+        }
+        AArch64Inst::Ldpsw(operand) => {
+            compile_ld_st_pair(basic_block, operand, MemOp::LoadSigned64, IrType::U32)
+        }
+        AArch64Inst::StpVar64(operand) => {
+            compile_ld_st_pair(basic_block, operand, MemOp::Store, IrType::U64)
+        }
+        AArch64Inst::LdpVar64(operand) => {
+            compile_ld_st_pair(basic_block, operand, MemOp::Load, IrType::U64)
+        }
+        AArch64Inst::StpSimdFPVar32(operand) => {
+            compile_ld_st_pair(basic_block, operand, MemOp::Store, IrType::U32)
+        }
+        AArch64Inst::LdpSimdFPVar32(operand) => {
+            compile_ld_st_pair(basic_block, operand, MemOp::Load, IrType::U32)
+        }
+        AArch64Inst::StpSimdFPVar64(operand) => {
+            compile_ld_st_pair(basic_block, operand, MemOp::Store, IrType::U64)
+        }
+        AArch64Inst::LdpSimdFPVar64(operand) => {
+            compile_ld_st_pair(basic_block, operand, MemOp::Load, IrType::U64)
         }
         AArch64Inst::StpSimdFpVar128(operand) => {
-            compile_stp_simd_fp(basic_block, operand, IrType::Vector(VecTy::U64, 2))
+            compile_ld_st_pair(basic_block, operand, MemOp::Store, IrType::U128)
         }
-        AArch64Inst::StrImmSimdFP64(operand) => {
-            compile_str_imm_simd_fp(basic_block, operand, IrType::U64)
+        AArch64Inst::LdpSimdFpVar128(operand) => {
+            compile_ld_st_pair(basic_block, operand, MemOp::Load, IrType::U128)
         }
-        AArch64Inst::StrImmSimdFP128(operand) => {
-            compile_str_imm_simd_fp(basic_block, operand, IrType::Vector(VecTy::U64, 2))
+        AArch64Inst::StnpVar32(operand) => {
+            compile_ld_st_no_alloc_pair(basic_block, operand, MemOp::Store, IrType::U32)
         }
-        AArch64Inst::StrRegSimdFP(operand) => compile_str_reg_simd_fp(basic_block, operand),
-        AArch64Inst::StlxrVar32(operand) => compile_stlxr(basic_block, operand, IrType::U32),
-        AArch64Inst::StxrVar64(operand) => compile_stxr(basic_block, operand, IrType::U64),
-        AArch64Inst::StxrVar32(operand) => compile_stxr(basic_block, operand, IrType::U32),
-        AArch64Inst::StrbRegShiftedReg(operand) => compile_strb_reg(basic_block, operand),
-
-        // Advanced SIMD and FP
-        AArch64Inst::DupGeneral(operand) => compile_dup_general(basic_block, operand),
-
-        // Arithmetic instructions
-        AArch64Inst::AddImm64(operand) => compile_add_imm(basic_block, operand, IrType::U64),
-        AArch64Inst::AddImm32(operand) => compile_add_imm(basic_block, operand, IrType::U32),
-        AArch64Inst::AddsImm64(operand) => compile_adds_imm(basic_block, operand, IrType::U64),
-        AArch64Inst::AddsImm32(operand) => compile_adds_imm(basic_block, operand, IrType::U32),
-        AArch64Inst::AddShiftedReg64(operand) => {
-            compile_add_shifted_reg(basic_block, operand, IrType::U64)
+        AArch64Inst::LdnpVar32(operand) => {
+            compile_ld_st_no_alloc_pair(basic_block, operand, MemOp::Load, IrType::U32)
         }
-        AArch64Inst::AddsShiftedReg64(operand) => {
-            compile_adds_shifted_reg(basic_block, operand, IrType::U64)
+        AArch64Inst::StnpVar64(operand) => {
+            compile_ld_st_no_alloc_pair(basic_block, operand, MemOp::Store, IrType::U64)
         }
-        AArch64Inst::AddExtReg64(operand) => compile_add_ext_reg(basic_block, operand, IrType::U64),
-        AArch64Inst::SubImm64(operand) => compile_sub_imm(basic_block, operand, IrType::U64),
-        AArch64Inst::SubImm32(operand) => compile_sub_imm(basic_block, operand, IrType::U32),
-        AArch64Inst::SubShiftedReg64(operand) => {
-            compile_sub_shifted_reg(basic_block, operand, IrType::U64)
+        AArch64Inst::LdnpVar64(operand) => {
+            compile_ld_st_no_alloc_pair(basic_block, operand, MemOp::Load, IrType::U64)
         }
-        AArch64Inst::SubsShiftedReg32(operand) => {
-            compile_subs_shifted_reg(basic_block, operand, IrType::U32)
+        AArch64Inst::StnpSimdFPVar32(operand) => {
+            compile_ld_st_no_alloc_pair(basic_block, operand, MemOp::Store, IrType::U32)
         }
-        AArch64Inst::SubsShiftedReg64(operand) => {
-            compile_subs_shifted_reg(basic_block, operand, IrType::U64)
+        AArch64Inst::LdnpSimdFPVar32(operand) => {
+            compile_ld_st_no_alloc_pair(basic_block, operand, MemOp::Load, IrType::U32)
         }
-        AArch64Inst::SubsExtReg64(operand) => {
-            compile_subs_ext_reg(basic_block, operand, IrType::U64)
-        }
-        AArch64Inst::SubsImm64(operand) => compile_subs_imm(basic_block, operand, IrType::U64),
-        AArch64Inst::SubsImm32(operand) => compile_subs_imm(basic_block, operand, IrType::U32),
-        AArch64Inst::Madd32(operand) => compile_madd(basic_block, operand, IrType::U32),
-        AArch64Inst::Madd64(operand) => compile_madd(basic_block, operand, IrType::U64),
-        AArch64Inst::Msub32(operand) => compile_msub(basic_block, operand, IrType::U32),
-        AArch64Inst::SdivVar32(operand) => compile_div(basic_block, operand, IrType::I32),
-        AArch64Inst::SdivVar64(operand) => compile_div(basic_block, operand, IrType::I64),
-        AArch64Inst::UdivVar32(operand) => compile_div(basic_block, operand, IrType::U32),
-        AArch64Inst::UdivVar64(operand) => compile_div(basic_block, operand, IrType::U64),
-
-        // bitwise isntructions
-        AArch64Inst::Ubfm32(operand) => compile_ubfm(basic_block, operand, IrType::U32),
-        AArch64Inst::Ubfm64(operand) => compile_ubfm(basic_block, operand, IrType::U64),
-        AArch64Inst::Sbfm64(operand) => compile_sbfm(basic_block, operand, IrType::U64),
-        AArch64Inst::AndImm64(operand) => compile_and_imm(basic_block, operand, IrType::U64),
-        AArch64Inst::AndImm32(operand) => compile_and_imm(basic_block, operand, IrType::U32),
-        AArch64Inst::AndsImm64(operand) => compile_ands_imm(basic_block, operand, IrType::U64),
-        AArch64Inst::AndsImm32(operand) => compile_ands_imm(basic_block, operand, IrType::U32),
-        AArch64Inst::AndsShiftedReg32(operand) => {
-            compile_ands_shifted_reg(basic_block, operand, IrType::U32)
+        AArch64Inst::StnpSimdFPVar64(operand) => {
+            compile_ld_st_no_alloc_pair(basic_block, operand, MemOp::Store, IrType::U64)
         }
-        AArch64Inst::AndsShiftedReg64(operand) => {
-            compile_ands_shifted_reg(basic_block, operand, IrType::U64)
+        AArch64Inst::LdnpSimdFPVar64(operand) => {
+            compile_ld_st_no_alloc_pair(basic_block, operand, MemOp::Load, IrType::U64)
         }
-        AArch64Inst::AndShiftedReg64(operand) => {
-            compile_and_shifted_reg(basic_block, operand, IrType::U64)
+        AArch64Inst::StnpSimdFPVar128(operand) => {
+            compile_ld_st_no_alloc_pair(basic_block, operand, MemOp::Store, IrType::U128)
         }
-        AArch64Inst::OrrImm64(operand) => compile_orr_imm(basic_block, operand, IrType::U64),
-        AArch64Inst::OrrImm32(operand) => compile_orr_imm(basic_block, operand, IrType::U32),
-        AArch64Inst::OrrShiftedReg64(operand) => {
-            compile_orr_shifted_reg(basic_block, operand, IrType::U64)
+        AArch64Inst::LdnpSimdFPVar128(operand) => {
+            compile_ld_st_no_alloc_pair(basic_block, operand, MemOp::Load, IrType::U128)
         }
-        AArch64Inst::OrrShiftedReg32(operand) => {
-            compile_orr_shifted_reg(basic_block, operand, IrType::U32)
+        AArch64Inst::LdrLitVar32(operand) => {
+            compile_ldr_lit(basic_block, operand, MemOp::Load, IrType::U32)
         }
-        AArch64Inst::OrnShiftedReg64(operand) => {
-            compile_orn_shifted_reg(basic_block, operand, IrType::U64)
+        AArch64Inst::LdrLitVar64(operand) => {
+            compile_ldr_lit(basic_block, operand, MemOp::Load, IrType::U64)
         }
-        AArch64Inst::OrnShiftedReg32(operand) => {
-            compile_orn_shifted_reg(basic_block, operand, IrType::U32)
+        AArch64Inst::LdrswLit(operand) => {
+            compile_ldr_lit(basic_block, operand, MemOp::LoadSigned64, IrType::U32)
         }
+        AArch64Inst::LdrLitSimdFPVar32(operand) => {
+            compile_ldr_lit(basic_block, operand, MemOp::Load, IrType::U32)
+        }
+        AArch64Inst::LdrLitSimdFPVar64(operand) => {
+            compile_ldr_lit(basic_block, operand, MemOp::Load, IrType::U64)
+        }
+        AArch64Inst::LdrLitSimdFPVar128(operand) => {
+            compile_ldr_lit(basic_block, operand, MemOp::Load, IrType::U128)
+        }
+        AArch64Inst::St1MulStructures1RegsVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 1, 1)
+        }
+        AArch64Inst::St1MulStructures2RegsVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 1, 2)
+        }
+        AArch64Inst::St1MulStructures3RegsVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 1, 3)
+        }
+        AArch64Inst::St1MulStructures4RegsVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 1, 4)
+        }
+        AArch64Inst::St2MulStructures(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 2, 1)
+        }
+        AArch64Inst::St3MulStructures(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 3, 1)
+        }
+        AArch64Inst::St4MulStructures(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 4, 1)
+        }
+        AArch64Inst::St1MulStructures1RegRegOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 1, 1)
+        }
+        AArch64Inst::St1MulStructures2RegRegOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 1, 2)
+        }
+        AArch64Inst::St1MulStructures3RegRegOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 1, 3)
+        }
+        AArch64Inst::St1MulStructures4RegRegOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 1, 4)
+        }
+        AArch64Inst::St2MulStructuresRegOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 2, 1)
+        }
+        AArch64Inst::St3MulStructuresRegOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 3, 1)
+        }
+        AArch64Inst::St4MulStructuresRegOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 4, 1)
+        }
+        AArch64Inst::St1MulStructures1RegImmOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 1, 1)
+        }
+        AArch64Inst::St1MulStructures2RegImmOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 1, 2)
+        }
+        AArch64Inst::St1MulStructures3RegImmOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 1, 3)
+        }
+        AArch64Inst::St1MulStructures4RegImmOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 1, 4)
+        }
+        AArch64Inst::St2MulStructuresImmOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 2, 1)
+        }
+        AArch64Inst::St3MulStructuresImmOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 3, 1)
+        }
+        AArch64Inst::St4MulStructuresImmOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Store, 4, 1)
+        }
+        AArch64Inst::Ld1MulStructures1RegsVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 1, 1)
+        }
+        AArch64Inst::Ld1MulStructures2RegsVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 1, 2)
+        }
+        AArch64Inst::Ld1MulStructures3RegsVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 1, 3)
+        }
+        AArch64Inst::Ld1MulStructures4RegsVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 1, 4)
+        }
+        AArch64Inst::Ld2MulStructures(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 2, 1)
+        }
+        AArch64Inst::Ld3MulStructures(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 3, 1)
+        }
+        AArch64Inst::Ld4MulStructures(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 4, 1)
+        }
+        AArch64Inst::Ld1MulStructures1RegRegOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 1, 1)
+        }
+        AArch64Inst::Ld1MulStructures2RegRegOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 1, 2)
+        }
+        AArch64Inst::Ld1MulStructures3RegRegOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 1, 3)
+        }
+        AArch64Inst::Ld1MulStructures4RegRegOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 1, 4)
+        }
+        AArch64Inst::Ld2MulStructuresRegOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 2, 1)
+        }
+        AArch64Inst::Ld3MulStructuresRegOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 3, 1)
+        }
+        AArch64Inst::Ld4MulStructuresRegOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 4, 1)
+        }
+        AArch64Inst::Ld1MulStructures1RegImmOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 1, 1)
+        }
+        AArch64Inst::Ld1MulStructures2RegImmOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 1, 2)
+        }
+        AArch64Inst::Ld1MulStructures3RegImmOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 1, 3)
+        }
+        AArch64Inst::Ld1MulStructures4RegImmOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 1, 4)
+        }
+        AArch64Inst::Ld2MulStructuresImmOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 2, 1)
+        }
+        AArch64Inst::Ld3MulStructuresImmOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 3, 1)
+        }
+        AArch64Inst::Ld4MulStructuresImmOffsetVar(operand) => {
+            compile_ld_st_multiple(basic_block, operand.into(), MemOp::Load, 4, 1)
+        }
+        AArch64Inst::St1SingleStructureVar8(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 1, 8)
+        }
+        AArch64Inst::St1SingleStructureVar16(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 1, 16)
+        }
+        AArch64Inst::St1SingleStructureVar32(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 1, 32)
+        }
+        AArch64Inst::St1SingleStructureVar64(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 1, 64)
+        }
+        AArch64Inst::St2SingleStructureVar8(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 2, 8)
+        }
+        AArch64Inst::St2SingleStructureVar16(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 2, 16)
+        }
+        AArch64Inst::St2SingleStructureVar32(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 2, 32)
+        }
+        AArch64Inst::St2SingleStructureVar64(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 2, 64)
+        }
+        AArch64Inst::St3SingleStructureVar8(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 3, 8)
+        }
+        AArch64Inst::St3SingleStructureVar16(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 3, 16)
+        }
+        AArch64Inst::St3SingleStructureVar32(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 3, 32)
+        }
+        AArch64Inst::St3SingleStructureVar64(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 3, 64)
+        }
+        AArch64Inst::St4SingleStructureVar8(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 4, 8)
+        }
+        AArch64Inst::St4SingleStructureVar16(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 4, 16)
+        }
+        AArch64Inst::St4SingleStructureVar32(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 4, 32)
+        }
+        AArch64Inst::St4SingleStructureVar64(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 4, 64)
+        }
+        AArch64Inst::Ld1SingleStructureVar8(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 1, 8)
+        }
+        AArch64Inst::Ld1SingleStructureVar16(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 1, 16)
+        }
+        AArch64Inst::Ld1SingleStructureVar32(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 1, 32)
+        }
+        AArch64Inst::Ld1SingleStructureVar64(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 1, 64)
+        }
+        AArch64Inst::Ld2SingleStructureVar8(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 2, 8)
+        }
+        AArch64Inst::Ld2SingleStructureVar16(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 2, 16)
+        }
+        AArch64Inst::Ld2SingleStructureVar32(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 2, 32)
+        }
+        AArch64Inst::Ld2SingleStructureVar64(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 2, 64)
+        }
+        AArch64Inst::Ld3SingleStructureVar8(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 3, 8)
+        }
+        AArch64Inst::Ld3SingleStructureVar16(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 3, 16)
+        }
+        AArch64Inst::Ld3SingleStructureVar32(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 3, 32)
+        }
+        AArch64Inst::Ld3SingleStructureVar64(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 3, 64)
+        }
+        AArch64Inst::Ld4SingleStructureVar8(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 4, 8)
+        }
+        AArch64Inst::Ld4SingleStructureVar16(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 4, 16)
+        }
+        AArch64Inst::Ld4SingleStructureVar32(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 4, 32)
+        }
+        AArch64Inst::Ld4SingleStructureVar64(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 4, 64)
+        }
+        AArch64Inst::Ld1r(operand) => compile_ld_replicate(basic_block, operand.into(), 1),
+        AArch64Inst::Ld2r(operand) => compile_ld_replicate(basic_block, operand.into(), 2),
+        AArch64Inst::Ld3r(operand) => compile_ld_replicate(basic_block, operand.into(), 3),
+        AArch64Inst::Ld4r(operand) => compile_ld_replicate(basic_block, operand.into(), 4),
+        AArch64Inst::St1SingleStructurePostIndexedVar8(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 1, 8)
+        }
+        AArch64Inst::St1SingleStructurePostIndexedVar16(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 1, 16)
+        }
+        AArch64Inst::St1SingleStructurePostIndexedVar32(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 1, 32)
+        }
+        AArch64Inst::St1SingleStructurePostIndexedVar64(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 1, 64)
+        }
+        AArch64Inst::St2SingleStructurePostIndexedVar8(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 2, 8)
+        }
+        AArch64Inst::St2SingleStructurePostIndexedVar16(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 2, 16)
+        }
+        AArch64Inst::St2SingleStructurePostIndexedVar32(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 2, 32)
+        }
+        AArch64Inst::St2SingleStructurePostIndexedVar64(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 2, 64)
+        }
+        AArch64Inst::St3SingleStructurePostIndexedVar8(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 3, 8)
+        }
+        AArch64Inst::St3SingleStructurePostIndexedVar16(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 3, 16)
+        }
+        AArch64Inst::St3SingleStructurePostIndexedVar32(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 3, 32)
+        }
+        AArch64Inst::St3SingleStructurePostIndexedVar64(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 3, 64)
+        }
+        AArch64Inst::St4SingleStructurePostIndexedVar8(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 4, 8)
+        }
+        AArch64Inst::St4SingleStructurePostIndexedVar16(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 4, 16)
+        }
+        AArch64Inst::St4SingleStructurePostIndexedVar32(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 4, 32)
+        }
+        AArch64Inst::St4SingleStructurePostIndexedVar64(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Store, 4, 64)
+        }
+        AArch64Inst::Ld1SingleStructurePostIndexedVar8(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 1, 8)
+        }
+        AArch64Inst::Ld1SingleStructurePostIndexedVar16(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 1, 16)
+        }
+        AArch64Inst::Ld1SingleStructurePostIndexedVar32(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 1, 32)
+        }
+        AArch64Inst::Ld1SingleStructurePostIndexedVar64(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 1, 64)
+        }
+        AArch64Inst::Ld2SingleStructurePostIndexedVar8(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 2, 8)
+        }
+        AArch64Inst::Ld2SingleStructurePostIndexedVar16(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 2, 16)
+        }
+        AArch64Inst::Ld2SingleStructurePostIndexedVar32(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 2, 32)
+        }
+        AArch64Inst::Ld2SingleStructurePostIndexedVar64(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 2, 64)
+        }
+        AArch64Inst::Ld3SingleStructurePostIndexedVar8(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 3, 8)
+        }
+        AArch64Inst::Ld3SingleStructurePostIndexedVar16(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 3, 16)
+        }
+        AArch64Inst::Ld3SingleStructurePostIndexedVar32(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 3, 32)
+        }
+        AArch64Inst::Ld3SingleStructurePostIndexedVar64(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 3, 64)
+        }
+        AArch64Inst::Ld4SingleStructurePostIndexedVar8(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 4, 8)
+        }
+        AArch64Inst::Ld4SingleStructurePostIndexedVar16(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 4, 16)
+        }
+        AArch64Inst::Ld4SingleStructurePostIndexedVar32(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 4, 32)
+        }
+        AArch64Inst::Ld4SingleStructurePostIndexedVar64(operand) => {
+            compile_ld_st_single(basic_block, operand.into(), MemOp::Load, 4, 64)
+        }
+        AArch64Inst::Ld1rPostIndexed(operand) => {
+            compile_ld_replicate(basic_block, operand.into(), 1)
+        }
+        AArch64Inst::Ld2rPostIndexed(operand) => {
+            compile_ld_replicate(basic_block, operand.into(), 2)
+        }
+        AArch64Inst::Ld3rPostIndexed(operand) => {
+            compile_ld_replicate(basic_block, operand.into(), 3)
+        }
+        AArch64Inst::Ld4rPostIndexed(operand) => {
+            compile_ld_replicate(basic_block, operand.into(), 4)
+        }
+        // Prefetches are hints without architectural effect
+        AArch64Inst::PrfmImm(_)
+        | AArch64Inst::PrfmReg(_)
+        | AArch64Inst::PrfmLit(_)
+        | AArch64Inst::Prefum(_) => compiler_prelude::gen_move_pc(basic_block),
 
-        AArch64Inst::LslvVar64(operand) => compile_lslv(basic_block, operand, IrType::U64),
-        AArch64Inst::LslvVar32(operand) => compile_lslv(basic_block, operand, IrType::U32),
-
-        // Branch instructions
-        AArch64Inst::BlImm(operand) => compile_bl_imm(basic_block, operand),
-        AArch64Inst::BImm(operand) => compile_b_imm(basic_block, operand),
-        AArch64Inst::Br(operand) => compile_br(basic_block, operand),
-        AArch64Inst::Blr(operand) => compile_blr(basic_block, operand),
-        AArch64Inst::BCond(operand) => compile_b_cond(basic_block, operand),
-        AArch64Inst::Cbz64(operand) => compile_cbz(basic_block, operand, IrType::U32),
-        AArch64Inst::Cbz32(operand) => compile_cbz(basic_block, operand, IrType::U64),
-        AArch64Inst::Cbnz32(operand) => compile_cbnz(basic_block, operand, IrType::U32),
-        AArch64Inst::Cbnz64(operand) => compile_cbnz(basic_block, operand, IrType::U64),
-        AArch64Inst::Ret(operand) => compile_ret(basic_block, operand),
-        AArch64Inst::Tbz(operand) => compile_tbz(basic_block, operand),
-        AArch64Inst::Tbnz(operand) => compile_tbnz(basic_block, operand),
-
-        // Conditional Instructions
-        AArch64Inst::CcmpImmVar32(operand) => compile_ccmp_imm(basic_block, operand, IrType::U32),
-        AArch64Inst::CcmpImmVar64(operand) => compile_ccmp_imm(basic_block, operand, IrType::U64),
-        AArch64Inst::CcmpRegVar64(operand) => compile_ccmp_reg(basic_block, operand, IrType::U64),
-        AArch64Inst::CcmnImmVar64(operand) => compile_ccmn_imm(basic_block, operand, IrType::U64),
-        AArch64Inst::Csel32(operand) => compile_csel(basic_block, operand, IrType::U32),
-        AArch64Inst::Csel64(operand) => compile_csel(basic_block, operand, IrType::U64),
-        AArch64Inst::Csinv64(operand) => compile_csinv(basic_block, operand, IrType::U64),
-
-        // Interrupt Instructions
-        AArch64Inst::Svc(operand) => compile_svc(basic_block, operand),
-        AArch64Inst::Brk(operand) => compile_brk(basic_block, operand),
-
-        // Speical instructions
-        AArch64Inst::Mrs(operand) => compile_mrs(basic_block, operand),
-        AArch64Inst::MsrReg(operand) => compile_msr_reg(basic_block, operand),
-        AArch64Inst::MsrImm(operand) => compile_msr_imm(basic_block, operand),
-        AArch64Inst::Nop | AArch64Inst::Wfi | AArch64Inst::Dmb(_) | AArch64Inst::Isb(_) => {
-            todo!()
+        AArch64Inst::Ldxrb(operand) => compile_load_exclusive(
+            basic_block,
+            &[operand.rt],
+            operand.rn,
+            IrType::U8,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::Stxrb(operand) => compile_store_exclusive(
+            basic_block,
+            operand.rs,
+            &[operand.rt],
+            operand.rn,
+            IrType::U8,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::Ldxrh(operand) => compile_load_exclusive(
+            basic_block,
+            &[operand.rt],
+            operand.rn,
+            IrType::U16,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::Stxrh(operand) => compile_store_exclusive(
+            basic_block,
+            operand.rs,
+            &[operand.rt],
+            operand.rn,
+            IrType::U16,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdxrVar32(operand) => compile_load_exclusive(
+            basic_block,
+            &[operand.rt],
+            operand.rn,
+            IrType::U32,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::StxrVar32(operand) => compile_store_exclusive(
+            basic_block,
+            operand.rs,
+            &[operand.rt],
+            operand.rn,
+            IrType::U32,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdxrVar64(operand) => compile_load_exclusive(
+            basic_block,
+            &[operand.rt],
+            operand.rn,
+            IrType::U64,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::StxrVar64(operand) => compile_store_exclusive(
+            basic_block,
+            operand.rs,
+            &[operand.rt],
+            operand.rn,
+            IrType::U64,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdxpVar32(operand) => compile_load_exclusive(
+            basic_block,
+            &[operand.rt, operand.rt2],
+            operand.rn,
+            IrType::U32,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::StxpVar32(operand) => compile_store_exclusive(
+            basic_block,
+            operand.rs,
+            &[operand.rt, operand.rt2],
+            operand.rn,
+            IrType::U32,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdxpVar64(operand) => compile_load_exclusive(
+            basic_block,
+            &[operand.rt, operand.rt2],
+            operand.rn,
+            IrType::U64,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::StxpVar64(operand) => compile_store_exclusive(
+            basic_block,
+            operand.rs,
+            &[operand.rt, operand.rt2],
+            operand.rn,
+            IrType::U64,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::Ldaxrb(operand) => compile_load_exclusive(
+            basic_block,
+            &[operand.rt],
+            operand.rn,
+            IrType::U8,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::Stlxrb(operand) => compile_store_exclusive(
+            basic_block,
+            operand.rs,
+            &[operand.rt],
+            operand.rn,
+            IrType::U8,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::Ldaxrh(operand) => compile_load_exclusive(
+            basic_block,
+            &[operand.rt],
+            operand.rn,
+            IrType::U16,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::Stlxrh(operand) => compile_store_exclusive(
+            basic_block,
+            operand.rs,
+            &[operand.rt],
+            operand.rn,
+            IrType::U16,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdaxrVar32(operand) => compile_load_exclusive(
+            basic_block,
+            &[operand.rt],
+            operand.rn,
+            IrType::U32,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::StlxrVar32(operand) => compile_store_exclusive(
+            basic_block,
+            operand.rs,
+            &[operand.rt],
+            operand.rn,
+            IrType::U32,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdaxrVar64(operand) => compile_load_exclusive(
+            basic_block,
+            &[operand.rt],
+            operand.rn,
+            IrType::U64,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::StlxrVar64(operand) => compile_store_exclusive(
+            basic_block,
+            operand.rs,
+            &[operand.rt],
+            operand.rn,
+            IrType::U64,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdaxpVar32(operand) => compile_load_exclusive(
+            basic_block,
+            &[operand.rt, operand.rt2],
+            operand.rn,
+            IrType::U32,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::StlxpVar32(operand) => compile_store_exclusive(
+            basic_block,
+            operand.rs,
+            &[operand.rt, operand.rt2],
+            operand.rn,
+            IrType::U32,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdaxpVar64(operand) => compile_load_exclusive(
+            basic_block,
+            &[operand.rt, operand.rt2],
+            operand.rn,
+            IrType::U64,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::StlxpVar64(operand) => compile_store_exclusive(
+            basic_block,
+            operand.rs,
+            &[operand.rt, operand.rt2],
+            operand.rn,
+            IrType::U64,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::Clrex(_) => compile_clrex(basic_block),
+        AArch64Inst::Ldarb(operand) => {
+            compile_load_acquire(basic_block, operand.rt, operand.rn, IrType::U8)
         }
-        _ => unimplemented!(),
+        AArch64Inst::Stlrb(operand) => {
+            compile_store_release(basic_block, operand.rt, operand.rn, IrType::U8)
+        }
+        AArch64Inst::Ldarh(operand) => {
+            compile_load_acquire(basic_block, operand.rt, operand.rn, IrType::U16)
+        }
+        AArch64Inst::Stlrh(operand) => {
+            compile_store_release(basic_block, operand.rt, operand.rn, IrType::U16)
+        }
+        AArch64Inst::LdarVar32(operand) => {
+            compile_load_acquire(basic_block, operand.rt, operand.rn, IrType::U32)
+        }
+        AArch64Inst::StlrVar32(operand) => {
+            compile_store_release(basic_block, operand.rt, operand.rn, IrType::U32)
+        }
+        AArch64Inst::LdarVar64(operand) => {
+            compile_load_acquire(basic_block, operand.rt, operand.rn, IrType::U64)
+        }
+        AArch64Inst::StlrVar64(operand) => {
+            compile_store_release(basic_block, operand.rt, operand.rn, IrType::U64)
+        }
+        AArch64Inst::Ldaprb(operand) => {
+            compile_load_acquire(basic_block, operand.rt, operand.rn, IrType::U8)
+        }
+        AArch64Inst::Ldaprh(operand) => {
+            compile_load_acquire(basic_block, operand.rt, operand.rn, IrType::U16)
+        }
+        AArch64Inst::LdaprVar32(operand) => {
+            compile_load_acquire(basic_block, operand.rt, operand.rn, IrType::U32)
+        }
+        AArch64Inst::LdaprVar64(operand) => {
+            compile_load_acquire(basic_block, operand.rt, operand.rn, IrType::U64)
+        }
+        AArch64Inst::Casb(operand) => {
+            compile_cas(basic_block, operand, IrType::U8, MemOrdering::Relaxed)
+        }
+        AArch64Inst::Casab(operand) => {
+            compile_cas(basic_block, operand, IrType::U8, MemOrdering::Acquire)
+        }
+        AArch64Inst::Caslb(operand) => {
+            compile_cas(basic_block, operand, IrType::U8, MemOrdering::Release)
+        }
+        AArch64Inst::Casalb(operand) => compile_cas(
+            basic_block,
+            operand,
+            IrType::U8,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::Cash(operand) => {
+            compile_cas(basic_block, operand, IrType::U16, MemOrdering::Relaxed)
+        }
+        AArch64Inst::Casah(operand) => {
+            compile_cas(basic_block, operand, IrType::U16, MemOrdering::Acquire)
+        }
+        AArch64Inst::Caslh(operand) => {
+            compile_cas(basic_block, operand, IrType::U16, MemOrdering::Release)
+        }
+        AArch64Inst::Casalh(operand) => compile_cas(
+            basic_block,
+            operand,
+            IrType::U16,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::CasVar32(operand) => {
+            compile_cas(basic_block, operand, IrType::U32, MemOrdering::Relaxed)
+        }
+        AArch64Inst::CasaVar32(operand) => {
+            compile_cas(basic_block, operand, IrType::U32, MemOrdering::Acquire)
+        }
+        AArch64Inst::CaslVar32(operand) => {
+            compile_cas(basic_block, operand, IrType::U32, MemOrdering::Release)
+        }
+        AArch64Inst::CasalVar32(operand) => compile_cas(
+            basic_block,
+            operand,
+            IrType::U32,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::CasVar64(operand) => {
+            compile_cas(basic_block, operand, IrType::U64, MemOrdering::Relaxed)
+        }
+        AArch64Inst::CasaVar64(operand) => {
+            compile_cas(basic_block, operand, IrType::U64, MemOrdering::Acquire)
+        }
+        AArch64Inst::CaslVar64(operand) => {
+            compile_cas(basic_block, operand, IrType::U64, MemOrdering::Release)
+        }
+        AArch64Inst::CasalVar64(operand) => compile_cas(
+            basic_block,
+            operand,
+            IrType::U64,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::CaspVar32(operand) => {
+            compile_casp(basic_block, operand, IrType::U32, MemOrdering::Relaxed)
+        }
+        AArch64Inst::CaspaVar32(operand) => {
+            compile_casp(basic_block, operand, IrType::U32, MemOrdering::Acquire)
+        }
+        AArch64Inst::CasplVar32(operand) => {
+            compile_casp(basic_block, operand, IrType::U32, MemOrdering::Release)
+        }
+        AArch64Inst::CaspalVar32(operand) => compile_casp(
+            basic_block,
+            operand,
+            IrType::U32,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::CaspVar64(operand) => {
+            compile_casp(basic_block, operand, IrType::U64, MemOrdering::Relaxed)
+        }
+        AArch64Inst::CaspaVar64(operand) => {
+            compile_casp(basic_block, operand, IrType::U64, MemOrdering::Acquire)
+        }
+        AArch64Inst::CasplVar64(operand) => {
+            compile_casp(basic_block, operand, IrType::U64, MemOrdering::Release)
+        }
+        AArch64Inst::CaspalVar64(operand) => compile_casp(
+            basic_block,
+            operand,
+            IrType::U64,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdaddbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Add,
+            IrType::U8,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdaddabVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Add,
+            IrType::U8,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdaddlbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Add,
+            IrType::U8,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdaddalbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Add,
+            IrType::U8,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdclrbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Clear,
+            IrType::U8,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdclrabVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Clear,
+            IrType::U8,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdclrlbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Clear,
+            IrType::U8,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdclralbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Clear,
+            IrType::U8,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdeorbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Xor,
+            IrType::U8,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdeorabVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Xor,
+            IrType::U8,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdeorlbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Xor,
+            IrType::U8,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdeoralbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Xor,
+            IrType::U8,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdsetbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Set,
+            IrType::U8,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdsetabVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Set,
+            IrType::U8,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdsetlbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Set,
+            IrType::U8,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdsetalbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Set,
+            IrType::U8,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdsmaxbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMax,
+            IrType::U8,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdsmaxabVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMax,
+            IrType::U8,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdsmaxlbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMax,
+            IrType::U8,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdsmaxalbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMax,
+            IrType::U8,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdsminbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMin,
+            IrType::U8,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdsminabVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMin,
+            IrType::U8,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdsminlbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMin,
+            IrType::U8,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdsminalbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMin,
+            IrType::U8,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdumaxbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMax,
+            IrType::U8,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdumaxabVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMax,
+            IrType::U8,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdumaxlbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMax,
+            IrType::U8,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdumaxalbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMax,
+            IrType::U8,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LduminbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMin,
+            IrType::U8,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LduminabVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMin,
+            IrType::U8,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LduminlbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMin,
+            IrType::U8,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LduminalbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMin,
+            IrType::U8,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::SwpbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Swap,
+            IrType::U8,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::SwpabVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Swap,
+            IrType::U8,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::SwplbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Swap,
+            IrType::U8,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::SwpalbVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Swap,
+            IrType::U8,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdaddhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Add,
+            IrType::U16,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdaddahVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Add,
+            IrType::U16,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdaddlhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Add,
+            IrType::U16,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdaddalhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Add,
+            IrType::U16,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdclrhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Clear,
+            IrType::U16,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdclrahVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Clear,
+            IrType::U16,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdclrlhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Clear,
+            IrType::U16,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdclralhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Clear,
+            IrType::U16,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdeorhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Xor,
+            IrType::U16,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdeorahVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Xor,
+            IrType::U16,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdeorlhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Xor,
+            IrType::U16,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdeoralhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Xor,
+            IrType::U16,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdsethVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Set,
+            IrType::U16,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdsetahVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Set,
+            IrType::U16,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdsetlhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Set,
+            IrType::U16,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdsetalhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Set,
+            IrType::U16,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdsmaxhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMax,
+            IrType::U16,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdsmaxahVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMax,
+            IrType::U16,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdsmaxlhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMax,
+            IrType::U16,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdsmaxalhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMax,
+            IrType::U16,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdsminhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMin,
+            IrType::U16,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdsminahVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMin,
+            IrType::U16,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdsminlhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMin,
+            IrType::U16,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdsminalhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMin,
+            IrType::U16,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdumaxhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMax,
+            IrType::U16,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdumaxahVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMax,
+            IrType::U16,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdumaxlhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMax,
+            IrType::U16,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdumaxalhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMax,
+            IrType::U16,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LduminhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMin,
+            IrType::U16,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LduminahVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMin,
+            IrType::U16,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LduminlhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMin,
+            IrType::U16,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LduminalhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMin,
+            IrType::U16,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::SwphVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Swap,
+            IrType::U16,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::SwpahVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Swap,
+            IrType::U16,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::SwplhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Swap,
+            IrType::U16,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::SwpalhVar(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Swap,
+            IrType::U16,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdaddVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Add,
+            IrType::U32,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdaddaVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Add,
+            IrType::U32,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdaddlVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Add,
+            IrType::U32,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdaddalVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Add,
+            IrType::U32,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdclrVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Clear,
+            IrType::U32,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdclraVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Clear,
+            IrType::U32,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdclrlVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Clear,
+            IrType::U32,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdclralVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Clear,
+            IrType::U32,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdeorVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Xor,
+            IrType::U32,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdeoraVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Xor,
+            IrType::U32,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdeorlVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Xor,
+            IrType::U32,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdeoralVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Xor,
+            IrType::U32,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdsetVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Set,
+            IrType::U32,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdsetaVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Set,
+            IrType::U32,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdsetlVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Set,
+            IrType::U32,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdsetalVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Set,
+            IrType::U32,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdsmaxVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMax,
+            IrType::U32,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdsmaxaVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMax,
+            IrType::U32,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdsmaxlVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMax,
+            IrType::U32,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdsmaxalVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMax,
+            IrType::U32,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdsminVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMin,
+            IrType::U32,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdsminaVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMin,
+            IrType::U32,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdsminlVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMin,
+            IrType::U32,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdsminalVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMin,
+            IrType::U32,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdumaxVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMax,
+            IrType::U32,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdumaxaVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMax,
+            IrType::U32,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdumaxlVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMax,
+            IrType::U32,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdumaxalVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMax,
+            IrType::U32,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LduminVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMin,
+            IrType::U32,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LduminaVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMin,
+            IrType::U32,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LduminlVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMin,
+            IrType::U32,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LduminalVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMin,
+            IrType::U32,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::SwpVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Swap,
+            IrType::U32,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::SwpaVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Swap,
+            IrType::U32,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::SwplVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Swap,
+            IrType::U32,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::SwpalVar32(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Swap,
+            IrType::U32,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdaddVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Add,
+            IrType::U64,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdaddaVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Add,
+            IrType::U64,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdaddlVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Add,
+            IrType::U64,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdaddalVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Add,
+            IrType::U64,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdclrVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Clear,
+            IrType::U64,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdclraVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Clear,
+            IrType::U64,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdclrlVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Clear,
+            IrType::U64,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdclralVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Clear,
+            IrType::U64,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdeorVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Xor,
+            IrType::U64,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdeoraVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Xor,
+            IrType::U64,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdeorlVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Xor,
+            IrType::U64,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdeoralVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Xor,
+            IrType::U64,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdsetVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Set,
+            IrType::U64,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdsetaVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Set,
+            IrType::U64,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdsetlVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Set,
+            IrType::U64,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdsetalVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Set,
+            IrType::U64,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdsmaxVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMax,
+            IrType::U64,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdsmaxaVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMax,
+            IrType::U64,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdsmaxlVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMax,
+            IrType::U64,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdsmaxalVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMax,
+            IrType::U64,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdsminVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMin,
+            IrType::U64,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdsminaVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMin,
+            IrType::U64,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdsminlVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMin,
+            IrType::U64,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdsminalVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::SignedMin,
+            IrType::U64,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LdumaxVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMax,
+            IrType::U64,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LdumaxaVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMax,
+            IrType::U64,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LdumaxlVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMax,
+            IrType::U64,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LdumaxalVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMax,
+            IrType::U64,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::LduminVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMin,
+            IrType::U64,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::LduminaVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMin,
+            IrType::U64,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::LduminlVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMin,
+            IrType::U64,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::LduminalVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::UnsignedMin,
+            IrType::U64,
+            MemOrdering::AcquireRelease,
+        ),
+        AArch64Inst::SwpVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Swap,
+            IrType::U64,
+            MemOrdering::Relaxed,
+        ),
+        AArch64Inst::SwpaVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Swap,
+            IrType::U64,
+            MemOrdering::Acquire,
+        ),
+        AArch64Inst::SwplVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Swap,
+            IrType::U64,
+            MemOrdering::Release,
+        ),
+        AArch64Inst::SwpalVar64(operand) => compile_atomic(
+            basic_block,
+            operand,
+            AtomicMemOp::Swap,
+            IrType::U64,
+            MemOrdering::AcquireRelease,
+        ),
+
+        // Advanced SIMD
+        AArch64Inst::Shadd(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::HalvingAdd, true)
+        }
+        AArch64Inst::Uhadd(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::HalvingAdd, false)
+        }
+        AArch64Inst::Srhadd(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::RoundingHalvingAdd, true)
+        }
+        AArch64Inst::Urhadd(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::RoundingHalvingAdd, false)
+        }
+        AArch64Inst::Shsub(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::HalvingSub, true)
+        }
+        AArch64Inst::Uhsub(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::HalvingSub, false)
+        }
+        AArch64Inst::Sqadd(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::SaturatingAdd, true)
+        }
+        AArch64Inst::Uqadd(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::SaturatingAdd, false)
+        }
+        AArch64Inst::Sqsub(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::SaturatingSub, true)
+        }
+        AArch64Inst::Uqsub(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::SaturatingSub, false)
+        }
+        AArch64Inst::CmgtReg(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::CompareGt, true)
+        }
+        AArch64Inst::CmhiReg(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::CompareGt, false)
+        }
+        AArch64Inst::CmgeReg(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::CompareGe, true)
+        }
+        AArch64Inst::CmhsReg(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::CompareGe, false)
+        }
+        AArch64Inst::CmeqReg(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::CompareEq, false)
+        }
+        AArch64Inst::Cmtst(operand) => compile_vec_int(basic_block, operand, VecIntOp::Test, false),
+        AArch64Inst::Sshl(operand) => compile_vec_int(
+            basic_block,
+            operand,
+            VecIntOp::Shift {
+                rounding: false,
+                saturating: false,
+            },
+            true,
+        ),
+        AArch64Inst::Ushl(operand) => compile_vec_int(
+            basic_block,
+            operand,
+            VecIntOp::Shift {
+                rounding: false,
+                saturating: false,
+            },
+            false,
+        ),
+        AArch64Inst::SqshlReg(operand) => compile_vec_int(
+            basic_block,
+            operand,
+            VecIntOp::Shift {
+                rounding: false,
+                saturating: true,
+            },
+            true,
+        ),
+        AArch64Inst::UqshlReg(operand) => compile_vec_int(
+            basic_block,
+            operand,
+            VecIntOp::Shift {
+                rounding: false,
+                saturating: true,
+            },
+            false,
+        ),
+        AArch64Inst::Srshl(operand) => compile_vec_int(
+            basic_block,
+            operand,
+            VecIntOp::Shift {
+                rounding: true,
+                saturating: false,
+            },
+            true,
+        ),
+        AArch64Inst::Urshl(operand) => compile_vec_int(
+            basic_block,
+            operand,
+            VecIntOp::Shift {
+                rounding: true,
+                saturating: false,
+            },
+            false,
+        ),
+        AArch64Inst::Sqrshl(operand) => compile_vec_int(
+            basic_block,
+            operand,
+            VecIntOp::Shift {
+                rounding: true,
+                saturating: true,
+            },
+            true,
+        ),
+        AArch64Inst::Uqrshl(operand) => compile_vec_int(
+            basic_block,
+            operand,
+            VecIntOp::Shift {
+                rounding: true,
+                saturating: true,
+            },
+            false,
+        ),
+        AArch64Inst::Smax(operand) => compile_vec_int(basic_block, operand, VecIntOp::Max, true),
+        AArch64Inst::Umax(operand) => compile_vec_int(basic_block, operand, VecIntOp::Max, false),
+        AArch64Inst::Smin(operand) => compile_vec_int(basic_block, operand, VecIntOp::Min, true),
+        AArch64Inst::Umin(operand) => compile_vec_int(basic_block, operand, VecIntOp::Min, false),
+        AArch64Inst::Sabd(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::AbsDiff, true)
+        }
+        AArch64Inst::Uabd(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::AbsDiff, false)
+        }
+        AArch64Inst::Saba(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::AbsDiffAccumulate, true)
+        }
+        AArch64Inst::Uaba(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::AbsDiffAccumulate, false)
+        }
+        AArch64Inst::AddVec(operand) => compile_vec_int(basic_block, operand, VecIntOp::Add, false),
+        AArch64Inst::SubVec(operand) => compile_vec_int(basic_block, operand, VecIntOp::Sub, false),
+        AArch64Inst::MulVec(operand) => compile_vec_int(basic_block, operand, VecIntOp::Mul, false),
+        AArch64Inst::MlaVec(operand) => compile_vec_int(basic_block, operand, VecIntOp::Mla, false),
+        AArch64Inst::MlsVec(operand) => compile_vec_int(basic_block, operand, VecIntOp::Mls, false),
+        AArch64Inst::Pmul(operand) => {
+            compile_vec_int(basic_block, operand, VecIntOp::PolyMul, false)
+        }
+        AArch64Inst::SqdmulhVec(operand) => compile_vec_int(
+            basic_block,
+            operand,
+            VecIntOp::DoublingMulHigh { rounding: false },
+            true,
+        ),
+        AArch64Inst::SqrdmulhVec(operand) => compile_vec_int(
+            basic_block,
+            operand,
+            VecIntOp::DoublingMulHigh { rounding: true },
+            true,
+        ),
+        AArch64Inst::AddpVec(operand) => {
+            compile_vec_int_pairwise(basic_block, operand, VecIntOp::Add, false)
+        }
+        AArch64Inst::Smaxp(operand) => {
+            compile_vec_int_pairwise(basic_block, operand, VecIntOp::Max, true)
+        }
+        AArch64Inst::Umaxp(operand) => {
+            compile_vec_int_pairwise(basic_block, operand, VecIntOp::Max, false)
+        }
+        AArch64Inst::Sminp(operand) => {
+            compile_vec_int_pairwise(basic_block, operand, VecIntOp::Min, true)
+        }
+        AArch64Inst::Uminp(operand) => {
+            compile_vec_int_pairwise(basic_block, operand, VecIntOp::Min, false)
+        }
+        AArch64Inst::AndVec(operand) => compile_vec_logical(basic_block, operand, LogicalOp::And),
+        AArch64Inst::BicVecReg(operand) => {
+            compile_vec_logical(basic_block, operand, LogicalOp::Bic)
+        }
+        AArch64Inst::OrrVecReg(operand) => {
+            compile_vec_logical(basic_block, operand, LogicalOp::Orr)
+        }
+        AArch64Inst::OrnVec(operand) => compile_vec_logical(basic_block, operand, LogicalOp::Orn),
+        AArch64Inst::EorVec(operand) => compile_vec_logical(basic_block, operand, LogicalOp::Eor),
+        AArch64Inst::Bsl(operand) => compile_bit_select(basic_block, operand, BitSelectOp::Bsl),
+        AArch64Inst::Bit(operand) => compile_bit_select(basic_block, operand, BitSelectOp::Bit),
+        AArch64Inst::Bif(operand) => compile_bit_select(basic_block, operand, BitSelectOp::Bif),
+        AArch64Inst::Sshr(operand) => compile_vec_shift_imm(
+            basic_block,
+            operand,
+            ShiftImmOp::Right {
+                rounding: false,
+                accumulate: false,
+            },
+            true,
+        ),
+        AArch64Inst::Ushr(operand) => compile_vec_shift_imm(
+            basic_block,
+            operand,
+            ShiftImmOp::Right {
+                rounding: false,
+                accumulate: false,
+            },
+            false,
+        ),
+        AArch64Inst::Ssra(operand) => compile_vec_shift_imm(
+            basic_block,
+            operand,
+            ShiftImmOp::Right {
+                rounding: false,
+                accumulate: true,
+            },
+            true,
+        ),
+        AArch64Inst::Usra(operand) => compile_vec_shift_imm(
+            basic_block,
+            operand,
+            ShiftImmOp::Right {
+                rounding: false,
+                accumulate: true,
+            },
+            false,
+        ),
+        AArch64Inst::Srshr(operand) => compile_vec_shift_imm(
+            basic_block,
+            operand,
+            ShiftImmOp::Right {
+                rounding: true,
+                accumulate: false,
+            },
+            true,
+        ),
+        AArch64Inst::Urshr(operand) => compile_vec_shift_imm(
+            basic_block,
+            operand,
+            ShiftImmOp::Right {
+                rounding: true,
+                accumulate: false,
+            },
+            false,
+        ),
+        AArch64Inst::Srsra(operand) => compile_vec_shift_imm(
+            basic_block,
+            operand,
+            ShiftImmOp::Right {
+                rounding: true,
+                accumulate: true,
+            },
+            true,
+        ),
+        AArch64Inst::Ursra(operand) => compile_vec_shift_imm(
+            basic_block,
+            operand,
+            ShiftImmOp::Right {
+                rounding: true,
+                accumulate: true,
+            },
+            false,
+        ),
+        AArch64Inst::Shl(operand) => compile_vec_shift_imm(
+            basic_block,
+            operand,
+            ShiftImmOp::Left(Narrowing::Truncate),
+            false,
+        ),
+        AArch64Inst::SqshlImm(operand) => compile_vec_shift_imm(
+            basic_block,
+            operand,
+            ShiftImmOp::Left(Narrowing::Signed),
+            true,
+        ),
+        AArch64Inst::UqshlImm(operand) => compile_vec_shift_imm(
+            basic_block,
+            operand,
+            ShiftImmOp::Left(Narrowing::Unsigned),
+            false,
+        ),
+        AArch64Inst::Sqshlu(operand) => compile_vec_shift_imm(
+            basic_block,
+            operand,
+            ShiftImmOp::Left(Narrowing::Unsigned),
+            true,
+        ),
+        AArch64Inst::Sri(operand) => {
+            compile_vec_shift_imm(basic_block, operand, ShiftImmOp::RightInsert, false)
+        }
+        AArch64Inst::Sli(operand) => {
+            compile_vec_shift_imm(basic_block, operand, ShiftImmOp::LeftInsert, false)
+        }
+        AArch64Inst::Shrn(operand) => {
+            let narrowing = Narrowing::Truncate;
+            compile_vec_shift_right_narrow(basic_block, operand, false, false, narrowing)
+        }
+        AArch64Inst::Rshrn(operand) => {
+            let narrowing = Narrowing::Truncate;
+            compile_vec_shift_right_narrow(basic_block, operand, true, false, narrowing)
+        }
+        AArch64Inst::Sqshrn(operand) => {
+            let narrowing = Narrowing::Signed;
+            compile_vec_shift_right_narrow(basic_block, operand, false, true, narrowing)
+        }
+        AArch64Inst::Sqrshrn(operand) => {
+            let narrowing = Narrowing::Signed;
+            compile_vec_shift_right_narrow(basic_block, operand, true, true, narrowing)
+        }
+        AArch64Inst::Uqshrn(operand) => {
+            let narrowing = Narrowing::Unsigned;
+            compile_vec_shift_right_narrow(basic_block, operand, false, false, narrowing)
+        }
+        AArch64Inst::Uqrshrn(operand) => {
+            let narrowing = Narrowing::Unsigned;
+            compile_vec_shift_right_narrow(basic_block, operand, true, false, narrowing)
+        }
+        AArch64Inst::Sqshrun(operand) => {
+            let narrowing = Narrowing::Unsigned;
+            compile_vec_shift_right_narrow(basic_block, operand, false, true, narrowing)
+        }
+        AArch64Inst::Sqrshrun(operand) => {
+            let narrowing = Narrowing::Unsigned;
+            compile_vec_shift_right_narrow(basic_block, operand, true, true, narrowing)
+        }
+        AArch64Inst::Sshll(operand) => compile_vec_shift_left_long(basic_block, operand, true),
+        AArch64Inst::Ushll(operand) => compile_vec_shift_left_long(basic_block, operand, false),
+        AArch64Inst::Abs(operand) => {
+            compile_vec_unary(basic_block, operand, VecUnaryOp::Abs(Narrowing::Truncate))
+        }
+        AArch64Inst::Sqabs(operand) => {
+            compile_vec_unary(basic_block, operand, VecUnaryOp::Abs(Narrowing::Signed))
+        }
+        AArch64Inst::NegVec(operand) => {
+            compile_vec_unary(basic_block, operand, VecUnaryOp::Neg(Narrowing::Truncate))
+        }
+        AArch64Inst::Sqneg(operand) => {
+            compile_vec_unary(basic_block, operand, VecUnaryOp::Neg(Narrowing::Signed))
+        }
+        AArch64Inst::ClsVec(operand) => compile_vec_unary(basic_block, operand, VecUnaryOp::Cls),
+        AArch64Inst::ClzVec(operand) => compile_vec_unary(basic_block, operand, VecUnaryOp::Clz),
+        AArch64Inst::CmgtZero(operand) => {
+            compile_vec_compare_zero(basic_block, operand, VecIntOp::CompareGt, false)
+        }
+        AArch64Inst::CmgeZero(operand) => {
+            compile_vec_compare_zero(basic_block, operand, VecIntOp::CompareGe, false)
+        }
+        AArch64Inst::CmeqZero(operand) => {
+            compile_vec_compare_zero(basic_block, operand, VecIntOp::CompareEq, false)
+        }
+        AArch64Inst::CmltZero(operand) => {
+            compile_vec_compare_zero(basic_block, operand, VecIntOp::CompareGt, true)
+        }
+        AArch64Inst::CmleZero(operand) => {
+            compile_vec_compare_zero(basic_block, operand, VecIntOp::CompareGe, true)
+        }
+        AArch64Inst::Rev16Vec(operand) => compile_vec_rev(basic_block, operand, 16),
+        AArch64Inst::Rev32Vec(operand) => compile_vec_rev(basic_block, operand, 32),
+        AArch64Inst::Rev64(operand) => compile_vec_rev(basic_block, operand, 64),
+        AArch64Inst::Cnt(operand) => compile_cnt(basic_block, operand),
+        AArch64Inst::RbitVec(operand) => compile_vec_rbit(basic_block, operand),
+        AArch64Inst::Not(operand) => compile_vec_not(basic_block, operand),
+        AArch64Inst::Saddlp(operand) => {
+            compile_vec_add_pairwise_long(basic_block, operand, true, false)
+        }
+        AArch64Inst::Uaddlp(operand) => {
+            compile_vec_add_pairwise_long(basic_block, operand, false, false)
+        }
+        AArch64Inst::Sadalp(operand) => {
+            compile_vec_add_pairwise_long(basic_block, operand, true, true)
+        }
+        AArch64Inst::Uadalp(operand) => {
+            compile_vec_add_pairwise_long(basic_block, operand, false, true)
+        }
+        AArch64Inst::Suqadd(operand) => {
+            compile_vec_saturating_accumulate(basic_block, operand, true)
+        }
+        AArch64Inst::Usqadd(operand) => {
+            compile_vec_saturating_accumulate(basic_block, operand, false)
+        }
+        AArch64Inst::XtnXtn2(operand) => {
+            compile_vec_extract_narrow(basic_block, operand, false, Narrowing::Truncate)
+        }
+        AArch64Inst::Sqxtn(operand) => {
+            compile_vec_extract_narrow(basic_block, operand, true, Narrowing::Signed)
+        }
+        AArch64Inst::Uqxtn(operand) => {
+            compile_vec_extract_narrow(basic_block, operand, false, Narrowing::Unsigned)
+        }
+        AArch64Inst::Sqxtun(operand) => {
+            compile_vec_extract_narrow(basic_block, operand, true, Narrowing::Unsigned)
+        }
+        AArch64Inst::Shll(operand) => compile_shll(basic_block, operand),
+        AArch64Inst::Addv(operand) => {
+            compile_vec_reduce(basic_block, operand, ReduceOp::Add, false)
+        }
+        AArch64Inst::Saddlv(operand) => {
+            compile_vec_reduce(basic_block, operand, ReduceOp::AddLong, true)
+        }
+        AArch64Inst::Uaddlv(operand) => {
+            compile_vec_reduce(basic_block, operand, ReduceOp::AddLong, false)
+        }
+        AArch64Inst::Smaxv(operand) => {
+            compile_vec_reduce(basic_block, operand, ReduceOp::Max, true)
+        }
+        AArch64Inst::Umaxv(operand) => {
+            compile_vec_reduce(basic_block, operand, ReduceOp::Max, false)
+        }
+        AArch64Inst::Sminv(operand) => {
+            compile_vec_reduce(basic_block, operand, ReduceOp::Min, true)
+        }
+        AArch64Inst::Uminv(operand) => {
+            compile_vec_reduce(basic_block, operand, ReduceOp::Min, false)
+        }
+        AArch64Inst::AddpScalar(operand) => compile_addp_scalar(basic_block, operand),
+        AArch64Inst::SqaddScalar(operand) => {
+            compile_scalar_int(basic_block, operand, VecIntOp::SaturatingAdd, true)
+        }
+        AArch64Inst::UqaddScalar(operand) => {
+            compile_scalar_int(basic_block, operand, VecIntOp::SaturatingAdd, false)
+        }
+        AArch64Inst::SqsubScalar(operand) => {
+            compile_scalar_int(basic_block, operand, VecIntOp::SaturatingSub, true)
+        }
+        AArch64Inst::UqsubScalar(operand) => {
+            compile_scalar_int(basic_block, operand, VecIntOp::SaturatingSub, false)
+        }
+        AArch64Inst::CmgtRegScalar(operand) => {
+            compile_scalar_int(basic_block, operand, VecIntOp::CompareGt, true)
+        }
+        AArch64Inst::CmhiRegScalar(operand) => {
+            compile_scalar_int(basic_block, operand, VecIntOp::CompareGt, false)
+        }
+        AArch64Inst::CmgeRegScalar(operand) => {
+            compile_scalar_int(basic_block, operand, VecIntOp::CompareGe, true)
+        }
+        AArch64Inst::CmhsRegScalar(operand) => {
+            compile_scalar_int(basic_block, operand, VecIntOp::CompareGe, false)
+        }
+        AArch64Inst::CmeqRegScalar(operand) => {
+            compile_scalar_int(basic_block, operand, VecIntOp::CompareEq, false)
+        }
+        AArch64Inst::CmtstScalar(operand) => {
+            compile_scalar_int(basic_block, operand, VecIntOp::Test, false)
+        }
+        AArch64Inst::AddScalar(operand) => {
+            compile_scalar_int(basic_block, operand, VecIntOp::Add, false)
+        }
+        AArch64Inst::SubScalar(operand) => {
+            compile_scalar_int(basic_block, operand, VecIntOp::Sub, false)
+        }
+        AArch64Inst::SshlScalar(operand) => {
+            let op = VecIntOp::Shift {
+                rounding: false,
+                saturating: false,
+            };
+            compile_scalar_int(basic_block, operand, op, true)
+        }
+        AArch64Inst::UshlScalar(operand) => {
+            let op = VecIntOp::Shift {
+                rounding: false,
+                saturating: false,
+            };
+            compile_scalar_int(basic_block, operand, op, false)
+        }
+        AArch64Inst::SqshlRegScalar(operand) => {
+            let op = VecIntOp::Shift {
+                rounding: false,
+                saturating: true,
+            };
+            compile_scalar_int(basic_block, operand, op, true)
+        }
+        AArch64Inst::UqshlRegScalar(operand) => {
+            let op = VecIntOp::Shift {
+                rounding: false,
+                saturating: true,
+            };
+            compile_scalar_int(basic_block, operand, op, false)
+        }
+        AArch64Inst::SrshlScalar(operand) => {
+            let op = VecIntOp::Shift {
+                rounding: true,
+                saturating: false,
+            };
+            compile_scalar_int(basic_block, operand, op, true)
+        }
+        AArch64Inst::UrshlScalar(operand) => {
+            let op = VecIntOp::Shift {
+                rounding: true,
+                saturating: false,
+            };
+            compile_scalar_int(basic_block, operand, op, false)
+        }
+        AArch64Inst::SqrshlScalar(operand) => {
+            let op = VecIntOp::Shift {
+                rounding: true,
+                saturating: true,
+            };
+            compile_scalar_int(basic_block, operand, op, true)
+        }
+        AArch64Inst::UqrshlScalar(operand) => {
+            let op = VecIntOp::Shift {
+                rounding: true,
+                saturating: true,
+            };
+            compile_scalar_int(basic_block, operand, op, false)
+        }
+        AArch64Inst::SqdmulhScalar(operand) => {
+            let op = VecIntOp::DoublingMulHigh { rounding: false };
+            compile_scalar_int(basic_block, operand, op, true)
+        }
+        AArch64Inst::SqrdmulhScalar(operand) => {
+            let op = VecIntOp::DoublingMulHigh { rounding: true };
+            compile_scalar_int(basic_block, operand, op, true)
+        }
+        AArch64Inst::FmulxScalar(operand) => compile_scalar_float(
+            basic_block,
+            operand,
+            VecFloatOp::Float(FloatOp::MulExtended),
+        ),
+        AArch64Inst::FabdScalar(operand) => {
+            compile_scalar_float(basic_block, operand, VecFloatOp::AbsDiff)
+        }
+        AArch64Inst::FrecpsScalar(operand) => {
+            compile_scalar_float(basic_block, operand, VecFloatOp::Step(FloatOp::RecipStep))
+        }
+        AArch64Inst::FrsqrtsScalar(operand) => {
+            compile_scalar_float(basic_block, operand, VecFloatOp::Step(FloatOp::RSqrtStep))
+        }
+        AArch64Inst::FcmeqRegScalar(operand) => {
+            let op = VecFloatOp::Compare {
+                cond: FloatCond::Eq,
+                absolute: false,
+            };
+            compile_scalar_float(basic_block, operand, op)
+        }
+        AArch64Inst::FcmgeRegScalar(operand) => {
+            let op = VecFloatOp::Compare {
+                cond: FloatCond::Ge,
+                absolute: false,
+            };
+            compile_scalar_float(basic_block, operand, op)
+        }
+        AArch64Inst::FcmgtRegScalar(operand) => {
+            let op = VecFloatOp::Compare {
+                cond: FloatCond::Gt,
+                absolute: false,
+            };
+            compile_scalar_float(basic_block, operand, op)
+        }
+        AArch64Inst::FacgeScalar(operand) => {
+            let op = VecFloatOp::Compare {
+                cond: FloatCond::Ge,
+                absolute: true,
+            };
+            compile_scalar_float(basic_block, operand, op)
+        }
+        AArch64Inst::FacgtScalar(operand) => {
+            let op = VecFloatOp::Compare {
+                cond: FloatCond::Gt,
+                absolute: true,
+            };
+            compile_scalar_float(basic_block, operand, op)
+        }
+        AArch64Inst::SuqaddScalar(operand) => {
+            let op = ScalarUnaryOp::SaturatingAccumulate { signed: true };
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::UsqaddScalar(operand) => {
+            let op = ScalarUnaryOp::SaturatingAccumulate { signed: false };
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::AbsScalar(operand) => {
+            let op = ScalarUnaryOp::Int(VecUnaryOp::Abs(Narrowing::Truncate));
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::SqabsScalar(operand) => {
+            let op = ScalarUnaryOp::Int(VecUnaryOp::Abs(Narrowing::Signed));
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::NegScalar(operand) => {
+            let op = ScalarUnaryOp::Int(VecUnaryOp::Neg(Narrowing::Truncate));
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::SqnegScalar(operand) => {
+            let op = ScalarUnaryOp::Int(VecUnaryOp::Neg(Narrowing::Signed));
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::CmgtZeroScalar(operand) => {
+            let op = ScalarUnaryOp::CompareZero {
+                op: VecIntOp::CompareGt,
+                swap: false,
+            };
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::CmgeZeroScalar(operand) => {
+            let op = ScalarUnaryOp::CompareZero {
+                op: VecIntOp::CompareGe,
+                swap: false,
+            };
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::CmeqZeroScalar(operand) => {
+            let op = ScalarUnaryOp::CompareZero {
+                op: VecIntOp::CompareEq,
+                swap: false,
+            };
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::CmltZeroScalar(operand) => {
+            let op = ScalarUnaryOp::CompareZero {
+                op: VecIntOp::CompareGt,
+                swap: true,
+            };
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::CmleZeroScalar(operand) => {
+            let op = ScalarUnaryOp::CompareZero {
+                op: VecIntOp::CompareGe,
+                swap: true,
+            };
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::SqxtnScalar(operand) => {
+            let op = ScalarUnaryOp::ExtractNarrow {
+                signed: true,
+                narrowing: Narrowing::Signed,
+            };
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::UqxtnScalar(operand) => {
+            let op = ScalarUnaryOp::ExtractNarrow {
+                signed: false,
+                narrowing: Narrowing::Unsigned,
+            };
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::SqxtunScalar(operand) => {
+            let op = ScalarUnaryOp::ExtractNarrow {
+                signed: true,
+                narrowing: Narrowing::Unsigned,
+            };
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::FrecpeScalar(operand) => {
+            let op = ScalarUnaryOp::Float(FloatOp::RecipEstimate);
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::FrsqrteScalar(operand) => {
+            let op = ScalarUnaryOp::Float(FloatOp::RSqrtEstimate);
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::ScvtfScalarInt(operand) => {
+            let op = ScalarUnaryOp::Float(FloatOp::FromInt {
+                signed: true,
+                fbits: 0,
+            });
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::UcvtfScalarInt(operand) => {
+            let op = ScalarUnaryOp::Float(FloatOp::FromInt {
+                signed: false,
+                fbits: 0,
+            });
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::FcmgtZeroScalar(operand) => {
+            let op = ScalarUnaryOp::FloatCompareZero {
+                cond: FloatCond::Gt,
+                swap: false,
+            };
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::FcmgeZeroScalar(operand) => {
+            let op = ScalarUnaryOp::FloatCompareZero {
+                cond: FloatCond::Ge,
+                swap: false,
+            };
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::FcmeqZeroScalar(operand) => {
+            let op = ScalarUnaryOp::FloatCompareZero {
+                cond: FloatCond::Eq,
+                swap: false,
+            };
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::FcmltZeroScalar(operand) => {
+            let op = ScalarUnaryOp::FloatCompareZero {
+                cond: FloatCond::Gt,
+                swap: true,
+            };
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::FcmleZeroScalar(operand) => {
+            let op = ScalarUnaryOp::FloatCompareZero {
+                cond: FloatCond::Ge,
+                swap: true,
+            };
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::FcvtxnScalar(operand) => {
+            let op = ScalarUnaryOp::ConvertToOdd;
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::Frecpx(operand) => {
+            let op = ScalarUnaryOp::RecipExponent;
+            compile_scalar_two_misc(basic_block, operand, op)
+        }
+        AArch64Inst::FcvtnsScalar(operand) => {
+            compile_scalar_fcvt_int(basic_block, operand, true, FloatRounding::TiesToEven)
+        }
+        AArch64Inst::FcvtnuScalar(operand) => {
+            compile_scalar_fcvt_int(basic_block, operand, false, FloatRounding::TiesToEven)
+        }
+        AArch64Inst::FcvtpsScalar(operand) => {
+            compile_scalar_fcvt_int(basic_block, operand, true, FloatRounding::TowardPositive)
+        }
+        AArch64Inst::FcvtpuScalar(operand) => {
+            compile_scalar_fcvt_int(basic_block, operand, false, FloatRounding::TowardPositive)
+        }
+        AArch64Inst::FcvtmsScalar(operand) => {
+            compile_scalar_fcvt_int(basic_block, operand, true, FloatRounding::TowardNegative)
+        }
+        AArch64Inst::FcvtmuScalar(operand) => {
+            compile_scalar_fcvt_int(basic_block, operand, false, FloatRounding::TowardNegative)
+        }
+        AArch64Inst::FcvtzsScalarInt(operand) => {
+            compile_scalar_fcvt_int(basic_block, operand, true, FloatRounding::TowardZero)
+        }
+        AArch64Inst::FcvtzuScalarInt(operand) => {
+            compile_scalar_fcvt_int(basic_block, operand, false, FloatRounding::TowardZero)
+        }
+        AArch64Inst::FcvtasScalar(operand) => {
+            compile_scalar_fcvt_int(basic_block, operand, true, FloatRounding::TiesToAway)
+        }
+        AArch64Inst::FcvtauScalar(operand) => {
+            compile_scalar_fcvt_int(basic_block, operand, false, FloatRounding::TiesToAway)
+        }
+        AArch64Inst::FaddVec(operand) => {
+            compile_vec_float(basic_block, operand, VecFloatOp::Float(FloatOp::Add))
+        }
+        AArch64Inst::FsubVec(operand) => {
+            compile_vec_float(basic_block, operand, VecFloatOp::Float(FloatOp::Sub))
+        }
+        AArch64Inst::FmulVec(operand) => {
+            compile_vec_float(basic_block, operand, VecFloatOp::Float(FloatOp::Mul))
+        }
+        AArch64Inst::FdivVec(operand) => {
+            compile_vec_float(basic_block, operand, VecFloatOp::Float(FloatOp::Div))
+        }
+        AArch64Inst::Fmulx(operand) => compile_vec_float(
+            basic_block,
+            operand,
+            VecFloatOp::Float(FloatOp::MulExtended),
+        ),
+        AArch64Inst::FmaxVec(operand) => {
+            compile_vec_float(basic_block, operand, VecFloatOp::Float(FloatOp::Max))
+        }
+        AArch64Inst::FminVec(operand) => {
+            compile_vec_float(basic_block, operand, VecFloatOp::Float(FloatOp::Min))
+        }
+        AArch64Inst::FmaxnmVec(operand) => {
+            compile_vec_float(basic_block, operand, VecFloatOp::Float(FloatOp::MaxNum))
+        }
+        AArch64Inst::FminnmVec(operand) => {
+            compile_vec_float(basic_block, operand, VecFloatOp::Float(FloatOp::MinNum))
+        }
+        AArch64Inst::Fabd(operand) => compile_vec_float(basic_block, operand, VecFloatOp::AbsDiff),
+        AArch64Inst::Frecps(operand) => {
+            compile_vec_float(basic_block, operand, VecFloatOp::Step(FloatOp::RecipStep))
+        }
+        AArch64Inst::Frsqrts(operand) => {
+            compile_vec_float(basic_block, operand, VecFloatOp::Step(FloatOp::RSqrtStep))
+        }
+        AArch64Inst::FmlaVec(operand) => {
+            compile_vec_float(basic_block, operand, VecFloatOp::MulAdd { negate: false })
+        }
+        AArch64Inst::FmlsVec(operand) => {
+            compile_vec_float(basic_block, operand, VecFloatOp::MulAdd { negate: true })
+        }
+        AArch64Inst::FcmeqReg(operand) => {
+            let op = VecFloatOp::Compare {
+                cond: FloatCond::Eq,
+                absolute: false,
+            };
+            compile_vec_float(basic_block, operand, op)
+        }
+        AArch64Inst::FcmgeReg(operand) => {
+            let op = VecFloatOp::Compare {
+                cond: FloatCond::Ge,
+                absolute: false,
+            };
+            compile_vec_float(basic_block, operand, op)
+        }
+        AArch64Inst::FcmgtReg(operand) => {
+            let op = VecFloatOp::Compare {
+                cond: FloatCond::Gt,
+                absolute: false,
+            };
+            compile_vec_float(basic_block, operand, op)
+        }
+        AArch64Inst::Facge(operand) => {
+            let op = VecFloatOp::Compare {
+                cond: FloatCond::Ge,
+                absolute: true,
+            };
+            compile_vec_float(basic_block, operand, op)
+        }
+        AArch64Inst::Facgt(operand) => {
+            let op = VecFloatOp::Compare {
+                cond: FloatCond::Gt,
+                absolute: true,
+            };
+            compile_vec_float(basic_block, operand, op)
+        }
+        AArch64Inst::FaddpVec(operand) => {
+            compile_vec_float_pairwise(basic_block, operand, FloatOp::Add)
+        }
+        AArch64Inst::FmaxpVec(operand) => {
+            compile_vec_float_pairwise(basic_block, operand, FloatOp::Max)
+        }
+        AArch64Inst::FminpVec(operand) => {
+            compile_vec_float_pairwise(basic_block, operand, FloatOp::Min)
+        }
+        AArch64Inst::FmaxnmpVec(operand) => {
+            compile_vec_float_pairwise(basic_block, operand, FloatOp::MaxNum)
+        }
+        AArch64Inst::FminnmpVec(operand) => {
+            compile_vec_float_pairwise(basic_block, operand, FloatOp::MinNum)
+        }
+        AArch64Inst::FaddpScalarEncoding(operand) => {
+            compile_float_pairwise_scalar(basic_block, operand, FloatOp::Add)
+        }
+        AArch64Inst::FmaxpScalarEncoding(operand) => {
+            compile_float_pairwise_scalar(basic_block, operand, FloatOp::Max)
+        }
+        AArch64Inst::FminpScalarEncoding(operand) => {
+            compile_float_pairwise_scalar(basic_block, operand, FloatOp::Min)
+        }
+        AArch64Inst::FmaxnmpScalarEncoding(operand) => {
+            compile_float_pairwise_scalar(basic_block, operand, FloatOp::MaxNum)
+        }
+        AArch64Inst::FminnmpScalarEncoding(operand) => {
+            compile_float_pairwise_scalar(basic_block, operand, FloatOp::MinNum)
+        }
+        AArch64Inst::FmaxvEncoding(operand) => {
+            compile_float_reduce(basic_block, operand, FloatOp::Max)
+        }
+        AArch64Inst::FminvEncoding(operand) => {
+            compile_float_reduce(basic_block, operand, FloatOp::Min)
+        }
+        AArch64Inst::FmaxnvmEncoding(operand) => {
+            compile_float_reduce(basic_block, operand, FloatOp::MaxNum)
+        }
+        AArch64Inst::FminnmvEncoding(operand) => {
+            compile_float_reduce(basic_block, operand, FloatOp::MinNum)
+        }
+        AArch64Inst::FabsVec(operand) => {
+            compile_vec_float_sign(basic_block, operand, FloatSignOp::Abs)
+        }
+        AArch64Inst::FnegVec(operand) => {
+            compile_vec_float_sign(basic_block, operand, FloatSignOp::Neg)
+        }
+        AArch64Inst::FsqrtVec(operand) => {
+            compile_vec_float_unary(basic_block, operand, FloatOp::Sqrt)
+        }
+        AArch64Inst::Frecpe(operand) => {
+            compile_vec_float_unary(basic_block, operand, FloatOp::RecipEstimate)
+        }
+        AArch64Inst::Frsqrte(operand) => {
+            compile_vec_float_unary(basic_block, operand, FloatOp::RSqrtEstimate)
+        }
+        AArch64Inst::Urecpe(operand) => compile_vec_unsigned_estimate(basic_block, operand, false),
+        AArch64Inst::Ursqrte(operand) => compile_vec_unsigned_estimate(basic_block, operand, true),
+        AArch64Inst::FrintnVec(operand) => {
+            compile_vec_frint(basic_block, operand, Some(FloatRounding::TiesToEven))
+        }
+        AArch64Inst::FrintpVec(operand) => {
+            compile_vec_frint(basic_block, operand, Some(FloatRounding::TowardPositive))
+        }
+        AArch64Inst::FrintmVec(operand) => {
+            compile_vec_frint(basic_block, operand, Some(FloatRounding::TowardNegative))
+        }
+        AArch64Inst::FrintzVec(operand) => {
+            compile_vec_frint(basic_block, operand, Some(FloatRounding::TowardZero))
+        }
+        AArch64Inst::FrintaVec(operand) => {
+            compile_vec_frint(basic_block, operand, Some(FloatRounding::TiesToAway))
+        }
+        AArch64Inst::FrintiVec(operand) => compile_vec_frint(basic_block, operand, None),
+        AArch64Inst::FrintxVec(operand) => {
+            let op = FloatOp::RoundInt {
+                rounding: None,
+                exact: true,
+            };
+            compile_vec_float_unary(basic_block, operand, op)
+        }
+        AArch64Inst::FcvtnsVec(operand) => {
+            compile_vec_fcvt_int(basic_block, operand, true, FloatRounding::TiesToEven)
+        }
+        AArch64Inst::FcvtnuVec(operand) => {
+            compile_vec_fcvt_int(basic_block, operand, false, FloatRounding::TiesToEven)
+        }
+        AArch64Inst::FcvtpsVec(operand) => {
+            compile_vec_fcvt_int(basic_block, operand, true, FloatRounding::TowardPositive)
+        }
+        AArch64Inst::FcvtpuVec(operand) => {
+            compile_vec_fcvt_int(basic_block, operand, false, FloatRounding::TowardPositive)
+        }
+        AArch64Inst::FcvtmsVec(operand) => {
+            compile_vec_fcvt_int(basic_block, operand, true, FloatRounding::TowardNegative)
+        }
+        AArch64Inst::FcvtmuVec(operand) => {
+            compile_vec_fcvt_int(basic_block, operand, false, FloatRounding::TowardNegative)
+        }
+        AArch64Inst::FcvtzsVecInt(operand) => {
+            compile_vec_fcvt_int(basic_block, operand, true, FloatRounding::TowardZero)
+        }
+        AArch64Inst::FcvtzuVecInt(operand) => {
+            compile_vec_fcvt_int(basic_block, operand, false, FloatRounding::TowardZero)
+        }
+        AArch64Inst::FcvtasVec(operand) => {
+            compile_vec_fcvt_int(basic_block, operand, true, FloatRounding::TiesToAway)
+        }
+        AArch64Inst::FcvtauVec(operand) => {
+            compile_vec_fcvt_int(basic_block, operand, false, FloatRounding::TiesToAway)
+        }
+        AArch64Inst::ScvtfVecInt(operand) => {
+            let op = FloatOp::FromInt {
+                signed: true,
+                fbits: 0,
+            };
+            compile_vec_float_unary(basic_block, operand, op)
+        }
+        AArch64Inst::UcvtfVecInt(operand) => {
+            let op = FloatOp::FromInt {
+                signed: false,
+                fbits: 0,
+            };
+            compile_vec_float_unary(basic_block, operand, op)
+        }
+        AArch64Inst::Fcvtn(operand) => compile_vec_fcvt_narrow(basic_block, operand, None),
+        AArch64Inst::Fcvtxn(operand) => {
+            compile_vec_fcvt_narrow(basic_block, operand, Some(FloatRounding::ToOdd))
+        }
+        AArch64Inst::Fcvtl(operand) => compile_vec_fcvt_long(basic_block, operand),
+        AArch64Inst::FcmgtZero(operand) => {
+            compile_vec_float_compare_zero(basic_block, operand, FloatCond::Gt, false)
+        }
+        AArch64Inst::FcmgeZero(operand) => {
+            compile_vec_float_compare_zero(basic_block, operand, FloatCond::Ge, false)
+        }
+        AArch64Inst::FcmeqZero(operand) => {
+            compile_vec_float_compare_zero(basic_block, operand, FloatCond::Eq, false)
+        }
+        AArch64Inst::FcmltZero(operand) => {
+            compile_vec_float_compare_zero(basic_block, operand, FloatCond::Gt, true)
+        }
+        AArch64Inst::FcmleZero(operand) => {
+            compile_vec_float_compare_zero(basic_block, operand, FloatCond::Ge, true)
+        }
+        AArch64Inst::ScvtfVecFixedPt(operand) => {
+            compile_vec_fcvt_fixed(basic_block, operand, true, false)
+        }
+        AArch64Inst::UcvtfVecFixedPt(operand) => {
+            compile_vec_fcvt_fixed(basic_block, operand, false, false)
+        }
+        AArch64Inst::FcvtzsVecFixedPt(operand) => {
+            compile_vec_fcvt_fixed(basic_block, operand, true, true)
+        }
+        AArch64Inst::FcvtzuVecFixedPt(operand) => {
+            compile_vec_fcvt_fixed(basic_block, operand, false, true)
+        }
+        AArch64Inst::MulByElem(operand) => {
+            compile_int_by_elem(basic_block, operand.into(), VecIntOp::Mul)
+        }
+        AArch64Inst::MlaByElem(operand) => {
+            compile_int_by_elem(basic_block, operand.into(), VecIntOp::Mla)
+        }
+        AArch64Inst::MlsByElem(operand) => {
+            compile_int_by_elem(basic_block, operand.into(), VecIntOp::Mls)
+        }
+        AArch64Inst::SqdmulhByElem(operand) => {
+            let op = VecIntOp::DoublingMulHigh { rounding: false };
+            compile_int_by_elem(basic_block, operand.into(), op)
+        }
+        AArch64Inst::SqrdmulhByElem(operand) => {
+            let op = VecIntOp::DoublingMulHigh { rounding: true };
+            compile_int_by_elem(basic_block, operand.into(), op)
+        }
+        AArch64Inst::SqdmulhByElemScalar(operand) => {
+            let op = VecIntOp::DoublingMulHigh { rounding: false };
+            compile_int_by_elem(basic_block, operand.into(), op)
+        }
+        AArch64Inst::SqrdmulhByElemScalar(operand) => {
+            let op = VecIntOp::DoublingMulHigh { rounding: true };
+            compile_int_by_elem(basic_block, operand.into(), op)
+        }
+        AArch64Inst::SmullByElem(operand) => {
+            compile_mul_long_by_elem(basic_block, operand.into(), true, false, Accumulate::None)
+        }
+        AArch64Inst::UmullByElem(operand) => {
+            compile_mul_long_by_elem(basic_block, operand.into(), false, false, Accumulate::None)
+        }
+        AArch64Inst::SmlalByElem(operand) => {
+            compile_mul_long_by_elem(basic_block, operand.into(), true, false, Accumulate::Add)
+        }
+        AArch64Inst::UmlalByElem(operand) => {
+            compile_mul_long_by_elem(basic_block, operand.into(), false, false, Accumulate::Add)
+        }
+        AArch64Inst::SmlslByElem(operand) => {
+            compile_mul_long_by_elem(basic_block, operand.into(), true, false, Accumulate::Sub)
+        }
+        AArch64Inst::UmlslByElem(operand) => {
+            compile_mul_long_by_elem(basic_block, operand.into(), false, false, Accumulate::Sub)
+        }
+        AArch64Inst::SqdmullByElem(operand) => {
+            compile_mul_long_by_elem(basic_block, operand.into(), true, true, Accumulate::None)
+        }
+        AArch64Inst::SqdmlalByElem(operand) => {
+            compile_mul_long_by_elem(basic_block, operand.into(), true, true, Accumulate::Add)
+        }
+        AArch64Inst::SqdmlslByElem(operand) => {
+            compile_mul_long_by_elem(basic_block, operand.into(), true, true, Accumulate::Sub)
+        }
+        AArch64Inst::SqdmullByElemScalar(operand) => {
+            compile_mul_long_by_elem(basic_block, operand.into(), true, true, Accumulate::None)
+        }
+        AArch64Inst::SqdmlalByElemScalar(operand) => {
+            compile_mul_long_by_elem(basic_block, operand.into(), true, true, Accumulate::Add)
+        }
+        AArch64Inst::SqdmlslByElemScalar(operand) => {
+            compile_mul_long_by_elem(basic_block, operand.into(), true, true, Accumulate::Sub)
+        }
+        AArch64Inst::FmulByElemEncoding(operand) => {
+            let op = VecFloatOp::Float(FloatOp::Mul);
+            compile_float_by_elem(basic_block, operand.into(), op)
+        }
+        AArch64Inst::FmulxByElemEncoding(operand) => {
+            let op = VecFloatOp::Float(FloatOp::MulExtended);
+            compile_float_by_elem(basic_block, operand.into(), op)
+        }
+        AArch64Inst::FmlaByElemEncoding(operand) => {
+            let op = VecFloatOp::MulAdd { negate: false };
+            compile_float_by_elem(basic_block, operand.into(), op)
+        }
+        AArch64Inst::FmlsByElemEncoding(operand) => {
+            let op = VecFloatOp::MulAdd { negate: true };
+            compile_float_by_elem(basic_block, operand.into(), op)
+        }
+        AArch64Inst::FmulByElemScalarEncoding(operand) => {
+            let op = VecFloatOp::Float(FloatOp::Mul);
+            compile_float_by_elem(basic_block, operand.into(), op)
+        }
+        AArch64Inst::FmulxByElemScalarEncoding(operand) => {
+            let op = VecFloatOp::Float(FloatOp::MulExtended);
+            compile_float_by_elem(basic_block, operand.into(), op)
+        }
+        AArch64Inst::FmlaByElemScalarEncoding(operand) => {
+            let op = VecFloatOp::MulAdd { negate: false };
+            compile_float_by_elem(basic_block, operand.into(), op)
+        }
+        AArch64Inst::FmlsByElemScalarEncoding(operand) => {
+            let op = VecFloatOp::MulAdd { negate: true };
+            compile_float_by_elem(basic_block, operand.into(), op)
+        }
+        AArch64Inst::Tbl(operand) => compile_table_lookup(basic_block, operand, false),
+        AArch64Inst::Tbx(operand) => compile_table_lookup(basic_block, operand, true),
+        AArch64Inst::Saddl(operand) => {
+            let op = ThreeDifferentOp::Long(VecIntOp::Add);
+            compile_three_different(basic_block, operand, op, true)
+        }
+        AArch64Inst::Saddw(operand) => {
+            let op = ThreeDifferentOp::Wide(VecIntOp::Add);
+            compile_three_different(basic_block, operand, op, true)
+        }
+        AArch64Inst::Ssubl(operand) => {
+            let op = ThreeDifferentOp::Long(VecIntOp::Sub);
+            compile_three_different(basic_block, operand, op, true)
+        }
+        AArch64Inst::Ssubw(operand) => {
+            let op = ThreeDifferentOp::Wide(VecIntOp::Sub);
+            compile_three_different(basic_block, operand, op, true)
+        }
+        AArch64Inst::Addhn(operand) => {
+            let op = ThreeDifferentOp::NarrowHigh {
+                subtract: false,
+                rounding: false,
+            };
+            compile_three_different(basic_block, operand, op, false)
+        }
+        AArch64Inst::Sabal(operand) => {
+            let op = ThreeDifferentOp::Long(VecIntOp::AbsDiffAccumulate);
+            compile_three_different(basic_block, operand, op, true)
+        }
+        AArch64Inst::Subhn(operand) => {
+            let op = ThreeDifferentOp::NarrowHigh {
+                subtract: true,
+                rounding: false,
+            };
+            compile_three_different(basic_block, operand, op, false)
+        }
+        AArch64Inst::Sabdl(operand) => {
+            let op = ThreeDifferentOp::Long(VecIntOp::AbsDiff);
+            compile_three_different(basic_block, operand, op, true)
+        }
+        AArch64Inst::Smlal(operand) => {
+            let op = ThreeDifferentOp::MulLong {
+                doubling: false,
+                accumulate: Accumulate::Add,
+            };
+            compile_three_different(basic_block, operand, op, true)
+        }
+        AArch64Inst::Sqdmlal(operand) => {
+            let op = ThreeDifferentOp::MulLong {
+                doubling: true,
+                accumulate: Accumulate::Add,
+            };
+            compile_three_different(basic_block, operand, op, true)
+        }
+        AArch64Inst::Smlsl(operand) => {
+            let op = ThreeDifferentOp::MulLong {
+                doubling: false,
+                accumulate: Accumulate::Sub,
+            };
+            compile_three_different(basic_block, operand, op, true)
+        }
+        AArch64Inst::Sqdmlsl(operand) => {
+            let op = ThreeDifferentOp::MulLong {
+                doubling: true,
+                accumulate: Accumulate::Sub,
+            };
+            compile_three_different(basic_block, operand, op, true)
+        }
+        AArch64Inst::Smull(operand) => {
+            let op = ThreeDifferentOp::MulLong {
+                doubling: false,
+                accumulate: Accumulate::None,
+            };
+            compile_three_different(basic_block, operand, op, true)
+        }
+        AArch64Inst::Sqdmull(operand) => {
+            let op = ThreeDifferentOp::MulLong {
+                doubling: true,
+                accumulate: Accumulate::None,
+            };
+            compile_three_different(basic_block, operand, op, true)
+        }
+        AArch64Inst::Pmull(operand) => {
+            let op = ThreeDifferentOp::PolyMulLong;
+            compile_three_different(basic_block, operand, op, false)
+        }
+        AArch64Inst::Uaddl(operand) => {
+            let op = ThreeDifferentOp::Long(VecIntOp::Add);
+            compile_three_different(basic_block, operand, op, false)
+        }
+        AArch64Inst::Uaddw(operand) => {
+            let op = ThreeDifferentOp::Wide(VecIntOp::Add);
+            compile_three_different(basic_block, operand, op, false)
+        }
+        AArch64Inst::Usubl(operand) => {
+            let op = ThreeDifferentOp::Long(VecIntOp::Sub);
+            compile_three_different(basic_block, operand, op, false)
+        }
+        AArch64Inst::Usubw(operand) => {
+            let op = ThreeDifferentOp::Wide(VecIntOp::Sub);
+            compile_three_different(basic_block, operand, op, false)
+        }
+        AArch64Inst::Raddhn(operand) => {
+            let op = ThreeDifferentOp::NarrowHigh {
+                subtract: false,
+                rounding: true,
+            };
+            compile_three_different(basic_block, operand, op, false)
+        }
+        AArch64Inst::Uabal(operand) => {
+            let op = ThreeDifferentOp::Long(VecIntOp::AbsDiffAccumulate);
+            compile_three_different(basic_block, operand, op, false)
+        }
+        AArch64Inst::Rsubhn(operand) => {
+            let op = ThreeDifferentOp::NarrowHigh {
+                subtract: true,
+                rounding: true,
+            };
+            compile_three_different(basic_block, operand, op, false)
+        }
+        AArch64Inst::Uabdl(operand) => {
+            let op = ThreeDifferentOp::Long(VecIntOp::AbsDiff);
+            compile_three_different(basic_block, operand, op, false)
+        }
+        AArch64Inst::Umlal(operand) => {
+            let op = ThreeDifferentOp::MulLong {
+                doubling: false,
+                accumulate: Accumulate::Add,
+            };
+            compile_three_different(basic_block, operand, op, false)
+        }
+        AArch64Inst::Umlsl(operand) => {
+            let op = ThreeDifferentOp::MulLong {
+                doubling: false,
+                accumulate: Accumulate::Sub,
+            };
+            compile_three_different(basic_block, operand, op, false)
+        }
+        AArch64Inst::Umull(operand) => {
+            let op = ThreeDifferentOp::MulLong {
+                doubling: false,
+                accumulate: Accumulate::None,
+            };
+            compile_three_different(basic_block, operand, op, false)
+        }
+        AArch64Inst::Uzp1(operand) => compile_vec_permute(basic_block, operand, PermuteOp::Uzp, 0),
+        AArch64Inst::Uzp2(operand) => compile_vec_permute(basic_block, operand, PermuteOp::Uzp, 1),
+        AArch64Inst::Trn1(operand) => compile_vec_permute(basic_block, operand, PermuteOp::Trn, 0),
+        AArch64Inst::Trn2(operand) => compile_vec_permute(basic_block, operand, PermuteOp::Trn, 1),
+        AArch64Inst::Zip1(operand) => compile_vec_permute(basic_block, operand, PermuteOp::Zip, 0),
+        AArch64Inst::Zip2(operand) => compile_vec_permute(basic_block, operand, PermuteOp::Zip, 1),
+        AArch64Inst::Ext(operand) => compile_ext(basic_block, operand),
+        AArch64Inst::DupElement(operand) => compile_dup_element(basic_block, operand),
+        AArch64Inst::DupGeneral(operand) => compile_dup_general(basic_block, operand),
+        AArch64Inst::Smov(operand) => compile_mov_to_general(basic_block, operand, true),
+        AArch64Inst::Umov(operand) => compile_mov_to_general(basic_block, operand, false),
+        AArch64Inst::InsGeneral(operand) => compile_ins_general(basic_block, operand),
+        AArch64Inst::InsElement(operand) => compile_ins_element(basic_block, operand),
+        AArch64Inst::MoviShiftedImmVar32(operand) => {
+            compile_vec_imm(basic_block, operand, VecImmOp::Movi)
+        }
+        AArch64Inst::MoviShiftedImmVar16(operand) => {
+            compile_vec_imm(basic_block, operand, VecImmOp::Movi)
+        }
+        AArch64Inst::MoviShiftingOnesVar32(operand) => {
+            compile_vec_imm(basic_block, operand, VecImmOp::Movi)
+        }
+        AArch64Inst::MoviVar8(operand) => compile_vec_imm(basic_block, operand, VecImmOp::Movi),
+        AArch64Inst::MoviScalarVar64(operand) => {
+            compile_vec_imm(basic_block, operand, VecImmOp::Movi)
+        }
+        AArch64Inst::MoviVectorVar64(operand) => {
+            compile_vec_imm(basic_block, operand, VecImmOp::Movi)
+        }
+        AArch64Inst::FmovVecImmSinglePrecisionVar(operand) => {
+            compile_vec_imm(basic_block, operand, VecImmOp::Movi)
+        }
+        AArch64Inst::FmovVecImmDoublePrecisionVar(operand) => {
+            compile_vec_imm(basic_block, operand, VecImmOp::Movi)
+        }
+        AArch64Inst::MvniShiftedImmVar32(operand) => {
+            compile_vec_imm(basic_block, operand, VecImmOp::Mvni)
+        }
+        AArch64Inst::MvniShiftedImmVar16(operand) => {
+            compile_vec_imm(basic_block, operand, VecImmOp::Mvni)
+        }
+        AArch64Inst::MvniShiftingOnesVar32(operand) => {
+            compile_vec_imm(basic_block, operand, VecImmOp::Mvni)
+        }
+        AArch64Inst::OrrVecImmVar32(operand) => {
+            compile_vec_imm(basic_block, operand, VecImmOp::Orr)
+        }
+        AArch64Inst::OrrVecImmVar16(operand) => {
+            compile_vec_imm(basic_block, operand, VecImmOp::Orr)
+        }
+        AArch64Inst::BicVecImmVar32(operand) => {
+            compile_vec_imm(basic_block, operand, VecImmOp::Bic)
+        }
+        AArch64Inst::BicVecImmVar16(operand) => {
+            compile_vec_imm(basic_block, operand, VecImmOp::Bic)
+        }
+
+        // Arithmetic instructions
+        AArch64Inst::AddImm32(operand) => {
+            compile_add_sub_imm(basic_block, operand, IrType::U32, AddSubOp::Add)
+        }
+        AArch64Inst::AddImm64(operand) => {
+            compile_add_sub_imm(basic_block, operand, IrType::U64, AddSubOp::Add)
+        }
+        AArch64Inst::AddsImm32(operand) => {
+            compile_add_sub_imm(basic_block, operand, IrType::U32, AddSubOp::Adds)
+        }
+        AArch64Inst::AddsImm64(operand) => {
+            compile_add_sub_imm(basic_block, operand, IrType::U64, AddSubOp::Adds)
+        }
+        AArch64Inst::SubImm32(operand) => {
+            compile_add_sub_imm(basic_block, operand, IrType::U32, AddSubOp::Sub)
+        }
+        AArch64Inst::SubImm64(operand) => {
+            compile_add_sub_imm(basic_block, operand, IrType::U64, AddSubOp::Sub)
+        }
+        AArch64Inst::SubsImm32(operand) => {
+            compile_add_sub_imm(basic_block, operand, IrType::U32, AddSubOp::Subs)
+        }
+        AArch64Inst::SubsImm64(operand) => {
+            compile_add_sub_imm(basic_block, operand, IrType::U64, AddSubOp::Subs)
+        }
+        AArch64Inst::AddShiftedReg32(operand) => {
+            compile_add_sub_shifted_reg(basic_block, operand, IrType::U32, AddSubOp::Add)
+        }
+        AArch64Inst::AddShiftedReg64(operand) => {
+            compile_add_sub_shifted_reg(basic_block, operand, IrType::U64, AddSubOp::Add)
+        }
+        AArch64Inst::AddsShiftedReg32(operand) => {
+            compile_add_sub_shifted_reg(basic_block, operand, IrType::U32, AddSubOp::Adds)
+        }
+        AArch64Inst::AddsShiftedReg64(operand) => {
+            compile_add_sub_shifted_reg(basic_block, operand, IrType::U64, AddSubOp::Adds)
+        }
+        AArch64Inst::SubShiftedReg32(operand) => {
+            compile_add_sub_shifted_reg(basic_block, operand, IrType::U32, AddSubOp::Sub)
+        }
+        AArch64Inst::SubShiftedReg64(operand) => {
+            compile_add_sub_shifted_reg(basic_block, operand, IrType::U64, AddSubOp::Sub)
+        }
+        AArch64Inst::SubsShiftedReg32(operand) => {
+            compile_add_sub_shifted_reg(basic_block, operand, IrType::U32, AddSubOp::Subs)
+        }
+        AArch64Inst::SubsShiftedReg64(operand) => {
+            compile_add_sub_shifted_reg(basic_block, operand, IrType::U64, AddSubOp::Subs)
+        }
+        AArch64Inst::AddExtReg32(operand) => {
+            compile_add_sub_ext_reg(basic_block, operand, IrType::U32, AddSubOp::Add)
+        }
+        AArch64Inst::AddExtReg64(operand) => {
+            compile_add_sub_ext_reg(basic_block, operand, IrType::U64, AddSubOp::Add)
+        }
+        AArch64Inst::AddsExtReg32(operand) => {
+            compile_add_sub_ext_reg(basic_block, operand, IrType::U32, AddSubOp::Adds)
+        }
+        AArch64Inst::AddsExtReg64(operand) => {
+            compile_add_sub_ext_reg(basic_block, operand, IrType::U64, AddSubOp::Adds)
+        }
+        AArch64Inst::SubExtReg32(operand) => {
+            compile_add_sub_ext_reg(basic_block, operand, IrType::U32, AddSubOp::Sub)
+        }
+        AArch64Inst::SubExtReg64(operand) => {
+            compile_add_sub_ext_reg(basic_block, operand, IrType::U64, AddSubOp::Sub)
+        }
+        AArch64Inst::SubsExtReg32(operand) => {
+            compile_add_sub_ext_reg(basic_block, operand, IrType::U32, AddSubOp::Subs)
+        }
+        AArch64Inst::SubsExtReg64(operand) => {
+            compile_add_sub_ext_reg(basic_block, operand, IrType::U64, AddSubOp::Subs)
+        }
+        AArch64Inst::AdcVar32(operand) => {
+            compile_add_sub_carry(basic_block, operand, IrType::U32, AddSubOp::Add)
+        }
+        AArch64Inst::AdcVar64(operand) => {
+            compile_add_sub_carry(basic_block, operand, IrType::U64, AddSubOp::Add)
+        }
+        AArch64Inst::AdcsVar32(operand) => {
+            compile_add_sub_carry(basic_block, operand, IrType::U32, AddSubOp::Adds)
+        }
+        AArch64Inst::AdcsVar64(operand) => {
+            compile_add_sub_carry(basic_block, operand, IrType::U64, AddSubOp::Adds)
+        }
+        AArch64Inst::SbcVar32(operand) => {
+            compile_add_sub_carry(basic_block, operand, IrType::U32, AddSubOp::Sub)
+        }
+        AArch64Inst::SbcVar64(operand) => {
+            compile_add_sub_carry(basic_block, operand, IrType::U64, AddSubOp::Sub)
+        }
+        AArch64Inst::SbcsVar32(operand) => {
+            compile_add_sub_carry(basic_block, operand, IrType::U32, AddSubOp::Subs)
+        }
+        AArch64Inst::SbcsVar64(operand) => {
+            compile_add_sub_carry(basic_block, operand, IrType::U64, AddSubOp::Subs)
+        }
+        AArch64Inst::Madd32(operand) => compile_madd(basic_block, operand, IrType::U32, false),
+        AArch64Inst::Madd64(operand) => compile_madd(basic_block, operand, IrType::U64, false),
+        AArch64Inst::Msub32(operand) => compile_madd(basic_block, operand, IrType::U32, true),
+        AArch64Inst::Msub64(operand) => compile_madd(basic_block, operand, IrType::U64, true),
+        AArch64Inst::Smaddl(operand) => compile_maddl(basic_block, operand, true, false),
+        AArch64Inst::Smsubl(operand) => compile_maddl(basic_block, operand, true, true),
+        AArch64Inst::Umaddl(operand) => compile_maddl(basic_block, operand, false, false),
+        AArch64Inst::Umsubl(operand) => compile_maddl(basic_block, operand, false, true),
+        AArch64Inst::Smulh(operand) => compile_mulh(basic_block, operand, true),
+        AArch64Inst::Umulh(operand) => compile_mulh(basic_block, operand, false),
+        AArch64Inst::SdivVar32(operand) => compile_div(basic_block, operand, IrType::U32, true),
+        AArch64Inst::SdivVar64(operand) => compile_div(basic_block, operand, IrType::U64, true),
+        AArch64Inst::UdivVar32(operand) => compile_div(basic_block, operand, IrType::U32, false),
+        AArch64Inst::UdivVar64(operand) => compile_div(basic_block, operand, IrType::U64, false),
+
+        // bitwise isntructions
+        AArch64Inst::Sbfm32(operand) => {
+            compile_bitfield(basic_block, operand, IrType::U32, BitfieldOp::Sbfm)
+        }
+        AArch64Inst::Sbfm64(operand) => {
+            compile_bitfield(basic_block, operand, IrType::U64, BitfieldOp::Sbfm)
+        }
+        AArch64Inst::Bfm32(operand) => {
+            compile_bitfield(basic_block, operand, IrType::U32, BitfieldOp::Bfm)
+        }
+        AArch64Inst::Bfm64(operand) => {
+            compile_bitfield(basic_block, operand, IrType::U64, BitfieldOp::Bfm)
+        }
+        AArch64Inst::Ubfm32(operand) => {
+            compile_bitfield(basic_block, operand, IrType::U32, BitfieldOp::Ubfm)
+        }
+        AArch64Inst::Ubfm64(operand) => {
+            compile_bitfield(basic_block, operand, IrType::U64, BitfieldOp::Ubfm)
+        }
+        AArch64Inst::Extr32(operand) => compile_extr(basic_block, operand, IrType::U32),
+        AArch64Inst::Extr64(operand) => compile_extr(basic_block, operand, IrType::U64),
+        AArch64Inst::AndImm32(operand) => {
+            compile_logical_imm(basic_block, operand, IrType::U32, LogicalOp::And)
+        }
+        AArch64Inst::AndImm64(operand) => {
+            compile_logical_imm(basic_block, operand, IrType::U64, LogicalOp::And)
+        }
+        AArch64Inst::OrrImm32(operand) => {
+            compile_logical_imm(basic_block, operand, IrType::U32, LogicalOp::Orr)
+        }
+        AArch64Inst::OrrImm64(operand) => {
+            compile_logical_imm(basic_block, operand, IrType::U64, LogicalOp::Orr)
+        }
+        AArch64Inst::EorImm32(operand) => {
+            compile_logical_imm(basic_block, operand, IrType::U32, LogicalOp::Eor)
+        }
+        AArch64Inst::EorImm64(operand) => {
+            compile_logical_imm(basic_block, operand, IrType::U64, LogicalOp::Eor)
+        }
+        AArch64Inst::AndsImm32(operand) => {
+            compile_logical_imm(basic_block, operand, IrType::U32, LogicalOp::Ands)
+        }
+        AArch64Inst::AndsImm64(operand) => {
+            compile_logical_imm(basic_block, operand, IrType::U64, LogicalOp::Ands)
+        }
+        AArch64Inst::AndShiftedReg32(operand) => {
+            compile_logical_shifted_reg(basic_block, operand, IrType::U32, LogicalOp::And)
+        }
+        AArch64Inst::AndShiftedReg64(operand) => {
+            compile_logical_shifted_reg(basic_block, operand, IrType::U64, LogicalOp::And)
+        }
+        AArch64Inst::BicShiftedReg32(operand) => {
+            compile_logical_shifted_reg(basic_block, operand, IrType::U32, LogicalOp::Bic)
+        }
+        AArch64Inst::BicShiftedReg64(operand) => {
+            compile_logical_shifted_reg(basic_block, operand, IrType::U64, LogicalOp::Bic)
+        }
+        AArch64Inst::OrrShiftedReg32(operand) => {
+            compile_logical_shifted_reg(basic_block, operand, IrType::U32, LogicalOp::Orr)
+        }
+        AArch64Inst::OrrShiftedReg64(operand) => {
+            compile_logical_shifted_reg(basic_block, operand, IrType::U64, LogicalOp::Orr)
+        }
+        AArch64Inst::OrnShiftedReg32(operand) => {
+            compile_logical_shifted_reg(basic_block, operand, IrType::U32, LogicalOp::Orn)
+        }
+        AArch64Inst::OrnShiftedReg64(operand) => {
+            compile_logical_shifted_reg(basic_block, operand, IrType::U64, LogicalOp::Orn)
+        }
+        AArch64Inst::EorShiftedReg32(operand) => {
+            compile_logical_shifted_reg(basic_block, operand, IrType::U32, LogicalOp::Eor)
+        }
+        AArch64Inst::EorShiftedReg64(operand) => {
+            compile_logical_shifted_reg(basic_block, operand, IrType::U64, LogicalOp::Eor)
+        }
+        AArch64Inst::EonShiftedReg32(operand) => {
+            compile_logical_shifted_reg(basic_block, operand, IrType::U32, LogicalOp::Eon)
+        }
+        AArch64Inst::EonShiftedReg64(operand) => {
+            compile_logical_shifted_reg(basic_block, operand, IrType::U64, LogicalOp::Eon)
+        }
+        AArch64Inst::AndsShiftedReg32(operand) => {
+            compile_logical_shifted_reg(basic_block, operand, IrType::U32, LogicalOp::Ands)
+        }
+        AArch64Inst::AndsShiftedReg64(operand) => {
+            compile_logical_shifted_reg(basic_block, operand, IrType::U64, LogicalOp::Ands)
+        }
+        AArch64Inst::BicsShiftedReg32(operand) => {
+            compile_logical_shifted_reg(basic_block, operand, IrType::U32, LogicalOp::Bics)
+        }
+        AArch64Inst::BicsShiftedReg64(operand) => {
+            compile_logical_shifted_reg(basic_block, operand, IrType::U64, LogicalOp::Bics)
+        }
+
+        AArch64Inst::LslvVar32(operand) => compile_shiftv(basic_block, operand, IrType::U32, 0b00),
+        AArch64Inst::LslvVar64(operand) => compile_shiftv(basic_block, operand, IrType::U64, 0b00),
+        AArch64Inst::LsrvVar32(operand) => compile_shiftv(basic_block, operand, IrType::U32, 0b01),
+        AArch64Inst::LsrvVar64(operand) => compile_shiftv(basic_block, operand, IrType::U64, 0b01),
+        AArch64Inst::AsrvVar32(operand) => compile_shiftv(basic_block, operand, IrType::U32, 0b10),
+        AArch64Inst::AsrvVar64(operand) => compile_shiftv(basic_block, operand, IrType::U64, 0b10),
+        AArch64Inst::RorvVar32(operand) => compile_shiftv(basic_block, operand, IrType::U32, 0b11),
+        AArch64Inst::RorvVar64(operand) => compile_shiftv(basic_block, operand, IrType::U64, 0b11),
+
+        // Branch instructions
+        AArch64Inst::BlImm(operand) => compile_bl_imm(basic_block, operand),
+        AArch64Inst::BImm(operand) => compile_b_imm(basic_block, operand),
+        AArch64Inst::Br(operand) => compile_br(basic_block, operand),
+        AArch64Inst::Blr(operand) => compile_blr(basic_block, operand),
+        AArch64Inst::BCond(operand) | AArch64Inst::BcCond(operand) => {
+            compile_b_cond(basic_block, operand)
+        }
+        AArch64Inst::Cbz32(operand) => compile_cbz(basic_block, operand, IrType::U32, false),
+        AArch64Inst::Cbz64(operand) => compile_cbz(basic_block, operand, IrType::U64, false),
+        AArch64Inst::Cbnz32(operand) => compile_cbz(basic_block, operand, IrType::U32, true),
+        AArch64Inst::Cbnz64(operand) => compile_cbz(basic_block, operand, IrType::U64, true),
+        AArch64Inst::Ret(operand) => compile_ret(basic_block, operand),
+        AArch64Inst::ERet(_) => compile_eret(basic_block),
+        AArch64Inst::Tbz(operand) => compile_tbz(basic_block, operand, false),
+        AArch64Inst::Tbnz(operand) => compile_tbz(basic_block, operand, true),
+
+        // Conditional Instructions
+        AArch64Inst::CcmpImmVar32(operand) => {
+            compile_ccmp_imm(basic_block, operand, IrType::U32, true)
+        }
+        AArch64Inst::CcmpImmVar64(operand) => {
+            compile_ccmp_imm(basic_block, operand, IrType::U64, true)
+        }
+        AArch64Inst::CcmnImmVar32(operand) => {
+            compile_ccmp_imm(basic_block, operand, IrType::U32, false)
+        }
+        AArch64Inst::CcmnImmVar64(operand) => {
+            compile_ccmp_imm(basic_block, operand, IrType::U64, false)
+        }
+        AArch64Inst::CcmpRegVar32(operand) => {
+            compile_ccmp_reg(basic_block, operand, IrType::U32, true)
+        }
+        AArch64Inst::CcmpRegVar64(operand) => {
+            compile_ccmp_reg(basic_block, operand, IrType::U64, true)
+        }
+        AArch64Inst::CcmnRegVar32(operand) => {
+            compile_ccmp_reg(basic_block, operand, IrType::U32, false)
+        }
+        AArch64Inst::CcmnRegVar64(operand) => {
+            compile_ccmp_reg(basic_block, operand, IrType::U64, false)
+        }
+        AArch64Inst::Csel32(operand) => {
+            compile_csel(basic_block, operand, IrType::U32, CondSelOp::Csel)
+        }
+        AArch64Inst::Csel64(operand) => {
+            compile_csel(basic_block, operand, IrType::U64, CondSelOp::Csel)
+        }
+        AArch64Inst::Csinc32(operand) => {
+            compile_csel(basic_block, operand, IrType::U32, CondSelOp::Csinc)
+        }
+        AArch64Inst::Csinc64(operand) => {
+            compile_csel(basic_block, operand, IrType::U64, CondSelOp::Csinc)
+        }
+        AArch64Inst::Csinv32(operand) => {
+            compile_csel(basic_block, operand, IrType::U32, CondSelOp::Csinv)
+        }
+        AArch64Inst::Csinv64(operand) => {
+            compile_csel(basic_block, operand, IrType::U64, CondSelOp::Csinv)
+        }
+        AArch64Inst::Csneg32(operand) => {
+            compile_csel(basic_block, operand, IrType::U32, CondSelOp::Csneg)
+        }
+        AArch64Inst::Csneg64(operand) => {
+            compile_csel(basic_block, operand, IrType::U64, CondSelOp::Csneg)
+        }
+
+        // Flag manipulation
+        AArch64Inst::Cfinv(_) => compile_cfinv(basic_block),
+        AArch64Inst::Axflag(_) => compile_axflag(basic_block),
+        AArch64Inst::Xaflag(_) => compile_xaflag(basic_block),
+        AArch64Inst::Rmif(operand) => compile_rmif(basic_block, operand),
+        AArch64Inst::SetfVar8(operand) => compile_setf(basic_block, operand, 8),
+        AArch64Inst::SetfVar16(operand) => compile_setf(basic_block, operand, 16),
+
+        // Floating-point
+        AArch64Inst::FmAddSinglePrecision(operand) => {
+            compile_fmadd(basic_block, operand, IrType::U32, false, false)
+        }
+        AArch64Inst::FmSubSinglePrecision(operand) => {
+            compile_fmadd(basic_block, operand, IrType::U32, true, false)
+        }
+        AArch64Inst::FnmAddSinglePrecision(operand) => {
+            compile_fmadd(basic_block, operand, IrType::U32, true, true)
+        }
+        AArch64Inst::FnmSubSinglePrecision(operand) => {
+            compile_fmadd(basic_block, operand, IrType::U32, false, true)
+        }
+        AArch64Inst::FmAddDoublePrecision(operand) => {
+            compile_fmadd(basic_block, operand, IrType::U64, false, false)
+        }
+        AArch64Inst::FmSubDoublePrecision(operand) => {
+            compile_fmadd(basic_block, operand, IrType::U64, true, false)
+        }
+        AArch64Inst::FnmAddDoublePrecision(operand) => {
+            compile_fmadd(basic_block, operand, IrType::U64, true, true)
+        }
+        AArch64Inst::FnmSubDoublePrecision(operand) => {
+            compile_fmadd(basic_block, operand, IrType::U64, false, true)
+        }
+        AArch64Inst::FmAddHalfPrecision(operand) => {
+            compile_fmadd(basic_block, operand, IrType::U16, false, false)
+        }
+        AArch64Inst::FmSubHalfPrecision(operand) => {
+            compile_fmadd(basic_block, operand, IrType::U16, true, false)
+        }
+        AArch64Inst::FnmAddHalfPrecision(operand) => {
+            compile_fmadd(basic_block, operand, IrType::U16, true, true)
+        }
+        AArch64Inst::FnmSubHalfPrecision(operand) => {
+            compile_fmadd(basic_block, operand, IrType::U16, false, true)
+        }
+        AArch64Inst::FmulScalarSinglePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U32, FloatOp::Mul, false)
+        }
+        AArch64Inst::FdivScalarSinglePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U32, FloatOp::Div, false)
+        }
+        AArch64Inst::FaddScalarSinglePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U32, FloatOp::Add, false)
+        }
+        AArch64Inst::FsubScalarSinglePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U32, FloatOp::Sub, false)
+        }
+        AArch64Inst::FmaxScalarSinglePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U32, FloatOp::Max, false)
+        }
+        AArch64Inst::FminScalarSinglePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U32, FloatOp::Min, false)
+        }
+        AArch64Inst::FmaxnmScalarSinglePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U32, FloatOp::MaxNum, false)
+        }
+        AArch64Inst::FminnmScalarSinglePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U32, FloatOp::MinNum, false)
+        }
+        AArch64Inst::FnmulScalarSinglePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U32, FloatOp::Mul, true)
+        }
+        AArch64Inst::FmulScalarDoublePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U64, FloatOp::Mul, false)
+        }
+        AArch64Inst::FdivScalarDoublePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U64, FloatOp::Div, false)
+        }
+        AArch64Inst::FaddScalarDoublePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U64, FloatOp::Add, false)
+        }
+        AArch64Inst::FsubScalarDoublePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U64, FloatOp::Sub, false)
+        }
+        AArch64Inst::FmaxScalarDoublePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U64, FloatOp::Max, false)
+        }
+        AArch64Inst::FminScalarDoublePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U64, FloatOp::Min, false)
+        }
+        AArch64Inst::FmaxnmScalarDoublePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U64, FloatOp::MaxNum, false)
+        }
+        AArch64Inst::FminnmScalarDoublePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U64, FloatOp::MinNum, false)
+        }
+        AArch64Inst::FnmulScalarDoublePrecisionVar(operand) => {
+            compile_float_2src(basic_block, operand, IrType::U64, FloatOp::Mul, true)
+        }
+        AArch64Inst::FmovRegSinglePrecisionVar(operand) => {
+            compile_float_sign(basic_block, operand, IrType::U32, FloatSignOp::Mov)
+        }
+        AArch64Inst::FabsScalarSinglePrecisionVar(operand) => {
+            compile_float_sign(basic_block, operand, IrType::U32, FloatSignOp::Abs)
+        }
+        AArch64Inst::FnegScalarSinglePrecisionVar(operand) => {
+            compile_float_sign(basic_block, operand, IrType::U32, FloatSignOp::Neg)
+        }
+        AArch64Inst::FsqrtScalarSinglePrecisionVar(operand) => compile_float_1src(
+            basic_block,
+            operand,
+            FloatOp::Sqrt,
+            IrType::U32,
+            IrType::U32,
+        ),
+        AArch64Inst::FrintnScalarSinglePrecisionVar(operand) => compile_frint(
+            basic_block,
+            operand,
+            IrType::U32,
+            Some(FloatRounding::TiesToEven),
+        ),
+        AArch64Inst::FrintpScalarSinglePrecisionVar(operand) => compile_frint(
+            basic_block,
+            operand,
+            IrType::U32,
+            Some(FloatRounding::TowardPositive),
+        ),
+        AArch64Inst::FrintmScalarSinglePrecisionVar(operand) => compile_frint(
+            basic_block,
+            operand,
+            IrType::U32,
+            Some(FloatRounding::TowardNegative),
+        ),
+        AArch64Inst::FrintzScalarSinglePrecisionVar(operand) => compile_frint(
+            basic_block,
+            operand,
+            IrType::U32,
+            Some(FloatRounding::TowardZero),
+        ),
+        AArch64Inst::FrintaScalarSinglePrecisionVar(operand) => compile_frint(
+            basic_block,
+            operand,
+            IrType::U32,
+            Some(FloatRounding::TiesToAway),
+        ),
+        AArch64Inst::FrintiScalarSinglePrecisionVar(operand) => {
+            compile_frint(basic_block, operand, IrType::U32, None)
+        }
+        AArch64Inst::FrintxScalarSinglePrecisionVar(operand) => {
+            let op = FloatOp::RoundInt {
+                rounding: None,
+                exact: true,
+            };
+            compile_float_1src(basic_block, operand, op, IrType::U32, IrType::U32)
+        }
+        AArch64Inst::FmovRegDoublePrecisionVar(operand) => {
+            compile_float_sign(basic_block, operand, IrType::U64, FloatSignOp::Mov)
+        }
+        AArch64Inst::FabsScalarDoublePrecisionVar(operand) => {
+            compile_float_sign(basic_block, operand, IrType::U64, FloatSignOp::Abs)
+        }
+        AArch64Inst::FnegScalarDoublePrecisionVar(operand) => {
+            compile_float_sign(basic_block, operand, IrType::U64, FloatSignOp::Neg)
+        }
+        AArch64Inst::FsqrtScalarDoublePrecisionVar(operand) => compile_float_1src(
+            basic_block,
+            operand,
+            FloatOp::Sqrt,
+            IrType::U64,
+            IrType::U64,
+        ),
+        AArch64Inst::FrintnScalarDoublePrecisionVar(operand) => compile_frint(
+            basic_block,
+            operand,
+            IrType::U64,
+            Some(FloatRounding::TiesToEven),
+        ),
+        AArch64Inst::FrintpScalarDoublePrecisionVar(operand) => compile_frint(
+            basic_block,
+            operand,
+            IrType::U64,
+            Some(FloatRounding::TowardPositive),
+        ),
+        AArch64Inst::FrintmScalarDoublePrecisionVar(operand) => compile_frint(
+            basic_block,
+            operand,
+            IrType::U64,
+            Some(FloatRounding::TowardNegative),
+        ),
+        AArch64Inst::FrintzScalarDoublePrecisionVar(operand) => compile_frint(
+            basic_block,
+            operand,
+            IrType::U64,
+            Some(FloatRounding::TowardZero),
+        ),
+        AArch64Inst::FrintaScalarDoublePrecisionVar(operand) => compile_frint(
+            basic_block,
+            operand,
+            IrType::U64,
+            Some(FloatRounding::TiesToAway),
+        ),
+        AArch64Inst::FrintiScalarDoublePrecisionVar(operand) => {
+            compile_frint(basic_block, operand, IrType::U64, None)
+        }
+        AArch64Inst::FrintxScalarDoublePrecisionVar(operand) => {
+            let op = FloatOp::RoundInt {
+                rounding: None,
+                exact: true,
+            };
+            compile_float_1src(basic_block, operand, op, IrType::U64, IrType::U64)
+        }
+        AArch64Inst::FmovRegHalfPrecisionVar(operand) => {
+            compile_float_sign(basic_block, operand, IrType::U16, FloatSignOp::Mov)
+        }
+        AArch64Inst::FabsScalarHalfPrecisionVar(operand) => {
+            compile_float_sign(basic_block, operand, IrType::U16, FloatSignOp::Abs)
+        }
+        AArch64Inst::FnegScalarHalfPrecisionVar(operand) => {
+            compile_float_sign(basic_block, operand, IrType::U16, FloatSignOp::Neg)
+        }
+        AArch64Inst::FsqrtScalarHalfPrecisionVar(operand) => compile_float_1src(
+            basic_block,
+            operand,
+            FloatOp::Sqrt,
+            IrType::U16,
+            IrType::U16,
+        ),
+        AArch64Inst::FrintnScalarHalfPrecisionVar(operand) => compile_frint(
+            basic_block,
+            operand,
+            IrType::U16,
+            Some(FloatRounding::TiesToEven),
+        ),
+        AArch64Inst::FrintpScalarHalfPrecisionVar(operand) => compile_frint(
+            basic_block,
+            operand,
+            IrType::U16,
+            Some(FloatRounding::TowardPositive),
+        ),
+        AArch64Inst::FrintmScalarHalfPrecisionVar(operand) => compile_frint(
+            basic_block,
+            operand,
+            IrType::U16,
+            Some(FloatRounding::TowardNegative),
+        ),
+        AArch64Inst::FrintzScalarHalfPrecisionVar(operand) => compile_frint(
+            basic_block,
+            operand,
+            IrType::U16,
+            Some(FloatRounding::TowardZero),
+        ),
+        AArch64Inst::FrintaScalarHalfPrecisionVar(operand) => compile_frint(
+            basic_block,
+            operand,
+            IrType::U16,
+            Some(FloatRounding::TiesToAway),
+        ),
+        AArch64Inst::FrintiScalarHalfPrecisionVar(operand) => {
+            compile_frint(basic_block, operand, IrType::U16, None)
+        }
+        AArch64Inst::FrintxScalarHalfPrecisionVar(operand) => {
+            let op = FloatOp::RoundInt {
+                rounding: None,
+                exact: true,
+            };
+            compile_float_1src(basic_block, operand, op, IrType::U16, IrType::U16)
+        }
+        AArch64Inst::FcvtHalfToSinglePrecisionVar(operand) => compile_float_1src(
+            basic_block,
+            operand,
+            FloatOp::Convert { rounding: None },
+            IrType::U16,
+            IrType::U32,
+        ),
+        AArch64Inst::FcvtHalfToDoublePrecisionVar(operand) => compile_float_1src(
+            basic_block,
+            operand,
+            FloatOp::Convert { rounding: None },
+            IrType::U16,
+            IrType::U64,
+        ),
+        AArch64Inst::FcvtSingleToDoublePrecisionVar(operand) => compile_float_1src(
+            basic_block,
+            operand,
+            FloatOp::Convert { rounding: None },
+            IrType::U32,
+            IrType::U64,
+        ),
+        AArch64Inst::FcvtSingleToHalfPrecisionVar(operand) => compile_float_1src(
+            basic_block,
+            operand,
+            FloatOp::Convert { rounding: None },
+            IrType::U32,
+            IrType::U16,
+        ),
+        AArch64Inst::FcvtDoubleToSinglePrecisionVar(operand) => compile_float_1src(
+            basic_block,
+            operand,
+            FloatOp::Convert { rounding: None },
+            IrType::U64,
+            IrType::U32,
+        ),
+        AArch64Inst::FcvtDoubleToHalfPrecisionVar(operand) => compile_float_1src(
+            basic_block,
+            operand,
+            FloatOp::Convert { rounding: None },
+            IrType::U64,
+            IrType::U16,
+        ),
+        AArch64Inst::Fcmp(operand) => compile_fcmp(basic_block, operand, false),
+        AArch64Inst::Fcmpe(operand) => compile_fcmp(basic_block, operand, true),
+        AArch64Inst::FcselSinglePrecisionVar(operand) => {
+            compile_csel(basic_block, operand, IrType::U32, CondSelOp::Csel)
+        }
+        AArch64Inst::FcselDoublePrecisionVar(operand) => {
+            compile_csel(basic_block, operand, IrType::U64, CondSelOp::Csel)
+        }
+        AArch64Inst::FmovScalarImmSinglePrecisionVar(operand) => {
+            compile_fmov_imm(basic_block, operand, IrType::U32)
+        }
+        AArch64Inst::FmovScalarImmDoublePrecisionVar(operand) => {
+            compile_fmov_imm(basic_block, operand, IrType::U64)
+        }
+        AArch64Inst::FcvtnsScalarSinglePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U32,
+            true,
+            FloatRounding::TiesToEven,
+        ),
+        AArch64Inst::FcvtnuScalarSinglePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U32,
+            false,
+            FloatRounding::TiesToEven,
+        ),
+        AArch64Inst::FcvtpsScalarSinglePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U32,
+            true,
+            FloatRounding::TowardPositive,
+        ),
+        AArch64Inst::FcvtpuScalarSinglePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U32,
+            false,
+            FloatRounding::TowardPositive,
+        ),
+        AArch64Inst::FcvtmsScalarSinglePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U32,
+            true,
+            FloatRounding::TowardNegative,
+        ),
+        AArch64Inst::FcvtmuScalarSinglePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U32,
+            false,
+            FloatRounding::TowardNegative,
+        ),
+        AArch64Inst::FcvtasScalarSinglePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U32,
+            true,
+            FloatRounding::TiesToAway,
+        ),
+        AArch64Inst::FcvtauScalarSinglePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U32,
+            false,
+            FloatRounding::TiesToAway,
+        ),
+        AArch64Inst::FcvtzsScalarIntSinglePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U32,
+            true,
+            FloatRounding::TowardZero,
+        ),
+        AArch64Inst::FcvtzsScalarFixedPtSinglePrecisionTo32(operand) => {
+            compile_fcvt_fixed(basic_block, operand, IrType::U32, IrType::U32, true)
+        }
+        AArch64Inst::FcvtzuScalarIntSinglePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U32,
+            false,
+            FloatRounding::TowardZero,
+        ),
+        AArch64Inst::FcvtzuScalarFixedPtSinglePrecisionTo32(operand) => {
+            compile_fcvt_fixed(basic_block, operand, IrType::U32, IrType::U32, false)
+        }
+        AArch64Inst::ScvtfScalarInt32ToSinglePrecision(operand) => {
+            compile_cvtf_int(basic_block, operand, IrType::U32, IrType::U32, true)
+        }
+        AArch64Inst::ScvtfScalarFixedPt32ToSinglePrecision(operand) => {
+            compile_cvtf_fixed(basic_block, operand, IrType::U32, IrType::U32, true)
+        }
+        AArch64Inst::UcvtfScalarInt32ToSinglePrecision(operand) => {
+            compile_cvtf_int(basic_block, operand, IrType::U32, IrType::U32, false)
+        }
+        AArch64Inst::UcvtfScalarFixedPt32ToSinglePrecision(operand) => {
+            compile_cvtf_fixed(basic_block, operand, IrType::U32, IrType::U32, false)
+        }
+        AArch64Inst::FcvtnsScalarSinglePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U64,
+            true,
+            FloatRounding::TiesToEven,
+        ),
+        AArch64Inst::FcvtnuScalarSinglePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U64,
+            false,
+            FloatRounding::TiesToEven,
+        ),
+        AArch64Inst::FcvtpsScalarSinglePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U64,
+            true,
+            FloatRounding::TowardPositive,
+        ),
+        AArch64Inst::FcvtpuScalarSinglePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U64,
+            false,
+            FloatRounding::TowardPositive,
+        ),
+        AArch64Inst::FcvtmsScalarSinglePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U64,
+            true,
+            FloatRounding::TowardNegative,
+        ),
+        AArch64Inst::FcvtmuScalarSinglePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U64,
+            false,
+            FloatRounding::TowardNegative,
+        ),
+        AArch64Inst::FcvtasScalarSinglePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U64,
+            true,
+            FloatRounding::TiesToAway,
+        ),
+        AArch64Inst::FcvtauScalarSinglePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U64,
+            false,
+            FloatRounding::TiesToAway,
+        ),
+        AArch64Inst::FcvtzsScalarIntSinglePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U64,
+            true,
+            FloatRounding::TowardZero,
+        ),
+        AArch64Inst::FcvtzsScalarFixedPtSinglePrecisionTo64(operand) => {
+            compile_fcvt_fixed(basic_block, operand, IrType::U32, IrType::U64, true)
+        }
+        AArch64Inst::FcvtzuScalarIntSinglePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U32,
+            IrType::U64,
+            false,
+            FloatRounding::TowardZero,
+        ),
+        AArch64Inst::FcvtzuScalarFixedPtSinglePrecisionTo64(operand) => {
+            compile_fcvt_fixed(basic_block, operand, IrType::U32, IrType::U64, false)
+        }
+        AArch64Inst::ScvtfScalarInt64ToSinglePrecision(operand) => {
+            compile_cvtf_int(basic_block, operand, IrType::U64, IrType::U32, true)
+        }
+        AArch64Inst::ScvtfScalarFixedPt64ToSinglePrecision(operand) => {
+            compile_cvtf_fixed(basic_block, operand, IrType::U64, IrType::U32, true)
+        }
+        AArch64Inst::UcvtfScalarInt64ToSinglePrecision(operand) => {
+            compile_cvtf_int(basic_block, operand, IrType::U64, IrType::U32, false)
+        }
+        AArch64Inst::UcvtfScalarFixedPt64ToSinglePrecision(operand) => {
+            compile_cvtf_fixed(basic_block, operand, IrType::U64, IrType::U32, false)
+        }
+        AArch64Inst::FcvtnsScalarDoublePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U32,
+            true,
+            FloatRounding::TiesToEven,
+        ),
+        AArch64Inst::FcvtnuScalarDoublePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U32,
+            false,
+            FloatRounding::TiesToEven,
+        ),
+        AArch64Inst::FcvtpsScalarDoublePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U32,
+            true,
+            FloatRounding::TowardPositive,
+        ),
+        AArch64Inst::FcvtpuScalarDoublePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U32,
+            false,
+            FloatRounding::TowardPositive,
+        ),
+        AArch64Inst::FcvtmsScalarDoublePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U32,
+            true,
+            FloatRounding::TowardNegative,
+        ),
+        AArch64Inst::FcvtmuScalarDoublePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U32,
+            false,
+            FloatRounding::TowardNegative,
+        ),
+        AArch64Inst::FcvtasScalarDoublePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U32,
+            true,
+            FloatRounding::TiesToAway,
+        ),
+        AArch64Inst::FcvtauScalarDoublePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U32,
+            false,
+            FloatRounding::TiesToAway,
+        ),
+        AArch64Inst::FcvtzsScalarIntDoublePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U32,
+            true,
+            FloatRounding::TowardZero,
+        ),
+        AArch64Inst::FcvtzsScalarFixedPtDoublePrecisionTo32(operand) => {
+            compile_fcvt_fixed(basic_block, operand, IrType::U64, IrType::U32, true)
+        }
+        AArch64Inst::FcvtzuScalarIntDoublePrecisionTo32(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U32,
+            false,
+            FloatRounding::TowardZero,
+        ),
+        AArch64Inst::FcvtzuScalarFixedPtDoublePrecisionTo32(operand) => {
+            compile_fcvt_fixed(basic_block, operand, IrType::U64, IrType::U32, false)
+        }
+        AArch64Inst::ScvtfScalarInt32ToDoublePrecision(operand) => {
+            compile_cvtf_int(basic_block, operand, IrType::U32, IrType::U64, true)
+        }
+        AArch64Inst::ScvtfScalarFixedPt32ToDoublePrecision(operand) => {
+            compile_cvtf_fixed(basic_block, operand, IrType::U32, IrType::U64, true)
+        }
+        AArch64Inst::UcvtfScalarInt32ToDoublePrecision(operand) => {
+            compile_cvtf_int(basic_block, operand, IrType::U32, IrType::U64, false)
+        }
+        AArch64Inst::UcvtfScalarFixedPt32ToDoublePrecision(operand) => {
+            compile_cvtf_fixed(basic_block, operand, IrType::U32, IrType::U64, false)
+        }
+        AArch64Inst::FcvtnsScalarDoublePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U64,
+            true,
+            FloatRounding::TiesToEven,
+        ),
+        AArch64Inst::FcvtnuScalarDoublePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U64,
+            false,
+            FloatRounding::TiesToEven,
+        ),
+        AArch64Inst::FcvtpsScalarDoublePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U64,
+            true,
+            FloatRounding::TowardPositive,
+        ),
+        AArch64Inst::FcvtpuScalarDoublePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U64,
+            false,
+            FloatRounding::TowardPositive,
+        ),
+        AArch64Inst::FcvtmsScalarDoublePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U64,
+            true,
+            FloatRounding::TowardNegative,
+        ),
+        AArch64Inst::FcvtmuScalarDoublePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U64,
+            false,
+            FloatRounding::TowardNegative,
+        ),
+        AArch64Inst::FcvtasScalarDoublePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U64,
+            true,
+            FloatRounding::TiesToAway,
+        ),
+        AArch64Inst::FcvtauScalarDoublePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U64,
+            false,
+            FloatRounding::TiesToAway,
+        ),
+        AArch64Inst::FcvtzsScalarIntDoublePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U64,
+            true,
+            FloatRounding::TowardZero,
+        ),
+        AArch64Inst::FcvtzsScalarFixedPtDoublePrecisionTo64(operand) => {
+            compile_fcvt_fixed(basic_block, operand, IrType::U64, IrType::U64, true)
+        }
+        AArch64Inst::FcvtzuScalarIntDoublePrecisionTo64(operand) => compile_fcvt_int(
+            basic_block,
+            operand,
+            IrType::U64,
+            IrType::U64,
+            false,
+            FloatRounding::TowardZero,
+        ),
+        AArch64Inst::FcvtzuScalarFixedPtDoublePrecisionTo64(operand) => {
+            compile_fcvt_fixed(basic_block, operand, IrType::U64, IrType::U64, false)
+        }
+        AArch64Inst::ScvtfScalarInt64ToDoublePrecision(operand) => {
+            compile_cvtf_int(basic_block, operand, IrType::U64, IrType::U64, true)
+        }
+        AArch64Inst::ScvtfScalarFixedPt64ToDoublePrecision(operand) => {
+            compile_cvtf_fixed(basic_block, operand, IrType::U64, IrType::U64, true)
+        }
+        AArch64Inst::UcvtfScalarInt64ToDoublePrecision(operand) => {
+            compile_cvtf_int(basic_block, operand, IrType::U64, IrType::U64, false)
+        }
+        AArch64Inst::UcvtfScalarFixedPt64ToDoublePrecision(operand) => {
+            compile_cvtf_fixed(basic_block, operand, IrType::U64, IrType::U64, false)
+        }
+        AArch64Inst::FmovGeneralSinglePrecisionTo32(operand) => {
+            compile_fmov_general(basic_block, operand, IrType::U32)
+        }
+        AArch64Inst::FmovGeneral32ToSinglePrecision(operand) => {
+            compile_fmov_general(basic_block, operand, IrType::U32)
+        }
+        AArch64Inst::FmovGeneralDoublePrecisionTo64(operand) => {
+            compile_fmov_general(basic_block, operand, IrType::U64)
+        }
+        AArch64Inst::FmovGeneral64ToDoublePrecision(operand) => {
+            compile_fmov_general(basic_block, operand, IrType::U64)
+        }
+        AArch64Inst::FmovGeneralTopHalfOf128To64(operand) => {
+            compile_fmov_from_top_half(basic_block, operand)
+        }
+        AArch64Inst::FmovGeneral64toTopHalfOf128(operand) => {
+            compile_fmov_to_top_half(basic_block, operand)
+        }
+
+        // Interrupt Instructions
+        AArch64Inst::Svc(operand) => compile_svc(basic_block, operand),
+        AArch64Inst::Hvc(operand) => compile_hvc(basic_block, operand),
+        AArch64Inst::Smc(operand) => compile_smc(basic_block, operand),
+        AArch64Inst::Brk(operand) => compile_brk(basic_block, operand),
+        AArch64Inst::Udf(operand) => compile_udf(basic_block, operand),
+
+        // Speical instructions
+        AArch64Inst::Mrs(operand) => compile_mrs(basic_block, operand),
+        AArch64Inst::MsrReg(operand) => compile_msr_reg(basic_block, operand),
+        AArch64Inst::MsrImm(operand) => compile_msr_imm(basic_block, operand),
+        AArch64Inst::Sys(operand) => compile_sys(basic_block, operand),
+
+        // Hints and barriers
+        AArch64Inst::Nop | AArch64Inst::Sev | AArch64Inst::Sevl | AArch64Inst::Isb(_) => {
+            compiler_prelude::gen_move_pc(basic_block)
+        }
+        // Pointer authentication isn't implemented, its instructions of the hint space are NOPs
+        // like the unallocated hints
+        AArch64Inst::Hint(_)
+        | AArch64Inst::Xpaclri
+        | AArch64Inst::Pacia1716Var
+        | AArch64Inst::Pacib1716Var
+        | AArch64Inst::Autia1716Var
+        | AArch64Inst::Autib1716Var
+        | AArch64Inst::PaciazVar
+        | AArch64Inst::PaciaspVar
+        | AArch64Inst::PacibzVar
+        | AArch64Inst::PacibspVar
+        | AArch64Inst::AutiazVar
+        | AArch64Inst::AutiaspVar
+        | AArch64Inst::AutibzVar
+        | AArch64Inst::AutibspVar => compiler_prelude::gen_move_pc(basic_block),
+        AArch64Inst::Yield | AArch64Inst::Wfe => {
+            compile_hint_interrupt(basic_block, Interrupt::Yield)
+        }
+        AArch64Inst::Wfi => compile_hint_interrupt(basic_block, Interrupt::WaitForInterrupt),
+        AArch64Inst::Dmb(operand) | AArch64Inst::DsbEncoding(operand) => {
+            compile_barrier(basic_block, operand)
+        }
+        _ => unimplemented!(),
+    }
+}
+
+fn compile_movz(bb: &mut BasicBlock, operand: &HwImm16Rd, ty: IrType) {
+    let pos = operand.hw << 4;
+    let value = (operand.imm16 as u64) << pos;
+
+    gen_assign(bb, reg(operand.rd, ty), imm(ty, value));
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_movn(bb: &mut BasicBlock, operand: &HwImm16Rd, ty: IrType) {
+    let pos = operand.hw << 4;
+    let value = !((operand.imm16 as u64) << pos);
+
+    gen_assign(bb, reg(operand.rd, ty), imm(ty, value));
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_movk(bb: &mut BasicBlock, operand: &HwImm16Rd, ty: IrType) {
+    let pos = operand.hw << 4;
+    let rd = reg(operand.rd, ty);
+
+    let kept = gen_and(bb, rd, imm(ty, !(0xffff << pos)));
+    let value = gen_or(bb, kept, imm(ty, (operand.imm16 as u64) << pos));
+    gen_assign(bb, rd, value);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The signed offset `immhi:immlo` of ADR and ADRP.
+fn pc_rel_offset(operand: &PcRelAddressing) -> u64 {
+    sign_extend(((operand.immhi as u64) << 2) | operand.immlo as u64, 21)
+}
+
+fn compile_adr(bb: &mut BasicBlock, operand: &PcRelAddressing) {
+    let pc = reg(AArch64RegisterId::Pc, IrType::U64);
+    let address = gen_add(bb, pc, imm(IrType::U64, pc_rel_offset(operand)));
+    gen_assign(bb, reg(operand.rd, IrType::U64), address);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// ADRP, the address of the 4KB page at a signed page offset from the page of the PC.
+fn compile_adrp(bb: &mut BasicBlock, operand: &PcRelAddressing) {
+    let pc = reg(AArch64RegisterId::Pc, IrType::U64);
+    let page = gen_and(bb, pc, imm(IrType::U64, !0xfff));
+    let address = gen_add(bb, page, imm(IrType::U64, pc_rel_offset(operand) << 12));
+    gen_assign(bb, reg(operand.rd, IrType::U64), address);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AddSubOp {
+    Add,
+    Adds,
+    Sub,
+    Subs,
+}
+
+impl AddSubOp {
+    fn is_sub(self) -> bool {
+        matches!(self, AddSubOp::Sub | AddSubOp::Subs)
+    }
+
+    fn sets_flags(self) -> bool {
+        matches!(self, AddSubOp::Adds | AddSubOp::Subs)
+    }
+}
+
+/// Compute `rn + operand2` or `rn - operand2` into `rd`, with `carry` added to the sum.
+fn gen_add_sub(
+    bb: &mut BasicBlock,
+    op: AddSubOp,
+    rd: AArch64RegisterId,
+    rn: IrValue,
+    operand2: IrValue,
+    carry: IrValue,
+) {
+    let operand2 = match op.is_sub() {
+        true => gen_not(bb, operand2),
+        false => operand2,
+    };
+
+    let result = gen_add_with_carry(bb, rn, operand2, carry, op.sets_flags());
+    gen_assign(bb, reg(rd, rn.ty()), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The carry of an addition, or of a subtraction computed as `rn + !operand2 + 1`.
+fn no_carry(op: AddSubOp, ty: IrType) -> IrValue {
+    imm(ty, op.is_sub() as u64)
+}
+
+fn compile_add_sub_imm(bb: &mut BasicBlock, operand: &ShImm12RnRd, ty: IrType, op: AddSubOp) {
+    let imm12 = (operand.imm12 as u64) << (12 * operand.sh);
+
+    gen_add_sub(
+        bb,
+        op,
+        operand.rd,
+        reg(operand.rn, ty),
+        imm(ty, imm12),
+        no_carry(op, ty),
+    );
+}
+
+fn compile_add_sub_shifted_reg(
+    bb: &mut BasicBlock,
+    operand: &ShiftRmImm6RnRd,
+    ty: IrType,
+    op: AddSubOp,
+) {
+    // ROR is reserved for the arithmetic instructions
+    if operand.shift == 0b11 || operand.imm6 as u64 >= bits(ty) {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    let operand2 = gen_shifted_reg(bb, operand, ty);
+    gen_add_sub(
+        bb,
+        op,
+        operand.rd,
+        reg(operand.rn, ty),
+        operand2,
+        no_carry(op, ty),
+    );
+}
+
+fn compile_add_sub_ext_reg(
+    bb: &mut BasicBlock,
+    operand: &AddSubtractExtReg,
+    ty: IrType,
+    op: AddSubOp,
+) {
+    if operand.imm3 > 4 {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    let operand2 = gen_extended_reg(bb, operand.rm, operand.option, operand.imm3, ty);
+    gen_add_sub(
+        bb,
+        op,
+        operand.rd,
+        reg(operand.rn, ty),
+        operand2,
+        no_carry(op, ty),
+    );
+}
+
+fn compile_add_sub_carry(bb: &mut BasicBlock, operand: &RmRnRd, ty: IrType, op: AddSubOp) {
+    let carry = compiler_prelude::gen_carry_flag(bb, ty);
+
+    gen_add_sub(
+        bb,
+        op,
+        operand.rd,
+        reg(operand.rn, ty),
+        reg(operand.rm, ty),
+        carry,
+    );
+}
+
+/// The second operand of the shifted register forms, `rm` shifted by `imm6`.
+fn gen_shifted_reg(bb: &mut BasicBlock, operand: &ShiftRmImm6RnRd, ty: IrType) -> IrValue {
+    let rm = reg(operand.rm, ty);
+    if operand.imm6 == 0 {
+        return rm;
+    }
+
+    gen_shift(bb, operand.shift, rm, imm(ty, operand.imm6 as u64))
+}
+
+/// `rm` extended to `ty` as selected by `option` and shifted left by `shift`, the extended
+/// register operand of the add/sub and load/store forms.
+fn gen_extended_reg(
+    bb: &mut BasicBlock,
+    rm: AArch64RegisterId,
+    option: u8,
+    shift: u8,
+    ty: IrType,
+) -> IrValue {
+    // option<1:0> is the size of the source, option<2> makes the extension signed
+    let (source_ty, read_ty) = match option & 0b11 {
+        0b00 => (IrType::U8, IrType::U32),
+        0b01 => (IrType::U16, IrType::U32),
+        0b10 => (IrType::U32, IrType::U32),
+        _ => (ty, ty),
+    };
+
+    let rm = reg(rm, read_ty);
+    let extended = match (source_ty == ty, option & 0b100 != 0) {
+        (true, _) => rm,
+        (false, signed) => {
+            let source = gen_zext(bb, rm, source_ty);
+            match signed {
+                true => gen_sext(bb, source, ty),
+                false => gen_zext(bb, source, ty),
+            }
+        }
+    };
+
+    match shift {
+        0 => extended,
+        shift => gen_shl(bb, extended, imm(ty, shift as u64)),
+    }
+}
+
+fn compile_madd(bb: &mut BasicBlock, operand: &DataProc3Src, ty: IrType, sub: bool) {
+    let product = gen_mul(bb, reg(operand.rn, ty), reg(operand.rm, ty));
+    let result = match sub {
+        true => gen_sub(bb, reg(operand.ra, ty), product),
+        false => gen_add(bb, reg(operand.ra, ty), product),
+    };
+
+    gen_assign(bb, reg(operand.rd, ty), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// SMADDL, SMSUBL, UMADDL and UMSUBL, the 32-bit sources are extended to 64 bits.
+fn compile_maddl(bb: &mut BasicBlock, operand: &DataProc3Src, signed: bool, sub: bool) {
+    let mut extend = |rx: AArch64RegisterId| match signed {
+        true => gen_sext(bb, reg(rx, IrType::U32), IrType::U64),
+        false => gen_zext(bb, reg(rx, IrType::U32), IrType::U64),
+    };
+    let (rn, rm) = (extend(operand.rn), extend(operand.rm));
+
+    let product = gen_mul(bb, rn, rm);
+    let result = match sub {
+        true => gen_sub(bb, reg(operand.ra, IrType::U64), product),
+        false => gen_add(bb, reg(operand.ra, IrType::U64), product),
+    };
+
+    gen_assign(bb, reg(operand.rd, IrType::U64), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// SMULH and UMULH, the high half of the 128-bit product.
+fn compile_mulh(bb: &mut BasicBlock, operand: &DataProc3Src, signed: bool) {
+    let mut extend = |rx: AArch64RegisterId| match signed {
+        true => gen_sext(bb, reg(rx, IrType::U64), IrType::U128),
+        false => gen_zext(bb, reg(rx, IrType::U64), IrType::U128),
+    };
+    let (rn, rm) = (extend(operand.rn), extend(operand.rm));
+
+    let product = gen_mul(bb, rn, rm);
+    // There are no 128-bit constants, the shift amount is extended from a 64-bit one
+    let half = gen_zext(bb, imm(IrType::U64, 64), IrType::U128);
+    let high = gen_shr(bb, product, half);
+    let result = gen_zext(bb, high, IrType::U64);
+
+    gen_assign(bb, reg(operand.rd, IrType::U64), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+// The IR division gives zero for a zero divisor and wraps on overflow, like SDIV and UDIV.
+fn compile_div(bb: &mut BasicBlock, operand: &DataProc2Src, ty: IrType, signed: bool) {
+    let div_ty = match signed {
+        true => compiler_prelude::signed(ty),
+        false => ty,
+    };
+
+    let rn = gen_zext(bb, reg(operand.rn, ty), div_ty);
+    let rm = gen_zext(bb, reg(operand.rm, ty), div_ty);
+    let quotient = gen_div(bb, rn, rm);
+    let result = gen_zext(bb, quotient, ty);
+
+    gen_assign(bb, reg(operand.rd, ty), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+// LSLV, LSRV, ASRV and RORV take the amount modulo the register width, as the IR shifts do.
+fn compile_shiftv(bb: &mut BasicBlock, operand: &DataProc2Src, ty: IrType, shift: u8) {
+    let result = gen_shift(bb, shift, reg(operand.rn, ty), reg(operand.rm, ty));
+
+    gen_assign(bb, reg(operand.rd, ty), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BitfieldOp {
+    Sbfm,
+    Bfm,
+    Ubfm,
+}
+
+fn compile_bitfield(bb: &mut BasicBlock, operand: &Bitfield, ty: IrType, op: BitfieldOp) {
+    let width = bits(ty);
+    let (immr, imms) = (operand.immr as u64, operand.imms as u64);
+
+    let masks =
+        compiler_prelude::decode_bit_masks(operand.n, operand.imms, operand.immr, false, width);
+    let Some((wmask, tmask)) = masks.filter(|_| immr < width && imms < width) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let src = reg(operand.rn, ty);
+    let rd = reg(operand.rd, ty);
+
+    // bot = (dst AND NOT(wmask)) OR (ROR(src, R) AND wmask), dst is zero unless BFM
+    let rotated = match immr {
+        0 => src,
+        r => gen_shift(bb, 0b11, src, imm(ty, r)),
+    };
+    let bot = gen_and(bb, rotated, imm(ty, wmask));
+    let bot = match op {
+        BitfieldOp::Bfm => {
+            let kept = gen_and(bb, rd, imm(ty, !wmask));
+            gen_or(bb, kept, bot)
+        }
+        BitfieldOp::Sbfm | BitfieldOp::Ubfm => bot,
+    };
+
+    // top is the sign bit src<S> replicated for SBFM, dst for BFM and zero for UBFM
+    let top = match op {
+        BitfieldOp::Sbfm => {
+            let sign = gen_shl(bb, src, imm(ty, width - 1 - imms));
+            Some(gen_shift(bb, 0b10, sign, imm(ty, width - 1)))
+        }
+        BitfieldOp::Bfm => Some(rd),
+        BitfieldOp::Ubfm => None,
+    };
+
+    let bot = gen_and(bb, bot, imm(ty, tmask));
+    let result = match top {
+        Some(top) => {
+            let top = gen_and(bb, top, imm(ty, !tmask));
+            gen_or(bb, top, bot)
+        }
+        None => bot,
+    };
+
+    gen_assign(bb, rd, result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_extr(bb: &mut BasicBlock, operand: &ExtractImm, ty: IrType) {
+    let lsb = operand.imms as u64;
+    if lsb >= bits(ty) {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    // The low bits of rn:rm from lsb
+    let rm = reg(operand.rm, ty);
+    let result = match lsb {
+        0 => rm,
+        lsb => {
+            let low = gen_shr(bb, rm, imm(ty, lsb));
+            let high = gen_shl(bb, reg(operand.rn, ty), imm(ty, bits(ty) - lsb));
+            gen_or(bb, high, low)
+        }
+    };
+
+    gen_assign(bb, reg(operand.rd, ty), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LogicalOp {
+    And,
+    Bic,
+    Orr,
+    Orn,
+    Eor,
+    Eon,
+    Ands,
+    Bics,
+}
+
+/// Compute `rn op operand2` into `rd`.
+fn gen_logical(
+    bb: &mut BasicBlock,
+    op: LogicalOp,
+    rd: AArch64RegisterId,
+    rn: IrValue,
+    operand2: IrValue,
+) {
+    let operand2 = match op {
+        LogicalOp::Bic | LogicalOp::Orn | LogicalOp::Eon | LogicalOp::Bics => gen_not(bb, operand2),
+        _ => operand2,
+    };
+
+    let result = match op {
+        LogicalOp::And | LogicalOp::Bic | LogicalOp::Ands | LogicalOp::Bics => {
+            gen_and(bb, rn, operand2)
+        }
+        LogicalOp::Orr | LogicalOp::Orn => gen_or(bb, rn, operand2),
+        LogicalOp::Eor | LogicalOp::Eon => gen_xor(bb, rn, operand2),
+    };
+
+    if matches!(op, LogicalOp::Ands | LogicalOp::Bics) {
+        compiler_prelude::gen_set_nzcv_logical(bb, result);
+    }
+
+    gen_assign(bb, reg(rd, rn.ty()), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_logical_imm(bb: &mut BasicBlock, operand: &LogicalImm, ty: IrType, op: LogicalOp) {
+    let masks =
+        compiler_prelude::decode_bit_masks(operand.n, operand.imms, operand.immr, true, bits(ty));
+    let Some((wmask, _)) = masks else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    gen_logical(bb, op, operand.rd, reg(operand.rn, ty), imm(ty, wmask));
+}
+
+fn compile_logical_shifted_reg(
+    bb: &mut BasicBlock,
+    operand: &ShiftRmImm6RnRd,
+    ty: IrType,
+    op: LogicalOp,
+) {
+    let operand2 = gen_shifted_reg(bb, operand, ty);
+    gen_logical(bb, op, operand.rd, reg(operand.rn, ty), operand2);
+}
+
+/// Swap the adjacent groups of `size` bits of `value`.
+fn gen_swap_groups(bb: &mut BasicBlock, value: IrValue, size: u64) -> IrValue {
+    let ty = value.ty();
+    let mask = (0..bits(ty))
+        .filter(|bit| (bit / size) & 1 == 0)
+        .fold(0u64, |mask, bit| mask | 1 << bit);
+
+    let low = gen_and(bb, value, imm(ty, mask));
+    let low = gen_shl(bb, low, imm(ty, size));
+    let high = gen_shr(bb, value, imm(ty, size));
+    let high = gen_and(bb, high, imm(ty, mask));
+    gen_or(bb, low, high)
+}
+
+fn compile_rbit(bb: &mut BasicBlock, operand: &RnRd, ty: IrType) {
+    let mut result = reg(operand.rn, ty);
+
+    let mut size = 1;
+    while size < bits(ty) {
+        result = gen_swap_groups(bb, result, size);
+        size <<= 1;
+    }
+
+    gen_assign(bb, reg(operand.rd, ty), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// REV16, REV32 and REV, reverse the bytes in each container of `container` bits.
+fn compile_rev(bb: &mut BasicBlock, operand: &RnRd, ty: IrType, container: u64) {
+    let mut result = reg(operand.rn, ty);
+
+    let mut size = 8;
+    while size < container {
+        result = gen_swap_groups(bb, result, size);
+        size <<= 1;
+    }
+
+    gen_assign(bb, reg(operand.rd, ty), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The number of leading zero bits of `value`.
+fn gen_count_leading_zeros(bb: &mut BasicBlock, value: IrValue) -> IrValue {
+    let ty = value.ty();
+    let width = bits(ty);
+    let repeat = |byte: u64| (0..width / 8).fold(0, |acc, i| acc | byte << (i * 8));
+
+    // Smear the highest set bit to the right, the leading zeros are the bits left clear
+    let mut smeared = value;
+    let mut shift = 1;
+    while shift < width {
+        let shifted = gen_shr(bb, smeared, imm(ty, shift));
+        smeared = gen_or(bb, smeared, shifted);
+        shift <<= 1;
+    }
+    let x = gen_not(bb, smeared);
+
+    // Population count of x, the sum of the counts of its bytes
+    let x = gen_count_ones_per_byte(bb, x);
+    let x = gen_mul(bb, x, imm(ty, repeat(0x01)));
+    gen_shr(bb, x, imm(ty, width - 8))
+}
+
+/// The number of set bits in each byte of `value`, in that byte.
+fn gen_count_ones_per_byte(bb: &mut BasicBlock, value: IrValue) -> IrValue {
+    let ty = value.ty();
+    let repeat = |byte: u64| (0..bits(ty) / 8).fold(0, |acc, i| acc | byte << (i * 8));
+
+    let pairs = gen_shr(bb, value, imm(ty, 1));
+    let pairs = gen_and(bb, pairs, imm(ty, repeat(0x55)));
+    let x = gen_sub(bb, value, pairs);
+    let low = gen_and(bb, x, imm(ty, repeat(0x33)));
+    let high = gen_shr(bb, x, imm(ty, 2));
+    let high = gen_and(bb, high, imm(ty, repeat(0x33)));
+    let x = gen_add(bb, low, high);
+    let high = gen_shr(bb, x, imm(ty, 4));
+    let x = gen_add(bb, x, high);
+    gen_and(bb, x, imm(ty, repeat(0x0f)))
+}
+
+fn compile_clz(bb: &mut BasicBlock, operand: &RnRd, ty: IrType) {
+    let result = gen_count_leading_zeros(bb, reg(operand.rn, ty));
+
+    gen_assign(bb, reg(operand.rd, ty), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_cls(bb: &mut BasicBlock, operand: &RnRd, ty: IrType) {
+    // The bits equal to the sign bit are the leading zeros of rn ^ (rn << 1), the lowest bit is
+    // set so that it doesn't count the last one.
+    let rn = reg(operand.rn, ty);
+    let shifted = gen_shl(bb, rn, imm(ty, 1));
+    let changes = gen_xor(bb, rn, shifted);
+    let changes = gen_or(bb, changes, imm(ty, 1));
+    let result = gen_count_leading_zeros(bb, changes);
+
+    gen_assign(bb, reg(operand.rd, ty), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// How a load or store transfers its register.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MemOp {
+    Store,
+    /// Load zero extended to the register
+    Load,
+    /// Load sign extended to W
+    LoadSigned32,
+    /// Load sign extended to X
+    LoadSigned64,
+}
+
+/// Where a load or store accesses memory relative to its base register.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Indexing {
+    /// Access `base + offset`, the base is left unchanged
+    Offset,
+    /// Access `base + offset` and write it back to the base
+    PreIndex,
+    /// Access `base` and write `base + offset` back to the base
+    PostIndex,
+}
+
+/// The width `rt` is accessed with to transfer `ty`, general purpose registers have no view
+/// smaller than W.
+fn transfer_view(rt: AArch64RegisterId, ty: IrType) -> IrType {
+    match rt {
+        AArch64RegisterId::V(_) => ty,
+        _ if bits(ty) < 32 => IrType::U32,
+        _ => ty,
+    }
+}
+
+/// Transfer `rts` to or from consecutive `ty` sized elements of memory at `rn` indexed by
+/// `offset`.
+fn gen_load_store(
+    bb: &mut BasicBlock,
+    op: MemOp,
+    ty: IrType,
+    rts: &[AArch64RegisterId],
+    rn: AArch64RegisterId,
+    offset: IrValue,
+    indexing: Indexing,
+) {
+    let base = reg(rn, IrType::U64);
+    let offset_addr = gen_add(bb, base, offset);
+    let addr = match indexing {
+        Indexing::PostIndex => base,
+        Indexing::Offset | Indexing::PreIndex => offset_addr,
+    };
+
+    let size = ty.size_in_bytes() as u64;
+    let addrs: Vec<_> = (0..rts.len() as u64)
+        .map(|i| match i {
+            0 => addr,
+            i => gen_add(bb, addr, imm(IrType::U64, i * size)),
+        })
+        .collect();
+
+    match op {
+        MemOp::Store => {
+            for (&rt, &addr) in rts.iter().zip(&addrs) {
+                let value = gen_stored_value(bb, rt, ty);
+                bb.push_inst(IrInst::Store {
+                    dst: addr,
+                    src: value,
+                });
+            }
+        }
+        MemOp::Load | MemOp::LoadSigned32 | MemOp::LoadSigned64 => {
+            // Every element is loaded before any register is written, a faulting access leaves
+            // the registers untouched.
+            let values: Vec<_> = addrs
+                .iter()
+                .map(|&addr| gen_value(bb, ty, |dst| IrInst::Load { dst, src: addr }))
+                .collect();
+
+            for (&rt, &value) in rts.iter().zip(&values) {
+                gen_assign_loaded(bb, op, rt, value);
+            }
+        }
+    }
+
+    if indexing != Indexing::Offset {
+        gen_assign(bb, base, offset_addr);
+    }
+}
+
+/// The `ty` sized value of `rt` a store writes to memory.
+fn gen_stored_value(bb: &mut BasicBlock, rt: AArch64RegisterId, ty: IrType) -> IrValue {
+    match transfer_view(rt, ty) {
+        view if view == ty => reg(rt, ty),
+        view => gen_zext(bb, reg(rt, view), ty),
+    }
+}
+
+/// Write the `value` a load of `op` read from memory to `rt`, extended to the register.
+fn gen_assign_loaded(bb: &mut BasicBlock, op: MemOp, rt: AArch64RegisterId, value: IrValue) {
+    let value = match op {
+        MemOp::LoadSigned32 => gen_sext(bb, value, IrType::U32),
+        MemOp::LoadSigned64 => gen_sext(bb, value, IrType::U64),
+        _ => match transfer_view(rt, value.ty()) {
+            view if view == value.ty() => value,
+            view => gen_zext(bb, value, view),
+        },
+    };
+    gen_assign(bb, reg(rt, value.ty()), value);
+}
+
+/// LDR and STR with an unsigned scaled offset, pre-indexed or post-indexed.
+fn compile_ld_st_imm(bb: &mut BasicBlock, operand: &OpcSizeImm12RnRt, op: MemOp, ty: IrType) {
+    let (offset, indexing) = match (operand.idxt, operand.imm12 & 0b11) {
+        (0b01, _) => (
+            (operand.imm12 as u64) << ty.size_in_bytes().trailing_zeros(),
+            Indexing::Offset,
+        ),
+        (_, 0b11) => (
+            sign_extend(operand.imm12 as u64 >> 2, 9),
+            Indexing::PreIndex,
+        ),
+        _ => (
+            sign_extend(operand.imm12 as u64 >> 2, 9),
+            Indexing::PostIndex,
+        ),
+    };
+
+    let offset = imm(IrType::U64, offset);
+    gen_load_store(bb, op, ty, &[operand.rt], operand.rn, offset, indexing);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// LDR and STR with a register offset, extended and optionally scaled by the access size.
+fn compile_ld_st_reg(bb: &mut BasicBlock, operand: &LoadStoreRegRegOffset, op: MemOp, ty: IrType) {
+    // Only the word and doubleword extensions are allocated
+    if operand.option & 0b010 == 0 {
+        compiler_prelude::gen_undefined(bb);
+        return;
+    }
+
+    let shift = match operand.s {
+        0b1 => ty.size_in_bytes().trailing_zeros() as u8,
+        _ => 0,
+    };
+    let offset = gen_extended_reg(bb, operand.rm, operand.option, shift, IrType::U64);
+    gen_load_store(
+        bb,
+        op,
+        ty,
+        &[operand.rt],
+        operand.rn,
+        offset,
+        Indexing::Offset,
+    );
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The SIMD&FP register offset forms, their size is encoded in `size` and `opc<1>`.
+fn compile_ld_st_reg_simd_fp(bb: &mut BasicBlock, operand: &LoadStoreRegRegOffset, op: MemOp) {
+    let ty = match (operand.size, operand.opc & 0b10) {
+        (0b00, 0b10) => IrType::U128,
+        (0b00, _) => IrType::U8,
+        (0b01, _) => IrType::U16,
+        (0b10, _) => IrType::U32,
+        _ => IrType::U64,
+    };
+
+    compile_ld_st_reg(bb, operand, op, ty);
+}
+
+fn gen_ld_st_imm9(
+    bb: &mut BasicBlock,
+    imm9: u16,
+    rn: AArch64RegisterId,
+    rt: AArch64RegisterId,
+    op: MemOp,
+    ty: IrType,
+) {
+    let offset = imm(IrType::U64, sign_extend(imm9 as u64, 9));
+    gen_load_store(bb, op, ty, &[rt], rn, offset, Indexing::Offset);
+}
+
+/// LDUR and STUR, with an unscaled signed offset.
+fn compile_ld_st_unscaled(
+    bb: &mut BasicBlock,
+    operand: &LdStRegUnscaledImm,
+    op: MemOp,
+    ty: IrType,
+) {
+    gen_ld_st_imm9(bb, operand.imm9, operand.rn, operand.rt, op, ty);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// LDTR and STTR, unscaled accesses made with EL0 permissions.
+fn compile_ld_st_unprivileged(bb: &mut BasicBlock, operand: &Imm9RnRt, op: MemOp, ty: IrType) {
+    // The accesses are translated in the unprivileged regime, which is the current one at EL0
+    let offset = imm(IrType::U64, sign_extend(operand.imm9 as u64, 9));
+    let addr = gen_add(bb, reg(operand.rn, IrType::U64), offset);
+
+    match op {
+        MemOp::Store => {
+            let value = gen_stored_value(bb, operand.rt, ty);
+            bb.push_inst(IrInst::StoreUnprivileged {
+                dst: addr,
+                src: value,
+            });
+        }
+        _ => {
+            let value = gen_value(bb, ty, |dst| IrInst::LoadUnprivileged { dst, src: addr });
+            gen_assign_loaded(bb, op, operand.rt, value);
+        }
+    }
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// LDAPUR and STLUR, unscaled accesses with acquire or release ordering.
+fn compile_ld_st_ordered_unscaled(bb: &mut BasicBlock, operand: &Imm9RnRt, op: MemOp, ty: IrType) {
+    if op == MemOp::Store {
+        bb.push_inst(IrInst::Fence {
+            ordering: IrOrdering::Release,
+        });
+    }
+
+    gen_ld_st_imm9(bb, operand.imm9, operand.rn, operand.rt, op, ty);
+
+    if op != MemOp::Store {
+        bb.push_inst(IrInst::Fence {
+            ordering: IrOrdering::Acquire,
+        });
+    }
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The ordering of the acquire and release forms of the atomic instructions.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MemOrdering {
+    Relaxed,
+    Acquire,
+    Release,
+    AcquireRelease,
+}
+
+impl MemOrdering {
+    /// The fence before the access, later accesses can't be reordered before a release.
+    fn gen_before(self, bb: &mut BasicBlock) {
+        if matches!(self, MemOrdering::Release | MemOrdering::AcquireRelease) {
+            bb.push_inst(IrInst::Fence {
+                ordering: IrOrdering::Release,
+            });
+        }
+    }
+
+    /// The fence after the access, earlier accesses can't be reordered after an acquire.
+    fn gen_after(self, bb: &mut BasicBlock) {
+        if matches!(self, MemOrdering::Acquire | MemOrdering::AcquireRelease) {
+            bb.push_inst(IrInst::Fence {
+                ordering: IrOrdering::Acquire,
+            });
+        }
+    }
+}
+
+/// The operations of the LSE atomic instructions, LD<op> and SWP.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AtomicMemOp {
+    Add,
+    Clear,
+    Xor,
+    Set,
+    SignedMax,
+    SignedMin,
+    UnsignedMax,
+    UnsignedMin,
+    Swap,
+}
+
+/// The low `ty` bits of the general purpose register `rt`.
+fn gen_read_transfer(bb: &mut BasicBlock, rt: AArch64RegisterId, ty: IrType) -> IrValue {
+    match transfer_view(rt, ty) {
+        view if view == ty => reg(rt, ty),
+        view => gen_zext(bb, reg(rt, view), ty),
+    }
+}
+
+/// Write `value` zero-extended to the general purpose register `rt`.
+fn gen_write_transfer(bb: &mut BasicBlock, rt: AArch64RegisterId, value: IrValue) {
+    let view = transfer_view(rt, uint(bits(value.ty())));
+    let value = match value.ty() == view {
+        true => value,
+        false => gen_zext(bb, value, view),
+    };
+    gen_assign(bb, reg(rt, view), value);
+}
+
+/// The registers `rts` of `ty` concatenated, the first one in the low bits. A pair is
+/// accessed as a single value of twice the width.
+fn gen_read_pair(bb: &mut BasicBlock, rts: &[AArch64RegisterId], ty: IrType) -> IrValue {
+    let wide = uint(bits(ty) * rts.len() as u64);
+    let mut value = gen_zext(bb, reg(rts[0], ty), wide);
+    for (i, &rt) in rts.iter().enumerate().skip(1) {
+        let part = gen_zext(bb, reg(rt, ty), wide);
+        let shift = gen_imm(bb, wide, i as u64 * bits(ty));
+        let part = gen_shl(bb, part, shift);
+        value = gen_or(bb, value, part);
+    }
+    value
+}
+
+/// Split `value` over the registers `rts`, the first one gets the low bits.
+fn gen_write_pair(bb: &mut BasicBlock, rts: &[AArch64RegisterId], value: IrValue) {
+    let ty = uint(bits(value.ty()) / rts.len() as u64);
+    for (i, &rt) in rts.iter().enumerate() {
+        let shift = gen_imm(bb, value.ty(), i as u64 * bits(ty));
+        let part = gen_shr(bb, value, shift);
+        let part = gen_zext(bb, part, ty);
+        gen_assign(bb, reg(rt, ty), part);
+    }
+}
+
+/// LDXR, LDAXR, LDXP and LDAXP, loads that set the exclusive monitors for a following
+/// exclusive store. A pair is a single access of twice the width.
+fn compile_load_exclusive(
+    bb: &mut BasicBlock,
+    rts: &[AArch64RegisterId],
+    rn: AArch64RegisterId,
+    ty: IrType,
+    ordering: MemOrdering,
+) {
+    let addr = reg(rn, IrType::U64);
+    let wide = uint(bits(ty) * rts.len() as u64);
+    let value = gen_value(bb, wide, |dst| IrInst::LoadExclusive { dst, src: addr });
+
+    match rts {
+        [rt] => gen_write_transfer(bb, *rt, value),
+        rts => gen_write_pair(bb, rts, value),
+    }
+    ordering.gen_after(bb);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// STXR, STLXR, STXP and STLXP, stores that only happen if the exclusive monitors still hold
+/// the reservation of the last exclusive load. `Ws` is 0 if the store happened and 1 if not.
+fn compile_store_exclusive(
+    bb: &mut BasicBlock,
+    rs: AArch64RegisterId,
+    rts: &[AArch64RegisterId],
+    rn: AArch64RegisterId,
+    ty: IrType,
+    ordering: MemOrdering,
+) {
+    ordering.gen_before(bb);
+
+    let addr = reg(rn, IrType::U64);
+    let value = match rts {
+        [rt] => gen_read_transfer(bb, *rt, ty),
+        rts => gen_read_pair(bb, rts, ty),
+    };
+    let stored = gen_value(bb, IrType::Bool, |status| IrInst::StoreExclusive {
+        dst: addr,
+        src: value,
+        status,
+    });
+
+    let stored = gen_zext(bb, stored, IrType::U32);
+    let failed = gen_xor(bb, stored, imm(IrType::U32, 1));
+    gen_assign(bb, reg(rs, IrType::U32), failed);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// CLREX, clears the local exclusive monitor so that the next exclusive store fails.
+fn compile_clrex(bb: &mut BasicBlock) {
+    bb.push_inst(IrInst::ClearExclusive);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// LDAR and LDAPR, loads with acquire ordering.
+fn compile_load_acquire(
+    bb: &mut BasicBlock,
+    rt: AArch64RegisterId,
+    rn: AArch64RegisterId,
+    ty: IrType,
+) {
+    let offset = imm(IrType::U64, 0);
+    gen_load_store(bb, MemOp::Load, ty, &[rt], rn, offset, Indexing::Offset);
+    MemOrdering::Acquire.gen_after(bb);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// STLR, a store with release ordering.
+fn compile_store_release(
+    bb: &mut BasicBlock,
+    rt: AArch64RegisterId,
+    rn: AArch64RegisterId,
+    ty: IrType,
+) {
+    MemOrdering::Release.gen_before(bb);
+    let offset = imm(IrType::U64, 0);
+    gen_load_store(bb, MemOp::Store, ty, &[rt], rn, offset, Indexing::Offset);
+
+    // A release is sequentially consistent with the acquires of LDAR, which can't be reordered
+    // before it.
+    bb.push_inst(IrInst::Fence {
+        ordering: IrOrdering::SeqCst,
+    });
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// LD<op> and SWP, atomically combine `Rs` with the memory and load the old value into `Rt`.
+/// The ST<op> aliases are the forms loading into the zero register.
+fn compile_atomic(
+    bb: &mut BasicBlock,
+    operand: &RsRnRt,
+    op: AtomicMemOp,
+    ty: IrType,
+    ordering: MemOrdering,
+) {
+    ordering.gen_before(bb);
+
+    let addr = reg(operand.rn, IrType::U64);
+    let src = gen_read_transfer(bb, operand.rs, ty);
+    let (op, src) = match op {
+        AtomicMemOp::Add => (AtomicOp::Add, src),
+        AtomicMemOp::Clear => (AtomicOp::And, gen_not(bb, src)),
+        AtomicMemOp::Xor => (AtomicOp::Xor, src),
+        AtomicMemOp::Set => (AtomicOp::Or, src),
+        AtomicMemOp::SignedMax => (AtomicOp::Max, gen_zext(bb, src, signed(ty))),
+        AtomicMemOp::SignedMin => (AtomicOp::Min, gen_zext(bb, src, signed(ty))),
+        AtomicMemOp::UnsignedMax => (AtomicOp::Max, src),
+        AtomicMemOp::UnsignedMin => (AtomicOp::Min, src),
+        AtomicMemOp::Swap => (AtomicOp::Swap, src),
+    };
+    let old = gen_value(bb, src.ty(), |dst| IrInst::AtomicRmw { op, dst, addr, src });
+
+    gen_write_transfer(bb, operand.rt, old);
+    ordering.gen_after(bb);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// CAS, stores `Rt` if the memory holds `Rs` and loads the old value into `Rs`.
+fn compile_cas(bb: &mut BasicBlock, operand: &RsRnRt, ty: IrType, ordering: MemOrdering) {
+    ordering.gen_before(bb);
+
+    let addr = reg(operand.rn, IrType::U64);
+    let expected = gen_read_transfer(bb, operand.rs, ty);
+    let src = gen_read_transfer(bb, operand.rt, ty);
+    let old = gen_value(bb, ty, |dst| IrInst::CompareExchange {
+        dst,
+        addr,
+        expected,
+        src,
+    });
+
+    gen_write_transfer(bb, operand.rs, old);
+    ordering.gen_after(bb);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// CASP, the pair form of CAS on the even and odd registers starting at `Rs` and `Rt`,
+/// compared and stored as a single value of twice the width.
+fn compile_casp(
+    bb: &mut BasicBlock,
+    operand: &CompareAndSwapPair,
+    ty: IrType,
+    ordering: MemOrdering,
+) {
+    // Odd registers are constrained unpredictable, this is the undefined choice.
+    if !operand.rs.is_multiple_of(2) || !operand.rt.is_multiple_of(2) {
+        compiler_prelude::gen_undefined(bb);
+        return;
+    }
+
+    let pair = |first: u8| {
+        [first, first + 1]
+            .map(|raw| AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, raw))
+    };
+    let (rss, rts) = (pair(operand.rs), pair(operand.rt));
+    ordering.gen_before(bb);
+
+    let addr = reg(operand.rn, IrType::U64);
+    let expected = gen_read_pair(bb, &rss, ty);
+    let src = gen_read_pair(bb, &rts, ty);
+    let old = gen_value(bb, expected.ty(), |dst| IrInst::CompareExchange {
+        dst,
+        addr,
+        expected,
+        src,
+    });
+
+    gen_write_pair(bb, &rss, old);
+    ordering.gen_after(bb);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// LDP and STP, the signed offset is scaled by the size of one register.
+fn compile_ld_st_pair(bb: &mut BasicBlock, operand: &LoadStoreRegPair, op: MemOp, ty: IrType) {
+    let indexing = match operand.o {
+        0b011 => Indexing::PreIndex,
+        0b001 => Indexing::PostIndex,
+        _ => Indexing::Offset,
+    };
+    let offset = sign_extend(operand.imm7 as u64, 7) << ty.size_in_bytes().trailing_zeros();
+
+    let rts = [operand.rt, operand.rt2];
+    gen_load_store(
+        bb,
+        op,
+        ty,
+        &rts,
+        operand.rn,
+        imm(IrType::U64, offset),
+        indexing,
+    );
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// LDNP and STNP, the non-temporal hint has no effect.
+fn compile_ld_st_no_alloc_pair(
+    bb: &mut BasicBlock,
+    operand: &LdStNoAllocPairOffset,
+    op: MemOp,
+    ty: IrType,
+) {
+    let offset = sign_extend(operand.imm7 as u64, 7) << ty.size_in_bytes().trailing_zeros();
+
+    let rts = [operand.rt, operand.rt2];
+    let offset = imm(IrType::U64, offset);
+    gen_load_store(bb, op, ty, &rts, operand.rn, offset, Indexing::Offset);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// LDR (literal), loads from the instruction address plus a word offset.
+fn compile_ldr_lit(bb: &mut BasicBlock, operand: &Imm19Rt, op: MemOp, ty: IrType) {
+    let offset = imm(IrType::U64, sign_extend(operand.imm19 as u64, 19) << 2);
+    let pc = AArch64RegisterId::Pc;
+    gen_load_store(bb, op, ty, &[operand.rt], pc, offset, Indexing::Offset);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The operands of the AdvSIMD structure loads and stores, `rm` is the post-index register of
+/// the post-indexed forms and XZR for their immediate post-index.
+#[derive(Clone, Copy)]
+struct StructureOperand {
+    q: u8,
+    s: u8,
+    size: u8,
+    rn: AArch64RegisterId,
+    rt: u8,
+    rm: Option<AArch64RegisterId>,
+}
+
+impl From<&AdvSimdLdStMultiStructures> for StructureOperand {
+    fn from(operand: &AdvSimdLdStMultiStructures) -> Self {
+        Self {
+            q: operand.q,
+            s: 0,
+            size: operand.size,
+            rn: operand.rn,
+            rt: operand.rt,
+            rm: None,
+        }
+    }
+}
+
+impl From<&AdvSimdLdStMultiStructuresPostIndexed> for StructureOperand {
+    fn from(operand: &AdvSimdLdStMultiStructuresPostIndexed) -> Self {
+        Self {
+            q: operand.q,
+            s: 0,
+            size: operand.size,
+            rn: operand.rn,
+            rt: operand.rt,
+            rm: Some(operand.rm),
+        }
+    }
+}
+
+impl From<&AdvSimdLdStSingleStructure> for StructureOperand {
+    fn from(operand: &AdvSimdLdStSingleStructure) -> Self {
+        Self {
+            q: operand.q,
+            s: operand.s,
+            size: operand.size,
+            rn: operand.rn,
+            rt: operand.rt,
+            rm: None,
+        }
+    }
+}
+
+impl From<&AdvSimdLdStSingleStructurePostIndexed> for StructureOperand {
+    fn from(operand: &AdvSimdLdStSingleStructurePostIndexed) -> Self {
+        Self {
+            q: operand.q,
+            s: operand.s,
+            size: operand.size,
+            rn: operand.rn,
+            rt: operand.rt,
+            rm: Some(operand.rm),
+        }
+    }
+}
+
+/// The `count` registers of a structure transfer, consecutive from `rt` modulo 32.
+fn structure_registers(rt: u8, count: u64) -> Vec<AArch64RegisterId> {
+    (0..count as u8)
+        .map(|i| AArch64RegisterId::V((rt + i) % 32))
+        .collect()
+}
+
+/// The addresses of `count` consecutive elements of `size` bytes at the base register.
+fn gen_element_addresses(
+    bb: &mut BasicBlock,
+    rn: AArch64RegisterId,
+    count: u64,
+    size: u64,
+) -> Vec<IrValue> {
+    let base = reg(rn, IrType::U64);
+    (0..count)
+        .map(|i| gen_add(bb, base, imm(IrType::U64, i * size)))
+        .collect()
+}
+
+fn gen_load_elements(bb: &mut BasicBlock, ty: IrType, addrs: &[IrValue]) -> Vec<IrValue> {
+    addrs
+        .iter()
+        .map(|&addr| gen_value(bb, ty, |dst| IrInst::Load { dst, src: addr }))
+        .collect()
+}
+
+/// Add the post-index of the post-indexed forms to the base register, the immediate post-index
+/// is the number of bytes transferred.
+fn gen_structure_writeback(bb: &mut BasicBlock, operand: &StructureOperand, size: u64) {
+    let offset = match operand.rm {
+        None => return,
+        Some(AArch64RegisterId::Xzr) => imm(IrType::U64, size),
+        Some(rm) => reg(rm, IrType::U64),
+    };
+
+    let base = reg(operand.rn, IrType::U64);
+    let value = gen_add(bb, base, offset);
+    gen_assign(bb, base, value);
+}
+
+/// LD1-LD4 and ST1-ST4 (multiple structures), the elements of a structure are interleaved
+/// across `selem` registers and LD1 and ST1 repeat the transfer for `rpt` registers.
+fn compile_ld_st_multiple(
+    bb: &mut BasicBlock,
+    operand: StructureOperand,
+    op: MemOp,
+    selem: u64,
+    rpt: u64,
+) {
+    let ty = uint(64 << operand.q);
+    let esize = 8 << operand.size;
+    let count = bits(ty) / esize;
+    // Only LD1 and ST1 have a 1D arrangement
+    if selem > 1 && count == 1 {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    let rts = structure_registers(operand.rt, selem * rpt);
+    if selem == 1 {
+        // Without interleaving every register is a contiguous access
+        let offset = imm(IrType::U64, 0);
+        gen_load_store(bb, op, ty, &rts, operand.rn, offset, Indexing::Offset);
+    } else {
+        // Element e of register s is at e * selem + s
+        let addrs = gen_element_addresses(bb, operand.rn, count * selem, esize / 8);
+        match op {
+            MemOp::Store => {
+                let registers: Vec<_> = rts
+                    .iter()
+                    .map(|&rt| gen_split(bb, reg(rt, ty), esize))
+                    .collect();
+                for (i, &addr) in addrs.iter().enumerate() {
+                    let src = registers[i % selem as usize][i / selem as usize];
+                    bb.push_inst(IrInst::Store { dst: addr, src });
+                }
+            }
+            _ => {
+                let values = gen_load_elements(bb, uint(esize), &addrs);
+                for (s, &rt) in rts.iter().enumerate() {
+                    let elements: Vec<_> = values
+                        .iter()
+                        .skip(s)
+                        .step_by(selem as usize)
+                        .copied()
+                        .collect();
+                    gen_write_elements(bb, rt, &elements);
+                }
+            }
+        }
+    }
+
+    gen_structure_writeback(bb, &operand, selem * rpt * bits(ty) / 8);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// LD1-LD4 and ST1-ST4 (single structure), transfer one `esize` bit element of `selem`
+/// consecutive registers, the other elements are left unchanged.
+fn compile_ld_st_single(
+    bb: &mut BasicBlock,
+    operand: StructureOperand,
+    op: MemOp,
+    selem: u64,
+    esize: u64,
+) {
+    // The index is in Q:S:size without the bits of size below the element size
+    let index = (operand.q << 3 | operand.s << 2 | operand.size) >> (esize / 8).trailing_zeros();
+    let index = index as usize;
+
+    let rts = structure_registers(operand.rt, selem);
+    let addrs = gen_element_addresses(bb, operand.rn, selem, esize / 8);
+    match op {
+        MemOp::Store => {
+            for (&rt, &addr) in rts.iter().zip(&addrs) {
+                let src = gen_split(bb, reg(rt, IrType::U128), esize)[index];
+                bb.push_inst(IrInst::Store { dst: addr, src });
+            }
+        }
+        _ => {
+            let values = gen_load_elements(bb, uint(esize), &addrs);
+            for (&rt, &value) in rts.iter().zip(&values) {
+                let mut elements = gen_split(bb, reg(rt, IrType::U128), esize);
+                elements[index] = value;
+                gen_write_elements(bb, rt, &elements);
+            }
+        }
+    }
+
+    gen_structure_writeback(bb, &operand, selem * esize / 8);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// LD1R-LD4R, load a single structure and replicate each element to all the lanes of its
+/// register.
+fn compile_ld_replicate(bb: &mut BasicBlock, operand: StructureOperand, selem: u64) {
+    let esize = 8 << operand.size;
+    let count = (64 << operand.q) / esize;
+
+    let rts = structure_registers(operand.rt, selem);
+    let addrs = gen_element_addresses(bb, operand.rn, selem, esize / 8);
+    let values = gen_load_elements(bb, uint(esize), &addrs);
+    for (&rt, &value) in rts.iter().zip(&values) {
+        gen_write_elements(bb, rt, &vec![value; count as usize]);
+    }
+
+    gen_structure_writeback(bb, &operand, selem * esize / 8);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// Continue at `target`, which must not depend on registers written by the instruction.
+fn gen_branch(bb: &mut BasicBlock, target: IrValue) {
+    gen_assign(bb, reg(AArch64RegisterId::Pc, IrType::U64), target);
+    bb.set_terminator(BasicBlockTerminator::Branch(target));
+}
+
+/// Continue at `target` if `cond`, a U64 of 0 or 1, is set and at the next instruction
+/// otherwise.
+fn gen_branch_cond(bb: &mut BasicBlock, cond: IrValue, target: IrValue) {
+    let pc = reg(AArch64RegisterId::Pc, IrType::U64);
+
+    // next + ((target - next) & -cond)
+    let next = gen_add(bb, pc, imm(IrType::U64, 4));
+    let distance = gen_sub(bb, target, next);
+    let mask = compiler_prelude::gen_neg(bb, cond);
+    let taken = gen_and(bb, distance, mask);
+    let new_pc = gen_add(bb, next, taken);
+    gen_assign(bb, pc, new_pc);
+
+    let cond = gen_zext(bb, cond, IrType::Bool);
+    bb.set_terminator(BasicBlockTerminator::BranchCond { cond, target });
+}
+
+/// The address of the instruction plus the signed word offset `field` of `width` bits.
+fn gen_pc_relative(bb: &mut BasicBlock, field: u32, width: u32) -> IrValue {
+    let offset = sign_extend(field as u64, width) << 2;
+    let pc = reg(AArch64RegisterId::Pc, IrType::U64);
+    gen_add(bb, pc, imm(IrType::U64, offset))
+}
+
+/// Write the return address, the instruction following the branch, to X30.
+fn gen_link(bb: &mut BasicBlock) {
+    let pc = reg(AArch64RegisterId::Pc, IrType::U64);
+    let ret = gen_add(bb, pc, imm(IrType::U64, 4));
+    gen_assign(bb, reg(AArch64RegisterId::X(30), IrType::U64), ret);
+}
+
+fn compile_b_imm(bb: &mut BasicBlock, operand: &Imm26) {
+    let target = gen_pc_relative(bb, operand.imm26, 26);
+    gen_branch(bb, target);
+}
+
+fn compile_bl_imm(bb: &mut BasicBlock, operand: &Imm26) {
+    let target = gen_pc_relative(bb, operand.imm26, 26);
+    gen_link(bb);
+    gen_branch(bb, target);
+}
+
+fn compile_br(bb: &mut BasicBlock, operand: &UncondBranchReg) {
+    gen_branch(bb, reg(operand.rn, IrType::U64));
+}
+
+fn compile_blr(bb: &mut BasicBlock, operand: &UncondBranchReg) {
+    // The target is read before X30 is written, `blr x30` branches to the old value
+    let rn = reg(operand.rn, IrType::U64);
+    let target = gen_value(bb, IrType::U64, |dst| IrInst::Assign { dst, src: rn });
+    gen_link(bb);
+    gen_branch(bb, target);
+}
+
+fn compile_ret(bb: &mut BasicBlock, operand: &UncondBranchReg) {
+    gen_branch(bb, reg(operand.rn, IrType::U64));
+}
+
+/// ERET, returns from an exception, see [`exception::gen_return`].
+fn compile_eret(bb: &mut BasicBlock) {
+    sysreg::gen_check_el(bb, 1);
+    exception::gen_return(bb);
+}
+
+/// B.cond and BC.cond, whose hint that the branch is consistent has no effect.
+fn compile_b_cond(bb: &mut BasicBlock, operand: &Imm19Cond) {
+    let target = gen_pc_relative(bb, operand.imm19, 19);
+    let cond = compiler_prelude::gen_condition_holds(bb, operand.cond);
+    gen_branch_cond(bb, cond, target);
+}
+
+/// CBZ and CBNZ, branch if `rt` is zero or not zero.
+fn compile_cbz(bb: &mut BasicBlock, operand: &Imm19Rt, ty: IrType, non_zero: bool) {
+    let target = gen_pc_relative(bb, operand.imm19, 19);
+    let zero = compiler_prelude::gen_is_zero(bb, reg(operand.rt, ty));
+    let zero = gen_zext(bb, zero, IrType::U64);
+    let cond = match non_zero {
+        true => gen_xor(bb, zero, imm(IrType::U64, 1)),
+        false => zero,
+    };
+    gen_branch_cond(bb, cond, target);
+}
+
+/// TBZ and TBNZ, branch if the bit `b5:b40` of `rt` is zero or not zero.
+fn compile_tbz(bb: &mut BasicBlock, operand: &B5B40Imm14Rt, non_zero: bool) {
+    let target = gen_pc_relative(bb, operand.imm14 as u32, 14);
+    let bit = ((operand.b5 as u64) << 5) | operand.b40 as u64;
+    let shifted = gen_shr(bb, reg(operand.rt, IrType::U64), imm(IrType::U64, bit));
+    let set = gen_and(bb, shifted, imm(IrType::U64, 1));
+    let cond = match non_zero {
+        true => set,
+        false => gen_xor(bb, set, imm(IrType::U64, 1)),
+    };
+    gen_branch_cond(bb, cond, target);
+}
+
+/// Sets NZCV from `rn` compared with `operand2` if `cond` holds and to the `nzcv` field if not.
+/// CCMP compares by subtraction and CCMN by addition.
+fn gen_cond_cmp(
+    bb: &mut BasicBlock,
+    rn: IrValue,
+    operand2: IrValue,
+    cond: u8,
+    nzcv: u8,
+    sub: bool,
+) {
+    let ty = rn.ty();
+    // The condition is evaluated on the flags before the comparison overwrites them
+    let holds = compiler_prelude::gen_condition_holds(bb, cond);
+
+    let (operand2, carry) = match sub {
+        true => (gen_not(bb, operand2), imm(ty, 1)),
+        false => (operand2, imm(ty, 0)),
+    };
+    gen_add_with_carry(bb, rn, operand2, carry, true);
+
+    let flags = reg(AArch64RegisterId::Nzcv, IrType::U64);
+    let fallback = imm(
+        IrType::U64,
+        (nzcv as u64 & 0xf) << compiler_prelude::nzcv::V,
+    );
+    let result = compiler_prelude::gen_select(bb, holds, flags, fallback);
+    gen_assign(bb, flags, result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_ccmp_imm(bb: &mut BasicBlock, operand: &CondCmpImm, ty: IrType, sub: bool) {
+    let rn = reg(operand.rn, ty);
+    let imm5 = imm(ty, operand.imm5 as u64);
+    gen_cond_cmp(bb, rn, imm5, operand.cond, operand.nzcv, sub);
+}
+
+fn compile_ccmp_reg(bb: &mut BasicBlock, operand: &CondCmpReg, ty: IrType, sub: bool) {
+    let (rn, rm) = (reg(operand.rn, ty), reg(operand.rm, ty));
+    gen_cond_cmp(bb, rn, rm, operand.cond, operand.nzcv, sub);
+}
+
+#[derive(Clone, Copy)]
+enum CondSelOp {
+    Csel,
+    Csinc,
+    Csinv,
+    Csneg,
+}
+
+/// `rn` if `cond` holds, otherwise `rm` as is, incremented, inverted or negated.
+fn compile_csel(bb: &mut BasicBlock, operand: &RmCondRnRd, ty: IrType, op: CondSelOp) {
+    let rm = reg(operand.rm, ty);
+    let otherwise = match op {
+        CondSelOp::Csel => rm,
+        CondSelOp::Csinc => gen_add(bb, rm, imm(ty, 1)),
+        CondSelOp::Csinv => gen_not(bb, rm),
+        CondSelOp::Csneg => compiler_prelude::gen_neg(bb, rm),
+    };
+
+    let holds = compiler_prelude::gen_condition_holds(bb, operand.cond);
+    let result = compiler_prelude::gen_select(bb, holds, reg(operand.rn, ty), otherwise);
+    gen_assign(bb, reg(operand.rd, ty), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_cfinv(bb: &mut BasicBlock) {
+    let nzcv = reg(AArch64RegisterId::Nzcv, IrType::U64);
+    let inverted = gen_xor(bb, nzcv, imm(IrType::U64, 1 << compiler_prelude::nzcv::C));
+    gen_assign(bb, nzcv, inverted);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// Convert the flags of a floating point comparison from the Arm format to the
+/// alternative one.
+fn compile_axflag(bb: &mut BasicBlock) {
+    use compiler_prelude::{gen_flag, nzcv};
+
+    let (z, c, v) = (
+        gen_flag(bb, nzcv::Z),
+        gen_flag(bb, nzcv::C),
+        gen_flag(bb, nzcv::V),
+    );
+    let new_z = gen_or(bb, z, v);
+    let not_v = gen_xor(bb, v, imm(IrType::U64, 1));
+    let new_c = gen_and(bb, c, not_v);
+
+    let zero = imm(IrType::U64, 0);
+    compiler_prelude::gen_set_nzcv(bb, zero, new_z, new_c, zero);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// Convert the flags of a floating point comparison from the alternative format back
+/// to the Arm one.
+fn compile_xaflag(bb: &mut BasicBlock) {
+    use compiler_prelude::{gen_flag, nzcv};
+
+    let (z, c) = (gen_flag(bb, nzcv::Z), gen_flag(bb, nzcv::C));
+    let not_c = gen_xor(bb, c, imm(IrType::U64, 1));
+    let not_z = gen_xor(bb, z, imm(IrType::U64, 1));
+
+    let n = gen_and(bb, not_c, not_z);
+    let new_z = gen_and(bb, z, c);
+    let new_c = gen_or(bb, c, z);
+    let v = gen_and(bb, not_c, z);
+    compiler_prelude::gen_set_nzcv(bb, n, new_z, new_c, v);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// Rotate `rn` right by `imm6` and insert the low four bits into the flags selected by
+/// `mask`.
+fn compile_rmif(bb: &mut BasicBlock, operand: &RotateRightIntoFlags) {
+    let ty = IrType::U64;
+    let rn = reg(operand.rn, ty);
+    let rotated = gen_shift(bb, 0b11, rn, imm(ty, operand.imm6 as u64));
+
+    let mask = (operand.mask as u64) << compiler_prelude::nzcv::V;
+    let shifted = gen_shl(bb, rotated, imm(ty, compiler_prelude::nzcv::V));
+    let inserted = gen_and(bb, shifted, imm(ty, mask));
+
+    let nzcv = reg(AArch64RegisterId::Nzcv, ty);
+    let kept = gen_and(bb, nzcv, imm(ty, !mask));
+    let result = gen_or(bb, kept, inserted);
+    gen_assign(bb, nzcv, result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// Set N, Z and V from the low `width` bits of `rn` taken as a signed value, leaving C
+/// untouched.
+fn compile_setf(bb: &mut BasicBlock, operand: &Rn, width: u64) {
+    let ty = IrType::U64;
+    let rn = reg(operand.rn, ty);
+
+    let msb = gen_shr(bb, rn, imm(ty, width - 1));
+    let n = gen_and(bb, msb, imm(ty, 1));
+    let low = gen_and(bb, rn, imm(ty, (1 << width) - 1));
+    let z = compiler_prelude::gen_is_zero(bb, low);
+    let overflow = gen_shr(bb, rn, imm(ty, width));
+    let overflow = gen_xor(bb, overflow, msb);
+    let v = gen_and(bb, overflow, imm(ty, 1));
+
+    let c = compiler_prelude::gen_flag(bb, compiler_prelude::nzcv::C);
+    compiler_prelude::gen_set_nzcv(bb, n, z, c, v);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The float type of the `ptype` field of the scalar floating-point encodings.
+fn float_type(ptype: u8) -> IrType {
+    match ptype {
+        0b00 => IrType::U32,
+        0b01 => IrType::U64,
+        0b11 => IrType::U16,
+        _ => unreachable!("reserved float type {ptype:#04b}"),
+    }
+}
+
+/// `value` with its sign flipped, the FPNeg of the architecture. NaNs are not special.
+fn gen_float_neg(bb: &mut BasicBlock, value: IrValue) -> IrValue {
+    let ty = value.ty();
+    gen_xor(bb, value, imm(ty, 1 << (bits(ty) - 1)))
+}
+
+/// `ra + rn * rm` rounded once, with the product and the addend negated as requested.
+fn compile_fmadd(
+    bb: &mut BasicBlock,
+    operand: &RmRaRnRd,
+    ty: IrType,
+    negate_product: bool,
+    negate_addend: bool,
+) {
+    let (rn, rm, ra) = (
+        reg(operand.rn, ty),
+        reg(operand.rm, ty),
+        reg(operand.ra, ty),
+    );
+    let rn = match negate_product {
+        true => gen_float_neg(bb, rn),
+        false => rn,
+    };
+    let ra = match negate_addend {
+        true => gen_float_neg(bb, ra),
+        false => ra,
+    };
+
+    let result = compiler_prelude::gen_float(bb, FloatOp::MulAdd, ty, vec![ra, rn, rm]);
+    gen_assign(bb, reg(operand.rd, ty), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// `rn op rm`, negated afterwards for FNMUL.
+fn compile_float_2src(
+    bb: &mut BasicBlock,
+    operand: &RmRnRd,
+    ty: IrType,
+    op: FloatOp,
+    negate: bool,
+) {
+    let (rn, rm) = (reg(operand.rn, ty), reg(operand.rm, ty));
+    let result = compiler_prelude::gen_float(bb, op, ty, vec![rn, rm]);
+    let result = match negate {
+        true => gen_float_neg(bb, result),
+        false => result,
+    };
+
+    gen_assign(bb, reg(operand.rd, ty), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// `op rn` from floats of type `from` to floats of type `to`.
+fn compile_float_1src(bb: &mut BasicBlock, operand: &RnRd, op: FloatOp, from: IrType, to: IrType) {
+    let result = compiler_prelude::gen_float(bb, op, to, vec![reg(operand.rn, from)]);
+    gen_assign(bb, reg(operand.rd, to), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_frint(bb: &mut BasicBlock, operand: &RnRd, ty: IrType, rounding: Option<FloatRounding>) {
+    let op = FloatOp::RoundInt {
+        rounding,
+        exact: false,
+    };
+    compile_float_1src(bb, operand, op, ty, ty);
+}
+
+#[derive(Clone, Copy)]
+enum FloatSignOp {
+    Mov,
+    Abs,
+    Neg,
+}
+
+/// FMOV, FABS and FNEG only touch the sign bit and never raise exceptions.
+fn compile_float_sign(bb: &mut BasicBlock, operand: &RnRd, ty: IrType, op: FloatSignOp) {
+    let rn = reg(operand.rn, ty);
+    let sign = 1 << (bits(ty) - 1);
+    let result = match op {
+        FloatSignOp::Mov => rn,
+        FloatSignOp::Abs => gen_and(bb, rn, imm(ty, !sign)),
+        FloatSignOp::Neg => gen_xor(bb, rn, imm(ty, sign)),
+    };
+
+    gen_assign(bb, reg(operand.rd, ty), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// Compare `rn` with `rm` or zero and set NZCV to 0110 when they are equal, 1000 when `rn`
+/// is less, 0010 when it is greater and 0011 when they are unordered.
+fn compile_fcmp(bb: &mut BasicBlock, operand: &FloatingPointCompare, signaling: bool) {
+    let ty = float_type(operand.ptype);
+    let rn = reg(operand.rn, ty);
+    let rm = match operand.opcode2 & 0b01000 {
+        0 => reg(operand.rm, ty),
+        _ => imm(ty, 0),
+    };
+
+    let u64 = IrType::U64;
+    let order = compiler_prelude::gen_float(bb, FloatOp::Compare { signaling }, u64, vec![rn, rm]);
+    let position = gen_shl(bb, order, imm(u64, 2));
+    let flags = gen_shr(bb, imm(u64, 0x3286), position);
+    let flags = gen_and(bb, flags, imm(u64, 0xf));
+    let flags = gen_shl(bb, flags, imm(u64, compiler_prelude::nzcv::V));
+    gen_assign(bb, reg(AArch64RegisterId::Nzcv, u64), flags);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_fmov_imm(bb: &mut BasicBlock, operand: &FloatingPointImmediate, ty: IrType) {
+    let value = compiler_prelude::vfp_expand_imm(operand.imm8, bits(ty));
+    gen_assign(bb, reg(operand.rd, ty), imm(ty, value));
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// Convert between the float and the integer registers, `from` and `to` are the types of
+/// `rn` and `rd`.
+fn gen_convert(
+    bb: &mut BasicBlock,
+    op: FloatOp,
+    rn: AArch64RegisterId,
+    rd: AArch64RegisterId,
+    from: IrType,
+    to: IrType,
+) {
+    let result = compiler_prelude::gen_float(bb, op, to, vec![reg(rn, from)]);
+    gen_assign(bb, reg(rd, to), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_fcvt_int(
+    bb: &mut BasicBlock,
+    operand: &RnRd,
+    from: IrType,
+    to: IrType,
+    signed: bool,
+    rounding: FloatRounding,
+) {
+    let op = FloatOp::ToInt {
+        signed,
+        fbits: 0,
+        rounding: Some(rounding),
+    };
+    gen_convert(bb, op, operand.rn, operand.rd, from, to);
+}
+
+fn compile_cvtf_int(bb: &mut BasicBlock, operand: &RnRd, from: IrType, to: IrType, signed: bool) {
+    let op = FloatOp::FromInt { signed, fbits: 0 };
+    gen_convert(bb, op, operand.rn, operand.rd, from, to);
+}
+
+/// The fraction bits of a fixed-point conversion, `None` when they don't fit the integer.
+fn fixed_point_fbits(operand: &ConvBetweenFloatAndFixedPoint, int: IrType) -> Option<u8> {
+    let fbits = 64 - operand.scale;
+    (fbits as u64 <= bits(int)).then_some(fbits)
+}
+
+fn compile_fcvt_fixed(
+    bb: &mut BasicBlock,
+    operand: &ConvBetweenFloatAndFixedPoint,
+    from: IrType,
+    to: IrType,
+    signed: bool,
+) {
+    let Some(fbits) = fixed_point_fbits(operand, to) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let op = FloatOp::ToInt {
+        signed,
+        fbits,
+        rounding: Some(FloatRounding::TowardZero),
+    };
+    gen_convert(bb, op, operand.rn, operand.rd, from, to);
+}
+
+fn compile_cvtf_fixed(
+    bb: &mut BasicBlock,
+    operand: &ConvBetweenFloatAndFixedPoint,
+    from: IrType,
+    to: IrType,
+    signed: bool,
+) {
+    let Some(fbits) = fixed_point_fbits(operand, from) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let op = FloatOp::FromInt { signed, fbits };
+    gen_convert(bb, op, operand.rn, operand.rd, from, to);
+}
+
+/// Move the raw bits between a general and a float register.
+fn compile_fmov_general(bb: &mut BasicBlock, operand: &RnRd, ty: IrType) {
+    gen_assign(bb, reg(operand.rd, ty), reg(operand.rn, ty));
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_fmov_from_top_half(bb: &mut BasicBlock, operand: &RnRd) {
+    let half = gen_zext(bb, imm(IrType::U64, 64), IrType::U128);
+    let top = gen_shr(bb, reg(operand.rn, IrType::U128), half);
+    bb.push_inst(IrInst::ZextCast {
+        dst: reg(operand.rd, IrType::U64),
+        src: top,
+    });
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// Write the top half of the vector register, keeping the bottom half.
+fn compile_fmov_to_top_half(bb: &mut BasicBlock, operand: &RnRd) {
+    let ty = IrType::U128;
+    let half = gen_zext(bb, imm(IrType::U64, 64), ty);
+    let rn = gen_zext(bb, reg(operand.rn, IrType::U64), ty);
+    let top = gen_shl(bb, rn, half);
+
+    let bottom = gen_zext(bb, reg(operand.rd, IrType::U64), ty);
+    let result = gen_or(bb, bottom, top);
+    gen_assign(bb, reg(operand.rd, ty), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The elements of a vector operand, `count` elements of `esize` bits.
+#[derive(Clone, Copy)]
+struct Arrangement {
+    esize: u64,
+    count: u64,
+}
+
+impl Arrangement {
+    /// The arrangement of the `size` and `Q` fields, `None` for the reserved 1D.
+    fn new(size: u8, q: u8) -> Option<Self> {
+        let esize = 8 << size;
+        let count = (64 << q) / esize;
+        (count > 1).then_some(Self { esize, count })
+    }
+
+    /// The type of the register view holding the elements.
+    fn ty(self) -> IrType {
+        uint(self.esize * self.count)
+    }
+}
+
+/// Split `value` into its elements of `esize` bits, the lowest first.
+fn gen_split(bb: &mut BasicBlock, value: IrValue, esize: u64) -> Vec<IrValue> {
+    let ty = value.ty();
+    let mut elements = Vec::new();
+    for i in 0..bits(ty) / esize {
+        let amount = gen_imm(bb, ty, i * esize);
+        let element = gen_shr(bb, value, amount);
+        elements.push(gen_zext(bb, element, uint(esize)));
+    }
+    elements
+}
+
+/// Concatenate elements of the same type, the first one in the lowest bits.
+fn gen_concat(bb: &mut BasicBlock, elements: &[IrValue]) -> IrValue {
+    let esize = bits(elements[0].ty());
+    let ty = uint(esize * elements.len() as u64);
+
+    let mut result = gen_zext(bb, elements[0], ty);
+    for (i, &element) in elements.iter().enumerate().skip(1) {
+        let element = gen_zext(bb, element, ty);
+        let amount = gen_imm(bb, ty, i as u64 * esize);
+        let element = gen_shl(bb, element, amount);
+        result = gen_or(bb, result, element);
+    }
+    result
+}
+
+fn gen_read_elements(
+    bb: &mut BasicBlock,
+    id: AArch64RegisterId,
+    arrangement: Arrangement,
+) -> Vec<IrValue> {
+    gen_split(bb, reg(id, arrangement.ty()), arrangement.esize)
+}
+
+/// The elements in the lower or upper half of the vector register.
+fn gen_read_half(
+    bb: &mut BasicBlock,
+    id: AArch64RegisterId,
+    esize: u64,
+    upper: bool,
+) -> Vec<IrValue> {
+    if upper {
+        let elements = gen_split(bb, reg(id, IrType::U128), esize);
+        elements[elements.len() / 2..].to_vec()
+    } else {
+        gen_split(bb, reg(id, IrType::U64), esize)
+    }
+}
+
+/// Write the elements through the view of their total width, which zeroes the rest of the
+/// register.
+fn gen_write_elements(bb: &mut BasicBlock, id: AArch64RegisterId, elements: &[IrValue]) {
+    let value = gen_concat(bb, elements);
+    gen_assign(bb, reg(id, value.ty()), value);
+}
+
+/// Write elements filling half the register, the "2" variants of the narrowing instructions
+/// write the upper half and keep the lower one.
+fn gen_write_half(bb: &mut BasicBlock, id: AArch64RegisterId, elements: &[IrValue], upper: bool) {
+    if upper {
+        let upper = gen_concat(bb, elements);
+        let value = gen_concat(bb, &[reg(id, IrType::U64), upper]);
+        gen_assign(bb, reg(id, IrType::U128), value);
+    } else {
+        gen_write_elements(bb, id, elements);
+    }
+}
+
+// The lanes are computed on 128-bit integers, which hold the results of the saturating,
+// rounding and widening operations on 64-bit elements exactly.
+
+fn gen_widen(bb: &mut BasicBlock, element: IrValue, signed: bool) -> IrValue {
+    if signed {
+        gen_sext(bb, element, IrType::U128)
+    } else {
+        gen_zext(bb, element, IrType::U128)
+    }
+}
+
+/// 1 if `lhs < rhs` for widened values, they are small enough for the difference not to
+/// overflow.
+fn gen_less(bb: &mut BasicBlock, lhs: IrValue, rhs: IrValue) -> IrValue {
+    let difference = gen_sub(bb, lhs, rhs);
+    compiler_prelude::gen_msb(bb, difference)
+}
+
+fn gen_asr(bb: &mut BasicBlock, value: IrValue, amount: IrValue) -> IrValue {
+    gen_shift(bb, 0b10, value, amount)
+}
+
+/// All ones in an element of `esize` bits if `flag` is set and zero otherwise.
+fn gen_mask(bb: &mut BasicBlock, flag: IrValue, esize: u64) -> IrValue {
+    let mask = compiler_prelude::gen_neg(bb, flag);
+    gen_zext(bb, mask, uint(esize))
+}
+
+/// The smallest and the largest integers of `esize` bits, as raw bits.
+fn int_range(esize: u64, signed: bool) -> (u64, u64) {
+    let ones = u64::MAX >> (64 - esize);
+    if signed {
+        (ones ^ (ones >> 1), ones >> 1)
+    } else {
+        (0, ones)
+    }
+}
+
+/// How a widened result is narrowed to its element.
+#[derive(Clone, Copy)]
+enum Narrowing {
+    Truncate,
+    /// Saturate to the signed range and set FPSR.QC if it is out of range.
+    Signed,
+    /// Saturate to the unsigned range and set FPSR.QC if it is out of range.
+    Unsigned,
+}
+
+fn gen_narrow(bb: &mut BasicBlock, value: IrValue, esize: u64, narrowing: Narrowing) -> IrValue {
+    let ty = uint(esize);
+    let signed = match narrowing {
+        Narrowing::Truncate => return gen_zext(bb, value, ty),
+        Narrowing::Signed => true,
+        Narrowing::Unsigned => false,
+    };
+
+    let (min, max) = int_range(esize, signed);
+    let wide_min = gen_widen(bb, imm(ty, min), signed);
+    let wide_max = gen_zext(bb, imm(ty, max), IrType::U128);
+    let below = gen_less(bb, value, wide_min);
+    let above = gen_less(bb, wide_max, value);
+    let saturated = gen_or(bb, below, above);
+    compiler_prelude::gen_set_qc(bb, saturated);
+
+    let result = gen_zext(bb, value, ty);
+    let result = gen_select(bb, below, imm(ty, min), result);
+    gen_select(bb, above, imm(ty, max), result)
+}
+
+/// Shift the widened `value` left by `amount`, at most `esize`, and narrow it to `esize` bits.
+/// The saturating narrowings saturate when shifting back doesn't give the value.
+fn gen_shift_left(
+    bb: &mut BasicBlock,
+    value: IrValue,
+    amount: IrValue,
+    esize: u64,
+    narrowing: Narrowing,
+) -> IrValue {
+    let ty = uint(esize);
+    let shifted = gen_shl(bb, value, amount);
+    let shifted = gen_zext(bb, shifted, ty);
+    let signed = match narrowing {
+        Narrowing::Truncate => return shifted,
+        Narrowing::Signed => true,
+        Narrowing::Unsigned => false,
+    };
+
+    let back = gen_widen(bb, shifted, signed);
+    let back = gen_asr(bb, back, amount);
+    let lost = gen_xor(bb, back, value);
+    let exact = compiler_prelude::gen_is_zero(bb, lost);
+    let one = gen_imm(bb, IrType::U128, 1);
+    let saturated = gen_xor(bb, exact, one);
+    compiler_prelude::gen_set_qc(bb, saturated);
+
+    let (min, max) = int_range(esize, signed);
+    let negative = compiler_prelude::gen_msb(bb, value);
+    let limit = gen_select(bb, negative, imm(ty, min), imm(ty, max));
+    gen_select(bb, saturated, limit, shifted)
+}
+
+/// Shift the widened `value` right by `amount`, rounding the result to nearest if `rounding`.
+fn gen_shift_right(
+    bb: &mut BasicBlock,
+    value: IrValue,
+    amount: IrValue,
+    rounding: bool,
+) -> IrValue {
+    let value = if rounding {
+        let one = gen_imm(bb, IrType::U128, 1);
+        let half = gen_shl(bb, one, amount);
+        let half = gen_shr(bb, half, one);
+        gen_add(bb, value, half)
+    } else {
+        value
+    };
+    gen_asr(bb, value, amount)
+}
+
+/// SSHL and the other shifts by register: shift left by the signed bottom byte of `shift`,
+/// which shifts right when it is negative.
+fn gen_shift_by_register(
+    bb: &mut BasicBlock,
+    value: IrValue,
+    shift: IrValue,
+    esize: u64,
+    rounding: bool,
+    narrowing: Narrowing,
+) -> IrValue {
+    let wide = IrType::U128;
+    let shift = gen_zext(bb, shift, IrType::U8);
+    let shift = gen_sext(bb, shift, wide);
+    let negative = compiler_prelude::gen_msb(bb, shift);
+    let zero = gen_imm(bb, wide, 0);
+
+    // Shifting left by more than the element leaves nothing, like shifting by its width, and
+    // shifting right by more than one past it leaves nothing but the sign, even with rounding.
+    let limit = gen_imm(bb, wide, esize);
+    let too_far = gen_less(bb, limit, shift);
+    let left = gen_select(bb, too_far, limit, shift);
+    let left = gen_select(bb, negative, zero, left);
+    let left = gen_shift_left(bb, value, left, esize, narrowing);
+
+    let limit = gen_imm(bb, wide, esize + 1);
+    let right = compiler_prelude::gen_neg(bb, shift);
+    let too_far = gen_less(bb, limit, right);
+    let right = gen_select(bb, too_far, limit, right);
+    let right = gen_select(bb, negative, right, zero);
+    let right = gen_shift_right(bb, value, right, rounding);
+    let right = gen_zext(bb, right, uint(esize));
+
+    gen_select(bb, negative, right, left)
+}
+
+/// Polynomial multiplication over {0, 1} of two elements, the low half of the product. PMULL
+/// zero-extends its elements to get the whole product.
+fn gen_poly_mul(bb: &mut BasicBlock, lhs: IrValue, rhs: IrValue) -> IrValue {
+    let ty = lhs.ty();
+    let mut result = gen_imm(bb, ty, 0);
+    let one = gen_imm(bb, ty, 1);
+    for bit in 0..bits(ty) {
+        let amount = gen_imm(bb, ty, bit);
+        let set = gen_shr(bb, rhs, amount);
+        let set = gen_and(bb, set, one);
+        let mask = compiler_prelude::gen_neg(bb, set);
+        let partial = gen_shl(bb, lhs, amount);
+        let partial = gen_and(bb, partial, mask);
+        result = gen_xor(bb, result, partial);
+    }
+    result
+}
+
+/// The lane operations of the integer three-same instructions.
+#[derive(Clone, Copy)]
+enum VecIntOp {
+    Add,
+    Sub,
+    Mul,
+    /// Multiply-add into the destination
+    Mla,
+    /// Multiply-subtract from the destination
+    Mls,
+    HalvingAdd,
+    RoundingHalvingAdd,
+    HalvingSub,
+    SaturatingAdd,
+    SaturatingSub,
+    Max,
+    Min,
+    AbsDiff,
+    /// Absolute difference added to the destination
+    AbsDiffAccumulate,
+    CompareGt,
+    CompareGe,
+    CompareEq,
+    /// Compare bitwise test bits nonzero
+    Test,
+    Shift {
+        rounding: bool,
+        saturating: bool,
+    },
+    PolyMul,
+    /// The high half of the doubled product, saturated
+    DoublingMulHigh {
+        rounding: bool,
+    },
+}
+
+impl VecIntOp {
+    fn accumulates(self) -> bool {
+        matches!(self, Self::Mla | Self::Mls | Self::AbsDiffAccumulate)
+    }
+
+    fn is_reserved(self, esize: u64) -> bool {
+        match self {
+            Self::Mul
+            | Self::Mla
+            | Self::Mls
+            | Self::HalvingAdd
+            | Self::RoundingHalvingAdd
+            | Self::HalvingSub
+            | Self::Max
+            | Self::Min
+            | Self::AbsDiff
+            | Self::AbsDiffAccumulate => esize == 64,
+            Self::PolyMul => esize != 8,
+            Self::DoublingMulHigh { .. } => esize == 8 || esize == 64,
+            _ => false,
+        }
+    }
+}
+
+/// Compute a lane of `op` from the elements of rn, rm and rd, for the accumulating operations.
+fn gen_vec_int_op(
+    bb: &mut BasicBlock,
+    op: VecIntOp,
+    rn: IrValue,
+    rm: IrValue,
+    rd: Option<IrValue>,
+    signed: bool,
+) -> IrValue {
+    let esize = bits(rn.ty());
+    let saturation = if signed {
+        Narrowing::Signed
+    } else {
+        Narrowing::Unsigned
+    };
+
+    match op {
+        VecIntOp::PolyMul => return gen_poly_mul(bb, rn, rm),
+        VecIntOp::Shift {
+            rounding,
+            saturating,
+        } => {
+            let narrowing = if saturating {
+                saturation
+            } else {
+                Narrowing::Truncate
+            };
+            let value = gen_widen(bb, rn, signed);
+            return gen_shift_by_register(bb, value, rm, esize, rounding, narrowing);
+        }
+        _ => {}
+    }
+
+    let a = gen_widen(bb, rn, signed);
+    let b = gen_widen(bb, rm, signed);
+    let one = gen_imm(bb, IrType::U128, 1);
+
+    let result = match op {
+        VecIntOp::Add => gen_add(bb, a, b),
+        VecIntOp::Sub => gen_sub(bb, a, b),
+        VecIntOp::Mul => gen_mul(bb, a, b),
+        VecIntOp::Mla | VecIntOp::Mls => {
+            let product = gen_mul(bb, a, b);
+            let rd = gen_zext(bb, rd.unwrap(), IrType::U128);
+            match op {
+                VecIntOp::Mla => gen_add(bb, rd, product),
+                _ => gen_sub(bb, rd, product),
+            }
+        }
+        VecIntOp::HalvingAdd => {
+            let sum = gen_add(bb, a, b);
+            gen_asr(bb, sum, one)
+        }
+        VecIntOp::RoundingHalvingAdd => {
+            let sum = gen_add(bb, a, b);
+            let sum = gen_add(bb, sum, one);
+            gen_asr(bb, sum, one)
+        }
+        VecIntOp::HalvingSub => {
+            let difference = gen_sub(bb, a, b);
+            gen_asr(bb, difference, one)
+        }
+        VecIntOp::SaturatingAdd => {
+            let sum = gen_add(bb, a, b);
+            return gen_narrow(bb, sum, esize, saturation);
+        }
+        VecIntOp::SaturatingSub => {
+            let difference = gen_sub(bb, a, b);
+            return gen_narrow(bb, difference, esize, saturation);
+        }
+        VecIntOp::Max => {
+            let less = gen_less(bb, a, b);
+            gen_select(bb, less, b, a)
+        }
+        VecIntOp::Min => {
+            let less = gen_less(bb, a, b);
+            gen_select(bb, less, a, b)
+        }
+        VecIntOp::AbsDiff | VecIntOp::AbsDiffAccumulate => {
+            let less = gen_less(bb, a, b);
+            let difference = gen_sub(bb, a, b);
+            let negated = compiler_prelude::gen_neg(bb, difference);
+            let result = gen_select(bb, less, negated, difference);
+            match rd {
+                Some(rd) => {
+                    let rd = gen_zext(bb, rd, IrType::U128);
+                    gen_add(bb, rd, result)
+                }
+                None => result,
+            }
+        }
+        VecIntOp::CompareGt => {
+            let greater = gen_less(bb, b, a);
+            return gen_mask(bb, greater, esize);
+        }
+        VecIntOp::CompareGe => {
+            let less = gen_less(bb, a, b);
+            let greater_equal = gen_xor(bb, less, one);
+            return gen_mask(bb, greater_equal, esize);
+        }
+        VecIntOp::CompareEq => {
+            let difference = gen_xor(bb, a, b);
+            let equal = compiler_prelude::gen_is_zero(bb, difference);
+            return gen_mask(bb, equal, esize);
+        }
+        VecIntOp::Test => {
+            let common = gen_and(bb, a, b);
+            let none = compiler_prelude::gen_is_zero(bb, common);
+            let some = gen_xor(bb, none, one);
+            return gen_mask(bb, some, esize);
+        }
+        VecIntOp::DoublingMulHigh { rounding } => {
+            let product = gen_mul(bb, a, b);
+            let product = gen_add(bb, product, product);
+            let product = if rounding {
+                let half = gen_imm(bb, IrType::U128, 1 << (esize - 1));
+                gen_add(bb, product, half)
+            } else {
+                product
+            };
+            let high = gen_imm(bb, IrType::U128, esize);
+            let high = gen_asr(bb, product, high);
+            return gen_narrow(bb, high, esize, Narrowing::Signed);
+        }
+        VecIntOp::PolyMul | VecIntOp::Shift { .. } => unreachable!(),
+    };
+
+    gen_zext(bb, result, uint(esize))
+}
+
+fn compile_vec_int(bb: &mut BasicBlock, operand: &QSizeRmRnRd, op: VecIntOp, signed: bool) {
+    let arrangement = Arrangement::new(operand.size, operand.q);
+    let Some(arrangement) = arrangement.filter(|a| !op.is_reserved(a.esize)) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let rn = gen_read_elements(bb, operand.rn, arrangement);
+    let rm = gen_read_elements(bb, operand.rm, arrangement);
+    let rd = match op.accumulates() {
+        true => gen_read_elements(bb, operand.rd, arrangement),
+        false => Vec::new(),
+    };
+
+    let mut result = Vec::new();
+    for (i, (&rn, &rm)) in rn.iter().zip(&rm).enumerate() {
+        result.push(gen_vec_int_op(bb, op, rn, rm, rd.get(i).copied(), signed));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// ADDP, SMAXP and the like: `op` on the adjacent pairs of the concatenation of rn and rm.
+fn compile_vec_int_pairwise(
+    bb: &mut BasicBlock,
+    operand: &QSizeRmRnRd,
+    op: VecIntOp,
+    signed: bool,
+) {
+    let arrangement = Arrangement::new(operand.size, operand.q);
+    let Some(arrangement) = arrangement.filter(|a| !op.is_reserved(a.esize)) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let mut elements = gen_read_elements(bb, operand.rn, arrangement);
+    elements.extend(gen_read_elements(bb, operand.rm, arrangement));
+
+    let mut result = Vec::new();
+    for pair in elements.chunks(2) {
+        result.push(gen_vec_int_op(bb, op, pair[0], pair[1], None, signed));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn vec_type(q: u8) -> IrType {
+    match q {
+        0 => IrType::U64,
+        _ => IrType::U128,
+    }
+}
+
+/// AND, BIC, ORR, ORN and EOR on the whole vector.
+fn compile_vec_logical(bb: &mut BasicBlock, operand: &QSizeRmRnRd, op: LogicalOp) {
+    let ty = vec_type(operand.q);
+    gen_logical(bb, op, operand.rd, reg(operand.rn, ty), reg(operand.rm, ty));
+}
+
+#[derive(Clone, Copy)]
+enum BitSelectOp {
+    /// Select rn where rd is set and rm elsewhere
+    Bsl,
+    /// Insert rn where rm is set
+    Bit,
+    /// Insert rn where rm is clear
+    Bif,
+}
+
+fn compile_bit_select(bb: &mut BasicBlock, operand: &QSizeRmRnRd, op: BitSelectOp) {
+    let ty = vec_type(operand.q);
+    let (rd, rn, rm) = (
+        reg(operand.rd, ty),
+        reg(operand.rn, ty),
+        reg(operand.rm, ty),
+    );
+    let (selector, then, otherwise) = match op {
+        BitSelectOp::Bsl => (rd, rn, rm),
+        BitSelectOp::Bit => (rm, rn, rd),
+        BitSelectOp::Bif => (rm, rd, rn),
+    };
+
+    let then = gen_and(bb, then, selector);
+    let not_selector = gen_not(bb, selector);
+    let otherwise = gen_and(bb, otherwise, not_selector);
+    let result = gen_or(bb, then, otherwise);
+    gen_assign(bb, rd, result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The element size and the immh:immb field of a shift by immediate.
+fn shift_imm_fields(operand: &AdvSimdShiftByImm) -> (u64, u64) {
+    let esize = 8 << (7 - operand.immh.leading_zeros());
+    let immhb = (operand.immh as u64) << 3 | operand.immb as u64;
+    (esize, immhb)
+}
+
+#[derive(Clone, Copy)]
+enum ShiftImmOp {
+    Right {
+        rounding: bool,
+        accumulate: bool,
+    },
+    Left(Narrowing),
+    /// SRI, shift right and insert into the low bits of the destination
+    RightInsert,
+    /// SLI, shift left and insert into the high bits of the destination
+    LeftInsert,
+}
+
+fn compile_vec_shift_imm(
+    bb: &mut BasicBlock,
+    operand: &AdvSimdShiftByImm,
+    op: ShiftImmOp,
+    signed: bool,
+) {
+    let (esize, immhb) = shift_imm_fields(operand);
+    let Some(arrangement) = Arrangement::new((esize / 8).trailing_zeros() as u8, operand.q) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+    let right = 2 * esize - immhb;
+    let left = immhb - esize;
+    let ty = uint(esize);
+
+    let rn = gen_read_elements(bb, operand.rn, arrangement);
+    let rd = match op {
+        ShiftImmOp::Right {
+            accumulate: false, ..
+        }
+        | ShiftImmOp::Left(_) => Vec::new(),
+        _ => gen_read_elements(bb, operand.rd, arrangement),
+    };
+
+    let mut result = Vec::new();
+    for (i, &element) in rn.iter().enumerate() {
+        let value = gen_widen(bb, element, signed);
+        let element = match op {
+            ShiftImmOp::Right {
+                rounding,
+                accumulate,
+            } => {
+                let amount = gen_imm(bb, IrType::U128, right);
+                let shifted = gen_shift_right(bb, value, amount, rounding);
+                let shifted = gen_zext(bb, shifted, ty);
+                match accumulate {
+                    true => gen_add(bb, rd[i], shifted),
+                    false => shifted,
+                }
+            }
+            ShiftImmOp::Left(narrowing) => {
+                let amount = gen_imm(bb, IrType::U128, left);
+                gen_shift_left(bb, value, amount, esize, narrowing)
+            }
+            ShiftImmOp::RightInsert => {
+                let ones = u64::MAX >> (64 - esize);
+                let mask = ones.checked_shr(right as u32).unwrap_or(0);
+                let amount = gen_imm(bb, IrType::U128, right);
+                let shifted = gen_shr(bb, value, amount);
+                let shifted = gen_zext(bb, shifted, ty);
+                let kept = gen_and(bb, rd[i], imm(ty, !mask));
+                gen_or(bb, kept, shifted)
+            }
+            ShiftImmOp::LeftInsert => {
+                let mask = u64::MAX << left;
+                let shifted = gen_shl(bb, element, imm(ty, left));
+                let kept = gen_and(bb, rd[i], imm(ty, !mask));
+                gen_or(bb, kept, shifted)
+            }
+        };
+        result.push(element);
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// SHRN and the like: shift the double-width elements of rn right into half of rd.
+fn compile_vec_shift_right_narrow(
+    bb: &mut BasicBlock,
+    operand: &AdvSimdShiftByImm,
+    rounding: bool,
+    signed: bool,
+    narrowing: Narrowing,
+) {
+    let (esize, immhb) = shift_imm_fields(operand);
+    if esize == 64 {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    let source = gen_split(bb, reg(operand.rn, IrType::U128), 2 * esize);
+    let amount = gen_imm(bb, IrType::U128, 2 * esize - immhb);
+    let mut result = Vec::new();
+    for element in source {
+        let value = gen_widen(bb, element, signed);
+        let shifted = gen_shift_right(bb, value, amount, rounding);
+        result.push(gen_narrow(bb, shifted, esize, narrowing));
+    }
+
+    gen_write_half(bb, operand.rd, &result, operand.q == 1);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// SSHLL and USHLL: shift the elements of half of rn left into double-width elements.
+fn compile_vec_shift_left_long(bb: &mut BasicBlock, operand: &AdvSimdShiftByImm, signed: bool) {
+    let (esize, immhb) = shift_imm_fields(operand);
+    if esize == 64 {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    let source = gen_read_half(bb, operand.rn, esize, operand.q == 1);
+    let amount = gen_imm(bb, IrType::U128, immhb - esize);
+    let mut result = Vec::new();
+    for element in source {
+        let value = gen_widen(bb, element, signed);
+        let shifted = gen_shl(bb, value, amount);
+        result.push(gen_zext(bb, shifted, uint(2 * esize)));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The element-wise integer operations of the two-register miscellaneous instructions.
+#[derive(Clone, Copy)]
+enum VecUnaryOp {
+    Abs(Narrowing),
+    Neg(Narrowing),
+    Cls,
+    Clz,
+}
+
+fn compile_vec_unary(bb: &mut BasicBlock, operand: &QSizeRnRd, op: VecUnaryOp) {
+    let arrangement = Arrangement::new(operand.size, operand.q);
+    let reserved = |esize| matches!(op, VecUnaryOp::Cls | VecUnaryOp::Clz) && esize == 64;
+    let Some(arrangement) = arrangement.filter(|a| !reserved(a.esize)) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let mut result = Vec::new();
+    for element in gen_read_elements(bb, operand.rn, arrangement) {
+        result.push(gen_vec_unary_op(bb, op, element));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn gen_vec_unary_op(bb: &mut BasicBlock, op: VecUnaryOp, element: IrValue) -> IrValue {
+    let ty = element.ty();
+    match op {
+        VecUnaryOp::Abs(narrowing) => {
+            let value = gen_widen(bb, element, true);
+            let negative = compiler_prelude::gen_msb(bb, value);
+            let negated = compiler_prelude::gen_neg(bb, value);
+            let value = gen_select(bb, negative, negated, value);
+            gen_narrow(bb, value, bits(ty), narrowing)
+        }
+        VecUnaryOp::Neg(narrowing) => {
+            let value = gen_widen(bb, element, true);
+            let negated = compiler_prelude::gen_neg(bb, value);
+            gen_narrow(bb, negated, bits(ty), narrowing)
+        }
+        VecUnaryOp::Cls => {
+            let shifted = gen_shl(bb, element, imm(ty, 1));
+            let changes = gen_xor(bb, element, shifted);
+            let changes = gen_or(bb, changes, imm(ty, 1));
+            gen_count_leading_zeros(bb, changes)
+        }
+        VecUnaryOp::Clz => gen_count_leading_zeros(bb, element),
+    }
+}
+
+/// CMGT, CMEQ and the like against zero, with zero as the first operand if `swap`.
+fn compile_vec_compare_zero(bb: &mut BasicBlock, operand: &QSizeRnRd, op: VecIntOp, swap: bool) {
+    let Some(arrangement) = Arrangement::new(operand.size, operand.q) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let zero = imm(uint(arrangement.esize), 0);
+    let mut result = Vec::new();
+    for element in gen_read_elements(bb, operand.rn, arrangement) {
+        let (lhs, rhs) = if swap {
+            (zero, element)
+        } else {
+            (element, zero)
+        };
+        result.push(gen_vec_int_op(bb, op, lhs, rhs, None, true));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// Apply `op` to each 64-bit half of the vector in rn, for the operations within bytes.
+fn gen_map_halves(
+    bb: &mut BasicBlock,
+    operand: &QSizeRnRd,
+    mut op: impl FnMut(&mut BasicBlock, IrValue) -> IrValue,
+) {
+    let mut result = Vec::new();
+    for half in gen_split(bb, reg(operand.rn, vec_type(operand.q)), 64) {
+        result.push(op(bb, half));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// REV16, REV32 and REV64, reverse the elements in each container of `container` bits.
+fn compile_vec_rev(bb: &mut BasicBlock, operand: &QSizeRnRd, container: u64) {
+    let esize = 8 << operand.size;
+    if esize >= container {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    gen_map_halves(bb, operand, |bb, mut half| {
+        let mut size = esize;
+        while size < container {
+            half = gen_swap_groups(bb, half, size);
+            size <<= 1;
+        }
+        half
+    });
+}
+
+fn compile_cnt(bb: &mut BasicBlock, operand: &QSizeRnRd) {
+    if operand.size != 0 {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    gen_map_halves(bb, operand, gen_count_ones_per_byte);
+}
+
+fn compile_vec_rbit(bb: &mut BasicBlock, operand: &QSizeRnRd) {
+    gen_map_halves(bb, operand, |bb, mut half| {
+        for size in [1, 2, 4] {
+            half = gen_swap_groups(bb, half, size);
+        }
+        half
+    });
+}
+
+fn compile_vec_not(bb: &mut BasicBlock, operand: &QSizeRnRd) {
+    gen_map_halves(bb, operand, gen_not);
+}
+
+/// SADDLP, UADDLP and their accumulating SADALP, UADALP: add the adjacent pairs of elements
+/// into double-width elements.
+fn compile_vec_add_pairwise_long(
+    bb: &mut BasicBlock,
+    operand: &QSizeRnRd,
+    signed: bool,
+    accumulate: bool,
+) {
+    let arrangement = Arrangement::new(operand.size, operand.q);
+    let Some(arrangement) = arrangement.filter(|a| a.esize != 64) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+    let long = Arrangement {
+        esize: arrangement.esize * 2,
+        count: arrangement.count / 2,
+    };
+
+    let rn = gen_read_elements(bb, operand.rn, arrangement);
+    let rd = match accumulate {
+        true => gen_read_elements(bb, operand.rd, long),
+        false => Vec::new(),
+    };
+
+    let mut result = Vec::new();
+    for (i, pair) in rn.chunks(2).enumerate() {
+        let low = gen_widen(bb, pair[0], signed);
+        let high = gen_widen(bb, pair[1], signed);
+        let sum = gen_add(bb, low, high);
+        let sum = gen_zext(bb, sum, uint(long.esize));
+        result.push(match accumulate {
+            true => gen_add(bb, rd[i], sum),
+            false => sum,
+        });
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// SUQADD adds unsigned elements of rn to the signed ones of rd, USQADD signed ones to unsigned,
+/// both saturating to the range of rd.
+fn compile_vec_saturating_accumulate(bb: &mut BasicBlock, operand: &QSizeRnRd, signed: bool) {
+    let Some(arrangement) = Arrangement::new(operand.size, operand.q) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let rn = gen_read_elements(bb, operand.rn, arrangement);
+    let rd = gen_read_elements(bb, operand.rd, arrangement);
+    let mut result = Vec::new();
+    for (&rn, &rd) in rn.iter().zip(&rd) {
+        result.push(gen_saturating_accumulate(bb, rn, rd, signed));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The element rn of the other signedness added to rd, saturated to the signedness of rd.
+fn gen_saturating_accumulate(
+    bb: &mut BasicBlock,
+    rn: IrValue,
+    rd: IrValue,
+    signed: bool,
+) -> IrValue {
+    let narrowing = match signed {
+        true => Narrowing::Signed,
+        false => Narrowing::Unsigned,
+    };
+    let addend = gen_widen(bb, rn, !signed);
+    let value = gen_widen(bb, rd, signed);
+    let sum = gen_add(bb, value, addend);
+    gen_narrow(bb, sum, bits(rd.ty()), narrowing)
+}
+
+/// XTN, SQXTN, UQXTN and SQXTUN: narrow the double-width elements of rn into half of rd.
+fn compile_vec_extract_narrow(
+    bb: &mut BasicBlock,
+    operand: &QSizeRnRd,
+    signed: bool,
+    narrowing: Narrowing,
+) {
+    let esize = 8 << operand.size;
+    if esize == 64 {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    let mut result = Vec::new();
+    for element in gen_split(bb, reg(operand.rn, IrType::U128), 2 * esize) {
+        let value = gen_widen(bb, element, signed);
+        result.push(gen_narrow(bb, value, esize, narrowing));
+    }
+
+    gen_write_half(bb, operand.rd, &result, operand.q == 1);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// SHLL: shift the elements of half of rn left by their size into double-width elements.
+fn compile_shll(bb: &mut BasicBlock, operand: &QSizeRnRd) {
+    let esize = 8 << operand.size;
+    if esize == 64 {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    let ty = uint(2 * esize);
+    let mut result = Vec::new();
+    for element in gen_read_half(bb, operand.rn, esize, operand.q == 1) {
+        let element = gen_zext(bb, element, ty);
+        result.push(gen_shl(bb, element, imm(ty, esize)));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+#[derive(Clone, Copy)]
+enum ReduceOp {
+    Add,
+    /// Add into a double-width result
+    AddLong,
+    Max,
+    Min,
+}
+
+/// ADDV, SADDLV, SMAXV and the like: reduce the elements of rn into a scalar.
+fn compile_vec_reduce(bb: &mut BasicBlock, operand: &QSizeRnRd, op: ReduceOp, signed: bool) {
+    let arrangement = Arrangement::new(operand.size, operand.q);
+    let Some(arrangement) = arrangement.filter(|a| a.esize != 64 && a.count > 2) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let mut elements = Vec::new();
+    for element in gen_read_elements(bb, operand.rn, arrangement) {
+        elements.push(gen_widen(bb, element, signed));
+    }
+
+    let mut result = elements[0];
+    for &element in &elements[1..] {
+        result = match op {
+            ReduceOp::Add | ReduceOp::AddLong => gen_add(bb, result, element),
+            ReduceOp::Max => {
+                let less = gen_less(bb, result, element);
+                gen_select(bb, less, element, result)
+            }
+            ReduceOp::Min => {
+                let less = gen_less(bb, element, result);
+                gen_select(bb, less, element, result)
+            }
+        };
+    }
+
+    let width = match op {
+        ReduceOp::AddLong => 2 * arrangement.esize,
+        _ => arrangement.esize,
+    };
+    let result = gen_zext(bb, result, uint(width));
+    gen_assign(bb, reg(operand.rd, result.ty()), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// Scalar ADDP, the sum of the two 64-bit elements of rn.
+fn compile_addp_scalar(bb: &mut BasicBlock, operand: &AdvSimdScalarPairwise) {
+    if operand.size != 0b11 {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    let elements = gen_split(bb, reg(operand.rn, IrType::U128), 64);
+    let sum = gen_add(bb, elements[0], elements[1]);
+    gen_assign(bb, reg(operand.rd, IrType::U64), sum);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+#[derive(Clone, Copy)]
+enum PermuteOp {
+    /// Unzip, the even (part 0) or odd (part 1) elements of rn then rm
+    Uzp,
+    /// Transpose, the even or odd elements of rn interleaved with those of rm
+    Trn,
+    /// Zip, interleave the lower (part 0) or upper (part 1) halves of rn and rm
+    Zip,
+}
+
+fn compile_vec_permute(bb: &mut BasicBlock, operand: &QSizeRmRnRd, op: PermuteOp, part: usize) {
+    let Some(arrangement) = Arrangement::new(operand.size, operand.q) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let rn = gen_read_elements(bb, operand.rn, arrangement);
+    let rm = gen_read_elements(bb, operand.rm, arrangement);
+    let count = rn.len();
+    let result: Vec<_> = match op {
+        PermuteOp::Uzp => rn
+            .iter()
+            .chain(&rm)
+            .skip(part)
+            .step_by(2)
+            .copied()
+            .collect(),
+        PermuteOp::Trn => (0..count / 2)
+            .flat_map(|i| [rn[2 * i + part], rm[2 * i + part]])
+            .collect(),
+        PermuteOp::Zip => {
+            let base = part * count / 2;
+            (base..base + count / 2)
+                .flat_map(|i| [rn[i], rm[i]])
+                .collect()
+        }
+    };
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// TBL and TBX, the bytes of the table selected by the indices in rm. The out of range indices
+/// give zero for TBL and keep the destination byte for TBX.
+fn compile_table_lookup(bb: &mut BasicBlock, operand: &AdvSimdTableLookup, extension: bool) {
+    let AArch64RegisterId::V(rn) = operand.rn else {
+        unreachable!("{:?} is not a vector register", operand.rn)
+    };
+
+    let ty = vec_type(operand.q);
+    let indices = gen_split(bb, reg(operand.rm, ty), 8);
+    let rd = match extension {
+        true => gen_split(bb, reg(operand.rd, ty), 8),
+        false => Vec::new(),
+    };
+    // The table wraps around from v31 to v0
+    let table: Vec<_> = (0..=operand.len)
+        .map(|i| reg(AArch64RegisterId::V((rn + i) % 32), IrType::U128))
+        .collect();
+
+    let mut result = Vec::new();
+    for (i, &index) in indices.iter().enumerate() {
+        let register = gen_shr(bb, index, imm(IrType::U8, 4));
+        let byte = gen_and(bb, index, imm(IrType::U8, 0xf));
+        let amount = gen_shl(bb, byte, imm(IrType::U8, 3));
+        let amount = gen_zext(bb, amount, IrType::U128);
+
+        let mut element = rd.get(i).copied().unwrap_or(imm(IrType::U8, 0));
+        for (j, &table) in table.iter().enumerate() {
+            let other = gen_xor(bb, register, imm(IrType::U8, j as u64));
+            let hit = compiler_prelude::gen_is_zero(bb, other);
+            let byte = gen_shr(bb, table, amount);
+            let byte = gen_zext(bb, byte, IrType::U8);
+            element = gen_select(bb, hit, byte, element);
+        }
+        result.push(element);
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// EXT, the bytes of the concatenation of rm above rn from the byte at imm4.
+fn compile_ext(bb: &mut BasicBlock, operand: &AdvancedSimdExtract) {
+    if operand.q == 0 && operand.imm4 >= 8 {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    let ty = vec_type(operand.q);
+    let mut bytes = gen_split(bb, reg(operand.rn, ty), 8);
+    bytes.extend(gen_split(bb, reg(operand.rm, ty), 8));
+    let start = operand.imm4 as usize;
+    let count = bits(ty) as usize / 8;
+
+    gen_write_elements(bb, operand.rd, &bytes[start..start + count]);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The element size and the index encoded in the imm5 field of the copy instructions.
+fn copy_element(imm5: u8) -> Option<(u64, usize)> {
+    let size = imm5.trailing_zeros();
+    (size < 4).then(|| (8 << size, (imm5 >> (size + 1)) as usize))
+}
+
+fn compile_dup_element(bb: &mut BasicBlock, operand: &AdvancedSimdCopy) {
+    let copy = copy_element(operand.imm5);
+    let Some((esize, index)) = copy.filter(|&(esize, _)| esize != 64 || operand.q == 1) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let element = gen_split(bb, reg(operand.rn, IrType::U128), esize)[index];
+    let count = (64 << operand.q) / esize;
+    gen_write_elements(bb, operand.rd, &vec![element; count as usize]);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_dup_general(bb: &mut BasicBlock, operand: &AdvancedSimdCopy) {
+    let copy = copy_element(operand.imm5);
+    let Some((esize, _)) = copy.filter(|&(esize, _)| esize != 64 || operand.q == 1) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let element = gen_zext(bb, reg(operand.rn, IrType::U64), uint(esize));
+    let count = (64 << operand.q) / esize;
+    gen_write_elements(bb, operand.rd, &vec![element; count as usize]);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// SMOV and UMOV, move an element extended to W when Q is 0 and to X when it is 1.
+fn compile_mov_to_general(bb: &mut BasicBlock, operand: &AdvancedSimdCopy, signed: bool) {
+    let ty = match operand.q {
+        0 => IrType::U32,
+        _ => IrType::U64,
+    };
+    // SMOV extends to a wider register and UMOV needs the full width of X
+    let valid = |esize: u64| match signed {
+        true => esize < bits(ty),
+        false => esize <= 32 || operand.q == 1,
+    };
+    let copy = copy_element(operand.imm5);
+    let Some((esize, index)) = copy.filter(|&(esize, _)| valid(esize)) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let element = gen_split(bb, reg(operand.rn, IrType::U128), esize)[index];
+    let value = match signed {
+        true => gen_sext(bb, element, ty),
+        false => gen_zext(bb, element, ty),
+    };
+    gen_assign(bb, reg(operand.rd, ty), value);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_ins_general(bb: &mut BasicBlock, operand: &AdvancedSimdCopy) {
+    let Some((esize, index)) = copy_element(operand.imm5) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let mut elements = gen_split(bb, reg(operand.rd, IrType::U128), esize);
+    elements[index] = gen_zext(bb, reg(operand.rn, IrType::U64), uint(esize));
+    gen_write_elements(bb, operand.rd, &elements);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_ins_element(bb: &mut BasicBlock, operand: &AdvancedSimdCopy) {
+    let Some((esize, index)) = copy_element(operand.imm5) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+    let source = (operand.imm4 >> (esize / 8).trailing_zeros()) as usize;
+
+    let element = gen_split(bb, reg(operand.rn, IrType::U128), esize)[source];
+    let mut elements = gen_split(bb, reg(operand.rd, IrType::U128), esize);
+    elements[index] = element;
+    gen_write_elements(bb, operand.rd, &elements);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// AdvSIMDExpandImm, the 64-bit pattern of a modified immediate.
+pub(crate) fn adv_simd_expand_imm(op: u8, cmode: u8, imm8: u8) -> u64 {
+    let replicate =
+        |value: u64, esize: u64| (0..64 / esize).fold(0, |acc, i| acc | value << (i * esize));
+    let byte = imm8 as u64;
+
+    match cmode >> 1 {
+        0b000..=0b011 => replicate(byte << (8 * (cmode >> 1)), 32),
+        0b100 | 0b101 => replicate(byte << (8 * (cmode >> 1 & 1)), 16),
+        0b110 => match cmode & 1 {
+            0 => replicate(byte << 8 | 0xff, 32),
+            _ => replicate(byte << 16 | 0xffff, 32),
+        },
+        _ => match (cmode & 1, op) {
+            (0, 0) => replicate(byte, 8),
+            // Each bit of imm8 expands to a byte
+            (0, _) => (0..8)
+                .filter(|bit| byte >> bit & 1 == 1)
+                .fold(0, |acc, bit| acc | 0xff << (bit * 8)),
+            (_, 0) => replicate(compiler_prelude::vfp_expand_imm(imm8, 32), 32),
+            _ => compiler_prelude::vfp_expand_imm(imm8, 64),
+        },
+    }
+}
+
+#[derive(Clone, Copy)]
+enum VecImmOp {
+    Movi,
+    Mvni,
+    Orr,
+    Bic,
+}
+
+/// MOVI, MVNI, FMOV (vector, immediate) and the ORR and BIC with an immediate.
+fn compile_vec_imm(bb: &mut BasicBlock, operand: &AdvSimdModifiedImm, op: VecImmOp) {
+    // The double-precision FMOV only has a 2D form
+    if operand.op == 1 && operand.cmode == 0b1111 && operand.q == 0 {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    let fields = [operand.a, operand.b, operand.c, operand.d];
+    let fields = fields
+        .into_iter()
+        .chain([operand.e, operand.f, operand.g, operand.h]);
+    let imm8 = fields.fold(0, |acc, bit| acc << 1 | bit);
+    let pattern = adv_simd_expand_imm(operand.op, operand.cmode, imm8);
+    let pattern = match op {
+        VecImmOp::Mvni => imm(IrType::U64, !pattern),
+        _ => imm(IrType::U64, pattern),
+    };
+    let value = match operand.q {
+        0 => pattern,
+        _ => gen_concat(bb, &[pattern, pattern]),
+    };
+
+    let rd = reg(operand.rd, value.ty());
+    match op {
+        VecImmOp::Movi | VecImmOp::Mvni => {
+            gen_assign(bb, rd, value);
+            compiler_prelude::gen_move_pc(bb);
+        }
+        VecImmOp::Orr => gen_logical(bb, LogicalOp::Orr, operand.rd, rd, value),
+        VecImmOp::Bic => gen_logical(bb, LogicalOp::Bic, operand.rd, rd, value),
+    }
+}
+
+/// The arrangement of the vector floating-point instructions, singles or doubles by the low
+/// bit of `size`. `None` for the reserved 1D.
+fn float_arrangement(size: u8, q: u8) -> Option<Arrangement> {
+    Arrangement::new(2 + (size & 1), q)
+}
+
+/// The conditions of the floating-point compares, which write all ones when they hold.
+#[derive(Clone, Copy)]
+enum FloatCond {
+    Eq,
+    Ge,
+    Gt,
+}
+
+/// All ones in an element as wide as the floats if `lhs cond rhs`. Only the equality is quiet,
+/// the other conditions raise invalid on any NaN.
+fn gen_float_compare(bb: &mut BasicBlock, cond: FloatCond, lhs: IrValue, rhs: IrValue) -> IrValue {
+    let u64 = IrType::U64;
+    let signaling = !matches!(cond, FloatCond::Eq);
+    let order =
+        compiler_prelude::gen_float(bb, FloatOp::Compare { signaling }, u64, vec![lhs, rhs]);
+
+    // Bit n is set if the condition holds for the order n
+    let holds = match cond {
+        FloatCond::Eq => 0b0001,
+        FloatCond::Ge => 0b0101,
+        FloatCond::Gt => 0b0100,
+    };
+    let flag = gen_shr(bb, imm(u64, holds), order);
+    let flag = gen_and(bb, flag, imm(u64, 1));
+    gen_mask(bb, flag, bits(lhs.ty()))
+}
+
+fn gen_float_abs(bb: &mut BasicBlock, value: IrValue) -> IrValue {
+    let ty = value.ty();
+    gen_and(bb, value, imm(ty, !(1 << (bits(ty) - 1))))
+}
+
+/// The lane operations of the floating-point three-same and by-element instructions.
+#[derive(Clone, Copy)]
+enum VecFloatOp {
+    Float(FloatOp),
+    AbsDiff,
+    /// FRECPS and FRSQRTS, the step on the negated elements of rn
+    Step(FloatOp),
+    /// Fused multiply-add into the destination, FMLS negates the elements of rn
+    MulAdd {
+        negate: bool,
+    },
+    /// FACGE and FACGT compare the absolute values
+    Compare {
+        cond: FloatCond,
+        absolute: bool,
+    },
+}
+
+/// Compute a lane of `op` from the elements of rn and rm, and rd for the multiply-adds.
+fn gen_vec_float_op(
+    bb: &mut BasicBlock,
+    op: VecFloatOp,
+    rn: IrValue,
+    rm: IrValue,
+    rd: Option<IrValue>,
+) -> IrValue {
+    let ty = rn.ty();
+    match op {
+        VecFloatOp::Float(op) => compiler_prelude::gen_float(bb, op, ty, vec![rn, rm]),
+        VecFloatOp::AbsDiff => {
+            let difference = compiler_prelude::gen_float(bb, FloatOp::Sub, ty, vec![rn, rm]);
+            gen_float_abs(bb, difference)
+        }
+        VecFloatOp::Step(op) => {
+            let rn = gen_float_neg(bb, rn);
+            compiler_prelude::gen_float(bb, op, ty, vec![rn, rm])
+        }
+        VecFloatOp::MulAdd { negate } => {
+            let rn = match negate {
+                true => gen_float_neg(bb, rn),
+                false => rn,
+            };
+            compiler_prelude::gen_float(bb, FloatOp::MulAdd, ty, vec![rd.unwrap(), rn, rm])
+        }
+        VecFloatOp::Compare { cond, absolute } => {
+            let (rn, rm) = match absolute {
+                true => (gen_float_abs(bb, rn), gen_float_abs(bb, rm)),
+                false => (rn, rm),
+            };
+            gen_float_compare(bb, cond, rn, rm)
+        }
+    }
+}
+
+fn compile_vec_float(bb: &mut BasicBlock, operand: &QSizeRmRnRd, op: VecFloatOp) {
+    let Some(arrangement) = float_arrangement(operand.size, operand.q) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let rn = gen_read_elements(bb, operand.rn, arrangement);
+    let rm = gen_read_elements(bb, operand.rm, arrangement);
+    let rd = match op {
+        VecFloatOp::MulAdd { .. } => gen_read_elements(bb, operand.rd, arrangement),
+        _ => Vec::new(),
+    };
+
+    let mut result = Vec::new();
+    for (i, (&rn, &rm)) in rn.iter().zip(&rm).enumerate() {
+        result.push(gen_vec_float_op(bb, op, rn, rm, rd.get(i).copied()));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// FADDP, FMAXP and the like: `op` on the adjacent pairs of the concatenation of rn and rm.
+fn compile_vec_float_pairwise(bb: &mut BasicBlock, operand: &QSizeRmRnRd, op: FloatOp) {
+    let Some(arrangement) = float_arrangement(operand.size, operand.q) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let mut elements = gen_read_elements(bb, operand.rn, arrangement);
+    elements.extend(gen_read_elements(bb, operand.rm, arrangement));
+
+    let ty = uint(arrangement.esize);
+    let mut result = Vec::new();
+    for pair in elements.chunks(2) {
+        result.push(compiler_prelude::gen_float(bb, op, ty, pair.to_vec()));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The scalar FADDP and the like, `op` on the two elements of rn.
+fn compile_float_pairwise_scalar(
+    bb: &mut BasicBlock,
+    operand: &AdvSimdScalarPairwise,
+    op: FloatOp,
+) {
+    let esize = 32 << (operand.size & 1);
+    let ty = uint(esize);
+
+    let elements = gen_split(bb, reg(operand.rn, uint(2 * esize)), esize);
+    let result = compiler_prelude::gen_float(bb, op, ty, elements);
+    gen_assign(bb, reg(operand.rd, ty), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// FMAXV and the like, `op` on the halves of the four singles of rn then on their results.
+fn compile_float_reduce(bb: &mut BasicBlock, operand: &QSizeRnRd, op: FloatOp) {
+    if operand.q == 0 || operand.size & 1 == 1 {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    let ty = IrType::U32;
+    let elements = gen_split(bb, reg(operand.rn, IrType::U128), 32);
+    let low = compiler_prelude::gen_float(bb, op, ty, elements[..2].to_vec());
+    let high = compiler_prelude::gen_float(bb, op, ty, elements[2..].to_vec());
+    let result = compiler_prelude::gen_float(bb, op, ty, vec![low, high]);
+    gen_assign(bb, reg(operand.rd, ty), result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The element-wise float operations of the two-register miscellaneous instructions, the
+/// conversions to and from integers keep the width of the elements.
+fn compile_vec_float_unary(bb: &mut BasicBlock, operand: &QSizeRnRd, op: FloatOp) {
+    let Some(arrangement) = float_arrangement(operand.size, operand.q) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let ty = uint(arrangement.esize);
+    let mut result = Vec::new();
+    for element in gen_read_elements(bb, operand.rn, arrangement) {
+        result.push(compiler_prelude::gen_float(bb, op, ty, vec![element]));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_vec_frint(bb: &mut BasicBlock, operand: &QSizeRnRd, rounding: Option<FloatRounding>) {
+    let op = FloatOp::RoundInt {
+        rounding,
+        exact: false,
+    };
+    compile_vec_float_unary(bb, operand, op);
+}
+
+fn compile_vec_fcvt_int(
+    bb: &mut BasicBlock,
+    operand: &QSizeRnRd,
+    signed: bool,
+    rounding: FloatRounding,
+) {
+    let op = FloatOp::ToInt {
+        signed,
+        fbits: 0,
+        rounding: Some(rounding),
+    };
+    compile_vec_float_unary(bb, operand, op);
+}
+
+/// The vector FABS and FNEG, which only touch the sign bits.
+fn compile_vec_float_sign(bb: &mut BasicBlock, operand: &QSizeRnRd, op: FloatSignOp) {
+    let Some(arrangement) = float_arrangement(operand.size, operand.q) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let mut result = Vec::new();
+    for element in gen_read_elements(bb, operand.rn, arrangement) {
+        result.push(match op {
+            FloatSignOp::Mov => element,
+            FloatSignOp::Abs => gen_float_abs(bb, element),
+            FloatSignOp::Neg => gen_float_neg(bb, element),
+        });
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// FCMGT and the like against zero, `swap` compares zero with the elements for FCMLT and
+/// FCMLE.
+fn compile_vec_float_compare_zero(
+    bb: &mut BasicBlock,
+    operand: &QSizeRnRd,
+    cond: FloatCond,
+    swap: bool,
+) {
+    let Some(arrangement) = float_arrangement(operand.size, operand.q) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let zero = imm(uint(arrangement.esize), 0);
+    let mut result = Vec::new();
+    for element in gen_read_elements(bb, operand.rn, arrangement) {
+        result.push(match swap {
+            true => gen_float_compare(bb, cond, zero, element),
+            false => gen_float_compare(bb, cond, element, zero),
+        });
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// FCVTN and FCVTXN: convert the elements of rn to half their width into half of rd, doubles
+/// to singles or singles to halves by the low bit of `size`.
+fn compile_vec_fcvt_narrow(
+    bb: &mut BasicBlock,
+    operand: &QSizeRnRd,
+    rounding: Option<FloatRounding>,
+) {
+    let esize = 16 << (operand.size & 1);
+    // FCVTXN only narrows doubles
+    if rounding == Some(FloatRounding::ToOdd) && esize == 16 {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    let op = FloatOp::Convert { rounding };
+    let mut result = Vec::new();
+    for element in gen_split(bb, reg(operand.rn, IrType::U128), 2 * esize) {
+        result.push(compiler_prelude::gen_float(
+            bb,
+            op,
+            uint(esize),
+            vec![element],
+        ));
+    }
+
+    gen_write_half(bb, operand.rd, &result, operand.q == 1);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// FCVTL: convert the elements of half of rn to twice their width, halves to singles or
+/// singles to doubles by the low bit of `size`.
+fn compile_vec_fcvt_long(bb: &mut BasicBlock, operand: &QSizeRnRd) {
+    let esize = 16 << (operand.size & 1);
+
+    let op = FloatOp::Convert { rounding: None };
+    let mut result = Vec::new();
+    for element in gen_read_half(bb, operand.rn, esize, operand.q == 1) {
+        result.push(compiler_prelude::gen_float(
+            bb,
+            op,
+            uint(2 * esize),
+            vec![element],
+        ));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The scalar three same instructions on the elements in the low bits of the registers. Only
+/// the saturating operations and SQDMULH take other sizes than 64 bits.
+fn compile_scalar_int(
+    bb: &mut BasicBlock,
+    operand: &AdvSimdScalarThreeSame,
+    op: VecIntOp,
+    signed: bool,
+) {
+    let esize = 8 << operand.size;
+    let reserved = match op {
+        VecIntOp::SaturatingAdd
+        | VecIntOp::SaturatingSub
+        | VecIntOp::Shift {
+            saturating: true, ..
+        } => false,
+        VecIntOp::DoublingMulHigh { .. } => op.is_reserved(esize),
+        _ => esize != 64,
+    };
+    if reserved {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    let ty = uint(esize);
+    let (rn, rm) = (reg(operand.rn, ty), reg(operand.rm, ty));
+    let result = gen_vec_int_op(bb, op, rn, rm, None, signed);
+    gen_write_elements(bb, operand.rd, &[result]);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_scalar_float(bb: &mut BasicBlock, operand: &AdvSimdScalarThreeSame, op: VecFloatOp) {
+    let ty = uint(32 << (operand.size & 1));
+    let (rn, rm) = (reg(operand.rn, ty), reg(operand.rm, ty));
+    let result = gen_vec_float_op(bb, op, rn, rm, None);
+    gen_write_elements(bb, operand.rd, &[result]);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The operations of the scalar two-register miscellaneous instructions.
+#[derive(Clone, Copy)]
+enum ScalarUnaryOp {
+    /// ABS, NEG and their saturating variants
+    Int(VecUnaryOp),
+    /// CMGT, CMEQ and the like against zero, with zero as the first operand if `swap`
+    CompareZero {
+        op: VecIntOp,
+        swap: bool,
+    },
+    /// SUQADD and USQADD
+    SaturatingAccumulate {
+        signed: bool,
+    },
+    /// SQXTN, UQXTN and SQXTUN of the double-width element of rn
+    ExtractNarrow {
+        signed: bool,
+        narrowing: Narrowing,
+    },
+    Float(FloatOp),
+    FloatCompareZero {
+        cond: FloatCond,
+        swap: bool,
+    },
+    /// FCVTXN of the double in rn
+    ConvertToOdd,
+    /// FRECPX
+    RecipExponent,
+}
+
+fn compile_scalar_two_misc(
+    bb: &mut BasicBlock,
+    operand: &AdvSimdScalarTwoRegMisc,
+    op: ScalarUnaryOp,
+) {
+    let esize = match op {
+        ScalarUnaryOp::Float(_)
+        | ScalarUnaryOp::FloatCompareZero { .. }
+        | ScalarUnaryOp::RecipExponent => 32 << (operand.size & 1),
+        ScalarUnaryOp::ConvertToOdd => 32,
+        _ => 8 << operand.size,
+    };
+    let reserved = match op {
+        ScalarUnaryOp::Int(VecUnaryOp::Abs(narrowing) | VecUnaryOp::Neg(narrowing)) => {
+            matches!(narrowing, Narrowing::Truncate) && esize != 64
+        }
+        ScalarUnaryOp::CompareZero { .. } => esize != 64,
+        ScalarUnaryOp::ExtractNarrow { .. } => esize == 64,
+        ScalarUnaryOp::ConvertToOdd => operand.size & 1 == 0,
+        _ => false,
+    };
+    if reserved {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    let ty = uint(esize);
+    let rn = reg(operand.rn, ty);
+    let result = match op {
+        ScalarUnaryOp::Int(op) => gen_vec_unary_op(bb, op, rn),
+        ScalarUnaryOp::CompareZero { op, swap } => match swap {
+            true => gen_vec_int_op(bb, op, imm(ty, 0), rn, None, true),
+            false => gen_vec_int_op(bb, op, rn, imm(ty, 0), None, true),
+        },
+        ScalarUnaryOp::SaturatingAccumulate { signed } => {
+            gen_saturating_accumulate(bb, rn, reg(operand.rd, ty), signed)
+        }
+        ScalarUnaryOp::ExtractNarrow { signed, narrowing } => {
+            let value = gen_widen(bb, reg(operand.rn, uint(2 * esize)), signed);
+            gen_narrow(bb, value, esize, narrowing)
+        }
+        ScalarUnaryOp::Float(op) => compiler_prelude::gen_float(bb, op, ty, vec![rn]),
+        ScalarUnaryOp::FloatCompareZero { cond, swap } => match swap {
+            true => gen_float_compare(bb, cond, imm(ty, 0), rn),
+            false => gen_float_compare(bb, cond, rn, imm(ty, 0)),
+        },
+        ScalarUnaryOp::ConvertToOdd => {
+            let op = FloatOp::Convert {
+                rounding: Some(FloatRounding::ToOdd),
+            };
+            compiler_prelude::gen_float(bb, op, ty, vec![reg(operand.rn, IrType::U64)])
+        }
+        ScalarUnaryOp::RecipExponent => gen_recip_exponent(bb, rn),
+    };
+
+    gen_write_elements(bb, operand.rd, &[result]);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_scalar_fcvt_int(
+    bb: &mut BasicBlock,
+    operand: &AdvSimdScalarTwoRegMisc,
+    signed: bool,
+    rounding: FloatRounding,
+) {
+    let op = FloatOp::ToInt {
+        signed,
+        fbits: 0,
+        rounding: Some(rounding),
+    };
+    compile_scalar_two_misc(bb, operand, ScalarUnaryOp::Float(op));
+}
+
+/// FRECPX, the sign and the inverted exponent of a float with a zero fraction. The zeros and
+/// denormals get the largest finite exponent and the NaNs are processed like by the
+/// arithmetic.
+fn gen_recip_exponent(bb: &mut BasicBlock, value: IrValue) -> IrValue {
+    let ty = value.ty();
+    let esize = bits(ty);
+    let fraction = if esize == 32 { 23 } else { 52 };
+    let sign_bit = 1 << (esize - 1);
+    let exponent_mask = sign_bit - (1 << fraction);
+
+    let sign = gen_and(bb, value, imm(ty, sign_bit));
+    let exponent = gen_and(bb, value, imm(ty, exponent_mask));
+    let inverted = gen_xor(bb, exponent, imm(ty, exponent_mask));
+    let zero = compiler_prelude::gen_is_zero(bb, exponent);
+    let largest = imm(ty, exponent_mask - (1 << fraction));
+    let exponent = gen_select(bb, zero, largest, inverted);
+    let result = gen_or(bb, sign, exponent);
+
+    // NaNs are the magnitudes above the infinity
+    let magnitude = gen_and(bb, value, imm(ty, !sign_bit & (u64::MAX >> (64 - esize))));
+    let magnitude = gen_widen(bb, magnitude, false);
+    let infinity = gen_widen(bb, imm(ty, exponent_mask), false);
+    let nan = gen_less(bb, infinity, magnitude);
+    let processed = compiler_prelude::gen_float(bb, FloatOp::Max, ty, vec![value, value]);
+    gen_select(bb, nan, processed, result)
+}
+
+/// The vector SCVTF, UCVTF, FCVTZS and FCVTZU with fraction bits encoded like a right shift.
+fn compile_vec_fcvt_fixed(
+    bb: &mut BasicBlock,
+    operand: &AdvSimdShiftByImm,
+    signed: bool,
+    to_int: bool,
+) {
+    // Half precision is not supported
+    let (esize, immhb) = shift_imm_fields(operand);
+    if esize < 32 {
+        return compiler_prelude::gen_undefined(bb);
+    }
+    let Some(arrangement) = Arrangement::new((esize / 8).trailing_zeros() as u8, operand.q) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let fbits = (2 * esize - immhb) as u8;
+    let op = match to_int {
+        true => FloatOp::ToInt {
+            signed,
+            fbits,
+            rounding: Some(FloatRounding::TowardZero),
+        },
+        false => FloatOp::FromInt { signed, fbits },
+    };
+    let ty = uint(esize);
+    let mut result = Vec::new();
+    for element in gen_read_elements(bb, operand.rn, arrangement) {
+        result.push(compiler_prelude::gen_float(bb, op, ty, vec![element]));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// URECPE and URSQRTE: the 9-bit estimates of the reciprocal and the reciprocal square root
+/// of the 32-bit unsigned fixed point elements in [0, 1), or all ones when they are too small.
+fn compile_vec_unsigned_estimate(bb: &mut BasicBlock, operand: &QSizeRnRd, sqrt: bool) {
+    let arrangement = Arrangement::new(2, operand.q);
+    let Some(arrangement) = arrangement.filter(|_| operand.size & 1 == 0) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let u32 = IrType::U32;
+    let mut result = Vec::new();
+    for element in gen_read_elements(bb, operand.rn, arrangement) {
+        let top = gen_shr(bb, element, imm(u32, 23));
+        let (estimate, small) = match sqrt {
+            true => {
+                let estimate = gen_rsqrt_estimate(bb, top);
+                let small = gen_shr(bb, element, imm(u32, 30));
+                (estimate, small)
+            }
+            false => {
+                let estimate = gen_recip_estimate(bb, top);
+                let small = gen_shr(bb, element, imm(u32, 31));
+                (estimate, small)
+            }
+        };
+        let small = compiler_prelude::gen_is_zero(bb, small);
+        let estimate = gen_shl(bb, estimate, imm(u32, 23));
+        result.push(gen_select(bb, small, imm(u32, u32::MAX as u64), estimate));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The RecipEstimate of the architecture: the reciprocal of the 9-bit fixed point value in
+/// [0.5, 1) as a 9-bit fixed point value in [1, 2).
+fn gen_recip_estimate(bb: &mut BasicBlock, value: IrValue) -> IrValue {
+    let ty = value.ty();
+    let a = gen_add(bb, value, value);
+    let a = gen_add(bb, a, imm(ty, 1));
+    let b = gen_div(bb, imm(ty, 1 << 19), a);
+    let b = gen_add(bb, b, imm(ty, 1));
+    gen_shr(bb, b, imm(ty, 1))
+}
+
+/// The RecipSqrtEstimate of the architecture: the reciprocal square root of the 9-bit fixed
+/// point value in [0.25, 1) as a 9-bit fixed point value in [1, 2).
+fn gen_rsqrt_estimate(bb: &mut BasicBlock, value: IrValue) -> IrValue {
+    let u64 = IrType::U64;
+    let value = gen_zext(bb, value, u64);
+
+    // In units of 1/512 below 0.5 and of 1/256 above
+    let below = gen_less(bb, value, imm(u64, 256));
+    let fine = gen_add(bb, value, value);
+    let fine = gen_add(bb, fine, imm(u64, 1));
+    let coarse = gen_and(bb, value, imm(u64, !1));
+    let coarse = gen_add(bb, coarse, coarse);
+    let coarse = gen_add(bb, coarse, imm(u64, 2));
+    let a = gen_select(bb, below, fine, coarse);
+
+    // The largest b with a * b * b < 2^28, bit by bit
+    let mut b = imm(u64, 0);
+    for bit in (0..10).rev() {
+        let candidate = gen_or(bb, b, imm(u64, 1 << bit));
+        let square = gen_mul(bb, candidate, candidate);
+        let product = gen_mul(bb, a, square);
+        let fits = gen_less(bb, product, imm(u64, 1 << 28));
+        b = gen_select(bb, fits, candidate, b);
+    }
+
+    let b = gen_add(bb, b, imm(u64, 1));
+    let estimate = gen_shr(bb, b, imm(u64, 1));
+    gen_zext(bb, estimate, IrType::U32)
+}
+
+/// The by-element instructions in their vector and scalar forms, the scalar forms operate on
+/// the lowest element of rn only.
+struct IndexedOperand {
+    scalar: bool,
+    q: u8,
+    size: u8,
+    l: u8,
+    m: u8,
+    rm: AArch64RegisterId,
+    h: u8,
+    rn: AArch64RegisterId,
+    rd: AArch64RegisterId,
+}
+
+impl From<&AdvSimdXIndexedElem> for IndexedOperand {
+    fn from(operand: &AdvSimdXIndexedElem) -> Self {
+        Self {
+            scalar: false,
+            q: operand.q,
+            size: operand.size,
+            l: operand.l,
+            m: operand.m,
+            rm: operand.rm,
+            h: operand.h,
+            rn: operand.rn,
+            rd: operand.rd,
+        }
+    }
+}
+
+impl From<&AdvSimdScalarXIndexedElem> for IndexedOperand {
+    fn from(operand: &AdvSimdScalarXIndexedElem) -> Self {
+        Self {
+            scalar: true,
+            q: 0,
+            size: operand.size,
+            l: operand.l,
+            m: operand.m,
+            rm: operand.rm,
+            h: operand.h,
+            rn: operand.rn,
+            rd: operand.rd,
+        }
+    }
+}
+
+impl IndexedOperand {
+    /// The element size, the indexed element of rm and the number of lanes, `None` for the
+    /// reserved encodings. The float instructions take singles or doubles by the low bit of
+    /// `size`.
+    fn element(&self, float: bool) -> Option<(u64, usize, AArch64RegisterId, u64)> {
+        let AArch64RegisterId::V(rm) = self.rm else {
+            unreachable!("{:?} is not a vector register", self.rm)
+        };
+        let (h, l, m) = (self.h as usize, self.l as usize, self.m as usize);
+
+        // 16-bit elements only reach the first 16 registers, M extends the index instead
+        let esize = match float {
+            true => 32 << (self.size & 1),
+            false => 8 << self.size,
+        };
+        let (index, rm) = match esize {
+            16 => (h << 2 | l << 1 | m, rm),
+            32 => (h << 1 | l, self.m << 4 | rm),
+            64 if float && l == 0 => (h, self.m << 4 | rm),
+            _ => return None,
+        };
+
+        let count = match self.scalar {
+            true => 1,
+            false => (64 << self.q) / esize,
+        };
+        // The vector forms have no 1D arrangement
+        (self.scalar || count > 1).then_some((esize, index, AArch64RegisterId::V(rm), count))
+    }
+}
+
+/// MUL, MLA, MLS, SQDMULH and SQRDMULH by element.
+fn compile_int_by_elem(bb: &mut BasicBlock, operand: IndexedOperand, op: VecIntOp) {
+    let Some((esize, index, rm, count)) = operand.element(false) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let element = gen_split(bb, reg(rm, IrType::U128), esize)[index];
+    let ty = uint(esize * count);
+    let rn = gen_split(bb, reg(operand.rn, ty), esize);
+    let rd = match op.accumulates() {
+        true => gen_split(bb, reg(operand.rd, ty), esize),
+        false => Vec::new(),
+    };
+
+    let mut result = Vec::new();
+    for (i, &rn) in rn.iter().enumerate() {
+        result.push(gen_vec_int_op(
+            bb,
+            op,
+            rn,
+            element,
+            rd.get(i).copied(),
+            true,
+        ));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// How the double-width products of the long multiplies go into the destination.
+#[derive(Clone, Copy)]
+enum Accumulate {
+    None,
+    Add,
+    Sub,
+}
+
+/// SMULL, UMLAL, SQDMLSL and the other long multiplies by element. The vector forms take the
+/// elements of the lower half of rn, or of the upper half for the "2" variants. The doubling
+/// multiplies are signed and saturate both the product and the sum.
+fn compile_mul_long_by_elem(
+    bb: &mut BasicBlock,
+    operand: IndexedOperand,
+    signed: bool,
+    doubling: bool,
+    accumulate: Accumulate,
+) {
+    let Some((esize, index, rm, _)) = operand.element(false) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let element = gen_split(bb, reg(rm, IrType::U128), esize)[index];
+    let rn = match operand.scalar {
+        true => vec![reg(operand.rn, uint(esize))],
+        false => gen_read_half(bb, operand.rn, esize, operand.q == 1),
+    };
+    let wide = 2 * esize;
+    let rd = match accumulate {
+        Accumulate::None => Vec::new(),
+        _ => gen_split(bb, reg(operand.rd, uint(wide * rn.len() as u64)), wide),
+    };
+
+    let mut result = Vec::new();
+    for (i, &rn) in rn.iter().enumerate() {
+        let rd = rd.get(i).copied();
+        result.push(gen_mul_long(
+            bb, rn, element, rd, signed, doubling, accumulate,
+        ));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// The double-width product of the elements rn and rm, accumulated into the double-width
+/// element rd.
+fn gen_mul_long(
+    bb: &mut BasicBlock,
+    rn: IrValue,
+    rm: IrValue,
+    rd: Option<IrValue>,
+    signed: bool,
+    doubling: bool,
+    accumulate: Accumulate,
+) -> IrValue {
+    let wide = 2 * bits(rn.ty());
+    let rn = gen_widen(bb, rn, signed);
+    let rm = gen_widen(bb, rm, signed);
+    let product = gen_mul(bb, rn, rm);
+    let product = match doubling {
+        true => {
+            let product = gen_add(bb, product, product);
+            let product = gen_narrow(bb, product, wide, Narrowing::Signed);
+            gen_widen(bb, product, true)
+        }
+        false => product,
+    };
+
+    let value = match accumulate {
+        Accumulate::None => product,
+        Accumulate::Add | Accumulate::Sub => {
+            let rd = gen_widen(bb, rd.unwrap(), doubling);
+            match accumulate {
+                Accumulate::Add => gen_add(bb, rd, product),
+                _ => gen_sub(bb, rd, product),
+            }
+        }
+    };
+    match doubling {
+        true => gen_narrow(bb, value, wide, Narrowing::Signed),
+        false => gen_zext(bb, value, uint(wide)),
+    }
+}
+
+/// The operations of the three different instructions, the "2" variants take the elements of
+/// the upper halves of the narrow operands.
+#[derive(Clone, Copy)]
+enum ThreeDifferentOp {
+    /// ADDL, SUBL, ABDL and ABAL, the operation on the extended elements of rn and rm
+    Long(VecIntOp),
+    /// ADDW and SUBW, the operation on the wide rn and the extended elements of rm
+    Wide(VecIntOp),
+    /// ADDHN, SUBHN and their rounding variants, the high half of the sum or difference of
+    /// the wide rn and rm
+    NarrowHigh { subtract: bool, rounding: bool },
+    MulLong {
+        doubling: bool,
+        accumulate: Accumulate,
+    },
+    /// PMULL, also on 64-bit elements for the 128-bit product
+    PolyMulLong,
+}
+
+fn compile_three_different(
+    bb: &mut BasicBlock,
+    operand: &QSizeRmRnRd,
+    op: ThreeDifferentOp,
+    signed: bool,
+) {
+    let reserved = match op {
+        ThreeDifferentOp::PolyMulLong => matches!(operand.size, 0b01 | 0b10),
+        ThreeDifferentOp::MulLong { doubling: true, .. } => matches!(operand.size, 0b00 | 0b11),
+        _ => operand.size == 0b11,
+    };
+    if reserved {
+        return compiler_prelude::gen_undefined(bb);
+    }
+
+    let esize = 8 << operand.size;
+    let wide = 2 * esize;
+    let upper = operand.q == 1;
+    let read_wide = |bb: &mut BasicBlock, id| gen_split(bb, reg(id, IrType::U128), wide);
+    let extend = |bb: &mut BasicBlock, element| match signed {
+        true => gen_sext(bb, element, uint(wide)),
+        false => gen_zext(bb, element, uint(wide)),
+    };
+
+    let rn = match op {
+        ThreeDifferentOp::Wide(_) | ThreeDifferentOp::NarrowHigh { .. } => {
+            read_wide(bb, operand.rn)
+        }
+        _ => gen_read_half(bb, operand.rn, esize, upper),
+    };
+    let rm = match op {
+        ThreeDifferentOp::NarrowHigh { .. } => read_wide(bb, operand.rm),
+        _ => gen_read_half(bb, operand.rm, esize, upper),
+    };
+    let rd = match op {
+        ThreeDifferentOp::Long(VecIntOp::AbsDiffAccumulate)
+        | ThreeDifferentOp::MulLong {
+            accumulate: Accumulate::Add | Accumulate::Sub,
+            ..
+        } => read_wide(bb, operand.rd),
+        _ => Vec::new(),
+    };
+
+    let mut result = Vec::new();
+    for (i, (&rn, &rm)) in rn.iter().zip(&rm).enumerate() {
+        let rd = rd.get(i).copied();
+        result.push(match op {
+            ThreeDifferentOp::Long(op) => {
+                let rn = extend(bb, rn);
+                let rm = extend(bb, rm);
+                gen_vec_int_op(bb, op, rn, rm, rd, signed)
+            }
+            ThreeDifferentOp::Wide(op) => {
+                let rm = extend(bb, rm);
+                gen_vec_int_op(bb, op, rn, rm, rd, signed)
+            }
+            ThreeDifferentOp::NarrowHigh { subtract, rounding } => {
+                let value = match subtract {
+                    true => gen_sub(bb, rn, rm),
+                    false => gen_add(bb, rn, rm),
+                };
+                let value = match rounding {
+                    true => {
+                        let half = gen_imm(bb, uint(wide), 1 << (esize - 1));
+                        gen_add(bb, value, half)
+                    }
+                    false => value,
+                };
+                let amount = gen_imm(bb, uint(wide), esize);
+                let high = gen_shr(bb, value, amount);
+                gen_zext(bb, high, uint(esize))
+            }
+            ThreeDifferentOp::MulLong {
+                doubling,
+                accumulate,
+            } => gen_mul_long(bb, rn, rm, rd, signed, doubling, accumulate),
+            ThreeDifferentOp::PolyMulLong => {
+                let rn = gen_zext(bb, rn, uint(wide));
+                let rm = gen_zext(bb, rm, uint(wide));
+                gen_poly_mul(bb, rn, rm)
+            }
+        });
+    }
+
+    match op {
+        ThreeDifferentOp::NarrowHigh { .. } => gen_write_half(bb, operand.rd, &result, upper),
+        _ => gen_write_elements(bb, operand.rd, &result),
+    }
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// FMUL, FMULX, FMLA and FMLS by element.
+fn compile_float_by_elem(bb: &mut BasicBlock, operand: IndexedOperand, op: VecFloatOp) {
+    let Some((esize, index, rm, count)) = operand.element(true) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    let element = gen_split(bb, reg(rm, IrType::U128), esize)[index];
+    let ty = uint(esize * count);
+    let rn = gen_split(bb, reg(operand.rn, ty), esize);
+    let rd = match op {
+        VecFloatOp::MulAdd { .. } => gen_split(bb, reg(operand.rd, ty), esize),
+        _ => Vec::new(),
+    };
+
+    let mut result = Vec::new();
+    for (i, &rn) in rn.iter().enumerate() {
+        result.push(gen_vec_float_op(bb, op, rn, element, rd.get(i).copied()));
+    }
+
+    gen_write_elements(bb, operand.rd, &result);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+// The preferred return address of SVC, HVC and SMC is the next instruction,
+// so pc is moved before raising the exception.
+fn compile_svc(bb: &mut BasicBlock, operand: &ExceptionGen) {
+    let imm16 = operand.imm16 as u64;
+
+    compiler_prelude::gen_move_pc(bb);
+    compiler_prelude::gen_exception(
+        bb,
+        ExceptionKind::SupervisorCall(imm16),
+        compiler_prelude::syndrome(compiler_prelude::exception_class::SVC, imm16),
+    );
+}
+
+/// HVC and SMC are undefined at EL0.
+fn compile_hvc(bb: &mut BasicBlock, operand: &ExceptionGen) {
+    let imm16 = operand.imm16 as u64;
+
+    sysreg::gen_check_el(bb, 1);
+    compiler_prelude::gen_move_pc(bb);
+    compiler_prelude::gen_exception(
+        bb,
+        ExceptionKind::HypervisorCall(imm16),
+        compiler_prelude::syndrome(compiler_prelude::exception_class::HVC, imm16),
+    );
+}
+
+fn compile_smc(bb: &mut BasicBlock, operand: &ExceptionGen) {
+    let imm16 = operand.imm16 as u64;
+
+    sysreg::gen_check_el(bb, 1);
+    compiler_prelude::gen_move_pc(bb);
+    compiler_prelude::gen_exception(
+        bb,
+        ExceptionKind::SecureMonitorCall(imm16),
+        compiler_prelude::syndrome(compiler_prelude::exception_class::SMC, imm16),
+    );
+}
+
+// The preferred return address of BRK and UDF is the instruction itself,
+// resuming after the exception skips the instruction.
+fn compile_brk(bb: &mut BasicBlock, operand: &ExceptionGen) {
+    let imm16 = operand.imm16 as u64;
+
+    compiler_prelude::gen_exception(
+        bb,
+        ExceptionKind::Breakpoint(imm16),
+        compiler_prelude::syndrome(compiler_prelude::exception_class::BRK, imm16),
+    );
+    compiler_prelude::gen_move_pc(bb);
+}
+
+fn compile_udf(bb: &mut BasicBlock, _operand: &Imm16) {
+    compiler_prelude::gen_undefined(bb);
+}
+
+// Waiting hints resume at the next instruction, the scheduler decides when.
+fn compile_hint_interrupt(bb: &mut BasicBlock, interrupt: Interrupt) {
+    compiler_prelude::gen_move_pc(bb);
+    compiler_prelude::gen_interrupt(bb, interrupt);
+}
+
+fn compile_barrier(bb: &mut BasicBlock, operand: &Barriers) {
+    compiler_prelude::gen_barrier(bb, operand.crm);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// MRS, reads a system register into `Rt`. Unknown registers and reads from an exception level
+/// without access are undefined.
+fn compile_mrs(bb: &mut BasicBlock, operand: &SysRegMov) {
+    let sysreg = SysReg::lookup(operand.into());
+    let Some((sysreg, el)) = sysreg.and_then(|sysreg| Some((sysreg, sysreg.read?))) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    sysreg::gen_check_el(bb, el);
+    let value = sysreg.gen_read(bb);
+    gen_assign(bb, reg(operand.rt, IrType::U64), value);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// MSR (register), writes `Rt` to a system register. Unknown and read-only registers and writes
+/// from an exception level without access are undefined.
+fn compile_msr_reg(bb: &mut BasicBlock, operand: &SysRegMov) {
+    let sysreg = SysReg::lookup(operand.into());
+    let Some((sysreg, el)) = sysreg.and_then(|sysreg| Some((sysreg, sysreg.write?))) else {
+        return compiler_prelude::gen_undefined(bb);
+    };
+
+    sysreg::gen_check_el(bb, el);
+    sysreg.gen_write(bb, reg(operand.rt, IrType::U64));
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// MSR (immediate), writes CRm to a PSTATE field. It has the permissions of the system
+/// register of the field, DAIFSet and DAIFClr set and clear bits of DAIF.
+fn compile_msr_imm(bb: &mut BasicBlock, operand: &PstateOp) {
+    use compiler_prelude::pstate;
+
+    let crm = operand.crm as u64;
+    // The system register of the field and the position of its lowest bit
+    let (encoding, position) = match (operand.op1, operand.op2) {
+        (0b000, 0b011) => (SysRegEncoding::new(3, 0, 4, 2, 4), pstate::UAO),
+        (0b000, 0b100) => (SysRegEncoding::new(3, 0, 4, 2, 3), pstate::PAN),
+        (0b000, 0b101) => (SysRegEncoding::new(3, 0, 4, 2, 0), pstate::SP),
+        (0b011, 0b001) => (SysRegEncoding::new(3, 3, 4, 2, 6), pstate::SSBS),
+        (0b011, 0b010) => (SysRegEncoding::new(3, 3, 4, 2, 5), pstate::DIT),
+        (0b011, 0b110 | 0b111) => (SysRegEncoding::new(3, 3, 4, 2, 1), pstate::F),
+        _ => return compiler_prelude::gen_undefined(bb),
+    };
+    let sysreg = SysReg::lookup(encoding).expect("the PSTATE fields are system registers");
+
+    sysreg::gen_check_el(bb, sysreg.write.expect("the PSTATE fields are writable"));
+    let value = match operand.op2 {
+        0b110 => {
+            let daif = sysreg.gen_read(bb);
+            gen_or(bb, daif, imm(IrType::U64, crm << position))
+        }
+        0b111 => {
+            let daif = sysreg.gen_read(bb);
+            gen_and(bb, daif, imm(IrType::U64, !(crm << position)))
+        }
+        _ => imm(IrType::U64, (crm & 1) << position),
+    };
+    sysreg.gen_write(bb, value);
+    compiler_prelude::gen_move_pc(bb);
+}
+
+/// SYS, the TLB and cache maintenance instructions. The caches aren't modelled, their
+/// maintenance only checks the exception level. The TLB invalidations of the inner shareable
+/// domain are broadcast to every cpu.
+fn compile_sys(bb: &mut BasicBlock, operand: &SystemInstructions) {
+    let SystemInstructions {
+        op1,
+        crn,
+        crm,
+        op2,
+        rt,
+    } = *operand;
+
+    match (op1, crn, crm, op2) {
+        // IC IALLUIS, IC IALLU, DC IVAC, DC ISW, DC CSW and DC CISW
+        (0, 7, 1 | 5, 0) | (0, 7, 6, 1) | (0, 7, 6 | 10 | 14, 2) => sysreg::gen_check_el(bb, 1),
+        // IC IVAU, DC CVAC, DC CVAU, DC CVAP and DC CIVAC
+        (3, 7, 5, 1) | (3, 7, 10 | 11 | 12 | 14, 1) => {}
+        // TLBI VMALLE1(IS), VAE1(IS), ASIDE1(IS), VAAE1(IS), VALE1(IS) and VAALE1(IS)
+        (0, 8, 3 | 7, 0 | 1 | 2 | 3 | 5 | 7) => {
+            sysreg::gen_check_el(bb, 1);
+
+            let value = reg(rt, IrType::U64);
+            // The address is VA[55:12], the ASID is in the top 16 bits
+            let addr = matches!(op2, 1 | 3 | 5 | 7).then(|| {
+                let page = gen_and(bb, value, imm(IrType::U64, 0xfff_ffff_ffff));
+                gen_shl(bb, page, imm(IrType::U64, 12))
+            });
+            let asid = matches!(op2, 1 | 2 | 5).then(|| gen_shr(bb, value, imm(IrType::U64, 48)));
+            bb.push_inst(IrInst::InvalidateTlb {
+                addr,
+                asid,
+                broadcast: crm == 3,
+            });
+        }
+        _ => return compiler_prelude::gen_undefined(bb),
+    }
+    compiler_prelude::gen_move_pc(bb);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aarch64::testing::TestCpu;
+    use core::Instruction;
+    use AArch64RegisterId::{Fpcr, Fpsr, Nzcv, Pc, Pstate, Sp, X};
+
+    const N: u64 = 0b1000;
+    const Z: u64 = 0b0100;
+    const C: u64 = 0b0010;
+    const V: u64 = 0b0001;
+
+    /// Run the instructions with the given registers set.
+    fn run(registers: &[(AArch64RegisterId, u64)], insts: &[u32]) -> TestCpu {
+        let mut cpu = TestCpu::new();
+        for &(id, value) in registers {
+            cpu.set(id, value);
+        }
+
+        assert_eq!(cpu.run(insts), None);
+        cpu
+    }
+
+    /// Base of the memory mapped by `run_with_memory`.
+    const MEM: u64 = 0x1000;
+
+    /// Run the instructions with the given registers set and the memory at `MEM` initialized.
+    fn run_with_memory(
+        registers: &[(AArch64RegisterId, u64)],
+        memory: &[(u64, &[u8])],
+        insts: &[u32],
+    ) -> TestCpu {
+        let mut cpu = TestCpu::new();
+        cpu.map_memory(MEM, 0x1000);
+        for &(addr, bytes) in memory {
+            cpu.write_memory(addr, bytes);
+        }
+        for &(id, value) in registers {
+            cpu.set(id, value);
+        }
+
+        assert_eq!(cpu.run(insts), None);
+        cpu
+    }
+
+    fn read_u64(cpu: &TestCpu, addr: u64) -> u64 {
+        u64::from_le_bytes(cpu.read_memory(addr, 8).try_into().unwrap())
+    }
+
+    fn nzcv(cpu: &TestCpu) -> u64 {
+        cpu.get(Nzcv) >> 28
+    }
+
+    #[test]
+    fn add_sub_imm() {
+        let cpu = run(&[(X(1), 39)], &[0x9100_0c20]); // add x0, x1, #3
+        assert_eq!(cpu.get(X(0)), 42);
+        assert_eq!(cpu.get(AArch64RegisterId::Pc), 4);
+
+        let cpu = run(&[(Sp, 0x1000)], &[0x9100_43ff]); // add sp, sp, #16
+        assert_eq!(cpu.get(Sp), 0x1010);
+
+        // The 32-bit result is zero extended into the X register
+        let cpu = run(&[(X(0), u64::MAX), (X(1), 0)], &[0x5100_0420]); // sub w0, w1, #1
+        assert_eq!(cpu.get(X(0)), 0xffff_ffff);
+
+        let cpu = run(&[(X(3), 0x1000)], &[0x3140_0462]); // adds w2, w3, #1, lsl #12
+        assert_eq!(cpu.get(X(2)), 0x2000);
+        assert_eq!(nzcv(&cpu), 0);
+    }
+
+    #[test]
+    fn pc_relative_address() {
+        let cpu = run(&[(Pc, 0x1008)], &[0x1000_0020]); // adr x0, #4
+        assert_eq!(cpu.get(X(0)), 0x100c);
+        assert_eq!(cpu.get(Pc), 0x100c);
+
+        let cpu = run(&[(Pc, 0x1008)], &[0x10ff_ffe0]); // adr x0, #-4
+        assert_eq!(cpu.get(X(0)), 0x1004);
+
+        let cpu = run(&[(Pc, 0x1234)], &[0xb000_0001]); // adrp x1, #4096
+        assert_eq!(cpu.get(X(1)), 0x2000);
+        assert_eq!(cpu.get(Pc), 0x1238);
+
+        let cpu = run(&[(Pc, 0x5678)], &[0xf0ff_fff1]); // adrp x17, #-4096
+        assert_eq!(cpu.get(X(17)), 0x4000);
+    }
+
+    #[test]
+    fn add_sub_flags() {
+        let cpu = run(&[(X(1), 5)], &[0xf100_143f]); // cmp x1, #5
+        assert_eq!(nzcv(&cpu), Z | C);
+
+        let cpu = run(&[(X(1), 4)], &[0xf100_143f]); // cmp x1, #5
+        assert_eq!(nzcv(&cpu), N);
+
+        let cpu = run(&[(X(1), 1 << 63), (X(2), 1)], &[0xeb02_0020]); // subs x0, x1, x2
+        assert_eq!(cpu.get(X(0)), i64::MAX as u64);
+        assert_eq!(nzcv(&cpu), C | V);
+
+        let cpu = run(&[(X(1), 0x7fff_ffff), (X(2), 1)], &[0x2b02_0020]); // adds w0, w1, w2
+        assert_eq!(cpu.get(X(0)), 0x8000_0000);
+        assert_eq!(nzcv(&cpu), N | V);
+
+        let cpu = run(&[(X(1), 0xffff_ffff), (X(2), 1)], &[0x2b02_0020]); // adds w0, w1, w2
+        assert_eq!(cpu.get(X(0)), 0);
+        assert_eq!(nzcv(&cpu), Z | C);
+
+        let cpu = run(&[(X(1), 3)], &[0x6b01_03e0]); // negs w0, w1
+        assert_eq!(cpu.get(X(0)), 0xffff_fffd);
+        assert_eq!(nzcv(&cpu), N);
+    }
+
+    #[test]
+    fn add_sub_shifted_reg() {
+        let cpu = run(&[(X(1), 2), (X(2), 3)], &[0x8b02_1020]); // add x0, x1, x2, lsl #4
+        assert_eq!(cpu.get(X(0)), 50);
+
+        let cpu = run(&[(X(1), 0), (X(2), 0xffff_fff0)], &[0x4b82_0820]); // sub w0, w1, w2, asr #2
+        assert_eq!(cpu.get(X(0)), 4);
+
+        let cpu = run(&[(X(1), 7)], &[0xcb01_03e0]); // neg x0, x1
+        assert_eq!(cpu.get(X(0)), -7i64 as u64);
+    }
+
+    #[test]
+    fn add_sub_ext_reg() {
+        // add x0, x1, w2, sxtw #2
+        let cpu = run(
+            &[(X(1), 100), (X(2), 0xdead_0000_ffff_ffff)],
+            &[0x8b22_c820],
+        );
+        assert_eq!(cpu.get(X(0)), 96);
+
+        let cpu = run(&[(Sp, 0x1000), (X(2), 0x1ff)], &[0xcb22_03e0]); // sub x0, sp, w2, uxtb
+        assert_eq!(cpu.get(X(0)), 0xf01);
+
+        let cpu = run(&[(X(1), u64::MAX), (X(2), 1)], &[0xab22_603f]); // cmn x1, x2, uxtx
+        assert_eq!(nzcv(&cpu), Z | C);
+    }
+
+    #[test]
+    fn add_sub_with_carry() {
+        let carry = (Nzcv, C << 28);
+
+        let cpu = run(&[carry, (X(1), 1), (X(2), 2)], &[0x9a02_0020]); // adc x0, x1, x2
+        assert_eq!(cpu.get(X(0)), 4);
+
+        let cpu = run(&[(X(1), 1), (X(2), 2)], &[0x9a02_0020]); // adc x0, x1, x2
+        assert_eq!(cpu.get(X(0)), 3);
+
+        let cpu = run(&[carry, (X(1), 5), (X(2), 5)], &[0x7a02_0020]); // sbcs w0, w1, w2
+        assert_eq!(cpu.get(X(0)), 0);
+        assert_eq!(nzcv(&cpu), Z | C);
+
+        let cpu = run(&[(X(1), 5), (X(2), 5)], &[0x7a02_0020]); // sbcs w0, w1, w2
+        assert_eq!(cpu.get(X(0)), 0xffff_ffff);
+        assert_eq!(nzcv(&cpu), N);
+
+        let cpu = run(&[(X(1), 5)], &[0xda01_03e0]); // ngc x0, x1
+        assert_eq!(cpu.get(X(0)), -6i64 as u64);
+    }
+
+    #[test]
+    fn logical_imm() {
+        let cpu = run(&[(X(1), 0x1234_5678)], &[0x9278_1c20]); // and x0, x1, #0xff00
+        assert_eq!(cpu.get(X(0)), 0x5600);
+
+        let cpu = run(&[(X(0), u64::MAX)], &[0x3200_f3e0]); // orr w0, wzr, #0x55555555
+        assert_eq!(cpu.get(X(0)), 0x5555_5555);
+
+        let cpu = run(&[(X(1), u64::MAX)], &[0xd201_f020]); // eor x0, x1, #0xaaaaaaaaaaaaaaaa
+        assert_eq!(cpu.get(X(0)), 0x5555_5555_5555_5555);
+
+        let cpu = run(&[(X(1), 2), (Nzcv, 0xf << 28)], &[0xf240_003f]); // tst x1, #1
+        assert_eq!(nzcv(&cpu), Z);
+
+        let cpu = run(&[(X(1), u64::MAX)], &[0x7201_0020]); // ands w0, w1, #0x80000000
+        assert_eq!(cpu.get(X(0)), 0x8000_0000);
+        assert_eq!(nzcv(&cpu), N);
+
+        let cpu = run(&[(X(1), 0x8000)], &[0x9100_003f]); // mov sp, x1
+        assert_eq!(cpu.get(Sp), 0x8000);
+    }
+
+    #[test]
+    fn logical_shifted_reg() {
+        let cpu = run(&[(X(1), 0xff), (X(2), 0x0f)], &[0x8a22_0020]); // bic x0, x1, x2
+        assert_eq!(cpu.get(X(0)), 0xf0);
+
+        let cpu = run(&[(X(1), 0), (X(2), 0xff_ffff)], &[0x2a22_2020]); // orn w0, w1, w2, lsl #8
+        assert_eq!(cpu.get(X(0)), 0xff);
+
+        let cpu = run(&[(X(1), 0xf0), (X(2), 0xff)], &[0xca22_0020]); // eon x0, x1, x2
+        assert_eq!(cpu.get(X(0)), !0x0f);
+
+        // eor x0, x1, x2, ror #8
+        let cpu = run(&[(X(1), 0), (X(2), 0x12)], &[0xcac2_2020]);
+        assert_eq!(cpu.get(X(0)), 0x1200_0000_0000_0000);
+
+        let cpu = run(&[(X(1), 0xf), (X(2), 0xf)], &[0x6a22_0020]); // bics w0, w1, w2
+        assert_eq!(cpu.get(X(0)), 0);
+        assert_eq!(nzcv(&cpu), Z);
+    }
+
+    #[test]
+    fn move_wide() {
+        let cpu = run(&[], &[0xd2c2_4680]); // movz x0, #0x1234, lsl #32
+        assert_eq!(cpu.get(X(0)), 0x1234_0000_0000);
+
+        let cpu = run(&[(X(0), u64::MAX)], &[0x12a2_4680]); // movn w0, #0x1234, lsl #16
+        assert_eq!(cpu.get(X(0)), 0xedcb_ffff);
+
+        let cpu = run(&[], &[0x9280_0000]); // movn x0, #0
+        assert_eq!(cpu.get(X(0)), u64::MAX);
+
+        let cpu = run(&[(X(0), u64::MAX)], &[0xf2b7_dde0]); // movk x0, #0xbeef, lsl #16
+        assert_eq!(cpu.get(X(0)), 0xffff_ffff_beef_ffff);
+
+        let cpu = run(&[(X(0), 0x1_0000_0000)], &[0x729f_ffe0]); // movk w0, #0xffff
+        assert_eq!(cpu.get(X(0)), 0xffff);
+    }
+
+    #[test]
+    fn multiply() {
+        let regs = [(X(1), 6), (X(2), 7), (X(3), 100)];
+
+        let cpu = run(&regs, &[0x9b02_0c20]); // madd x0, x1, x2, x3
+        assert_eq!(cpu.get(X(0)), 142);
+
+        let cpu = run(&regs, &[0x1b02_8c20]); // msub w0, w1, w2, w3
+        assert_eq!(cpu.get(X(0)), 58);
+
+        let regs = [(X(1), 0xffff_fffe), (X(2), 3), (X(3), 10)];
+        let cpu = run(&regs, &[0x9b22_0c20]); // smaddl x0, w1, w2, x3
+        assert_eq!(cpu.get(X(0)), 4);
+
+        let cpu = run(&regs, &[0x9ba2_8c20]); // umsubl x0, w1, w2, x3
+        assert_eq!(cpu.get(X(0)), 10u64.wrapping_sub(0xffff_fffe * 3));
+
+        let regs = [(X(1), -2i64 as u64), (X(2), 1 << 62)];
+        let cpu = run(&regs, &[0x9b42_7c20]); // smulh x0, x1, x2
+        assert_eq!(cpu.get(X(0)), u64::MAX);
+
+        let cpu = run(&regs, &[0x9bc2_7c20]); // umulh x0, x1, x2
+        assert_eq!(cpu.get(X(0)), (1 << 62) - 1);
+    }
+
+    #[test]
+    fn divide() {
+        let cpu = run(&[(X(1), 0xffff_fff0), (X(2), 3)], &[0x1ac2_0820]); // udiv w0, w1, w2
+        assert_eq!(cpu.get(X(0)), 0xffff_fff0 / 3);
+
+        let cpu = run(&[(X(1), 7), (X(2), 0)], &[0x1ac2_0820]); // udiv w0, w1, w2
+        assert_eq!(cpu.get(X(0)), 0);
+
+        let cpu = run(&[(X(1), -7i64 as u64), (X(2), 2)], &[0x9ac2_0c20]); // sdiv x0, x1, x2
+        assert_eq!(cpu.get(X(0)), -3i64 as u64);
+
+        // sdiv x0, x1, x2
+        let cpu = run(&[(X(1), i64::MIN as u64), (X(2), u64::MAX)], &[0x9ac2_0c20]);
+        assert_eq!(cpu.get(X(0)), i64::MIN as u64);
+    }
+
+    #[test]
+    fn variable_shifts() {
+        let cpu = run(&[(X(1), 1), (X(2), 65)], &[0x9ac2_2020]); // lsl x0, x1, x2
+        assert_eq!(cpu.get(X(0)), 2);
+
+        let cpu = run(&[(X(1), 0x8000_0000), (X(2), 31)], &[0x1ac2_2420]); // lsr w0, w1, w2
+        assert_eq!(cpu.get(X(0)), 1);
+
+        let cpu = run(&[(X(1), 1 << 63), (X(2), 4)], &[0x9ac2_2820]); // asr x0, x1, x2
+        assert_eq!(cpu.get(X(0)), 0xf8 << 56);
+
+        let cpu = run(&[(X(1), 0x1234_5678), (X(2), 8)], &[0x1ac2_2c20]); // ror w0, w1, w2
+        assert_eq!(cpu.get(X(0)), 0x7812_3456);
+
+        let cpu = run(&[(X(1), 0x1234_5678), (X(2), 0)], &[0x1ac2_2c20]); // ror w0, w1, w2
+        assert_eq!(cpu.get(X(0)), 0x1234_5678);
+    }
+
+    #[test]
+    fn bitfield() {
+        let cpu = run(&[(X(1), 0x1234_5678)], &[0xd348_4c20]); // ubfx x0, x1, #8, #12
+        assert_eq!(cpu.get(X(0)), 0x456);
+
+        let cpu = run(&[(X(1), 0xf80)], &[0x1304_2c20]); // sbfx w0, w1, #4, #8
+        assert_eq!(cpu.get(X(0)), 0xffff_fff8);
+
+        let cpu = run(&[(X(0), u64::MAX), (X(1), 0x12)], &[0xb370_1c20]); // bfi x0, x1, #16, #8
+        assert_eq!(cpu.get(X(0)), 0xffff_ffff_ff12_ffff);
+
+        // bfxil w0, w1, #0, #4
+        let cpu = run(
+            &[(X(0), 0xffff_ffff_0000_0000), (X(1), 0xab)],
+            &[0x3300_0c20],
+        );
+        assert_eq!(cpu.get(X(0)), 0xb);
+
+        let cpu = run(&[(X(1), 0x2000_0001)], &[0x531d_7020]); // lsl w0, w1, #3
+        assert_eq!(cpu.get(X(0)), 8);
+
+        let cpu = run(&[(X(1), 1 << 63)], &[0x937f_fc20]); // asr x0, x1, #63
+        assert_eq!(cpu.get(X(0)), u64::MAX);
+
+        let cpu = run(&[(X(1), 0x80)], &[0x9340_1c20]); // sxtb x0, w1
+        assert_eq!(cpu.get(X(0)), -128i64 as u64);
+
+        let cpu = run(&[(X(1), 0x1_2345)], &[0x5300_3c20]); // uxth w0, w1
+        assert_eq!(cpu.get(X(0)), 0x2345);
+
+        let cpu = run(&[(X(1), 0x8000_0000)], &[0x9340_7c20]); // sxtw x0, w1
+        assert_eq!(cpu.get(X(0)), 0xffff_ffff_8000_0000);
+    }
+
+    #[test]
+    fn extract() {
+        // extr x0, x1, x2, #16
+        let cpu = run(&[(X(1), 0xabcd), (X(2), 0x1234 << 48)], &[0x93c2_4020]);
+        assert_eq!(cpu.get(X(0)), 0xabcd_1234_0000_0000);
+
+        let cpu = run(&[(X(1), 0x1234_5678)], &[0x1381_1020]); // ror w0, w1, #4
+        assert_eq!(cpu.get(X(0)), 0x8123_4567);
+    }
+
+    #[test]
+    fn reverse_and_count() {
+        let value = 0x0123_4567_89ab_cdef_u64;
+
+        let cpu = run(&[(X(1), value)], &[0xdac0_0020]); // rbit x0, x1
+        assert_eq!(cpu.get(X(0)), value.reverse_bits());
+
+        let cpu = run(&[(X(1), value)], &[0x5ac0_0020]); // rbit w0, w1
+        assert_eq!(cpu.get(X(0)), (value as u32).reverse_bits() as u64);
+
+        let cpu = run(&[(X(1), value)], &[0xdac0_0c20]); // rev x0, x1
+        assert_eq!(cpu.get(X(0)), value.swap_bytes());
+
+        let cpu = run(&[(X(1), value)], &[0x5ac0_0820]); // rev w0, w1
+        assert_eq!(cpu.get(X(0)), 0xefcd_ab89);
+
+        let cpu = run(&[(X(1), value)], &[0xdac0_0420]); // rev16 x0, x1
+        assert_eq!(cpu.get(X(0)), 0x2301_6745_ab89_efcd);
+
+        let cpu = run(&[(X(1), value)], &[0xdac0_0820]); // rev32 x0, x1
+        assert_eq!(cpu.get(X(0)), 0x6745_2301_efcd_ab89);
+
+        for value in [0, 1, 0x80, 0xffff_ffff, 1 << 63, u64::MAX, value] {
+            let cpu = run(&[(X(1), value)], &[0xdac0_1020]); // clz x0, x1
+            assert_eq!(
+                cpu.get(X(0)),
+                value.leading_zeros() as u64,
+                "clz {value:#x}"
+            );
+
+            let cpu = run(&[(X(1), value)], &[0x5ac0_1020]); // clz w0, w1
+            assert_eq!(cpu.get(X(0)), (value as u32).leading_zeros() as u64);
+
+            let signed = value as i64;
+            let cpu = run(&[(X(1), value)], &[0xdac0_1420]); // cls x0, x1
+            assert_eq!(
+                cpu.get(X(0)),
+                (signed ^ (signed >> 1)).leading_zeros() as u64 - 1
+            );
+
+            let signed = value as i32;
+            let cpu = run(&[(X(1), value)], &[0x5ac0_1420]); // cls w0, w1
+            assert_eq!(
+                cpu.get(X(0)),
+                (signed ^ (signed >> 1)).leading_zeros() as u64 - 1
+            );
+        }
+    }
+
+    #[test]
+    fn bit_masks() {
+        // and x0, x1, #0xff00
+        assert_eq!(
+            compiler_prelude::decode_bit_masks(1, 0b000111, 0b111000, true, 64),
+            Some((0xff00, 0xffff))
+        );
+        // All ones is not a valid immediate
+        assert_eq!(
+            compiler_prelude::decode_bit_masks(1, 0b111111, 0, true, 64),
+            None
+        );
+        // A 64-bit element doesn't fit a 32-bit register
+        assert_eq!(compiler_prelude::decode_bit_masks(1, 0, 0, false, 32), None);
+    }
+
+    #[test]
+    fn load_store_imm() {
+        let value = 0x1122_3344_5566_7788u64;
+        let memory: &[(u64, &[u8])] = &[(MEM + 8, &value.to_le_bytes())];
+        let cpu = run_with_memory(&[(X(1), MEM)], memory, &[0xf940_0420]); // ldr x0, [x1, #8]
+        assert_eq!(cpu.get(X(0)), value);
+        assert_eq!(cpu.get(X(1)), MEM);
+
+        // Narrow loads clear the rest of the register
+        let memory: &[(u64, &[u8])] = &[(MEM, &[0x80, 0x80, 0x80, 0x80])];
+        let registers = [(X(0), u64::MAX), (X(1), MEM - 3)];
+        let cpu = run_with_memory(&registers, memory, &[0x3940_0c20]); // ldrb w0, [x1, #3]
+        assert_eq!(cpu.get(X(0)), 0x80);
+
+        let cpu = run_with_memory(&[(X(1), MEM)], memory, &[0x3980_0020]); // ldrsb x0, [x1]
+        assert_eq!(cpu.get(X(0)), 0xffff_ffff_ffff_ff80);
+
+        let registers = [(X(0), u64::MAX), (X(1), MEM)];
+        let cpu = run_with_memory(&registers, memory, &[0x79c0_0020]); // ldrsh w0, [x1]
+        assert_eq!(cpu.get(X(0)), 0xffff_8080);
+
+        let cpu = run_with_memory(&[(X(1), MEM)], memory, &[0xb980_0020]); // ldrsw x0, [x1]
+        assert_eq!(cpu.get(X(0)), 0xffff_ffff_8080_8080);
+
+        let registers = [(Sp, MEM), (X(0), 0x1234), (X(2), 0x5678)];
+        let cpu = run_with_memory(
+            &registers,
+            &[],
+            &[
+                0x3900_07e0, // strb w0, [sp, #1]
+                0x7900_07e2, // strh w2, [sp, #2]
+            ],
+        );
+        assert_eq!(cpu.read_memory(MEM, 5), [0, 0x34, 0x78, 0x56, 0]);
+        assert_eq!(cpu.get(Pc), 8);
+    }
+
+    #[test]
+    fn load_store_indexed() {
+        let registers = [(X(0), 42), (X(1), MEM)];
+        let cpu = run_with_memory(&registers, &[], &[0xf801_0c20]); // str x0, [x1, #16]!
+        assert_eq!(read_u64(&cpu, MEM + 16), 42);
+        assert_eq!(cpu.get(X(1)), MEM + 16);
+
+        let memory: &[(u64, &[u8])] = &[(MEM + 8, &42u64.to_le_bytes())];
+        let registers = [(X(1), MEM + 8)];
+        let cpu = run_with_memory(&registers, memory, &[0xf85f_8420]); // ldr x0, [x1], #-8
+        assert_eq!(cpu.get(X(0)), 42);
+        assert_eq!(cpu.get(X(1)), MEM);
+
+        let memory: &[(u64, &[u8])] = &[(MEM + 1, &[0xfe])];
+        let cpu = run_with_memory(&[(X(1), MEM)], memory, &[0x38c0_1c20]); // ldrsb w0, [x1, #1]!
+        assert_eq!(cpu.get(X(0)), 0xffff_fffe);
+        assert_eq!(cpu.get(X(1)), MEM + 1);
+    }
+
+    #[test]
+    fn load_store_reg_offset() {
+        let memory: &[(u64, &[u8])] = &[(MEM + 16, &0x1122_3344_5566_7788u64.to_le_bytes())];
+
+        let registers = [(X(1), MEM), (X(2), 2)];
+        let cpu = run_with_memory(&registers, memory, &[0xf862_7820]); // ldr x0, [x1, x2, lsl #3]
+        assert_eq!(cpu.get(X(0)), 0x1122_3344_5566_7788);
+
+        let registers = [(X(1), MEM + 20), (X(2), 0xffff_fffc)];
+        let cpu = run_with_memory(&registers, memory, &[0xb862_c820]); // ldr w0, [x1, w2, sxtw]
+        assert_eq!(cpu.get(X(0)), 0x5566_7788);
+
+        let registers = [(X(1), MEM + 8), (X(2), 9)];
+        let cpu = run_with_memory(&registers, memory, &[0x3862_6820]); // ldrb w0, [x1, x2]
+        assert_eq!(cpu.get(X(0)), 0x77);
+
+        // The upper half of w2 is ignored by the extension
+        let registers = [(X(0), 0xdead_beef), (X(1), MEM), (X(2), 0x1_0000_0001)];
+        let cpu = run_with_memory(&registers, &[], &[0xb822_5820]); // str w0, [x1, w2, uxtw #2]
+        assert_eq!(read_u64(&cpu, MEM + 4), 0xdead_beef);
+    }
+
+    #[test]
+    fn load_store_unscaled() {
+        let memory: &[(u64, &[u8])] = &[(MEM, &0x1122_3344_5566_7788u64.to_le_bytes())];
+
+        let cpu = run_with_memory(&[(X(1), MEM + 1)], memory, &[0xf85f_f020]); // ldur x0, [x1, #-1]
+        assert_eq!(cpu.get(X(0)), 0x1122_3344_5566_7788);
+
+        let cpu = run_with_memory(&[(X(1), MEM - 8)], memory, &[0xf840_8820]); // ldtr x0, [x1, #8]
+        assert_eq!(cpu.get(X(0)), 0x1122_3344_5566_7788);
+
+        let cpu = run_with_memory(&[(X(1), MEM)], memory, &[0x9940_4020]); // ldapur w0, [x1, #4]
+        assert_eq!(cpu.get(X(0)), 0x1122_3344);
+
+        let registers = [(X(0), 0xaabb_ccdd), (X(1), MEM)];
+        let cpu = run_with_memory(&registers, &[], &[0xb800_3020]); // stur w0, [x1, #3]
+        assert_eq!(read_u64(&cpu, MEM), 0xaa_bbcc_dd00_0000);
+
+        let registers = [(X(0), u64::MAX), (X(1), MEM)];
+        let cpu = run_with_memory(&registers, &[], &[0xd900_0020]); // stlur x0, [x1]
+        assert_eq!(read_u64(&cpu, MEM), u64::MAX);
+    }
+
+    #[test]
+    fn load_store_pair() {
+        let mut memory = [0; 32];
+        memory[16..24].copy_from_slice(&1u64.to_le_bytes());
+        memory[24..].copy_from_slice(&(-2i64).to_le_bytes());
+        let memory: &[(u64, &[u8])] = &[(MEM, &memory)];
+
+        let cpu = run_with_memory(&[(Sp, MEM)], memory, &[0xa941_07e0]); // ldp x0, x1, [sp, #16]
+        assert_eq!((cpu.get(X(0)), cpu.get(X(1))), (1, -2i64 as u64));
+
+        let cpu = run_with_memory(&[(X(2), MEM + 20)], memory, &[0x28c1_0440]); // ldp w0, w1, [x2], #8
+        assert_eq!((cpu.get(X(0)), cpu.get(X(1))), (0, 0xffff_fffe));
+        assert_eq!(cpu.get(X(2)), MEM + 28);
+
+        let cpu = run_with_memory(&[(X(2), MEM + 20)], memory, &[0x6940_0440]); // ldpsw x0, x1, [x2]
+        assert_eq!((cpu.get(X(0)), cpu.get(X(1))), (0, -2i64 as u64));
+
+        let cpu = run_with_memory(&[(X(2), MEM)], memory, &[0xa841_0440]); // ldnp x0, x1, [x2, #16]
+        assert_eq!((cpu.get(X(0)), cpu.get(X(1))), (1, -2i64 as u64));
+
+        let registers = [(Sp, MEM + 32), (X(0), 3), (X(1), 4)];
+        let cpu = run_with_memory(&registers, &[], &[0xa9bf_07e0]); // stp x0, x1, [sp, #-16]!
+        assert_eq!(cpu.get(Sp), MEM + 16);
+        assert_eq!((read_u64(&cpu, MEM + 16), read_u64(&cpu, MEM + 24)), (3, 4));
+    }
+
+    #[test]
+    fn load_literal() {
+        let mut memory = [0; 32];
+        memory[..16].copy_from_slice(&u128::MAX.to_le_bytes());
+        memory[16..24].copy_from_slice(&0x8000_0000_1234_5678u64.to_le_bytes());
+        let memory: &[(u64, &[u8])] = &[(MEM, &memory)];
+
+        let cpu = run_with_memory(&[(Pc, MEM + 8)], memory, &[0x5800_0040]); // ldr x0, #8
+        assert_eq!(cpu.get(X(0)), 0x8000_0000_1234_5678);
+        assert_eq!(cpu.get(Pc), MEM + 12);
+
+        let cpu = run_with_memory(&[(Pc, MEM + 24)], memory, &[0x98ff_ffe0]); // ldrsw x0, #-4
+        assert_eq!(cpu.get(X(0)), 0xffff_ffff_8000_0000);
+
+        let cpu = run_with_memory(&[(Pc, MEM - 16)], memory, &[0x9c00_0084]); // ldr q4, #16
+        assert_eq!(cpu.get_u128(AArch64RegisterId::V(4)), u128::MAX);
+    }
+
+    #[test]
+    fn load_store_simd_fp() {
+        let value = 0x0011_2233_4455_6677_8899_aabb_ccdd_eeffu128;
+        let memory: &[(u64, &[u8])] = &[(MEM, &value.to_le_bytes())];
+
+        let cpu = run_with_memory(&[(X(1), MEM)], memory, &[0x3dc0_0020]); // ldr q0, [x1]
+        assert_eq!(cpu.get_u128(AArch64RegisterId::V(0)), value);
+
+        // Scalar loads clear the rest of the vector register
+        let mut cpu = TestCpu::new();
+        cpu.map_memory(MEM, 0x1000);
+        cpu.write_memory(MEM, &value.to_le_bytes());
+        cpu.set_u128(AArch64RegisterId::V(2), u128::MAX);
+        cpu.set_u128(AArch64RegisterId::V(3), u128::MAX);
+        cpu.set(X(1), MEM);
+        cpu.set(X(2), 1);
+        let insts = [
+            0x3d40_0422, // ldr b2, [x1, #1]
+            0xbc62_7823, // ldr s3, [x1, x2, lsl #2]
+        ];
+        assert_eq!(cpu.run(&insts), None);
+        assert_eq!(cpu.get_u128(AArch64RegisterId::V(2)), 0xee);
+        assert_eq!(cpu.get_u128(AArch64RegisterId::V(3)), 0x8899_aabb);
+
+        let cpu = run_with_memory(&[(X(1), MEM)], memory, &[0x7c40_2425]); // ldr h5, [x1], #2
+        assert_eq!(cpu.get_u128(AArch64RegisterId::V(5)), 0xeeff);
+        assert_eq!(cpu.get(X(1)), MEM + 2);
+
+        let cpu = run_with_memory(&[(X(1), MEM - 1)], memory, &[0x3cc0_1020]); // ldur q0, [x1, #1]
+        assert_eq!(cpu.get_u128(AArch64RegisterId::V(0)), value);
+
+        let mut cpu = TestCpu::new();
+        cpu.map_memory(MEM, 0x1000);
+        cpu.set_u128(AArch64RegisterId::V(0), value);
+        cpu.set_u128(AArch64RegisterId::V(1), !value);
+        cpu.set(X(1), MEM);
+        let insts = [
+            0xfd00_0421, // str d1, [x1, #8]
+            0xad40_0420, // ldp q0, q1, [x1]
+        ];
+        assert_eq!(cpu.run(&insts), None);
+        assert_eq!(
+            cpu.get_u128(AArch64RegisterId::V(0)),
+            (!value as u64 as u128) << 64
+        );
+        assert_eq!(cpu.get_u128(AArch64RegisterId::V(1)), 0);
+    }
+
+    #[test]
+    fn prefetch_and_faults() {
+        let cpu = run_with_memory(&[(X(1), 0)], &[], &[0xf980_0020]); // prfm pldl1keep, [x1]
+        assert_eq!(cpu.get(Pc), 4);
+
+        // A faulting pair load leaves both registers and the pc untouched
+        let mut cpu = TestCpu::new();
+        cpu.map_memory(MEM, 0x1000);
+        cpu.set(Sp, MEM + 0x1000 - 24);
+        cpu.set(X(0), 7);
+        assert!(matches!(
+            cpu.run(&[0xa941_07e0]), // ldp x0, x1, [sp, #16]
+            Some(Interrupt::Aborts(_))
+        ));
+        assert_eq!((cpu.get(X(0)), cpu.get(Pc)), (7, 0));
+    }
+
+    #[test]
+    fn exclusive_load_store() {
+        let value = 0x1122_3344_5566_7788u64;
+        let memory: &[(u64, &[u8])] = &[(MEM, &value.to_le_bytes())];
+        let registers = [(X(1), MEM), (X(2), 7), (X(3), 42)];
+        let cpu = run_with_memory(
+            &registers,
+            memory,
+            &[
+                0xc85f_7c20, // ldxr x0, [x1]
+                0xc802_7c23, // stxr w2, x3, [x1]
+            ],
+        );
+        assert_eq!((cpu.get(X(0)), cpu.get(X(2))), (value, 0));
+        assert_eq!(read_u64(&cpu, MEM), 42);
+
+        // The narrow loads zero extend, the status of the store is written to Ws
+        let cpu = run_with_memory(
+            &registers,
+            &[(MEM, &[0xff])],
+            &[
+                0x085f_fc20, // ldaxrb w0, [x1]
+                0x0802_fc23, // stlxrb w2, w3, [x1]
+            ],
+        );
+        assert_eq!((cpu.get(X(0)), cpu.get(X(2))), (0xff, 0));
+        assert_eq!(cpu.read_memory(MEM, 2), [42, 0]);
+
+        // The pairs are a single access, the first register at the lower address
+        let registers = [(X(1), MEM), (X(6), 1), (X(7), 2)];
+        let cpu = run_with_memory(
+            &registers,
+            memory,
+            &[
+                0xc87f_1424, // ldxp x4, x5, [x1]
+                0xc822_1c26, // stxp w2, x6, x7, [x1]
+            ],
+        );
+        assert_eq!((cpu.get(X(4)), cpu.get(X(5)), cpu.get(X(2))), (value, 0, 0));
+        assert_eq!((read_u64(&cpu, MEM), read_u64(&cpu, MEM + 8)), (1, 2));
+    }
+
+    #[test]
+    fn exclusive_store_fails_without_reservation() {
+        let registers = [(X(1), MEM), (X(3), 42)];
+
+        // No exclusive load
+        let cpu = run_with_memory(&registers, &[], &[0xc802_7c23]); // stxr w2, x3, [x1]
+        assert_eq!((cpu.get(X(2)), read_u64(&cpu, MEM)), (1, 0));
+
+        // Another address than the one reserved
+        let cpu = run_with_memory(
+            &[(X(1), MEM), (X(4), MEM + 8), (X(3), 42)],
+            &[],
+            &[
+                0xc85f_7c20, // ldxr x0, [x1]
+                0xc802_7c83, // stxr w2, x3, [x4]
+            ],
+        );
+        assert_eq!((cpu.get(X(2)), read_u64(&cpu, MEM + 8)), (1, 0));
+
+        // A store of another cpu in between breaks the reservation of the global monitor
+        let mut cpu = TestCpu::new();
+        cpu.map_memory(MEM, 0x1000);
+        cpu.set(X(1), MEM);
+        cpu.set(X(3), 42);
+        assert_eq!(cpu.run(&[0xc85f_7c20]), None); // ldxr x0, [x1]
+        cpu.write_memory(MEM, &[5]);
+        assert_eq!(cpu.run(&[0xc802_7c23]), None); // stxr w2, x3, [x1]
+        assert_eq!((cpu.get(X(2)), read_u64(&cpu, MEM)), (1, 5));
+
+        // The reservation is used up by the store
+        assert_eq!(cpu.run(&[0xc85f_7c20, 0xc802_7c23, 0xc802_7c23]), None);
+        assert_eq!((cpu.get(X(2)), read_u64(&cpu, MEM)), (1, 42));
+    }
+
+    #[test]
+    fn clrex_and_exceptions_clear_the_reservation() {
+        let registers = [(X(1), MEM), (X(3), 42)];
+        let cpu = run_with_memory(
+            &registers,
+            &[],
+            &[
+                0xc85f_7c20, // ldxr x0, [x1]
+                0xd503_3f5f, // clrex
+                0xc802_7c23, // stxr w2, x3, [x1]
+            ],
+        );
+        assert_eq!((cpu.get(X(2)), read_u64(&cpu, MEM)), (1, 0));
+
+        // The reservation is still held without CLREX
+        let mut cpu = TestCpu::new();
+        cpu.map_memory(MEM, 0x1000);
+        cpu.set(X(1), MEM);
+        cpu.set(X(3), 42);
+        assert_eq!(cpu.run(&[0xc85f_7c20, 0xd503_201f]), None); // ldxr x0, [x1]; nop
+        assert_eq!(cpu.run(&[0xc802_7c23]), None); // stxr w2, x3, [x1]
+        assert_eq!((cpu.get(X(2)), read_u64(&cpu, MEM)), (0, 42));
+
+        // Taking an exception and returning from it
+        assert_eq!(cpu.run(&[0xc85f_7c20]), None); // ldxr x0, [x1]
+        cpu.take(Interrupt::Interrupt(0));
+        assert_eq!(cpu.run(&[0xc802_7c23]), None); // stxr w2, x3, [x1]
+        assert_eq!(cpu.get(X(2)), 1);
+
+        cpu.set(AArch64RegisterId::ElrEl1, 0);
+        assert_eq!(cpu.run(&[0xc85f_7c20, 0xd69f_03e0]), None); // ldxr x0, [x1]; eret
+        assert_eq!(cpu.run(&[0xc802_7c23]), None); // stxr w2, x3, [x1]
+        assert_eq!(cpu.get(X(2)), 1);
+    }
+
+    #[test]
+    fn exclusive_increments_of_two_vcpus_are_exact() {
+        use core::{Exception, ExceptionKind, RegisterId};
+        use device::devices::{Memory, SoftMmu};
+        use execution::codegen::rustjit::{RustjitCodegen, RustjitContext};
+        use execution::codegen::ExecutionContext;
+        use execution::interrupt::InterruptAction;
+        use execution::machine::{Machine, VcpuExit};
+
+        const INCREMENTS: u64 = 10_000;
+        let code = [
+            0xc85f_7c20u32, // ldxr x0, [x1]
+            0x9100_0400,    // add x0, x0, #1
+            0xc802_7c20,    // stxr w2, x0, [x1]
+            0x35ff_ffa2,    // cbnz w2, #-12
+            0xf100_0463,    // subs x3, x3, #1
+            0x54ff_ff61,    // b.ne #-20
+            0xd420_0000,    // brk #0
+        ];
+        let mut mmu = SoftMmu::new();
+        mmu.map(Memory::allocate(0x2000), MEM, 0x2000);
+        let code: Vec<u8> = code.iter().flat_map(|word| word.to_le_bytes()).collect();
+        unsafe { mmu.try_write_all_at(MEM, &code) }.unwrap();
+
+        let desc = AArch64Architecture::get_register_file_desc();
+        let mut machine = Machine::<AArch64Architecture, _>::new(RustjitCodegen, mmu, 2);
+        for id in 0..2 {
+            let context = machine.vcpu_mut(id).context_mut();
+            for (id, value) in [(X(1), MEM + 0x1000), (X(3), INCREMENTS)] {
+                let register = desc.register(id.raw());
+                context.registers_mut()[register.offset..register.offset + 8]
+                    .copy_from_slice(&value.to_le_bytes());
+            }
+            context.set_pc(MEM);
+        }
+
+        let exits = unsafe { machine.run(|_| |_: &mut RustjitContext, _| InterruptAction::Stop) };
+        assert!(exits.iter().all(|exit| matches!(
+            exit,
+            VcpuExit::Interrupted(Interrupt::Exception(Exception {
+                kind: ExceptionKind::Breakpoint(0),
+                ..
+            }))
+        )));
+
+        let mut counter = [0; 8];
+        unsafe { machine.mmu().try_read_all_at(MEM + 0x1000, &mut counter) }.unwrap();
+        assert_eq!(u64::from_le_bytes(counter), 2 * INCREMENTS);
+    }
+
+    #[test]
+    fn unaligned_exclusive_aborts() {
+        let mut cpu = TestCpu::new();
+        cpu.map_memory(MEM, 0x1000);
+        cpu.set(X(1), MEM + 4);
+        assert!(matches!(
+            cpu.run(&[0xc85f_7c20]), // ldxr x0, [x1]
+            Some(Interrupt::Aborts(core::Abort {
+                kind: core::AbortKind::Unaligned,
+                fault_address: 0x1004,
+                ..
+            }))
+        ));
+        assert_eq!(cpu.get(Pc), 0);
+
+        // The atomic memory operations too
+        cpu.set(X(1), MEM + 2);
+        assert!(matches!(
+            cpu.run(&[0xf822_0020]), // ldadd x2, x0, [x1]
+            Some(Interrupt::Aborts(core::Abort {
+                kind: core::AbortKind::Unaligned,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn atomic_memory_operations() {
+        let registers = [(X(1), MEM), (X(2), 0x0f)];
+        let memory: &[(u64, &[u8])] = &[(MEM, &[0xf1, 0x80])];
+
+        let cpu = run_with_memory(&registers, memory, &[0xf822_0020]); // ldadd x2, x0, [x1]
+        assert_eq!((cpu.get(X(0)), read_u64(&cpu, MEM)), (0x80f1, 0x8100));
+
+        let cpu = run_with_memory(&registers, memory, &[0xb8e2_8020]); // swpal w2, w0, [x1]
+        assert_eq!((cpu.get(X(0)), read_u64(&cpu, MEM)), (0x80f1, 0x0f));
+
+        let cpu = run_with_memory(&registers, memory, &[0x7822_1020]); // ldclrh w2, w0, [x1]
+        assert_eq!((cpu.get(X(0)), read_u64(&cpu, MEM)), (0x80f1, 0x80f0));
+
+        // The byte 0xf1 is negative for the signed maximum, the old value is zero extended
+        let cpu = run_with_memory(&registers, memory, &[0x3822_4020]); // ldsmaxb w2, w0, [x1]
+        assert_eq!((cpu.get(X(0)), read_u64(&cpu, MEM)), (0xf1, 0x800f));
+
+        let cpu = run_with_memory(&registers, memory, &[0xf862_7020]); // lduminl x2, x0, [x1]
+        assert_eq!((cpu.get(X(0)), read_u64(&cpu, MEM)), (0x80f1, 0x0f));
+
+        // STADD is the alias loading into the zero register
+        let cpu = run_with_memory(&registers, memory, &[0xf822_003f]); // stadd x2, [x1]
+        assert_eq!(read_u64(&cpu, MEM), 0x8100);
+    }
+
+    #[test]
+    fn compare_and_swap() {
+        let memory: &[(u64, &[u8])] = &[(MEM, &[5])];
+
+        let cpu = run_with_memory(
+            &[(X(1), MEM), (X(2), 5), (X(3), 9)],
+            memory,
+            &[0xc8a2_7c23], // cas x2, x3, [x1]
+        );
+        assert_eq!((cpu.get(X(2)), read_u64(&cpu, MEM)), (5, 9));
+
+        let cpu = run_with_memory(
+            &[(X(1), MEM), (X(2), 6), (X(3), 9)],
+            memory,
+            &[0xc8a2_7c23], // cas x2, x3, [x1]
+        );
+        assert_eq!((cpu.get(X(2)), read_u64(&cpu, MEM)), (5, 5));
+
+        // Only the low byte is compared
+        let cpu = run_with_memory(
+            &[(X(1), MEM), (X(2), 0x105), (X(3), 0x209)],
+            memory,
+            &[0x08e2_fc23], // casalb w2, w3, [x1]
+        );
+        assert_eq!((cpu.get(X(2)), read_u64(&cpu, MEM)), (5, 9));
+
+        // The pair is compared as a whole, the first register at the lower address
+        let memory: &[(u64, &[u8])] = &[(MEM, &[1]), (MEM + 8, &[2])];
+        let registers = [(X(1), MEM), (X(2), 1), (X(3), 2), (X(4), 7), (X(5), 8)];
+        let cpu = run_with_memory(&registers, memory, &[0x4822_7c24]); // casp x2, x3, x4, x5, [x1]
+        assert_eq!((cpu.get(X(2)), cpu.get(X(3))), (1, 2));
+        assert_eq!((read_u64(&cpu, MEM), read_u64(&cpu, MEM + 8)), (7, 8));
+
+        let registers = [(X(1), MEM), (X(2), 1), (X(3), 3), (X(4), 7), (X(5), 8)];
+        let cpu = run_with_memory(&registers, memory, &[0x4822_7c24]); // casp x2, x3, x4, x5, [x1]
+        assert_eq!((cpu.get(X(2)), cpu.get(X(3))), (1, 2));
+        assert_eq!((read_u64(&cpu, MEM), read_u64(&cpu, MEM + 8)), (1, 2));
+
+        let registers = [(Sp, MEM), (X(2), 1), (X(3), 0), (X(4), 7), (X(5), 8)];
+        let cpu = run_with_memory(&registers, memory, &[0x0822_7fe4]); // casp w2, w3, w4, w5, [sp]
+        assert_eq!(read_u64(&cpu, MEM), 0x8_0000_0007);
+    }
+
+    #[test]
+    fn load_acquire_store_release() {
+        let memory: &[(u64, &[u8])] = &[(MEM, &[1, 2, 3, 4, 5])];
+        let registers = [(X(0), u64::MAX), (X(1), MEM), (X(2), 0x1234)];
+
+        let cpu = run_with_memory(&registers, memory, &[0x88df_fc20]); // ldar w0, [x1]
+        assert_eq!(cpu.get(X(0)), 0x0403_0201);
+
+        let cpu = run_with_memory(&registers, memory, &[0x78bf_c020]); // ldaprh w0, [x1]
+        assert_eq!(cpu.get(X(0)), 0x0201);
+
+        let cpu = run_with_memory(&registers, memory, &[0xc89f_fc22]); // stlr x2, [x1]
+        assert_eq!(read_u64(&cpu, MEM), 0x1234);
+
+        let cpu = run_with_memory(&registers, memory, &[0x089f_fc22]); // stlrb w2, [x1]
+        assert_eq!(cpu.read_memory(MEM, 2), [0x34, 2]);
+    }
+
+    #[test]
+    fn unconditional_branches() {
+        let cpu = run(&[(Pc, 0x1000)], &[0x1400_0002]); // b #8
+        assert_eq!(cpu.get(Pc), 0x1008);
+
+        let cpu = run(&[(Pc, 0x1000)], &[0x17ff_ffc0]); // b #-0x100
+        assert_eq!(cpu.get(Pc), 0xf00);
+
+        let cpu = run(&[(Pc, 0x1000)], &[0x97ff_fffe]); // bl #-8
+        assert_eq!((cpu.get(Pc), cpu.get(X(30))), (0xff8, 0x1004));
+
+        let cpu = run(&[(Pc, 0x1000), (X(2), 0x2000)], &[0xd61f_0040]); // br x2
+        assert_eq!(cpu.get(Pc), 0x2000);
+
+        let cpu = run(&[(Pc, 0x1000), (X(30), 0x2000)], &[0xd63f_03c0]); // blr x30
+        assert_eq!((cpu.get(Pc), cpu.get(X(30))), (0x2000, 0x1004));
+
+        let cpu = run(&[(Pc, 0x1000), (X(30), 0x2000)], &[0xd65f_03c0]); // ret
+        assert_eq!(cpu.get(Pc), 0x2000);
+    }
+
+    #[test]
+    fn conditional_branches() {
+        let holds = |cond: u8, flags: u64| {
+            let (n, z, c, v) = (
+                flags & N != 0,
+                flags & Z != 0,
+                flags & C != 0,
+                flags & V != 0,
+            );
+            let result = match cond >> 1 {
+                0b000 => z,
+                0b001 => c,
+                0b010 => n,
+                0b011 => v,
+                0b100 => c && !z,
+                0b101 => n == v,
+                0b110 => n == v && !z,
+                _ => true,
+            };
+            match cond {
+                0b1111 => true,
+                cond => result != (cond & 1 == 1),
+            }
+        };
+
+        for cond in 0..16 {
+            for flags in 0..16 {
+                // b.<cond> #8
+                let cpu = run(&[(Pc, 0x1000), (Nzcv, flags << 28)], &[0x5400_0040 | cond]);
+                let expected = match holds(cond as u8, flags) {
+                    true => 0x1008,
+                    false => 0x1004,
+                };
+                assert_eq!(cpu.get(Pc), expected, "cond {cond:#06b} nzcv {flags:#06b}");
+
+                // bc.<cond> #8
+                let cpu = run(&[(Pc, 0x1000), (Nzcv, flags << 28)], &[0x5400_0050 | cond]);
+                assert_eq!(cpu.get(Pc), expected, "cond {cond:#06b} nzcv {flags:#06b}");
+            }
+        }
+    }
+
+    #[test]
+    fn hints_only_move_the_pc() {
+        let hints = [
+            0xd503_2fff, // hint #0x7f
+            0xd503_20df, // hint #6
+            0xd503_20ff, // xpaclri
+            0xd503_211f, // pacia1716
+            0xd503_237f, // pacibsp
+            0xd503_23bf, // autiasp
+            0xd503_23df, // autibz
+        ];
+        let registers = [(Pc, 0x1000), (X(16), 0x1234), (X(30), 0x5678)];
+        let cpu = run(&registers, &hints);
+        assert_eq!(cpu.get(Pc), 0x1000 + 4 * hints.len() as u64);
+        assert_eq!((cpu.get(X(16)), cpu.get(X(30))), (0x1234, 0x5678));
+    }
+
+    #[test]
+    fn compare_and_test_branches() {
+        // Only the W register is compared by the 32-bit form
+        let cpu = run(&[(Pc, 0x1000), (X(0), 1 << 32)], &[0x3400_0040]); // cbz w0, #8
+        assert_eq!(cpu.get(Pc), 0x1008);
+
+        let cpu = run(&[(Pc, 0x1000), (X(0), 1 << 32)], &[0xb400_0040]); // cbz x0, #8
+        assert_eq!(cpu.get(Pc), 0x1004);
+
+        let cpu = run(&[(Pc, 0x1000), (X(0), 1 << 32)], &[0xb5ff_ffe0]); // cbnz x0, #-4
+        assert_eq!(cpu.get(Pc), 0xffc);
+
+        let cpu = run(&[(Pc, 0x1000), (X(1), 0b0111)], &[0x3618_0041]); // tbz w1, #3, #8
+        assert_eq!(cpu.get(Pc), 0x1008);
+
+        let cpu = run(&[(Pc, 0x1000), (X(1), 0b1000)], &[0x3618_0041]); // tbz w1, #3, #8
+        assert_eq!(cpu.get(Pc), 0x1004);
+
+        let cpu = run(&[(Pc, 0x1000), (X(1), 1 << 33)], &[0xb708_0061]); // tbnz x1, #33, #12
+        assert_eq!(cpu.get(Pc), 0x100c);
+    }
+
+    #[test]
+    fn branches_end_the_block() {
+        let lift = |inst: u32| {
+            let mut bb = BasicBlock::new(0);
+            AArch64Inst::decode(&inst.to_le_bytes()).compile_to_ir(&mut bb);
+            bb.terminator()
+        };
+
+        assert_eq!(lift(0x9100_0c20), BasicBlockTerminator::None); // add x0, x1, #3
+        assert!(matches!(
+            lift(0x1400_0002), // b #8
+            BasicBlockTerminator::Branch(_)
+        ));
+        assert!(matches!(
+            lift(0xd65f_03c0), // ret
+            BasicBlockTerminator::Branch(_)
+        ));
+        assert!(matches!(
+            lift(0x5400_0041), // b.ne #8
+            BasicBlockTerminator::BranchCond { .. }
+        ));
+        assert!(matches!(
+            lift(0xb708_0061), // tbnz x1, #33, #12
+            BasicBlockTerminator::BranchCond { .. }
+        ));
+    }
+
+    #[test]
+    fn conditional_select() {
+        let registers = |flags: u64| [(Nzcv, flags << 28), (X(1), 10), (X(2), 0xffff_ffff)];
+
+        let cpu = run(&registers(Z), &[0x9a82_0020]); // csel x0, x1, x2, eq
+        assert_eq!(cpu.get(X(0)), 10);
+        let cpu = run(&registers(0), &[0x9a82_0020]); // csel x0, x1, x2, eq
+        assert_eq!(cpu.get(X(0)), 0xffff_ffff);
+
+        let cpu = run(&registers(Z), &[0x1a82_1420]); // csinc w0, w1, w2, ne
+        assert_eq!(cpu.get(X(0)), 0);
+
+        let cpu = run(&registers(C), &[0x9a9f_97e0]); // cset x0, hi
+        assert_eq!(cpu.get(X(0)), 1);
+        let cpu = run(&registers(C | Z), &[0x9a9f_97e0]); // cset x0, hi
+        assert_eq!(cpu.get(X(0)), 0);
+
+        let cpu = run(&registers(N), &[0xda82_b020]); // csinv x0, x1, x2, lt
+        assert_eq!(cpu.get(X(0)), 10);
+        let cpu = run(&registers(N | V), &[0xda82_b020]); // csinv x0, x1, x2, lt
+        assert_eq!(cpu.get(X(0)), 0xffff_ffff_0000_0000);
+
+        let cpu = run(&registers(V), &[0x5a82_a420]); // csneg w0, w1, w2, ge
+        assert_eq!(cpu.get(X(0)), 1);
+
+        let cpu = run(&registers(Z), &[0x5a9f_13e0]); // csetm w0, eq
+        assert_eq!(cpu.get(X(0)), 0xffff_ffff);
+    }
+
+    #[test]
+    fn conditional_compare() {
+        let registers = |flags: u64| [(Nzcv, flags << 28), (X(1), 5), (X(2), 5)];
+
+        // The comparison is made when the condition holds, the flags are set from the
+        // immediate otherwise.
+        let cpu = run(&registers(Z), &[0xfa42_0022]); // ccmp x1, x2, #0b0010, eq
+        assert_eq!(nzcv(&cpu), Z | C);
+        let cpu = run(&registers(0), &[0xfa42_0022]); // ccmp x1, x2, #0b0010, eq
+        assert_eq!(nzcv(&cpu), C);
+
+        let cpu = run(&[(X(1), 3)], &[0x7a45_1828]); // ccmp w1, #5, #0b1000, ne
+        assert_eq!(nzcv(&cpu), N);
+        let cpu = run(&[(X(1), 7)], &[0x7a45_1828]); // ccmp w1, #5, #0b1000, ne
+        assert_eq!(nzcv(&cpu), C);
+
+        let cpu = run(&[(X(1), u64::MAX)], &[0xba41_e820]); // ccmn x1, #1, #0, al
+        assert_eq!(nzcv(&cpu), Z | C);
+
+        let registers = [(X(1), 0x7fff_ffff), (X(2), 1)];
+        let cpu = run(&registers, &[0x3a42_4024]); // ccmn w1, w2, #0b0100, mi
+        assert_eq!(nzcv(&cpu), Z);
+        let registers = [(Nzcv, N << 28), (X(1), 0x7fff_ffff), (X(2), 1)];
+        let cpu = run(&registers, &[0x3a42_4024]); // ccmn w1, w2, #0b0100, mi
+        assert_eq!(nzcv(&cpu), N | V);
+    }
+
+    /// The AddWithCarry pseudocode of the architecture on `width` bit operands, as the
+    /// result and the NZCV flags.
+    fn add_with_carry(x: u64, y: u64, carry: u64, width: u32) -> (u64, u64) {
+        let mask = u64::MAX >> (64 - width);
+        let signed = |value: u64| sign_extend(value & mask, width) as i64 as i128;
+
+        let unsigned_sum = (x & mask) as u128 + (y & mask) as u128 + carry as u128;
+        let signed_sum = signed(x) + signed(y) + carry as i128;
+        let result = unsigned_sum as u64 & mask;
+
+        let mut flags = 0;
+        if result >> (width - 1) != 0 {
+            flags |= N;
+        }
+        if result == 0 {
+            flags |= Z;
+        }
+        if result as u128 != unsigned_sum {
+            flags |= C;
+        }
+        if signed(result) != signed_sum {
+            flags |= V;
+        }
+        (result, flags)
+    }
+
+    #[test]
+    fn flags_match_add_with_carry() {
+        const VALUES: [u64; 11] = [
+            0,
+            1,
+            2,
+            0x7fff_ffff,
+            0x8000_0000,
+            0xffff_ffff,
+            0x1_0000_0000,
+            0x1234_5678_9abc_def0,
+            0x7fff_ffff_ffff_ffff,
+            0x8000_0000_0000_0000,
+            u64::MAX,
+        ];
+
+        // The instruction for the 32 and 64 bit forms, whether the second operand is
+        // inverted and whether the carry flag is used.
+        let ops = [
+            (0x2b02_0020, 0xab02_0020, false, false), // adds x0, x1, x2
+            (0x6b02_0020, 0xeb02_0020, true, false),  // subs x0, x1, x2
+            (0x3a02_0020, 0xba02_0020, false, true),  // adcs x0, x1, x2
+            (0x7a02_0020, 0xfa02_0020, true, true),   // sbcs x0, x1, x2
+        ];
+
+        for (inst32, inst64, invert, use_carry) in ops {
+            for (inst, width) in [(inst32, 32), (inst64, 64)] {
+                for x in VALUES {
+                    for y in VALUES {
+                        for carry_in in [0, 1] {
+                            let registers = [(Nzcv, (carry_in * C) << 28), (X(1), x), (X(2), y)];
+                            let cpu = run(&registers, &[inst]);
+
+                            let y = if invert { !y } else { y };
+                            let carry = match (invert, use_carry) {
+                                (_, true) => carry_in,
+                                (true, false) => 1,
+                                (false, false) => 0,
+                            };
+                            let expected = add_with_carry(x, y, carry, width);
+                            assert_eq!(
+                                (cpu.get(X(0)), nzcv(&cpu)),
+                                expected,
+                                "{inst:#010x} with {x:#x}, {y:#x} and carry {carry_in}"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn logical_flags() {
+        // N and Z from the result, C and V always cleared
+        let flags = |result: u64, width: u32| match result {
+            0 => Z,
+            _ => (result >> (width - 1)) * N,
+        };
+
+        let y = 0x8000_0000_7fff_0000;
+        for x in [0, 1, 0x8000_0000, 0xffff_ffff_0000_0000, u64::MAX] {
+            let registers = [(Nzcv, 0xf << 28), (X(1), x), (X(2), y)];
+
+            let cpu = run(&registers, &[0xea02_0020]); // ands x0, x1, x2
+            assert_eq!(cpu.get(X(0)), x & y);
+            assert_eq!(nzcv(&cpu), flags(x & y, 64));
+
+            let cpu = run(&registers, &[0x6a22_0020]); // bics w0, w1, w2
+            let result = (x & !y) & 0xffff_ffff;
+            assert_eq!(cpu.get(X(0)), result);
+            assert_eq!(nzcv(&cpu), flags(result, 32));
+        }
+    }
+
+    /// PSTATE at EL1 with SP_EL0 selected.
+    const EL1: u64 = 1 << compiler_prelude::pstate::EL;
+
+    fn is_undefined(interrupt: Option<Interrupt>) -> bool {
+        matches!(
+            interrupt,
+            Some(Interrupt::Exception(core::Exception {
+                kind: ExceptionKind::Undefined,
+                ..
+            }))
+        )
+    }
+
+    #[test]
+    fn system_registers_at_el0() {
+        let cpu = run(
+            &[(X(1), 0x1234)],
+            &[
+                0xd51b_d041, // msr tpidr_el0, x1
+                0xd53b_d040, // mrs x0, tpidr_el0
+            ],
+        );
+        assert_eq!(
+            (cpu.get(X(0)), cpu.get(AArch64RegisterId::TpidrEl0)),
+            (0x1234, 0x1234)
+        );
+
+        // Only the flags of NZCV and the implemented bits of FPCR are written
+        let cpu = run(
+            &[(X(1), u64::MAX)],
+            &[
+                0xd51b_4201, // msr nzcv, x1
+                0xd53b_4200, // mrs x0, nzcv
+            ],
+        );
+        assert_eq!((cpu.get(X(0)), cpu.get(Nzcv)), (0xf000_0000, 0xf000_0000));
+
+        let cpu = run(
+            &[(X(1), u64::MAX)],
+            &[
+                0xd51b_4401, // msr fpcr, x1
+                0xd53b_4400, // mrs x0, fpcr
+            ],
+        );
+        assert_eq!((cpu.get(X(0)), cpu.get(Fpcr)), (0x07c8_0000, 0x07c8_0000));
+
+        let cpu = run(&[], &[0xd53b_0020]); // mrs x0, ctr_el0
+        assert_eq!(cpu.get(X(0)), 0x8444_c004);
+        let cpu = run(&[], &[0xd53b_00e0]); // mrs x0, dczid_el0
+        assert_eq!(cpu.get(X(0)), 0x14);
+        let cpu = run(&[], &[0xd538_0000]); // mrs x0, midr_el1
+        assert_eq!(cpu.get(X(0)) >> 24, 0x41);
+
+        // The counter advances on every read
+        let cpu = run(
+            &[],
+            &[
+                0xd53b_e040, // mrs x0, cntvct_el0
+                0xd53b_e041, // mrs x1, cntvct_el0
+            ],
+        );
+        assert!(cpu.get(X(1)) > cpu.get(X(0)));
+    }
+
+    #[test]
+    fn system_register_permissions() {
+        // EL1 registers are undefined at EL0, the pc stays on the instruction
+        let mut cpu = TestCpu::new();
+        assert!(is_undefined(cpu.run(&[0xd538_1000]))); // mrs x0, sctlr_el1
+        assert!(is_undefined(cpu.run(&[0xd518_1001]))); // msr sctlr_el1, x1
+        assert!(is_undefined(cpu.run(&[0xd51b_d061]))); // msr tpidrro_el0, x1
+        assert_eq!(cpu.get(Pc), 0);
+
+        // Read-only and unknown registers are undefined at every level
+        let mut cpu = TestCpu::new();
+        cpu.set(Pstate, EL1);
+        assert!(is_undefined(cpu.run(&[0xd51b_0021]))); // msr ctr_el0, x1
+        assert!(is_undefined(cpu.run(&[0xd538_f000]))); // mrs x0, s3_0_c15_c0_0
+
+        let cpu = run(
+            &[(Pstate, EL1), (X(1), 0x5)],
+            &[
+                0xd518_1001, // msr sctlr_el1, x1
+                0xd538_1000, // mrs x0, sctlr_el1
+                0xd51b_d061, // msr tpidrro_el0, x1
+            ],
+        );
+        assert_eq!(cpu.get(X(0)), 0x5);
+        assert_eq!(cpu.get(AArch64RegisterId::TpidrroEl0), 0x5);
+
+        let cpu = run(&[(Pstate, EL1)], &[0xd538_4240]); // mrs x0, currentel
+        assert_eq!(cpu.get(X(0)), 0b0100);
+    }
+
+    #[test]
+    fn hypervisor_and_secure_monitor_calls() {
+        // Undefined at EL0, the pc stays on the instruction
+        let mut cpu = TestCpu::new();
+        assert!(is_undefined(cpu.run(&[0xd400_0202]))); // hvc #0x10
+        assert!(is_undefined(cpu.run(&[0xd400_0203]))); // smc #0x10
+        assert_eq!(cpu.get(Pc), 0);
+
+        let mut cpu = TestCpu::new();
+        cpu.set(Pstate, EL1);
+        assert!(matches!(
+            cpu.run(&[0xd400_0202]), // hvc #0x10
+            Some(Interrupt::Exception(core::Exception {
+                kind: ExceptionKind::HypervisorCall(0x10),
+                ..
+            }))
+        ));
+        assert_eq!(cpu.get(Pc), 4);
+        assert!(matches!(
+            cpu.run(&[0xd400_0203]), // smc #0x10
+            Some(Interrupt::Exception(core::Exception {
+                kind: ExceptionKind::SecureMonitorCall(0x10),
+                ..
+            }))
+        ));
     }
-}
 
-fn compile_movz(bb: &mut BasicBlock, operand: &HwImm16Rd) {
-    let rd = operand.rd.raw();
+    #[test]
+    fn pstate_fields() {
+        let cpu = run(
+            &[(Pstate, EL1)],
+            &[
+                0xd503_4fdf, // msr daifset, #0xf
+                0xd503_42ff, // msr daifclr, #2
+                0xd53b_4220, // mrs x0, daif
+            ],
+        );
+        assert_eq!(cpu.get(X(0)), 0b1101 << 6);
 
-    let pos = operand.hw << 4;
-    bb.push_inst(IrInst::Assign {
-        dst: IrValue::Register(IrType::U64, rd),
-        src: IrValue::Constant(IrConstant::U64((operand.imm16 as u64) << pos)),
-    });
+        let cpu = run(&[], &[0xd503_415f, 0xd53b_42a0]); // msr dit, #1; mrs x0, dit
+        assert_eq!(cpu.get(X(0)), 1 << 24);
 
-    compiler_prelude::gen_move_pc(bb);
-}
+        let mut cpu = TestCpu::new();
+        assert!(is_undefined(cpu.run(&[0xd503_4fdf]))); // msr daifset, #0xf
+    }
 
-fn compile_movn(bb: &mut BasicBlock, operand: &HwImm16Rd, ty: IrType) {
-    let rd = operand.rd.raw();
+    #[test]
+    fn stack_pointer_select() {
+        use AArch64RegisterId::{SpEl0, SpEl1};
 
-    bb.push_inst(IrInst::ZextCast {
-        dst: IrValue::Register(IrType::U64, rd),
-        src: IrValue::Constant(IrConstant::new(ty, {
-            let pos = operand.hw << 4;
-            !((operand.imm16 as u64) << pos)
-        })),
-    });
+        // Selecting SP_EL1 banks SP_EL0, which is then accessible
+        let registers = [(Pstate, EL1), (Sp, 0x100), (SpEl1, 0x200)];
+        let cpu = run(
+            &registers,
+            &[
+                0xd500_41bf, // msr spsel, #1
+                0xd538_4100, // mrs x0, sp_el0
+            ],
+        );
+        assert_eq!(
+            (cpu.get(Sp), cpu.get(SpEl0), cpu.get(X(0))),
+            (0x200, 0x100, 0x100)
+        );
+        assert_eq!(cpu.get(Pstate), EL1 | 1);
 
-    compiler_prelude::gen_move_pc(bb);
-}
+        // And back
+        let registers = [(Pstate, EL1 | 1), (Sp, 0x200), (SpEl0, 0x100), (X(1), 0)];
+        let cpu = run(&registers, &[0xd518_4201]); // msr spsel, x1
+        assert_eq!(
+            (cpu.get(Sp), cpu.get(SpEl1), cpu.get(Pstate)),
+            (0x100, 0x200, EL1)
+        );
+
+        // Selecting the current stack pointer changes nothing
+        let cpu = run(&registers, &[0xd500_41bf]); // msr spsel, #1
+        assert_eq!((cpu.get(Sp), cpu.get(SpEl0)), (0x200, 0x100));
+
+        // SP_EL0 is undefined while it is the current stack pointer
+        let mut cpu = TestCpu::new();
+        cpu.set(Pstate, EL1);
+        assert!(is_undefined(cpu.run(&[0xd538_4100]))); // mrs x0, sp_el0
+    }
+
+    #[test]
+    fn flag_manipulation() {
+        let cpu = run(&[(Nzcv, (N | C) << 28)], &[0xd500_401f]); // cfinv
+        assert_eq!(nzcv(&cpu), N);
+        let cpu = run(&[(Nzcv, V << 28)], &[0xd500_401f]); // cfinv
+        assert_eq!(nzcv(&cpu), C | V);
+
+        // The flags of FCMP for less, equal, greater and unordered in both formats
+        let converted = [(N, 0), (Z | C, Z | C), (C, C), (C | V, Z)];
+        for (arm, alternative) in converted {
+            let cpu = run(&[(Nzcv, arm << 28)], &[0xd500_405f]); // axflag
+            assert_eq!(nzcv(&cpu), alternative, "axflag of {arm:#06b}");
+            let cpu = run(&[(Nzcv, alternative << 28)], &[0xd500_403f]); // xaflag
+            assert_eq!(nzcv(&cpu), arm, "xaflag of {alternative:#06b}");
+        }
+
+        // rmif x1, #4, #10 sets N and C from bits 7 and 5, keeping Z and V
+        let cpu = run(&[(Nzcv, (Z | C) << 28), (X(1), 0x80)], &[0xba02_042a]);
+        assert_eq!(nzcv(&cpu), N | Z);
+        let cpu = run(&[(Nzcv, V << 28), (X(1), 0x2f)], &[0xba02_042a]);
+        assert_eq!(nzcv(&cpu), C | V);
+        // Bits rotated out at the bottom come back at the top
+        let cpu = run(&[(X(1), 0x8)], &[0xba1f_842f]); // rmif x1, #63, #15
+        assert_eq!(nzcv(&cpu), 0);
+        let cpu = run(&[(X(1), 1 << 63)], &[0xba1f_842f]); // rmif x1, #63, #15
+        assert_eq!(nzcv(&cpu), V);
+
+        let cpu = run(&[(Nzcv, C << 28), (X(1), 0x80)], &[0x3a00_082d]); // setf8 w1
+        assert_eq!(nzcv(&cpu), N | C | V);
+        let cpu = run(&[(X(1), 0x100)], &[0x3a00_082d]); // setf8 w1
+        assert_eq!(nzcv(&cpu), Z | V);
+        let cpu = run(&[(X(1), 0x1ff)], &[0x3a00_082d]); // setf8 w1
+        assert_eq!(nzcv(&cpu), N);
+        let cpu = run(&[(X(1), 0x7fff)], &[0x3a00_482d]); // setf16 w1
+        assert_eq!(nzcv(&cpu), 0);
+        let cpu = run(&[(X(1), 0xffff_0000)], &[0x3a00_482d]); // setf16 w1
+        assert_eq!(nzcv(&cpu), Z | V);
+    }
+
+    // Cumulative exception bits of FPSR
+    const IOC: u64 = 1 << 0;
+    const DZC: u64 = 1 << 1;
+    const OFC: u64 = 1 << 2;
+    const IXC: u64 = 1 << 4;
+    const IDC: u64 = 1 << 7;
+
+    // FPCR fields
+    const RMODE: u64 = 22;
+    const FZ16: u64 = 1 << 19;
+    const FZ: u64 = 1 << 24;
+    const DN: u64 = 1 << 25;
+
+    fn single(value: f32) -> u64 {
+        value.to_bits() as u64
+    }
+
+    fn double(value: f64) -> u64 {
+        value.to_bits()
+    }
+
+    #[test]
+    fn float_arithmetic() {
+        let v = AArch64RegisterId::V;
+
+        // The scalar result zeroes the rest of the vector register
+        let mut cpu = TestCpu::new();
+        cpu.set_u128(v(0), u128::MAX);
+        cpu.set(v(1), single(1.5));
+        cpu.set(v(2), single(2.25));
+        assert_eq!(cpu.run(&[0x1e22_2820]), None); // fadd s0, s1, s2
+        assert_eq!(cpu.get_u128(v(0)), single(3.75) as u128);
+        assert_eq!(cpu.get(Fpsr), 0);
+
+        let registers = [(v(1), double(1.0)), (v(2), double(0.25))];
+        let cpu = run(&registers, &[0x1e62_3820]); // fsub d0, d1, d2
+        assert_eq!(cpu.get(v(0)), double(0.75));
+
+        let registers = [(v(1), single(1.5)), (v(2), single(-2.0))];
+        let cpu = run(&registers, &[0x1e22_0820]); // fmul s0, s1, s2
+        assert_eq!(cpu.get(v(0)), single(-3.0));
+        let cpu = run(&registers, &[0x1e22_8820]); // fnmul s0, s1, s2
+        assert_eq!(cpu.get(v(0)), single(3.0));
+
+        let registers = [(v(1), double(1.0)), (v(2), double(3.0))];
+        let cpu = run(&registers, &[0x1e62_1820]); // fdiv d0, d1, d2
+        assert_eq!(cpu.get(v(0)), double(1.0 / 3.0));
+        assert_eq!(cpu.get(Fpsr), IXC);
+
+        let registers = [(v(1), double(-0.0)), (v(2), double(0.0))];
+        let cpu = run(&registers, &[0x1e62_4820]); // fmax d0, d1, d2
+        assert_eq!(cpu.get(v(0)), double(0.0));
+
+        // A quiet NaN loses against a number
+        let registers = [(v(1), single(f32::NAN)), (v(2), single(-1.0))];
+        let cpu = run(&registers, &[0x1e22_7820]); // fminnm s0, s1, s2
+        assert_eq!(cpu.get(v(0)), single(-1.0));
+
+        let cpu = run(&[(v(1), double(2.25))], &[0x1e61_c020]); // fsqrt d0, d1
+        assert_eq!(cpu.get(v(0)), double(1.5));
+    }
+
+    #[test]
+    fn float_fused_multiply_add() {
+        let v = AArch64RegisterId::V;
+
+        let registers = [
+            (v(1), double(2.0)),
+            (v(2), double(3.0)),
+            (v(3), double(1.0)),
+        ];
+        for (inst, expected) in [
+            (0x1f42_0c20, 7.0),  // fmadd d0, d1, d2, d3
+            (0x1f42_8c20, -5.0), // fmsub d0, d1, d2, d3
+            (0x1f62_0c20, -7.0), // fnmadd d0, d1, d2, d3
+            (0x1f62_8c20, 5.0),  // fnmsub d0, d1, d2, d3
+        ] {
+            let cpu = run(&registers, &[inst]);
+            assert_eq!(cpu.get(v(0)), double(expected), "{inst:#x}");
+        }
+
+        // The product is not rounded: (1 + 2^-52)(1 - 2^-52) - 1 is -2^-104
+        let epsilon = f64::EPSILON;
+        let registers = [
+            (v(1), double(1.0 + epsilon)),
+            (v(2), double(1.0 - epsilon)),
+            (v(3), double(-1.0)),
+        ];
+        let cpu = run(&registers, &[0x1f42_0c20]); // fmadd d0, d1, d2, d3
+        assert_eq!(cpu.get(v(0)), double(-epsilon * epsilon));
+
+        // Half precision 1.5 * 2 and 0.25
+        let registers = [(v(1), 0x3e00), (v(2), 0x4000), (v(3), 0x3400)];
+        let cpu = run(&registers, &[0x1fc2_0c20]); // fmadd h0, h1, h2, h3
+        assert_eq!(cpu.get_u128(v(0)), 0x4280);
+        let cpu = run(&registers, &[0x1fe2_8c20]); // fnmsub h0, h1, h2, h3
+        assert_eq!(cpu.get_u128(v(0)), 0x4180);
+    }
+
+    #[test]
+    fn float_rounding_modes() {
+        let v = AArch64RegisterId::V;
+
+        // 1/3 is rounded down to the nearest double
+        let third = 1.0f64 / 3.0;
+        let above = f64::from_bits(third.to_bits() + 1);
+        for (rmode, expected) in [(0b00, third), (0b01, above), (0b10, third), (0b11, third)] {
+            let registers = [
+                (Fpcr, rmode << RMODE),
+                (v(1), double(1.0)),
+                (v(2), double(3.0)),
+            ];
+            let cpu = run(&registers, &[0x1e62_1820]); // fdiv d0, d1, d2
+            assert_eq!(cpu.get(v(0)), double(expected), "rmode {rmode:#04b}");
+        }
+
+        // frinti follows FPCR and frintx also reports inexact
+        for (rmode, expected) in [(0b00, 2.0), (0b01, 3.0), (0b10, 2.0), (0b11, 2.0)] {
+            let registers = [(Fpcr, rmode << RMODE), (v(1), double(2.5))];
+            let cpu = run(&registers, &[0x1e67_c020]); // frinti d0, d1
+            assert_eq!(cpu.get(v(0)), double(expected), "rmode {rmode:#04b}");
+            assert_eq!(cpu.get(Fpsr), 0);
+            let cpu = run(&registers, &[0x1e67_4020]); // frintx d0, d1
+            assert_eq!(cpu.get(v(0)), double(expected), "rmode {rmode:#04b}");
+            assert_eq!(cpu.get(Fpsr), IXC);
+        }
+
+        // The other roundings have their own mode
+        let registers = [(Fpcr, 0b01 << RMODE), (v(1), double(2.5))];
+        let cpu = run(&registers, &[0x1e64_4020]); // frintn d0, d1
+        assert_eq!(cpu.get(v(0)), double(2.0));
+        let cpu = run(&registers, &[0x1e66_4020]); // frinta d0, d1
+        assert_eq!(cpu.get(v(0)), double(3.0));
+        let cpu = run(&[(v(1), single(-1.5))], &[0x1e25_4020]); // frintm s0, s1
+        assert_eq!(cpu.get(v(0)), single(-2.0));
+    }
+
+    #[test]
+    fn float_exceptions() {
+        let v = AArch64RegisterId::V;
+
+        let registers = [(v(1), double(1.0)), (v(2), double(0.0))];
+        let cpu = run(&registers, &[0x1e62_1820]); // fdiv d0, d1, d2
+        assert_eq!(cpu.get(v(0)), double(f64::INFINITY));
+        assert_eq!(cpu.get(Fpsr), DZC);
+
+        // An invalid operation creates the default NaN
+        let cpu = run(&[(v(1), double(-1.0))], &[0x1e61_c020]); // fsqrt d0, d1
+        assert_eq!(cpu.get(v(0)), 0x7ff8_0000_0000_0000);
+        assert_eq!(cpu.get(Fpsr), IOC);
+
+        // The flags accumulate with the ones already set
+        let registers = [(Fpsr, DZC), (v(1), double(f64::MAX)), (v(2), double(2.0))];
+        let cpu = run(&registers, &[0x1e62_0820]); // fmul d0, d1, d2
+        assert_eq!(cpu.get(v(0)), double(f64::INFINITY));
+        assert_eq!(cpu.get(Fpsr), DZC | OFC | IXC);
+
+        // FABS and FNEG never raise exceptions, even on signaling NaNs
+        let snan = 0x7ff0_0000_0000_0001;
+        let cpu = run(&[(v(1), snan)], &[0x1e61_4020]); // fneg d0, d1
+        assert_eq!(cpu.get(v(0)), snan | 1 << 63);
+        assert_eq!(cpu.get(Fpsr), 0);
+        let cpu = run(&[(v(1), single(-2.0))], &[0x1e20_c020]); // fabs s0, s1
+        assert_eq!(cpu.get(v(0)), single(2.0));
+        let cpu = run(&[(v(1), single(-2.0))], &[0x1e20_4020]); // fmov s0, s1
+        assert_eq!(cpu.get(v(0)), single(-2.0));
+    }
+
+    #[test]
+    fn float_flush_to_zero_and_default_nan() {
+        let v = AArch64RegisterId::V;
+
+        let tiny = f64::from_bits(1);
+        let registers = [(v(1), double(tiny)), (v(2), double(1.0))];
+        let cpu = run(&registers, &[0x1e62_0820]); // fmul d0, d1, d2
+        assert_eq!(cpu.get(v(0)), double(tiny));
+        assert_eq!(cpu.get(Fpsr), 0);
+        let cpu = run(&[&registers[..], &[(Fpcr, FZ)]].concat(), &[0x1e62_0820]);
+        assert_eq!(cpu.get(v(0)), 0);
+        assert_eq!(cpu.get(Fpsr), IDC);
+
+        let nan = 0x7ff8_0000_0000_1234;
+        let registers = [(v(1), nan), (v(2), double(1.0))];
+        let cpu = run(&registers, &[0x1e62_2820]); // fadd d0, d1, d2
+        assert_eq!(cpu.get(v(0)), nan);
+        let cpu = run(&[&registers[..], &[(Fpcr, DN)]].concat(), &[0x1e62_2820]);
+        assert_eq!(cpu.get(v(0)), 0x7ff8_0000_0000_0000);
+
+        // Half precision is flushed by FZ16 instead of FZ, without reporting input denormal
+        let registers = [(v(1), 0x0001), (v(2), 0x3c00), (v(3), 0)];
+        let cpu = run(&[&registers[..], &[(Fpcr, FZ)]].concat(), &[0x1fc2_0c20]); // fmadd h0, h1, h2, h3
+        assert_eq!(cpu.get(v(0)), 0x0001);
+        let cpu = run(&[&registers[..], &[(Fpcr, FZ16)]].concat(), &[0x1fc2_0c20]);
+        assert_eq!(cpu.get(v(0)), 0);
+        assert_eq!(cpu.get(Fpsr), 0);
+    }
+
+    #[test]
+    fn float_compare() {
+        let v = AArch64RegisterId::V;
+
+        for (lhs, expected) in [(1.0, N), (2.0, Z | C), (3.0, C), (f64::NAN, C | V)] {
+            let registers = [(v(1), double(lhs)), (v(2), double(2.0))];
+            let cpu = run(&registers, &[0x1e62_2020]); // fcmp d1, d2
+            assert_eq!(nzcv(&cpu), expected, "{lhs}");
+            assert_eq!(cpu.get(Fpsr), 0);
+        }
+
+        // Only the signaling compare raises invalid on quiet NaNs
+        let registers = [(v(1), double(f64::NAN)), (v(2), double(2.0))];
+        let cpu = run(&registers, &[0x1e62_2030]); // fcmpe d1, d2
+        assert_eq!(nzcv(&cpu), C | V);
+        assert_eq!(cpu.get(Fpsr), IOC);
+
+        let cpu = run(&[(v(1), single(-0.0))], &[0x1e20_2028]); // fcmp s1, #0.0
+        assert_eq!(nzcv(&cpu), Z | C);
+
+        let registers = [(v(1), 0x3c00), (v(2), 0x4000)];
+        let cpu = run(&registers, &[0x1ee2_2020]); // fcmp h1, h2
+        assert_eq!(nzcv(&cpu), N);
+
+        let registers = [(v(1), double(1.0)), (v(2), double(2.0))];
+        let cpu = run(
+            &[&registers[..], &[(Nzcv, Z << 28)]].concat(),
+            &[0x1e62_0c20],
+        ); // fcsel d0, d1, d2, eq
+        assert_eq!(cpu.get(v(0)), double(1.0));
+        let cpu = run(&registers, &[0x1e62_0c20]); // fcsel d0, d1, d2, eq
+        assert_eq!(cpu.get(v(0)), double(2.0));
+    }
+
+    #[test]
+    fn float_conversions() {
+        let v = AArch64RegisterId::V;
+
+        let cpu = run(&[(v(1), single(1.5))], &[0x1e22_c020]); // fcvt d0, s1
+        assert_eq!(cpu.get(v(0)), double(1.5));
+        let cpu = run(&[(v(1), double(0.1))], &[0x1e62_4020]); // fcvt s0, d1
+        assert_eq!(cpu.get(v(0)), single(0.1));
+        assert_eq!(cpu.get(Fpsr), IXC);
+        let cpu = run(&[(v(1), single(65536.0))], &[0x1e23_c020]); // fcvt h0, s1
+        assert_eq!(cpu.get_u128(v(0)), 0x7c00);
+        assert_eq!(cpu.get(Fpsr), OFC | IXC);
+        let cpu = run(&[(v(1), double(1.0))], &[0x1e63_c020]); // fcvt h0, d1
+        assert_eq!(cpu.get_u128(v(0)), 0x3c00);
+
+        for (inst, value, expected) in [
+            (0x1e78_0020, double(-2.7), 0xffff_fffe),  // fcvtzs w0, d1
+            (0x1e79_0020, double(3e9), 3_000_000_000), // fcvtzu w0, d1
+            (0x9e60_0020, double(2.5), 2),             // fcvtns x0, d1
+            (0x1e24_0020, single(2.5), 3),             // fcvtas w0, s1
+            (0x1e71_0020, double(2.7), 2),             // fcvtmu w0, d1
+            (0x9e28_0020, single(2.1), 3),             // fcvtps x0, s1
+            (0x9e58_e020, double(1.5), 384),           // fcvtzs x0, d1, #8
+            (0x1e19_c020, single(0.5), 0x8000),        // fcvtzu w0, s1, #16
+        ] {
+            let cpu = run(&[(X(0), u64::MAX), (v(1), value)], &[inst]);
+            assert_eq!(cpu.get(X(0)), expected, "{inst:#x}");
+        }
+
+        // Out of range values saturate
+        let cpu = run(&[(v(1), double(-1.0))], &[0x9e79_0020]); // fcvtzu x0, d1
+        assert_eq!(cpu.get(X(0)), 0);
+        assert_eq!(cpu.get(Fpsr), IOC);
+        let cpu = run(&[(v(1), double(1e10))], &[0x1e78_0020]); // fcvtzs w0, d1
+        assert_eq!(cpu.get(X(0)), 0x7fff_ffff);
+        assert_eq!(cpu.get(Fpsr), IOC);
+
+        let cpu = run(&[(X(1), -3i64 as u64)], &[0x9e62_0020]); // scvtf d0, x1
+        assert_eq!(cpu.get(v(0)), double(-3.0));
+        let cpu = run(&[(X(1), u64::MAX)], &[0x1e23_0020]); // ucvtf s0, w1
+        assert_eq!(cpu.get(v(0)), single(4294967296.0));
+        assert_eq!(cpu.get(Fpsr), IXC);
+        let cpu = run(&[(X(1), -8i32 as u32 as u64)], &[0x1e42_f020]); // scvtf d0, w1, #4
+        assert_eq!(cpu.get(v(0)), double(-0.5));
+    }
+
+    #[test]
+    fn float_half_precision_1src() {
+        let v = AArch64RegisterId::V;
+
+        // The half precision result zeroes the rest of the vector register
+        let mut cpu = TestCpu::new();
+        cpu.set_u128(v(0), u128::MAX);
+        cpu.set(v(1), 0xbc00);
+        assert_eq!(cpu.run(&[0x1ee0_c020]), None); // fabs h0, h1
+        assert_eq!(cpu.get_u128(v(0)), 0x3c00);
+
+        for (inst, value, expected, fpsr) in [
+            (0x1ee0_4020, 0xbc00, 0xbc00, 0),                    // fmov h0, h1
+            (0x1ee1_4020, 0x3c00, 0xbc00, 0),                    // fneg h0, h1
+            (0x1ee1_c020, 0x4080, 0x3e00, 0),                    // fsqrt h0, h1
+            (0x1ee4_4020, 0x4100, 0x4000, 0),                    // frintn h0, h1
+            (0x1ee7_c020, 0x3e00, 0x4000, 0),                    // frinti h0, h1
+            (0x1ee7_4020, 0x3e00, 0x4000, IXC),                  // frintx h0, h1
+            (0x1ee2_4020, 0x3e00, single(1.5), 0),               // fcvt s0, h1
+            (0x1ee2_c020, 0xfc00, double(f64::NEG_INFINITY), 0), // fcvt d0, h1
+            (0x1ee2_4020, 0x7d00, 0x7fe0_0000, IOC),             // fcvt s0, h1
+        ] {
+            let cpu = run(&[(v(1), value)], &[inst]);
+            assert_eq!(cpu.get_u128(v(0)), expected as u128, "{inst:#x}");
+            assert_eq!(cpu.get(Fpsr), fpsr, "{inst:#x}");
+        }
+    }
+
+    #[test]
+    fn float_moves() {
+        let v = AArch64RegisterId::V;
+
+        let cpu = run(&[], &[0x1e2e_1000]); // fmov s0, #1.0
+        assert_eq!(cpu.get(v(0)), single(1.0));
+        let cpu = run(&[], &[0x1e78_1000]); // fmov d0, #-0.125
+        assert_eq!(cpu.get(v(0)), double(-0.125));
+        let cpu = run(&[], &[0x1e67_f000]); // fmov d0, #31.0
+        assert_eq!(cpu.get(v(0)), double(31.0));
+
+        let value = 0x0123_4567_89ab_cdef;
+        let cpu = run(&[(v(1), value)], &[0x9e66_0020]); // fmov x0, d1
+        assert_eq!(cpu.get(X(0)), value);
+        let cpu = run(&[(v(1), value)], &[0x1e26_0020]); // fmov w0, s1
+        assert_eq!(cpu.get(X(0)), 0x89ab_cdef);
+
+        let mut cpu = TestCpu::new();
+        cpu.set_u128(v(0), u128::MAX);
+        cpu.set(X(1), value);
+        assert_eq!(cpu.run(&[0x1e27_0020]), None); // fmov s0, w1
+        assert_eq!(cpu.get_u128(v(0)), 0x89ab_cdef);
+        cpu.set_u128(v(0), u128::MAX);
+        assert_eq!(cpu.run(&[0x9e67_0020]), None); // fmov d0, x1
+        assert_eq!(cpu.get_u128(v(0)), value as u128);
+
+        // The top half moves keep the bottom half of the vector register
+        cpu.set_u128(v(1), 0xaaaa << 64 | 0xbbbb);
+        assert_eq!(cpu.run(&[0x9eae_0020]), None); // fmov x0, v1.d[1]
+        assert_eq!(cpu.get(X(0)), 0xaaaa);
+        cpu.set_u128(v(0), 0xcccc << 64 | 0xdddd);
+        assert_eq!(cpu.run(&[0x9eaf_0020]), None); // fmov v0.d[1], x1
+        assert_eq!(cpu.get_u128(v(0)), (value as u128) << 64 | 0xdddd);
+    }
+
+    const QC: u64 = 1 << 27;
+
+    /// Pack `values` into a vector of `esize`-bit elements, the first one lowest.
+    fn lanes(esize: u32, values: &[i64]) -> u128 {
+        let mask = u128::MAX >> (128 - esize);
+        values
+            .iter()
+            .rev()
+            .fold(0, |acc, &value| acc << esize | (value as u128 & mask))
+    }
+
+    /// Run the instructions with the given vector registers set.
+    fn run_vectors(vectors: &[(u8, u128)], insts: &[u32]) -> TestCpu {
+        let mut cpu = TestCpu::new();
+        for &(n, value) in vectors {
+            cpu.set_u128(AArch64RegisterId::V(n), value);
+        }
+
+        assert_eq!(cpu.run(insts), None);
+        cpu
+    }
+
+    /// The result in v0 of a vector instruction on v1 and v2.
+    fn vector_op(inst: u32, v1: u128, v2: u128) -> u128 {
+        run_vectors(&[(1, v1), (2, v2)], &[inst]).get_u128(AArch64RegisterId::V(0))
+    }
+
+    #[test]
+    fn vector_arithmetic() {
+        let v = AArch64RegisterId::V;
+        let bytes: Vec<i64> = (1..=16).collect();
+
+        let (n, m) = (lanes(8, &bytes), lanes(8, &[0xff; 16]));
+        let sums: Vec<i64> = (0..16).collect();
+        assert_eq!(vector_op(0x4e22_8420, n, m), lanes(8, &sums)); // add v0.16b, v1.16b, v2.16b
+                                                                   // The 64-bit arrangements clear the upper half
+        let cpu = run_vectors(&[(0, u128::MAX), (1, n), (2, m)], &[0x0e22_8420]); // add v0.8b, v1.8b, v2.8b
+        assert_eq!(cpu.get_u128(v(0)), lanes(8, &sums[..8]));
+
+        let (n, m) = (lanes(32, &[5, 0, 100, -1]), lanes(32, &[7, 1, 50, -1]));
+        assert_eq!(vector_op(0x6ea2_8420, n, m), lanes(32, &[-2, -1, 50, 0])); // sub v0.4s, v1.4s, v2.4s
+        let (n, m) = (lanes(64, &[-1, 1]), lanes(64, &[1, 2]));
+        assert_eq!(vector_op(0x4ee2_8420, n, m), lanes(64, &[0, 3])); // add v0.2d, v1.2d, v2.2d
+
+        let (n, m) = (
+            lanes(16, &[3, -2, 0x100, 0x7fff]),
+            lanes(16, &[4, 3, 0x100, 2]),
+        );
+        let products = lanes(16, &[12, -6, 0, 0xfffe]);
+        assert_eq!(vector_op(0x4e62_9c20, n, m), products); // mul v0.8h, v1.8h, v2.8h
+
+        let registers = [
+            (0, lanes(32, &[1, 2, 3, 4])),
+            (1, lanes(32, &[2, 3, 4, 5])),
+            (2, lanes(32, &[10, 10, 10, -1])),
+        ];
+        let cpu = run_vectors(&registers, &[0x4ea2_9420]); // mla v0.4s, v1.4s, v2.4s
+        assert_eq!(cpu.get_u128(v(0)), lanes(32, &[21, 32, 43, -1]));
+        let registers = [
+            (0, lanes(8, &[10])),
+            (1, lanes(8, &[2])),
+            (2, lanes(8, &[3])),
+        ];
+        let cpu = run_vectors(&registers, &[0x2e22_9420]); // mls v0.8b, v1.8b, v2.8b
+        assert_eq!(cpu.get_u128(v(0)), lanes(8, &[4]));
+
+        let (n, m) = (lanes(16, &[-1, 5]), lanes(16, &[1, -5]));
+        assert_eq!(vector_op(0x4e62_6420, n, m), lanes(16, &[1, 5])); // smax v0.8h, v1.8h, v2.8h
+        assert_eq!(vector_op(0x6e62_6c20, n, m), lanes(16, &[1, 5])); // umin v0.8h, v1.8h, v2.8h
+        let (n, m) = (lanes(8, &[-128, 10]), lanes(8, &[127, 20]));
+        assert_eq!(vector_op(0x4e22_7420, n, m), lanes(8, &[0xff, 10])); // sabd v0.16b, v1.16b, v2.16b
+        let registers = [
+            (0, lanes(32, &[1, 1])),
+            (1, lanes(32, &[0, 10])),
+            (2, lanes(32, &[0xffff_ffff, 3])),
+        ];
+        let cpu = run_vectors(&registers, &[0x6ea2_7c20]); // uaba v0.4s, v1.4s, v2.4s
+        assert_eq!(cpu.get_u128(v(0)), lanes(32, &[0, 8]));
+
+        // Halving operations round toward minus infinity unless they round to nearest
+        let (n, m) = (lanes(8, &[-128, 127, -1]), lanes(8, &[-128, 127, 0]));
+        assert_eq!(vector_op(0x0e22_0420, n, m), lanes(8, &[-128, 127, -1])); // shadd v0.8b, v1.8b, v2.8b
+        let (n, m) = (lanes(8, &[0xff, 1]), lanes(8, &[0xff, 2]));
+        assert_eq!(vector_op(0x2e22_1420, n, m), lanes(8, &[0xff, 2])); // urhadd v0.8b, v1.8b, v2.8b
+        let (n, m) = (lanes(8, &[0, 5]), lanes(8, &[1, 2]));
+        assert_eq!(vector_op(0x2e22_2420, n, m), lanes(8, &[0xff, 1])); // uhsub v0.8b, v1.8b, v2.8b
+
+        let (n, m) = (lanes(32, &[1, 2, 3, 4]), lanes(32, &[10, 20, 30, 40]));
+        assert_eq!(vector_op(0x4ea2_bc20, n, m), lanes(32, &[3, 7, 30, 70])); // addp v0.4s, v1.4s, v2.4s
+        let n = lanes(8, &[1, -2, 3, 4, -5, -6, 7, 0]);
+        let maxima = lanes(8, &[1, 4, -5, 7]);
+        assert_eq!(vector_op(0x0e22_a420, n, 0), maxima); // smaxp v0.8b, v1.8b, v2.8b
+        let (n, m) = (lanes(16, &[1, 0xffff, 5, 6]), lanes(16, &[7, 8]));
+        assert_eq!(vector_op(0x2e62_ac20, n, m), lanes(16, &[1, 5, 7, 0])); // uminp v0.4h, v1.4h, v2.4h
+
+        let (n, m) = (lanes(8, &[3, 0x80, 0x53]), lanes(8, &[3, 2, 0xca]));
+        assert_eq!(vector_op(0x2e22_9c20, n, m), lanes(8, &[5, 0, 0x7e])); // pmul v0.8b, v1.8b, v2.8b
+
+        // 1D arrangements are reserved
+        let mut cpu = TestCpu::new();
+        assert!(matches!(
+            cpu.run(&[0x0ee2_8420]), // add v0.1d, v1.1d, v2.1d
+            Some(Interrupt::Exception(core::Exception {
+                kind: ExceptionKind::Undefined,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn vector_compare() {
+        let (n, m) = (lanes(32, &[1, 2, 3, 4]), lanes(32, &[1, 0, 3, 0]));
+        assert_eq!(vector_op(0x6ea2_8c20, n, m), lanes(32, &[-1, 0, -1, 0])); // cmeq v0.4s, v1.4s, v2.4s
+        let (n, m) = (lanes(32, &[-1, 5, 0, 1]), lanes(32, &[0, 4, 0, -1]));
+        assert_eq!(vector_op(0x4ea2_3420, n, m), lanes(32, &[0, -1, 0, -1])); // cmgt v0.4s, v1.4s, v2.4s
+        assert_eq!(vector_op(0x6ea2_3420, n, m), lanes(32, &[-1, -1, 0, 0])); // cmhi v0.4s, v1.4s, v2.4s
+        let (n, m) = (lanes(64, &[-1, 0]), lanes(64, &[-1, 1]));
+        assert_eq!(vector_op(0x6ee2_3c20, n, m), lanes(64, &[-1, 0])); // cmhs v0.2d, v1.2d, v2.2d
+        let (n, m) = (lanes(8, &[0b1010, 0b0101]), lanes(8, &[0b0010, 0b1010]));
+        assert_eq!(vector_op(0x0e22_8c20, n, m), lanes(8, &[-1, 0])); // cmtst v0.8b, v1.8b, v2.8b
+
+        let n = lanes(16, &[-1, 0, 1, -0x8000, 0x7fff, 0, 0, 0]);
+        let less = lanes(16, &[-1, 0, 0, -1, 0, 0, 0, 0]);
+        assert_eq!(vector_op(0x4e60_a820, n, 0), less); // cmlt v0.8h, v1.8h, #0
+        let less_equal = lanes(16, &[-1, -1, 0, -1, 0, -1, -1, -1]);
+        assert_eq!(vector_op(0x6e60_9820, n, 0), less_equal); // cmle v0.8h, v1.8h, #0
+        let greater_equal = lanes(16, &[0, -1, -1, 0, -1, -1, -1, -1]);
+        assert_eq!(vector_op(0x6e60_8820, n, 0), greater_equal); // cmge v0.8h, v1.8h, #0
+        let greater = lanes(16, &[0, 0, -1, 0, -1, 0, 0, 0]);
+        assert_eq!(vector_op(0x4e60_8820, n, 0), greater); // cmgt v0.8h, v1.8h, #0
+        let equal = lanes(16, &[0, -1, 0, 0, 0, -1, -1, -1]);
+        assert_eq!(vector_op(0x4e60_9820, n, 0), equal); // cmeq v0.8h, v1.8h, #0
+    }
+
+    #[test]
+    fn vector_saturation() {
+        let v = AArch64RegisterId::V;
+        // The result and FPSR.QC of a vector instruction on v1 and v2
+        let saturating = |inst: u32, v1: u128, v2: u128| {
+            let cpu = run_vectors(&[(1, v1), (2, v2)], &[inst]);
+            (cpu.get_u128(v(0)), cpu.get(Fpsr) & QC)
+        };
+
+        let (n, m) = (lanes(8, &[100, -100, 1]), lanes(8, &[100, -100, 2]));
+        let expected = (lanes(8, &[127, -128, 3]), QC);
+        assert_eq!(saturating(0x0e22_0c20, n, m), expected); // sqadd v0.8b, v1.8b, v2.8b
+        let expected = (lanes(8, &[3]), 0);
+        assert_eq!(
+            saturating(0x0e22_0c20, lanes(8, &[1]), lanes(8, &[2])),
+            expected
+        );
+
+        let (n, m) = (lanes(16, &[1, 5]), lanes(16, &[2, 3]));
+        assert_eq!(saturating(0x2e62_2c20, n, m), (lanes(16, &[0, 2]), QC)); // uqsub v0.4h, v1.4h, v2.4h
+        let (n, m) = (lanes(64, &[-1, 1]), lanes(64, &[1, 1]));
+        assert_eq!(saturating(0x6ee2_0c20, n, m), (lanes(64, &[-1, 2]), QC)); // uqadd v0.2d, v1.2d, v2.2d
+        let (n, m) = (lanes(64, &[i64::MIN, 0]), lanes(64, &[1, i64::MIN]));
+        let expected = (lanes(64, &[i64::MIN, i64::MAX]), QC);
+        assert_eq!(saturating(0x4ee2_2c20, n, m), expected); // sqsub v0.2d, v1.2d, v2.2d
+
+        // Only the product of the most negative values saturates
+        let min = i32::MIN as i64;
+        let n = lanes(32, &[min, 0x4000_0000, -3, 0]);
+        let m = lanes(32, &[min, 0x4000_0000, 0x4000_0000, 0]);
+        let expected = (lanes(32, &[i32::MAX as i64, 0x2000_0000, -2, 0]), QC);
+        assert_eq!(saturating(0x4ea2_b420, n, m), expected); // sqdmulh v0.4s, v1.4s, v2.4s
+        let (n, m) = (lanes(16, &[0x4000, -3]), lanes(16, &[0x4000, 0x4000]));
+        let expected = (lanes(16, &[0x2000, -1]), 0);
+        assert_eq!(saturating(0x6e62_b420, n, m), expected); // sqrdmulh v0.8h, v1.8h, v2.8h
+    }
+
+    #[test]
+    fn vector_shift_by_register() {
+        let v = AArch64RegisterId::V;
+
+        // The shift is the signed bottom byte of each element, negative shifts are to the right
+        let n = lanes(32, &[1, -16, 0x4000_0000, -1]);
+        let m = lanes(32, &[0x104, -2, 2, -40]);
+        let expected = lanes(32, &[16, -4, 0, -1]);
+        assert_eq!(vector_op(0x4ea2_4420, n, m), expected); // sshl v0.4s, v1.4s, v2.4s
+        let (n, m) = (lanes(64, &[1, -1]), lanes(64, &[63, -64]));
+        assert_eq!(vector_op(0x6ee2_4420, n, m), lanes(64, &[i64::MIN, 0])); // ushl v0.2d, v1.2d, v2.2d
+
+        let (n, m) = (lanes(32, &[5, -5, 7, 1]), lanes(32, &[-1, -1, -3, -32]));
+        assert_eq!(vector_op(0x4ea2_5420, n, m), lanes(32, &[3, -2, 1, 0])); // srshl v0.4s, v1.4s, v2.4s
+
+        let n = lanes(8, &[1, -1, 64, -65, -128]);
+        let m = lanes(8, &[6, 7, 1, 1, 8]);
+        let cpu = run_vectors(&[(1, n), (2, m)], &[0x4e22_4c20]); // sqshl v0.16b, v1.16b, v2.16b
+        assert_eq!(cpu.get_u128(v(0)), lanes(8, &[64, -128, 127, -128, -128]));
+        assert_eq!(cpu.get(Fpsr), QC);
+
+        let (n, m) = (lanes(64, &[1, 3]), lanes(64, &[63, 63]));
+        let cpu = run_vectors(&[(1, n), (2, m)], &[0x6ee2_4c20]); // uqshl v0.2d, v1.2d, v2.2d
+        assert_eq!(cpu.get_u128(v(0)), lanes(64, &[i64::MIN, -1]));
+        assert_eq!(cpu.get(Fpsr), QC);
+        let (n, m) = (lanes(64, &[-1, 1]), lanes(64, &[-1, 64]));
+        let cpu = run_vectors(&[(1, n), (2, m)], &[0x6ee2_5c20]); // uqrshl v0.2d, v1.2d, v2.2d
+        assert_eq!(cpu.get_u128(v(0)), lanes(64, &[i64::MIN, -1]));
+        assert_eq!(cpu.get(Fpsr), QC);
+    }
+
+    #[test]
+    fn vector_logical() {
+        let v = AArch64RegisterId::V;
+        let d = 0x3333_3333_3333_3333_3333_3333_3333_3333;
+        let n = 0xff00_ff00_ff00_ff00_0123_4567_89ab_cdef;
+        let m = 0x0f0f_0f0f_0f0f_0f0f_f0f0_f0f0_f0f0_f0f0;
+        let run = |inst: u32| run_vectors(&[(0, d), (1, n), (2, m)], &[inst]).get_u128(v(0));
+
+        assert_eq!(run(0x4e22_1c20), n & m); // and v0.16b, v1.16b, v2.16b
+        assert_eq!(run(0x0e62_1c20), (n & !m) as u64 as u128); // bic v0.8b, v1.8b, v2.8b
+        assert_eq!(run(0x4ea2_1c20), n | m); // orr v0.16b, v1.16b, v2.16b
+        assert_eq!(run(0x4ee2_1c20), n | !m); // orn v0.16b, v1.16b, v2.16b
+        assert_eq!(run(0x6e22_1c20), n ^ m); // eor v0.16b, v1.16b, v2.16b
+        assert_eq!(run(0x6e62_1c20), (d & n) | (!d & m)); // bsl v0.16b, v1.16b, v2.16b
+        assert_eq!(run(0x6ea2_1c20), (n & m) | (d & !m)); // bit v0.16b, v1.16b, v2.16b
+        assert_eq!(run(0x6ee2_1c20), (d & m) | (n & !m)); // bif v0.16b, v1.16b, v2.16b
+        assert_eq!(run(0x6e20_5820), !n); // not v0.16b, v1.16b
+    }
+
+    #[test]
+    fn vector_shift_by_immediate() {
+        let v = AArch64RegisterId::V;
+
+        let n = lanes(32, &[-5, 5, i32::MIN as i64, 1]);
+        let expected = lanes(32, &[-3, 2, -0x4000_0000, 0]);
+        assert_eq!(vector_op(0x4f3f_0420, n, 0), expected); // sshr v0.4s, v1.4s, #1
+        assert_eq!(vector_op(0x6f40_0420, lanes(64, &[-1, 1]), 0), 0); // ushr v0.2d, v1.2d, #64
+        let n = lanes(16, &[5, -6, 2, -2]);
+        assert_eq!(vector_op(0x4f1e_2420, n, 0), lanes(16, &[1, -1, 1, 0])); // srshr v0.8h, v1.8h, #2
+
+        let registers = [(0, lanes(32, &[10, 10])), (1, lanes(32, &[-1, 1]))];
+        let cpu = run_vectors(&registers, &[0x4f21_1420]); // ssra v0.4s, v1.4s, #31
+        assert_eq!(cpu.get_u128(v(0)), lanes(32, &[9, 10]));
+        let registers = [(0, lanes(8, &[1, 1])), (1, lanes(8, &[0x80, 0x7f]))];
+        let cpu = run_vectors(&registers, &[0x6f08_3420]); // ursra v0.16b, v1.16b, #8
+        assert_eq!(cpu.get_u128(v(0)), lanes(8, &[2, 1]));
+
+        let n = lanes(8, &[1, 3]);
+        assert_eq!(vector_op(0x4f0f_5420, n, 0), lanes(8, &[0x80, 0x80])); // shl v0.16b, v1.16b, #7
+
+        let cpu = run_vectors(&[(1, lanes(32, &[-1, 3, 0x7fff_ffff]))], &[0x6f21_6420]); // sqshlu v0.4s, v1.4s, #1
+        assert_eq!(cpu.get_u128(v(0)), lanes(32, &[0, 6, 0xffff_fffe]));
+        assert_eq!(cpu.get(Fpsr), QC);
+        let cpu = run_vectors(&[(1, lanes(8, &[0x0f, 0x10]))], &[0x2f0c_7420]); // uqshl v0.8b, v1.8b, #4
+        assert_eq!(cpu.get_u128(v(0)), lanes(8, &[0xf0, 0xff]));
+        assert_eq!(cpu.get(Fpsr), QC);
+        let cpu = run_vectors(&[(1, lanes(64, &[-1, 1]))], &[0x4f7f_7420]); // sqshl v0.2d, v1.2d, #63
+        assert_eq!(cpu.get_u128(v(0)), lanes(64, &[i64::MIN, i64::MAX]));
+        assert_eq!(cpu.get(Fpsr), QC);
+
+        // Inserting shifts keep the bits of the destination that are shifted in
+        let registers = [(0, lanes(8, &[0xff, 0xff])), (1, lanes(8, &[0x80, 0x0f]))];
+        let cpu = run_vectors(&registers, &[0x2f0d_4420]); // sri v0.8b, v1.8b, #3
+        assert_eq!(cpu.get_u128(v(0)), lanes(8, &[0xf0, 0xe1]));
+        let cpu = run_vectors(&registers, &[0x2f08_4420]); // sri v0.8b, v1.8b, #8
+        assert_eq!(cpu.get_u128(v(0)), lanes(8, &[0xff, 0xff]));
+        let registers = [(0, lanes(16, &[0xffff])), (1, lanes(16, &[0x1234]))];
+        let cpu = run_vectors(&registers, &[0x2f14_5420]); // sli v0.4h, v1.4h, #4
+        assert_eq!(cpu.get_u128(v(0)), lanes(16, &[0x234f]));
 
-fn compile_movk(bb: &mut BasicBlock, operand: &HwImm16Rd, ty: IrType) {}
-fn compile_movi(bb: &mut BasicBlock, operand: &AdvSimdModifiedImm) {}
-fn compile_adr(bb: &mut BasicBlock, operand: &PcRelAddressing) {}
-fn compile_adrp(bb: &mut BasicBlock, operand: &PcRelAddressing) {}
-fn compile_rev_var(bb: &mut BasicBlock, operand: &RnRd, ty: IrType) {}
-fn compile_ldr_imm(bb: &mut BasicBlock, operand: &OpcSizeImm12RnRt, ty: IrType) {}
-fn compile_ldr_imm_simd_fp(bb: &mut BasicBlock, operand: &OpcSizeImm12RnRt, ty: IrType) {}
-fn compile_ldr_lit_var64(bb: &mut BasicBlock, operand: &Imm19Rt) {}
-fn compile_ldrh_imm(bb: &mut BasicBlock, operand: &OpcSizeImm12RnRt) {}
-fn compile_ldrb_imm(bb: &mut BasicBlock, operand: &OpcSizeImm12RnRt) {}
-fn compile_ldr_reg(bb: &mut BasicBlock, operand: &LoadStoreRegRegOffset, ty: IrType) {}
-fn compile_ldrb_reg_shifted_reg(bb: &mut BasicBlock, operand: &LoadStoreRegRegOffset) {}
-fn compile_ldp(bb: &mut BasicBlock, operand: &LoadStoreRegPair, ty: IrType) {}
-fn compile_ldrsh_reg(bb: &mut BasicBlock, operand: &LoadStoreRegRegOffset, ty: IrType) {}
-fn compile_ldaxr(bb: &mut BasicBlock, operand: &RsRt2RnRt, ty: IrType) {}
-fn compile_ldar(bb: &mut BasicBlock, operand: &RsRt2RnRt, ty: IrType) {}
-fn compile_ldur(bb: &mut BasicBlock, operand: &LdStRegUnscaledImm, ty: IrType) {}
-fn compile_ldp_simd_fp(bb: &mut BasicBlock, operand: &LoadStoreRegPair, ty: IrType) {}
-fn compile_ldr_reg_simd_fp(bb: &mut BasicBlock, operand: &LoadStoreRegRegOffset) {}
-fn compile_ldxr(bb: &mut BasicBlock, operand: &RsRt2RnRt, ty: IrType) {}
-fn compile_str_imm(bb: &mut BasicBlock, operand: &OpcSizeImm12RnRt, ty: IrType) {}
-fn compile_stp_var(bb: &mut BasicBlock, operand: &LoadStoreRegPair, ty: IrType) {}
-fn compile_strb_imm(bb: &mut BasicBlock, operand: &OpcSizeImm12RnRt) {}
-fn compile_sturb_imm(bb: &mut BasicBlock, operand: &LdStRegUnscaledImm) {}
-fn compile_str_reg(bb: &mut BasicBlock, operand: &LoadStoreRegRegOffset, ty: IrType) {}
-fn compile_stur(bb: &mut BasicBlock, operand: &LdStRegUnscaledImm, ty: IrType) {}
-fn compile_stur_simd_fp(bb: &mut BasicBlock, operand: &LdStRegUnscaledImm, ty: IrType) {}
-fn compile_stp_simd_fp(bb: &mut BasicBlock, operand: &LoadStoreRegPair, ty: IrType) {}
-fn compile_str_imm_simd_fp(bb: &mut BasicBlock, operand: &OpcSizeImm12RnRt, ty: IrType) {}
-fn compile_str_reg_simd_fp(bb: &mut BasicBlock, operand: &LoadStoreRegRegOffset) {}
-fn compile_stlxr(bb: &mut BasicBlock, operand: &RsRt2RnRt, ty: IrType) {}
-fn compile_stxr(bb: &mut BasicBlock, operand: &RsRt2RnRt, ty: IrType) {}
-fn compile_strb_reg(bb: &mut BasicBlock, operand: &LoadStoreRegRegOffset) {}
-fn compile_dup_general(bb: &mut BasicBlock, operand: &AdvancedSimdCopy) {}
-fn compile_add_imm(bb: &mut BasicBlock, operand: &ShImm12RnRd, ty: IrType) {}
-fn compile_adds_imm(bb: &mut BasicBlock, operand: &ShImm12RnRd, ty: IrType) {}
-fn compile_add_shifted_reg(bb: &mut BasicBlock, operand: &ShiftRmImm6RnRd, ty: IrType) {}
-fn compile_adds_shifted_reg(bb: &mut BasicBlock, operand: &ShiftRmImm6RnRd, ty: IrType) {}
-fn compile_add_ext_reg(bb: &mut BasicBlock, operand: &AddSubtractExtReg, ty: IrType) {}
-fn compile_sub_imm(bb: &mut BasicBlock, operand: &ShImm12RnRd, ty: IrType) {}
-fn compile_sub_shifted_reg(bb: &mut BasicBlock, operand: &ShiftRmImm6RnRd, ty: IrType) {}
-fn compile_subs_shifted_reg(bb: &mut BasicBlock, operand: &ShiftRmImm6RnRd, ty: IrType) {}
-fn compile_subs_ext_reg(bb: &mut BasicBlock, operand: &AddSubtractExtReg, ty: IrType) {}
-fn compile_subs_imm(bb: &mut BasicBlock, operand: &ShImm12RnRd, ty: IrType) {}
-fn compile_madd(bb: &mut BasicBlock, operand: &DataProc3Src, ty: IrType) {}
-fn compile_msub(bb: &mut BasicBlock, operand: &DataProc3Src, ty: IrType) {}
-fn compile_div(bb: &mut BasicBlock, operand: &DataProc2Src, ty: IrType) {}
-fn compile_ubfm(bb: &mut BasicBlock, operand: &Bitfield, ty: IrType) {}
-fn compile_sbfm(bb: &mut BasicBlock, operand: &Bitfield, ty: IrType) {}
-fn compile_and_imm(bb: &mut BasicBlock, operand: &LogicalImm, ty: IrType) {}
-fn compile_ands_imm(bb: &mut BasicBlock, operand: &LogicalImm, ty: IrType) {}
-fn compile_ands_shifted_reg(bb: &mut BasicBlock, operand: &ShiftRmImm6RnRd, ty: IrType) {}
-fn compile_and_shifted_reg(bb: &mut BasicBlock, operand: &ShiftRmImm6RnRd, ty: IrType) {}
-fn compile_orr_imm(bb: &mut BasicBlock, operand: &LogicalImm, ty: IrType) {}
-fn compile_orr_shifted_reg(bb: &mut BasicBlock, operand: &ShiftRmImm6RnRd, ty: IrType) {}
-fn compile_orn_shifted_reg(bb: &mut BasicBlock, operand: &ShiftRmImm6RnRd, ty: IrType) {}
-fn compile_lslv(bb: &mut BasicBlock, operand: &DataProc2Src, ty: IrType) {}
-fn compile_bl_imm(bb: &mut BasicBlock, operand: &Imm26) {}
-fn compile_b_imm(bb: &mut BasicBlock, operand: &Imm26) {}
-fn compile_br(bb: &mut BasicBlock, operand: &UncondBranchReg) {}
-fn compile_blr(bb: &mut BasicBlock, operand: &UncondBranchReg) {}
-fn compile_b_cond(bb: &mut BasicBlock, operand: &Imm19Cond) {}
-fn compile_cbz(bb: &mut BasicBlock, operand: &Imm19Rt, ty: IrType) {}
-fn compile_cbnz(bb: &mut BasicBlock, operand: &Imm19Rt, ty: IrType) {}
-fn compile_ret(bb: &mut BasicBlock, operand: &UncondBranchReg) {}
-fn compile_tbz(bb: &mut BasicBlock, operand: &B5B40Imm14Rt) {}
-fn compile_tbnz(bb: &mut BasicBlock, operand: &B5B40Imm14Rt) {}
-fn compile_ccmp_imm(bb: &mut BasicBlock, operand: &CondCmpImm, ty: IrType) {}
-fn compile_ccmp_reg(bb: &mut BasicBlock, operand: &CondCmpReg, ty: IrType) {}
-fn compile_ccmn_imm(bb: &mut BasicBlock, operand: &CondCmpImm, ty: IrType) {}
-fn compile_csel(bb: &mut BasicBlock, operand: &RmCondRnRd, ty: IrType) {}
-fn compile_csinv(bb: &mut BasicBlock, operand: &RmCondRnRd, ty: IrType) {}
-fn compile_svc(bb: &mut BasicBlock, operand: &ExceptionGen) {}
-fn compile_brk(bb: &mut BasicBlock, operand: &ExceptionGen) {}
-fn compile_mrs(bb: &mut BasicBlock, operand: &SysRegMov) {}
-fn compile_msr_reg(bb: &mut BasicBlock, operand: &SysRegMov) {}
-fn compile_msr_imm(bb: &mut BasicBlock, operand: &PstateOp) {}
+        // The narrowing shifts write the lower half, or the upper half for the "2" variants
+        let n = lanes(16, &[0x1234, 0x0ff8, -1]);
+        assert_eq!(vector_op(0x0f0c_8420, n, 0), lanes(8, &[0x23, 0xff, 0xff])); // shrn v0.8b, v1.8h, #4
+        let cpu = run_vectors(&[(0, u128::MAX), (1, n)], &[0x4f0c_8c20]); // rshrn2 v0.16b, v1.8h, #4
+        let upper = lanes(8, &[0x23, 0x00, 0x00]);
+        assert_eq!(cpu.get_u128(v(0)), upper << 64 | u64::MAX as u128);
+        let n = lanes(32, &[-0x100, 0x1_2345, 0x7f80, 0x100]);
+        let cpu = run_vectors(&[(1, n)], &[0x2f18_8420]); // sqshrun v0.4h, v1.4s, #8
+        assert_eq!(cpu.get_u128(v(0)), lanes(16, &[0, 0x123, 0x7f, 1]));
+        assert_eq!(cpu.get(Fpsr), QC);
+        let n = lanes(64, &[-1, 0xffff_ffff]);
+        let expected = lanes(32, &[0xffff_ffff, 0]);
+        assert_eq!(vector_op(0x2f20_9420, n, 0), expected); // uqshrn v0.2s, v1.2d, #32
+        let n = lanes(16, &[3, -3, 0x7fff]);
+        let expected = lanes(8, &[2, -1, 0x7f]);
+        assert_eq!(vector_op(0x0f0f_9c20, n, 0), expected); // sqrshrn v0.8b, v1.8h, #1
+
+        let n = lanes(32, &[1, 2, -3, 4]);
+        assert_eq!(vector_op(0x4f20_a420, n, 0), lanes(64, &[-3, 4])); // sshll2 v0.2d, v1.4s, #0
+        let n = lanes(8, &[1, 0xff]);
+        assert_eq!(vector_op(0x2f0f_a420, n, 0), lanes(16, &[0x80, 0x7f80])); // ushll v0.8h, v1.8b, #7
+    }
+
+    #[test]
+    fn vector_miscellaneous() {
+        let v = AArch64RegisterId::V;
+        let unary = |inst: u32, v1: u128| vector_op(inst, v1, 0);
+
+        assert_eq!(
+            unary(0x4ea0_0820, lanes(32, &[1, 2, 3, 4])),
+            lanes(32, &[2, 1, 4, 3])
+        ); // rev64 v0.4s, v1.4s
+        let n = lanes(16, &[1, 2, 3, 4, 5, 6, 7, 8]);
+        let expected = lanes(16, &[2, 1, 4, 3, 6, 5, 8, 7]);
+        assert_eq!(unary(0x6e60_0820, n), expected); // rev32 v0.8h, v1.8h
+        let n = lanes(8, &(0..16).collect::<Vec<_>>());
+        let expected: Vec<_> = (0..16).map(|i| i ^ 1).collect();
+        assert_eq!(unary(0x4e20_1820, n), lanes(8, &expected)); // rev16 v0.16b, v1.16b
+
+        let n = lanes(8, &[0, 1, 0xff, 0x0f, 0x80, 0x55]);
+        assert_eq!(unary(0x4e20_5820, n), lanes(8, &[0, 1, 8, 4, 1, 4])); // cnt v0.16b, v1.16b
+        let n = lanes(8, &[0x01, 0x80, 0x0f, 0x12]);
+        assert_eq!(unary(0x2e60_5820, n), lanes(8, &[0x80, 0x01, 0xf0, 0x48])); // rbit v0.8b, v1.8b
+        let n = lanes(32, &[0, -1, 1, 0xc000_0000]);
+        assert_eq!(unary(0x4ea0_4820, n), lanes(32, &[31, 31, 30, 1])); // cls v0.4s, v1.4s
+        let n = lanes(16, &[0, 1, 0x8000, 0x00ff]);
+        let expected = lanes(16, &[16, 15, 0, 8, 16, 16, 16, 16]);
+        assert_eq!(unary(0x6e60_4820, n), expected); // clz v0.8h, v1.8h
+
+        let n = lanes(64, &[i64::MIN, -5]);
+        assert_eq!(unary(0x4ee0_b820, n), lanes(64, &[i64::MIN, 5])); // abs v0.2d, v1.2d
+        let n = lanes(32, &[1, i32::MIN as i64, 0, -7]);
+        let expected = lanes(32, &[-1, i32::MIN as i64, 0, 7]);
+        assert_eq!(unary(0x6ea0_b820, n), expected); // neg v0.4s, v1.4s
+        let cpu = run_vectors(&[(1, lanes(8, &[-128, -5, 7]))], &[0x4e20_7820]); // sqabs v0.16b, v1.16b
+        assert_eq!(cpu.get_u128(v(0)), lanes(8, &[127, 5, 7]));
+        assert_eq!(cpu.get(Fpsr), QC);
+        let cpu = run_vectors(&[(1, lanes(32, &[i32::MIN as i64, 3]))], &[0x6ea0_7820]); // sqneg v0.4s, v1.4s
+        assert_eq!(cpu.get_u128(v(0)), lanes(32, &[i32::MAX as i64, -3]));
+        assert_eq!(cpu.get(Fpsr), QC);
+
+        let n = lanes(16, &[1, -2, 0x7fff, 0x7fff, -1, -1, 0, 5]);
+        let expected = lanes(32, &[-1, 0xfffe, -2, 5]);
+        assert_eq!(unary(0x4e60_2820, n), expected); // saddlp v0.4s, v1.8h
+        let registers = [
+            (0, lanes(64, &[10, -1])),
+            (1, lanes(32, &[0xffff_ffff, 1, 2, 3])),
+        ];
+        let cpu = run_vectors(&registers, &[0x6ea0_6820]); // uadalp v0.2d, v1.4s
+        assert_eq!(cpu.get_u128(v(0)), lanes(64, &[0x1_0000_000a, 4]));
+
+        let registers = [
+            (0, lanes(8, &[100, -100, 0, -1])),
+            (1, lanes(8, &[100, 0xff, 0x80, 1])),
+        ];
+        let cpu = run_vectors(&registers, &[0x4e20_3820]); // suqadd v0.16b, v1.16b
+        assert_eq!(cpu.get_u128(v(0)), lanes(8, &[127, 127, 127, 0]));
+        assert_eq!(cpu.get(Fpsr), QC);
+        let registers = [
+            (0, lanes(8, &[200, 5, 250])),
+            (1, lanes(8, &[100, -10, -1])),
+        ];
+        let cpu = run_vectors(&registers, &[0x6e20_3820]); // usqadd v0.16b, v1.16b
+        assert_eq!(cpu.get_u128(v(0)), lanes(8, &[0xff, 0, 249]));
+        assert_eq!(cpu.get(Fpsr), QC);
+    }
+
+    #[test]
+    fn vector_narrow_and_widen() {
+        let v = AArch64RegisterId::V;
+        let n = lanes(16, &[0x1234, -0x80]);
+
+        let cpu = run_vectors(&[(0, u128::MAX), (1, n)], &[0x0e21_2820]); // xtn v0.8b, v1.8h
+        assert_eq!(cpu.get_u128(v(0)), lanes(8, &[0x34, 0x80]));
+        let low = 0x1111_1111_1111_1111;
+        let cpu = run_vectors(&[(0, low), (1, n)], &[0x4e21_2820]); // xtn2 v0.16b, v1.8h
+        assert_eq!(cpu.get_u128(v(0)), lanes(8, &[0x34, 0x80]) << 64 | low);
+
+        let n = lanes(32, &[0x1_2345, -0x1_2345, 100, -100]);
+        let cpu = run_vectors(&[(1, n)], &[0x0e61_4820]); // sqxtn v0.4h, v1.4s
+        assert_eq!(cpu.get_u128(v(0)), lanes(16, &[0x7fff, -0x8000, 100, -100]));
+        assert_eq!(cpu.get(Fpsr), QC);
+        let cpu = run_vectors(&[(1, lanes(64, &[0x1_0000_0000, 7]))], &[0x2ea1_4820]); // uqxtn v0.2s, v1.2d
+        assert_eq!(cpu.get_u128(v(0)), lanes(32, &[0xffff_ffff, 7]));
+        assert_eq!(cpu.get(Fpsr), QC);
+        let cpu = run_vectors(&[(1, lanes(16, &[-1, 0x100, 0x7f, 0xff]))], &[0x2e21_2820]); // sqxtun v0.8b, v1.8h
+        assert_eq!(cpu.get_u128(v(0)), lanes(8, &[0, 0xff, 0x7f, 0xff]));
+        assert_eq!(cpu.get(Fpsr), QC);
+
+        let n = lanes(16, &[0, 0, 0, 0, 1, 0xffff, 2, 3]);
+        let expected = lanes(32, &[0x1_0000, 0xffff_0000, 0x2_0000, 0x3_0000]);
+        assert_eq!(vector_op(0x6e61_3820, n, 0), expected); // shll2 v0.4s, v1.8h, #16
+    }
+
+    #[test]
+    fn vector_three_different() {
+        let v = AArch64RegisterId::V;
+
+        let n = lanes(8, &[0, 0, 0, 0, 0, 0, 0, 0, -1, 2, 0x7f, -0x80]);
+        let m = lanes(8, &[0, 0, 0, 0, 0, 0, 0, 0, -1, 3, 0x7f, -0x80]);
+        let expected = lanes(16, &[-2, 5, 0xfe, -0x100]);
+        assert_eq!(vector_op(0x4e22_0020, n, m), expected); // saddl2 v0.8h, v1.16b, v2.16b
+        let (n, m) = (
+            lanes(32, &[1, 0xffff_ffff, 5, 6]),
+            lanes(16, &[0xffff, 1, 2, 3]),
+        );
+        let expected = lanes(32, &[0x1_0000, 0, 7, 9]);
+        assert_eq!(vector_op(0x2e62_1020, n, m), expected); // uaddw v0.4s, v1.4s, v2.4h
+        let (n, m) = (lanes(64, &[1, 2]), lanes(32, &[0, 0, 3, -1]));
+        assert_eq!(vector_op(0x4ea2_3020, n, m), lanes(64, &[-2, 3])); // ssubw2 v0.2d, v1.2d, v2.4s
+
+        let acc = lanes(16, &[1, 2, 3, 4]);
+        let (n, m) = (lanes(8, &[0xff, 0, 7, 9]), lanes(8, &[0, 0xff, 9, 7]));
+        let cpu = run_vectors(&[(0, acc), (1, n), (2, m)], &[0x2e22_5020]); // uabal v0.8h, v1.8b, v2.8b
+        assert_eq!(cpu.get_u128(v(0)), lanes(16, &[0x100, 0x101, 5, 6]));
+
+        // The narrow high halves keep the lower half of the destination with the "2" suffix
+        let low = 0x1111_1111_1111_1111;
+        let (n, m) = (
+            lanes(16, &[0x1280, 0x17f, -0x80, 0x7f]),
+            lanes(16, &[0, 0x80, 0, 0]),
+        );
+        let cpu = run_vectors(&[(0, low), (1, n), (2, m)], &[0x6e22_4020]); // raddhn2 v0.16b, v1.8h, v2.8h
+        assert_eq!(cpu.get_u128(v(0)), lanes(8, &[0x13, 2, 0, 0]) << 64 | low);
+        let (n, m) = (
+            lanes(32, &[0x3_0000, 0, 0x1_ffff]),
+            lanes(32, &[0x1_0000, 1, 0]),
+        );
+        let expected = lanes(16, &[2, 0xffff, 1]);
+        assert_eq!(vector_op(0x0e62_6020, n, m), expected); // subhn v0.4h, v1.4s, v2.4s
+
+        let acc = lanes(32, &[1, 0x7fff_ffff, 0, 0]);
+        let (n, m) = (lanes(16, &[3, 1, -0x8000]), lanes(16, &[-2, 1, -0x8000]));
+        let cpu = run_vectors(&[(0, acc), (1, n), (2, m)], &[0x0e62_9020]); // sqdmlal v0.4s, v1.4h, v2.4h
+        let expected = lanes(32, &[-11, 0x7fff_ffff, 0x7fff_ffff, 0]);
+        assert_eq!(cpu.get_u128(v(0)), expected);
+        assert_eq!(cpu.get(Fpsr), QC);
+        let (n, m) = (lanes(32, &[0xffff_ffff, 2]), lanes(32, &[0xffff_ffff, 3]));
+        let expected = lanes(64, &[0xffff_fffe_0000_0001_u64 as i64, 6]);
+        assert_eq!(vector_op(0x2ea2_c020, n, m), expected); // umull v0.2d, v1.2s, v2.2s
+
+        let (n, m) = (lanes(8, &[3, 0xff]), lanes(8, &[3, 0xff]));
+        assert_eq!(vector_op(0x0e22_e020, n, m), lanes(16, &[5, 0x5555])); // pmull v0.8h, v1.8b, v2.8b
+        let (n, m) = (lanes(64, &[0, i64::MIN | 1]), lanes(64, &[0, 3]));
+        let expected = 1 << 64 | 1 << 63 | 3;
+        assert_eq!(vector_op(0x4ee2_e020, n, m), expected); // pmull2 v0.1q, v1.2d, v2.2d
+
+        // The doubling multiplies have no 8-bit elements and PMULL no 16 or 32-bit elements
+        for inst in [0x0e22_d020, 0x0e62_e020] {
+            let mut cpu = TestCpu::new();
+            assert!(matches!(
+                cpu.run(&[inst]),
+                Some(Interrupt::Exception(core::Exception {
+                    kind: ExceptionKind::Undefined,
+                    ..
+                }))
+            ));
+        }
+    }
+
+    #[test]
+    fn vector_table_lookup() {
+        let v = AArch64RegisterId::V;
+        let table = |first: i64| lanes(8, &(first..first + 16).collect::<Vec<_>>());
+
+        let indices = lanes(
+            8,
+            &[0, 15, 16, 31, 32, 0xff, 5, 17, 0x80, 1, 2, 3, 4, 5, 6, 7],
+        );
+        let (n, m) = (table(0x10), table(0x20));
+        let cpu = run_vectors(&[(1, n), (2, m), (3, indices)], &[0x4e03_2020]); // tbl v0.16b, { v1.16b, v2.16b }, v3.16b
+        let expected = lanes(
+            8,
+            &[
+                0x10, 0x1f, 0x20, 0x2f, 0, 0, 0x15, 0x21, 0, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16,
+                0x17,
+            ],
+        );
+        assert_eq!(cpu.get_u128(v(0)), expected);
+
+        // The table wraps around to v0, which is also the destination
+        let indices = lanes(8, &[0, 16, 32, 47, 48, 0xff, 1, 17]);
+        let vectors = [
+            (31, table(0x40)),
+            (0, table(0x50)),
+            (1, table(0x60)),
+            (3, indices),
+        ];
+        let cpu = run_vectors(&vectors, &[0x0e03_53e0]); // tbx v0.8b, { v31.16b, v0.16b, v1.16b }, v3.8b
+        let expected = lanes(8, &[0x40, 0x50, 0x60, 0x6f, 0x54, 0x55, 0x41, 0x51]);
+        assert_eq!(cpu.get_u128(v(0)), expected);
+    }
+
+    #[test]
+    fn vector_across_lanes() {
+        let v = AArch64RegisterId::V;
+        // The scalar result in v0 of a reduction of v1, v0 starts with all ones
+        let reduce = |inst: u32, v1: u128| {
+            let cpu = run_vectors(&[(0, u128::MAX), (1, v1)], &[inst]);
+            cpu.get_u128(v(0))
+        };
+
+        let bytes: Vec<i64> = (1..=16).collect();
+        assert_eq!(reduce(0x4e31_b820, lanes(8, &bytes)), 136); // addv b0, v1.16b
+        assert_eq!(reduce(0x4e70_3820, lanes(16, &[-1; 8])), 0xffff_fff8); // saddlv s0, v1.8h
+        assert_eq!(reduce(0x2e30_3820, lanes(8, &[0xff; 8])), 0x7f8); // uaddlv h0, v1.8b
+        assert_eq!(reduce(0x4eb0_a820, lanes(32, &[-5, 3, -1, 2])), 3); // smaxv s0, v1.4s
+                                                                        // Only the lower half is reduced for the 64-bit arrangements
+        let n = lanes(8, &[1, 0x80, 0x7f, 0, 0, 0, 0, 0, 0xff]);
+        assert_eq!(reduce(0x2e30_a820, n), 0x80); // umaxv b0, v1.8b
+        assert_eq!(reduce(0x4e71_a820, lanes(16, &[5, -3, 7])), 0xfffd); // sminv h0, v1.8h
+        let n = lanes(32, &[5, 0xffff_ffff, 3, 9]);
+        assert_eq!(reduce(0x6eb1_a820, n), 3); // uminv s0, v1.4s
+        assert_eq!(reduce(0x5ef1_b820, lanes(64, &[-1, 2])), 1); // addp d0, v1.2d
+    }
+
+    #[test]
+    fn vector_permute() {
+        let (n, m) = (
+            lanes(16, &[0, 1, 2, 3, 4, 5, 6, 7]),
+            lanes(16, &[10, 11, 12, 13, 14, 15, 16, 17]),
+        );
+        let expected = lanes(16, &[0, 2, 4, 6, 10, 12, 14, 16]);
+        assert_eq!(vector_op(0x4e42_1820, n, m), expected); // uzp1 v0.8h, v1.8h, v2.8h
+
+        let (n, m) = (lanes(32, &[0, 1, 2, 3]), lanes(32, &[10, 11, 12, 13]));
+        assert_eq!(vector_op(0x4e82_5820, n, m), lanes(32, &[1, 3, 11, 13])); // uzp2 v0.4s, v1.4s, v2.4s
+        assert_eq!(vector_op(0x4e82_2820, n, m), lanes(32, &[0, 10, 2, 12])); // trn1 v0.4s, v1.4s, v2.4s
+        assert_eq!(vector_op(0x4e82_3820, n, m), lanes(32, &[0, 10, 1, 11])); // zip1 v0.4s, v1.4s, v2.4s
+        let (n, m) = (lanes(64, &[0, 1]), lanes(64, &[10, 11]));
+        assert_eq!(vector_op(0x4ec2_7820, n, m), lanes(64, &[1, 11])); // zip2 v0.2d, v1.2d, v2.2d
+        let n = lanes(8, &[0, 1, 2, 3, 4, 5, 6, 7]);
+        let m = lanes(8, &[10, 11, 12, 13, 14, 15, 16, 17]);
+        let expected = lanes(8, &[1, 11, 3, 13, 5, 15, 7, 17]);
+        assert_eq!(vector_op(0x0e02_6820, n, m), expected); // trn2 v0.8b, v1.8b, v2.8b
+
+        let bytes: Vec<i64> = (0..32).collect();
+        let (n, m) = (lanes(8, &bytes[..16]), lanes(8, &bytes[16..]));
+        assert_eq!(vector_op(0x6e02_1820, n, m), lanes(8, &bytes[3..19])); // ext v0.16b, v1.16b, v2.16b, #3
+        let expected = lanes(8, &[7, 16, 17, 18, 19, 20, 21, 22]);
+        assert_eq!(vector_op(0x2e02_3820, n, m), expected); // ext v0.8b, v1.8b, v2.8b, #7
+    }
+
+    #[test]
+    fn vector_copy() {
+        let v = AArch64RegisterId::V;
+        let halves = lanes(16, &[0, 1, -0x7fff, 3, 4, 0x55, 6, -0x7fff]);
+
+        assert_eq!(vector_op(0x4e16_0420, halves, 0), lanes(16, &[0x55; 8])); // dup v0.8h, v1.h[5]
+        let n = lanes(32, &[1, 2, 3, 0xdead]);
+        assert_eq!(vector_op(0x0e1c_0420, n, 0), lanes(32, &[0xdead; 2])); // dup v0.2s, v1.s[3]
+        let cpu = run(&[(X(1), 0x1234)], &[0x4e01_0c20]); // dup v0.16b, w1
+        assert_eq!(cpu.get_u128(v(0)), lanes(8, &[0x34; 16]));
+        let cpu = run(&[(X(1), 0x1234)], &[0x4e08_0c20]); // dup v0.2d, x1
+        assert_eq!(cpu.get_u128(v(0)), lanes(64, &[0x1234; 2]));
+
+        let mut cpu = TestCpu::new();
+        cpu.set_u128(v(1), halves | 0x80 << 120);
+        cpu.set(X(0), u64::MAX);
+        assert_eq!(cpu.run(&[0x4e1f_2c20]), None); // smov x0, v1.b[15]
+        assert_eq!(cpu.get(X(0)), 0xffff_ffff_ffff_ff80);
+        assert_eq!(cpu.run(&[0x0e06_2c20]), None); // smov w0, v1.h[1]
+        assert_eq!(cpu.get(X(0)), 1);
+        assert_eq!(cpu.run(&[0x0e1e_3c20]), None); // umov w0, v1.h[7]
+        assert_eq!(cpu.get(X(0)), 0x8001);
+        assert_eq!(cpu.run(&[0x4e18_3c20]), None); // mov x0, v1.d[1]
+        assert_eq!(cpu.get(X(0)), (halves >> 64) as u64 | 0x80 << 56);
+
+        // Inserting an element keeps the others
+        let d = lanes(32, &[1, 2, 3, 4]);
+        let mut cpu = TestCpu::new();
+        cpu.set_u128(v(0), d);
+        cpu.set(X(1), 0xaaaa_bbbb_cccc);
+        assert_eq!(cpu.run(&[0x4e14_1c20]), None); // mov v0.s[2], w1
+        assert_eq!(cpu.get_u128(v(0)), lanes(32, &[1, 2, 0xbbbb_cccc, 4]));
+
+        let n = lanes(8, &(0..16).collect::<Vec<_>>());
+        let registers = [(0, d), (1, n)];
+        let cpu = run_vectors(&registers, &[0x6e07_7420]); // mov v0.b[3], v1.b[14]
+        assert_eq!(cpu.get_u128(v(0)), d & !(0xff << 24) | 14 << 24);
+        let cpu = run_vectors(&registers, &[0x6e08_4420]); // mov v0.d[0], v1.d[1]
+        assert_eq!(cpu.get_u128(v(0)), d & !(u64::MAX as u128) | n >> 64);
+    }
+
+    #[test]
+    fn vector_modified_immediate() {
+        let v = AArch64RegisterId::V;
+        // The value of v0 after the instruction, starting with all ones
+        let run = |inst: u32| run_vectors(&[(0, u128::MAX)], &[inst]).get_u128(v(0));
+
+        assert_eq!(run(0x4f05_4560), lanes(32, &[0xab_0000; 4])); // movi v0.4s, #0xab, lsl #16
+        assert_eq!(run(0x4f00_a640), lanes(16, &[0x1200; 8])); // movi v0.8h, #0x12, lsl #8
+        assert_eq!(run(0x0f00_d640), lanes(32, &[0x12_ffff; 2])); // movi v0.2s, #0x12, msl #16
+        assert_eq!(run(0x4f03_e7e0), lanes(8, &[0x7f; 16])); // movi v0.16b, #0x7f
+        assert_eq!(run(0x2f05_e4a0), 0xff00_ff00_00ff_00ff); // movi d0, #0xff00ff0000ff00ff
+        let expected = lanes(64, &[0xff00_0000_0000_00ffu64 as i64; 2]);
+        assert_eq!(run(0x6f04_e420), expected); // movi v0.2d, #0xff000000000000ff
+        assert_eq!(run(0x6f00_2640), lanes(32, &[0xffff_edff; 4])); // mvni v0.4s, #0x12, lsl #8
+        assert_eq!(run(0x2f00_8420), lanes(16, &[0xfffe; 4])); // mvni v0.4h, #0x1
+        assert_eq!(run(0x6f00_c640), lanes(32, &[0xffff_ed00; 4])); // mvni v0.4s, #0x12, msl #8
+
+        let d = lanes(32, &[1, 2, 3, 4]);
+        let expected = lanes(32, &[0x0100_0001, 0x0100_0002, 0x0100_0003, 0x0100_0004]);
+        let cpu = run_vectors(&[(0, d)], &[0x4f00_7420]); // orr v0.4s, #0x1, lsl #24
+        assert_eq!(cpu.get_u128(v(0)), expected);
+        let cpu = run_vectors(&[(0, lanes(16, &[0x1234; 8]))], &[0x6f07_97e0]); // bic v0.8h, #0xff
+        assert_eq!(cpu.get_u128(v(0)), lanes(16, &[0x1200; 8]));
+
+        assert_eq!(run(0x4f03_f600), lanes(32, &[single(1.0) as i64; 4])); // fmov v0.4s, #1.0
+        assert_eq!(run(0x6f04_f400), lanes(64, &[double(-2.0) as i64; 2])); // fmov v0.2d, #-2.0
+        assert_eq!(run(0x0f03_f400), lanes(32, &[single(0.5) as i64; 2])); // fmov v0.2s, #0.5
+    }
+
+    /// The `esize`-bit little endian elements of `bytes`.
+    fn split_elements(bytes: &[u8], esize: usize) -> Vec<i64> {
+        bytes
+            .chunks(esize / 8)
+            .map(|chunk| {
+                let mut element = [0; 8];
+                element[..chunk.len()].copy_from_slice(chunk);
+                i64::from_le_bytes(element)
+            })
+            .collect()
+    }
+
+    /// Every `step`-th element from `start`.
+    fn every(elements: &[i64], start: usize, step: usize) -> Vec<i64> {
+        elements.iter().skip(start).step_by(step).copied().collect()
+    }
+
+    #[test]
+    fn load_multiple_structures() {
+        let v = AArch64RegisterId::V;
+        let bytes: Vec<u8> = (1..=64).collect();
+        let memory: &[(u64, &[u8])] = &[(MEM, &bytes)];
+        let vector = |range: std::ops::Range<usize>| lanes(8, &split_elements(&bytes[range], 8));
+
+        let cpu = run_with_memory(&[(X(1), MEM)], memory, &[0x4c40_7020]); // ld1 {v0.16b}, [x1]
+        assert_eq!(cpu.get_u128(v(0)), vector(0..16));
+        let cpu = run_with_memory(&[(X(1), MEM)], memory, &[0x4cdf_a820]); // ld1 {v0.4s, v1.4s}, [x1], #32
+        assert_eq!(cpu.get_u128(v(0)), vector(0..16));
+        assert_eq!(cpu.get_u128(v(1)), vector(16..32));
+        assert_eq!(cpu.get(X(1)), MEM + 32);
+
+        // The registers wrap around from v31 to v0
+        let registers = [(X(1), MEM), (X(2), 5)];
+        let cpu = run_with_memory(&registers, memory, &[0x4cc2_6c3f]); // ld1 {v31.2d, v0.2d, v1.2d}, [x1], x2
+        assert_eq!(cpu.get_u128(v(31)), vector(0..16));
+        assert_eq!(cpu.get_u128(v(0)), vector(16..32));
+        assert_eq!(cpu.get_u128(v(1)), vector(32..48));
+        assert_eq!(cpu.get(X(1)), MEM + 5);
+
+        let mut cpu = TestCpu::new();
+        cpu.map_memory(MEM, 0x1000);
+        cpu.write_memory(MEM, &bytes);
+        cpu.set_u128(v(0), u128::MAX);
+        cpu.set(X(1), MEM);
+        assert_eq!(cpu.run(&[0x0c40_7c20]), None); // ld1 {v0.1d}, [x1]
+        assert_eq!(cpu.get_u128(v(0)), vector(0..8));
+
+        // The structures are de-interleaved, element i of the structure goes to register i
+        let halves = split_elements(&bytes, 16);
+        let cpu = run_with_memory(&[(X(1), MEM)], memory, &[0x4c40_8420]); // ld2 {v0.8h, v1.8h}, [x1]
+        assert_eq!(cpu.get_u128(v(0)), lanes(16, &every(&halves[..16], 0, 2)));
+        assert_eq!(cpu.get_u128(v(1)), lanes(16, &every(&halves[..16], 1, 2)));
+
+        let bytes8 = split_elements(&bytes, 8);
+        let cpu = run_with_memory(&[(X(1), MEM)], memory, &[0x0cdf_4020]); // ld3 {v0.8b, v1.8b, v2.8b}, [x1], #24
+        for i in 0..3 {
+            let expected = lanes(8, &every(&bytes8[..24], i, 3));
+            assert_eq!(cpu.get_u128(v(i as u8)), expected);
+        }
+        assert_eq!(cpu.get(X(1)), MEM + 24);
+
+        let words = split_elements(&bytes, 32);
+        let cpu = run_with_memory(&[(X(1), MEM)], memory, &[0x4c40_0820]); // ld4 {v0.4s, v1.4s, v2.4s, v3.4s}, [x1]
+        for i in 0..4 {
+            assert_eq!(cpu.get_u128(v(i as u8)), lanes(32, &every(&words, i, 4)));
+        }
+
+        // Interleaving 1D arrangements is reserved
+        let mut cpu = TestCpu::new();
+        assert!(matches!(
+            cpu.run(&[0x0c40_8c20]), // ld2 {v0.1d, v1.1d}, [x1]
+            Some(Interrupt::Exception(core::Exception {
+                kind: ExceptionKind::Undefined,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn store_multiple_structures() {
+        let v = AArch64RegisterId::V;
+        // Store the vectors, return the cpu and the first 64 bytes of memory
+        let store = |registers: &[(AArch64RegisterId, u64)], vectors: &[u128], inst: u32| {
+            let mut cpu = TestCpu::new();
+            cpu.map_memory(MEM, 0x1000);
+            for (i, &value) in vectors.iter().enumerate() {
+                cpu.set_u128(v(i as u8), value);
+            }
+            for &(id, value) in registers {
+                cpu.set(id, value);
+            }
+            assert_eq!(cpu.run(&[inst]), None);
+            let memory = cpu.read_memory(MEM, 64);
+            (cpu, memory)
+        };
+        let sequence = |esize: usize, count: i64| {
+            let elements: Vec<i64> = (0..count).collect();
+            let bytes: Vec<u8> = (0..count as usize * esize / 8)
+                .map(|i| (elements[i / (esize / 8)] as u64 >> (i % (esize / 8) * 8)) as u8)
+                .collect();
+            bytes
+        };
+
+        let vectors = [lanes(32, &[0, 2, 4, 6]), lanes(32, &[1, 3, 5, 7])];
+        let (_, memory) = store(&[(X(1), MEM)], &vectors, 0x4c00_8820); // st2 {v0.4s, v1.4s}, [x1]
+        assert_eq!(memory[..32], sequence(32, 8));
+
+        let vectors: Vec<_> = (0..4)
+            .map(|s| lanes(8, &(0..16).map(|e| 4 * e + s).collect::<Vec<_>>()))
+            .collect();
+        let (cpu, memory) = store(&[(X(1), MEM)], &vectors, 0x4c9f_0020); // st4 {v0.16b, v1.16b, v2.16b, v3.16b}, [x1], #64
+        assert_eq!(memory, sequence(8, 64));
+        assert_eq!(cpu.get(X(1)), MEM + 64);
+
+        // Only the lower half of the 64-bit arrangements is stored
+        let vectors: Vec<_> = (0..4)
+            .map(|i| u128::MAX << 64 | lanes(8, &(8 * i..8 * i + 8).collect::<Vec<_>>()))
+            .collect();
+        let (_, memory) = store(&[(X(1), MEM)], &vectors, 0x0c00_2020); // st1 {v0.8b, v1.8b, v2.8b, v3.8b}, [x1]
+        assert_eq!(memory[..32], sequence(8, 32));
+        assert_eq!(memory[32..], [0; 32]);
+
+        let vectors: Vec<_> = (0..3).map(|s| lanes(64, &[s, 3 + s])).collect();
+        let registers = [(X(1), MEM), (X(2), (-16i64) as u64)];
+        let (cpu, memory) = store(&registers, &vectors, 0x4c82_4c20); // st3 {v0.2d, v1.2d, v2.2d}, [x1], x2
+        assert_eq!(memory[..48], sequence(64, 6));
+        assert_eq!(cpu.get(X(1)), MEM - 16);
+
+        let (_, memory) = store(&[(Sp, MEM)], &[lanes(64, &[0, 1])], 0x4c00_7fe0); // st1 {v0.2d}, [sp]
+        assert_eq!(memory[..16], sequence(64, 2));
+    }
+
+    #[test]
+    fn single_structures() {
+        let v = AArch64RegisterId::V;
+        let bytes: Vec<u8> = (1..=64).collect();
+        let halves = split_elements(&bytes, 16);
+        let words = split_elements(&bytes, 32);
+        let doubles = split_elements(&bytes, 64);
+
+        // Run with every vector register set to all ones
+        let run = |registers: &[(AArch64RegisterId, u64)], inst: u32| {
+            let mut cpu = TestCpu::new();
+            cpu.map_memory(MEM, 0x1000);
+            cpu.write_memory(MEM, &bytes);
+            for i in 0..32 {
+                cpu.set_u128(v(i), u128::MAX);
+            }
+            for &(id, value) in registers {
+                cpu.set(id, value);
+            }
+            assert_eq!(cpu.run(&[inst]), None);
+            cpu
+        };
+        // All ones with `element` in lane `index`
+        let insert = |esize: u32, index: u32, element: i64| {
+            let mask = (u128::MAX >> (128 - esize)) << (index * esize);
+            !mask | (element as u128) << (index * esize)
+        };
+
+        // The other lanes are left unchanged
+        let cpu = run(&[(X(1), MEM)], 0x4d40_0420); // ld1 {v0.b}[9], [x1]
+        assert_eq!(cpu.get_u128(v(0)), insert(8, 9, 1));
+        let cpu = run(&[(X(1), MEM)], 0x4dff_4820); // ld2 {v0.h, v1.h}[5], [x1], #4
+        assert_eq!(cpu.get_u128(v(0)), insert(16, 5, halves[0]));
+        assert_eq!(cpu.get_u128(v(1)), insert(16, 5, halves[1]));
+        assert_eq!(cpu.get(X(1)), MEM + 4);
+        let cpu = run(&[(X(1), MEM)], 0x4d40_b020); // ld3 {v0.s, v1.s, v2.s}[3], [x1]
+        for i in 0..3 {
+            assert_eq!(cpu.get_u128(v(i)), insert(32, 3, words[i as usize]));
+        }
+        let cpu = run(&[(X(1), MEM), (X(2), 3)], 0x4de2_a420); // ld4 {v0.d, v1.d, v2.d, v3.d}[1], [x1], x2
+        for i in 0..4 {
+            assert_eq!(cpu.get_u128(v(i)), insert(64, 1, doubles[i as usize]));
+        }
+        assert_eq!(cpu.get(X(1)), MEM + 3);
+
+        let cpu = run(&[(X(1), MEM)], 0x4d00_8420); // st1 {v0.d}[1], [x1]
+        assert_eq!(
+            cpu.read_memory(MEM, 9),
+            [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 9]
+        );
+        let cpu = run(&[(X(1), MEM)], 0x4dbf_1c20); // st2 {v0.b, v1.b}[15], [x1], #2
+        assert_eq!(cpu.read_memory(MEM, 3), [0xff, 0xff, 3]);
+        assert_eq!(cpu.get(X(1)), MEM + 2);
+        let cpu = run(&[(X(1), MEM)], 0x4d20_a020); // st4 {v0.s, v1.s, v2.s, v3.s}[2], [x1]
+        assert_eq!(
+            cpu.read_memory(MEM, 17),
+            [[0xff; 16].as_slice(), &[17]].concat()
+        );
+
+        // The replicating loads write every lane and clear the upper half of the 64-bit
+        // arrangements
+        let cpu = run(&[(X(1), MEM)], 0x4d40_c420); // ld1r {v0.8h}, [x1]
+        assert_eq!(cpu.get_u128(v(0)), lanes(16, &[halves[0]; 8]));
+        let cpu = run(&[(X(1), MEM)], 0x0dff_c820); // ld2r {v0.2s, v1.2s}, [x1], #8
+        assert_eq!(cpu.get_u128(v(0)), lanes(32, &[words[0]; 2]));
+        assert_eq!(cpu.get_u128(v(1)), lanes(32, &[words[1]; 2]));
+        assert_eq!(cpu.get(X(1)), MEM + 8);
+        let cpu = run(&[(X(1), MEM)], 0x4d40_e020); // ld3r {v0.16b, v1.16b, v2.16b}, [x1]
+        for i in 0..3 {
+            assert_eq!(cpu.get_u128(v(i)), lanes(8, &[i as i64 + 1; 16]));
+        }
+        let cpu = run(&[(X(1), MEM), (X(2), 0x10)], 0x0de2_ec20); // ld4r {v0.1d, v1.1d, v2.1d, v3.1d}, [x1], x2
+        for i in 0..4 {
+            assert_eq!(cpu.get_u128(v(i)), doubles[i as usize] as u64 as u128);
+        }
+        assert_eq!(cpu.get(X(1)), MEM + 0x10);
+        let cpu = run(&[(X(1), MEM)], 0x4d60_e820); // ld4r {v0.4s, v1.4s, v2.4s, v3.4s}, [x1]
+        for i in 0..4 {
+            assert_eq!(cpu.get_u128(v(i)), lanes(32, &[words[i as usize]; 4]));
+        }
+    }
+
+    /// Pack singles into a vector, the first one lowest.
+    fn single_lanes(values: &[f32]) -> u128 {
+        let values: Vec<i64> = values.iter().map(|value| value.to_bits() as i64).collect();
+        lanes(32, &values)
+    }
+
+    fn double_lanes(values: &[f64]) -> u128 {
+        let values: Vec<i64> = values.iter().map(|value| value.to_bits() as i64).collect();
+        lanes(64, &values)
+    }
+
+    #[test]
+    fn vector_float_arithmetic() {
+        let v = AArch64RegisterId::V;
+        let (a, b) = ([1.0f32, -2.0, 3.5, 0.25], [2.0f32, 0.5, -1.5, 4.0]);
+        let expected = |f: fn(f32, f32) -> f32| {
+            let values: Vec<f32> = a.iter().zip(&b).map(|(&x, &y)| f(x, y)).collect();
+            single_lanes(&values)
+        };
+
+        let (n, m) = (single_lanes(&a), single_lanes(&b));
+        assert_eq!(vector_op(0x4e22_d420, n, m), expected(|x, y| x + y)); // fadd v0.4s, v1.4s, v2.4s
+        assert_eq!(vector_op(0x6e22_fc20, n, m), expected(|x, y| x / y)); // fdiv v0.4s, v1.4s, v2.4s
+        assert_eq!(vector_op(0x6ea2_d420, n, m), expected(|x, y| (x - y).abs())); // fabd v0.4s, v1.4s, v2.4s
+        assert_eq!(vector_op(0x4ea2_f420, n, m), expected(f32::min)); // fmin v0.4s, v1.4s, v2.4s
+        assert_eq!(vector_op(0x4e22_fc20, n, m), expected(|x, y| 2.0 - x * y)); // frecps v0.4s, v1.4s, v2.4s
+                                                                                // The 64-bit arrangements clear the upper half
+        assert_eq!(vector_op(0x2e22_dc20, n, m), single_lanes(&[2.0, -1.0])); // fmul v0.2s, v1.2s, v2.2s
+
+        // The multiply-adds are fused
+        let cpu = run_vectors(
+            &[(0, single_lanes(&[1.0; 4])), (1, n), (2, m)],
+            &[0x4e22_cc20],
+        ); // fmla v0.4s, v1.4s, v2.4s
+        assert_eq!(cpu.get_u128(v(0)), expected(|x, y| x.mul_add(y, 1.0)));
+        let (n, m) = (double_lanes(&[1.0, 0.1]), double_lanes(&[0.25, 0.2]));
+        assert_eq!(
+            vector_op(0x4ee2_d420, n, m),
+            double_lanes(&[0.75, 0.1 - 0.2])
+        ); // fsub v0.2d, v1.2d, v2.2d
+        let cpu = run_vectors(
+            &[(0, double_lanes(&[1.0; 2])), (1, n), (2, m)],
+            &[0x4ee2_cc20],
+        ); // fmls v0.2d, v1.2d, v2.2d
+        assert_eq!(
+            cpu.get_u128(v(0)),
+            double_lanes(&[0.75, (-0.1f64).mul_add(0.2, 1.0)])
+        );
+
+        // An infinity times a zero is 2 for FMULX and the steps, and invalid for FMUL
+        let n = single_lanes(&[f32::INFINITY, -0.0, 3.0, 1.0]);
+        let m = single_lanes(&[0.0, f32::INFINITY, 2.0, 1.0]);
+        assert_eq!(
+            vector_op(0x4e22_dc20, n, m),
+            single_lanes(&[2.0, -2.0, 6.0, 1.0])
+        ); // fmulx v0.4s, v1.4s, v2.4s
+        let cpu = run_vectors(&[(1, n), (2, m)], &[0x6e22_dc20]); // fmul v0.4s, v1.4s, v2.4s
+        assert_eq!(cpu.get(Fpsr), IOC);
+        let (n, m) = (
+            double_lanes(&[3.0, 0.0]),
+            double_lanes(&[1.0, f64::INFINITY]),
+        );
+        assert_eq!(vector_op(0x4ee2_fc20, n, m), double_lanes(&[0.0, 1.5])); // frsqrts v0.2d, v1.2d, v2.2d
+
+        let nan = f32::NAN;
+        let (n, m) = (
+            single_lanes(&[nan, 1.0, -0.0, 5.0]),
+            single_lanes(&[2.0, nan, 0.0, 4.0]),
+        );
+        assert_eq!(
+            vector_op(0x4e22_c420, n, m),
+            single_lanes(&[2.0, 1.0, 0.0, 5.0])
+        ); // fmaxnm v0.4s, v1.4s, v2.4s
+
+        // The exceptions of every lane accumulate
+        let (n, m) = (single_lanes(&[1.0; 4]), single_lanes(&[1.0, 0.0, 2.0, 4.0]));
+        let cpu = run_vectors(&[(1, n), (2, m)], &[0x6e22_fc20]); // fdiv v0.4s, v1.4s, v2.4s
+        assert_eq!(cpu.get(Fpsr), DZC);
+
+        // 1D arrangements are reserved
+        let mut cpu = TestCpu::new();
+        assert!(matches!(
+            cpu.run(&[0x0e62_d420]), // fadd v0.1d, v1.1d, v2.1d
+            Some(Interrupt::Exception(core::Exception {
+                kind: ExceptionKind::Undefined,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn vector_float_compare() {
+        let v = AArch64RegisterId::V;
+        let nan = f32::NAN;
+
+        // Only the equality is quiet on NaNs
+        let n = single_lanes(&[1.0, nan, 2.0, -0.0]);
+        let m = single_lanes(&[1.0, nan, 3.0, 0.0]);
+        let cpu = run_vectors(&[(1, n), (2, m)], &[0x4e22_e420]); // fcmeq v0.4s, v1.4s, v2.4s
+        assert_eq!(cpu.get_u128(v(0)), lanes(32, &[-1, 0, 0, -1]));
+        assert_eq!(cpu.get(Fpsr), 0);
+        let cpu = run_vectors(&[(1, n), (2, m)], &[0x6e22_e420]); // fcmge v0.4s, v1.4s, v2.4s
+        assert_eq!(cpu.get_u128(v(0)), lanes(32, &[-1, 0, 0, -1]));
+        assert_eq!(cpu.get(Fpsr), IOC);
+        assert_eq!(vector_op(0x6ea2_e420, n, m), 0); // fcmgt v0.4s, v1.4s, v2.4s
+
+        let n = single_lanes(&[-3.0, 1.0, -1.0, 0.5]);
+        let m = single_lanes(&[2.0, -1.0, 0.5, -4.0]);
+        assert_eq!(vector_op(0x6ea2_ec20, n, m), lanes(32, &[-1, 0, -1, 0])); // facgt v0.4s, v1.4s, v2.4s
+
+        let n = single_lanes(&[-1.0, 0.0, 2.0, nan]);
+        assert_eq!(vector_op(0x4ea0_e820, n, 0), lanes(32, &[-1, 0, 0, 0])); // fcmlt v0.4s, v1.4s, #0.0
+        assert_eq!(vector_op(0x6ea0_c820, n, 0), lanes(32, &[0, -1, -1, 0])); // fcmge v0.4s, v1.4s, #0.0
+        let n = double_lanes(&[-0.0, 1.0]);
+        assert_eq!(vector_op(0x4ee0_d820, n, 0), lanes(64, &[-1, 0])); // fcmeq v0.2d, v1.2d, #0.0
+    }
+
+    #[test]
+    fn vector_float_pairwise_and_across_lanes() {
+        let v = AArch64RegisterId::V;
+
+        let n = single_lanes(&[1.0, 2.0, 3.0, 4.0]);
+        let m = single_lanes(&[10.0, 20.0, 30.0, 40.0]);
+        let sums = single_lanes(&[3.0, 7.0, 30.0, 70.0]);
+        assert_eq!(vector_op(0x6e22_d420, n, m), sums); // faddp v0.4s, v1.4s, v2.4s
+        let (n, m) = (double_lanes(&[1.0, 5.0]), double_lanes(&[-1.0, -7.0]));
+        assert_eq!(vector_op(0x6e62_f420, n, m), double_lanes(&[5.0, -1.0])); // fmaxp v0.2d, v1.2d, v2.2d
+
+        // The scalar results clear the rest of v0, which starts with all ones
+        let reduce = |inst: u32, v1: u128| {
+            let cpu = run_vectors(&[(0, u128::MAX), (1, v1)], &[inst]);
+            cpu.get_u128(v(0))
+        };
+        let n = single_lanes(&[1.5, -2.0, 7.0, 9.0]);
+        assert_eq!(reduce(0x7e30_d820, n), single(-0.5) as u128); // faddp s0, v1.2s
+        assert_eq!(reduce(0x7eb0_f820, n), single(-2.0) as u128); // fminp s0, v1.2s
+        let n = double_lanes(&[0.5, 0.25]);
+        assert_eq!(reduce(0x7e70_d820, n), double(0.75) as u128); // faddp d0, v1.2d
+
+        let n = single_lanes(&[3.0, f32::NAN, -1.0, 8.0]);
+        assert_eq!(reduce(0x6e30_c820, n), single(8.0) as u128); // fmaxnmv s0, v1.4s
+        assert_eq!(reduce(0x6eb0_f820, n), single(f32::NAN) as u128); // fminv s0, v1.4s
+    }
+
+    #[test]
+    fn vector_float_miscellaneous() {
+        let v = AArch64RegisterId::V;
+
+        let n = single_lanes(&[-2.5, 0.5, 1.5, -0.0]);
+        let magnitudes = single_lanes(&[2.5, 0.5, 1.5, 0.0]);
+        assert_eq!(vector_op(0x4ea0_f820, n, 0), magnitudes); // fabs v0.4s, v1.4s
+        let even = single_lanes(&[-2.0, 0.0, 2.0, -0.0]);
+        assert_eq!(vector_op(0x4e21_8820, n, 0), even); // frintn v0.4s, v1.4s
+        let away = single_lanes(&[-3.0, 1.0, 2.0, -0.0]);
+        assert_eq!(vector_op(0x6e21_8820, n, 0), away); // frinta v0.4s, v1.4s
+        assert_eq!(vector_op(0x4ea1_b820, n, 0), lanes(32, &[-2, 0, 1, 0])); // fcvtzs v0.4s, v1.4s
+        let n = double_lanes(&[-2.5, 0.5]);
+        assert_eq!(vector_op(0x4e61_9820, n, 0), double_lanes(&[-3.0, 0.0])); // frintm v0.2d, v1.2d
+        assert_eq!(vector_op(0x6ee0_f820, n, 0), double_lanes(&[2.5, -0.5])); // fneg v0.2d, v1.2d
+
+        let cpu = run_vectors(&[(1, double_lanes(&[2.5, -1.0]))], &[0x6e61_c820]); // fcvtau v0.2d, v1.2d
+        assert_eq!(cpu.get_u128(v(0)), lanes(64, &[3, 0]));
+        assert_eq!(cpu.get(Fpsr), IOC | IXC);
+        let n = lanes(32, &[-3, 0, 7, (1 << 24) + 1]);
+        let floats = single_lanes(&[-3.0, 0.0, 7.0, 16777216.0]);
+        assert_eq!(vector_op(0x4e21_d820, n, 0), floats); // scvtf v0.4s, v1.4s
+        let n = lanes(64, &[-1, 5]);
+        let floats = double_lanes(&[u64::MAX as f64, 5.0]);
+        assert_eq!(vector_op(0x6e61_d820, n, 0), floats); // ucvtf v0.2d, v1.2d
+        let cpu = run_vectors(
+            &[(1, single_lanes(&[4.0, 2.25, 0.0, -1.0]))],
+            &[0x6ea1_f820],
+        ); // fsqrt v0.4s, v1.4s
+        assert_eq!(cpu.get_u128(v(0)) as u64 as u128, single_lanes(&[2.0, 1.5]));
+        assert_eq!(cpu.get(Fpsr), IOC);
+
+        // Conversions to half and twice the width
+        let n = double_lanes(&[1.5, 0.1]);
+        assert_eq!(vector_op(0x0e61_6820, n, 0), single_lanes(&[1.5, 0.1])); // fcvtn v0.2s, v1.2d
+        let cpu = run_vectors(&[(0, u128::MAX), (1, n)], &[0x4e61_6820]); // fcvtn2 v0.4s, v1.2d
+        let upper = single_lanes(&[1.5, 0.1]) << 64;
+        assert_eq!(cpu.get_u128(v(0)), upper | u64::MAX as u128);
+        let n = double_lanes(&[1.0 + 2f64.powi(-30), 1.0]);
+        let odd = lanes(32, &[0x3f80_0001, 0x3f80_0000]);
+        assert_eq!(vector_op(0x2e61_6820, n, 0), odd); // fcvtxn v0.2s, v1.2d
+        let n = single_lanes(&[0.1, -2.0]);
+        let wide = double_lanes(&[0.1f32 as f64, -2.0]);
+        assert_eq!(vector_op(0x0e61_7820, n, 0), wide); // fcvtl v0.2d, v1.2s
+        let n = lanes(16, &[0, 0, 0, 0, 0x3c00, 0xc000, 0x3800, 0x7c00]);
+        let wide = single_lanes(&[1.0, -2.0, 0.5, f32::INFINITY]);
+        assert_eq!(vector_op(0x4e21_7820, n, 0), wide); // fcvtl2 v0.4s, v1.8h
+
+        // Fixed point conversions
+        let n = lanes(32, &[256, -128, 1, 0]);
+        let floats = single_lanes(&[1.0, -0.5, 1.0 / 256.0, 0.0]);
+        assert_eq!(vector_op(0x4f38_e420, n, 0), floats); // scvtf v0.4s, v1.4s, #8
+        let cpu = run_vectors(&[(1, double_lanes(&[1.5, -1.0]))], &[0x6f70_fc20]); // fcvtzu v0.2d, v1.2d, #16
+        assert_eq!(cpu.get_u128(v(0)), lanes(64, &[0x18000, 0]));
+        assert_eq!(cpu.get(Fpsr), IOC);
+        let n = single_lanes(&[1.25, -1.75]);
+        assert_eq!(vector_op(0x4f3f_fc20, n, 0), lanes(32, &[2, -3])); // fcvtzs v0.4s, v1.4s, #1
+
+        // Estimates
+        let n = single_lanes(&[1.0, -4.0, 0.0, f32::INFINITY]);
+        let cpu = run_vectors(&[(1, n)], &[0x4ea1_d820]); // frecpe v0.4s, v1.4s
+        let estimates = single_lanes(&[511.0 / 512.0, -511.0 / 2048.0, f32::INFINITY, 0.0]);
+        assert_eq!(cpu.get_u128(v(0)), estimates);
+        assert_eq!(cpu.get(Fpsr), DZC);
+        let n = single_lanes(&[4.0, 2.0]);
+        let estimates = single_lanes(&[511.0 / 1024.0, 361.0 / 512.0]);
+        assert_eq!(vector_op(0x2ea1_d820, n, 0), estimates); // frsqrte v0.2s, v1.2s
+        let n = lanes(32, &[0x8000_0000, 0x7fff_ffff, 0xffff_ffff, 0xc000_0000]);
+        let estimates = lanes(32, &[0xff80_0000, 0xffff_ffff, 0x8000_0000, 0xaa80_0000]);
+        assert_eq!(vector_op(0x4ea1_c820, n, 0), estimates); // urecpe v0.4s, v1.4s
+        let n = lanes(32, &[0x4000_0000, 0x3fff_ffff, 0x8000_0000, 0xffff_ffff]);
+        let estimates = lanes(32, &[0xff80_0000, 0xffff_ffff, 0xb480_0000, 0x8000_0000]);
+        assert_eq!(vector_op(0x6ea1_c820, n, 0), estimates); // ursqrte v0.4s, v1.4s
+    }
+
+    #[test]
+    fn scalar_three_same() {
+        let v = AArch64RegisterId::V;
+        // The result in v0 of a scalar instruction on v1 and v2, v0 starts with all ones
+        let scalar_op = |inst: u32, v1: u128, v2: u128| {
+            let cpu = run_vectors(&[(0, u128::MAX), (1, v1), (2, v2)], &[inst]);
+            (cpu.get_u128(v(0)), cpu.get(Fpsr))
+        };
+
+        let (n, m) = (5 | 7 << 64, u64::MAX as u128);
+        assert_eq!(scalar_op(0x5ee2_8420, n, m), (4, 0)); // add d0, d1, d2
+        let m = u64::MAX as u128 - 1;
+        assert_eq!(
+            scalar_op(0x5ee2_3420, u64::MAX as u128, m),
+            (u64::MAX as u128, 0)
+        ); // cmgt d0, d1, d2
+        let m = (-4_i64) as u64 as u128;
+        assert_eq!(scalar_op(0x7ee2_4420, 0x80, m), (0x8, 0)); // ushl d0, d1, d2
+        assert_eq!(scalar_op(0x5e22_0c20, 0x7f, 1), (0x7f, QC)); // sqadd b0, b1, b2
+        assert_eq!(scalar_op(0x5e62_b420, 0x4000, 0x4000), (0x2000, 0)); // sqdmulh h0, h1, h2
+        assert_eq!(scalar_op(0x7ea2_5c20, 0xffff_ffff, 1), (0xffff_ffff, QC)); // uqrshl s0, s1, s2
+
+        let (n, m) = (1.5_f64.to_bits() as u128, 4.0_f64.to_bits() as u128);
+        assert_eq!(scalar_op(0x7ee2_d420, n, m), (2.5_f64.to_bits() as u128, 0)); // fabd d0, d1, d2
+        let (n, m) = (2.0_f32.to_bits() as u128, 2.0_f32.to_bits() as u128);
+        assert_eq!(scalar_op(0x7e22_e420, n, m), (0xffff_ffff, 0)); // fcmge s0, s1, s2
+
+        // The operations without saturation only have the 64-bit form
+        let mut cpu = TestCpu::new();
+        assert!(matches!(
+            cpu.run(&[0x5ea2_8420]), // add with size = 2
+            Some(Interrupt::Exception(core::Exception {
+                kind: ExceptionKind::Undefined,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn scalar_two_misc() {
+        let v = AArch64RegisterId::V;
+        // The result in v0 of a scalar instruction on v1, v0 starts with all ones
+        let scalar_op = |inst: u32, v1: u128| {
+            let cpu = run_vectors(&[(0, u128::MAX), (1, v1)], &[inst]);
+            (cpu.get_u128(v(0)), cpu.get(Fpsr))
+        };
+        let double = |value: f64| value.to_bits() as u128;
+
+        let estimate = double(511.0 / 512.0);
+        assert_eq!(scalar_op(0x5ee1_d820, double(1.0)), (estimate, 0)); // frecpe d0, d1
+        let int = (-2_i64) as u64 as u128;
+        assert_eq!(scalar_op(0x5ee1_b820, double(-2.75)), (int, IXC)); // fcvtzs d0, d1
+        let float = (-3.0_f32).to_bits() as u128;
+        assert_eq!(scalar_op(0x5e21_d820, 0xffff_fffd), (float, 0)); // scvtf s0, s1
+        let odd = f32::from_bits(0x3f80_0001).to_bits() as u128;
+        assert_eq!(
+            scalar_op(0x7e61_6820, double(1.0 + 1.0 / (1u64 << 40) as f64)),
+            (odd, IXC)
+        ); // fcvtxn s0, d1
+        assert_eq!(scalar_op(0x7ee0_d820, double(-1.0)), (u64::MAX as u128, 0)); // fcmle d0, d1, #0.0
+
+        let min = i64::MIN as u64 as u128;
+        assert_eq!(scalar_op(0x5ee0_b820, min), (min, 0)); // abs d0, d1
+        assert_eq!(scalar_op(0x7ee0_b820, 5), ((-5_i64) as u64 as u128, 0)); // neg d0, d1
+        assert_eq!(scalar_op(0x5ee0_8820, 1), (u64::MAX as u128, 0)); // cmgt d0, d1, #0
+        assert_eq!(scalar_op(0x5e20_7820, 0x80), (0x7f, QC)); // sqabs b0, b1
+        assert_eq!(scalar_op(0x5e21_4820, 0x1234), (0x7f, QC)); // sqxtn b0, h1
+        assert_eq!(scalar_op(0x7e61_2820, 0xffff_ffff), (0, QC)); // sqxtun h0, s1
+        let cpu = run_vectors(&[(0, 0x7f), (1, 1)], &[0x5e20_3820]); // suqadd b0, b1
+        assert_eq!((cpu.get_u128(v(0)), cpu.get(Fpsr)), (0x7f, QC));
+
+        // FRECPX inverts the exponent, zeros get the largest finite one
+        assert_eq!(scalar_op(0x5ee1_f820, double(3.0)), (double(1.0), 0)); // frecpx d0, d1
+        assert_eq!(
+            scalar_op(0x5ee1_f820, double(0.0)),
+            (double(f64::MAX) & !0xf_ffff_ffff_ffff, 0)
+        );
+        assert_eq!(
+            scalar_op(0x5ee1_f820, double(f64::NEG_INFINITY)),
+            (double(-0.0), 0)
+        );
+        assert_eq!(
+            scalar_op(0x5ee1_f820, 0x7ff0_0000_0000_0001),
+            (0x7ff8_0000_0000_0001, IOC)
+        );
+    }
+
+    #[test]
+    fn by_element() {
+        let v = AArch64RegisterId::V;
+
+        let n = lanes(16, &[1, 2, 3, 4, 5, 6, 7, -8]);
+        let m = lanes(16, &[0, 0, 0, 0, 0, 0, 0, 3]);
+        let products = lanes(16, &[3, 6, 9, 12, 15, 18, 21, -24]);
+        assert_eq!(vector_op(0x4f72_8820, n, m), products); // mul v0.8h, v1.8h, v2.h[7]
+                                                            // M selects the upper registers for 32-bit elements
+        let vectors = [(1, lanes(32, &[1, 2, 3, 4])), (18, lanes(32, &[0, 5]))];
+        let cpu = run_vectors(&vectors, &[0x4fb2_8020]); // mul v0.4s, v1.4s, v18.s[1]
+        assert_eq!(cpu.get_u128(v(0)), lanes(32, &[5, 10, 15, 20]));
+        let vectors = [
+            (0, lanes(16, &[1; 8])),
+            (1, lanes(16, &[1, 2, 3, 4])),
+            (2, lanes(16, &[0, 0, 10])),
+        ];
+        let cpu = run_vectors(&vectors, &[0x2f62_0020]); // mla v0.4h, v1.4h, v2.h[2]
+        assert_eq!(cpu.get_u128(v(0)), lanes(16, &[11, 21, 31, 41]));
+
+        let n = lanes(32, &[i32::MIN as i64, 0x4000_0000, -2, 0]);
+        let m = lanes(32, &[0, i32::MIN as i64]);
+        let cpu = run_vectors(&[(1, n), (2, m)], &[0x4fa2_c020]); // sqdmulh v0.4s, v1.4s, v2.s[1]
+        let high = lanes(32, &[i32::MAX as i64, -0x4000_0000, 2, 0]);
+        assert_eq!(cpu.get_u128(v(0)), high);
+        assert_eq!(cpu.get(Fpsr), QC);
+
+        // The scalar forms only use the lowest element and clear the rest of the register
+        let vectors = [
+            (0, u128::MAX),
+            (1, lanes(32, &[0x4000_0000, 99])),
+            (2, lanes(32, &[0, 0x4000_0000])),
+        ];
+        let cpu = run_vectors(&vectors, &[0x5fa2_c020]); // sqdmulh s0, s1, v2.s[1]
+        assert_eq!(cpu.get_u128(v(0)), 0x2000_0000);
+        let vectors = [(1, 0x4000), (2, lanes(16, &[0, 0, 0, 1]))];
+        let cpu = run_vectors(&vectors, &[0x5f72_d020]); // sqrdmulh h0, h1, v2.h[3]
+        assert_eq!(cpu.get_u128(v(0)), 1);
+
+        // Long multiplies
+        let n = lanes(16, &[1, -2, 3, 4, 100, 100, 100, 100]);
+        let m = lanes(16, &[0, -1000]);
+        let products = lanes(32, &[-1000, 2000, -3000, -4000]);
+        assert_eq!(vector_op(0x0f52_a020, n, m), products); // smull v0.4s, v1.4h, v2.h[1]
+        let n = lanes(32, &[1, 2, 0xffff_ffff, 3]);
+        let m = lanes(32, &[0, 0, 0, 0xffff_ffff]);
+        let products = lanes(64, &[0xffff_fffe_0000_0001u64 as i64, 0x2_ffff_fffd]);
+        assert_eq!(vector_op(0x6fa2_a820, n, m), products); // umull2 v0.2d, v1.4s, v2.s[3]
+        let vectors = [
+            (0, lanes(64, &[10, -10])),
+            (1, lanes(32, &[-3, 4])),
+            (2, lanes(32, &[0, 5])),
+        ];
+        let cpu = run_vectors(&vectors, &[0x0fa2_2020]); // smlal v0.2d, v1.2s, v2.s[1]
+        assert_eq!(cpu.get_u128(v(0)), lanes(64, &[-5, 10]));
+        let vectors = [
+            (0, lanes(32, &[100; 4])),
+            (1, lanes(16, &[1, 2, 3, 0xffff])),
+            (2, 2),
+        ];
+        let cpu = run_vectors(&vectors, &[0x2f42_6020]); // umlsl v0.4s, v1.4h, v2.h[0]
+        assert_eq!(cpu.get_u128(v(0)), lanes(32, &[98, 96, 94, 100 - 0x1_fffe]));
+
+        // The doubling long multiplies saturate the product and the sum
+        let n = lanes(16, &[i16::MIN as i64, 3]);
+        let m = lanes(16, &[0, 0, 0, 0, 0, i16::MIN as i64]);
+        let cpu = run_vectors(&[(1, n), (2, m)], &[0x0f52_b820]); // sqdmull v0.4s, v1.4h, v2.h[5]
+        assert_eq!(
+            cpu.get_u128(v(0)),
+            lanes(32, &[i32::MAX as i64, -196608, 0, 0])
+        );
+        assert_eq!(cpu.get(Fpsr), QC);
+        let vectors = [
+            (0, lanes(64, &[i64::MAX, 5])),
+            (1, lanes(32, &[0, 0, 1, 2])),
+            (2, 3),
+        ];
+        let cpu = run_vectors(&vectors, &[0x4f82_3020]); // sqdmlal2 v0.2d, v1.4s, v2.s[0]
+        assert_eq!(cpu.get_u128(v(0)), lanes(64, &[i64::MAX, 17]));
+        assert_eq!(cpu.get(Fpsr), QC);
+        let vectors = [(1, 3), (2, lanes(16, &[0, -4]))];
+        let cpu = run_vectors(&vectors, &[0x5f52_b020]); // sqdmull s0, h1, v2.h[1]
+        assert_eq!(cpu.get_u128(v(0)), 0xffff_ffe8);
+        let vectors = [
+            (0, u128::MAX << 64 | 100),
+            (1, -7i32 as u32 as u128),
+            (2, lanes(32, &[0, 0, 0, 3])),
+        ];
+        let cpu = run_vectors(&vectors, &[0x5fa2_3820]); // sqdmlal d0, s1, v2.s[3]
+        assert_eq!(cpu.get_u128(v(0)), 58);
+
+        // Floating-point
+        let n = single_lanes(&[1.0, 2.0, 3.0, 4.0]);
+        let m = single_lanes(&[0.0, 0.0, 0.0, 0.5]);
+        let products = single_lanes(&[0.5, 1.0, 1.5, 2.0]);
+        assert_eq!(vector_op(0x4fa2_9820, n, m), products); // fmul v0.4s, v1.4s, v2.s[3]
+        let vectors = [
+            (1, double_lanes(&[1.5, -2.0])),
+            (18, double_lanes(&[0.0, 4.0])),
+        ];
+        let cpu = run_vectors(&vectors, &[0x4fd2_9820]); // fmul v0.2d, v1.2d, v18.d[1]
+        assert_eq!(cpu.get_u128(v(0)), double_lanes(&[6.0, -8.0]));
+        let vectors = [
+            (0, single_lanes(&[1.0; 4])),
+            (1, single_lanes(&[2.0, 3.0])),
+            (2, single_lanes(&[0.0, 10.0])),
+        ];
+        let cpu = run_vectors(&vectors, &[0x0fa2_1020]); // fmla v0.2s, v1.2s, v2.s[1]
+        assert_eq!(cpu.get_u128(v(0)), single_lanes(&[21.0, 31.0]));
+        let vectors = [
+            (0, single_lanes(&[10.0; 4])),
+            (1, single_lanes(&[1.0, 2.0, 3.0, 4.0])),
+            (2, single_lanes(&[2.0])),
+        ];
+        let cpu = run_vectors(&vectors, &[0x4f82_5020]); // fmls v0.4s, v1.4s, v2.s[0]
+        assert_eq!(cpu.get_u128(v(0)), single_lanes(&[8.0, 6.0, 4.0, 2.0]));
+        let n = single_lanes(&[f32::INFINITY, f32::NEG_INFINITY, 2.0, 0.0]);
+        let products = single_lanes(&[2.0, -2.0, 0.0, 0.0]);
+        assert_eq!(vector_op(0x6f82_9820, n, 0), products); // fmulx v0.4s, v1.4s, v2.s[2]
+
+        let vectors = [
+            (0, u128::MAX << 64 | double(1.0) as u128),
+            (1, double(2.0) as u128),
+            (2, double_lanes(&[0.0, 3.0])),
+        ];
+        let cpu = run_vectors(&vectors, &[0x5fc2_1820]); // fmla d0, d1, v2.d[1]
+        assert_eq!(cpu.get_u128(v(0)), double(7.0) as u128);
+        let vectors = [
+            (1, single(1.5) as u128),
+            (2, single_lanes(&[0.0, 0.0, 0.0, -2.0])),
+        ];
+        let cpu = run_vectors(&vectors, &[0x5fa2_9820]); // fmul s0, s1, v2.s[3]
+        assert_eq!(cpu.get_u128(v(0)), single(-3.0) as u128);
+        let vectors = [(1, 0), (2, double(f64::INFINITY) as u128)];
+        let cpu = run_vectors(&vectors, &[0x7fc2_9020]); // fmulx d0, d1, v2.d[0]
+        assert_eq!(cpu.get_u128(v(0)), double(2.0) as u128);
+
+        // The vector forms have no 1D arrangement
+        let mut cpu = TestCpu::new();
+        assert!(matches!(
+            cpu.run(&[0x0fd2_9820]), // fmul v0.1d, v1.1d, v18.d[1]
+            Some(Interrupt::Exception(core::Exception {
+                kind: ExceptionKind::Undefined,
+                ..
+            }))
+        ));
+    }
+}
@@ -1,9 +1,12 @@
 use core::{
-    ir::{BasicBlock, IrConstant, IrInst, IrType, IrValue},
-    Architecture, RegisterId,
+    ir::{
+        float_control, float_status, BasicBlock, FloatOp, IrConstant, IrInst, IrIntrinsic,
+        IrOrdering, IrType, IrValue, TypeOf,
+    },
+    Architecture, Exception, ExceptionKind, Interrupt, RegisterId,
 };
 
-use super::AArch64Architecture;
+use super::{AArch64Architecture, AArch64RegisterId};
 
 pub fn gen_move_pc(bb: &mut BasicBlock) {
     bb.push_inst(IrInst::Add {
@@ -12,3 +15,542 @@ pub fn gen_move_pc(bb: &mut BasicBlock) {
         rhs: IrValue::Constant(IrConstant::U64(4)),
     });
 }
+
+/// Exception classes of the syndrome register (ESR_ELx.EC)
+pub mod exception_class {
+    pub const UNKNOWN: u64 = 0b000000;
+    pub const SVC: u64 = 0b010101;
+    pub const HVC: u64 = 0b010110;
+    pub const SMC: u64 = 0b010111;
+    /// Instruction abort taken to a higher exception level
+    pub const INSTRUCTION_ABORT_LOWER: u64 = 0b100000;
+    /// Instruction abort taken without a change of exception level
+    pub const INSTRUCTION_ABORT: u64 = 0b100001;
+    /// Data abort taken to a higher exception level
+    pub const DATA_ABORT_LOWER: u64 = 0b100100;
+    /// Data abort taken without a change of exception level
+    pub const DATA_ABORT: u64 = 0b100101;
+    pub const BRK: u64 = 0b111100;
+}
+
+/// Build the syndrome of a 32-bit instruction from its exception class and ISS.
+pub fn syndrome(ec: u64, iss: u64) -> u64 {
+    const IL: u64 = 1 << 25;
+    (ec << 26) | IL | iss
+}
+
+pub fn gen_exception(bb: &mut BasicBlock, kind: ExceptionKind, syndrome: u64) {
+    gen_interrupt(bb, Interrupt::Exception(Exception { kind, syndrome }));
+}
+
+pub fn gen_interrupt(bb: &mut BasicBlock, interrupt: Interrupt) {
+    bb.push_inst(IrInst::Interrupt {
+        cond: IrValue::Constant(IrConstant::Bool(true)),
+        interrupt,
+    });
+}
+
+/// Generate the fence of a DMB/DSB from the CRm field of the barrier.
+pub fn gen_barrier(bb: &mut BasicBlock, crm: u8) {
+    // CRm<1:0> selects the access types: 0b01 reads, 0b10 writes, 0b11 all.
+    let ordering = match crm & 0b11 {
+        0b01 => IrOrdering::Acquire,
+        0b10 => IrOrdering::Release,
+        _ => IrOrdering::SeqCst,
+    };
+
+    bb.push_inst(IrInst::Fence { ordering });
+}
+
+pub fn gen_undefined(bb: &mut BasicBlock) {
+    gen_exception(
+        bb,
+        ExceptionKind::Undefined,
+        syndrome(exception_class::UNKNOWN, 0),
+    );
+    gen_move_pc(bb);
+}
+
+/// Raise the exception of an undefined instruction if `cond`, a value that is 0 or 1, is set.
+pub fn gen_undefined_if(bb: &mut BasicBlock, cond: IrValue) {
+    let cond = gen_zext(bb, cond, IrType::Bool);
+    bb.push_inst(IrInst::Interrupt {
+        cond,
+        interrupt: Interrupt::Exception(Exception {
+            kind: ExceptionKind::Undefined,
+            syndrome: syndrome(exception_class::UNKNOWN, 0),
+        }),
+    });
+}
+
+/// Bit positions of the condition flags in the NZCV register
+pub mod nzcv {
+    pub const N: u64 = 31;
+    pub const Z: u64 = 30;
+    pub const C: u64 = 29;
+    pub const V: u64 = 28;
+}
+
+/// Bit positions of the fields in the PSTATE register, where they sit in SPSR_ELx. The
+/// condition flags live in the NZCV register, so SPSR_ELx is the two ORed together.
+pub mod pstate {
+    /// Stack pointer select, SP_ELx when set
+    pub const SP: u64 = 0;
+    /// Exception level, two bits
+    pub const EL: u64 = 2;
+    /// Execution state, always clear for AArch64
+    pub const NRW: u64 = 4;
+    pub const F: u64 = 6;
+    pub const I: u64 = 7;
+    pub const A: u64 = 8;
+    pub const D: u64 = 9;
+    /// Branch target type, two bits
+    pub const BTYPE: u64 = 10;
+    pub const SSBS: u64 = 12;
+    /// Illegal execution state
+    pub const IL: u64 = 20;
+    /// Software step
+    pub const SS: u64 = 21;
+    pub const PAN: u64 = 22;
+    pub const UAO: u64 = 23;
+    pub const DIT: u64 = 24;
+    pub const TCO: u64 = 25;
+}
+
+/// The current exception level from PSTATE.EL, as a U64.
+pub fn gen_current_el(bb: &mut BasicBlock) -> IrValue {
+    let pstate = reg(AArch64RegisterId::Pstate, IrType::U64);
+    let el = gen_shr(bb, pstate, imm(IrType::U64, pstate::EL));
+    gen_and(bb, el, imm(IrType::U64, 0b11))
+}
+
+/// The register `id` accessed through its view with the width of `ty`.
+pub fn reg(id: AArch64RegisterId, ty: IrType) -> IrValue {
+    IrValue::Register(ty, id.view(ty).raw())
+}
+
+/// A constant of type `ty` from its raw bits.
+pub fn imm(ty: IrType, bits: u64) -> IrValue {
+    IrValue::Constant(IrConstant::from_bits(ty, bits as u128))
+}
+
+/// Like [`imm`] but also for the 128-bit integers, which have no constants and get the
+/// zero-extended bits instead.
+pub fn gen_imm(bb: &mut BasicBlock, ty: IrType, bits: u64) -> IrValue {
+    match ty {
+        IrType::U128 | IrType::I128 => gen_zext(bb, imm(IrType::U64, bits), ty),
+        _ => imm(ty, bits),
+    }
+}
+
+/// The unsigned integer type of `bits` bits.
+pub fn uint(bits: u64) -> IrType {
+    match bits {
+        8 => IrType::U8,
+        16 => IrType::U16,
+        32 => IrType::U32,
+        64 => IrType::U64,
+        128 => IrType::U128,
+        _ => unreachable!("no integer type has {bits} bits"),
+    }
+}
+
+/// The signed integer type with the width of `ty`.
+pub fn signed(ty: IrType) -> IrType {
+    match ty {
+        IrType::U8 | IrType::I8 => IrType::I8,
+        IrType::U16 | IrType::I16 => IrType::I16,
+        IrType::U32 | IrType::I32 => IrType::I32,
+        IrType::U64 | IrType::I64 => IrType::I64,
+        IrType::U128 | IrType::I128 => IrType::I128,
+        _ => unreachable!("{ty:?} is not an integer type"),
+    }
+}
+
+pub fn bits(ty: IrType) -> u64 {
+    ty.size_in_bytes() as u64 * 8
+}
+
+/// Push the instruction built by `inst` on a new variable of type `ty` and return the variable.
+pub fn gen_value(bb: &mut BasicBlock, ty: IrType, inst: impl FnOnce(IrValue) -> IrInst) -> IrValue {
+    let dst = bb.new_variable(ty);
+    bb.push_inst(inst(dst));
+    dst
+}
+
+pub fn gen_assign(bb: &mut BasicBlock, dst: IrValue, src: IrValue) {
+    bb.push_inst(IrInst::Assign { dst, src });
+}
+
+macro_rules! gen_binary {
+    ($($name:ident => $inst:ident,)*) => {
+        $(
+            pub fn $name(bb: &mut BasicBlock, lhs: IrValue, rhs: IrValue) -> IrValue {
+                gen_value(bb, lhs.ty(), |dst| IrInst::$inst { dst, lhs, rhs })
+            }
+        )*
+    };
+}
+
+gen_binary! {
+    gen_add => Add,
+    gen_sub => Sub,
+    gen_mul => Mul,
+    gen_div => Div,
+    gen_and => BitAnd,
+    gen_or => BitOr,
+    gen_xor => BitXor,
+    gen_shl => Shl,
+    gen_shr => Shr,
+}
+
+pub fn gen_not(bb: &mut BasicBlock, src: IrValue) -> IrValue {
+    gen_value(bb, src.ty(), |dst| IrInst::BitNot { dst, src })
+}
+
+pub fn gen_neg(bb: &mut BasicBlock, src: IrValue) -> IrValue {
+    gen_value(bb, src.ty(), |dst| IrInst::Neg { dst, src })
+}
+
+/// Zero extend or truncate `src` to `ty`.
+pub fn gen_zext(bb: &mut BasicBlock, src: IrValue, ty: IrType) -> IrValue {
+    gen_value(bb, ty, |dst| IrInst::ZextCast { dst, src })
+}
+
+/// Sign extend or truncate `src` to `ty`.
+pub fn gen_sext(bb: &mut BasicBlock, src: IrValue, ty: IrType) -> IrValue {
+    gen_value(bb, ty, |dst| IrInst::SextCast { dst, src })
+}
+
+/// Shift `value` by `amount` with the shift type of the instruction encodings: 0b00 LSL,
+/// 0b01 LSR, 0b10 ASR and 0b11 ROR.
+///
+/// The amount is taken modulo the width of `value`, like the variable shift instructions do.
+pub fn gen_shift(bb: &mut BasicBlock, shift: u8, value: IrValue, amount: IrValue) -> IrValue {
+    let ty = value.ty();
+
+    match shift {
+        0b00 => gen_shl(bb, value, amount),
+        0b01 => gen_shr(bb, value, amount),
+        0b10 => {
+            let value = gen_zext(bb, value, signed(ty));
+            let amount = gen_zext(bb, amount, signed(ty));
+            let shifted = gen_shr(bb, value, amount);
+            gen_zext(bb, shifted, ty)
+        }
+        0b11 => {
+            let right = gen_shr(bb, value, amount);
+            let left_amount = gen_sub(bb, imm(ty, bits(ty)), amount);
+            let left = gen_shl(bb, value, left_amount);
+            gen_or(bb, right, left)
+        }
+        _ => unreachable!("invalid shift type {shift}"),
+    }
+}
+
+/// The most significant bit of `value`, as 0 or 1 of the same type.
+pub fn gen_msb(bb: &mut BasicBlock, value: IrValue) -> IrValue {
+    let ty = value.ty();
+    let amount = gen_imm(bb, ty, bits(ty) - 1);
+    gen_shr(bb, value, amount)
+}
+
+/// 1 if `value` is zero and 0 otherwise, of the same type.
+pub fn gen_is_zero(bb: &mut BasicBlock, value: IrValue) -> IrValue {
+    // Either a value or its negation has the top bit set, unless the value is zero
+    let negated = gen_neg(bb, value);
+    let either = gen_or(bb, value, negated);
+    let non_zero = gen_msb(bb, either);
+    let one = gen_imm(bb, value.ty(), 1);
+    gen_xor(bb, non_zero, one)
+}
+
+/// Set NZCV from flags of the same type that are 0 or 1 each.
+pub fn gen_set_nzcv(bb: &mut BasicBlock, n: IrValue, z: IrValue, c: IrValue, v: IrValue) {
+    let ty = n.ty();
+
+    let mut flags = imm(ty, 0);
+    for (flag, position) in [(n, nzcv::N), (z, nzcv::Z), (c, nzcv::C), (v, nzcv::V)] {
+        let flag = gen_shl(bb, flag, imm(ty, position));
+        flags = gen_or(bb, flags, flag);
+    }
+
+    bb.push_inst(IrInst::ZextCast {
+        dst: reg(AArch64RegisterId::Nzcv, IrType::U64),
+        src: flags,
+    });
+}
+
+/// Set N and Z from `result` and clear C and V, as the logical instructions do.
+pub fn gen_set_nzcv_logical(bb: &mut BasicBlock, result: IrValue) {
+    let ty = result.ty();
+
+    let n = gen_msb(bb, result);
+    let z = gen_is_zero(bb, result);
+    gen_set_nzcv(bb, n, z, imm(ty, 0), imm(ty, 0));
+}
+
+/// The condition flag at `position` of NZCV as a U64 0 or 1.
+pub fn gen_flag(bb: &mut BasicBlock, position: u64) -> IrValue {
+    let flags = reg(AArch64RegisterId::Nzcv, IrType::U64);
+    let shifted = gen_shr(bb, flags, imm(IrType::U64, position));
+    gen_and(bb, shifted, imm(IrType::U64, 1))
+}
+
+/// The carry flag as 0 or 1 of type `ty`.
+pub fn gen_carry_flag(bb: &mut BasicBlock, ty: IrType) -> IrValue {
+    let carry = gen_flag(bb, nzcv::C);
+    gen_zext(bb, carry, ty)
+}
+
+/// `lhs + rhs + carry`, the AddWithCarry of the architecture. NZCV is updated when
+/// `set_flags` is set.
+///
+/// Subtraction is `lhs + !rhs + 1`.
+pub fn gen_add_with_carry(
+    bb: &mut BasicBlock,
+    lhs: IrValue,
+    rhs: IrValue,
+    carry: IrValue,
+    set_flags: bool,
+) -> IrValue {
+    let partial = gen_add(bb, lhs, rhs);
+    let result = gen_add(bb, partial, carry);
+
+    if set_flags {
+        let n = gen_msb(bb, result);
+        let z = gen_is_zero(bb, result);
+
+        // The carry out of the top bit is set when both inputs have the top bit, or one of them
+        // has it and the result doesn't.
+        let both = gen_and(bb, lhs, rhs);
+        let either = gen_or(bb, lhs, rhs);
+        let not_result = gen_not(bb, result);
+        let either_lost = gen_and(bb, either, not_result);
+        let carries = gen_or(bb, both, either_lost);
+        let c = gen_msb(bb, carries);
+
+        // Signed overflow when the inputs have the same sign and the result has the other one
+        let lhs_changed = gen_xor(bb, lhs, result);
+        let rhs_changed = gen_xor(bb, rhs, result);
+        let overflows = gen_and(bb, lhs_changed, rhs_changed);
+        let v = gen_msb(bb, overflows);
+
+        gen_set_nzcv(bb, n, z, c, v);
+    }
+
+    result
+}
+
+/// Decode the masks of the logical immediate and bitfield instructions, the DecodeBitMasks of
+/// the architecture. Returns `(wmask, tmask)` or `None` for a reserved encoding.
+pub fn decode_bit_masks(
+    n: u8,
+    imms: u8,
+    immr: u8,
+    immediate: bool,
+    width: u64,
+) -> Option<(u64, u64)> {
+    fn ones(len: u64) -> u64 {
+        match len {
+            64.. => u64::MAX,
+            len => (1 << len) - 1,
+        }
+    }
+
+    // The element size is given by the highest set bit of N:NOT(imms)
+    let combined = ((n as u64 & 1) << 6) | (!imms as u64 & 0x3f);
+    let len = combined.checked_ilog2()? as u64;
+    let esize = 1 << len;
+    if len < 1 || esize > width {
+        return None;
+    }
+
+    let levels = esize - 1;
+    let s = imms as u64 & levels;
+    let r = immr as u64 & levels;
+    if immediate && s == levels {
+        return None;
+    }
+
+    let welem = ones(s + 1);
+    let telem = ones((s.wrapping_sub(r) & levels) + 1);
+    let welem = match r {
+        0 => welem,
+        r => ((welem >> r) | (welem << (esize - r))) & ones(esize),
+    };
+
+    let replicate = |elem: u64| (0..width / esize).fold(0, |mask, i| mask | elem << (i * esize));
+    Some((replicate(welem), replicate(telem)))
+}
+
+/// Sign extend the `width` bits wide immediate field `value` to 64 bits.
+pub fn sign_extend(value: u64, width: u32) -> u64 {
+    let shift = 64 - width;
+    (((value << shift) as i64) >> shift) as u64
+}
+
+/// 1 if the condition `cond` holds for the current NZCV and 0 otherwise, as a U64. This is the
+/// ConditionHolds of the architecture, shared by the conditional branches, selects and compares.
+pub fn gen_condition_holds(bb: &mut BasicBlock, cond: u8) -> IrValue {
+    let ty = IrType::U64;
+    let mut flag = |position: u64| gen_flag(bb, position);
+
+    let result = match cond >> 1 {
+        // EQ/NE, CS/CC, MI/PL, VS/VC
+        0b000 => flag(nzcv::Z),
+        0b001 => flag(nzcv::C),
+        0b010 => flag(nzcv::N),
+        0b011 => flag(nzcv::V),
+        // HI/LS: C set and Z clear
+        0b100 => {
+            let (c, z) = (flag(nzcv::C), flag(nzcv::Z));
+            let not_z = gen_xor(bb, z, imm(ty, 1));
+            gen_and(bb, c, not_z)
+        }
+        // GE/LT: N equal to V
+        0b101 => {
+            let (n, v) = (flag(nzcv::N), flag(nzcv::V));
+            let differ = gen_xor(bb, n, v);
+            gen_xor(bb, differ, imm(ty, 1))
+        }
+        // GT/LE: N equal to V and Z clear
+        0b110 => {
+            let (n, v, z) = (flag(nzcv::N), flag(nzcv::V), flag(nzcv::Z));
+            let differ = gen_xor(bb, n, v);
+            let either = gen_or(bb, differ, z);
+            gen_xor(bb, either, imm(ty, 1))
+        }
+        // AL and NV always hold
+        _ => return imm(ty, 1),
+    };
+
+    match cond & 1 {
+        0b1 => gen_xor(bb, result, imm(ty, 1)),
+        _ => result,
+    }
+}
+
+/// `then` if `cond`, a U64 of 0 or 1, is set and `otherwise` if not.
+pub fn gen_select(
+    bb: &mut BasicBlock,
+    cond: IrValue,
+    then: IrValue,
+    otherwise: IrValue,
+) -> IrValue {
+    let ty = then.ty();
+    let cond = gen_zext(bb, cond, ty);
+    let mask = gen_neg(bb, cond);
+    let then = gen_and(bb, then, mask);
+    let not_mask = gen_not(bb, mask);
+    let otherwise = gen_and(bb, otherwise, not_mask);
+    gen_or(bb, then, otherwise)
+}
+
+/// Bit positions of the fields in the FPCR register
+pub mod fpcr {
+    /// Flush-to-zero of half precision
+    pub const FZ16: u64 = 19;
+    /// Rounding mode, two bits in the order of `FloatRounding`
+    pub const RMODE: u64 = 22;
+    /// Flush-to-zero of single and double precision
+    pub const FZ: u64 = 24;
+    /// Default NaN
+    pub const DN: u64 = 25;
+}
+
+/// Bit positions of the fields in the FPSR register
+pub mod fpsr {
+    /// Cumulative saturation of the integer vector instructions
+    pub const QC: u64 = 27;
+}
+
+/// Set FPSR.QC if `saturated`, a value that is 0 or 1, is set.
+pub fn gen_set_qc(bb: &mut BasicBlock, saturated: IrValue) {
+    let fpsr = reg(AArch64RegisterId::Fpsr, IrType::U64);
+    let saturated = gen_zext(bb, saturated, IrType::U64);
+    let qc = gen_shl(bb, saturated, imm(IrType::U64, fpsr::QC));
+    let result = gen_or(bb, fpsr, qc);
+    gen_assign(bb, fpsr, result);
+}
+
+/// The float control word of FPCR for an operation on floats of type `ty`.
+fn gen_float_control(bb: &mut BasicBlock, ty: IrType) -> IrValue {
+    let ty64 = IrType::U64;
+    let mut field = |position: u64, mask: u64, to: u64| {
+        let fpcr = reg(AArch64RegisterId::Fpcr, ty64);
+        let shifted = gen_shr(bb, fpcr, imm(ty64, position));
+        let field = gen_and(bb, shifted, imm(ty64, mask));
+        gen_shl(bb, field, imm(ty64, to))
+    };
+
+    let flush = match ty {
+        IrType::U16 => fpcr::FZ16,
+        _ => fpcr::FZ,
+    };
+    let rounding = field(fpcr::RMODE, 0b11, float_control::ROUNDING);
+    let flush = field(flush, 1, float_control::FLUSH_TO_ZERO);
+    let default_nan = field(fpcr::DN, 1, float_control::DEFAULT_NAN);
+
+    let control = gen_or(bb, rounding, flush);
+    gen_or(bb, control, default_nan)
+}
+
+/// Run the float operation `op` on `srcs` under the modes of FPCR and return its result of type
+/// `ty`. The exceptions it raises accumulate in FPSR, whose cumulative bits are laid out like
+/// the float status.
+///
+/// The flush-to-zero bit is taken from the format of the float operand, the source of a
+/// conversion between two formats.
+pub fn gen_float(bb: &mut BasicBlock, op: FloatOp, ty: IrType, srcs: Vec<IrValue>) -> IrValue {
+    let float_ty = match op {
+        FloatOp::FromInt { .. } => ty,
+        _ => srcs[0].ty(),
+    };
+    let control = gen_float_control(bb, float_ty);
+    let status = bb.new_variable(IrType::U64);
+    let dst = bb.new_variable(ty);
+    bb.push_inst(IrInst::Intrinsic(IrIntrinsic::Float {
+        op,
+        dst,
+        srcs,
+        control,
+        status,
+    }));
+
+    // Flushing half precision inputs does not report input denormal
+    let status = match float_ty {
+        IrType::U16 => gen_and(
+            bb,
+            status,
+            imm(IrType::U64, !(1 << float_status::INPUT_DENORMAL)),
+        ),
+        _ => status,
+    };
+    let fpsr = reg(AArch64RegisterId::Fpsr, IrType::U64);
+    let accumulated = gen_or(bb, fpsr, status);
+    gen_assign(bb, fpsr, accumulated);
+
+    dst
+}
+
+/// The raw bits of the `width` bits wide float encoded by the 8-bit immediate of FMOV, the
+/// VFPExpandImm of the architecture.
+pub fn vfp_expand_imm(imm8: u8, width: u64) -> u64 {
+    let exponent_bits = match width {
+        16 => 5,
+        32 => 8,
+        _ => 11,
+    };
+    let fraction_bits = width - exponent_bits - 1;
+    let imm8 = imm8 as u64;
+
+    // NOT(imm8<6>):Replicate(imm8<6>, E-3):imm8<5:4>
+    let b6 = (imm8 >> 6) & 1;
+    let exponent = ((b6 ^ 1) << (exponent_bits - 1))
+        | ((b6 * ((1 << (exponent_bits - 3)) - 1)) << 2)
+        | ((imm8 >> 4) & 0b11);
+    let fraction = (imm8 & 0xf) << (fraction_bits - 4);
+
+    ((imm8 >> 7) << (width - 1)) | (exponent << fraction_bits) | fraction
+}
@@ -0,0 +1,4496 @@
+use core::ir::IrType;
+use std::fmt::{self, Display, Formatter};
+
+use super::compiler::adv_simd_expand_imm;
+use super::compiler_prelude::{decode_bit_masks, sign_extend, vfp_expand_imm};
+use super::sysreg::{SysReg, SysRegEncoding};
+use super::{
+    AArch64Inst, AArch64RegisterId, AddSubtractExtReg, AdvSimdModifiedImm, AdvSimdScalarThreeSame,
+    AdvSimdScalarTwoRegMisc, AdvSimdShiftByImm, AdvSimdXIndexedElem, Bitfield, CompareAndSwapPair,
+    ExtractImm, HwImm16Rd, LdStNoAllocPairOffset, LoadStoreRegPair, LoadStoreRegRegOffset,
+    LogicalImm, OpcSizeImm12RnRt, PstateOp, QSizeRmRnRd, QSizeRnRd, RmRnRd, RsRnRt, ShImm12RnRd,
+    ShiftRmImm6RnRd, SysRegMov, SystemInstructions,
+};
+
+pub(crate) const CONDITIONS: [&str; 16] = [
+    "eq", "ne", "hs", "lo", "mi", "pl", "vs", "vc", "hi", "ls", "ge", "lt", "gt", "le", "al", "nv",
+];
+pub(crate) const SHIFTS: [&str; 4] = ["lsl", "lsr", "asr", "ror"];
+pub(crate) const EXTENDS: [&str; 8] = [
+    "uxtb", "uxth", "uxtw", "uxtx", "sxtb", "sxth", "sxtw", "sxtx",
+];
+/// The operations of the LD<op> atomics that have a ST<op> alias.
+const ATOMIC_OPS: [&str; 8] = ["add", "clr", "eor", "set", "smax", "smin", "umax", "umin"];
+
+/// An instruction with the address it was fetched from, displayed with the pc-relative targets
+/// resolved to absolute addresses like `objdump` prints them.
+pub struct Disassembly<'a> {
+    inst: &'a AArch64Inst,
+    pc: u64,
+}
+
+impl Display for Disassembly<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.inst.fmt_asm(f, Some(self.pc))
+    }
+}
+
+/// The instruction in the ARM assembly syntax with the preferred aliases, e.g. `mov x0, x1` for
+/// `orr x0, xzr, x1`. The pc-relative targets are offsets like `#-16` as the address of the
+/// instruction is unknown, `display_at` resolves them.
+impl Display for AArch64Inst {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_asm(f, None)
+    }
+}
+
+impl AArch64Inst {
+    /// The mnemonic of the instruction without its aliases, e.g. `orr` for `mov x0, x1`.
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            AArch64Inst::AddImm32(_)
+            | AArch64Inst::AddImm64(_)
+            | AArch64Inst::AddShiftedReg32(_)
+            | AArch64Inst::AddShiftedReg64(_)
+            | AArch64Inst::AddExtReg32(_)
+            | AArch64Inst::AddExtReg64(_)
+            | AArch64Inst::AddVec(_)
+            | AArch64Inst::AddScalar(_) => "add",
+            AArch64Inst::AddsImm32(_)
+            | AArch64Inst::AddsImm64(_)
+            | AArch64Inst::AddsShiftedReg32(_)
+            | AArch64Inst::AddsShiftedReg64(_)
+            | AArch64Inst::AddsExtReg32(_)
+            | AArch64Inst::AddsExtReg64(_) => "adds",
+            AArch64Inst::SubImm32(_)
+            | AArch64Inst::SubImm64(_)
+            | AArch64Inst::SubShiftedReg32(_)
+            | AArch64Inst::SubShiftedReg64(_)
+            | AArch64Inst::SubExtReg32(_)
+            | AArch64Inst::SubExtReg64(_)
+            | AArch64Inst::SubVec(_)
+            | AArch64Inst::SubScalar(_) => "sub",
+            AArch64Inst::SubsImm32(_)
+            | AArch64Inst::SubsImm64(_)
+            | AArch64Inst::SubsShiftedReg32(_)
+            | AArch64Inst::SubsShiftedReg64(_)
+            | AArch64Inst::SubsExtReg32(_)
+            | AArch64Inst::SubsExtReg64(_) => "subs",
+            AArch64Inst::AndImm32(_)
+            | AArch64Inst::AndImm64(_)
+            | AArch64Inst::AndShiftedReg32(_)
+            | AArch64Inst::AndShiftedReg64(_)
+            | AArch64Inst::AndVec(_) => "and",
+            AArch64Inst::OrrImm32(_)
+            | AArch64Inst::OrrImm64(_)
+            | AArch64Inst::OrrShiftedReg32(_)
+            | AArch64Inst::OrrShiftedReg64(_)
+            | AArch64Inst::OrrVecImmVar32(_)
+            | AArch64Inst::OrrVecImmVar16(_)
+            | AArch64Inst::OrrVecReg(_) => "orr",
+            AArch64Inst::EorImm32(_)
+            | AArch64Inst::EorImm64(_)
+            | AArch64Inst::EorShiftedReg32(_)
+            | AArch64Inst::EorShiftedReg64(_)
+            | AArch64Inst::EorVec(_) => "eor",
+            AArch64Inst::AndsImm32(_)
+            | AArch64Inst::AndsImm64(_)
+            | AArch64Inst::AndsShiftedReg32(_)
+            | AArch64Inst::AndsShiftedReg64(_) => "ands",
+            AArch64Inst::Addg(_) => "addg",
+            AArch64Inst::Subg(_) => "subg",
+            AArch64Inst::Extr32(_) | AArch64Inst::Extr64(_) => "extr",
+            AArch64Inst::Clrex(_) => "clrex",
+            AArch64Inst::DsbEncoding(_) => "dsb",
+            AArch64Inst::Dmb(_) => "dmb",
+            AArch64Inst::Isb(_) => "isb",
+            AArch64Inst::Sbfm32(_) | AArch64Inst::Sbfm64(_) => "sbfm",
+            AArch64Inst::Bfm32(_) | AArch64Inst::Bfm64(_) => "bfm",
+            AArch64Inst::Ubfm32(_) | AArch64Inst::Ubfm64(_) => "ubfm",
+            AArch64Inst::AdcVar32(_) | AArch64Inst::AdcVar64(_) => "adc",
+            AArch64Inst::AdcsVar32(_) | AArch64Inst::AdcsVar64(_) => "adcs",
+            AArch64Inst::SbcVar32(_) | AArch64Inst::SbcVar64(_) => "sbc",
+            AArch64Inst::SbcsVar32(_) | AArch64Inst::SbcsVar64(_) => "sbcs",
+            AArch64Inst::FmAddSinglePrecision(_)
+            | AArch64Inst::FmAddDoublePrecision(_)
+            | AArch64Inst::FmAddHalfPrecision(_) => "fmadd",
+            AArch64Inst::FmSubSinglePrecision(_)
+            | AArch64Inst::FmSubDoublePrecision(_)
+            | AArch64Inst::FmSubHalfPrecision(_) => "fmsub",
+            AArch64Inst::FnmAddSinglePrecision(_)
+            | AArch64Inst::FnmAddDoublePrecision(_)
+            | AArch64Inst::FnmAddHalfPrecision(_) => "fnmadd",
+            AArch64Inst::FnmSubSinglePrecision(_)
+            | AArch64Inst::FnmSubDoublePrecision(_)
+            | AArch64Inst::FnmSubHalfPrecision(_) => "fnmsub",
+            AArch64Inst::CaspVar32(_) | AArch64Inst::CaspVar64(_) => "casp",
+            AArch64Inst::CasplVar32(_) | AArch64Inst::CasplVar64(_) => "caspl",
+            AArch64Inst::CaspaVar32(_) | AArch64Inst::CaspaVar64(_) => "caspa",
+            AArch64Inst::CaspalVar32(_) | AArch64Inst::CaspalVar64(_) => "caspal",
+            AArch64Inst::StgEncoding(_) => "stg",
+            AArch64Inst::Stzgm(_) => "stzgm",
+            AArch64Inst::Ldg(_) => "ldg",
+            AArch64Inst::StzgEncoding(_) => "stzg",
+            AArch64Inst::St2gEncoding(_) => "st2g",
+            AArch64Inst::Stgm(_) => "stgm",
+            AArch64Inst::Stz2gEncoding(_) => "stz2g",
+            AArch64Inst::Ldgm(_) => "ldgm",
+            AArch64Inst::StxpVar32(_) | AArch64Inst::StxpVar64(_) => "stxp",
+            AArch64Inst::StlxpVar32(_) | AArch64Inst::StlxpVar64(_) => "stlxp",
+            AArch64Inst::LdxpVar32(_) | AArch64Inst::LdxpVar64(_) => "ldxp",
+            AArch64Inst::LdaxpVar32(_) | AArch64Inst::LdaxpVar64(_) => "ldaxp",
+            AArch64Inst::Stlurb(_) => "stlurb",
+            AArch64Inst::Ldapurb(_) => "ldapurb",
+            AArch64Inst::LdapursbVar64(_) | AArch64Inst::LdapursbVar32(_) => "ldapursb",
+            AArch64Inst::Stlurh(_) => "stlurh",
+            AArch64Inst::Ldapurh(_) => "ldapurh",
+            AArch64Inst::LdapurshVar64(_) | AArch64Inst::LdapurshVar32(_) => "ldapursh",
+            AArch64Inst::StlurVar32(_) | AArch64Inst::StlurVar64(_) => "stlur",
+            AArch64Inst::LdapurVar32(_) | AArch64Inst::LdapurVar64(_) => "ldapur",
+            AArch64Inst::Ldapursw(_) => "ldapursw",
+            AArch64Inst::StnpVar32(_)
+            | AArch64Inst::StnpSimdFPVar32(_)
+            | AArch64Inst::StnpSimdFPVar64(_)
+            | AArch64Inst::StnpVar64(_)
+            | AArch64Inst::StnpSimdFPVar128(_) => "stnp",
+            AArch64Inst::LdnpVar32(_)
+            | AArch64Inst::LdnpSimdFPVar32(_)
+            | AArch64Inst::LdnpSimdFPVar64(_)
+            | AArch64Inst::LdnpVar64(_)
+            | AArch64Inst::LdnpSimdFPVar128(_) => "ldnp",
+            AArch64Inst::Sttrb(_) => "sttrb",
+            AArch64Inst::Ldtrb(_) => "ldtrb",
+            AArch64Inst::LdtrsbVar64(_) | AArch64Inst::LdtrsbVar32(_) => "ldtrsb",
+            AArch64Inst::Sttrh(_) => "sttrh",
+            AArch64Inst::Ldtrh(_) => "ldtrh",
+            AArch64Inst::LdtrshVar64(_) | AArch64Inst::LdtrshVar32(_) => "ldtrsh",
+            AArch64Inst::SttrVar32(_) | AArch64Inst::SttrVar64(_) => "sttr",
+            AArch64Inst::LdtrVar32(_) | AArch64Inst::LdtrVar64(_) => "ldtr",
+            AArch64Inst::Ldtrsw(_) => "ldtrsw",
+            AArch64Inst::StrbImm(_)
+            | AArch64Inst::StrbRegExtReg(_)
+            | AArch64Inst::StrbRegShiftedReg(_) => "strb",
+            AArch64Inst::LdrbImm(_)
+            | AArch64Inst::LdrbRegExtReg(_)
+            | AArch64Inst::LdrbRegShiftedReg(_) => "ldrb",
+            AArch64Inst::LdrsbImm32(_)
+            | AArch64Inst::LdrsbImm64(_)
+            | AArch64Inst::LdrsbRegExtReg64(_)
+            | AArch64Inst::LdrsbRegShiftedReg64(_)
+            | AArch64Inst::LdrsbRegExtReg32(_)
+            | AArch64Inst::LdrsbRegShiftedReg32(_) => "ldrsb",
+            AArch64Inst::StrImmSimdFP8(_)
+            | AArch64Inst::StrImmSimdFP128(_)
+            | AArch64Inst::StrImmSimdFP16(_)
+            | AArch64Inst::StrImm32(_)
+            | AArch64Inst::StrImmSimdFP32(_)
+            | AArch64Inst::StrImm64(_)
+            | AArch64Inst::StrImmSimdFP64(_)
+            | AArch64Inst::StrRegSimdFP(_)
+            | AArch64Inst::StrReg32(_)
+            | AArch64Inst::StrReg64(_) => "str",
+            AArch64Inst::LdrImmSimdFP8(_)
+            | AArch64Inst::LdrImmSimdFP128(_)
+            | AArch64Inst::LdrImmSimdFP16(_)
+            | AArch64Inst::LdrImm32(_)
+            | AArch64Inst::LdrImmSimdFP32(_)
+            | AArch64Inst::LdrImm64(_)
+            | AArch64Inst::LdrImmSimdFP64(_)
+            | AArch64Inst::LdrRegSimdFP(_)
+            | AArch64Inst::LdrReg32(_)
+            | AArch64Inst::LdrReg64(_)
+            | AArch64Inst::LdrLitVar32(_)
+            | AArch64Inst::LdrLitSimdFPVar32(_)
+            | AArch64Inst::LdrLitVar64(_)
+            | AArch64Inst::LdrLitSimdFPVar64(_)
+            | AArch64Inst::LdrLitSimdFPVar128(_) => "ldr",
+            AArch64Inst::StrhImm(_) | AArch64Inst::StrhReg(_) => "strh",
+            AArch64Inst::LdrhImm(_) | AArch64Inst::LdrhReg(_) => "ldrh",
+            AArch64Inst::LdrshImm32(_)
+            | AArch64Inst::LdrshImm64(_)
+            | AArch64Inst::LdrshReg64(_)
+            | AArch64Inst::LdrshReg32(_) => "ldrsh",
+            AArch64Inst::LdrswImm(_) | AArch64Inst::LdrswReg(_) | AArch64Inst::LdrswLit(_) => {
+                "ldrsw"
+            }
+            AArch64Inst::PrfmImm(_) | AArch64Inst::PrfmReg(_) | AArch64Inst::PrfmLit(_) => "prfm",
+            AArch64Inst::Stgp(_) => "stgp",
+            AArch64Inst::Ldpsw(_) => "ldpsw",
+            AArch64Inst::Sturb(_) => "sturb",
+            AArch64Inst::Ldurb(_) => "ldurb",
+            AArch64Inst::Ldursb64(_) | AArch64Inst::Ldursb32(_) => "ldursb",
+            AArch64Inst::SturSimdFP8(_)
+            | AArch64Inst::SturSimdFP128(_)
+            | AArch64Inst::SturSimdFP16(_)
+            | AArch64Inst::Stur32(_)
+            | AArch64Inst::SturSimdFP32(_)
+            | AArch64Inst::Stur64(_)
+            | AArch64Inst::SturSimdFP64(_) => "stur",
+            AArch64Inst::LdurSimdFP8(_)
+            | AArch64Inst::LdurSimdFP128(_)
+            | AArch64Inst::LdurSimdFP16(_)
+            | AArch64Inst::Ldur32(_)
+            | AArch64Inst::LdurSimdFP32(_)
+            | AArch64Inst::Ldur64(_)
+            | AArch64Inst::LdurSimdFP64(_) => "ldur",
+            AArch64Inst::Sturh(_) => "sturh",
+            AArch64Inst::Ldurh(_) => "ldurh",
+            AArch64Inst::Ldursh64(_) | AArch64Inst::Ldursh32(_) => "ldursh",
+            AArch64Inst::Ldursw(_) => "ldursw",
+            AArch64Inst::Prefum(_) => "prfum",
+            AArch64Inst::StpVar32(_)
+            | AArch64Inst::StpSimdFPVar32(_)
+            | AArch64Inst::StpSimdFPVar64(_)
+            | AArch64Inst::StpVar64(_)
+            | AArch64Inst::StpSimdFpVar128(_) => "stp",
+            AArch64Inst::LdpVar32(_)
+            | AArch64Inst::LdpSimdFPVar32(_)
+            | AArch64Inst::LdpSimdFPVar64(_)
+            | AArch64Inst::LdpVar64(_)
+            | AArch64Inst::LdpSimdFpVar128(_) => "ldp",
+            AArch64Inst::Stxrb(_) => "stxrb",
+            AArch64Inst::Ldxrb(_) => "ldxrb",
+            AArch64Inst::Stxrh(_) => "stxrh",
+            AArch64Inst::Ldxrh(_) => "ldxrh",
+            AArch64Inst::StxrVar32(_) | AArch64Inst::StxrVar64(_) => "stxr",
+            AArch64Inst::LdxrVar32(_) | AArch64Inst::LdxrVar64(_) => "ldxr",
+            AArch64Inst::Stlxrb(_) => "stlxrb",
+            AArch64Inst::Ldaxrb(_) => "ldaxrb",
+            AArch64Inst::Stlxrh(_) => "stlxrh",
+            AArch64Inst::Ldaxrh(_) => "ldaxrh",
+            AArch64Inst::StlxrVar32(_) | AArch64Inst::StlxrVar64(_) => "stlxr",
+            AArch64Inst::LdaxrVar32(_) | AArch64Inst::LdaxrVar64(_) => "ldaxr",
+            AArch64Inst::Stlrb(_) => "stlrb",
+            AArch64Inst::Ldarb(_) => "ldarb",
+            AArch64Inst::Stlrh(_) => "stlrh",
+            AArch64Inst::Ldarh(_) => "ldarh",
+            AArch64Inst::StlrVar32(_) | AArch64Inst::StlrVar64(_) => "stlr",
+            AArch64Inst::LdarVar32(_) | AArch64Inst::LdarVar64(_) => "ldar",
+            AArch64Inst::BImm(_) | AArch64Inst::BCond(_) => "b",
+            AArch64Inst::BlImm(_) => "bl",
+            AArch64Inst::BcCond(_) => "bc",
+            AArch64Inst::Tbz(_) => "tbz",
+            AArch64Inst::Tbnz(_) => "tbnz",
+            AArch64Inst::Cbz32(_) | AArch64Inst::Cbz64(_) => "cbz",
+            AArch64Inst::Cbnz32(_) | AArch64Inst::Cbnz64(_) => "cbnz",
+            AArch64Inst::Wfet(_) => "wfet",
+            AArch64Inst::Wfit(_) => "wfit",
+            AArch64Inst::MsrImm(_) | AArch64Inst::MsrReg(_) => "msr",
+            AArch64Inst::Cfinv(_) => "cfinv",
+            AArch64Inst::Xaflag(_) => "xaflag",
+            AArch64Inst::Axflag(_) => "axflag",
+            AArch64Inst::Tstart(_) => "tstart",
+            AArch64Inst::Ttest(_) => "ttest",
+            AArch64Inst::Sys(_) => "sys",
+            AArch64Inst::Sysl(_) => "sysl",
+            AArch64Inst::Mrs(_) => "mrs",
+            AArch64Inst::Csel32(_) | AArch64Inst::Csel64(_) => "csel",
+            AArch64Inst::Csinc32(_) | AArch64Inst::Csinc64(_) => "csinc",
+            AArch64Inst::Csinv32(_) | AArch64Inst::Csinv64(_) => "csinv",
+            AArch64Inst::Csneg32(_) | AArch64Inst::Csneg64(_) => "csneg",
+            AArch64Inst::MovnVar32(_) | AArch64Inst::MovnVar64(_) => "movn",
+            AArch64Inst::MovzVar32(_) | AArch64Inst::MovzVar64(_) => "movz",
+            AArch64Inst::MovkVar32(_) | AArch64Inst::MovkVar64(_) => "movk",
+            AArch64Inst::BicShiftedReg32(_)
+            | AArch64Inst::BicShiftedReg64(_)
+            | AArch64Inst::BicVecImmVar32(_)
+            | AArch64Inst::BicVecImmVar16(_)
+            | AArch64Inst::BicVecReg(_) => "bic",
+            AArch64Inst::OrnShiftedReg32(_)
+            | AArch64Inst::OrnShiftedReg64(_)
+            | AArch64Inst::OrnVec(_) => "orn",
+            AArch64Inst::EonShiftedReg32(_) | AArch64Inst::EonShiftedReg64(_) => "eon",
+            AArch64Inst::BicsShiftedReg32(_) | AArch64Inst::BicsShiftedReg64(_) => "bics",
+            AArch64Inst::Madd32(_) | AArch64Inst::Madd64(_) => "madd",
+            AArch64Inst::Msub32(_) | AArch64Inst::Msub64(_) => "msub",
+            AArch64Inst::Smaddl(_) => "smaddl",
+            AArch64Inst::Smsubl(_) => "smsubl",
+            AArch64Inst::Smulh(_) => "smulh",
+            AArch64Inst::Umaddl(_) => "umaddl",
+            AArch64Inst::Umsubl(_) => "umsubl",
+            AArch64Inst::Umulh(_) => "umulh",
+            AArch64Inst::UdivVar32(_) | AArch64Inst::UdivVar64(_) => "udiv",
+            AArch64Inst::SdivVar32(_) | AArch64Inst::SdivVar64(_) => "sdiv",
+            AArch64Inst::LslvVar32(_) | AArch64Inst::LslvVar64(_) => "lslv",
+            AArch64Inst::LsrvVar32(_) | AArch64Inst::LsrvVar64(_) => "lsrv",
+            AArch64Inst::AsrvVar32(_) | AArch64Inst::AsrvVar64(_) => "asrv",
+            AArch64Inst::RorvVar32(_) | AArch64Inst::RorvVar64(_) => "rorv",
+            AArch64Inst::Pacga(_) => "pacga",
+            AArch64Inst::Rmif(_) => "rmif",
+            AArch64Inst::SetfVar8(_) => "setf8",
+            AArch64Inst::SetfVar16(_) => "setf16",
+            AArch64Inst::CcmnRegVar32(_)
+            | AArch64Inst::CcmnRegVar64(_)
+            | AArch64Inst::CcmnImmVar32(_)
+            | AArch64Inst::CcmnImmVar64(_) => "ccmn",
+            AArch64Inst::CcmpRegVar32(_)
+            | AArch64Inst::CcmpRegVar64(_)
+            | AArch64Inst::CcmpImmVar32(_)
+            | AArch64Inst::CcmpImmVar64(_) => "ccmp",
+            AArch64Inst::RbitVar32(_) | AArch64Inst::RbitVar64(_) | AArch64Inst::RbitVec(_) => {
+                "rbit"
+            }
+            AArch64Inst::Rev16Var32(_) | AArch64Inst::Rev16Var64(_) | AArch64Inst::Rev16Vec(_) => {
+                "rev16"
+            }
+            AArch64Inst::RevVar32(_) | AArch64Inst::RevVar64(_) => "rev",
+            AArch64Inst::ClzVar32(_) | AArch64Inst::ClzVar64(_) | AArch64Inst::ClzVec(_) => "clz",
+            AArch64Inst::ClsVar32(_) | AArch64Inst::ClsVar64(_) | AArch64Inst::ClsVec(_) => "cls",
+            AArch64Inst::Rev32(_) | AArch64Inst::Rev32Vec(_) => "rev32",
+            AArch64Inst::Br(_) => "br",
+            AArch64Inst::Blr(_) => "blr",
+            AArch64Inst::Ret(_) => "ret",
+            AArch64Inst::ERet(_) => "eret",
+            AArch64Inst::Drps(_) => "drps",
+            AArch64Inst::Hint(_) => "hint",
+            AArch64Inst::Nop => "nop",
+            AArch64Inst::Yield => "yield",
+            AArch64Inst::Wfe => "wfe",
+            AArch64Inst::Wfi => "wfi",
+            AArch64Inst::Sev => "sev",
+            AArch64Inst::Sevl => "sevl",
+            AArch64Inst::Xpaclri => "xpaclri",
+            AArch64Inst::Pacia1716Var => "pacia1716",
+            AArch64Inst::Pacib1716Var => "pacib1716",
+            AArch64Inst::Autia1716Var => "autia1716",
+            AArch64Inst::Autib1716Var => "autib1716",
+            AArch64Inst::PaciazVar => "paciaz",
+            AArch64Inst::PaciaspVar => "paciasp",
+            AArch64Inst::PacibzVar => "pacibz",
+            AArch64Inst::PacibspVar => "pacibsp",
+            AArch64Inst::AutiazVar => "autiaz",
+            AArch64Inst::AutiaspVar => "autiasp",
+            AArch64Inst::AutibzVar => "autibz",
+            AArch64Inst::AutibspVar => "autibsp",
+            AArch64Inst::Adr(_) => "adr",
+            AArch64Inst::Adrp(_) => "adrp",
+            AArch64Inst::Svc(_) => "svc",
+            AArch64Inst::Hvc(_) => "hvc",
+            AArch64Inst::Smc(_) => "smc",
+            AArch64Inst::Brk(_) => "brk",
+            AArch64Inst::Hlt(_) => "hlt",
+            AArch64Inst::TCancle(_) => "tcancel",
+            AArch64Inst::DcpS1(_) => "dcps1",
+            AArch64Inst::DcpS2(_) => "dcps2",
+            AArch64Inst::DcpS3(_) => "dcps3",
+            AArch64Inst::DupElement(_) | AArch64Inst::DupGeneral(_) => "dup",
+            AArch64Inst::Smov(_) => "smov",
+            AArch64Inst::Umov(_) => "umov",
+            AArch64Inst::InsGeneral(_) | AArch64Inst::InsElement(_) => "ins",
+            AArch64Inst::St1SingleStructureVar8(_)
+            | AArch64Inst::St1SingleStructureVar16(_)
+            | AArch64Inst::St1SingleStructureVar32(_)
+            | AArch64Inst::St1SingleStructureVar64(_)
+            | AArch64Inst::St1SingleStructurePostIndexedVar8(_)
+            | AArch64Inst::St1SingleStructurePostIndexedVar16(_)
+            | AArch64Inst::St1SingleStructurePostIndexedVar32(_)
+            | AArch64Inst::St1SingleStructurePostIndexedVar64(_)
+            | AArch64Inst::St1MulStructures4RegsVar(_)
+            | AArch64Inst::St1MulStructures3RegsVar(_)
+            | AArch64Inst::St1MulStructures1RegsVar(_)
+            | AArch64Inst::St1MulStructures2RegsVar(_)
+            | AArch64Inst::St1MulStructures4RegRegOffsetVar(_)
+            | AArch64Inst::St1MulStructures3RegRegOffsetVar(_)
+            | AArch64Inst::St1MulStructures1RegRegOffsetVar(_)
+            | AArch64Inst::St1MulStructures2RegRegOffsetVar(_)
+            | AArch64Inst::St1MulStructures4RegImmOffsetVar(_)
+            | AArch64Inst::St1MulStructures3RegImmOffsetVar(_)
+            | AArch64Inst::St1MulStructures1RegImmOffsetVar(_)
+            | AArch64Inst::St1MulStructures2RegImmOffsetVar(_) => "st1",
+            AArch64Inst::St3SingleStructureVar8(_)
+            | AArch64Inst::St3SingleStructureVar16(_)
+            | AArch64Inst::St3SingleStructureVar32(_)
+            | AArch64Inst::St3SingleStructureVar64(_)
+            | AArch64Inst::St3SingleStructurePostIndexedVar8(_)
+            | AArch64Inst::St3SingleStructurePostIndexedVar16(_)
+            | AArch64Inst::St3SingleStructurePostIndexedVar32(_)
+            | AArch64Inst::St3SingleStructurePostIndexedVar64(_)
+            | AArch64Inst::St3MulStructures(_)
+            | AArch64Inst::St3MulStructuresRegOffsetVar(_)
+            | AArch64Inst::St3MulStructuresImmOffsetVar(_) => "st3",
+            AArch64Inst::St2SingleStructureVar8(_)
+            | AArch64Inst::St2SingleStructureVar16(_)
+            | AArch64Inst::St2SingleStructureVar32(_)
+            | AArch64Inst::St2SingleStructureVar64(_)
+            | AArch64Inst::St2SingleStructurePostIndexedVar8(_)
+            | AArch64Inst::St2SingleStructurePostIndexedVar16(_)
+            | AArch64Inst::St2SingleStructurePostIndexedVar32(_)
+            | AArch64Inst::St2SingleStructurePostIndexedVar64(_)
+            | AArch64Inst::St2MulStructures(_)
+            | AArch64Inst::St2MulStructuresRegOffsetVar(_)
+            | AArch64Inst::St2MulStructuresImmOffsetVar(_) => "st2",
+            AArch64Inst::St4SingleStructureVar8(_)
+            | AArch64Inst::St4SingleStructureVar16(_)
+            | AArch64Inst::St4SingleStructureVar32(_)
+            | AArch64Inst::St4SingleStructureVar64(_)
+            | AArch64Inst::St4SingleStructurePostIndexedVar8(_)
+            | AArch64Inst::St4SingleStructurePostIndexedVar16(_)
+            | AArch64Inst::St4SingleStructurePostIndexedVar32(_)
+            | AArch64Inst::St4SingleStructurePostIndexedVar64(_)
+            | AArch64Inst::St4MulStructures(_)
+            | AArch64Inst::St4MulStructuresRegOffsetVar(_)
+            | AArch64Inst::St4MulStructuresImmOffsetVar(_) => "st4",
+            AArch64Inst::Ld1SingleStructureVar8(_)
+            | AArch64Inst::Ld1SingleStructureVar16(_)
+            | AArch64Inst::Ld1SingleStructureVar32(_)
+            | AArch64Inst::Ld1SingleStructureVar64(_)
+            | AArch64Inst::Ld1SingleStructurePostIndexedVar8(_)
+            | AArch64Inst::Ld1SingleStructurePostIndexedVar16(_)
+            | AArch64Inst::Ld1SingleStructurePostIndexedVar32(_)
+            | AArch64Inst::Ld1SingleStructurePostIndexedVar64(_)
+            | AArch64Inst::Ld1MulStructures4RegsVar(_)
+            | AArch64Inst::Ld1MulStructures3RegsVar(_)
+            | AArch64Inst::Ld1MulStructures1RegsVar(_)
+            | AArch64Inst::Ld1MulStructures2RegsVar(_)
+            | AArch64Inst::Ld1MulStructures4RegRegOffsetVar(_)
+            | AArch64Inst::Ld1MulStructures3RegRegOffsetVar(_)
+            | AArch64Inst::Ld1MulStructures1RegRegOffsetVar(_)
+            | AArch64Inst::Ld1MulStructures2RegRegOffsetVar(_)
+            | AArch64Inst::Ld1MulStructures4RegImmOffsetVar(_)
+            | AArch64Inst::Ld1MulStructures3RegImmOffsetVar(_)
+            | AArch64Inst::Ld1MulStructures1RegImmOffsetVar(_)
+            | AArch64Inst::Ld1MulStructures2RegImmOffsetVar(_) => "ld1",
+            AArch64Inst::Ld3SingleStructureVar8(_)
+            | AArch64Inst::Ld3SingleStructureVar16(_)
+            | AArch64Inst::Ld3SingleStructureVar32(_)
+            | AArch64Inst::Ld3SingleStructureVar64(_)
+            | AArch64Inst::Ld3SingleStructurePostIndexedVar8(_)
+            | AArch64Inst::Ld3SingleStructurePostIndexedVar16(_)
+            | AArch64Inst::Ld3SingleStructurePostIndexedVar32(_)
+            | AArch64Inst::Ld3SingleStructurePostIndexedVar64(_)
+            | AArch64Inst::Ld3MulStructures(_)
+            | AArch64Inst::Ld3MulStructuresRegOffsetVar(_)
+            | AArch64Inst::Ld3MulStructuresImmOffsetVar(_) => "ld3",
+            AArch64Inst::Ld1r(_) | AArch64Inst::Ld1rPostIndexed(_) => "ld1r",
+            AArch64Inst::Ld3r(_) | AArch64Inst::Ld3rPostIndexed(_) => "ld3r",
+            AArch64Inst::Ld2SingleStructureVar8(_)
+            | AArch64Inst::Ld2SingleStructureVar16(_)
+            | AArch64Inst::Ld2SingleStructureVar32(_)
+            | AArch64Inst::Ld2SingleStructureVar64(_)
+            | AArch64Inst::Ld2SingleStructurePostIndexedVar8(_)
+            | AArch64Inst::Ld2SingleStructurePostIndexedVar16(_)
+            | AArch64Inst::Ld2SingleStructurePostIndexedVar32(_)
+            | AArch64Inst::Ld2SingleStructurePostIndexedVar64(_)
+            | AArch64Inst::Ld2MulStructures(_)
+            | AArch64Inst::Ld2MulStructuresRegOffsetVar(_)
+            | AArch64Inst::Ld2MulStructuresImmOffsetVar(_) => "ld2",
+            AArch64Inst::Ld4SingleStructureVar8(_)
+            | AArch64Inst::Ld4SingleStructureVar16(_)
+            | AArch64Inst::Ld4SingleStructureVar32(_)
+            | AArch64Inst::Ld4SingleStructureVar64(_)
+            | AArch64Inst::Ld4SingleStructurePostIndexedVar8(_)
+            | AArch64Inst::Ld4SingleStructurePostIndexedVar16(_)
+            | AArch64Inst::Ld4SingleStructurePostIndexedVar32(_)
+            | AArch64Inst::Ld4SingleStructurePostIndexedVar64(_)
+            | AArch64Inst::Ld4MulStructures(_)
+            | AArch64Inst::Ld4MulStructuresRegOffsetVar(_)
+            | AArch64Inst::Ld4MulStructuresImmOffsetVar(_) => "ld4",
+            AArch64Inst::Ld2r(_) | AArch64Inst::Ld2rPostIndexed(_) => "ld2r",
+            AArch64Inst::Ld4r(_) | AArch64Inst::Ld4rPostIndexed(_) => "ld4r",
+            AArch64Inst::FcvtnsScalarSinglePrecisionTo32(_)
+            | AArch64Inst::FcvtnsScalarDoublePrecisionTo32(_)
+            | AArch64Inst::FcvtnsScalarSinglePrecisionTo64(_)
+            | AArch64Inst::FcvtnsScalarDoublePrecisionTo64(_)
+            | AArch64Inst::FcvtnsVec(_)
+            | AArch64Inst::FcvtnsScalar(_) => "fcvtns",
+            AArch64Inst::FcvtnuScalarSinglePrecisionTo32(_)
+            | AArch64Inst::FcvtnuScalarDoublePrecisionTo32(_)
+            | AArch64Inst::FcvtnuScalarSinglePrecisionTo64(_)
+            | AArch64Inst::FcvtnuScalarDoublePrecisionTo64(_)
+            | AArch64Inst::FcvtnuVec(_)
+            | AArch64Inst::FcvtnuScalar(_) => "fcvtnu",
+            AArch64Inst::ScvtfScalarInt32ToSinglePrecision(_)
+            | AArch64Inst::ScvtfScalarInt32ToDoublePrecision(_)
+            | AArch64Inst::ScvtfScalarInt64ToSinglePrecision(_)
+            | AArch64Inst::ScvtfScalarInt64ToDoublePrecision(_)
+            | AArch64Inst::ScvtfVecFixedPt(_)
+            | AArch64Inst::ScvtfVecInt(_)
+            | AArch64Inst::ScvtfScalarInt(_)
+            | AArch64Inst::ScvtfScalarFixedPt32ToSinglePrecision(_)
+            | AArch64Inst::ScvtfScalarFixedPt32ToDoublePrecision(_)
+            | AArch64Inst::ScvtfScalarFixedPt64ToSinglePrecision(_)
+            | AArch64Inst::ScvtfScalarFixedPt64ToDoublePrecision(_) => "scvtf",
+            AArch64Inst::UcvtfScalarInt32ToSinglePrecision(_)
+            | AArch64Inst::UcvtfScalarInt32ToDoublePrecision(_)
+            | AArch64Inst::UcvtfScalarInt64ToSinglePrecision(_)
+            | AArch64Inst::UcvtfScalarInt64ToDoublePrecision(_)
+            | AArch64Inst::UcvtfVecFixedPt(_)
+            | AArch64Inst::UcvtfVecInt(_)
+            | AArch64Inst::UcvtfScalarInt(_)
+            | AArch64Inst::UcvtfScalarFixedPt32ToSinglePrecision(_)
+            | AArch64Inst::UcvtfScalarFixedPt32ToDoublePrecision(_)
+            | AArch64Inst::UcvtfScalarFixedPt64ToSinglePrecision(_)
+            | AArch64Inst::UcvtfScalarFixedPt64ToDoublePrecision(_) => "ucvtf",
+            AArch64Inst::FcvtasScalarSinglePrecisionTo32(_)
+            | AArch64Inst::FcvtasScalarDoublePrecisionTo32(_)
+            | AArch64Inst::FcvtasScalarSinglePrecisionTo64(_)
+            | AArch64Inst::FcvtasScalarDoublePrecisionTo64(_)
+            | AArch64Inst::FcvtasVec(_)
+            | AArch64Inst::FcvtasScalar(_) => "fcvtas",
+            AArch64Inst::FcvtauScalarSinglePrecisionTo32(_)
+            | AArch64Inst::FcvtauScalarDoublePrecisionTo32(_)
+            | AArch64Inst::FcvtauScalarSinglePrecisionTo64(_)
+            | AArch64Inst::FcvtauScalarDoublePrecisionTo64(_)
+            | AArch64Inst::FcvtauVec(_)
+            | AArch64Inst::FcvtauScalar(_) => "fcvtau",
+            AArch64Inst::FmovGeneralSinglePrecisionTo32(_)
+            | AArch64Inst::FmovGeneral32ToSinglePrecision(_)
+            | AArch64Inst::FmovGeneralDoublePrecisionTo64(_)
+            | AArch64Inst::FmovGeneral64ToDoublePrecision(_)
+            | AArch64Inst::FmovGeneralTopHalfOf128To64(_)
+            | AArch64Inst::FmovGeneral64toTopHalfOf128(_)
+            | AArch64Inst::FmovVecImmSinglePrecisionVar(_)
+            | AArch64Inst::FmovVecImmDoublePrecisionVar(_)
+            | AArch64Inst::FmovRegSinglePrecisionVar(_)
+            | AArch64Inst::FmovRegDoublePrecisionVar(_)
+            | AArch64Inst::FmovRegHalfPrecisionVar(_)
+            | AArch64Inst::FmovScalarImmSinglePrecisionVar(_)
+            | AArch64Inst::FmovScalarImmDoublePrecisionVar(_) => "fmov",
+            AArch64Inst::FcvtpsScalarSinglePrecisionTo32(_)
+            | AArch64Inst::FcvtpsScalarDoublePrecisionTo32(_)
+            | AArch64Inst::FcvtpsScalarSinglePrecisionTo64(_)
+            | AArch64Inst::FcvtpsScalarDoublePrecisionTo64(_)
+            | AArch64Inst::FcvtpsVec(_)
+            | AArch64Inst::FcvtpsScalar(_) => "fcvtps",
+            AArch64Inst::FcvtpuScalarSinglePrecisionTo32(_)
+            | AArch64Inst::FcvtpuScalarDoublePrecisionTo32(_)
+            | AArch64Inst::FcvtpuScalarSinglePrecisionTo64(_)
+            | AArch64Inst::FcvtpuScalarDoublePrecisionTo64(_)
+            | AArch64Inst::FcvtpuVec(_)
+            | AArch64Inst::FcvtpuScalar(_) => "fcvtpu",
+            AArch64Inst::FcvtmsScalarSinglePrecisionTo32(_)
+            | AArch64Inst::FcvtmsScalarDoublePrecisionTo32(_)
+            | AArch64Inst::FcvtmsScalarSinglePrecisionTo64(_)
+            | AArch64Inst::FcvtmsScalarDoublePrecisionTo64(_)
+            | AArch64Inst::FcvtmsVec(_)
+            | AArch64Inst::FcvtmsScalar(_) => "fcvtms",
+            AArch64Inst::FcvtmuScalarSinglePrecisionTo32(_)
+            | AArch64Inst::FcvtmuScalarDoublePrecisionTo32(_)
+            | AArch64Inst::FcvtmuScalarSinglePrecisionTo64(_)
+            | AArch64Inst::FcvtmuScalarDoublePrecisionTo64(_)
+            | AArch64Inst::FcvtmuVec(_)
+            | AArch64Inst::FcvtmuScalar(_) => "fcvtmu",
+            AArch64Inst::FcvtzsScalarIntSinglePrecisionTo32(_)
+            | AArch64Inst::FcvtzsScalarIntDoublePrecisionTo32(_)
+            | AArch64Inst::FcvtzsScalarIntSinglePrecisionTo64(_)
+            | AArch64Inst::FcvtzsScalarIntDoublePrecisionTo64(_)
+            | AArch64Inst::FcvtzsVecFixedPt(_)
+            | AArch64Inst::FcvtzsVecInt(_)
+            | AArch64Inst::FcvtzsScalarInt(_)
+            | AArch64Inst::FcvtzsScalarFixedPtSinglePrecisionTo32(_)
+            | AArch64Inst::FcvtzsScalarFixedPtDoublePrecisionTo32(_)
+            | AArch64Inst::FcvtzsScalarFixedPtSinglePrecisionTo64(_)
+            | AArch64Inst::FcvtzsScalarFixedPtDoublePrecisionTo64(_) => "fcvtzs",
+            AArch64Inst::FcvtzuScalarIntSinglePrecisionTo32(_)
+            | AArch64Inst::FcvtzuScalarIntDoublePrecisionTo32(_)
+            | AArch64Inst::FcvtzuScalarIntSinglePrecisionTo64(_)
+            | AArch64Inst::FcvtzuScalarIntDoublePrecisionTo64(_)
+            | AArch64Inst::FcvtzuVecFixedPt(_)
+            | AArch64Inst::FcvtzuVecInt(_)
+            | AArch64Inst::FcvtzuScalarInt(_)
+            | AArch64Inst::FcvtzuScalarFixedPtSinglePrecisionTo32(_)
+            | AArch64Inst::FcvtzuScalarFixedPtDoublePrecisionTo32(_)
+            | AArch64Inst::FcvtzuScalarFixedPtSinglePrecisionTo64(_)
+            | AArch64Inst::FcvtzuScalarFixedPtDoublePrecisionTo64(_) => "fcvtzu",
+            AArch64Inst::Fjcvtzs(_) => "fjcvtzs",
+            AArch64Inst::MoviShiftedImmVar32(_)
+            | AArch64Inst::MoviShiftedImmVar16(_)
+            | AArch64Inst::MoviShiftingOnesVar32(_)
+            | AArch64Inst::MoviVar8(_)
+            | AArch64Inst::MoviScalarVar64(_)
+            | AArch64Inst::MoviVectorVar64(_) => "movi",
+            AArch64Inst::MvniShiftedImmVar32(_)
+            | AArch64Inst::MvniShiftedImmVar16(_)
+            | AArch64Inst::MvniShiftingOnesVar32(_) => "mvni",
+            AArch64Inst::Ext(_) => "ext",
+            AArch64Inst::Shadd(_) => "shadd",
+            AArch64Inst::Sqadd(_) | AArch64Inst::SqaddScalar(_) => "sqadd",
+            AArch64Inst::Srhadd(_) => "srhadd",
+            AArch64Inst::Shsub(_) => "shsub",
+            AArch64Inst::Sqsub(_) | AArch64Inst::SqsubScalar(_) => "sqsub",
+            AArch64Inst::CmgtReg(_)
+            | AArch64Inst::CmgtRegScalar(_)
+            | AArch64Inst::CmgtZero(_)
+            | AArch64Inst::CmgtZeroScalar(_) => "cmgt",
+            AArch64Inst::CmgeReg(_)
+            | AArch64Inst::CmgeRegScalar(_)
+            | AArch64Inst::CmgeZero(_)
+            | AArch64Inst::CmgeZeroScalar(_) => "cmge",
+            AArch64Inst::Sshl(_) | AArch64Inst::SshlScalar(_) => "sshl",
+            AArch64Inst::SqshlReg(_)
+            | AArch64Inst::SqshlRegScalar(_)
+            | AArch64Inst::SqshlImm(_) => "sqshl",
+            AArch64Inst::Srshl(_) | AArch64Inst::SrshlScalar(_) => "srshl",
+            AArch64Inst::Sqrshl(_) | AArch64Inst::SqrshlScalar(_) => "sqrshl",
+            AArch64Inst::Smax(_) => "smax",
+            AArch64Inst::Smin(_) => "smin",
+            AArch64Inst::Sabd(_) => "sabd",
+            AArch64Inst::Saba(_) => "saba",
+            AArch64Inst::Cmtst(_) | AArch64Inst::CmtstScalar(_) => "cmtst",
+            AArch64Inst::MlaVec(_) | AArch64Inst::MlaByElem(_) => "mla",
+            AArch64Inst::MulVec(_) | AArch64Inst::MulByElem(_) => "mul",
+            AArch64Inst::Smaxp(_) => "smaxp",
+            AArch64Inst::Sminp(_) => "sminp",
+            AArch64Inst::SqdmulhVec(_)
+            | AArch64Inst::SqdmulhScalar(_)
+            | AArch64Inst::SqdmulhByElem(_)
+            | AArch64Inst::SqdmulhByElemScalar(_) => "sqdmulh",
+            AArch64Inst::AddpVec(_) | AArch64Inst::AddpScalar(_) => "addp",
+            AArch64Inst::FmaxnmVec(_)
+            | AArch64Inst::FmaxnmScalarSinglePrecisionVar(_)
+            | AArch64Inst::FmaxnmScalarDoublePrecisionVar(_) => "fmaxnm",
+            AArch64Inst::FmlaVec(_)
+            | AArch64Inst::FmlaByElemEncoding(_)
+            | AArch64Inst::FmlaByElemScalarEncoding(_) => "fmla",
+            AArch64Inst::FaddVec(_)
+            | AArch64Inst::FaddScalarSinglePrecisionVar(_)
+            | AArch64Inst::FaddScalarDoublePrecisionVar(_) => "fadd",
+            AArch64Inst::Fmulx(_)
+            | AArch64Inst::FmulxScalar(_)
+            | AArch64Inst::FmulxByElemEncoding(_)
+            | AArch64Inst::FmulxByElemScalarEncoding(_) => "fmulx",
+            AArch64Inst::FcmeqReg(_)
+            | AArch64Inst::FcmeqRegScalar(_)
+            | AArch64Inst::FcmeqZero(_)
+            | AArch64Inst::FcmeqZeroScalar(_) => "fcmeq",
+            AArch64Inst::FmaxVec(_)
+            | AArch64Inst::FmaxScalarSinglePrecisionVar(_)
+            | AArch64Inst::FmaxScalarDoublePrecisionVar(_) => "fmax",
+            AArch64Inst::Frecps(_) | AArch64Inst::FrecpsScalar(_) => "frecps",
+            AArch64Inst::FminnmVec(_)
+            | AArch64Inst::FminnmScalarSinglePrecisionVar(_)
+            | AArch64Inst::FminnmScalarDoublePrecisionVar(_) => "fminnm",
+            AArch64Inst::FmlsVec(_)
+            | AArch64Inst::FmlsByElemEncoding(_)
+            | AArch64Inst::FmlsByElemScalarEncoding(_) => "fmls",
+            AArch64Inst::FsubVec(_)
+            | AArch64Inst::FsubScalarSinglePrecisionVar(_)
+            | AArch64Inst::FsubScalarDoublePrecisionVar(_) => "fsub",
+            AArch64Inst::FminVec(_)
+            | AArch64Inst::FminScalarSinglePrecisionVar(_)
+            | AArch64Inst::FminScalarDoublePrecisionVar(_) => "fmin",
+            AArch64Inst::Frsqrts(_) | AArch64Inst::FrsqrtsScalar(_) => "frsqrts",
+            AArch64Inst::Uhadd(_) => "uhadd",
+            AArch64Inst::Uqadd(_) | AArch64Inst::UqaddScalar(_) => "uqadd",
+            AArch64Inst::Urhadd(_) => "urhadd",
+            AArch64Inst::Uhsub(_) => "uhsub",
+            AArch64Inst::Uqsub(_) | AArch64Inst::UqsubScalar(_) => "uqsub",
+            AArch64Inst::CmhiReg(_) | AArch64Inst::CmhiRegScalar(_) => "cmhi",
+            AArch64Inst::CmhsReg(_) | AArch64Inst::CmhsRegScalar(_) => "cmhs",
+            AArch64Inst::Ushl(_) | AArch64Inst::UshlScalar(_) => "ushl",
+            AArch64Inst::UqshlReg(_)
+            | AArch64Inst::UqshlRegScalar(_)
+            | AArch64Inst::UqshlImm(_) => "uqshl",
+            AArch64Inst::Urshl(_) | AArch64Inst::UrshlScalar(_) => "urshl",
+            AArch64Inst::Uqrshl(_) | AArch64Inst::UqrshlScalar(_) => "uqrshl",
+            AArch64Inst::Umax(_) => "umax",
+            AArch64Inst::Umin(_) => "umin",
+            AArch64Inst::Uabd(_) => "uabd",
+            AArch64Inst::Uaba(_) => "uaba",
+            AArch64Inst::CmeqReg(_)
+            | AArch64Inst::CmeqRegScalar(_)
+            | AArch64Inst::CmeqZero(_)
+            | AArch64Inst::CmeqZeroScalar(_) => "cmeq",
+            AArch64Inst::MlsVec(_) | AArch64Inst::MlsByElem(_) => "mls",
+            AArch64Inst::Pmul(_) => "pmul",
+            AArch64Inst::Umaxp(_) => "umaxp",
+            AArch64Inst::Uminp(_) => "uminp",
+            AArch64Inst::SqrdmulhVec(_)
+            | AArch64Inst::SqrdmulhScalar(_)
+            | AArch64Inst::SqrdmulhByElem(_)
+            | AArch64Inst::SqrdmulhByElemScalar(_) => "sqrdmulh",
+            AArch64Inst::FmaxnmpVec(_) | AArch64Inst::FmaxnmpScalarEncoding(_) => "fmaxnmp",
+            AArch64Inst::FaddpVec(_) | AArch64Inst::FaddpScalarEncoding(_) => "faddp",
+            AArch64Inst::FmulVec(_)
+            | AArch64Inst::FmulScalarSinglePrecisionVar(_)
+            | AArch64Inst::FmulScalarDoublePrecisionVar(_)
+            | AArch64Inst::FmulByElemEncoding(_)
+            | AArch64Inst::FmulByElemScalarEncoding(_) => "fmul",
+            AArch64Inst::FcmgeReg(_)
+            | AArch64Inst::FcmgeRegScalar(_)
+            | AArch64Inst::FcmgeZero(_)
+            | AArch64Inst::FcmgeZeroScalar(_) => "fcmge",
+            AArch64Inst::Facge(_) | AArch64Inst::FacgeScalar(_) => "facge",
+            AArch64Inst::FmaxpVec(_) | AArch64Inst::FmaxpScalarEncoding(_) => "fmaxp",
+            AArch64Inst::FdivVec(_)
+            | AArch64Inst::FdivScalarSinglePrecisionVar(_)
+            | AArch64Inst::FdivScalarDoublePrecisionVar(_) => "fdiv",
+            AArch64Inst::Bsl(_) => "bsl",
+            AArch64Inst::FminnmpVec(_) | AArch64Inst::FminnmpScalarEncoding(_) => "fminnmp",
+            AArch64Inst::Frecpx(_) => "frecpx",
+            AArch64Inst::Fabd(_) | AArch64Inst::FabdScalar(_) => "fabd",
+            AArch64Inst::FcmgtReg(_)
+            | AArch64Inst::FcmgtRegScalar(_)
+            | AArch64Inst::FcmgtZero(_)
+            | AArch64Inst::FcmgtZeroScalar(_) => "fcmgt",
+            AArch64Inst::Facgt(_) | AArch64Inst::FacgtScalar(_) => "facgt",
+            AArch64Inst::FminpVec(_) | AArch64Inst::FminpScalarEncoding(_) => "fminp",
+            AArch64Inst::Bit(_) => "bit",
+            AArch64Inst::Bif(_) => "bif",
+            AArch64Inst::Sshr(_) => "sshr",
+            AArch64Inst::Ssra(_) => "ssra",
+            AArch64Inst::Srshr(_) => "srshr",
+            AArch64Inst::Srsra(_) => "srsra",
+            AArch64Inst::Shl(_) => "shl",
+            AArch64Inst::Shrn(_) => "shrn",
+            AArch64Inst::Rshrn(_) => "rshrn",
+            AArch64Inst::Sqshrn(_) => "sqshrn",
+            AArch64Inst::Sqrshrn(_) => "sqrshrn",
+            AArch64Inst::Sshll(_) => "sshll",
+            AArch64Inst::Ushr(_) => "ushr",
+            AArch64Inst::Usra(_) => "usra",
+            AArch64Inst::Urshr(_) => "urshr",
+            AArch64Inst::Ursra(_) => "ursra",
+            AArch64Inst::Sri(_) => "sri",
+            AArch64Inst::Sli(_) => "sli",
+            AArch64Inst::Sqshlu(_) => "sqshlu",
+            AArch64Inst::Sqshrun(_) => "sqshrun",
+            AArch64Inst::Sqrshrun(_) => "sqrshrun",
+            AArch64Inst::Uqshrn(_) => "uqshrn",
+            AArch64Inst::Uqrshrn(_) => "uqrshrn",
+            AArch64Inst::Ushll(_) => "ushll",
+            AArch64Inst::FabsScalarSinglePrecisionVar(_)
+            | AArch64Inst::FabsScalarDoublePrecisionVar(_)
+            | AArch64Inst::FabsScalarHalfPrecisionVar(_)
+            | AArch64Inst::FabsVec(_) => "fabs",
+            AArch64Inst::FnegScalarSinglePrecisionVar(_)
+            | AArch64Inst::FnegScalarDoublePrecisionVar(_)
+            | AArch64Inst::FnegScalarHalfPrecisionVar(_)
+            | AArch64Inst::FnegVec(_) => "fneg",
+            AArch64Inst::FsqrtScalarSinglePrecisionVar(_)
+            | AArch64Inst::FsqrtScalarDoublePrecisionVar(_)
+            | AArch64Inst::FsqrtScalarHalfPrecisionVar(_)
+            | AArch64Inst::FsqrtVec(_) => "fsqrt",
+            AArch64Inst::FcvtSingleToDoublePrecisionVar(_)
+            | AArch64Inst::FcvtSingleToHalfPrecisionVar(_)
+            | AArch64Inst::FcvtDoubleToSinglePrecisionVar(_)
+            | AArch64Inst::FcvtDoubleToHalfPrecisionVar(_)
+            | AArch64Inst::FcvtHalfToSinglePrecisionVar(_)
+            | AArch64Inst::FcvtHalfToDoublePrecisionVar(_) => "fcvt",
+            AArch64Inst::FrintnScalarSinglePrecisionVar(_)
+            | AArch64Inst::FrintnScalarDoublePrecisionVar(_)
+            | AArch64Inst::FrintnScalarHalfPrecisionVar(_)
+            | AArch64Inst::FrintnVec(_) => "frintn",
+            AArch64Inst::FrintpScalarSinglePrecisionVar(_)
+            | AArch64Inst::FrintpScalarDoublePrecisionVar(_)
+            | AArch64Inst::FrintpScalarHalfPrecisionVar(_)
+            | AArch64Inst::FrintpVec(_) => "frintp",
+            AArch64Inst::FrintmScalarSinglePrecisionVar(_)
+            | AArch64Inst::FrintmScalarDoublePrecisionVar(_)
+            | AArch64Inst::FrintmScalarHalfPrecisionVar(_)
+            | AArch64Inst::FrintmVec(_) => "frintm",
+            AArch64Inst::FrintzScalarSinglePrecisionVar(_)
+            | AArch64Inst::FrintzScalarDoublePrecisionVar(_)
+            | AArch64Inst::FrintzScalarHalfPrecisionVar(_)
+            | AArch64Inst::FrintzVec(_) => "frintz",
+            AArch64Inst::FrintaScalarSinglePrecisionVar(_)
+            | AArch64Inst::FrintaScalarDoublePrecisionVar(_)
+            | AArch64Inst::FrintaScalarHalfPrecisionVar(_)
+            | AArch64Inst::FrintaVec(_) => "frinta",
+            AArch64Inst::FrintxScalarSinglePrecisionVar(_)
+            | AArch64Inst::FrintxScalarDoublePrecisionVar(_)
+            | AArch64Inst::FrintxScalarHalfPrecisionVar(_)
+            | AArch64Inst::FrintxVec(_) => "frintx",
+            AArch64Inst::FrintiScalarSinglePrecisionVar(_)
+            | AArch64Inst::FrintiScalarDoublePrecisionVar(_)
+            | AArch64Inst::FrintiScalarHalfPrecisionVar(_)
+            | AArch64Inst::FrintiVec(_) => "frinti",
+            AArch64Inst::Rev64(_) => "rev64",
+            AArch64Inst::Saddlp(_) => "saddlp",
+            AArch64Inst::Suqadd(_) | AArch64Inst::SuqaddScalar(_) => "suqadd",
+            AArch64Inst::Cnt(_) => "cnt",
+            AArch64Inst::Sadalp(_) => "sadalp",
+            AArch64Inst::Sqabs(_) | AArch64Inst::SqabsScalar(_) => "sqabs",
+            AArch64Inst::CmltZero(_) | AArch64Inst::CmltZeroScalar(_) => "cmlt",
+            AArch64Inst::Abs(_) | AArch64Inst::AbsScalar(_) => "abs",
+            AArch64Inst::XtnXtn2(_) => "xtn",
+            AArch64Inst::Sqxtn(_) | AArch64Inst::SqxtnScalar(_) => "sqxtn",
+            AArch64Inst::Fcvtn(_) => "fcvtn",
+            AArch64Inst::Fcvtl(_) => "fcvtl",
+            AArch64Inst::FcmltZero(_) | AArch64Inst::FcmltZeroScalar(_) => "fcmlt",
+            AArch64Inst::Urecpe(_) => "urecpe",
+            AArch64Inst::Frecpe(_) | AArch64Inst::FrecpeScalar(_) => "frecpe",
+            AArch64Inst::Uaddlp(_) => "uaddlp",
+            AArch64Inst::Usqadd(_) | AArch64Inst::UsqaddScalar(_) => "usqadd",
+            AArch64Inst::Uadalp(_) => "uadalp",
+            AArch64Inst::Sqneg(_) | AArch64Inst::SqnegScalar(_) => "sqneg",
+            AArch64Inst::CmleZero(_) | AArch64Inst::CmleZeroScalar(_) => "cmle",
+            AArch64Inst::NegVec(_) | AArch64Inst::NegScalar(_) => "neg",
+            AArch64Inst::Sqxtun(_) | AArch64Inst::SqxtunScalar(_) => "sqxtun",
+            AArch64Inst::Shll(_) => "shll",
+            AArch64Inst::Uqxtn(_) | AArch64Inst::UqxtnScalar(_) => "uqxtn",
+            AArch64Inst::Fcvtxn(_) | AArch64Inst::FcvtxnScalar(_) => "fcvtxn",
+            AArch64Inst::Not(_) => "not",
+            AArch64Inst::FcmleZero(_) | AArch64Inst::FcmleZeroScalar(_) => "fcmle",
+            AArch64Inst::Ursqrte(_) => "ursqrte",
+            AArch64Inst::Frsqrte(_) | AArch64Inst::FrsqrteScalar(_) => "frsqrte",
+            AArch64Inst::Saddlv(_) => "saddlv",
+            AArch64Inst::Smaxv(_) => "smaxv",
+            AArch64Inst::Sminv(_) => "sminv",
+            AArch64Inst::Addv(_) => "addv",
+            AArch64Inst::Uaddlv(_) => "uaddlv",
+            AArch64Inst::Umaxv(_) => "umaxv",
+            AArch64Inst::Uminv(_) => "uminv",
+            AArch64Inst::FmaxnvmEncoding(_) => "fmaxnmv",
+            AArch64Inst::FmaxvEncoding(_) => "fmaxv",
+            AArch64Inst::FminnmvEncoding(_) => "fminnmv",
+            AArch64Inst::FminvEncoding(_) => "fminv",
+            AArch64Inst::Udf(_) => "udf",
+            AArch64Inst::Casb(_) => "casb",
+            AArch64Inst::Caslb(_) => "caslb",
+            AArch64Inst::Casab(_) => "casab",
+            AArch64Inst::Casalb(_) => "casalb",
+            AArch64Inst::Cash(_) => "cash",
+            AArch64Inst::Caslh(_) => "caslh",
+            AArch64Inst::Casah(_) => "casah",
+            AArch64Inst::Casalh(_) => "casalh",
+            AArch64Inst::CasVar32(_) | AArch64Inst::CasVar64(_) => "cas",
+            AArch64Inst::CaslVar32(_) | AArch64Inst::CaslVar64(_) => "casl",
+            AArch64Inst::CasaVar32(_) | AArch64Inst::CasaVar64(_) => "casa",
+            AArch64Inst::CasalVar32(_) | AArch64Inst::CasalVar64(_) => "casal",
+            AArch64Inst::LdaddbVar(_) => "ldaddb",
+            AArch64Inst::LdclrbVar(_) => "ldclrb",
+            AArch64Inst::LdeorbVar(_) => "ldeorb",
+            AArch64Inst::LdsetbVar(_) => "ldsetb",
+            AArch64Inst::LdsmaxbVar(_) => "ldsmaxb",
+            AArch64Inst::LdsminbVar(_) => "ldsminb",
+            AArch64Inst::LdumaxbVar(_) => "ldumaxb",
+            AArch64Inst::LduminbVar(_) => "lduminb",
+            AArch64Inst::SwpbVar(_) => "swpb",
+            AArch64Inst::LdaddlbVar(_) => "ldaddlb",
+            AArch64Inst::LdclrlbVar(_) => "ldclrlb",
+            AArch64Inst::LdeorlbVar(_) => "ldeorlb",
+            AArch64Inst::LdsetlbVar(_) => "ldsetlb",
+            AArch64Inst::LdsmaxlbVar(_) => "ldsmaxlb",
+            AArch64Inst::LdsminlbVar(_) => "ldsminlb",
+            AArch64Inst::LdumaxlbVar(_) => "ldumaxlb",
+            AArch64Inst::LduminlbVar(_) => "lduminlb",
+            AArch64Inst::SwplbVar(_) => "swplb",
+            AArch64Inst::LdaddabVar(_) => "ldaddab",
+            AArch64Inst::LdclrabVar(_) => "ldclrab",
+            AArch64Inst::LdeorabVar(_) => "ldeorab",
+            AArch64Inst::LdsetabVar(_) => "ldsetab",
+            AArch64Inst::LdsmaxabVar(_) => "ldsmaxab",
+            AArch64Inst::LdsminabVar(_) => "ldsminab",
+            AArch64Inst::LdumaxabVar(_) => "ldumaxab",
+            AArch64Inst::LduminabVar(_) => "lduminab",
+            AArch64Inst::SwpabVar(_) => "swpab",
+            AArch64Inst::Ldaprb(_) => "ldaprb",
+            AArch64Inst::LdaddalbVar(_) => "ldaddalb",
+            AArch64Inst::LdclralbVar(_) => "ldclralb",
+            AArch64Inst::LdeoralbVar(_) => "ldeoralb",
+            AArch64Inst::LdsetalbVar(_) => "ldsetalb",
+            AArch64Inst::LdsmaxalbVar(_) => "ldsmaxalb",
+            AArch64Inst::LdsminalbVar(_) => "ldsminalb",
+            AArch64Inst::LdumaxalbVar(_) => "ldumaxalb",
+            AArch64Inst::LduminalbVar(_) => "lduminalb",
+            AArch64Inst::SwpalbVar(_) => "swpalb",
+            AArch64Inst::LdaddhVar(_) => "ldaddh",
+            AArch64Inst::LdclrhVar(_) => "ldclrh",
+            AArch64Inst::LdeorhVar(_) => "ldeorh",
+            AArch64Inst::LdsethVar(_) => "ldseth",
+            AArch64Inst::LdsmaxhVar(_) => "ldsmaxh",
+            AArch64Inst::LdsminhVar(_) => "ldsminh",
+            AArch64Inst::LdumaxhVar(_) => "ldumaxh",
+            AArch64Inst::LduminhVar(_) => "lduminh",
+            AArch64Inst::SwphVar(_) => "swph",
+            AArch64Inst::LdaddlhVar(_) => "ldaddlh",
+            AArch64Inst::LdclrlhVar(_) => "ldclrlh",
+            AArch64Inst::LdeorlhVar(_) => "ldeorlh",
+            AArch64Inst::LdsetlhVar(_) => "ldsetlh",
+            AArch64Inst::LdsmaxlhVar(_) => "ldsmaxlh",
+            AArch64Inst::LdsminlhVar(_) => "ldsminlh",
+            AArch64Inst::LdumaxlhVar(_) => "ldumaxlh",
+            AArch64Inst::LduminlhVar(_) => "lduminlh",
+            AArch64Inst::SwplhVar(_) => "swplh",
+            AArch64Inst::LdaddahVar(_) => "ldaddah",
+            AArch64Inst::LdclrahVar(_) => "ldclrah",
+            AArch64Inst::LdeorahVar(_) => "ldeorah",
+            AArch64Inst::LdsetahVar(_) => "ldsetah",
+            AArch64Inst::LdsmaxahVar(_) => "ldsmaxah",
+            AArch64Inst::LdsminahVar(_) => "ldsminah",
+            AArch64Inst::LdumaxahVar(_) => "ldumaxah",
+            AArch64Inst::LduminahVar(_) => "lduminah",
+            AArch64Inst::SwpahVar(_) => "swpah",
+            AArch64Inst::Ldaprh(_) => "ldaprh",
+            AArch64Inst::LdaddalhVar(_) => "ldaddalh",
+            AArch64Inst::LdclralhVar(_) => "ldclralh",
+            AArch64Inst::LdeoralhVar(_) => "ldeoralh",
+            AArch64Inst::LdsetalhVar(_) => "ldsetalh",
+            AArch64Inst::LdsmaxalhVar(_) => "ldsmaxalh",
+            AArch64Inst::LdsminalhVar(_) => "ldsminalh",
+            AArch64Inst::LdumaxalhVar(_) => "ldumaxalh",
+            AArch64Inst::LduminalhVar(_) => "lduminalh",
+            AArch64Inst::SwpalhVar(_) => "swpalh",
+            AArch64Inst::LdaddVar32(_) | AArch64Inst::LdaddVar64(_) => "ldadd",
+            AArch64Inst::LdclrVar32(_) | AArch64Inst::LdclrVar64(_) => "ldclr",
+            AArch64Inst::LdeorVar32(_) | AArch64Inst::LdeorVar64(_) => "ldeor",
+            AArch64Inst::LdsetVar32(_) | AArch64Inst::LdsetVar64(_) => "ldset",
+            AArch64Inst::LdsmaxVar32(_) | AArch64Inst::LdsmaxVar64(_) => "ldsmax",
+            AArch64Inst::LdsminVar32(_) | AArch64Inst::LdsminVar64(_) => "ldsmin",
+            AArch64Inst::LdumaxVar32(_) | AArch64Inst::LdumaxVar64(_) => "ldumax",
+            AArch64Inst::LduminVar32(_) | AArch64Inst::LduminVar64(_) => "ldumin",
+            AArch64Inst::SwpVar32(_) | AArch64Inst::SwpVar64(_) => "swp",
+            AArch64Inst::LdaddlVar32(_) | AArch64Inst::LdaddlVar64(_) => "ldaddl",
+            AArch64Inst::LdclrlVar32(_) | AArch64Inst::LdclrlVar64(_) => "ldclrl",
+            AArch64Inst::LdeorlVar32(_) | AArch64Inst::LdeorlVar64(_) => "ldeorl",
+            AArch64Inst::LdsetlVar32(_) | AArch64Inst::LdsetlVar64(_) => "ldsetl",
+            AArch64Inst::LdsmaxlVar32(_) | AArch64Inst::LdsmaxlVar64(_) => "ldsmaxl",
+            AArch64Inst::LdsminlVar32(_) | AArch64Inst::LdsminlVar64(_) => "ldsminl",
+            AArch64Inst::LdumaxlVar32(_) | AArch64Inst::LdumaxlVar64(_) => "ldumaxl",
+            AArch64Inst::LduminlVar32(_) | AArch64Inst::LduminlVar64(_) => "lduminl",
+            AArch64Inst::SwplVar32(_) | AArch64Inst::SwplVar64(_) => "swpl",
+            AArch64Inst::LdaddaVar32(_) | AArch64Inst::LdaddaVar64(_) => "ldadda",
+            AArch64Inst::LdclraVar32(_) | AArch64Inst::LdclraVar64(_) => "ldclra",
+            AArch64Inst::LdeoraVar32(_) | AArch64Inst::LdeoraVar64(_) => "ldeora",
+            AArch64Inst::LdsetaVar32(_) | AArch64Inst::LdsetaVar64(_) => "ldseta",
+            AArch64Inst::LdsmaxaVar32(_) | AArch64Inst::LdsmaxaVar64(_) => "ldsmaxa",
+            AArch64Inst::LdsminaVar32(_) | AArch64Inst::LdsminaVar64(_) => "ldsmina",
+            AArch64Inst::LdumaxaVar32(_) | AArch64Inst::LdumaxaVar64(_) => "ldumaxa",
+            AArch64Inst::LduminaVar32(_) | AArch64Inst::LduminaVar64(_) => "ldumina",
+            AArch64Inst::SwpaVar32(_) | AArch64Inst::SwpaVar64(_) => "swpa",
+            AArch64Inst::LdaprVar32(_) | AArch64Inst::LdaprVar64(_) => "ldapr",
+            AArch64Inst::LdaddalVar32(_) | AArch64Inst::LdaddalVar64(_) => "ldaddal",
+            AArch64Inst::LdclralVar32(_) | AArch64Inst::LdclralVar64(_) => "ldclral",
+            AArch64Inst::LdeoralVar32(_) | AArch64Inst::LdeoralVar64(_) => "ldeoral",
+            AArch64Inst::LdsetalVar32(_) | AArch64Inst::LdsetalVar64(_) => "ldsetal",
+            AArch64Inst::LdsmaxalVar32(_) | AArch64Inst::LdsmaxalVar64(_) => "ldsmaxal",
+            AArch64Inst::LdsminalVar32(_) | AArch64Inst::LdsminalVar64(_) => "ldsminal",
+            AArch64Inst::LdumaxalVar32(_) | AArch64Inst::LdumaxalVar64(_) => "ldumaxal",
+            AArch64Inst::LduminalVar32(_) | AArch64Inst::LduminalVar64(_) => "lduminal",
+            AArch64Inst::SwpalVar32(_) | AArch64Inst::SwpalVar64(_) => "swpal",
+            AArch64Inst::St64bv0(_) => "st64bv0",
+            AArch64Inst::St64bv(_) => "st64bv",
+            AArch64Inst::St64b(_) => "st64b",
+            AArch64Inst::Ld64b(_) => "ld64b",
+            AArch64Inst::Fcmp(_) => "fcmp",
+            AArch64Inst::Fcmpe(_) => "fcmpe",
+            AArch64Inst::Tbl(_) => "tbl",
+            AArch64Inst::Tbx(_) => "tbx",
+            AArch64Inst::Saddl(_) => "saddl",
+            AArch64Inst::Saddw(_) => "saddw",
+            AArch64Inst::Ssubl(_) => "ssubl",
+            AArch64Inst::Ssubw(_) => "ssubw",
+            AArch64Inst::Addhn(_) => "addhn",
+            AArch64Inst::Sabal(_) => "sabal",
+            AArch64Inst::Subhn(_) => "subhn",
+            AArch64Inst::Sabdl(_) => "sabdl",
+            AArch64Inst::Smlal(_) => "smlal",
+            AArch64Inst::Sqdmlal(_) => "sqdmlal",
+            AArch64Inst::Smlsl(_) => "smlsl",
+            AArch64Inst::Sqdmlsl(_) => "sqdmlsl",
+            AArch64Inst::Smull(_) => "smull",
+            AArch64Inst::Sqdmull(_) => "sqdmull",
+            AArch64Inst::Pmull(_) => "pmull",
+            AArch64Inst::Uaddl(_) => "uaddl",
+            AArch64Inst::Uaddw(_) => "uaddw",
+            AArch64Inst::Usubl(_) => "usubl",
+            AArch64Inst::Usubw(_) => "usubw",
+            AArch64Inst::Raddhn(_) => "raddhn",
+            AArch64Inst::Uabal(_) => "uabal",
+            AArch64Inst::Rsubhn(_) => "rsubhn",
+            AArch64Inst::Uabdl(_) => "uabdl",
+            AArch64Inst::Umlal(_) => "umlal",
+            AArch64Inst::Umlsl(_) => "umlsl",
+            AArch64Inst::Umull(_) => "umull",
+            AArch64Inst::Uzp1(_) => "uzp1",
+            AArch64Inst::Trn1(_) => "trn1",
+            AArch64Inst::Zip1(_) => "zip1",
+            AArch64Inst::Uzp2(_) => "uzp2",
+            AArch64Inst::Trn2(_) => "trn2",
+            AArch64Inst::Zip2(_) => "zip2",
+            AArch64Inst::FnmulScalarSinglePrecisionVar(_)
+            | AArch64Inst::FnmulScalarDoublePrecisionVar(_) => "fnmul",
+            AArch64Inst::FcselSinglePrecisionVar(_) | AArch64Inst::FcselDoublePrecisionVar(_) => {
+                "fcsel"
+            }
+            AArch64Inst::SmlalByElem(_) => "smlal",
+            AArch64Inst::SqdmlalByElem(_) | AArch64Inst::SqdmlalByElemScalar(_) => "sqdmlal",
+            AArch64Inst::SmlslByElem(_) => "smlsl",
+            AArch64Inst::SqdmlslByElem(_) | AArch64Inst::SqdmlslByElemScalar(_) => "sqdmlsl",
+            AArch64Inst::SmullByElem(_) => "smull",
+            AArch64Inst::SqdmullByElem(_) | AArch64Inst::SqdmullByElemScalar(_) => "sqdmull",
+            AArch64Inst::UmlalByElem(_) => "umlal",
+            AArch64Inst::UmlslByElem(_) => "umlsl",
+            AArch64Inst::UmullByElem(_) => "umull",
+        }
+    }
+
+    /// Display the instruction fetched from `pc`, with the pc-relative targets resolved.
+    pub fn display_at(&self, pc: u64) -> Disassembly<'_> {
+        Disassembly { inst: self, pc }
+    }
+
+    /// The decoder accepts some reserved encodings of the SIMD instructions, their size or
+    /// element fields have no arrangement to display.
+    fn is_reserved(&self) -> bool {
+        match self {
+            AArch64Inst::DupElement(operand)
+            | AArch64Inst::DupGeneral(operand)
+            | AArch64Inst::Smov(operand)
+            | AArch64Inst::Umov(operand)
+            | AArch64Inst::InsGeneral(operand)
+            | AArch64Inst::InsElement(operand) => operand.imm5 & 0b1111 == 0,
+            AArch64Inst::Saddlp(operand)
+            | AArch64Inst::Sadalp(operand)
+            | AArch64Inst::Uaddlp(operand)
+            | AArch64Inst::Uadalp(operand)
+            | AArch64Inst::XtnXtn2(operand)
+            | AArch64Inst::Sqxtn(operand)
+            | AArch64Inst::Sqxtun(operand)
+            | AArch64Inst::Uqxtn(operand)
+            | AArch64Inst::Shll(operand) => operand.size == 0b11,
+            AArch64Inst::Shrn(operand)
+            | AArch64Inst::Rshrn(operand)
+            | AArch64Inst::Sqshrn(operand)
+            | AArch64Inst::Sqrshrn(operand)
+            | AArch64Inst::Sqshrun(operand)
+            | AArch64Inst::Sqrshrun(operand)
+            | AArch64Inst::Uqshrn(operand)
+            | AArch64Inst::Uqrshrn(operand)
+            | AArch64Inst::Sshll(operand)
+            | AArch64Inst::Ushll(operand) => operand.immh & 0b1000 != 0,
+            AArch64Inst::SmlalByElem(operand)
+            | AArch64Inst::SqdmlalByElem(operand)
+            | AArch64Inst::SmlslByElem(operand)
+            | AArch64Inst::SqdmlslByElem(operand)
+            | AArch64Inst::SmullByElem(operand)
+            | AArch64Inst::SqdmullByElem(operand)
+            | AArch64Inst::UmlalByElem(operand)
+            | AArch64Inst::UmlslByElem(operand)
+            | AArch64Inst::UmullByElem(operand) => matches!(operand.size, 0b00 | 0b11),
+            AArch64Inst::Saddl(operand)
+            | AArch64Inst::Saddw(operand)
+            | AArch64Inst::Ssubl(operand)
+            | AArch64Inst::Ssubw(operand)
+            | AArch64Inst::Addhn(operand)
+            | AArch64Inst::Sabal(operand)
+            | AArch64Inst::Subhn(operand)
+            | AArch64Inst::Sabdl(operand)
+            | AArch64Inst::Smlal(operand)
+            | AArch64Inst::Smlsl(operand)
+            | AArch64Inst::Smull(operand)
+            | AArch64Inst::Uaddl(operand)
+            | AArch64Inst::Uaddw(operand)
+            | AArch64Inst::Usubl(operand)
+            | AArch64Inst::Usubw(operand)
+            | AArch64Inst::Raddhn(operand)
+            | AArch64Inst::Uabal(operand)
+            | AArch64Inst::Rsubhn(operand)
+            | AArch64Inst::Uabdl(operand)
+            | AArch64Inst::Umlal(operand)
+            | AArch64Inst::Umlsl(operand)
+            | AArch64Inst::Umull(operand) => operand.size == 0b11,
+            AArch64Inst::Sqdmlal(operand)
+            | AArch64Inst::Sqdmlsl(operand)
+            | AArch64Inst::Sqdmull(operand) => matches!(operand.size, 0b00 | 0b11),
+            AArch64Inst::Pmull(operand) => matches!(operand.size, 0b01 | 0b10),
+            AArch64Inst::CmgtRegScalar(operand)
+            | AArch64Inst::CmgeRegScalar(operand)
+            | AArch64Inst::SshlScalar(operand)
+            | AArch64Inst::SrshlScalar(operand)
+            | AArch64Inst::AddScalar(operand)
+            | AArch64Inst::CmtstScalar(operand)
+            | AArch64Inst::CmhiRegScalar(operand)
+            | AArch64Inst::CmhsRegScalar(operand)
+            | AArch64Inst::UshlScalar(operand)
+            | AArch64Inst::UrshlScalar(operand)
+            | AArch64Inst::SubScalar(operand)
+            | AArch64Inst::CmeqRegScalar(operand) => operand.size != 0b11,
+            AArch64Inst::SqdmulhScalar(operand) | AArch64Inst::SqrdmulhScalar(operand) => {
+                matches!(operand.size, 0b00 | 0b11)
+            }
+            AArch64Inst::CmgtZeroScalar(operand)
+            | AArch64Inst::CmeqZeroScalar(operand)
+            | AArch64Inst::CmltZeroScalar(operand)
+            | AArch64Inst::CmgeZeroScalar(operand)
+            | AArch64Inst::CmleZeroScalar(operand)
+            | AArch64Inst::AbsScalar(operand)
+            | AArch64Inst::NegScalar(operand) => operand.size != 0b11,
+            AArch64Inst::SqxtnScalar(operand)
+            | AArch64Inst::SqxtunScalar(operand)
+            | AArch64Inst::UqxtnScalar(operand) => operand.size == 0b11,
+            AArch64Inst::FcvtxnScalar(operand) => operand.size == 0b00,
+            _ => false,
+        }
+    }
+
+    fn fmt_asm(&self, f: &mut Formatter<'_>, pc: Option<u64>) -> fmt::Result {
+        use IrType::{U128, U16, U32, U64, U8};
+
+        if self.is_reserved() {
+            return write!(f, ".inst {:#010x}", self.encode());
+        }
+        let m = self.mnemonic();
+        let target = |offset: i64| Target { pc, offset };
+        match self {
+            // Data processing with immediates
+            AArch64Inst::AddImm32(operand)
+            | AArch64Inst::AddsImm32(operand)
+            | AArch64Inst::SubImm32(operand)
+            | AArch64Inst::SubsImm32(operand) => fmt_add_sub_imm(f, m, operand, U32),
+            AArch64Inst::AddImm64(operand)
+            | AArch64Inst::AddsImm64(operand)
+            | AArch64Inst::SubImm64(operand)
+            | AArch64Inst::SubsImm64(operand) => fmt_add_sub_imm(f, m, operand, U64),
+            AArch64Inst::AndImm32(operand)
+            | AArch64Inst::OrrImm32(operand)
+            | AArch64Inst::EorImm32(operand)
+            | AArch64Inst::AndsImm32(operand) => fmt_logical_imm(f, m, operand, U32),
+            AArch64Inst::AndImm64(operand)
+            | AArch64Inst::OrrImm64(operand)
+            | AArch64Inst::EorImm64(operand)
+            | AArch64Inst::AndsImm64(operand) => fmt_logical_imm(f, m, operand, U64),
+            AArch64Inst::Addg(operand) | AArch64Inst::Subg(operand) => write!(
+                f,
+                "{m} {}, {}, #{}, #{}",
+                x(operand.rd),
+                x(operand.rn),
+                operand.uimm6 as u64 * 16,
+                operand.uimm4
+            ),
+            AArch64Inst::Extr32(operand) => fmt_extract(f, m, operand, U32),
+            AArch64Inst::Extr64(operand) => fmt_extract(f, m, operand, U64),
+            AArch64Inst::Sbfm32(operand)
+            | AArch64Inst::Bfm32(operand)
+            | AArch64Inst::Ubfm32(operand) => fmt_bitfield(f, m, operand, U32),
+            AArch64Inst::Sbfm64(operand)
+            | AArch64Inst::Bfm64(operand)
+            | AArch64Inst::Ubfm64(operand) => fmt_bitfield(f, m, operand, U64),
+            AArch64Inst::MovnVar32(operand)
+            | AArch64Inst::MovzVar32(operand)
+            | AArch64Inst::MovkVar32(operand) => fmt_move_wide(f, m, operand, U32),
+            AArch64Inst::MovnVar64(operand)
+            | AArch64Inst::MovzVar64(operand)
+            | AArch64Inst::MovkVar64(operand) => fmt_move_wide(f, m, operand, U64),
+            AArch64Inst::Adr(operand) => {
+                let imm = (operand.immhi << 2 | operand.immlo as u32) as u64;
+                write!(
+                    f,
+                    "{m} {}, {}",
+                    x(operand.rd),
+                    target(sign_extend(imm, 21) as i64)
+                )
+            }
+            AArch64Inst::Adrp(operand) => {
+                let imm = (operand.immhi << 2 | operand.immlo as u32) as u64;
+                let page = Target {
+                    pc: pc.map(|pc| pc & !0xfff),
+                    offset: sign_extend(imm, 21) as i64 * 4096,
+                };
+                write!(f, "{m} {}, {page}", x(operand.rd))
+            }
+
+            // Data processing with registers
+            AArch64Inst::AddShiftedReg32(operand)
+            | AArch64Inst::AddsShiftedReg32(operand)
+            | AArch64Inst::SubShiftedReg32(operand)
+            | AArch64Inst::SubsShiftedReg32(operand)
+            | AArch64Inst::AndShiftedReg32(operand)
+            | AArch64Inst::BicShiftedReg32(operand)
+            | AArch64Inst::OrrShiftedReg32(operand)
+            | AArch64Inst::OrnShiftedReg32(operand)
+            | AArch64Inst::EorShiftedReg32(operand)
+            | AArch64Inst::EonShiftedReg32(operand)
+            | AArch64Inst::AndsShiftedReg32(operand)
+            | AArch64Inst::BicsShiftedReg32(operand) => fmt_shifted_reg(f, m, operand, U32),
+            AArch64Inst::AddShiftedReg64(operand)
+            | AArch64Inst::AddsShiftedReg64(operand)
+            | AArch64Inst::SubShiftedReg64(operand)
+            | AArch64Inst::SubsShiftedReg64(operand)
+            | AArch64Inst::AndShiftedReg64(operand)
+            | AArch64Inst::BicShiftedReg64(operand)
+            | AArch64Inst::OrrShiftedReg64(operand)
+            | AArch64Inst::OrnShiftedReg64(operand)
+            | AArch64Inst::EorShiftedReg64(operand)
+            | AArch64Inst::EonShiftedReg64(operand)
+            | AArch64Inst::AndsShiftedReg64(operand)
+            | AArch64Inst::BicsShiftedReg64(operand) => fmt_shifted_reg(f, m, operand, U64),
+            AArch64Inst::AddExtReg32(operand)
+            | AArch64Inst::AddsExtReg32(operand)
+            | AArch64Inst::SubExtReg32(operand)
+            | AArch64Inst::SubsExtReg32(operand) => fmt_ext_reg(f, m, operand, U32),
+            AArch64Inst::AddExtReg64(operand)
+            | AArch64Inst::AddsExtReg64(operand)
+            | AArch64Inst::SubExtReg64(operand)
+            | AArch64Inst::SubsExtReg64(operand) => fmt_ext_reg(f, m, operand, U64),
+            AArch64Inst::AdcVar32(operand)
+            | AArch64Inst::AdcsVar32(operand)
+            | AArch64Inst::SbcVar32(operand)
+            | AArch64Inst::SbcsVar32(operand) => fmt_add_sub_carry(f, m, operand, U32),
+            AArch64Inst::AdcVar64(operand)
+            | AArch64Inst::AdcsVar64(operand)
+            | AArch64Inst::SbcVar64(operand)
+            | AArch64Inst::SbcsVar64(operand) => fmt_add_sub_carry(f, m, operand, U64),
+            AArch64Inst::Madd32(operand) | AArch64Inst::Msub32(operand) => {
+                let (rd, rn, rm, ra) = (w(operand.rd), w(operand.rn), w(operand.rm), w(operand.ra));
+                match (m, operand.ra == AArch64RegisterId::Xzr) {
+                    ("madd", true) => write!(f, "mul {rd}, {rn}, {rm}"),
+                    ("msub", true) => write!(f, "mneg {rd}, {rn}, {rm}"),
+                    _ => write!(f, "{m} {rd}, {rn}, {rm}, {ra}"),
+                }
+            }
+            AArch64Inst::Madd64(operand) | AArch64Inst::Msub64(operand) => {
+                let (rd, rn, rm, ra) = (x(operand.rd), x(operand.rn), x(operand.rm), x(operand.ra));
+                match (m, operand.ra == AArch64RegisterId::Xzr) {
+                    ("madd", true) => write!(f, "mul {rd}, {rn}, {rm}"),
+                    ("msub", true) => write!(f, "mneg {rd}, {rn}, {rm}"),
+                    _ => write!(f, "{m} {rd}, {rn}, {rm}, {ra}"),
+                }
+            }
+            AArch64Inst::Smaddl(operand)
+            | AArch64Inst::Smsubl(operand)
+            | AArch64Inst::Umaddl(operand)
+            | AArch64Inst::Umsubl(operand) => {
+                let (rd, rn, rm, ra) = (x(operand.rd), w(operand.rn), w(operand.rm), x(operand.ra));
+                let alias = match m {
+                    "smaddl" => "smull",
+                    "smsubl" => "smnegl",
+                    "umaddl" => "umull",
+                    _ => "umnegl",
+                };
+                match operand.ra == AArch64RegisterId::Xzr {
+                    true => write!(f, "{alias} {rd}, {rn}, {rm}"),
+                    false => write!(f, "{m} {rd}, {rn}, {rm}, {ra}"),
+                }
+            }
+            AArch64Inst::Smulh(operand) | AArch64Inst::Umulh(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}, {}",
+                    x(operand.rd),
+                    x(operand.rn),
+                    x(operand.rm)
+                )
+            }
+            AArch64Inst::UdivVar32(operand) | AArch64Inst::SdivVar32(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}, {}",
+                    w(operand.rd),
+                    w(operand.rn),
+                    w(operand.rm)
+                )
+            }
+            AArch64Inst::UdivVar64(operand)
+            | AArch64Inst::SdivVar64(operand)
+            | AArch64Inst::Pacga(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}, {}",
+                    x(operand.rd),
+                    x(operand.rn),
+                    x(operand.rm)
+                )
+            }
+            // The variable shifts are always displayed with their alias, `lsl` for `lslv`
+            AArch64Inst::LslvVar32(operand)
+            | AArch64Inst::LsrvVar32(operand)
+            | AArch64Inst::AsrvVar32(operand)
+            | AArch64Inst::RorvVar32(operand) => {
+                let m = m.trim_end_matches('v');
+                write!(
+                    f,
+                    "{m} {}, {}, {}",
+                    w(operand.rd),
+                    w(operand.rn),
+                    w(operand.rm)
+                )
+            }
+            AArch64Inst::LslvVar64(operand)
+            | AArch64Inst::LsrvVar64(operand)
+            | AArch64Inst::AsrvVar64(operand)
+            | AArch64Inst::RorvVar64(operand) => {
+                let m = m.trim_end_matches('v');
+                write!(
+                    f,
+                    "{m} {}, {}, {}",
+                    x(operand.rd),
+                    x(operand.rn),
+                    x(operand.rm)
+                )
+            }
+            AArch64Inst::RbitVar32(operand)
+            | AArch64Inst::Rev16Var32(operand)
+            | AArch64Inst::RevVar32(operand)
+            | AArch64Inst::ClzVar32(operand)
+            | AArch64Inst::ClsVar32(operand) => {
+                write!(f, "{m} {}, {}", w(operand.rd), w(operand.rn))
+            }
+            AArch64Inst::RbitVar64(operand)
+            | AArch64Inst::Rev16Var64(operand)
+            | AArch64Inst::Rev32(operand)
+            | AArch64Inst::RevVar64(operand)
+            | AArch64Inst::ClzVar64(operand)
+            | AArch64Inst::ClsVar64(operand) => {
+                write!(f, "{m} {}, {}", x(operand.rd), x(operand.rn))
+            }
+            AArch64Inst::Csel32(operand)
+            | AArch64Inst::Csinc32(operand)
+            | AArch64Inst::Csinv32(operand)
+            | AArch64Inst::Csneg32(operand) => {
+                let (rd, rn, rm) = (w(operand.rd), w(operand.rn), w(operand.rm));
+                fmt_cond_select(f, m, [rd, rn, rm], operand.cond)
+            }
+            AArch64Inst::Csel64(operand)
+            | AArch64Inst::Csinc64(operand)
+            | AArch64Inst::Csinv64(operand)
+            | AArch64Inst::Csneg64(operand) => {
+                let (rd, rn, rm) = (x(operand.rd), x(operand.rn), x(operand.rm));
+                fmt_cond_select(f, m, [rd, rn, rm], operand.cond)
+            }
+            AArch64Inst::CcmnRegVar32(operand) | AArch64Inst::CcmpRegVar32(operand) => write!(
+                f,
+                "{m} {}, {}, #{}, {}",
+                w(operand.rn),
+                w(operand.rm),
+                operand.nzcv,
+                CONDITIONS[operand.cond as usize]
+            ),
+            AArch64Inst::CcmnRegVar64(operand) | AArch64Inst::CcmpRegVar64(operand) => write!(
+                f,
+                "{m} {}, {}, #{}, {}",
+                x(operand.rn),
+                x(operand.rm),
+                operand.nzcv,
+                CONDITIONS[operand.cond as usize]
+            ),
+            AArch64Inst::CcmnImmVar32(operand) | AArch64Inst::CcmpImmVar32(operand) => write!(
+                f,
+                "{m} {}, #{}, #{}, {}",
+                w(operand.rn),
+                operand.imm5,
+                operand.nzcv,
+                CONDITIONS[operand.cond as usize]
+            ),
+            AArch64Inst::CcmnImmVar64(operand) | AArch64Inst::CcmpImmVar64(operand) => write!(
+                f,
+                "{m} {}, #{}, #{}, {}",
+                x(operand.rn),
+                operand.imm5,
+                operand.nzcv,
+                CONDITIONS[operand.cond as usize]
+            ),
+            AArch64Inst::Rmif(operand) => {
+                write!(
+                    f,
+                    "{m} {}, #{}, #{}",
+                    x(operand.rn),
+                    operand.imm6,
+                    operand.mask
+                )
+            }
+            AArch64Inst::SetfVar8(operand) | AArch64Inst::SetfVar16(operand) => {
+                write!(f, "{m} {}", w(operand.rn))
+            }
+
+            // Branches, exceptions and system instructions
+            AArch64Inst::BImm(operand) | AArch64Inst::BlImm(operand) => {
+                let offset = sign_extend((operand.imm26 as u64) << 2, 28) as i64;
+                write!(f, "{m} {}", target(offset))
+            }
+            AArch64Inst::BCond(operand) | AArch64Inst::BcCond(operand) => {
+                let offset = sign_extend((operand.imm19 as u64) << 2, 21) as i64;
+                let cond = CONDITIONS[operand.cond as usize];
+                write!(f, "{m}.{cond} {}", target(offset))
+            }
+            AArch64Inst::Cbz32(operand) | AArch64Inst::Cbnz32(operand) => {
+                let offset = sign_extend((operand.imm19 as u64) << 2, 21) as i64;
+                write!(f, "{m} {}, {}", w(operand.rt), target(offset))
+            }
+            AArch64Inst::Cbz64(operand) | AArch64Inst::Cbnz64(operand) => {
+                let offset = sign_extend((operand.imm19 as u64) << 2, 21) as i64;
+                write!(f, "{m} {}, {}", x(operand.rt), target(offset))
+            }
+            AArch64Inst::Tbz(operand) | AArch64Inst::Tbnz(operand) => {
+                let rt = if operand.b5 == 1 {
+                    x(operand.rt)
+                } else {
+                    w(operand.rt)
+                };
+                let bit = operand.b5 << 5 | operand.b40;
+                let offset = sign_extend((operand.imm14 as u64) << 2, 16) as i64;
+                write!(f, "{m} {rt}, #{bit}, {}", target(offset))
+            }
+            AArch64Inst::Br(operand) | AArch64Inst::Blr(operand) => {
+                write!(f, "{m} {}", x(operand.rn))
+            }
+            AArch64Inst::Ret(operand) => match operand.rn {
+                AArch64RegisterId::X(30) => f.write_str(m),
+                rn => write!(f, "{m} {}", x(rn)),
+            },
+            AArch64Inst::ERet(_) | AArch64Inst::Drps(_) => f.write_str(m),
+            AArch64Inst::Svc(operand)
+            | AArch64Inst::Hvc(operand)
+            | AArch64Inst::Smc(operand)
+            | AArch64Inst::Brk(operand)
+            | AArch64Inst::Hlt(operand)
+            | AArch64Inst::TCancle(operand) => write!(f, "{m} #{}", Hex(operand.imm16 as u64, 1)),
+            AArch64Inst::DcpS1(operand)
+            | AArch64Inst::DcpS2(operand)
+            | AArch64Inst::DcpS3(operand) => match operand.imm16 {
+                0 => f.write_str(m),
+                imm16 => write!(f, "{m} #{imm16:#x}"),
+            },
+            AArch64Inst::Udf(operand) => write!(f, "{m} #{}", operand.imm16),
+            AArch64Inst::Clrex(operand) | AArch64Inst::Isb(operand) => match operand.crm {
+                0b1111 => f.write_str(m),
+                crm => write!(f, "{m} #{crm}"),
+            },
+            AArch64Inst::DsbEncoding(operand) => match operand.crm {
+                0b0000 => f.write_str("ssbb"),
+                0b0100 => f.write_str("pssbb"),
+                crm => write!(f, "{m} {}", BarrierOption(crm)),
+            },
+            AArch64Inst::Dmb(operand) => write!(f, "{m} {}", BarrierOption(operand.crm)),
+            AArch64Inst::Hint(operand) => write!(f, "{m} #{}", operand.crm << 3 | operand.op2),
+            AArch64Inst::Nop
+            | AArch64Inst::Yield
+            | AArch64Inst::Wfe
+            | AArch64Inst::Wfi
+            | AArch64Inst::Sev
+            | AArch64Inst::Sevl
+            | AArch64Inst::Xpaclri
+            | AArch64Inst::Pacia1716Var
+            | AArch64Inst::Pacib1716Var
+            | AArch64Inst::Autia1716Var
+            | AArch64Inst::Autib1716Var
+            | AArch64Inst::PaciazVar
+            | AArch64Inst::PaciaspVar
+            | AArch64Inst::PacibzVar
+            | AArch64Inst::PacibspVar
+            | AArch64Inst::AutiazVar
+            | AArch64Inst::AutiaspVar
+            | AArch64Inst::AutibzVar
+            | AArch64Inst::AutibspVar => f.write_str(m),
+            AArch64Inst::Wfet(operand)
+            | AArch64Inst::Wfit(operand)
+            | AArch64Inst::Tstart(operand)
+            | AArch64Inst::Ttest(operand) => write!(f, "{m} {}", x(operand.rt)),
+            AArch64Inst::MsrImm(operand) => fmt_msr_imm(f, operand),
+            AArch64Inst::Cfinv(operand) => match operand.crm {
+                0 => f.write_str(m),
+                _ => fmt_msr_imm(f, operand),
+            },
+            AArch64Inst::Xaflag(_) | AArch64Inst::Axflag(_) => f.write_str(m),
+            AArch64Inst::Mrs(operand) => {
+                write!(f, "{m} {}, {}", x(operand.rt), SysRegName(operand))
+            }
+            AArch64Inst::MsrReg(operand) => {
+                write!(f, "{m} {}, {}", SysRegName(operand), x(operand.rt))
+            }
+            AArch64Inst::Sys(operand) => fmt_sys(f, operand),
+            AArch64Inst::Sysl(operand) => write!(
+                f,
+                "{m} {}, #{}, c{}, c{}, #{}",
+                x(operand.rt),
+                operand.op1,
+                operand.crn,
+                operand.crm,
+                operand.op2
+            ),
+
+            // Loads and stores
+            AArch64Inst::LdrLitVar32(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}",
+                    w(operand.rt),
+                    target(literal(operand.imm19))
+                )
+            }
+            AArch64Inst::LdrLitVar64(operand) | AArch64Inst::LdrswLit(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}",
+                    x(operand.rt),
+                    target(literal(operand.imm19))
+                )
+            }
+            AArch64Inst::LdrLitSimdFPVar32(operand) => {
+                let rt = scalar(operand.rt, U32);
+                write!(f, "{m} {rt}, {}", target(literal(operand.imm19)))
+            }
+            AArch64Inst::LdrLitSimdFPVar64(operand) => {
+                let rt = scalar(operand.rt, U64);
+                write!(f, "{m} {rt}, {}", target(literal(operand.imm19)))
+            }
+            AArch64Inst::LdrLitSimdFPVar128(operand) => {
+                let rt = scalar(operand.rt, U128);
+                write!(f, "{m} {rt}, {}", target(literal(operand.imm19)))
+            }
+            AArch64Inst::PrfmLit(operand) => {
+                let op = PrefetchOp(operand.rt.number());
+                write!(f, "{m} {op}, {}", target(literal(operand.imm19)))
+            }
+            AArch64Inst::StrbImm(operand)
+            | AArch64Inst::LdrbImm(operand)
+            | AArch64Inst::LdrsbImm32(operand) => {
+                write!(f, "{m} {}, {}", w(operand.rt), imm12_address(operand, U8))
+            }
+            AArch64Inst::LdrsbImm64(operand) => {
+                write!(f, "{m} {}, {}", x(operand.rt), imm12_address(operand, U8))
+            }
+            AArch64Inst::StrhImm(operand)
+            | AArch64Inst::LdrhImm(operand)
+            | AArch64Inst::LdrshImm32(operand) => {
+                write!(f, "{m} {}, {}", w(operand.rt), imm12_address(operand, U16))
+            }
+            AArch64Inst::LdrshImm64(operand) => {
+                write!(f, "{m} {}, {}", x(operand.rt), imm12_address(operand, U16))
+            }
+            AArch64Inst::StrImm32(operand) | AArch64Inst::LdrImm32(operand) => {
+                write!(f, "{m} {}, {}", w(operand.rt), imm12_address(operand, U32))
+            }
+            AArch64Inst::LdrswImm(operand) => {
+                write!(f, "{m} {}, {}", x(operand.rt), imm12_address(operand, U32))
+            }
+            AArch64Inst::StrImm64(operand) | AArch64Inst::LdrImm64(operand) => {
+                write!(f, "{m} {}, {}", x(operand.rt), imm12_address(operand, U64))
+            }
+            AArch64Inst::PrfmImm(operand) => {
+                let op = PrefetchOp(operand.rt.number());
+                write!(f, "{m} {op}, {}", imm12_address(operand, U64))
+            }
+            AArch64Inst::StrImmSimdFP8(operand) | AArch64Inst::LdrImmSimdFP8(operand) => {
+                fmt_ld_st_imm_simd_fp(f, m, operand, U8)
+            }
+            AArch64Inst::StrImmSimdFP16(operand) | AArch64Inst::LdrImmSimdFP16(operand) => {
+                fmt_ld_st_imm_simd_fp(f, m, operand, U16)
+            }
+            AArch64Inst::StrImmSimdFP32(operand) | AArch64Inst::LdrImmSimdFP32(operand) => {
+                fmt_ld_st_imm_simd_fp(f, m, operand, U32)
+            }
+            AArch64Inst::StrImmSimdFP64(operand) | AArch64Inst::LdrImmSimdFP64(operand) => {
+                fmt_ld_st_imm_simd_fp(f, m, operand, U64)
+            }
+            AArch64Inst::StrImmSimdFP128(operand) | AArch64Inst::LdrImmSimdFP128(operand) => {
+                fmt_ld_st_imm_simd_fp(f, m, operand, U128)
+            }
+            AArch64Inst::StrbRegExtReg(operand)
+            | AArch64Inst::StrbRegShiftedReg(operand)
+            | AArch64Inst::LdrbRegExtReg(operand)
+            | AArch64Inst::LdrbRegShiftedReg(operand)
+            | AArch64Inst::LdrsbRegExtReg32(operand)
+            | AArch64Inst::LdrsbRegShiftedReg32(operand) => {
+                write!(f, "{m} {}, {}", w(operand.rt), RegisterOffset(operand, U8))
+            }
+            AArch64Inst::LdrsbRegExtReg64(operand) | AArch64Inst::LdrsbRegShiftedReg64(operand) => {
+                write!(f, "{m} {}, {}", x(operand.rt), RegisterOffset(operand, U8))
+            }
+            AArch64Inst::StrhReg(operand)
+            | AArch64Inst::LdrhReg(operand)
+            | AArch64Inst::LdrshReg32(operand) => {
+                write!(f, "{m} {}, {}", w(operand.rt), RegisterOffset(operand, U16))
+            }
+            AArch64Inst::LdrshReg64(operand) => {
+                write!(f, "{m} {}, {}", x(operand.rt), RegisterOffset(operand, U16))
+            }
+            AArch64Inst::StrReg32(operand) | AArch64Inst::LdrReg32(operand) => {
+                write!(f, "{m} {}, {}", w(operand.rt), RegisterOffset(operand, U32))
+            }
+            AArch64Inst::LdrswReg(operand) => {
+                write!(f, "{m} {}, {}", x(operand.rt), RegisterOffset(operand, U32))
+            }
+            AArch64Inst::StrReg64(operand) | AArch64Inst::LdrReg64(operand) => {
+                write!(f, "{m} {}, {}", x(operand.rt), RegisterOffset(operand, U64))
+            }
+            AArch64Inst::PrfmReg(operand) => {
+                let op = PrefetchOp(operand.rt.number());
+                write!(f, "{m} {op}, {}", RegisterOffset(operand, U64))
+            }
+            AArch64Inst::StrRegSimdFP(operand) | AArch64Inst::LdrRegSimdFP(operand) => {
+                // The size of the SIMD&FP forms is encoded in `size` and `opc<1>`
+                let ty = match (operand.size, operand.opc & 0b10) {
+                    (0b00, 0b10) => U128,
+                    (size, _) => element(size),
+                };
+                let rt = scalar(operand.rt, ty);
+                write!(f, "{m} {rt}, {}", RegisterOffset(operand, ty))
+            }
+            AArch64Inst::Sturb(operand)
+            | AArch64Inst::Ldurb(operand)
+            | AArch64Inst::Ldursb32(operand)
+            | AArch64Inst::Sturh(operand)
+            | AArch64Inst::Ldurh(operand)
+            | AArch64Inst::Ldursh32(operand)
+            | AArch64Inst::Stur32(operand)
+            | AArch64Inst::Ldur32(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}",
+                    w(operand.rt),
+                    unscaled(operand.rn, operand.imm9)
+                )
+            }
+            AArch64Inst::Ldursb64(operand)
+            | AArch64Inst::Ldursh64(operand)
+            | AArch64Inst::Ldursw(operand)
+            | AArch64Inst::Stur64(operand)
+            | AArch64Inst::Ldur64(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}",
+                    x(operand.rt),
+                    unscaled(operand.rn, operand.imm9)
+                )
+            }
+            AArch64Inst::Prefum(operand) => {
+                let op = PrefetchOp(operand.rt.number());
+                write!(f, "{m} {op}, {}", unscaled(operand.rn, operand.imm9))
+            }
+            AArch64Inst::SturSimdFP8(operand) | AArch64Inst::LdurSimdFP8(operand) => {
+                let rt = scalar(operand.rt, U8);
+                write!(f, "{m} {rt}, {}", unscaled(operand.rn, operand.imm9))
+            }
+            AArch64Inst::SturSimdFP16(operand) | AArch64Inst::LdurSimdFP16(operand) => {
+                let rt = scalar(operand.rt, U16);
+                write!(f, "{m} {rt}, {}", unscaled(operand.rn, operand.imm9))
+            }
+            AArch64Inst::SturSimdFP32(operand) | AArch64Inst::LdurSimdFP32(operand) => {
+                let rt = scalar(operand.rt, U32);
+                write!(f, "{m} {rt}, {}", unscaled(operand.rn, operand.imm9))
+            }
+            AArch64Inst::SturSimdFP64(operand) | AArch64Inst::LdurSimdFP64(operand) => {
+                let rt = scalar(operand.rt, U64);
+                write!(f, "{m} {rt}, {}", unscaled(operand.rn, operand.imm9))
+            }
+            AArch64Inst::SturSimdFP128(operand) | AArch64Inst::LdurSimdFP128(operand) => {
+                let rt = scalar(operand.rt, U128);
+                write!(f, "{m} {rt}, {}", unscaled(operand.rn, operand.imm9))
+            }
+            AArch64Inst::Sttrb(operand)
+            | AArch64Inst::Ldtrb(operand)
+            | AArch64Inst::LdtrsbVar32(operand)
+            | AArch64Inst::Sttrh(operand)
+            | AArch64Inst::Ldtrh(operand)
+            | AArch64Inst::LdtrshVar32(operand)
+            | AArch64Inst::SttrVar32(operand)
+            | AArch64Inst::LdtrVar32(operand)
+            | AArch64Inst::Stlurb(operand)
+            | AArch64Inst::Ldapurb(operand)
+            | AArch64Inst::LdapursbVar32(operand)
+            | AArch64Inst::Stlurh(operand)
+            | AArch64Inst::Ldapurh(operand)
+            | AArch64Inst::LdapurshVar32(operand)
+            | AArch64Inst::StlurVar32(operand)
+            | AArch64Inst::LdapurVar32(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}",
+                    w(operand.rt),
+                    unscaled(operand.rn, operand.imm9)
+                )
+            }
+            AArch64Inst::LdtrsbVar64(operand)
+            | AArch64Inst::LdtrshVar64(operand)
+            | AArch64Inst::Ldtrsw(operand)
+            | AArch64Inst::SttrVar64(operand)
+            | AArch64Inst::LdtrVar64(operand)
+            | AArch64Inst::LdapursbVar64(operand)
+            | AArch64Inst::LdapurshVar64(operand)
+            | AArch64Inst::Ldapursw(operand)
+            | AArch64Inst::StlurVar64(operand)
+            | AArch64Inst::LdapurVar64(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}",
+                    x(operand.rt),
+                    unscaled(operand.rn, operand.imm9)
+                )
+            }
+            AArch64Inst::StpVar32(operand) | AArch64Inst::LdpVar32(operand) => {
+                let address = pair_address(operand, U32);
+                write!(f, "{m} {}, {}, {address}", w(operand.rt), w(operand.rt2))
+            }
+            AArch64Inst::Ldpsw(operand) => {
+                let address = pair_address(operand, U32);
+                write!(f, "{m} {}, {}, {address}", x(operand.rt), x(operand.rt2))
+            }
+            AArch64Inst::StpVar64(operand) | AArch64Inst::LdpVar64(operand) => {
+                let address = pair_address(operand, U64);
+                write!(f, "{m} {}, {}, {address}", x(operand.rt), x(operand.rt2))
+            }
+            AArch64Inst::Stgp(operand) => {
+                let address = pair_address(operand, U128);
+                write!(f, "{m} {}, {}, {address}", x(operand.rt), x(operand.rt2))
+            }
+            AArch64Inst::StpSimdFPVar32(operand) | AArch64Inst::LdpSimdFPVar32(operand) => {
+                fmt_ld_st_pair_simd_fp(f, m, operand, U32)
+            }
+            AArch64Inst::StpSimdFPVar64(operand) | AArch64Inst::LdpSimdFPVar64(operand) => {
+                fmt_ld_st_pair_simd_fp(f, m, operand, U64)
+            }
+            AArch64Inst::StpSimdFpVar128(operand) | AArch64Inst::LdpSimdFpVar128(operand) => {
+                fmt_ld_st_pair_simd_fp(f, m, operand, U128)
+            }
+            AArch64Inst::StnpVar32(operand) | AArch64Inst::LdnpVar32(operand) => {
+                let address = no_alloc_pair_address(operand, U32);
+                write!(f, "{m} {}, {}, {address}", w(operand.rt), w(operand.rt2))
+            }
+            AArch64Inst::StnpVar64(operand) | AArch64Inst::LdnpVar64(operand) => {
+                let address = no_alloc_pair_address(operand, U64);
+                write!(f, "{m} {}, {}, {address}", x(operand.rt), x(operand.rt2))
+            }
+            AArch64Inst::StnpSimdFPVar32(operand) | AArch64Inst::LdnpSimdFPVar32(operand) => {
+                let (rt, rt2) = (scalar(operand.rt, U32), scalar(operand.rt2, U32));
+                write!(
+                    f,
+                    "{m} {rt}, {rt2}, {}",
+                    no_alloc_pair_address(operand, U32)
+                )
+            }
+            AArch64Inst::StnpSimdFPVar64(operand) | AArch64Inst::LdnpSimdFPVar64(operand) => {
+                let (rt, rt2) = (scalar(operand.rt, U64), scalar(operand.rt2, U64));
+                write!(
+                    f,
+                    "{m} {rt}, {rt2}, {}",
+                    no_alloc_pair_address(operand, U64)
+                )
+            }
+            AArch64Inst::StnpSimdFPVar128(operand) | AArch64Inst::LdnpSimdFPVar128(operand) => {
+                let (rt, rt2) = (scalar(operand.rt, U128), scalar(operand.rt2, U128));
+                write!(
+                    f,
+                    "{m} {rt}, {rt2}, {}",
+                    no_alloc_pair_address(operand, U128)
+                )
+            }
+            AArch64Inst::StgEncoding(operand)
+            | AArch64Inst::StzgEncoding(operand)
+            | AArch64Inst::St2gEncoding(operand)
+            | AArch64Inst::Stz2gEncoding(operand) => {
+                let indexing = match operand.op2 {
+                    0b01 => Indexing::PostIndex,
+                    0b11 => Indexing::PreIndex,
+                    _ => Indexing::Offset,
+                };
+                let offset = sign_extend(operand.imm9 as u64, 9) as i64 * 16;
+                let address = Address {
+                    rn: operand.rn,
+                    offset,
+                    indexing,
+                };
+                write!(f, "{m} {}, {address}", x(operand.rt))
+            }
+            AArch64Inst::Ldg(operand)
+            | AArch64Inst::Ldgm(operand)
+            | AArch64Inst::Stgm(operand)
+            | AArch64Inst::Stzgm(operand) => {
+                let offset = sign_extend(operand.imm9 as u64, 9) as i64 * 16;
+                let address = Address {
+                    rn: operand.rn,
+                    offset,
+                    indexing: Indexing::Offset,
+                };
+                write!(f, "{m} {}, {address}", x(operand.rt))
+            }
+
+            // Exclusive, ordered and atomic accesses
+            AArch64Inst::Stxrb(operand)
+            | AArch64Inst::Stxrh(operand)
+            | AArch64Inst::StxrVar32(operand)
+            | AArch64Inst::Stlxrb(operand)
+            | AArch64Inst::Stlxrh(operand)
+            | AArch64Inst::StlxrVar32(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}, [{}]",
+                    w(operand.rs),
+                    w(operand.rt),
+                    x(operand.rn)
+                )
+            }
+            AArch64Inst::StxrVar64(operand) | AArch64Inst::StlxrVar64(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}, [{}]",
+                    w(operand.rs),
+                    x(operand.rt),
+                    x(operand.rn)
+                )
+            }
+            AArch64Inst::Ldxrb(operand)
+            | AArch64Inst::Ldxrh(operand)
+            | AArch64Inst::LdxrVar32(operand)
+            | AArch64Inst::Ldaxrb(operand)
+            | AArch64Inst::Ldaxrh(operand)
+            | AArch64Inst::LdaxrVar32(operand)
+            | AArch64Inst::Stlrb(operand)
+            | AArch64Inst::Ldarb(operand)
+            | AArch64Inst::Stlrh(operand)
+            | AArch64Inst::Ldarh(operand)
+            | AArch64Inst::StlrVar32(operand)
+            | AArch64Inst::LdarVar32(operand) => {
+                write!(f, "{m} {}, [{}]", w(operand.rt), x(operand.rn))
+            }
+            AArch64Inst::LdxrVar64(operand)
+            | AArch64Inst::LdaxrVar64(operand)
+            | AArch64Inst::StlrVar64(operand)
+            | AArch64Inst::LdarVar64(operand) => {
+                write!(f, "{m} {}, [{}]", x(operand.rt), x(operand.rn))
+            }
+            AArch64Inst::StxpVar32(operand) | AArch64Inst::StlxpVar32(operand) => write!(
+                f,
+                "{m} {}, {}, {}, [{}]",
+                w(operand.rs),
+                w(operand.rt),
+                w(operand.rt2),
+                x(operand.rn)
+            ),
+            AArch64Inst::StxpVar64(operand) | AArch64Inst::StlxpVar64(operand) => write!(
+                f,
+                "{m} {}, {}, {}, [{}]",
+                w(operand.rs),
+                x(operand.rt),
+                x(operand.rt2),
+                x(operand.rn)
+            ),
+            AArch64Inst::LdxpVar32(operand) | AArch64Inst::LdaxpVar32(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}, [{}]",
+                    w(operand.rt),
+                    w(operand.rt2),
+                    x(operand.rn)
+                )
+            }
+            AArch64Inst::LdxpVar64(operand) | AArch64Inst::LdaxpVar64(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}, [{}]",
+                    x(operand.rt),
+                    x(operand.rt2),
+                    x(operand.rn)
+                )
+            }
+            AArch64Inst::CaspVar32(operand)
+            | AArch64Inst::CasplVar32(operand)
+            | AArch64Inst::CaspaVar32(operand)
+            | AArch64Inst::CaspalVar32(operand) => fmt_casp(f, m, operand, U32),
+            AArch64Inst::CaspVar64(operand)
+            | AArch64Inst::CasplVar64(operand)
+            | AArch64Inst::CaspaVar64(operand)
+            | AArch64Inst::CaspalVar64(operand) => fmt_casp(f, m, operand, U64),
+            AArch64Inst::Ldaprb(operand)
+            | AArch64Inst::Ldaprh(operand)
+            | AArch64Inst::LdaprVar32(operand) => {
+                write!(f, "{m} {}, [{}]", w(operand.rt), x(operand.rn))
+            }
+            AArch64Inst::LdaprVar64(operand)
+            | AArch64Inst::St64b(operand)
+            | AArch64Inst::Ld64b(operand) => {
+                write!(f, "{m} {}, [{}]", x(operand.rt), x(operand.rn))
+            }
+            AArch64Inst::St64bv(operand) | AArch64Inst::St64bv0(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}, [{}]",
+                    x(operand.rs),
+                    x(operand.rt),
+                    x(operand.rn)
+                )
+            }
+            AArch64Inst::CasVar64(operand)
+            | AArch64Inst::CaslVar64(operand)
+            | AArch64Inst::CasaVar64(operand)
+            | AArch64Inst::CasalVar64(operand)
+            | AArch64Inst::SwpVar64(operand)
+            | AArch64Inst::SwplVar64(operand)
+            | AArch64Inst::SwpaVar64(operand)
+            | AArch64Inst::SwpalVar64(operand)
+            | AArch64Inst::LdaddVar64(operand)
+            | AArch64Inst::LdclrVar64(operand)
+            | AArch64Inst::LdeorVar64(operand)
+            | AArch64Inst::LdsetVar64(operand)
+            | AArch64Inst::LdsmaxVar64(operand)
+            | AArch64Inst::LdsminVar64(operand)
+            | AArch64Inst::LdumaxVar64(operand)
+            | AArch64Inst::LduminVar64(operand)
+            | AArch64Inst::LdaddlVar64(operand)
+            | AArch64Inst::LdclrlVar64(operand)
+            | AArch64Inst::LdeorlVar64(operand)
+            | AArch64Inst::LdsetlVar64(operand)
+            | AArch64Inst::LdsmaxlVar64(operand)
+            | AArch64Inst::LdsminlVar64(operand)
+            | AArch64Inst::LdumaxlVar64(operand)
+            | AArch64Inst::LduminlVar64(operand)
+            | AArch64Inst::LdaddaVar64(operand)
+            | AArch64Inst::LdclraVar64(operand)
+            | AArch64Inst::LdeoraVar64(operand)
+            | AArch64Inst::LdsetaVar64(operand)
+            | AArch64Inst::LdsmaxaVar64(operand)
+            | AArch64Inst::LdsminaVar64(operand)
+            | AArch64Inst::LdumaxaVar64(operand)
+            | AArch64Inst::LduminaVar64(operand)
+            | AArch64Inst::LdaddalVar64(operand)
+            | AArch64Inst::LdclralVar64(operand)
+            | AArch64Inst::LdeoralVar64(operand)
+            | AArch64Inst::LdsetalVar64(operand)
+            | AArch64Inst::LdsmaxalVar64(operand)
+            | AArch64Inst::LdsminalVar64(operand)
+            | AArch64Inst::LdumaxalVar64(operand)
+            | AArch64Inst::LduminalVar64(operand) => fmt_atomic(f, m, operand, U64),
+            // The remaining compare and swap, swap and atomic memory operations are 32-bit or
+            // narrower
+            AArch64Inst::Casb(operand)
+            | AArch64Inst::Caslb(operand)
+            | AArch64Inst::Casab(operand)
+            | AArch64Inst::Casalb(operand)
+            | AArch64Inst::Cash(operand)
+            | AArch64Inst::Caslh(operand)
+            | AArch64Inst::Casah(operand)
+            | AArch64Inst::Casalh(operand)
+            | AArch64Inst::CasVar32(operand)
+            | AArch64Inst::CaslVar32(operand)
+            | AArch64Inst::CasaVar32(operand)
+            | AArch64Inst::CasalVar32(operand)
+            | AArch64Inst::LdaddbVar(operand)
+            | AArch64Inst::LdclrbVar(operand)
+            | AArch64Inst::LdeorbVar(operand)
+            | AArch64Inst::LdsetbVar(operand)
+            | AArch64Inst::LdsmaxbVar(operand)
+            | AArch64Inst::LdsminbVar(operand)
+            | AArch64Inst::LdumaxbVar(operand)
+            | AArch64Inst::LduminbVar(operand)
+            | AArch64Inst::SwpbVar(operand)
+            | AArch64Inst::LdaddlbVar(operand)
+            | AArch64Inst::LdclrlbVar(operand)
+            | AArch64Inst::LdeorlbVar(operand)
+            | AArch64Inst::LdsetlbVar(operand)
+            | AArch64Inst::LdsmaxlbVar(operand)
+            | AArch64Inst::LdsminlbVar(operand)
+            | AArch64Inst::LdumaxlbVar(operand)
+            | AArch64Inst::LduminlbVar(operand)
+            | AArch64Inst::SwplbVar(operand)
+            | AArch64Inst::LdaddabVar(operand)
+            | AArch64Inst::LdclrabVar(operand)
+            | AArch64Inst::LdeorabVar(operand)
+            | AArch64Inst::LdsetabVar(operand)
+            | AArch64Inst::LdsmaxabVar(operand)
+            | AArch64Inst::LdsminabVar(operand)
+            | AArch64Inst::LdumaxabVar(operand)
+            | AArch64Inst::LduminabVar(operand)
+            | AArch64Inst::SwpabVar(operand)
+            | AArch64Inst::LdaddalbVar(operand)
+            | AArch64Inst::LdclralbVar(operand)
+            | AArch64Inst::LdeoralbVar(operand)
+            | AArch64Inst::LdsetalbVar(operand)
+            | AArch64Inst::LdsmaxalbVar(operand)
+            | AArch64Inst::LdsminalbVar(operand)
+            | AArch64Inst::LdumaxalbVar(operand)
+            | AArch64Inst::LduminalbVar(operand)
+            | AArch64Inst::SwpalbVar(operand)
+            | AArch64Inst::LdaddhVar(operand)
+            | AArch64Inst::LdclrhVar(operand)
+            | AArch64Inst::LdeorhVar(operand)
+            | AArch64Inst::LdsethVar(operand)
+            | AArch64Inst::LdsmaxhVar(operand)
+            | AArch64Inst::LdsminhVar(operand)
+            | AArch64Inst::LdumaxhVar(operand)
+            | AArch64Inst::LduminhVar(operand)
+            | AArch64Inst::SwphVar(operand)
+            | AArch64Inst::LdaddlhVar(operand)
+            | AArch64Inst::LdclrlhVar(operand)
+            | AArch64Inst::LdeorlhVar(operand)
+            | AArch64Inst::LdsetlhVar(operand)
+            | AArch64Inst::LdsmaxlhVar(operand)
+            | AArch64Inst::LdsminlhVar(operand)
+            | AArch64Inst::LdumaxlhVar(operand)
+            | AArch64Inst::LduminlhVar(operand)
+            | AArch64Inst::SwplhVar(operand)
+            | AArch64Inst::LdaddahVar(operand)
+            | AArch64Inst::LdclrahVar(operand)
+            | AArch64Inst::LdeorahVar(operand)
+            | AArch64Inst::LdsetahVar(operand)
+            | AArch64Inst::LdsmaxahVar(operand)
+            | AArch64Inst::LdsminahVar(operand)
+            | AArch64Inst::LdumaxahVar(operand)
+            | AArch64Inst::LduminahVar(operand)
+            | AArch64Inst::SwpahVar(operand)
+            | AArch64Inst::LdaddalhVar(operand)
+            | AArch64Inst::LdclralhVar(operand)
+            | AArch64Inst::LdeoralhVar(operand)
+            | AArch64Inst::LdsetalhVar(operand)
+            | AArch64Inst::LdsmaxalhVar(operand)
+            | AArch64Inst::LdsminalhVar(operand)
+            | AArch64Inst::LdumaxalhVar(operand)
+            | AArch64Inst::LduminalhVar(operand)
+            | AArch64Inst::SwpalhVar(operand)
+            | AArch64Inst::LdaddVar32(operand)
+            | AArch64Inst::LdclrVar32(operand)
+            | AArch64Inst::LdeorVar32(operand)
+            | AArch64Inst::LdsetVar32(operand)
+            | AArch64Inst::LdsmaxVar32(operand)
+            | AArch64Inst::LdsminVar32(operand)
+            | AArch64Inst::LdumaxVar32(operand)
+            | AArch64Inst::LduminVar32(operand)
+            | AArch64Inst::SwpVar32(operand)
+            | AArch64Inst::LdaddlVar32(operand)
+            | AArch64Inst::LdclrlVar32(operand)
+            | AArch64Inst::LdeorlVar32(operand)
+            | AArch64Inst::LdsetlVar32(operand)
+            | AArch64Inst::LdsmaxlVar32(operand)
+            | AArch64Inst::LdsminlVar32(operand)
+            | AArch64Inst::LdumaxlVar32(operand)
+            | AArch64Inst::LduminlVar32(operand)
+            | AArch64Inst::SwplVar32(operand)
+            | AArch64Inst::LdaddaVar32(operand)
+            | AArch64Inst::LdclraVar32(operand)
+            | AArch64Inst::LdeoraVar32(operand)
+            | AArch64Inst::LdsetaVar32(operand)
+            | AArch64Inst::LdsmaxaVar32(operand)
+            | AArch64Inst::LdsminaVar32(operand)
+            | AArch64Inst::LdumaxaVar32(operand)
+            | AArch64Inst::LduminaVar32(operand)
+            | AArch64Inst::SwpaVar32(operand)
+            | AArch64Inst::LdaddalVar32(operand)
+            | AArch64Inst::LdclralVar32(operand)
+            | AArch64Inst::LdeoralVar32(operand)
+            | AArch64Inst::LdsetalVar32(operand)
+            | AArch64Inst::LdsmaxalVar32(operand)
+            | AArch64Inst::LdsminalVar32(operand)
+            | AArch64Inst::LdumaxalVar32(operand)
+            | AArch64Inst::LduminalVar32(operand)
+            | AArch64Inst::SwpalVar32(operand) => fmt_atomic(f, m, operand, U32),
+
+            // Scalar floating-point
+            AArch64Inst::FmovGeneralTopHalfOf128To64(operand) => {
+                write!(f, "{m} {}, {}", x(operand.rd), Element(operand.rn, 3, 1))
+            }
+            AArch64Inst::FmovGeneral64toTopHalfOf128(operand) => {
+                write!(f, "{m} {}, {}", Element(operand.rd, 3, 1), x(operand.rn))
+            }
+            AArch64Inst::FcselSinglePrecisionVar(operand) => write!(
+                f,
+                "{m} {}, {}, {}, {}",
+                scalar(operand.rd, U32),
+                scalar(operand.rn, U32),
+                scalar(operand.rm, U32),
+                CONDITIONS[operand.cond as usize]
+            ),
+            AArch64Inst::FcselDoublePrecisionVar(operand) => write!(
+                f,
+                "{m} {}, {}, {}, {}",
+                scalar(operand.rd, U64),
+                scalar(operand.rn, U64),
+                scalar(operand.rm, U64),
+                CONDITIONS[operand.cond as usize]
+            ),
+            AArch64Inst::Fcmp(operand) | AArch64Inst::Fcmpe(operand) => {
+                let ty = match operand.ptype {
+                    0b00 => U32,
+                    0b01 => U64,
+                    _ => U16,
+                };
+                let rn = scalar(operand.rn, ty);
+                match operand.opcode2 & 0b1000 {
+                    0 => write!(f, "{m} {rn}, {}", scalar(operand.rm, ty)),
+                    _ => write!(f, "{m} {rn}, #0.0"),
+                }
+            }
+            AArch64Inst::FmovScalarImmSinglePrecisionVar(operand) => {
+                let value = f32::from_bits(vfp_expand_imm(operand.imm8, 32) as u32);
+                write!(f, "{m} {}, #{value:.8}", scalar(operand.rd, U32))
+            }
+            AArch64Inst::FmovScalarImmDoublePrecisionVar(operand) => {
+                let value = f64::from_bits(vfp_expand_imm(operand.imm8, 64));
+                write!(f, "{m} {}, #{value:.8}", scalar(operand.rd, U64))
+            }
+            AArch64Inst::ScvtfScalarFixedPt32ToSinglePrecision(operand)
+            | AArch64Inst::UcvtfScalarFixedPt32ToSinglePrecision(operand) => {
+                let fbits = 64 - operand.scale;
+                write!(
+                    f,
+                    "{m} {}, {}, #{fbits}",
+                    scalar(operand.rd, U32),
+                    w(operand.rn)
+                )
+            }
+            AArch64Inst::ScvtfScalarFixedPt32ToDoublePrecision(operand)
+            | AArch64Inst::UcvtfScalarFixedPt32ToDoublePrecision(operand) => {
+                let fbits = 64 - operand.scale;
+                write!(
+                    f,
+                    "{m} {}, {}, #{fbits}",
+                    scalar(operand.rd, U64),
+                    w(operand.rn)
+                )
+            }
+            AArch64Inst::ScvtfScalarFixedPt64ToSinglePrecision(operand)
+            | AArch64Inst::UcvtfScalarFixedPt64ToSinglePrecision(operand) => {
+                let fbits = 64 - operand.scale;
+                write!(
+                    f,
+                    "{m} {}, {}, #{fbits}",
+                    scalar(operand.rd, U32),
+                    x(operand.rn)
+                )
+            }
+            AArch64Inst::ScvtfScalarFixedPt64ToDoublePrecision(operand)
+            | AArch64Inst::UcvtfScalarFixedPt64ToDoublePrecision(operand) => {
+                let fbits = 64 - operand.scale;
+                write!(
+                    f,
+                    "{m} {}, {}, #{fbits}",
+                    scalar(operand.rd, U64),
+                    x(operand.rn)
+                )
+            }
+            AArch64Inst::FcvtzsScalarFixedPtSinglePrecisionTo32(operand)
+            | AArch64Inst::FcvtzuScalarFixedPtSinglePrecisionTo32(operand) => {
+                let fbits = 64 - operand.scale;
+                write!(
+                    f,
+                    "{m} {}, {}, #{fbits}",
+                    w(operand.rd),
+                    scalar(operand.rn, U32)
+                )
+            }
+            AArch64Inst::FcvtzsScalarFixedPtDoublePrecisionTo32(operand)
+            | AArch64Inst::FcvtzuScalarFixedPtDoublePrecisionTo32(operand) => {
+                let fbits = 64 - operand.scale;
+                write!(
+                    f,
+                    "{m} {}, {}, #{fbits}",
+                    w(operand.rd),
+                    scalar(operand.rn, U64)
+                )
+            }
+            AArch64Inst::FcvtzsScalarFixedPtSinglePrecisionTo64(operand)
+            | AArch64Inst::FcvtzuScalarFixedPtSinglePrecisionTo64(operand) => {
+                let fbits = 64 - operand.scale;
+                write!(
+                    f,
+                    "{m} {}, {}, #{fbits}",
+                    x(operand.rd),
+                    scalar(operand.rn, U32)
+                )
+            }
+            AArch64Inst::FcvtzsScalarFixedPtDoublePrecisionTo64(operand)
+            | AArch64Inst::FcvtzuScalarFixedPtDoublePrecisionTo64(operand) => {
+                let fbits = 64 - operand.scale;
+                write!(
+                    f,
+                    "{m} {}, {}, #{fbits}",
+                    x(operand.rd),
+                    scalar(operand.rn, U64)
+                )
+            }
+
+            // Advanced SIMD
+            AArch64Inst::AndVec(operand)
+            | AArch64Inst::BicVecReg(operand)
+            | AArch64Inst::OrnVec(operand)
+            | AArch64Inst::EorVec(operand)
+            | AArch64Inst::Bsl(operand)
+            | AArch64Inst::Bit(operand)
+            | AArch64Inst::Bif(operand) => fmt_three_same(f, m, operand, arrangement(operand.q, 0)),
+            AArch64Inst::OrrVecReg(operand) => {
+                let arrangement = arrangement(operand.q, 0);
+                match operand.rn == operand.rm {
+                    true => write!(
+                        f,
+                        "mov {}, {}",
+                        Vector(operand.rd, arrangement),
+                        Vector(operand.rn, arrangement)
+                    ),
+                    false => fmt_three_same(f, m, operand, arrangement),
+                }
+            }
+            AArch64Inst::FmaxnmVec(operand)
+            | AArch64Inst::FmlaVec(operand)
+            | AArch64Inst::FaddVec(operand)
+            | AArch64Inst::Fmulx(operand)
+            | AArch64Inst::FcmeqReg(operand)
+            | AArch64Inst::FmaxVec(operand)
+            | AArch64Inst::Frecps(operand)
+            | AArch64Inst::FminnmVec(operand)
+            | AArch64Inst::FmlsVec(operand)
+            | AArch64Inst::FsubVec(operand)
+            | AArch64Inst::FminVec(operand)
+            | AArch64Inst::Frsqrts(operand)
+            | AArch64Inst::FmaxnmpVec(operand)
+            | AArch64Inst::FaddpVec(operand)
+            | AArch64Inst::FmulVec(operand)
+            | AArch64Inst::FcmgeReg(operand)
+            | AArch64Inst::Facge(operand)
+            | AArch64Inst::FmaxpVec(operand)
+            | AArch64Inst::FdivVec(operand)
+            | AArch64Inst::FminnmpVec(operand)
+            | AArch64Inst::Fabd(operand)
+            | AArch64Inst::FcmgtReg(operand)
+            | AArch64Inst::Facgt(operand)
+            | AArch64Inst::FminpVec(operand) => {
+                fmt_three_same(f, m, operand, float_arrangement(operand.q, operand.size))
+            }
+            AArch64Inst::Shadd(operand)
+            | AArch64Inst::Sqadd(operand)
+            | AArch64Inst::Srhadd(operand)
+            | AArch64Inst::Shsub(operand)
+            | AArch64Inst::Sqsub(operand)
+            | AArch64Inst::CmgtReg(operand)
+            | AArch64Inst::CmgeReg(operand)
+            | AArch64Inst::Sshl(operand)
+            | AArch64Inst::SqshlReg(operand)
+            | AArch64Inst::Srshl(operand)
+            | AArch64Inst::Sqrshl(operand)
+            | AArch64Inst::Smax(operand)
+            | AArch64Inst::Smin(operand)
+            | AArch64Inst::Sabd(operand)
+            | AArch64Inst::Saba(operand)
+            | AArch64Inst::AddVec(operand)
+            | AArch64Inst::Cmtst(operand)
+            | AArch64Inst::MlaVec(operand)
+            | AArch64Inst::MulVec(operand)
+            | AArch64Inst::Smaxp(operand)
+            | AArch64Inst::Sminp(operand)
+            | AArch64Inst::SqdmulhVec(operand)
+            | AArch64Inst::AddpVec(operand)
+            | AArch64Inst::Uhadd(operand)
+            | AArch64Inst::Uqadd(operand)
+            | AArch64Inst::Urhadd(operand)
+            | AArch64Inst::Uhsub(operand)
+            | AArch64Inst::Uqsub(operand)
+            | AArch64Inst::CmhiReg(operand)
+            | AArch64Inst::CmhsReg(operand)
+            | AArch64Inst::Ushl(operand)
+            | AArch64Inst::UqshlReg(operand)
+            | AArch64Inst::Urshl(operand)
+            | AArch64Inst::Uqrshl(operand)
+            | AArch64Inst::Umax(operand)
+            | AArch64Inst::Umin(operand)
+            | AArch64Inst::Uabd(operand)
+            | AArch64Inst::Uaba(operand)
+            | AArch64Inst::SubVec(operand)
+            | AArch64Inst::CmeqReg(operand)
+            | AArch64Inst::MlsVec(operand)
+            | AArch64Inst::Pmul(operand)
+            | AArch64Inst::Umaxp(operand)
+            | AArch64Inst::Uminp(operand)
+            | AArch64Inst::SqrdmulhVec(operand)
+            | AArch64Inst::Uzp1(operand)
+            | AArch64Inst::Trn1(operand)
+            | AArch64Inst::Zip1(operand)
+            | AArch64Inst::Uzp2(operand)
+            | AArch64Inst::Trn2(operand)
+            | AArch64Inst::Zip2(operand) => {
+                fmt_three_same(f, m, operand, arrangement(operand.q, operand.size))
+            }
+            AArch64Inst::Tbl(operand) | AArch64Inst::Tbx(operand) => {
+                let arrangement = arrangement(operand.q, 0);
+                let table = RegisterList::multiple(operand.rn.number(), operand.len + 1, 1, 0);
+                let rd = Vector(operand.rd, arrangement);
+                let rm = Vector(operand.rm, arrangement);
+                write!(f, "{m} {rd}, {table}, {rm}")
+            }
+            AArch64Inst::Saddl(operand)
+            | AArch64Inst::Ssubl(operand)
+            | AArch64Inst::Sabal(operand)
+            | AArch64Inst::Sabdl(operand)
+            | AArch64Inst::Smlal(operand)
+            | AArch64Inst::Sqdmlal(operand)
+            | AArch64Inst::Smlsl(operand)
+            | AArch64Inst::Sqdmlsl(operand)
+            | AArch64Inst::Smull(operand)
+            | AArch64Inst::Sqdmull(operand)
+            | AArch64Inst::Uaddl(operand)
+            | AArch64Inst::Usubl(operand)
+            | AArch64Inst::Uabal(operand)
+            | AArch64Inst::Uabdl(operand)
+            | AArch64Inst::Umlal(operand)
+            | AArch64Inst::Umlsl(operand)
+            | AArch64Inst::Umull(operand) => {
+                let (wide, narrow) = (
+                    arrangement(1, operand.size + 1),
+                    arrangement(operand.q, operand.size),
+                );
+                fmt_three_different(f, m, operand, [wide, narrow, narrow])
+            }
+            AArch64Inst::Saddw(operand)
+            | AArch64Inst::Ssubw(operand)
+            | AArch64Inst::Uaddw(operand)
+            | AArch64Inst::Usubw(operand) => {
+                let (wide, narrow) = (
+                    arrangement(1, operand.size + 1),
+                    arrangement(operand.q, operand.size),
+                );
+                fmt_three_different(f, m, operand, [wide, wide, narrow])
+            }
+            AArch64Inst::Addhn(operand)
+            | AArch64Inst::Subhn(operand)
+            | AArch64Inst::Raddhn(operand)
+            | AArch64Inst::Rsubhn(operand) => {
+                let (wide, narrow) = (
+                    arrangement(1, operand.size + 1),
+                    arrangement(operand.q, operand.size),
+                );
+                fmt_three_different(f, m, operand, [narrow, wide, wide])
+            }
+            AArch64Inst::Pmull(operand) => {
+                let wide = match operand.size {
+                    0b11 => "1q",
+                    size => arrangement(1, size + 1),
+                };
+                let narrow = arrangement(operand.q, operand.size);
+                fmt_three_different(f, m, operand, [wide, narrow, narrow])
+            }
+            AArch64Inst::Rev64(operand)
+            | AArch64Inst::Rev16Vec(operand)
+            | AArch64Inst::Rev32Vec(operand)
+            | AArch64Inst::Suqadd(operand)
+            | AArch64Inst::ClsVec(operand)
+            | AArch64Inst::Cnt(operand)
+            | AArch64Inst::Sqabs(operand)
+            | AArch64Inst::Abs(operand)
+            | AArch64Inst::ClzVec(operand)
+            | AArch64Inst::Sqneg(operand)
+            | AArch64Inst::NegVec(operand)
+            | AArch64Inst::Usqadd(operand) => {
+                let arrangement = arrangement(operand.q, operand.size);
+                fmt_two_misc(f, m, operand, arrangement, arrangement)
+            }
+            // NOT is always displayed with its MVN alias
+            AArch64Inst::Not(operand) | AArch64Inst::RbitVec(operand) => {
+                let m = if m == "not" { "mvn" } else { m };
+                let arrangement = arrangement(operand.q, 0);
+                fmt_two_misc(f, m, operand, arrangement, arrangement)
+            }
+            AArch64Inst::CmgtZero(operand)
+            | AArch64Inst::CmeqZero(operand)
+            | AArch64Inst::CmltZero(operand)
+            | AArch64Inst::CmgeZero(operand)
+            | AArch64Inst::CmleZero(operand) => {
+                let arrangement = arrangement(operand.q, operand.size);
+                fmt_two_misc(f, m, operand, arrangement, arrangement)?;
+                f.write_str(", #0")
+            }
+            AArch64Inst::Saddlp(operand)
+            | AArch64Inst::Sadalp(operand)
+            | AArch64Inst::Uaddlp(operand)
+            | AArch64Inst::Uadalp(operand) => {
+                let ta = arrangement(operand.q, operand.size + 1);
+                fmt_two_misc(f, m, operand, ta, arrangement(operand.q, operand.size))
+            }
+            AArch64Inst::XtnXtn2(operand)
+            | AArch64Inst::Sqxtn(operand)
+            | AArch64Inst::Sqxtun(operand)
+            | AArch64Inst::Uqxtn(operand) => {
+                let tb = arrangement(operand.q, operand.size);
+                write!(f, "{m}{}", upper(operand.q))?;
+                fmt_two_misc(f, "", operand, tb, arrangement(1, operand.size + 1))
+            }
+            AArch64Inst::Shll(operand) => {
+                let ta = arrangement(1, operand.size + 1);
+                write!(f, "{m}{}", upper(operand.q))?;
+                fmt_two_misc(f, "", operand, ta, arrangement(operand.q, operand.size))?;
+                write!(f, ", #{}", 8 << operand.size)
+            }
+            AArch64Inst::Fcvtn(operand) | AArch64Inst::Fcvtxn(operand) => {
+                let sz = operand.size & 1;
+                let tb = arrangement(operand.q, 1 + sz);
+                write!(f, "{m}{}", upper(operand.q))?;
+                fmt_two_misc(f, "", operand, tb, arrangement(1, 2 + sz))
+            }
+            AArch64Inst::Fcvtl(operand) => {
+                let sz = operand.size & 1;
+                let ta = arrangement(1, 2 + sz);
+                write!(f, "{m}{}", upper(operand.q))?;
+                fmt_two_misc(f, "", operand, ta, arrangement(operand.q, 1 + sz))
+            }
+            AArch64Inst::FrintnVec(operand)
+            | AArch64Inst::FrintmVec(operand)
+            | AArch64Inst::FcvtnsVec(operand)
+            | AArch64Inst::FcvtmsVec(operand)
+            | AArch64Inst::FcvtasVec(operand)
+            | AArch64Inst::ScvtfVecInt(operand)
+            | AArch64Inst::FabsVec(operand)
+            | AArch64Inst::FrintpVec(operand)
+            | AArch64Inst::FrintzVec(operand)
+            | AArch64Inst::FcvtpsVec(operand)
+            | AArch64Inst::FcvtzsVecInt(operand)
+            | AArch64Inst::Urecpe(operand)
+            | AArch64Inst::Frecpe(operand)
+            | AArch64Inst::FrintaVec(operand)
+            | AArch64Inst::FrintxVec(operand)
+            | AArch64Inst::FcvtnuVec(operand)
+            | AArch64Inst::FcvtmuVec(operand)
+            | AArch64Inst::FcvtauVec(operand)
+            | AArch64Inst::UcvtfVecInt(operand)
+            | AArch64Inst::FnegVec(operand)
+            | AArch64Inst::FrintiVec(operand)
+            | AArch64Inst::FcvtpuVec(operand)
+            | AArch64Inst::FcvtzuVecInt(operand)
+            | AArch64Inst::Ursqrte(operand)
+            | AArch64Inst::Frsqrte(operand)
+            | AArch64Inst::FsqrtVec(operand) => {
+                let arrangement = float_arrangement(operand.q, operand.size);
+                fmt_two_misc(f, m, operand, arrangement, arrangement)
+            }
+            AArch64Inst::FcmgtZero(operand)
+            | AArch64Inst::FcmeqZero(operand)
+            | AArch64Inst::FcmltZero(operand)
+            | AArch64Inst::FcmgeZero(operand)
+            | AArch64Inst::FcmleZero(operand) => {
+                let arrangement = float_arrangement(operand.q, operand.size);
+                fmt_two_misc(f, m, operand, arrangement, arrangement)?;
+                f.write_str(", #0.0")
+            }
+            AArch64Inst::Saddlv(operand) | AArch64Inst::Uaddlv(operand) => {
+                let rd = scalar(operand.rd, element(operand.size + 1));
+                let rn = Vector(operand.rn, arrangement(operand.q, operand.size));
+                write!(f, "{m} {rd}, {rn}")
+            }
+            AArch64Inst::Smaxv(operand)
+            | AArch64Inst::Sminv(operand)
+            | AArch64Inst::Addv(operand)
+            | AArch64Inst::Umaxv(operand)
+            | AArch64Inst::Uminv(operand) => {
+                let rd = scalar(operand.rd, element(operand.size));
+                let rn = Vector(operand.rn, arrangement(operand.q, operand.size));
+                write!(f, "{m} {rd}, {rn}")
+            }
+            AArch64Inst::FmaxnvmEncoding(operand)
+            | AArch64Inst::FmaxvEncoding(operand)
+            | AArch64Inst::FminnmvEncoding(operand)
+            | AArch64Inst::FminvEncoding(operand) => {
+                let rn = Vector(operand.rn, arrangement(operand.q, 2));
+                write!(f, "{m} {}, {rn}", scalar(operand.rd, U32))
+            }
+            AArch64Inst::SqaddScalar(operand)
+            | AArch64Inst::SqsubScalar(operand)
+            | AArch64Inst::CmgtRegScalar(operand)
+            | AArch64Inst::CmgeRegScalar(operand)
+            | AArch64Inst::SshlScalar(operand)
+            | AArch64Inst::SqshlRegScalar(operand)
+            | AArch64Inst::SrshlScalar(operand)
+            | AArch64Inst::SqrshlScalar(operand)
+            | AArch64Inst::AddScalar(operand)
+            | AArch64Inst::CmtstScalar(operand)
+            | AArch64Inst::SqdmulhScalar(operand)
+            | AArch64Inst::UqaddScalar(operand)
+            | AArch64Inst::UqsubScalar(operand)
+            | AArch64Inst::CmhiRegScalar(operand)
+            | AArch64Inst::CmhsRegScalar(operand)
+            | AArch64Inst::UshlScalar(operand)
+            | AArch64Inst::UqshlRegScalar(operand)
+            | AArch64Inst::UrshlScalar(operand)
+            | AArch64Inst::UqrshlScalar(operand)
+            | AArch64Inst::SubScalar(operand)
+            | AArch64Inst::CmeqRegScalar(operand)
+            | AArch64Inst::SqrdmulhScalar(operand) => {
+                fmt_scalar_three_same(f, m, operand, element(operand.size))
+            }
+            AArch64Inst::FmulxScalar(operand)
+            | AArch64Inst::FcmeqRegScalar(operand)
+            | AArch64Inst::FrecpsScalar(operand)
+            | AArch64Inst::FrsqrtsScalar(operand)
+            | AArch64Inst::FcmgeRegScalar(operand)
+            | AArch64Inst::FacgeScalar(operand)
+            | AArch64Inst::FabdScalar(operand)
+            | AArch64Inst::FcmgtRegScalar(operand)
+            | AArch64Inst::FacgtScalar(operand) => {
+                fmt_scalar_three_same(f, m, operand, element(2 + (operand.size & 1)))
+            }
+            AArch64Inst::SuqaddScalar(operand)
+            | AArch64Inst::SqabsScalar(operand)
+            | AArch64Inst::AbsScalar(operand)
+            | AArch64Inst::UsqaddScalar(operand)
+            | AArch64Inst::SqnegScalar(operand)
+            | AArch64Inst::NegScalar(operand) => {
+                fmt_scalar_two_misc(f, m, operand, element(operand.size))
+            }
+            AArch64Inst::CmgtZeroScalar(operand)
+            | AArch64Inst::CmeqZeroScalar(operand)
+            | AArch64Inst::CmltZeroScalar(operand)
+            | AArch64Inst::CmgeZeroScalar(operand)
+            | AArch64Inst::CmleZeroScalar(operand) => {
+                fmt_scalar_two_misc(f, m, operand, element(operand.size))?;
+                f.write_str(", #0")
+            }
+            AArch64Inst::SqxtnScalar(operand)
+            | AArch64Inst::SqxtunScalar(operand)
+            | AArch64Inst::UqxtnScalar(operand) => {
+                let rd = scalar(operand.rd, element(operand.size));
+                let rn = scalar(operand.rn, element(operand.size + 1));
+                write!(f, "{m} {rd}, {rn}")
+            }
+            AArch64Inst::FcvtnsScalar(operand)
+            | AArch64Inst::FcvtmsScalar(operand)
+            | AArch64Inst::FcvtasScalar(operand)
+            | AArch64Inst::ScvtfScalarInt(operand)
+            | AArch64Inst::FcvtpsScalar(operand)
+            | AArch64Inst::FcvtzsScalarInt(operand)
+            | AArch64Inst::FrecpeScalar(operand)
+            | AArch64Inst::Frecpx(operand)
+            | AArch64Inst::FcvtnuScalar(operand)
+            | AArch64Inst::FcvtmuScalar(operand)
+            | AArch64Inst::FcvtauScalar(operand)
+            | AArch64Inst::UcvtfScalarInt(operand)
+            | AArch64Inst::FcvtpuScalar(operand)
+            | AArch64Inst::FcvtzuScalarInt(operand)
+            | AArch64Inst::FrsqrteScalar(operand) => {
+                fmt_scalar_two_misc(f, m, operand, element(2 + (operand.size & 1)))
+            }
+            AArch64Inst::FcmgtZeroScalar(operand)
+            | AArch64Inst::FcmeqZeroScalar(operand)
+            | AArch64Inst::FcmltZeroScalar(operand)
+            | AArch64Inst::FcmgeZeroScalar(operand)
+            | AArch64Inst::FcmleZeroScalar(operand) => {
+                fmt_scalar_two_misc(f, m, operand, element(2 + (operand.size & 1)))?;
+                f.write_str(", #0.0")
+            }
+            AArch64Inst::FcvtxnScalar(operand) => {
+                let (rd, rn) = (scalar(operand.rd, U32), scalar(operand.rn, U64));
+                write!(f, "{m} {rd}, {rn}")
+            }
+            AArch64Inst::AddpScalar(operand) => {
+                let rn = Vector(operand.rn, arrangement(1, 3));
+                write!(f, "{m} {}, {rn}", scalar(operand.rd, U64))
+            }
+            AArch64Inst::FaddpScalarEncoding(operand)
+            | AArch64Inst::FmaxnmpScalarEncoding(operand)
+            | AArch64Inst::FmaxpScalarEncoding(operand)
+            | AArch64Inst::FminnmpScalarEncoding(operand)
+            | AArch64Inst::FminpScalarEncoding(operand) => {
+                let sz = operand.size & 1;
+                let rn = Vector(operand.rn, arrangement(sz, 2 + sz));
+                write!(f, "{m} {}, {rn}", scalar(operand.rd, element(2 + sz)))
+            }
+            AArch64Inst::Ext(operand) => {
+                let arrangement = arrangement(operand.q, 0);
+                write!(
+                    f,
+                    "{m} {}, {}, {}, #{}",
+                    Vector(operand.rd, arrangement),
+                    Vector(operand.rn, arrangement),
+                    Vector(operand.rm, arrangement),
+                    operand.imm4
+                )
+            }
+            AArch64Inst::DupElement(operand) => {
+                let size = operand.imm5.trailing_zeros() as u8;
+                let rd = Vector(operand.rd, arrangement(operand.q, size));
+                let rn = Element(operand.rn, size, operand.imm5 >> (size + 1));
+                write!(f, "{m} {rd}, {rn}")
+            }
+            AArch64Inst::DupGeneral(operand) => {
+                let size = operand.imm5.trailing_zeros() as u8;
+                let rd = Vector(operand.rd, arrangement(operand.q, size));
+                let rn = if size == 3 {
+                    x(operand.rn)
+                } else {
+                    w(operand.rn)
+                };
+                write!(f, "{m} {rd}, {rn}")
+            }
+            AArch64Inst::Smov(operand) => {
+                let size = operand.imm5.trailing_zeros() as u8;
+                let rd = if operand.q == 1 {
+                    x(operand.rd)
+                } else {
+                    w(operand.rd)
+                };
+                let rn = Element(operand.rn, size, operand.imm5 >> (size + 1));
+                write!(f, "{m} {rd}, {rn}")
+            }
+            // UMOV of a word or a doubleword is displayed as MOV
+            AArch64Inst::Umov(operand) => {
+                let size = operand.imm5.trailing_zeros() as u8;
+                let rn = Element(operand.rn, size, operand.imm5 >> (size + 1));
+                match size {
+                    0b11 => write!(f, "mov {}, {rn}", x(operand.rd)),
+                    0b10 => write!(f, "mov {}, {rn}", w(operand.rd)),
+                    _ => write!(f, "{m} {}, {rn}", w(operand.rd)),
+                }
+            }
+            // INS is always displayed with its MOV alias
+            AArch64Inst::InsGeneral(operand) => {
+                let size = operand.imm5.trailing_zeros() as u8;
+                let rd = Element(operand.rd, size, operand.imm5 >> (size + 1));
+                let rn = if size == 3 {
+                    x(operand.rn)
+                } else {
+                    w(operand.rn)
+                };
+                write!(f, "mov {rd}, {rn}")
+            }
+            AArch64Inst::InsElement(operand) => {
+                let size = operand.imm5.trailing_zeros() as u8;
+                let rd = Element(operand.rd, size, operand.imm5 >> (size + 1));
+                let rn = Element(operand.rn, size, operand.imm4 >> size);
+                write!(f, "mov {rd}, {rn}")
+            }
+            AArch64Inst::MoviShiftedImmVar32(operand)
+            | AArch64Inst::OrrVecImmVar32(operand)
+            | AArch64Inst::MvniShiftedImmVar32(operand)
+            | AArch64Inst::BicVecImmVar32(operand) => {
+                let rd = Vector(operand.rd, arrangement(operand.q, 2));
+                write!(f, "{m} {rd}, #{}", modified_imm8(operand))?;
+                match (operand.cmode >> 1 & 0b11) * 8 {
+                    0 => Ok(()),
+                    shift => write!(f, ", lsl #{shift}"),
+                }
+            }
+            AArch64Inst::MoviShiftedImmVar16(operand)
+            | AArch64Inst::OrrVecImmVar16(operand)
+            | AArch64Inst::MvniShiftedImmVar16(operand)
+            | AArch64Inst::BicVecImmVar16(operand) => {
+                let rd = Vector(operand.rd, arrangement(operand.q, 1));
+                write!(f, "{m} {rd}, #{}", modified_imm8(operand))?;
+                match (operand.cmode >> 1 & 0b1) * 8 {
+                    0 => Ok(()),
+                    shift => write!(f, ", lsl #{shift}"),
+                }
+            }
+            AArch64Inst::MoviShiftingOnesVar32(operand)
+            | AArch64Inst::MvniShiftingOnesVar32(operand) => {
+                let rd = Vector(operand.rd, arrangement(operand.q, 2));
+                let shift = ((operand.cmode & 1) + 1) * 8;
+                write!(f, "{m} {rd}, #{}, msl #{shift}", modified_imm8(operand))
+            }
+            AArch64Inst::MoviVar8(operand) => {
+                let rd = Vector(operand.rd, arrangement(operand.q, 0));
+                write!(f, "{m} {rd}, #{}", modified_imm8(operand))
+            }
+            AArch64Inst::MoviScalarVar64(operand) => {
+                let imm = adv_simd_expand_imm(operand.op, operand.cmode, modified_imm8(operand));
+                write!(f, "{m} {}, #{}", scalar(operand.rd, U64), Hex(imm, 14))
+            }
+            AArch64Inst::MoviVectorVar64(operand) => {
+                let imm = adv_simd_expand_imm(operand.op, operand.cmode, modified_imm8(operand));
+                write!(
+                    f,
+                    "{m} {}, #{}",
+                    Vector(operand.rd, arrangement(1, 3)),
+                    Hex(imm, 14)
+                )
+            }
+            AArch64Inst::FmovVecImmSinglePrecisionVar(operand) => {
+                let imm = vfp_expand_imm(modified_imm8(operand), 32) as u32;
+                let rd = Vector(operand.rd, arrangement(operand.q, 2));
+                write!(f, "{m} {rd}, #{:.8}", f32::from_bits(imm))
+            }
+            AArch64Inst::FmovVecImmDoublePrecisionVar(operand) => {
+                let imm = vfp_expand_imm(modified_imm8(operand), 64);
+                let rd = Vector(operand.rd, arrangement(1, 3));
+                write!(f, "{m} {rd}, #{:.8}", f64::from_bits(imm))
+            }
+            AArch64Inst::Sshr(operand)
+            | AArch64Inst::Ssra(operand)
+            | AArch64Inst::Srshr(operand)
+            | AArch64Inst::Srsra(operand)
+            | AArch64Inst::Ushr(operand)
+            | AArch64Inst::Usra(operand)
+            | AArch64Inst::Urshr(operand)
+            | AArch64Inst::Ursra(operand)
+            | AArch64Inst::Sri(operand)
+            | AArch64Inst::ScvtfVecFixedPt(operand)
+            | AArch64Inst::FcvtzsVecFixedPt(operand)
+            | AArch64Inst::UcvtfVecFixedPt(operand)
+            | AArch64Inst::FcvtzuVecFixedPt(operand) => {
+                let (size, shift) = right_shift(operand);
+                let arrangement = arrangement(operand.q, size);
+                fmt_shift_by_imm(f, m, operand, [arrangement, arrangement], shift)
+            }
+            AArch64Inst::Shl(operand)
+            | AArch64Inst::SqshlImm(operand)
+            | AArch64Inst::Sli(operand)
+            | AArch64Inst::Sqshlu(operand)
+            | AArch64Inst::UqshlImm(operand) => {
+                let (size, shift) = left_shift(operand);
+                let arrangement = arrangement(operand.q, size);
+                fmt_shift_by_imm(f, m, operand, [arrangement, arrangement], shift)
+            }
+            AArch64Inst::Shrn(operand)
+            | AArch64Inst::Rshrn(operand)
+            | AArch64Inst::Sqshrn(operand)
+            | AArch64Inst::Sqrshrn(operand)
+            | AArch64Inst::Sqshrun(operand)
+            | AArch64Inst::Sqrshrun(operand)
+            | AArch64Inst::Uqshrn(operand)
+            | AArch64Inst::Uqrshrn(operand) => {
+                let (size, shift) = right_shift(operand);
+                let arrangements = [arrangement(operand.q, size), arrangement(1, size + 1)];
+                write!(f, "{m}{}", upper(operand.q))?;
+                fmt_shift_by_imm(f, "", operand, arrangements, shift)
+            }
+            AArch64Inst::Sshll(operand) | AArch64Inst::Ushll(operand) => {
+                let (size, shift) = left_shift(operand);
+                let arrangements = [arrangement(1, size + 1), arrangement(operand.q, size)];
+                write!(f, "{m}{}", upper(operand.q))?;
+                fmt_shift_by_imm(f, "", operand, arrangements, shift)
+            }
+            AArch64Inst::MulByElem(operand)
+            | AArch64Inst::MlaByElem(operand)
+            | AArch64Inst::MlsByElem(operand)
+            | AArch64Inst::SqdmulhByElem(operand)
+            | AArch64Inst::SqrdmulhByElem(operand) => {
+                let arrangement = arrangement(operand.q, operand.size);
+                fmt_by_elem(f, m, operand, operand.size, [arrangement, arrangement])
+            }
+            AArch64Inst::SmlalByElem(operand)
+            | AArch64Inst::SqdmlalByElem(operand)
+            | AArch64Inst::SmlslByElem(operand)
+            | AArch64Inst::SqdmlslByElem(operand)
+            | AArch64Inst::SmullByElem(operand)
+            | AArch64Inst::SqdmullByElem(operand)
+            | AArch64Inst::UmlalByElem(operand)
+            | AArch64Inst::UmlslByElem(operand)
+            | AArch64Inst::UmullByElem(operand) => {
+                let size = operand.size;
+                let arrangements = [arrangement(1, size + 1), arrangement(operand.q, size)];
+                write!(f, "{m}{}", upper(operand.q))?;
+                fmt_by_elem(f, "", operand, size, arrangements)
+            }
+            AArch64Inst::FmlaByElemEncoding(operand)
+            | AArch64Inst::FmlsByElemEncoding(operand)
+            | AArch64Inst::FmulByElemEncoding(operand)
+            | AArch64Inst::FmulxByElemEncoding(operand) => {
+                let size = 2 + (operand.size & 1);
+                let arrangement = arrangement(operand.q, size);
+                fmt_by_elem(f, m, operand, size, [arrangement, arrangement])
+            }
+            AArch64Inst::SqdmlalByElemScalar(operand)
+            | AArch64Inst::SqdmlslByElemScalar(operand)
+            | AArch64Inst::SqdmullByElemScalar(operand) => {
+                let size = operand.size;
+                let (rd, rn) = (
+                    scalar(operand.rd, element(size + 1)),
+                    scalar(operand.rn, element(size)),
+                );
+                write!(
+                    f,
+                    "{m} {rd}, {rn}, {}",
+                    indexed_elem(operand.rm, operand.m, operand.h, operand.l, size)
+                )
+            }
+            AArch64Inst::SqdmulhByElemScalar(operand)
+            | AArch64Inst::SqrdmulhByElemScalar(operand) => {
+                let size = operand.size;
+                let (rd, rn) = (
+                    scalar(operand.rd, element(size)),
+                    scalar(operand.rn, element(size)),
+                );
+                write!(
+                    f,
+                    "{m} {rd}, {rn}, {}",
+                    indexed_elem(operand.rm, operand.m, operand.h, operand.l, size)
+                )
+            }
+            AArch64Inst::FmlaByElemScalarEncoding(operand)
+            | AArch64Inst::FmlsByElemScalarEncoding(operand)
+            | AArch64Inst::FmulByElemScalarEncoding(operand)
+            | AArch64Inst::FmulxByElemScalarEncoding(operand) => {
+                let size = 2 + (operand.size & 1);
+                let (rd, rn) = (
+                    scalar(operand.rd, element(size)),
+                    scalar(operand.rn, element(size)),
+                );
+                write!(
+                    f,
+                    "{m} {rd}, {rn}, {}",
+                    indexed_elem(operand.rm, operand.m, operand.h, operand.l, size)
+                )
+            }
+            AArch64Inst::FaddScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FdivScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FmaxScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FmaxnmScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FminScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FminnmScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FmulScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FnmulScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FsubScalarDoublePrecisionVar(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}, {}",
+                    scalar(operand.rd, U64),
+                    scalar(operand.rn, U64),
+                    scalar(operand.rm, U64)
+                )
+            }
+            AArch64Inst::FaddScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FdivScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FmaxScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FmaxnmScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FminScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FminnmScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FmulScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FnmulScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FsubScalarSinglePrecisionVar(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}, {}",
+                    scalar(operand.rd, U32),
+                    scalar(operand.rn, U32),
+                    scalar(operand.rm, U32)
+                )
+            }
+            AArch64Inst::FabsScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FmovRegDoublePrecisionVar(operand)
+            | AArch64Inst::FnegScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FrintaScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FrintiScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FrintmScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FrintnScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FrintpScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FrintxScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FrintzScalarDoublePrecisionVar(operand)
+            | AArch64Inst::FsqrtScalarDoublePrecisionVar(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}",
+                    scalar(operand.rd, U64),
+                    scalar(operand.rn, U64)
+                )
+            }
+            AArch64Inst::FabsScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FmovRegSinglePrecisionVar(operand)
+            | AArch64Inst::FnegScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FrintaScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FrintiScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FrintmScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FrintnScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FrintpScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FrintxScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FrintzScalarSinglePrecisionVar(operand)
+            | AArch64Inst::FsqrtScalarSinglePrecisionVar(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}",
+                    scalar(operand.rd, U32),
+                    scalar(operand.rn, U32)
+                )
+            }
+            AArch64Inst::FabsScalarHalfPrecisionVar(operand)
+            | AArch64Inst::FmovRegHalfPrecisionVar(operand)
+            | AArch64Inst::FnegScalarHalfPrecisionVar(operand)
+            | AArch64Inst::FrintaScalarHalfPrecisionVar(operand)
+            | AArch64Inst::FrintiScalarHalfPrecisionVar(operand)
+            | AArch64Inst::FrintmScalarHalfPrecisionVar(operand)
+            | AArch64Inst::FrintnScalarHalfPrecisionVar(operand)
+            | AArch64Inst::FrintpScalarHalfPrecisionVar(operand)
+            | AArch64Inst::FrintxScalarHalfPrecisionVar(operand)
+            | AArch64Inst::FrintzScalarHalfPrecisionVar(operand)
+            | AArch64Inst::FsqrtScalarHalfPrecisionVar(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}",
+                    scalar(operand.rd, U16),
+                    scalar(operand.rn, U16)
+                )
+            }
+            AArch64Inst::FcvtHalfToSinglePrecisionVar(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}",
+                    scalar(operand.rd, U32),
+                    scalar(operand.rn, U16)
+                )
+            }
+            AArch64Inst::FcvtHalfToDoublePrecisionVar(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}",
+                    scalar(operand.rd, U64),
+                    scalar(operand.rn, U16)
+                )
+            }
+            AArch64Inst::FcvtDoubleToHalfPrecisionVar(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}",
+                    scalar(operand.rd, U16),
+                    scalar(operand.rn, U64)
+                )
+            }
+            AArch64Inst::FcvtDoubleToSinglePrecisionVar(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}",
+                    scalar(operand.rd, U32),
+                    scalar(operand.rn, U64)
+                )
+            }
+            AArch64Inst::FcvtSingleToDoublePrecisionVar(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}",
+                    scalar(operand.rd, U64),
+                    scalar(operand.rn, U32)
+                )
+            }
+            AArch64Inst::FcvtSingleToHalfPrecisionVar(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}",
+                    scalar(operand.rd, U16),
+                    scalar(operand.rn, U32)
+                )
+            }
+            AArch64Inst::FcvtasScalarDoublePrecisionTo32(operand)
+            | AArch64Inst::FcvtauScalarDoublePrecisionTo32(operand)
+            | AArch64Inst::FcvtmsScalarDoublePrecisionTo32(operand)
+            | AArch64Inst::FcvtmuScalarDoublePrecisionTo32(operand)
+            | AArch64Inst::FcvtnsScalarDoublePrecisionTo32(operand)
+            | AArch64Inst::FcvtnuScalarDoublePrecisionTo32(operand)
+            | AArch64Inst::FcvtpsScalarDoublePrecisionTo32(operand)
+            | AArch64Inst::FcvtpuScalarDoublePrecisionTo32(operand)
+            | AArch64Inst::FcvtzsScalarIntDoublePrecisionTo32(operand)
+            | AArch64Inst::FcvtzuScalarIntDoublePrecisionTo32(operand)
+            | AArch64Inst::Fjcvtzs(operand) => {
+                write!(f, "{m} {}, {}", w(operand.rd), scalar(operand.rn, U64))
+            }
+            AArch64Inst::FcvtasScalarDoublePrecisionTo64(operand)
+            | AArch64Inst::FcvtauScalarDoublePrecisionTo64(operand)
+            | AArch64Inst::FcvtmsScalarDoublePrecisionTo64(operand)
+            | AArch64Inst::FcvtmuScalarDoublePrecisionTo64(operand)
+            | AArch64Inst::FcvtnsScalarDoublePrecisionTo64(operand)
+            | AArch64Inst::FcvtnuScalarDoublePrecisionTo64(operand)
+            | AArch64Inst::FcvtpsScalarDoublePrecisionTo64(operand)
+            | AArch64Inst::FcvtpuScalarDoublePrecisionTo64(operand)
+            | AArch64Inst::FcvtzsScalarIntDoublePrecisionTo64(operand)
+            | AArch64Inst::FcvtzuScalarIntDoublePrecisionTo64(operand)
+            | AArch64Inst::FmovGeneralDoublePrecisionTo64(operand) => {
+                write!(f, "{m} {}, {}", x(operand.rd), scalar(operand.rn, U64))
+            }
+            AArch64Inst::FcvtasScalarSinglePrecisionTo32(operand)
+            | AArch64Inst::FcvtauScalarSinglePrecisionTo32(operand)
+            | AArch64Inst::FcvtmsScalarSinglePrecisionTo32(operand)
+            | AArch64Inst::FcvtmuScalarSinglePrecisionTo32(operand)
+            | AArch64Inst::FcvtnsScalarSinglePrecisionTo32(operand)
+            | AArch64Inst::FcvtnuScalarSinglePrecisionTo32(operand)
+            | AArch64Inst::FcvtpsScalarSinglePrecisionTo32(operand)
+            | AArch64Inst::FcvtpuScalarSinglePrecisionTo32(operand)
+            | AArch64Inst::FcvtzsScalarIntSinglePrecisionTo32(operand)
+            | AArch64Inst::FcvtzuScalarIntSinglePrecisionTo32(operand)
+            | AArch64Inst::FmovGeneralSinglePrecisionTo32(operand) => {
+                write!(f, "{m} {}, {}", w(operand.rd), scalar(operand.rn, U32))
+            }
+            AArch64Inst::FcvtasScalarSinglePrecisionTo64(operand)
+            | AArch64Inst::FcvtauScalarSinglePrecisionTo64(operand)
+            | AArch64Inst::FcvtmsScalarSinglePrecisionTo64(operand)
+            | AArch64Inst::FcvtmuScalarSinglePrecisionTo64(operand)
+            | AArch64Inst::FcvtnsScalarSinglePrecisionTo64(operand)
+            | AArch64Inst::FcvtnuScalarSinglePrecisionTo64(operand)
+            | AArch64Inst::FcvtpsScalarSinglePrecisionTo64(operand)
+            | AArch64Inst::FcvtpuScalarSinglePrecisionTo64(operand)
+            | AArch64Inst::FcvtzsScalarIntSinglePrecisionTo64(operand)
+            | AArch64Inst::FcvtzuScalarIntSinglePrecisionTo64(operand) => {
+                write!(f, "{m} {}, {}", x(operand.rd), scalar(operand.rn, U32))
+            }
+            AArch64Inst::FmovGeneral32ToSinglePrecision(operand)
+            | AArch64Inst::ScvtfScalarInt32ToSinglePrecision(operand)
+            | AArch64Inst::UcvtfScalarInt32ToSinglePrecision(operand) => {
+                write!(f, "{m} {}, {}", scalar(operand.rd, U32), w(operand.rn))
+            }
+            AArch64Inst::FmovGeneral64ToDoublePrecision(operand)
+            | AArch64Inst::ScvtfScalarInt64ToDoublePrecision(operand)
+            | AArch64Inst::UcvtfScalarInt64ToDoublePrecision(operand) => {
+                write!(f, "{m} {}, {}", scalar(operand.rd, U64), x(operand.rn))
+            }
+            // SKIP V.d[i], X ['FmovGeneral64toTopHalfOf128']
+            // SKIP X, V.d[i] ['FmovGeneralTopHalfOf128To64']
+            AArch64Inst::ScvtfScalarInt32ToDoublePrecision(operand)
+            | AArch64Inst::UcvtfScalarInt32ToDoublePrecision(operand) => {
+                write!(f, "{m} {}, {}", scalar(operand.rd, U64), w(operand.rn))
+            }
+            AArch64Inst::ScvtfScalarInt64ToSinglePrecision(operand)
+            | AArch64Inst::UcvtfScalarInt64ToSinglePrecision(operand) => {
+                write!(f, "{m} {}, {}", scalar(operand.rd, U32), x(operand.rn))
+            }
+            AArch64Inst::FmAddDoublePrecision(operand)
+            | AArch64Inst::FmSubDoublePrecision(operand)
+            | AArch64Inst::FnmAddDoublePrecision(operand)
+            | AArch64Inst::FnmSubDoublePrecision(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}, {}, {}",
+                    scalar(operand.rd, U64),
+                    scalar(operand.rn, U64),
+                    scalar(operand.rm, U64),
+                    scalar(operand.ra, U64)
+                )
+            }
+            AArch64Inst::FmAddHalfPrecision(operand)
+            | AArch64Inst::FmSubHalfPrecision(operand)
+            | AArch64Inst::FnmAddHalfPrecision(operand)
+            | AArch64Inst::FnmSubHalfPrecision(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}, {}, {}",
+                    scalar(operand.rd, U16),
+                    scalar(operand.rn, U16),
+                    scalar(operand.rm, U16),
+                    scalar(operand.ra, U16)
+                )
+            }
+            AArch64Inst::FmAddSinglePrecision(operand)
+            | AArch64Inst::FmSubSinglePrecision(operand)
+            | AArch64Inst::FnmAddSinglePrecision(operand)
+            | AArch64Inst::FnmSubSinglePrecision(operand) => {
+                write!(
+                    f,
+                    "{m} {}, {}, {}, {}",
+                    scalar(operand.rd, U32),
+                    scalar(operand.rn, U32),
+                    scalar(operand.rm, U32),
+                    scalar(operand.ra, U32)
+                )
+            }
+
+            // Advanced SIMD structure loads and stores
+            AArch64Inst::St1MulStructures1RegsVar(operand)
+            | AArch64Inst::Ld1MulStructures1RegsVar(operand) => {
+                let list = RegisterList::multiple(operand.rt, 1, operand.q, operand.size);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St2MulStructures(operand)
+            | AArch64Inst::St1MulStructures2RegsVar(operand)
+            | AArch64Inst::Ld2MulStructures(operand)
+            | AArch64Inst::Ld1MulStructures2RegsVar(operand) => {
+                let list = RegisterList::multiple(operand.rt, 2, operand.q, operand.size);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St3MulStructures(operand)
+            | AArch64Inst::St1MulStructures3RegsVar(operand)
+            | AArch64Inst::Ld3MulStructures(operand)
+            | AArch64Inst::Ld1MulStructures3RegsVar(operand) => {
+                let list = RegisterList::multiple(operand.rt, 3, operand.q, operand.size);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St4MulStructures(operand)
+            | AArch64Inst::St1MulStructures4RegsVar(operand)
+            | AArch64Inst::Ld4MulStructures(operand)
+            | AArch64Inst::Ld1MulStructures4RegsVar(operand) => {
+                let list = RegisterList::multiple(operand.rt, 4, operand.q, operand.size);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St1MulStructures1RegRegOffsetVar(operand)
+            | AArch64Inst::St1MulStructures1RegImmOffsetVar(operand)
+            | AArch64Inst::Ld1MulStructures1RegRegOffsetVar(operand)
+            | AArch64Inst::Ld1MulStructures1RegImmOffsetVar(operand) => {
+                let list = RegisterList::multiple(operand.rt, 1, operand.q, operand.size);
+                let post = PostIndex(operand.rm, 1 << (3 + operand.q));
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St2MulStructuresRegOffsetVar(operand)
+            | AArch64Inst::St1MulStructures2RegRegOffsetVar(operand)
+            | AArch64Inst::St2MulStructuresImmOffsetVar(operand)
+            | AArch64Inst::St1MulStructures2RegImmOffsetVar(operand)
+            | AArch64Inst::Ld2MulStructuresRegOffsetVar(operand)
+            | AArch64Inst::Ld1MulStructures2RegRegOffsetVar(operand)
+            | AArch64Inst::Ld2MulStructuresImmOffsetVar(operand)
+            | AArch64Inst::Ld1MulStructures2RegImmOffsetVar(operand) => {
+                let list = RegisterList::multiple(operand.rt, 2, operand.q, operand.size);
+                let post = PostIndex(operand.rm, 2 << (3 + operand.q));
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St3MulStructuresRegOffsetVar(operand)
+            | AArch64Inst::St1MulStructures3RegRegOffsetVar(operand)
+            | AArch64Inst::St3MulStructuresImmOffsetVar(operand)
+            | AArch64Inst::St1MulStructures3RegImmOffsetVar(operand)
+            | AArch64Inst::Ld3MulStructuresRegOffsetVar(operand)
+            | AArch64Inst::Ld1MulStructures3RegRegOffsetVar(operand)
+            | AArch64Inst::Ld3MulStructuresImmOffsetVar(operand)
+            | AArch64Inst::Ld1MulStructures3RegImmOffsetVar(operand) => {
+                let list = RegisterList::multiple(operand.rt, 3, operand.q, operand.size);
+                let post = PostIndex(operand.rm, 3 << (3 + operand.q));
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St4MulStructuresRegOffsetVar(operand)
+            | AArch64Inst::St1MulStructures4RegRegOffsetVar(operand)
+            | AArch64Inst::St4MulStructuresImmOffsetVar(operand)
+            | AArch64Inst::St1MulStructures4RegImmOffsetVar(operand)
+            | AArch64Inst::Ld4MulStructuresRegOffsetVar(operand)
+            | AArch64Inst::Ld1MulStructures4RegRegOffsetVar(operand)
+            | AArch64Inst::Ld4MulStructuresImmOffsetVar(operand)
+            | AArch64Inst::Ld1MulStructures4RegImmOffsetVar(operand) => {
+                let list = RegisterList::multiple(operand.rt, 4, operand.q, operand.size);
+                let post = PostIndex(operand.rm, 4 << (3 + operand.q));
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St1SingleStructureVar8(operand)
+            | AArch64Inst::Ld1SingleStructureVar8(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 0);
+                let list = RegisterList::single(operand.rt, 1, 0, index);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St1SingleStructureVar16(operand)
+            | AArch64Inst::Ld1SingleStructureVar16(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 1);
+                let list = RegisterList::single(operand.rt, 1, 1, index);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St1SingleStructureVar32(operand)
+            | AArch64Inst::Ld1SingleStructureVar32(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 2);
+                let list = RegisterList::single(operand.rt, 1, 2, index);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St1SingleStructureVar64(operand)
+            | AArch64Inst::Ld1SingleStructureVar64(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 3);
+                let list = RegisterList::single(operand.rt, 1, 3, index);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St2SingleStructureVar8(operand)
+            | AArch64Inst::Ld2SingleStructureVar8(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 0);
+                let list = RegisterList::single(operand.rt, 2, 0, index);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St2SingleStructureVar16(operand)
+            | AArch64Inst::Ld2SingleStructureVar16(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 1);
+                let list = RegisterList::single(operand.rt, 2, 1, index);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St2SingleStructureVar32(operand)
+            | AArch64Inst::Ld2SingleStructureVar32(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 2);
+                let list = RegisterList::single(operand.rt, 2, 2, index);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St2SingleStructureVar64(operand)
+            | AArch64Inst::Ld2SingleStructureVar64(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 3);
+                let list = RegisterList::single(operand.rt, 2, 3, index);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St3SingleStructureVar8(operand)
+            | AArch64Inst::Ld3SingleStructureVar8(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 0);
+                let list = RegisterList::single(operand.rt, 3, 0, index);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St3SingleStructureVar16(operand)
+            | AArch64Inst::Ld3SingleStructureVar16(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 1);
+                let list = RegisterList::single(operand.rt, 3, 1, index);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St3SingleStructureVar32(operand)
+            | AArch64Inst::Ld3SingleStructureVar32(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 2);
+                let list = RegisterList::single(operand.rt, 3, 2, index);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St3SingleStructureVar64(operand)
+            | AArch64Inst::Ld3SingleStructureVar64(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 3);
+                let list = RegisterList::single(operand.rt, 3, 3, index);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St4SingleStructureVar8(operand)
+            | AArch64Inst::Ld4SingleStructureVar8(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 0);
+                let list = RegisterList::single(operand.rt, 4, 0, index);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St4SingleStructureVar16(operand)
+            | AArch64Inst::Ld4SingleStructureVar16(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 1);
+                let list = RegisterList::single(operand.rt, 4, 1, index);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St4SingleStructureVar32(operand)
+            | AArch64Inst::Ld4SingleStructureVar32(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 2);
+                let list = RegisterList::single(operand.rt, 4, 2, index);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St4SingleStructureVar64(operand)
+            | AArch64Inst::Ld4SingleStructureVar64(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 3);
+                let list = RegisterList::single(operand.rt, 4, 3, index);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::Ld1r(operand) => {
+                let list = RegisterList::multiple(operand.rt, 1, operand.q, operand.size);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::Ld2r(operand) => {
+                let list = RegisterList::multiple(operand.rt, 2, operand.q, operand.size);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::Ld3r(operand) => {
+                let list = RegisterList::multiple(operand.rt, 3, operand.q, operand.size);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::Ld4r(operand) => {
+                let list = RegisterList::multiple(operand.rt, 4, operand.q, operand.size);
+                write!(f, "{m} {list}, [{}]", x(operand.rn))
+            }
+            AArch64Inst::St1SingleStructurePostIndexedVar8(operand)
+            | AArch64Inst::Ld1SingleStructurePostIndexedVar8(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 0);
+                let list = RegisterList::single(operand.rt, 1, 0, index);
+                let post = PostIndex(operand.rm, 1);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St1SingleStructurePostIndexedVar16(operand)
+            | AArch64Inst::Ld1SingleStructurePostIndexedVar16(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 1);
+                let list = RegisterList::single(operand.rt, 1, 1, index);
+                let post = PostIndex(operand.rm, 2);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St1SingleStructurePostIndexedVar32(operand)
+            | AArch64Inst::Ld1SingleStructurePostIndexedVar32(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 2);
+                let list = RegisterList::single(operand.rt, 1, 2, index);
+                let post = PostIndex(operand.rm, 4);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St1SingleStructurePostIndexedVar64(operand)
+            | AArch64Inst::Ld1SingleStructurePostIndexedVar64(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 3);
+                let list = RegisterList::single(operand.rt, 1, 3, index);
+                let post = PostIndex(operand.rm, 8);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St2SingleStructurePostIndexedVar8(operand)
+            | AArch64Inst::Ld2SingleStructurePostIndexedVar8(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 0);
+                let list = RegisterList::single(operand.rt, 2, 0, index);
+                let post = PostIndex(operand.rm, 2);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St2SingleStructurePostIndexedVar16(operand)
+            | AArch64Inst::Ld2SingleStructurePostIndexedVar16(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 1);
+                let list = RegisterList::single(operand.rt, 2, 1, index);
+                let post = PostIndex(operand.rm, 4);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St2SingleStructurePostIndexedVar32(operand)
+            | AArch64Inst::Ld2SingleStructurePostIndexedVar32(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 2);
+                let list = RegisterList::single(operand.rt, 2, 2, index);
+                let post = PostIndex(operand.rm, 8);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St2SingleStructurePostIndexedVar64(operand)
+            | AArch64Inst::Ld2SingleStructurePostIndexedVar64(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 3);
+                let list = RegisterList::single(operand.rt, 2, 3, index);
+                let post = PostIndex(operand.rm, 16);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St3SingleStructurePostIndexedVar8(operand)
+            | AArch64Inst::Ld3SingleStructurePostIndexedVar8(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 0);
+                let list = RegisterList::single(operand.rt, 3, 0, index);
+                let post = PostIndex(operand.rm, 3);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St3SingleStructurePostIndexedVar16(operand)
+            | AArch64Inst::Ld3SingleStructurePostIndexedVar16(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 1);
+                let list = RegisterList::single(operand.rt, 3, 1, index);
+                let post = PostIndex(operand.rm, 6);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St3SingleStructurePostIndexedVar32(operand)
+            | AArch64Inst::Ld3SingleStructurePostIndexedVar32(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 2);
+                let list = RegisterList::single(operand.rt, 3, 2, index);
+                let post = PostIndex(operand.rm, 12);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St3SingleStructurePostIndexedVar64(operand)
+            | AArch64Inst::Ld3SingleStructurePostIndexedVar64(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 3);
+                let list = RegisterList::single(operand.rt, 3, 3, index);
+                let post = PostIndex(operand.rm, 24);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St4SingleStructurePostIndexedVar8(operand)
+            | AArch64Inst::Ld4SingleStructurePostIndexedVar8(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 0);
+                let list = RegisterList::single(operand.rt, 4, 0, index);
+                let post = PostIndex(operand.rm, 4);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St4SingleStructurePostIndexedVar16(operand)
+            | AArch64Inst::Ld4SingleStructurePostIndexedVar16(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 1);
+                let list = RegisterList::single(operand.rt, 4, 1, index);
+                let post = PostIndex(operand.rm, 8);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St4SingleStructurePostIndexedVar32(operand)
+            | AArch64Inst::Ld4SingleStructurePostIndexedVar32(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 2);
+                let list = RegisterList::single(operand.rt, 4, 2, index);
+                let post = PostIndex(operand.rm, 16);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::St4SingleStructurePostIndexedVar64(operand)
+            | AArch64Inst::Ld4SingleStructurePostIndexedVar64(operand) => {
+                let index = single_index(operand.q, operand.s, operand.size, 3);
+                let list = RegisterList::single(operand.rt, 4, 3, index);
+                let post = PostIndex(operand.rm, 32);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::Ld1rPostIndexed(operand) => {
+                let list = RegisterList::multiple(operand.rt, 1, operand.q, operand.size);
+                let post = PostIndex(operand.rm, 1 << operand.size);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::Ld2rPostIndexed(operand) => {
+                let list = RegisterList::multiple(operand.rt, 2, operand.q, operand.size);
+                let post = PostIndex(operand.rm, 2 << operand.size);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::Ld3rPostIndexed(operand) => {
+                let list = RegisterList::multiple(operand.rt, 3, operand.q, operand.size);
+                let post = PostIndex(operand.rm, 3 << operand.size);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+            AArch64Inst::Ld4rPostIndexed(operand) => {
+                let list = RegisterList::multiple(operand.rt, 4, operand.q, operand.size);
+                let post = PostIndex(operand.rm, 4 << operand.size);
+                write!(f, "{m} {list}, [{}], {post}", x(operand.rn))
+            }
+        }
+    }
+}
+
+fn x(id: AArch64RegisterId) -> AArch64RegisterId {
+    id.view(IrType::U64)
+}
+
+fn w(id: AArch64RegisterId) -> AArch64RegisterId {
+    id.view(IrType::U32)
+}
+
+fn is_zero_reg(id: AArch64RegisterId) -> bool {
+    id.parent() == AArch64RegisterId::Xzr
+}
+
+/// The general purpose register `n`, 31 is the zero register.
+fn gpr(n: u8) -> AArch64RegisterId {
+    match n {
+        31 => AArch64RegisterId::Xzr,
+        n => AArch64RegisterId::X(n),
+    }
+}
+
+/// The scalar view of a SIMD&FP register, `q` for the 128-bit accesses.
+fn scalar(id: AArch64RegisterId, ty: IrType) -> AArch64RegisterId {
+    match (id, ty) {
+        (AArch64RegisterId::V(n), IrType::U128) => AArch64RegisterId::Q(n),
+        (id, ty) => id.view(ty),
+    }
+}
+
+/// The type of an element of `8 << size` bits.
+fn element(size: u8) -> IrType {
+    match size {
+        0 => IrType::U8,
+        1 => IrType::U16,
+        2 => IrType::U32,
+        3 => IrType::U64,
+        _ => IrType::U128,
+    }
+}
+
+/// A pc-relative target, the absolute address if the address of the instruction is known.
+struct Target {
+    pc: Option<u64>,
+    offset: i64,
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.pc {
+            Some(pc) => write!(f, "{:#x}", pc.wrapping_add(self.offset as u64)),
+            None => write!(f, "#{}", self.offset),
+        }
+    }
+}
+
+/// The offset of the literal loads and the compare and branch instructions.
+fn literal(imm19: u32) -> i64 {
+    sign_extend((imm19 as u64) << 2, 21) as i64
+}
+
+/// An immediate in hexadecimal with at least `digits` digits, zero is printed as plain `0`.
+struct Hex(u64, usize);
+
+impl Display for Hex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            0 => f.write_str("0"),
+            value => write!(f, "0x{value:0width$x}", width = self.1),
+        }
+    }
+}
+
+// Addressing modes
+
+/// Where a load or store accesses memory relative to its base register.
+#[derive(Clone, Copy)]
+enum Indexing {
+    /// `[base, #offset]`, the base is left unchanged
+    Offset,
+    /// `[base, #offset]!`, the address is written back to the base
+    PreIndex,
+    /// `[base], #offset`, the base plus the offset is written back to the base
+    PostIndex,
+}
+
+/// A base register with an immediate offset.
+struct Address {
+    rn: AArch64RegisterId,
+    offset: i64,
+    indexing: Indexing,
+}
+
+impl Display for Address {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let rn = x(self.rn);
+        match (self.indexing, self.offset) {
+            (Indexing::Offset, 0) => write!(f, "[{rn}]"),
+            (Indexing::Offset, offset) => write!(f, "[{rn}, #{offset}]"),
+            (Indexing::PreIndex, offset) => write!(f, "[{rn}, #{offset}]!"),
+            (Indexing::PostIndex, offset) => write!(f, "[{rn}], #{offset}"),
+        }
+    }
+}
+
+/// The address of LDR and STR with an unsigned scaled offset, pre-indexed or post-indexed.
+fn imm12_address(operand: &OpcSizeImm12RnRt, ty: IrType) -> Address {
+    let (offset, indexing) = match (operand.idxt, operand.imm12 & 0b11) {
+        (0b01, _) => (
+            (operand.imm12 as i64) << ty.size_in_bytes().trailing_zeros(),
+            Indexing::Offset,
+        ),
+        (_, 0b11) => (
+            sign_extend(operand.imm12 as u64 >> 2, 9) as i64,
+            Indexing::PreIndex,
+        ),
+        _ => (
+            sign_extend(operand.imm12 as u64 >> 2, 9) as i64,
+            Indexing::PostIndex,
+        ),
+    };
+
+    Address {
+        rn: operand.rn,
+        offset,
+        indexing,
+    }
+}
+
+/// The address of the unscaled accesses, a signed 9-bit byte offset.
+fn unscaled(rn: AArch64RegisterId, imm9: u16) -> Address {
+    Address {
+        rn,
+        offset: sign_extend(imm9 as u64, 9) as i64,
+        indexing: Indexing::Offset,
+    }
+}
+
+/// The address of LDP and STP, a signed 7-bit offset scaled by the size of a register.
+fn pair_address(operand: &LoadStoreRegPair, ty: IrType) -> Address {
+    let indexing = match operand.o {
+        0b001 => Indexing::PostIndex,
+        0b011 => Indexing::PreIndex,
+        _ => Indexing::Offset,
+    };
+
+    Address {
+        rn: operand.rn,
+        offset: sign_extend(operand.imm7 as u64, 7) as i64 * ty.size_in_bytes() as i64,
+        indexing,
+    }
+}
+
+fn no_alloc_pair_address(operand: &LdStNoAllocPairOffset, ty: IrType) -> Address {
+    Address {
+        rn: operand.rn,
+        offset: sign_extend(operand.imm7 as u64, 7) as i64 * ty.size_in_bytes() as i64,
+        indexing: Indexing::Offset,
+    }
+}
+
+/// A register offset, extended and optionally scaled by the access size, e.g. `[x0, w1, sxtw #3]`.
+struct RegisterOffset<'a>(&'a LoadStoreRegRegOffset, IrType);
+
+impl Display for RegisterOffset<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let RegisterOffset(operand, ty) = self;
+        let rn = x(operand.rn);
+        // option<0> selects a 64-bit offset register
+        let rm = match operand.option & 1 {
+            1 => x(operand.rm),
+            _ => w(operand.rm),
+        };
+        let amount = ty.size_in_bytes().trailing_zeros();
+        match (operand.option, operand.s) {
+            (0b011, 0) => write!(f, "[{rn}, {rm}]"),
+            (0b011, _) => write!(f, "[{rn}, {rm}, lsl #{amount}]"),
+            (option, 0) => write!(f, "[{rn}, {rm}, {}]", EXTENDS[option as usize]),
+            (option, _) => write!(f, "[{rn}, {rm}, {} #{amount}]", EXTENDS[option as usize]),
+        }
+    }
+}
+
+fn fmt_ld_st_imm_simd_fp(
+    f: &mut Formatter<'_>,
+    m: &str,
+    operand: &OpcSizeImm12RnRt,
+    ty: IrType,
+) -> fmt::Result {
+    let rt = scalar(operand.rt, ty);
+    write!(f, "{m} {rt}, {}", imm12_address(operand, ty))
+}
+
+fn fmt_ld_st_pair_simd_fp(
+    f: &mut Formatter<'_>,
+    m: &str,
+    operand: &LoadStoreRegPair,
+    ty: IrType,
+) -> fmt::Result {
+    let (rt, rt2) = (scalar(operand.rt, ty), scalar(operand.rt2, ty));
+    write!(f, "{m} {rt}, {rt2}, {}", pair_address(operand, ty))
+}
+
+/// The operation of the prefetch instructions encoded in `Rt`, e.g. `pldl1keep`.
+struct PrefetchOp(u8);
+
+impl Display for PrefetchOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let kind = match self.0 >> 3 {
+            0b00 => "pld",
+            0b01 => "pli",
+            0b10 => "pst",
+            _ => return write!(f, "#{}", self.0),
+        };
+        let target = match self.0 >> 1 & 0b11 {
+            0b00 => "l1",
+            0b01 => "l2",
+            0b10 => "l3",
+            _ => return write!(f, "#{}", self.0),
+        };
+        let policy = match self.0 & 1 {
+            0 => "keep",
+            _ => "strm",
+        };
+        write!(f, "{kind}{target}{policy}")
+    }
+}
+
+// Atomic accesses
+
+fn fmt_casp(
+    f: &mut Formatter<'_>,
+    m: &str,
+    operand: &CompareAndSwapPair,
+    ty: IrType,
+) -> fmt::Result {
+    let [rs, rs2, rt, rt2] =
+        [operand.rs, operand.rs + 1, operand.rt, operand.rt + 1].map(|n| gpr(n.min(31)).view(ty));
+    write!(f, "{m} {rs}, {rs2}, {rt}, {rt2}, [{}]", x(operand.rn))
+}
+
+/// CAS, SWP and the atomic memory operations. The LD<op> without acquire semantics and with the
+/// zero register as destination are displayed as ST<op>.
+fn fmt_atomic(f: &mut Formatter<'_>, m: &str, operand: &RsRnRt, ty: IrType) -> fmt::Result {
+    let (rs, rt, rn) = (operand.rs.view(ty), operand.rt.view(ty), x(operand.rn));
+    let release_or_size = m.strip_prefix("ld").and_then(|op| {
+        let op = ATOMIC_OPS.iter().find(|atomic| op.starts_with(*atomic))?;
+        Some(&m[2 + op.len()..])
+    });
+    match release_or_size {
+        Some("" | "l" | "b" | "lb" | "h" | "lh") if is_zero_reg(operand.rt) => {
+            write!(f, "st{} {rs}, [{rn}]", &m[2..])
+        }
+        _ => write!(f, "{m} {rs}, {rt}, [{rn}]"),
+    }
+}
+
+// Data processing
+
+fn fmt_add_sub_imm(
+    f: &mut Formatter<'_>,
+    m: &str,
+    operand: &ShImm12RnRd,
+    ty: IrType,
+) -> fmt::Result {
+    let (rd, rn, imm) = (operand.rd.view(ty), operand.rn.view(ty), operand.imm12);
+    let shift = if operand.sh == 1 { ", lsl #12" } else { "" };
+    let to_or_from_sp = operand.rd == AArch64RegisterId::Sp || operand.rn == AArch64RegisterId::Sp;
+    match m {
+        "add" if imm == 0 && operand.sh == 0 && to_or_from_sp => write!(f, "mov {rd}, {rn}"),
+        "adds" if is_zero_reg(operand.rd) => write!(f, "cmn {rn}, #{imm}{shift}"),
+        "subs" if is_zero_reg(operand.rd) => write!(f, "cmp {rn}, #{imm}{shift}"),
+        _ => write!(f, "{m} {rd}, {rn}, #{imm}{shift}"),
+    }
+}
+
+/// The immediate of the logical instructions, `None` for a reserved encoding.
+fn logical_imm(operand: &LogicalImm, ty: IrType) -> u64 {
+    let width = ty.size_in_bytes() as u64 * 8;
+    decode_bit_masks(operand.n, operand.imms, operand.immr, true, width)
+        .map_or(0, |(wmask, _)| wmask)
+}
+
+/// A signed immediate of `ty`, the way the MOV aliases display it.
+fn signed_imm(imm: u64, ty: IrType) -> i64 {
+    match ty {
+        IrType::U32 => imm as u32 as i32 as i64,
+        _ => imm as i64,
+    }
+}
+
+fn fmt_logical_imm(
+    f: &mut Formatter<'_>,
+    m: &str,
+    operand: &LogicalImm,
+    ty: IrType,
+) -> fmt::Result {
+    let (rd, rn, imm) = (
+        operand.rd.view(ty),
+        operand.rn.view(ty),
+        logical_imm(operand, ty),
+    );
+    let move_wide = move_wide_preferred(ty, operand.n, operand.imms, operand.immr);
+    match m {
+        "orr" if is_zero_reg(operand.rn) && !move_wide => {
+            write!(f, "mov {rd}, #{}", signed_imm(imm, ty))
+        }
+        "ands" if is_zero_reg(operand.rd) => write!(f, "tst {rn}, #{imm:#x}"),
+        _ => write!(f, "{m} {rd}, {rn}, #{imm:#x}"),
+    }
+}
+
+/// Whether the bitmask immediate can be built with a MOVZ or MOVN, MOV of the immediate is then
+/// preferred to display these. This is the MoveWidePreferred of the architecture.
+fn move_wide_preferred(ty: IrType, n: u8, imms: u8, immr: u8) -> bool {
+    let width = ty.size_in_bytes() as u8 * 8;
+    let allocated = match ty {
+        IrType::U64 => n == 1,
+        _ => n == 0 && imms & 0b100000 == 0,
+    };
+    if !allocated {
+        return false;
+    }
+
+    // A single run of ones shifted by a multiple of 16
+    if imms < 16 {
+        return (16 - immr % 16) % 16 <= 15 - imms;
+    }
+    if imms >= width - 15 {
+        return immr % 16 <= imms - (width - 15);
+    }
+    false
+}
+
+fn fmt_bitfield(f: &mut Formatter<'_>, m: &str, operand: &Bitfield, ty: IrType) -> fmt::Result {
+    let (rd, rn) = (operand.rd.view(ty), operand.rn.view(ty));
+    let (immr, imms) = (operand.immr, operand.imms);
+    let width = ty.size_in_bytes() as u8 * 8;
+    // The 32-bit forms with immr or imms above 31 are reserved and have no alias
+    if immr >= width || imms >= width {
+        return write!(f, "{m} {rd}, {rn}, #{immr}, #{imms}");
+    }
+    // The inserted field at `lsb` for the *BFIZ and BFI forms, the extracted one otherwise
+    let (lsb, field) = match imms < immr {
+        true => ((width - immr) % width, imms + 1),
+        false => (immr, imms - immr + 1),
+    };
+    match (m, immr, imms) {
+        ("sbfm", immr, imms) if imms == width - 1 => write!(f, "asr {rd}, {rn}, #{immr}"),
+        ("sbfm", 0, 7) => write!(f, "sxtb {rd}, {}", w(operand.rn)),
+        ("sbfm", 0, 15) => write!(f, "sxth {rd}, {}", w(operand.rn)),
+        ("sbfm", 0, 31) => write!(f, "sxtw {rd}, {}", w(operand.rn)),
+        ("sbfm", immr, imms) if imms < immr => write!(f, "sbfiz {rd}, {rn}, #{lsb}, #{field}"),
+        ("sbfm", ..) => write!(f, "sbfx {rd}, {rn}, #{lsb}, #{field}"),
+        ("ubfm", immr, imms) if imms != width - 1 && imms + 1 == immr => {
+            write!(f, "lsl {rd}, {rn}, #{}", width - 1 - imms)
+        }
+        ("ubfm", immr, imms) if imms == width - 1 => write!(f, "lsr {rd}, {rn}, #{immr}"),
+        ("ubfm", 0, 7) if ty == IrType::U32 => write!(f, "uxtb {rd}, {rn}"),
+        ("ubfm", 0, 15) if ty == IrType::U32 => write!(f, "uxth {rd}, {rn}"),
+        ("ubfm", immr, imms) if imms < immr => write!(f, "ubfiz {rd}, {rn}, #{lsb}, #{field}"),
+        ("ubfm", ..) => write!(f, "ubfx {rd}, {rn}, #{lsb}, #{field}"),
+        (_, immr, imms) if imms < immr && is_zero_reg(operand.rn) => {
+            write!(f, "bfc {rd}, #{lsb}, #{field}")
+        }
+        (_, immr, imms) if imms < immr => write!(f, "bfi {rd}, {rn}, #{lsb}, #{field}"),
+        _ => write!(f, "bfxil {rd}, {rn}, #{lsb}, #{field}"),
+    }
+}
+
+fn fmt_move_wide(f: &mut Formatter<'_>, m: &str, operand: &HwImm16Rd, ty: IrType) -> fmt::Result {
+    let (rd, imm16, shift) = (operand.rd.view(ty), operand.imm16, operand.hw as u32 * 16);
+    // The MOV alias is not used when the immediate could be encoded with a lower shift
+    let movable = imm16 != 0 || shift == 0;
+    let value = (imm16 as u64) << shift;
+    match m {
+        "movz" if movable => write!(f, "mov {rd}, #{}", signed_imm(value, ty)),
+        "movn" if movable && !(ty == IrType::U32 && imm16 == 0xffff) => {
+            write!(f, "mov {rd}, #{}", signed_imm(!value, ty))
+        }
+        _ if shift == 0 => write!(f, "{m} {rd}, #{imm16}"),
+        _ => write!(f, "{m} {rd}, #{imm16}, lsl #{shift}"),
+    }
+}
+
+fn fmt_extract(f: &mut Formatter<'_>, m: &str, operand: &ExtractImm, ty: IrType) -> fmt::Result {
+    let (rd, rn, rm) = (
+        operand.rd.view(ty),
+        operand.rn.view(ty),
+        operand.rm.view(ty),
+    );
+    match operand.rn == operand.rm {
+        true => write!(f, "ror {rd}, {rn}, #{}", operand.imms),
+        false => write!(f, "{m} {rd}, {rn}, {rm}, #{}", operand.imms),
+    }
+}
+
+/// A shifted register operand, e.g. `x1, lsr #3`. LSL by 0 is omitted.
+struct ShiftedRegister(AArch64RegisterId, u8, u8);
+
+impl Display for ShiftedRegister {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ShiftedRegister(rm, 0, 0) => write!(f, "{rm}"),
+            ShiftedRegister(rm, shift, amount) => {
+                write!(f, "{rm}, {} #{amount}", SHIFTS[*shift as usize])
+            }
+        }
+    }
+}
+
+fn fmt_shifted_reg(
+    f: &mut Formatter<'_>,
+    m: &str,
+    operand: &ShiftRmImm6RnRd,
+    ty: IrType,
+) -> fmt::Result {
+    let (rd, rn) = (operand.rd.view(ty), operand.rn.view(ty));
+    let rm = ShiftedRegister(operand.rm.view(ty), operand.shift, operand.imm6);
+    let (to_zero, from_zero) = (is_zero_reg(operand.rd), is_zero_reg(operand.rn));
+    match m {
+        "adds" if to_zero => write!(f, "cmn {rn}, {rm}"),
+        "subs" if to_zero => write!(f, "cmp {rn}, {rm}"),
+        "sub" if from_zero => write!(f, "neg {rd}, {rm}"),
+        "subs" if from_zero => write!(f, "negs {rd}, {rm}"),
+        "orr" if from_zero && operand.shift == 0 && operand.imm6 == 0 => {
+            write!(f, "mov {rd}, {rm}")
+        }
+        "orn" if from_zero => write!(f, "mvn {rd}, {rm}"),
+        "ands" if to_zero => write!(f, "tst {rn}, {rm}"),
+        _ => write!(f, "{m} {rd}, {rn}, {rm}"),
+    }
+}
+
+fn fmt_ext_reg(
+    f: &mut Formatter<'_>,
+    m: &str,
+    operand: &AddSubtractExtReg,
+    ty: IrType,
+) -> fmt::Result {
+    let (rd, rn) = (operand.rd.view(ty), operand.rn.view(ty));
+    // Only the UXTX and SXTX extensions of the 64-bit forms read a 64-bit register
+    let rm = match (ty, operand.option & 0b011) {
+        (IrType::U64, 0b011) => x(operand.rm),
+        _ => w(operand.rm),
+    };
+    // The extension matching the register size is displayed as LSL next to the stack pointer
+    let lsl = match ty {
+        IrType::U64 => 0b011,
+        _ => 0b010,
+    };
+    let sp = operand.rd == AArch64RegisterId::Sp || operand.rn == AArch64RegisterId::Sp;
+    let extend = match (operand.option, operand.imm3) {
+        (option, 0) if sp && option == lsl => String::new(),
+        (option, amount) if sp && option == lsl => format!(", lsl #{amount}"),
+        (option, 0) => format!(", {}", EXTENDS[option as usize]),
+        (option, amount) => format!(", {} #{amount}", EXTENDS[option as usize]),
+    };
+    match m {
+        "adds" if is_zero_reg(operand.rd) => write!(f, "cmn {rn}, {rm}{extend}"),
+        "subs" if is_zero_reg(operand.rd) => write!(f, "cmp {rn}, {rm}{extend}"),
+        _ => write!(f, "{m} {rd}, {rn}, {rm}{extend}"),
+    }
+}
+
+fn fmt_add_sub_carry(f: &mut Formatter<'_>, m: &str, operand: &RmRnRd, ty: IrType) -> fmt::Result {
+    let (rd, rn, rm) = (
+        operand.rd.view(ty),
+        operand.rn.view(ty),
+        operand.rm.view(ty),
+    );
+    match m {
+        "sbc" if is_zero_reg(operand.rn) => write!(f, "ngc {rd}, {rm}"),
+        "sbcs" if is_zero_reg(operand.rn) => write!(f, "ngcs {rd}, {rm}"),
+        _ => write!(f, "{m} {rd}, {rn}, {rm}"),
+    }
+}
+
+fn fmt_cond_select(
+    f: &mut Formatter<'_>,
+    m: &str,
+    [rd, rn, rm]: [AArch64RegisterId; 3],
+    cond: u8,
+) -> fmt::Result {
+    // The aliases test the inverted condition, AL and NV can't be inverted
+    let invertible = cond >> 1 != 0b111;
+    let inverted = CONDITIONS[(cond ^ 1) as usize];
+    let both_zero = is_zero_reg(rn) && is_zero_reg(rm);
+    match m {
+        "csinc" if invertible && both_zero => write!(f, "cset {rd}, {inverted}"),
+        "csinv" if invertible && both_zero => write!(f, "csetm {rd}, {inverted}"),
+        "csinc" if invertible && rn == rm && !is_zero_reg(rm) => {
+            write!(f, "cinc {rd}, {rn}, {inverted}")
+        }
+        "csinv" if invertible && rn == rm && !is_zero_reg(rm) => {
+            write!(f, "cinv {rd}, {rn}, {inverted}")
+        }
+        "csneg" if invertible && rn == rm => write!(f, "cneg {rd}, {rn}, {inverted}"),
+        _ => write!(f, "{m} {rd}, {rn}, {rm}, {}", CONDITIONS[cond as usize]),
+    }
+}
+
+// System instructions
+
+/// The option of DMB and DSB, e.g. `ish`.
+pub(crate) struct BarrierOption(pub(crate) u8);
+
+impl Display for BarrierOption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self.0 {
+            0b0001 => "oshld",
+            0b0010 => "oshst",
+            0b0011 => "osh",
+            0b0101 => "nshld",
+            0b0110 => "nshst",
+            0b0111 => "nsh",
+            0b1001 => "ishld",
+            0b1010 => "ishst",
+            0b1011 => "ish",
+            0b1101 => "ld",
+            0b1110 => "st",
+            0b1111 => "sy",
+            crm => return write!(f, "#{crm}"),
+        };
+        f.write_str(name)
+    }
+}
+
+/// The PSTATE fields written by MSR (immediate), by `op1` and `op2`, with the largest immediate
+/// they accept.
+const PSTATE_FIELDS: [(u8, u8, &str, u8); 8] = [
+    (0b000, 0b011, "UAO", 1),
+    (0b000, 0b100, "PAN", 1),
+    (0b000, 0b101, "SPSel", 15),
+    (0b011, 0b001, "SSBS", 1),
+    (0b011, 0b010, "DIT", 1),
+    (0b011, 0b100, "TCO", 15),
+    (0b011, 0b110, "DAIFSet", 15),
+    (0b011, 0b111, "DAIFClr", 15),
+];
+
+fn fmt_msr_imm(f: &mut Formatter<'_>, operand: &PstateOp) -> fmt::Result {
+    let (op1, crm, op2) = (operand.op1, operand.crm, operand.op2);
+    let field = PSTATE_FIELDS
+        .iter()
+        .find(|field| (field.0, field.1) == (op1, op2) && crm <= field.3);
+    match field {
+        Some((_, _, name, _)) => write!(f, "msr {name}, #{crm}"),
+        None => write!(f, "msr S0_{op1}_C4_C{crm}_{op2}, xzr"),
+    }
+}
+
+/// The name of the system register accessed by MRS and MSR, `S<op0>_<op1>_C<n>_C<m>_<op2>` for
+/// the registers without a model.
+struct SysRegName<'a>(&'a SysRegMov);
+
+impl Display for SysRegName<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let encoding = SysRegEncoding::from(self.0);
+        match SysReg::lookup(encoding) {
+            Some(sysreg) => f.write_str(sysreg.name),
+            None => {
+                let SysRegEncoding {
+                    op0,
+                    op1,
+                    crn,
+                    crm,
+                    op2,
+                } = encoding;
+                write!(f, "S{op0}_{op1}_C{crn}_C{crm}_{op2}")
+            }
+        }
+    }
+}
+
+/// The cache maintenance, address translation and TLB maintenance operations of SYS, by `op1`,
+/// `CRn`, `CRm` and `op2`. The operations in `SYS_OPS_WITHOUT_REG` don't take a register.
+const SYS_OPS: [(u8, u8, u8, u8, &str, &str); 54] = [
+    (0, 7, 1, 0, "ic", "ialluis"),
+    (0, 7, 5, 0, "ic", "iallu"),
+    (3, 7, 5, 1, "ic", "ivau"),
+    (3, 7, 4, 1, "dc", "zva"),
+    (0, 7, 6, 1, "dc", "ivac"),
+    (0, 7, 6, 2, "dc", "isw"),
+    (3, 7, 10, 1, "dc", "cvac"),
+    (0, 7, 10, 2, "dc", "csw"),
+    (3, 7, 11, 1, "dc", "cvau"),
+    (3, 7, 12, 1, "dc", "cvap"),
+    (3, 7, 13, 1, "dc", "cvadp"),
+    (3, 7, 14, 1, "dc", "civac"),
+    (0, 7, 14, 2, "dc", "cisw"),
+    (0, 7, 8, 0, "at", "s1e1r"),
+    (0, 7, 8, 1, "at", "s1e1w"),
+    (0, 7, 8, 2, "at", "s1e0r"),
+    (0, 7, 8, 3, "at", "s1e0w"),
+    (0, 7, 9, 0, "at", "s1e1rp"),
+    (0, 7, 9, 1, "at", "s1e1wp"),
+    (4, 7, 8, 0, "at", "s1e2r"),
+    (4, 7, 8, 1, "at", "s1e2w"),
+    (4, 7, 8, 4, "at", "s12e1r"),
+    (4, 7, 8, 5, "at", "s12e1w"),
+    (4, 7, 8, 6, "at", "s12e0r"),
+    (4, 7, 8, 7, "at", "s12e0w"),
+    (6, 7, 8, 0, "at", "s1e3r"),
+    (6, 7, 8, 1, "at", "s1e3w"),
+    (0, 8, 3, 0, "tlbi", "vmalle1is"),
+    (0, 8, 3, 1, "tlbi", "vae1is"),
+    (0, 8, 3, 2, "tlbi", "aside1is"),
+    (0, 8, 3, 3, "tlbi", "vaae1is"),
+    (0, 8, 3, 5, "tlbi", "vale1is"),
+    (0, 8, 3, 7, "tlbi", "vaale1is"),
+    (0, 8, 7, 0, "tlbi", "vmalle1"),
+    (0, 8, 7, 1, "tlbi", "vae1"),
+    (0, 8, 7, 2, "tlbi", "aside1"),
+    (0, 8, 7, 3, "tlbi", "vaae1"),
+    (0, 8, 7, 5, "tlbi", "vale1"),
+    (0, 8, 7, 7, "tlbi", "vaale1"),
+    (4, 8, 3, 0, "tlbi", "alle2is"),
+    (4, 8, 3, 1, "tlbi", "vae2is"),
+    (4, 8, 3, 4, "tlbi", "alle1is"),
+    (4, 8, 3, 5, "tlbi", "vale2is"),
+    (4, 8, 3, 6, "tlbi", "vmalls12e1is"),
+    (4, 8, 7, 0, "tlbi", "alle2"),
+    (4, 8, 7, 1, "tlbi", "vae2"),
+    (4, 8, 7, 4, "tlbi", "alle1"),
+    (4, 8, 7, 5, "tlbi", "vale2"),
+    (4, 8, 7, 6, "tlbi", "vmalls12e1"),
+    (6, 8, 3, 0, "tlbi", "alle3is"),
+    (6, 8, 3, 1, "tlbi", "vae3is"),
+    (6, 8, 3, 5, "tlbi", "vale3is"),
+    (6, 8, 7, 0, "tlbi", "alle3"),
+    (6, 8, 7, 1, "tlbi", "vae3"),
+];
+
+const SYS_OPS_WITHOUT_REG: [&str; 12] = [
+    "ialluis",
+    "iallu",
+    "vmalle1is",
+    "vmalle1",
+    "alle2is",
+    "alle1is",
+    "vmalls12e1is",
+    "alle2",
+    "alle1",
+    "vmalls12e1",
+    "alle3is",
+    "alle3",
+];
+
+fn fmt_sys(f: &mut Formatter<'_>, operand: &SystemInstructions) -> fmt::Result {
+    let SystemInstructions {
+        op1,
+        crn,
+        crm,
+        op2,
+        rt,
+    } = *operand;
+    let op = SYS_OPS
+        .iter()
+        .find(|op| (op.0, op.1, op.2, op.3) == (op1, crn, crm, op2));
+    match op {
+        Some((.., m, op)) if !SYS_OPS_WITHOUT_REG.contains(op) => write!(f, "{m} {op}, {}", x(rt)),
+        Some((.., m, op)) if is_zero_reg(rt) => write!(f, "{m} {op}"),
+        _ if is_zero_reg(rt) => write!(f, "sys #{op1}, c{crn}, c{crm}, #{op2}"),
+        _ => write!(f, "sys #{op1}, c{crn}, c{crm}, #{op2}, {}", x(rt)),
+    }
+}
+
+// Advanced SIMD
+
+const ARRANGEMENTS: [&str; 8] = ["8b", "16b", "4h", "8h", "2s", "4s", "1d", "2d"];
+const ELEMENTS: [&str; 4] = ["b", "h", "s", "d"];
+
+/// The arrangement of a vector of `8 << size` bits elements, 128 bits wide if `q` is set.
+fn arrangement(q: u8, size: u8) -> &'static str {
+    ARRANGEMENTS[(size * 2 + q) as usize]
+}
+
+/// The arrangement of the single and double-precision vectors, by `sz` in the low bit of `size`.
+fn float_arrangement(q: u8, size: u8) -> &'static str {
+    arrangement(q, 2 + (size & 1))
+}
+
+/// The `2` suffix of the widening and narrowing instructions accessing the upper half of a
+/// vector.
+fn upper(q: u8) -> &'static str {
+    match q {
+        1 => "2",
+        _ => "",
+    }
+}
+
+/// A vector register with its arrangement, e.g. `v3.4s`.
+struct Vector(AArch64RegisterId, &'static str);
+
+impl Display for Vector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}.{}", self.0.number(), self.1)
+    }
+}
+
+/// An element of a vector register, e.g. `v3.s[1]` for the size 2.
+struct Element(AArch64RegisterId, u8, u8);
+
+impl Display for Element {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Element(id, size, index) = self;
+        write!(f, "v{}.{}[{index}]", id.number(), ELEMENTS[*size as usize])
+    }
+}
+
+/// The consecutive registers of the structure loads and stores, e.g. `{ v0.4s, v1.4s }` or
+/// `{ v0.s, v1.s }[1]` for a single structure.
+struct RegisterList {
+    first: u8,
+    count: u8,
+    suffix: &'static str,
+    index: Option<u8>,
+}
+
+impl RegisterList {
+    fn multiple(first: u8, count: u8, q: u8, size: u8) -> Self {
+        Self {
+            first,
+            count,
+            suffix: arrangement(q, size),
+            index: None,
+        }
+    }
+
+    fn single(first: u8, count: u8, size: u8, index: u8) -> Self {
+        Self {
+            first,
+            count,
+            suffix: ELEMENTS[size as usize],
+            index: Some(index),
+        }
+    }
+}
+
+impl Display for RegisterList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("{ ")?;
+        for i in 0..self.count {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            // The list wraps around from v31 to v0
+            write!(f, "v{}.{}", (self.first + i) % 32, self.suffix)?;
+        }
+        f.write_str(" }")?;
+        match self.index {
+            Some(index) => write!(f, "[{index}]"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The index of a single structure of `8 << size` bits elements, encoded in `Q:S:size`.
+fn single_index(q: u8, s: u8, size_field: u8, size: u8) -> u8 {
+    (q << 3 | s << 2 | size_field) >> size
+}
+
+/// The post-index of the structure loads and stores, the immediate is the size of the
+/// transferred structures and is used when `Rm` is the zero register.
+struct PostIndex(AArch64RegisterId, u32);
+
+impl Display for PostIndex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PostIndex(rm, imm) if is_zero_reg(*rm) => write!(f, "#{imm}"),
+            PostIndex(rm, _) => write!(f, "{}", x(*rm)),
+        }
+    }
+}
+
+fn fmt_three_same(
+    f: &mut Formatter<'_>,
+    m: &str,
+    operand: &QSizeRmRnRd,
+    arrangement: &'static str,
+) -> fmt::Result {
+    let rd = Vector(operand.rd, arrangement);
+    let rn = Vector(operand.rn, arrangement);
+    let rm = Vector(operand.rm, arrangement);
+    write!(f, "{m} {rd}, {rn}, {rm}")
+}
+
+fn fmt_scalar_three_same(
+    f: &mut Formatter<'_>,
+    m: &str,
+    operand: &AdvSimdScalarThreeSame,
+    ty: IrType,
+) -> fmt::Result {
+    let rd = scalar(operand.rd, ty);
+    let rn = scalar(operand.rn, ty);
+    let rm = scalar(operand.rm, ty);
+    write!(f, "{m} {rd}, {rn}, {rm}")
+}
+
+fn fmt_scalar_two_misc(
+    f: &mut Formatter<'_>,
+    m: &str,
+    operand: &AdvSimdScalarTwoRegMisc,
+    ty: IrType,
+) -> fmt::Result {
+    write!(
+        f,
+        "{m} {}, {}",
+        scalar(operand.rd, ty),
+        scalar(operand.rn, ty)
+    )
+}
+
+/// The three different instructions, with the `2` suffix when accessing the upper halves.
+fn fmt_three_different(
+    f: &mut Formatter<'_>,
+    m: &str,
+    operand: &QSizeRmRnRd,
+    [rd, rn, rm]: [&'static str; 3],
+) -> fmt::Result {
+    let rd = Vector(operand.rd, rd);
+    let rn = Vector(operand.rn, rn);
+    let rm = Vector(operand.rm, rm);
+    write!(f, "{m}{} {rd}, {rn}, {rm}", upper(operand.q))
+}
+
+fn fmt_two_misc(
+    f: &mut Formatter<'_>,
+    m: &str,
+    operand: &QSizeRnRd,
+    rd: &'static str,
+    rn: &'static str,
+) -> fmt::Result {
+    write!(
+        f,
+        "{m} {}, {}",
+        Vector(operand.rd, rd),
+        Vector(operand.rn, rn)
+    )
+}
+
+/// The `abcdefgh` immediate of the modified immediate instructions.
+fn modified_imm8(operand: &AdvSimdModifiedImm) -> u8 {
+    [
+        operand.a, operand.b, operand.c, operand.d, operand.e, operand.f, operand.g, operand.h,
+    ]
+    .iter()
+    .fold(0, |imm8, bit| imm8 << 1 | bit)
+}
+
+/// The element size of the shifts by immediate, by the highest set bit of `immh`.
+fn shift_size(operand: &AdvSimdShiftByImm) -> u8 {
+    7 - operand.immh.leading_zeros() as u8
+}
+
+/// The element size and the amount of a right shift, `2 * esize - immh:immb`.
+fn right_shift(operand: &AdvSimdShiftByImm) -> (u8, u32) {
+    let size = shift_size(operand);
+    let immhb = (operand.immh as u32) << 3 | operand.immb as u32;
+    (size, (16 << size) - immhb)
+}
+
+/// The element size and the amount of a left shift, `immh:immb - esize`.
+fn left_shift(operand: &AdvSimdShiftByImm) -> (u8, u32) {
+    let size = shift_size(operand);
+    let immhb = (operand.immh as u32) << 3 | operand.immb as u32;
+    (size, immhb - (8 << size))
+}
+
+fn fmt_shift_by_imm(
+    f: &mut Formatter<'_>,
+    m: &str,
+    operand: &AdvSimdShiftByImm,
+    [rd, rn]: [&'static str; 2],
+    shift: u32,
+) -> fmt::Result {
+    let (rd, rn) = (Vector(operand.rd, rd), Vector(operand.rn, rn));
+    write!(f, "{m} {rd}, {rn}, #{shift}")
+}
+
+/// The element of the by element instructions, its register is `M:Rm` and its index `H:L:M`
+/// for the halfwords, `H:L` for the words and `H` for the doublewords.
+fn indexed_elem(rm: AArch64RegisterId, m: u8, h: u8, l: u8, size: u8) -> Element {
+    let rm = rm.number();
+    let (register, index) = match size {
+        1 => (rm, h << 2 | l << 1 | m),
+        2 => (m << 4 | rm, h << 1 | l),
+        _ => (m << 4 | rm, h),
+    };
+    Element(AArch64RegisterId::V(register), size, index)
+}
+
+fn fmt_by_elem(
+    f: &mut Formatter<'_>,
+    m: &str,
+    operand: &AdvSimdXIndexedElem,
+    size: u8,
+    [rd, rn]: [&'static str; 2],
+) -> fmt::Result {
+    let (rd, rn) = (Vector(operand.rd, rd), Vector(operand.rn, rn));
+    let rm = indexed_elem(operand.rm, operand.m, operand.h, operand.l, size);
+    write!(f, "{m} {rd}, {rn}, {rm}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::decode_aarch64_inst;
+    use utility::SplitMix64;
+
+    fn disassemble(word: u32) -> String {
+        decode_aarch64_inst(&word.to_le_bytes()).to_string()
+    }
+
+    #[test]
+    fn preferred_syntax() {
+        let cases = [
+            (0xaa01_03e0, "mov x0, x1"),
+            (0x2a1f_03e3, "mov w3, wzr"),
+            (0x9100_005f, "mov sp, x2"),
+            (0x9100_03e2, "mov x2, sp"),
+            (0xd280_00a0, "mov x0, #5"),
+            (0x1280_0000, "mov w0, #-1"),
+            (0xd2a0_0020, "mov x0, #65536"),
+            (0x92a0_0021, "mov x1, #-65537"),
+            (0xb208_9fe0, "mov x0, #-71777214294589696"),
+            (0xf100_101f, "cmp x0, #4"),
+            (0x6b02_003f, "cmp w1, w2"),
+            (0xb100_047f, "cmn x3, #1"),
+            (0xeb02_0c3f, "cmp x1, x2, lsl #3"),
+            (0xf240_1c1f, "tst x0, #0xff"),
+            (0x6a02_003f, "tst w1, w2"),
+            (0xcb01_03e0, "neg x0, x1"),
+            (0x6b43_0be2, "negs w2, w3, lsr #2"),
+            (0xaa21_03e0, "mvn x0, x1"),
+            (0xda01_03e0, "ngc x0, x1"),
+            (0x7a04_03e3, "ngcs w3, w4"),
+            (0x9b02_7c20, "mul x0, x1, x2"),
+            (0x1b02_fc20, "mneg w0, w1, w2"),
+            (0x9ba2_7c20, "umull x0, w1, w2"),
+            (0x9b42_7c20, "smulh x0, x1, x2"),
+            (0x93c1_1c20, "ror x0, x1, #7"),
+            (0x1ac2_2c20, "ror w0, w1, w2"),
+            (0xd37d_f020, "lsl x0, x1, #3"),
+            (0x5305_7c20, "lsr w0, w1, #5"),
+            (0x937f_fc20, "asr x0, x1, #63"),
+            (0x9ac2_2020, "lsl x0, x1, x2"),
+            (0x1a9f_17e0, "cset w0, eq"),
+            (0xda9f_a3e1, "csetm x1, lt"),
+            (0x9a81_0420, "cinc x0, x1, ne"),
+            (0x5a83_9062, "cinv w2, w3, hi"),
+            (0xda81_b420, "cneg x0, x1, ge"),
+            (0x9344_2c20, "sbfx x0, x1, #4, #8"),
+            (0x5301_0c20, "ubfx w0, w1, #1, #3"),
+            (0x937c_1c20, "sbfiz x0, x1, #4, #8"),
+            (0x531f_0820, "ubfiz w0, w1, #1, #3"),
+            (0xb37c_1c20, "bfi x0, x1, #4, #8"),
+            (0x3301_0c20, "bfxil w0, w1, #1, #3"),
+            (0xb37d_0fe0, "bfc x0, #3, #4"),
+            (0x9340_1c20, "sxtb x0, w1"),
+            (0x1300_3c20, "sxth w0, w1"),
+            (0x9340_7c20, "sxtw x0, w1"),
+            (0x5300_1c20, "uxtb w0, w1"),
+            (0x5300_3c20, "uxth w0, w1"),
+            (0xd2a0_0020, "mov x0, #65536"),
+            (0x92a0_0000, "movn x0, #0, lsl #16"),
+            (0xf2e2_4680, "movk x0, #4660, lsl #48"),
+            (0xf820_003f, "stadd x0, [x1]"),
+            (0xb860_003f, "staddl w0, [x1]"),
+            (0x3820_103f, "stclrb w0, [x1]"),
+            (0x7860_33ff, "stsetlh w0, [sp]"),
+            (0xf820_0041, "ldadd x0, x1, [x2]"),
+            (0xf8e0_005f, "ldaddal x0, xzr, [x2]"),
+            (0xf820_803f, "swp x0, xzr, [x1]"),
+            (0xd508_751f, "ic iallu"),
+            (0xd50b_7520, "ic ivau, x0"),
+            (0xd50b_7421, "dc zva, x1"),
+            (0xd50b_7e22, "dc civac, x2"),
+            (0xd508_831f, "tlbi vmalle1is"),
+            (0xd508_8723, "tlbi vae1, x3"),
+            (0xd508_7804, "at s1e1r, x4"),
+            (0xd65f_03c0, "ret"),
+            (0xd65f_0060, "ret x3"),
+            (0xd61f_0200, "br x16"),
+            (0xd63f_0020, "blr x1"),
+            (0x4ea1_1c20, "mov v0.16b, v1.16b"),
+            (0x0ea1_1c20, "mov v0.8b, v1.8b"),
+            (0x6e20_5820, "mvn v0.16b, v1.16b"),
+            (0x6e0c_0420, "mov v0.s[1], v1.s[0]"),
+            (0x4e18_1c20, "mov v0.d[1], x1"),
+            (0x4e18_3c01, "mov x1, v0.d[1]"),
+            (0x0e14_3c01, "mov w1, v0.s[2]"),
+            (0x0e0a_3c01, "umov w1, v0.h[2]"),
+            (0x4e05_2c01, "smov x1, v0.b[2]"),
+            (0x4e04_0c20, "dup v0.4s, w1"),
+            (0x0f08_a420, "sshll v0.8h, v1.8b, #0"),
+            (0x6f10_a420, "ushll2 v0.4s, v1.8h, #0"),
+            (0x9e67_0020, "fmov d0, x1"),
+            (0x9e66_0020, "fmov x0, d1"),
+            (0x9eaf_0020, "fmov v0.d[1], x1"),
+            (0x1e2e_1000, "fmov s0, #1.00000000"),
+            (0x1e78_1000, "fmov d0, #-0.12500000"),
+            (0x6f00_e400, "movi v0.2d, #0"),
+            (0x4f00_2420, "movi v0.4s, #1, lsl #8"),
+            (0x6f00_c420, "mvni v0.4s, #1, msl #8"),
+            (0xd503_201f, "nop"),
+            (0xd503_2fff, "hint #127"),
+            (0xd503_20df, "hint #6"),
+            (0xd503_203f, "yield"),
+            (0xd503_207f, "wfi"),
+            (0xd503_209f, "sev"),
+            (0xd503_3bbf, "dmb ish"),
+            (0xd503_3f9f, "dsb sy"),
+            (0xd503_3fdf, "isb"),
+            (0xd503_309f, "ssbb"),
+            (0xd503_349f, "pssbb"),
+            (0xd503_3f5f, "clrex"),
+            (0xd53b_4200, "mrs x0, NZCV"),
+            (0xd51b_d041, "msr TPIDR_EL0, x1"),
+            (0xd503_42df, "msr DAIFSet, #2"),
+            (0xd500_41bf, "msr SPSel, #1"),
+            (0xd508_71e0, "sys #0, c7, c1, #7, x0"),
+            (0xd528_71e0, "sysl x0, #0, c7, c1, #7"),
+            (0xf240_001f, "tst x0, #0x1"),
+            (0xea01_001f, "tst x0, x1"),
+            (0xb100_041f, "cmn x0, #1"),
+            (0xf980_0000, "prfm pldl1keep, [x0]"),
+            (0xf8a1_7813, "prfm pstl2strm, [x0, x1, lsl #3]"),
+            (0xf89f_d024, "prfum pldl3keep, [x1, #-3]"),
+            (0xf940_0020, "ldr x0, [x1]"),
+            (0xf840_8c20, "ldr x0, [x1, #8]!"),
+            (0xf840_8420, "ldr x0, [x1], #8"),
+            (0xb862_d820, "ldr w0, [x1, w2, sxtw #2]"),
+            (0xf862_6820, "ldr x0, [x1, x2]"),
+            (0x3862_7820, "ldrb w0, [x1, x2, lsl #0]"),
+            (0xa9ff_07e0, "ldp x0, x1, [sp, #-16]!"),
+            (0xad01_0400, "stp q0, q1, [x0, #32]"),
+            (0x4cdf_a000, "ld1 { v0.16b, v1.16b }, [x0], #32"),
+            (0x0dc2_9000, "ld1 { v0.s }[1], [x0], x2"),
+            (0x4d60_e800, "ld4r { v0.4s, v1.4s, v2.4s, v3.4s }, [x0]"),
+            (0x4e03_2020, "tbl v0.16b, { v1.16b, v2.16b }, v3.16b"),
+            (0x0e03_53e0, "tbx v0.8b, { v31.16b, v0.16b, v1.16b }, v3.8b"),
+            (0x4e22_0020, "saddl2 v0.8h, v1.16b, v2.16b"),
+            (0x2e62_1020, "uaddw v0.4s, v1.4s, v2.4h"),
+            (0x6e22_4020, "raddhn2 v0.16b, v1.8h, v2.8h"),
+            (0x0e62_9020, "sqdmlal v0.4s, v1.4h, v2.4h"),
+            (0x0ee2_e020, "pmull v0.1q, v1.1d, v2.1d"),
+            (0x4ee2_e020, "pmull2 v0.1q, v1.2d, v2.2d"),
+            (0x1ee0_c020, "fabs h0, h1"),
+            (0x1ee7_4020, "frintx h0, h1"),
+            (0x1ee2_c020, "fcvt d0, h1"),
+            (0x5ee2_8420, "add d0, d1, d2"),
+            (0x7ee2_d420, "fabd d0, d1, d2"),
+            (0x5e62_b420, "sqdmulh h0, h1, h2"),
+            (0x7e22_4c20, "uqshl b0, b1, b2"),
+            (0x5ee0_8820, "cmgt d0, d1, #0"),
+            (0x5ea0_c820, "fcmgt s0, s1, #0.0"),
+            (0x5e21_4820, "sqxtn b0, h1"),
+            (0x7e61_6820, "fcvtxn s0, d1"),
+            (0x5ee1_d820, "frecpe d0, d1"),
+            (0x5ee1_f820, "frecpx d0, d1"),
+            (0x3618_0040, "tbz w0, #3, #8"),
+            (0xb5ff_ffe0, "cbnz x0, #-4"),
+            (0x5400_0081, "b.ne #16"),
+            (0x97ff_fffe, "bl #-8"),
+            (0x1000_0060, "adr x0, #12"),
+            (0xb000_0000, "adrp x0, #4096"),
+            (0xd420_0020, "brk #0x1"),
+            (0xd400_0001, "svc #0"),
+            (0xd400_0202, "hvc #0x10"),
+            (0x0000_0000, "udf #0"),
+            (0xd69f_03e0, "eret"),
+        ];
+        for (word, expected) in cases {
+            assert_eq!(disassemble(word), expected, "{word:#010x}");
+        }
+    }
+
+    #[test]
+    fn targets_resolved_at_pc() {
+        let cases = [
+            (0x5400_0081, "b.ne 0x40010"),
+            (0x97ff_fffe, "bl 0x3fff8"),
+            (0xb5ff_ffe0, "cbnz x0, 0x3fffc"),
+            (0x3618_0040, "tbz w0, #3, 0x40008"),
+            (0x1000_0060, "adr x0, 0x4000c"),
+            (0xb000_0000, "adrp x0, 0x41000"),
+        ];
+        for (word, expected) in cases {
+            let inst = decode_aarch64_inst(&u32::to_le_bytes(word));
+            assert_eq!(
+                inst.display_at(0x40000).to_string(),
+                expected,
+                "{word:#010x}"
+            );
+        }
+    }
+
+    #[test]
+    fn reserved_encodings() {
+        let words = [
+            0x6e10_672c, // ins with imm5 = 0b10000
+            0x0e00_2d9a, // smov with imm5 = 0
+            0x2ee0_6b41, // uadalp with size = 3
+            0x4f49_8704, // shrn with immh = 0b1001
+            0x4f6d_a4ac, // sshll with immh = 0b1101
+            0x0ffe_ab18, // smull by element with size = 3
+            0x0ee2_0020, // saddl with size = 3
+            0x0e22_d020, // sqdmull with size = 0
+            0x0e62_e020, // pmull with size = 1
+            0x5ea2_8420, // scalar add with size = 2
+            0x7ee1_2820, // scalar sqxtun with size = 3
+            0x7e21_6820, // scalar fcvtxn with size = 0
+        ];
+        for word in words {
+            assert_eq!(disassemble(word), format!(".inst {word:#010x}"));
+        }
+    }
+
+    /// Every word accepted by the decoder can be displayed.
+    #[test]
+    fn display_random_words() {
+        let mut rng = SplitMix64::new(0x49);
+        let mut displayed = 0;
+        for _ in 0..100_000 {
+            let word = rng.next_u64() as u32;
+            let Ok(inst) = std::panic::catch_unwind(|| decode_aarch64_inst(&word.to_le_bytes()))
+            else {
+                continue;
+            };
+            let text = std::panic::catch_unwind(|| inst.to_string());
+            assert!(text.is_ok(), "{word:#010x} {inst:?}");
+            displayed += 1;
+        }
+        assert!(displayed > 20_000, "{displayed}");
+    }
+}
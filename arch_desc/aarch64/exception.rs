@@ -0,0 +1,547 @@
+use core::ir::{BasicBlock, BasicBlockTerminator, IrInst, IrType, IrValue};
+use core::{Abort, AbortKind, AccessType, ExceptionKind, Interrupt};
+
+use super::compiler_prelude::{
+    exception_class, gen_add, gen_and, gen_assign, gen_current_el, gen_is_zero, gen_msb, gen_or,
+    gen_select, gen_shl, gen_shr, gen_sub, gen_xor, imm, pstate, reg, syndrome,
+};
+use super::sysreg::gen_switch_stack;
+use super::AArch64RegisterId;
+
+/// Offsets in the vector table at VBAR_ELx, the group of the source of an exception plus its
+/// type. FIQ at 0x100 and SError at 0x180 are never raised.
+pub mod vector {
+    pub const SYNCHRONOUS: u64 = 0x000;
+    pub const IRQ: u64 = 0x080;
+
+    /// From the current exception level while SP_EL0 is selected
+    pub const CURRENT_SP0: u64 = 0x000;
+    /// From the current exception level while SP_ELx is selected
+    pub const CURRENT_SPX: u64 = 0x200;
+    /// From a lower exception level in AArch64
+    pub const LOWER_AARCH64: u64 = 0x400;
+}
+
+/// Fault status codes of the aborts (ESR_ELx.ISS.xFSC), the ones of the translation faults
+/// are ORed with the level of the lookup.
+pub mod fault_status {
+    pub const TRANSLATION: u64 = 0b000100;
+    pub const ACCESS_FLAG: u64 = 0b001000;
+    pub const PERMISSION: u64 = 0b001100;
+    /// Synchronous external abort, nothing is mapped at the physical address
+    pub const EXTERNAL: u64 = 0b010000;
+    /// Synchronous external abort on a translation table walk
+    pub const TABLE_WALK: u64 = 0b010100;
+    pub const ALIGNMENT: u64 = 0b100001;
+}
+
+/// Write not read, set in the syndrome of the data aborts of writes.
+const WNR: u64 = 1 << 6;
+
+/// HCR_EL2.IMO and SCR_EL3.IRQ, route the physical IRQs to EL2 and EL3.
+const HCR_IMO: u64 = 1 << 4;
+const SCR_IRQ: u64 = 1 << 1;
+
+/// The debug, SError, IRQ and FIQ masks, all set on exception entry.
+const DAIF: u64 = (1 << pstate::D) | (1 << pstate::A) | (1 << pstate::I) | (1 << pstate::F);
+
+/// The bits of SPSR_ELx that hold PSTATE fields, the condition flags aside.
+const PSTATE_FIELDS: u64 = (1 << pstate::SP)
+    | (0b11 << pstate::EL)
+    | (1 << pstate::NRW)
+    | DAIF
+    | (0b11 << pstate::BTYPE)
+    | (1 << pstate::SSBS)
+    | (1 << pstate::IL)
+    | (1 << pstate::SS)
+    | (1 << pstate::PAN)
+    | (1 << pstate::UAO)
+    | (1 << pstate::DIT)
+    | (1 << pstate::TCO);
+
+/// The fields that keep their value when an exception is taken, the entry sets or clears the
+/// other ones.
+const PSTATE_KEPT: u64 =
+    (1 << pstate::SSBS) | (1 << pstate::PAN) | (1 << pstate::DIT) | (1 << pstate::TCO);
+
+/// The registers of an exception level that exceptions are taken to.
+struct Bank {
+    elr: AArch64RegisterId,
+    spsr: AArch64RegisterId,
+    esr: AArch64RegisterId,
+    far: AArch64RegisterId,
+    vbar: AArch64RegisterId,
+}
+
+/// The banks of EL1 to EL3.
+const BANKS: [Bank; 3] = [
+    Bank {
+        elr: AArch64RegisterId::ElrEl1,
+        spsr: AArch64RegisterId::SpsrEl1,
+        esr: AArch64RegisterId::EsrEl1,
+        far: AArch64RegisterId::FarEl1,
+        vbar: AArch64RegisterId::VbarEl1,
+    },
+    Bank {
+        elr: AArch64RegisterId::ElrEl2,
+        spsr: AArch64RegisterId::SpsrEl2,
+        esr: AArch64RegisterId::EsrEl2,
+        far: AArch64RegisterId::FarEl2,
+        vbar: AArch64RegisterId::VbarEl2,
+    },
+    Bank {
+        elr: AArch64RegisterId::ElrEl3,
+        spsr: AArch64RegisterId::SpsrEl3,
+        esr: AArch64RegisterId::EsrEl3,
+        far: AArch64RegisterId::FarEl3,
+        vbar: AArch64RegisterId::VbarEl3,
+    },
+];
+
+/// Generate how the guest takes `interrupt`.
+///
+/// Exceptions and aborts are taken to EL1, or to the current exception level if it is higher,
+/// HVC to EL2 and SMC to EL3. IRQs go to EL1 unless SCR_EL3 or HCR_EL2 route them higher,
+/// whether they are masked is up to the caller. The preferred return address is the pc the
+/// interrupt was raised at. A reset restarts at its address in EL3, the hints have no effect.
+pub fn gen_take(bb: &mut BasicBlock, interrupt: Interrupt) {
+    let el = gen_current_el(bb);
+
+    match interrupt {
+        Interrupt::Exception(exception) => {
+            let lowest = match exception.kind {
+                ExceptionKind::HypervisorCall(_) => 2,
+                ExceptionKind::SecureMonitorCall(_) => 3,
+                ExceptionKind::SupervisorCall(_)
+                | ExceptionKind::Breakpoint(_)
+                | ExceptionKind::Undefined => 1,
+            };
+            let target = gen_target_el(bb, el, imm(IrType::U64, lowest));
+            let esr = Some((exception.syndrome, exception.syndrome));
+            gen_entry(bb, el, target, vector::SYNCHRONOUS, esr, None);
+        }
+        Interrupt::Aborts(abort) => {
+            let target = gen_target_el(bb, el, imm(IrType::U64, 1));
+            let esr = Some(abort_syndromes(&abort));
+            let far = Some(abort.fault_address);
+            gen_entry(bb, el, target, vector::SYNCHRONOUS, esr, far);
+        }
+        Interrupt::Interrupt(_) => {
+            let target = gen_irq_target_el(bb, el);
+            gen_entry(bb, el, target, vector::IRQ, None, None);
+        }
+        Interrupt::Reset(address) => gen_reset(bb, address),
+        Interrupt::Yield | Interrupt::WaitForInterrupt => {}
+    }
+}
+
+/// The syndromes of an abort taken from a lower and from the same exception level.
+fn abort_syndromes(abort: &Abort) -> (u64, u64) {
+    let status = match abort.kind {
+        AbortKind::Unmapped => fault_status::EXTERNAL,
+        AbortKind::Unaligned => fault_status::ALIGNMENT,
+        AbortKind::Translation { level } => fault_status::TRANSLATION | level as u64,
+        AbortKind::AccessFlag { level } => fault_status::ACCESS_FLAG | level as u64,
+        AbortKind::Permission { level } => fault_status::PERMISSION | level as u64,
+        AbortKind::TableWalk { level } => fault_status::TABLE_WALK | level as u64,
+    };
+
+    let (lower, same, iss) = match abort.access {
+        AccessType::Execute => (
+            exception_class::INSTRUCTION_ABORT_LOWER,
+            exception_class::INSTRUCTION_ABORT,
+            status,
+        ),
+        AccessType::Read => (
+            exception_class::DATA_ABORT_LOWER,
+            exception_class::DATA_ABORT,
+            status,
+        ),
+        AccessType::Write => (
+            exception_class::DATA_ABORT_LOWER,
+            exception_class::DATA_ABORT,
+            status | WNR,
+        ),
+    };
+
+    let iss = iss | abort.syndrome;
+    (syndrome(lower, iss), syndrome(same, iss))
+}
+
+/// The higher of `lowest` and the current exception level `el`.
+fn gen_target_el(bb: &mut BasicBlock, el: IrValue, lowest: IrValue) -> IrValue {
+    let difference = gen_sub(bb, el, lowest);
+    let below = gen_msb(bb, difference);
+    gen_select(bb, below, lowest, el)
+}
+
+fn gen_irq_target_el(bb: &mut BasicBlock, el: IrValue) -> IrValue {
+    let hcr = reg(AArch64RegisterId::HcrEl2, IrType::U64);
+    let scr = reg(AArch64RegisterId::ScrEl3, IrType::U64);
+
+    let to_el2 = gen_and(bb, hcr, imm(IrType::U64, HCR_IMO));
+    let to_el2 = gen_is_zero(bb, to_el2);
+    let lowest = gen_select(bb, to_el2, imm(IrType::U64, 1), imm(IrType::U64, 2));
+
+    let to_el3 = gen_and(bb, scr, imm(IrType::U64, SCR_IRQ));
+    let to_el3 = gen_is_zero(bb, to_el3);
+    let lowest = gen_select(bb, to_el3, lowest, imm(IrType::U64, 3));
+
+    gen_target_el(bb, el, lowest)
+}
+
+/// `1` if the U64 `value` equals `constant`, `0` otherwise.
+fn gen_equals(bb: &mut BasicBlock, value: IrValue, constant: u64) -> IrValue {
+    let difference = gen_xor(bb, value, imm(IrType::U64, constant));
+    gen_is_zero(bb, difference)
+}
+
+/// Write `value` to the register `id` if `cond`, a U64 of 0 or 1, is set.
+fn gen_write_if(bb: &mut BasicBlock, cond: IrValue, id: AArch64RegisterId, value: IrValue) {
+    let dst = reg(id, IrType::U64);
+    let value = gen_select(bb, cond, value, dst);
+    gen_assign(bb, dst, value);
+}
+
+fn gen_jump(bb: &mut BasicBlock, target: IrValue) {
+    gen_assign(bb, reg(AArch64RegisterId::Pc, IrType::U64), target);
+    bb.set_terminator(BasicBlockTerminator::Branch(target));
+}
+
+/// Take an exception from `el` to `target` at `offset` in its group of vectors, writing the
+/// syndrome from a lower and from the same exception level to ESR_ELx and the fault address
+/// to FAR_ELx if given.
+fn gen_entry(
+    bb: &mut BasicBlock,
+    el: IrValue,
+    target: IrValue,
+    offset: u64,
+    esr: Option<(u64, u64)>,
+    far: Option<u64>,
+) {
+    let pstate = reg(AArch64RegisterId::Pstate, IrType::U64);
+    let spsr = gen_or(bb, pstate, reg(AArch64RegisterId::Nzcv, IrType::U64));
+    let pc = reg(AArch64RegisterId::Pc, IrType::U64);
+
+    let difference = gen_sub(bb, el, target);
+    let from_lower = gen_msb(bb, difference);
+    let select = gen_and(bb, pstate, imm(IrType::U64, 1 << pstate::SP));
+    let current = gen_select(
+        bb,
+        select,
+        imm(IrType::U64, vector::CURRENT_SPX),
+        imm(IrType::U64, vector::CURRENT_SP0),
+    );
+    let group = gen_select(
+        bb,
+        from_lower,
+        imm(IrType::U64, vector::LOWER_AARCH64),
+        current,
+    );
+    let esr = esr.map(|(lower, same)| {
+        gen_select(
+            bb,
+            from_lower,
+            imm(IrType::U64, lower),
+            imm(IrType::U64, same),
+        )
+    });
+
+    let mut vbar = imm(IrType::U64, 0);
+    for (index, bank) in BANKS.iter().enumerate() {
+        let is_target = gen_equals(bb, target, index as u64 + 1);
+
+        gen_write_if(bb, is_target, bank.elr, pc);
+        gen_write_if(bb, is_target, bank.spsr, spsr);
+        if let Some(esr) = esr {
+            gen_write_if(bb, is_target, bank.esr, esr);
+        }
+        if let Some(far) = far {
+            gen_write_if(bb, is_target, bank.far, imm(IrType::U64, far));
+        }
+        vbar = gen_select(bb, is_target, reg(bank.vbar, IrType::U64), vbar);
+    }
+
+    // The new exception level with its own stack and every interrupt masked
+    let kept = gen_and(bb, pstate, imm(IrType::U64, PSTATE_KEPT));
+    let new_el = gen_shl(bb, target, imm(IrType::U64, pstate::EL));
+    let new_pstate = gen_or(bb, kept, new_el);
+    let new_pstate = gen_or(bb, new_pstate, imm(IrType::U64, (1 << pstate::SP) | DAIF));
+    gen_switch_stack(bb, new_pstate);
+    gen_assign(bb, pstate, new_pstate);
+
+    bb.push_inst(IrInst::ClearExclusive);
+
+    let vector = gen_add(bb, vbar, group);
+    let vector = gen_add(bb, vector, imm(IrType::U64, offset));
+    gen_jump(bb, vector);
+}
+
+/// Restart at `address` in EL3 with its own stack and every interrupt masked.
+fn gen_reset(bb: &mut BasicBlock, address: u64) {
+    let pstate = (3 << pstate::EL) | (1 << pstate::SP) | DAIF;
+    gen_assign(
+        bb,
+        reg(AArch64RegisterId::Pstate, IrType::U64),
+        imm(IrType::U64, pstate),
+    );
+    bb.push_inst(IrInst::ClearExclusive);
+    gen_jump(bb, imm(IrType::U64, address));
+}
+
+/// Return from an exception with ERET, restoring PSTATE from SPSR_ELx and the pc from
+/// ELR_ELx of the current exception level, which is checked by the caller.
+///
+/// A return to a higher exception level, to AArch32 or to EL0 with SP_EL0 deselected is
+/// illegal, it keeps the exception level and the stack and sets PSTATE.IL.
+pub fn gen_return(bb: &mut BasicBlock) {
+    let el = gen_current_el(bb);
+
+    let mut spsr = imm(IrType::U64, 0);
+    let mut elr = imm(IrType::U64, 0);
+    for (index, bank) in BANKS.iter().enumerate() {
+        let is_current = gen_equals(bb, el, index as u64 + 1);
+        spsr = gen_select(bb, is_current, reg(bank.spsr, IrType::U64), spsr);
+        elr = gen_select(bb, is_current, reg(bank.elr, IrType::U64), elr);
+    }
+
+    let target = gen_shr(bb, spsr, imm(IrType::U64, pstate::EL));
+    let target = gen_and(bb, target, imm(IrType::U64, 0b11));
+    let difference = gen_sub(bb, el, target);
+    let to_higher = gen_msb(bb, difference);
+
+    let mode = gen_and(bb, spsr, imm(IrType::U64, 0b1_1111));
+    let to_aarch32 = gen_shr(bb, mode, imm(IrType::U64, pstate::NRW));
+    let to_el0_spx = gen_equals(bb, mode, 1 << pstate::SP);
+    let illegal = gen_or(bb, to_higher, to_aarch32);
+    let illegal = gen_or(bb, illegal, to_el0_spx);
+
+    let pstate = reg(AArch64RegisterId::Pstate, IrType::U64);
+    let mode_mask = (1 << pstate::SP) | (0b11 << pstate::EL) | (1 << pstate::NRW);
+    let restored = gen_and(bb, spsr, imm(IrType::U64, PSTATE_FIELDS));
+    let fields = gen_and(bb, restored, imm(IrType::U64, !mode_mask));
+    let kept_mode = gen_and(
+        bb,
+        pstate,
+        imm(IrType::U64, (1 << pstate::SP) | (0b11 << pstate::EL)),
+    );
+    let illegal_pstate = gen_or(bb, fields, kept_mode);
+    let illegal_pstate = gen_or(bb, illegal_pstate, imm(IrType::U64, 1 << pstate::IL));
+    let new_pstate = gen_select(bb, illegal, illegal_pstate, restored);
+
+    gen_switch_stack(bb, new_pstate);
+    gen_assign(bb, pstate, new_pstate);
+    let nzcv = gen_and(bb, spsr, imm(IrType::U64, 0xf000_0000));
+    gen_assign(bb, reg(AArch64RegisterId::Nzcv, IrType::U64), nzcv);
+
+    bb.push_inst(IrInst::ClearExclusive);
+    gen_jump(bb, elr);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aarch64::testing::TestCpu;
+    use core::Exception;
+    use AArch64RegisterId::*;
+
+    const VBAR: u64 = 0x10_0000;
+    const MASKED: u64 = (1 << pstate::SP) | DAIF;
+
+    fn el(el: u64) -> u64 {
+        el << pstate::EL
+    }
+
+    fn cpu_with(registers: &[(AArch64RegisterId, u64)]) -> TestCpu {
+        let mut cpu = TestCpu::new();
+        for &(id, value) in registers {
+            cpu.set(id, value);
+        }
+        cpu
+    }
+
+    fn data_abort(access: AccessType) -> Interrupt {
+        Interrupt::Aborts(Abort {
+            fault_address: 0xdead_0000,
+            access,
+            kind: AbortKind::Unmapped,
+            syndrome: 0,
+        })
+    }
+
+    #[test]
+    fn supervisor_call_and_return() {
+        let mut cpu = cpu_with(&[
+            (Pc, 0x400),
+            (Pstate, el(0)),
+            (Nzcv, 0x6000_0000),
+            (Sp, 0x1000),
+            (SpEl1, 0x8000),
+            (VbarEl1, VBAR),
+        ]);
+
+        let interrupt = cpu.run(&[0xd400_00a1]).unwrap(); // svc #5
+        cpu.take(interrupt);
+        assert_eq!(cpu.get(Pc), VBAR + vector::LOWER_AARCH64);
+        assert_eq!(cpu.get(ElrEl1), 0x404);
+        assert_eq!(cpu.get(SpsrEl1), 0x6000_0000);
+        assert_eq!(cpu.get(EsrEl1), syndrome(exception_class::SVC, 5));
+        assert_eq!(cpu.get(Pstate), el(1) | MASKED);
+        assert_eq!((cpu.get(Sp), cpu.get(SpEl0)), (0x8000, 0x1000));
+
+        cpu.set(Nzcv, 0);
+        cpu.set(Sp, 0x7ff0);
+        assert_eq!(cpu.run(&[0xd69f_03e0]), None); // eret
+        assert_eq!(cpu.get(Pc), 0x404);
+        assert_eq!(cpu.get(Pstate), el(0));
+        assert_eq!(cpu.get(Nzcv), 0x6000_0000);
+        assert_eq!((cpu.get(Sp), cpu.get(SpEl1)), (0x1000, 0x7ff0));
+    }
+
+    #[test]
+    fn vector_groups() {
+        // The current exception level with SP_EL0 and with SP_EL1 selected
+        for (select, group) in [(0, vector::CURRENT_SP0), (1, vector::CURRENT_SPX)] {
+            let mut cpu = cpu_with(&[(Pc, 0x400), (Pstate, el(1) | select), (VbarEl1, VBAR)]);
+            let interrupt = cpu.run(&[0xd420_0020]).unwrap(); // brk #1
+            cpu.take(interrupt);
+            assert_eq!(cpu.get(Pc), VBAR + group);
+            assert_eq!(cpu.get(ElrEl1), 0x400);
+            assert_eq!(cpu.get(SpsrEl1), el(1) | select);
+        }
+
+        let mut cpu = cpu_with(&[(Pc, 0x400), (Pstate, el(0)), (VbarEl1, VBAR)]);
+        cpu.take(Interrupt::Interrupt(0));
+        assert_eq!(cpu.get(Pc), VBAR + vector::LOWER_AARCH64 + vector::IRQ);
+        assert_eq!(cpu.get(ElrEl1), 0x400);
+    }
+
+    #[test]
+    fn target_exception_levels() {
+        let registers = [
+            (Pstate, el(1) | 1),
+            (VbarEl1, VBAR),
+            (VbarEl2, 2 * VBAR),
+            (VbarEl3, 3 * VBAR),
+        ];
+
+        let mut cpu = cpu_with(&registers);
+        let interrupt = cpu.run(&[0xd400_0042]).unwrap(); // hvc #2
+        cpu.take(interrupt);
+        assert_eq!(cpu.get(Pstate), el(2) | MASKED);
+        assert_eq!(cpu.get(Pc), 2 * VBAR + vector::LOWER_AARCH64);
+        assert_eq!(cpu.get(ElrEl2), 4);
+        assert_eq!(cpu.get(EsrEl2), syndrome(exception_class::HVC, 2));
+
+        let mut cpu = cpu_with(&registers);
+        let interrupt = cpu.run(&[0xd400_0063]).unwrap(); // smc #3
+        cpu.take(interrupt);
+        assert_eq!(cpu.get(Pstate), el(3) | MASKED);
+        assert_eq!(cpu.get(Pc), 3 * VBAR + vector::LOWER_AARCH64);
+        assert_eq!(cpu.get(SpsrEl3), el(1) | 1);
+
+        // Exceptions are never taken to a lower exception level
+        let mut cpu = cpu_with(&[(Pstate, el(2) | 1), (VbarEl2, 2 * VBAR)]);
+        cpu.take(Interrupt::Exception(Exception {
+            kind: ExceptionKind::Undefined,
+            syndrome: syndrome(exception_class::UNKNOWN, 0),
+        }));
+        assert_eq!(cpu.get(Pstate), el(2) | MASKED);
+        assert_eq!(cpu.get(Pc), 2 * VBAR + vector::CURRENT_SPX);
+    }
+
+    #[test]
+    fn irq_routing() {
+        let registers = [(Pstate, el(0)), (VbarEl2, 2 * VBAR), (VbarEl3, 3 * VBAR)];
+
+        let mut cpu = cpu_with(&registers);
+        cpu.set(HcrEl2, HCR_IMO);
+        cpu.take(Interrupt::Interrupt(0));
+        assert_eq!(cpu.get(Pstate), el(2) | MASKED);
+        assert_eq!(cpu.get(Pc), 2 * VBAR + vector::LOWER_AARCH64 + vector::IRQ);
+
+        let mut cpu = cpu_with(&registers);
+        cpu.set(HcrEl2, HCR_IMO);
+        cpu.set(ScrEl3, SCR_IRQ);
+        cpu.take(Interrupt::Interrupt(0));
+        assert_eq!(cpu.get(Pstate), el(3) | MASKED);
+        assert_eq!(cpu.get(Pc), 3 * VBAR + vector::LOWER_AARCH64 + vector::IRQ);
+    }
+
+    #[test]
+    fn abort_syndromes_and_fault_address() {
+        let mut cpu = cpu_with(&[(Pstate, el(0))]);
+        cpu.take(data_abort(AccessType::Write));
+        let iss = fault_status::EXTERNAL | WNR;
+        assert_eq!(
+            cpu.get(EsrEl1),
+            syndrome(exception_class::DATA_ABORT_LOWER, iss)
+        );
+        assert_eq!(cpu.get(FarEl1), 0xdead_0000);
+
+        let mut cpu = cpu_with(&[(Pstate, el(1))]);
+        cpu.take(data_abort(AccessType::Read));
+        let esr = syndrome(exception_class::DATA_ABORT, fault_status::EXTERNAL);
+        assert_eq!(cpu.get(EsrEl1), esr);
+
+        let mut cpu = cpu_with(&[(Pstate, el(1))]);
+        cpu.take(data_abort(AccessType::Execute));
+        let esr = syndrome(exception_class::INSTRUCTION_ABORT, fault_status::EXTERNAL);
+        assert_eq!(cpu.get(EsrEl1), esr);
+    }
+
+    #[test]
+    fn stacks_are_banked_per_exception_level() {
+        let mut cpu = cpu_with(&[
+            (Pstate, el(1) | 1),
+            (Sp, 0x1000),
+            (SpEl0, 0x100),
+            (SpEl2, 0x2000),
+        ]);
+        cpu.take(Interrupt::Exception(Exception {
+            kind: ExceptionKind::HypervisorCall(0),
+            syndrome: syndrome(exception_class::HVC, 0),
+        }));
+        assert_eq!(cpu.get(Sp), 0x2000);
+        assert_eq!((cpu.get(SpEl0), cpu.get(SpEl1)), (0x100, 0x1000));
+
+        // SP_EL1 is in its bank at EL2
+        assert_eq!(cpu.run(&[0xd53c_4100]), None); // mrs x0, sp_el1
+        assert_eq!(cpu.get(X(0)), 0x1000);
+    }
+
+    #[test]
+    fn illegal_exception_return() {
+        // To a higher exception level
+        let mut cpu = cpu_with(&[
+            (Pstate, el(1) | 1),
+            (SpsrEl1, el(2) | 1 | (1 << pstate::I)),
+            (ElrEl1, 0x800),
+        ]);
+        assert_eq!(cpu.run(&[0xd69f_03e0]), None); // eret
+        assert_eq!(cpu.get(Pc), 0x800);
+        assert_eq!(
+            cpu.get(Pstate),
+            el(1) | 1 | (1 << pstate::I) | (1 << pstate::IL)
+        );
+
+        // From EL0
+        let mut cpu = cpu_with(&[(Pstate, el(0))]);
+        let interrupt = cpu.run(&[0xd69f_03e0]).unwrap(); // eret
+        assert!(matches!(
+            interrupt,
+            Interrupt::Exception(Exception {
+                kind: ExceptionKind::Undefined,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn reset() {
+        let mut cpu = cpu_with(&[(Pstate, el(0)), (Pc, 0x400)]);
+        cpu.take(Interrupt::Reset(0x8000));
+        assert_eq!(cpu.get(Pc), 0x8000);
+        assert_eq!(cpu.get(Pstate), el(3) | MASKED);
+    }
+}
@@ -3,7 +3,7 @@ use core::Instruction;
 use std::fmt::Debug;
 
 use crate::aarch64::inst_operand::*;
-use crate::aarch64::{compile_aarch64_to_ir, decode_aarch64_inst};
+use crate::aarch64::{compile_aarch64_to_ir, decode_aarch64_inst, encode_aarch64_inst};
 
 // AArch64 instruction
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -392,7 +392,7 @@ pub enum AArch64Inst {
     ERet(UncondBranchReg),
     Drps(UncondBranchReg),
 
-    Hint,
+    Hint(Hints),
     Nop,
     Yield,
     Wfe,
@@ -471,6 +471,44 @@ pub enum AArch64Inst {
     Ld2r(AdvSimdLdStSingleStructure),
     Ld4r(AdvSimdLdStSingleStructure),
 
+    St1SingleStructurePostIndexedVar8(AdvSimdLdStSingleStructurePostIndexed),
+    St3SingleStructurePostIndexedVar8(AdvSimdLdStSingleStructurePostIndexed),
+    St1SingleStructurePostIndexedVar16(AdvSimdLdStSingleStructurePostIndexed),
+    St3SingleStructurePostIndexedVar16(AdvSimdLdStSingleStructurePostIndexed),
+    St1SingleStructurePostIndexedVar32(AdvSimdLdStSingleStructurePostIndexed),
+    St1SingleStructurePostIndexedVar64(AdvSimdLdStSingleStructurePostIndexed),
+    St3SingleStructurePostIndexedVar32(AdvSimdLdStSingleStructurePostIndexed),
+    St3SingleStructurePostIndexedVar64(AdvSimdLdStSingleStructurePostIndexed),
+    St2SingleStructurePostIndexedVar8(AdvSimdLdStSingleStructurePostIndexed),
+    St4SingleStructurePostIndexedVar8(AdvSimdLdStSingleStructurePostIndexed),
+    St2SingleStructurePostIndexedVar16(AdvSimdLdStSingleStructurePostIndexed),
+    St4SingleStructurePostIndexedVar16(AdvSimdLdStSingleStructurePostIndexed),
+    St2SingleStructurePostIndexedVar32(AdvSimdLdStSingleStructurePostIndexed),
+    St2SingleStructurePostIndexedVar64(AdvSimdLdStSingleStructurePostIndexed),
+    St4SingleStructurePostIndexedVar32(AdvSimdLdStSingleStructurePostIndexed),
+    St4SingleStructurePostIndexedVar64(AdvSimdLdStSingleStructurePostIndexed),
+
+    Ld1SingleStructurePostIndexedVar8(AdvSimdLdStSingleStructurePostIndexed),
+    Ld3SingleStructurePostIndexedVar8(AdvSimdLdStSingleStructurePostIndexed),
+    Ld1SingleStructurePostIndexedVar16(AdvSimdLdStSingleStructurePostIndexed),
+    Ld3SingleStructurePostIndexedVar16(AdvSimdLdStSingleStructurePostIndexed),
+    Ld1SingleStructurePostIndexedVar32(AdvSimdLdStSingleStructurePostIndexed),
+    Ld1SingleStructurePostIndexedVar64(AdvSimdLdStSingleStructurePostIndexed),
+    Ld3SingleStructurePostIndexedVar32(AdvSimdLdStSingleStructurePostIndexed),
+    Ld3SingleStructurePostIndexedVar64(AdvSimdLdStSingleStructurePostIndexed),
+    Ld1rPostIndexed(AdvSimdLdStSingleStructurePostIndexed),
+    Ld3rPostIndexed(AdvSimdLdStSingleStructurePostIndexed),
+    Ld2SingleStructurePostIndexedVar8(AdvSimdLdStSingleStructurePostIndexed),
+    Ld4SingleStructurePostIndexedVar8(AdvSimdLdStSingleStructurePostIndexed),
+    Ld2SingleStructurePostIndexedVar16(AdvSimdLdStSingleStructurePostIndexed),
+    Ld4SingleStructurePostIndexedVar16(AdvSimdLdStSingleStructurePostIndexed),
+    Ld2SingleStructurePostIndexedVar32(AdvSimdLdStSingleStructurePostIndexed),
+    Ld2SingleStructurePostIndexedVar64(AdvSimdLdStSingleStructurePostIndexed),
+    Ld4SingleStructurePostIndexedVar32(AdvSimdLdStSingleStructurePostIndexed),
+    Ld4SingleStructurePostIndexedVar64(AdvSimdLdStSingleStructurePostIndexed),
+    Ld2rPostIndexed(AdvSimdLdStSingleStructurePostIndexed),
+    Ld4rPostIndexed(AdvSimdLdStSingleStructurePostIndexed),
+
     St4MulStructures(AdvSimdLdStMultiStructures),
     St1MulStructures4RegsVar(AdvSimdLdStMultiStructures),
     St3MulStructures(AdvSimdLdStMultiStructures),
@@ -668,6 +706,38 @@ pub enum AArch64Inst {
     Bit(QSizeRmRnRd),
     Bif(QSizeRmRnRd),
 
+    SqaddScalar(AdvSimdScalarThreeSame),
+    SqsubScalar(AdvSimdScalarThreeSame),
+    CmgtRegScalar(AdvSimdScalarThreeSame),
+    CmgeRegScalar(AdvSimdScalarThreeSame),
+    SshlScalar(AdvSimdScalarThreeSame),
+    SqshlRegScalar(AdvSimdScalarThreeSame),
+    SrshlScalar(AdvSimdScalarThreeSame),
+    SqrshlScalar(AdvSimdScalarThreeSame),
+    AddScalar(AdvSimdScalarThreeSame),
+    CmtstScalar(AdvSimdScalarThreeSame),
+    SqdmulhScalar(AdvSimdScalarThreeSame),
+    FmulxScalar(AdvSimdScalarThreeSame),
+    FcmeqRegScalar(AdvSimdScalarThreeSame),
+    FrecpsScalar(AdvSimdScalarThreeSame),
+    FrsqrtsScalar(AdvSimdScalarThreeSame),
+    UqaddScalar(AdvSimdScalarThreeSame),
+    UqsubScalar(AdvSimdScalarThreeSame),
+    CmhiRegScalar(AdvSimdScalarThreeSame),
+    CmhsRegScalar(AdvSimdScalarThreeSame),
+    UshlScalar(AdvSimdScalarThreeSame),
+    UqshlRegScalar(AdvSimdScalarThreeSame),
+    UrshlScalar(AdvSimdScalarThreeSame),
+    UqrshlScalar(AdvSimdScalarThreeSame),
+    SubScalar(AdvSimdScalarThreeSame),
+    CmeqRegScalar(AdvSimdScalarThreeSame),
+    SqrdmulhScalar(AdvSimdScalarThreeSame),
+    FcmgeRegScalar(AdvSimdScalarThreeSame),
+    FacgeScalar(AdvSimdScalarThreeSame),
+    FabdScalar(AdvSimdScalarThreeSame),
+    FcmgtRegScalar(AdvSimdScalarThreeSame),
+    FacgtScalar(AdvSimdScalarThreeSame),
+
     Sshr(AdvSimdShiftByImm),
     Ssra(AdvSimdShiftByImm),
     Srshr(AdvSimdShiftByImm),
@@ -723,6 +793,19 @@ pub enum AArch64Inst {
     FrintaScalarDoublePrecisionVar(RnRd),
     FrintxScalarDoublePrecisionVar(RnRd),
     FrintiScalarDoublePrecisionVar(RnRd),
+    FmovRegHalfPrecisionVar(RnRd),
+    FabsScalarHalfPrecisionVar(RnRd),
+    FnegScalarHalfPrecisionVar(RnRd),
+    FsqrtScalarHalfPrecisionVar(RnRd),
+    FcvtHalfToSinglePrecisionVar(RnRd),
+    FcvtHalfToDoublePrecisionVar(RnRd),
+    FrintnScalarHalfPrecisionVar(RnRd),
+    FrintpScalarHalfPrecisionVar(RnRd),
+    FrintmScalarHalfPrecisionVar(RnRd),
+    FrintzScalarHalfPrecisionVar(RnRd),
+    FrintaScalarHalfPrecisionVar(RnRd),
+    FrintxScalarHalfPrecisionVar(RnRd),
+    FrintiScalarHalfPrecisionVar(RnRd),
 
     AddpScalar(AdvSimdScalarPairwise),
     FmaxnmpScalarEncoding(AdvSimdScalarPairwise),
@@ -794,6 +877,42 @@ pub enum AArch64Inst {
     Frsqrte(QSizeRnRd),
     FsqrtVec(QSizeRnRd),
 
+    SuqaddScalar(AdvSimdScalarTwoRegMisc),
+    SqabsScalar(AdvSimdScalarTwoRegMisc),
+    CmgtZeroScalar(AdvSimdScalarTwoRegMisc),
+    CmeqZeroScalar(AdvSimdScalarTwoRegMisc),
+    CmltZeroScalar(AdvSimdScalarTwoRegMisc),
+    AbsScalar(AdvSimdScalarTwoRegMisc),
+    SqxtnScalar(AdvSimdScalarTwoRegMisc),
+    FcvtnsScalar(AdvSimdScalarTwoRegMisc),
+    FcvtmsScalar(AdvSimdScalarTwoRegMisc),
+    FcvtasScalar(AdvSimdScalarTwoRegMisc),
+    ScvtfScalarInt(AdvSimdScalarTwoRegMisc),
+    FcmgtZeroScalar(AdvSimdScalarTwoRegMisc),
+    FcmeqZeroScalar(AdvSimdScalarTwoRegMisc),
+    FcmltZeroScalar(AdvSimdScalarTwoRegMisc),
+    FcvtpsScalar(AdvSimdScalarTwoRegMisc),
+    FcvtzsScalarInt(AdvSimdScalarTwoRegMisc),
+    FrecpeScalar(AdvSimdScalarTwoRegMisc),
+    Frecpx(AdvSimdScalarTwoRegMisc),
+    UsqaddScalar(AdvSimdScalarTwoRegMisc),
+    SqnegScalar(AdvSimdScalarTwoRegMisc),
+    CmgeZeroScalar(AdvSimdScalarTwoRegMisc),
+    CmleZeroScalar(AdvSimdScalarTwoRegMisc),
+    NegScalar(AdvSimdScalarTwoRegMisc),
+    SqxtunScalar(AdvSimdScalarTwoRegMisc),
+    UqxtnScalar(AdvSimdScalarTwoRegMisc),
+    FcvtxnScalar(AdvSimdScalarTwoRegMisc),
+    FcvtnuScalar(AdvSimdScalarTwoRegMisc),
+    FcvtmuScalar(AdvSimdScalarTwoRegMisc),
+    FcvtauScalar(AdvSimdScalarTwoRegMisc),
+    UcvtfScalarInt(AdvSimdScalarTwoRegMisc),
+    FcmgeZeroScalar(AdvSimdScalarTwoRegMisc),
+    FcmleZeroScalar(AdvSimdScalarTwoRegMisc),
+    FcvtpuScalar(AdvSimdScalarTwoRegMisc),
+    FcvtzuScalarInt(AdvSimdScalarTwoRegMisc),
+    FrsqrteScalar(AdvSimdScalarTwoRegMisc),
+
     Saddlv(QSizeRnRd),
     Smaxv(QSizeRnRd),
     Sminv(QSizeRnRd),
@@ -1003,6 +1122,36 @@ pub enum AArch64Inst {
     Fcmp(FloatingPointCompare),
     Fcmpe(FloatingPointCompare),
 
+    Tbl(AdvSimdTableLookup),
+    Tbx(AdvSimdTableLookup),
+
+    Saddl(QSizeRmRnRd),
+    Saddw(QSizeRmRnRd),
+    Ssubl(QSizeRmRnRd),
+    Ssubw(QSizeRmRnRd),
+    Addhn(QSizeRmRnRd),
+    Sabal(QSizeRmRnRd),
+    Subhn(QSizeRmRnRd),
+    Sabdl(QSizeRmRnRd),
+    Smlal(QSizeRmRnRd),
+    Sqdmlal(QSizeRmRnRd),
+    Smlsl(QSizeRmRnRd),
+    Sqdmlsl(QSizeRmRnRd),
+    Smull(QSizeRmRnRd),
+    Sqdmull(QSizeRmRnRd),
+    Pmull(QSizeRmRnRd),
+    Uaddl(QSizeRmRnRd),
+    Uaddw(QSizeRmRnRd),
+    Usubl(QSizeRmRnRd),
+    Usubw(QSizeRmRnRd),
+    Raddhn(QSizeRmRnRd),
+    Uabal(QSizeRmRnRd),
+    Rsubhn(QSizeRmRnRd),
+    Uabdl(QSizeRmRnRd),
+    Umlal(QSizeRmRnRd),
+    Umlsl(QSizeRmRnRd),
+    Umull(QSizeRmRnRd),
+
     Uzp1(QSizeRmRnRd),
     Trn1(QSizeRmRnRd),
     Zip1(QSizeRmRnRd),
@@ -1076,6 +1225,23 @@ pub enum AArch64Inst {
     UmlslByElem(AdvSimdXIndexedElem),
     UmullByElem(AdvSimdXIndexedElem),
     FmulxByElemEncoding(AdvSimdXIndexedElem),
+
+    SqdmlalByElemScalar(AdvSimdScalarXIndexedElem),
+    SqdmlslByElemScalar(AdvSimdScalarXIndexedElem),
+    SqdmullByElemScalar(AdvSimdScalarXIndexedElem),
+    SqdmulhByElemScalar(AdvSimdScalarXIndexedElem),
+    SqrdmulhByElemScalar(AdvSimdScalarXIndexedElem),
+    FmlaByElemScalarEncoding(AdvSimdScalarXIndexedElem),
+    FmlsByElemScalarEncoding(AdvSimdScalarXIndexedElem),
+    FmulByElemScalarEncoding(AdvSimdScalarXIndexedElem),
+    FmulxByElemScalarEncoding(AdvSimdScalarXIndexedElem),
+}
+
+impl AArch64Inst {
+    /// Encode the instruction into its 32-bit word.
+    pub fn encode(&self) -> u32 {
+        encode_aarch64_inst(self)
+    }
 }
 
 impl Instruction for AArch64Inst {
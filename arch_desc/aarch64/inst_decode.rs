@@ -17,7 +17,7 @@ pub(crate) fn decode_aarch64_inst(raw: &[u8]) -> AArch64Inst {
             "0_xx_0000_xxxxxxxxxxxxxxxxxxxxxxxxx",
             |raw_instr: &[u8],
              Extract(op0): Extract<u8, 29, 31>,
-             Extract(op1): Extract<u8, 16, 25>,
+             Extract(op1): Extract<u16, 16, 25>,
              Extract(imm16): Extract<u16, 0, 16>| {
                 let imm16 = Imm16 { imm16 };
                 match (op0, op1) {
@@ -52,7 +52,10 @@ pub(crate) fn decode_aarch64_inst(raw: &[u8]) -> AArch64Inst {
         m
     });
 
-    MATCHER.try_match(raw).unwrap()
+    // Instructions are stored little-endian, but the patterns are written most
+    // significant bit first.
+    let raw = u32::from_le_bytes(raw[..4].try_into().unwrap()).to_be_bytes();
+    MATCHER.try_match(&raw).unwrap()
 }
 
 // parse DPI(Data Processing Immediate) instructions in AArch64
@@ -203,7 +206,7 @@ fn parse_aarch64_dp_sfp_adv_simd(raw_instr: &[u8]) -> AArch64Inst {
                 "{}_xxx_{}_{}_{}_xxxxxxxxxx",
                 "01x1", "0x", "x100", "00xxxxx10"
             ),
-            |_raw_instr: &[u8]| todo!("Advanced SIMD scalar two-register miscellaneous"),
+            parse_adv_simd_scalar_2reg_miscellaneous,
         )
         .bind(
             &format!(
@@ -224,7 +227,7 @@ fn parse_aarch64_dp_sfp_adv_simd(raw_instr: &[u8]) -> AArch64Inst {
                 "{}_xxx_{}_{}_{}_xxxxxxxxxx",
                 "01x1", "0x", "x1xx", "xxxxxxxx1"
             ),
-            |_raw_instr: &[u8]| todo!("Advanced SIMD scalar three same"),
+            parse_adv_simd_scalar_three_same,
         )
         .bind(
             &format!(
@@ -245,7 +248,7 @@ fn parse_aarch64_dp_sfp_adv_simd(raw_instr: &[u8]) -> AArch64Inst {
                 "{}_xxx_{}_{}_{}_xxxxxxxxxx",
                 "0x00", "0x", "x0xx", "xxx0xxx00"
             ),
-            |_raw_instr: &[u8]| todo!("Advanced SIMD table lookup"),
+            parse_advanced_simd_table_lookup,
         )
         .bind(
             &format!(
@@ -308,7 +311,7 @@ fn parse_aarch64_dp_sfp_adv_simd(raw_instr: &[u8]) -> AArch64Inst {
                 "{}_xxx_{}_{}_{}_xxxxxxxxxx",
                 "0xx0", "0x", "x1xx", "xxxxxxx00"
             ),
-            |_raw_instr: &[u8]| todo!("Advanced SIMD three different"),
+            parse_advanced_simd_three_different,
         )
         .bind(
             &format!(
@@ -446,6 +449,14 @@ fn parse_aarch64_dp_sfp_adv_simd(raw_instr: &[u8]) -> AArch64Inst {
     }
 }
 
+// The transfer register of a load or store is a SIMD&FP register when V is set.
+fn ld_st_rt_hint(v: u8) -> AArch64MnemonicHint {
+    match v {
+        0b1 => AArch64MnemonicHint::V,
+        _ => AArch64MnemonicHint::X,
+    }
+}
+
 // parse Load and stores instructions i pairn AArch64
 fn parse_aarch64_load_and_stores(raw_instr: &[u8]) -> AArch64Inst {
     pub static MATCHER: Lazy<BitPatternMatcher<AArch64Inst>> = Lazy::new(|| {
@@ -468,9 +479,7 @@ fn parse_aarch64_load_and_stores(raw_instr: &[u8]) -> AArch64Inst {
         )
         .bind(
             "0x00_1_1_0_11_x_xxxxxx_xxxx_xx_xxxxxxxxxx",
-            |_raw_instr: &[u8]| {
-                todo!("Advanced SIMD Load/Store single structure(post-indexed)");
-            },
+            parse_adv_simd_ld_st_single_structure_post_indexed,
         )
         .bind(
             "1101_1_0_0_1x_x_1xxxxx_xxxx_xx_xxxxxxxxxx",
@@ -673,6 +682,11 @@ fn parse_add_sub_immediate(raw_instr: &[u8]) -> AArch64Inst {
              Extract(imm12): Extract<u16, 10, 22>,
              Extract(rn): Extract<u8, 5, 10>,
              Extract(rd): Extract<u8, 0, 5>| {
+                // The flag setting forms write to the zero register instead of sp
+                let rd_hint = match sf_op_s & 0b1 {
+                    0b0 => AArch64MnemonicHint::X_SP,
+                    _ => AArch64MnemonicHint::X,
+                };
                 let data = ShImm12RnRd {
                     sh,
                     imm12,
@@ -680,7 +694,7 @@ fn parse_add_sub_immediate(raw_instr: &[u8]) -> AArch64Inst {
                         AArch64MnemonicHint::X_SP,
                         rn,
                     ),
-                    rd: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rd),
+                    rd: AArch64Architecture::get_register_by_mnemonic(rd_hint, rd),
                 };
 
                 match sf_op_s {
@@ -779,10 +793,7 @@ fn parse_load_store_reg_unsigned_imm(raw_instr: &[u8]) -> AArch64Inst {
                         AArch64MnemonicHint::X_SP,
                         rn,
                     ),
-                    rt: AArch64Architecture::get_register_by_mnemonic(
-                        AArch64MnemonicHint::X_SP,
-                        rt,
-                    ),
+                    rt: AArch64Architecture::get_register_by_mnemonic(ld_st_rt_hint(v), rt),
                 };
 
                 match (size, v, opc) {
@@ -1146,7 +1157,7 @@ fn parse_hints(raw_instr: &[u8]) -> AArch64Inst {
         let mut m = BitPatternMatcher::new();
         m.bind(
             "11010101000000110010_xxxx_xxx_11111",
-            |raw_instr: &[u8],
+            |_raw_instr: &[u8],
              Extract(crm): Extract<u8, 8, 12>,
              Extract(op2): Extract<u8, 5, 8>| match (crm, op2) {
                 (0b0000, 0b000) => AArch64Inst::Nop,
@@ -1170,7 +1181,7 @@ fn parse_hints(raw_instr: &[u8]) -> AArch64Inst {
                 (0b0011, 0b101) => AArch64Inst::AutiaspVar,
                 (0b0011, 0b110) => AArch64Inst::AutibzVar,
                 (0b0011, 0b111) => AArch64Inst::AutibspVar,
-                _ => todo!("Unknown instruction {:?}", raw_instr),
+                (crm, op2) => AArch64Inst::Hint(Hints { crm, op2 }),
             },
         );
 
@@ -1285,7 +1296,7 @@ fn parse_load_store_reg_reg_offset(raw_instr: &[u8]) -> AArch64Inst {
                         AArch64MnemonicHint::X_SP,
                         rn,
                     ),
-                    rt: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rt),
+                    rt: AArch64Architecture::get_register_by_mnemonic(ld_st_rt_hint(v), rt),
                 };
 
                 match (size, v, opc, option) {
@@ -1337,6 +1348,11 @@ fn parse_add_sub_ext_reg(raw_instr: &[u8]) -> AArch64Inst {
              Extract(imm3): Extract<u8, 10, 13>,
              Extract(rn): Extract<u8, 5, 10>,
              Extract(rd): Extract<u8, 0, 5>| {
+                // The flag setting forms write to the zero register instead of sp
+                let rd_hint = match sf_op_s & 0b1 {
+                    0b0 => AArch64MnemonicHint::X_SP,
+                    _ => AArch64MnemonicHint::X,
+                };
                 let data = AddSubtractExtReg {
                     rm: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rm),
                     option,
@@ -1345,10 +1361,7 @@ fn parse_add_sub_ext_reg(raw_instr: &[u8]) -> AArch64Inst {
                         AArch64MnemonicHint::X_SP,
                         rn,
                     ),
-                    rd: AArch64Architecture::get_register_by_mnemonic(
-                        AArch64MnemonicHint::X_SP,
-                        rd,
-                    ),
+                    rd: AArch64Architecture::get_register_by_mnemonic(rd_hint, rd),
                 };
 
                 match (sf_op_s, opt) {
@@ -1432,15 +1445,17 @@ fn parse_logical_imm(raw_instr: &[u8]) -> AArch64Inst {
              Extract(imms): Extract<u8, 10, 16>,
              Extract(rn): Extract<u8, 5, 10>,
              Extract(rd): Extract<u8, 0, 5>| {
+                // ANDS writes to the zero register instead of sp
+                let rd_hint = match opc {
+                    0b11 => AArch64MnemonicHint::X,
+                    _ => AArch64MnemonicHint::X_SP,
+                };
                 let data = LogicalImm {
                     n,
                     immr,
                     imms,
                     rn: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rn),
-                    rd: AArch64Architecture::get_register_by_mnemonic(
-                        AArch64MnemonicHint::X_SP,
-                        rd,
-                    ),
+                    rd: AArch64Architecture::get_register_by_mnemonic(rd_hint, rd),
                 };
 
                 match (sf, opc, n) {
@@ -1485,12 +1500,12 @@ fn parse_load_store_reg_pair_offset(raw_instr: &[u8]) -> AArch64Inst {
                     opc,
                     imm7,
                     o: 0b010,
-                    rt2,
+                    rt2: AArch64Architecture::get_register_by_mnemonic(ld_st_rt_hint(v), rt2),
                     rn: AArch64Architecture::get_register_by_mnemonic(
                         AArch64MnemonicHint::X_SP,
                         rn,
                     ),
-                    rt: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rt),
+                    rt: AArch64Architecture::get_register_by_mnemonic(ld_st_rt_hint(v), rt),
                 };
 
                 match (opc, v, l) {
@@ -1751,7 +1766,7 @@ fn parse_load_store_reg_unscaled_imm(raw_instr: &[u8]) -> AArch64Inst {
                         AArch64MnemonicHint::X_SP,
                         rn,
                     ),
-                    rt: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rt),
+                    rt: AArch64Architecture::get_register_by_mnemonic(ld_st_rt_hint(v), rt),
                 };
 
                 match (size, v, opc) {
@@ -1852,12 +1867,12 @@ fn parse_load_store_reg_pair_pre_indexed(raw_instr: &[u8]) -> AArch64Inst {
                     opc,
                     o: 0b011,
                     imm7,
-                    rt2,
+                    rt2: AArch64Architecture::get_register_by_mnemonic(ld_st_rt_hint(v), rt2),
                     rn: AArch64Architecture::get_register_by_mnemonic(
                         AArch64MnemonicHint::X_SP,
                         rn,
                     ),
-                    rt: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rt),
+                    rt: AArch64Architecture::get_register_by_mnemonic(ld_st_rt_hint(v), rt),
                 };
 
                 match (opc, v, l) {
@@ -1905,12 +1920,12 @@ fn parse_load_store_reg_pair_post_indexed(raw_instr: &[u8]) -> AArch64Inst {
                     opc,
                     o: 0b001,
                     imm7,
-                    rt2,
+                    rt2: AArch64Architecture::get_register_by_mnemonic(ld_st_rt_hint(v), rt2),
                     rn: AArch64Architecture::get_register_by_mnemonic(
                         AArch64MnemonicHint::X_SP,
                         rn,
                     ),
-                    rt: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rt),
+                    rt: AArch64Architecture::get_register_by_mnemonic(ld_st_rt_hint(v), rt),
                 };
 
                 match (opc, v, l) {
@@ -2011,7 +2026,7 @@ fn parse_load_store_reg_imm_pre_indexed(raw_instr: &[u8]) -> AArch64Inst {
                         AArch64MnemonicHint::X_SP,
                         rn,
                     ),
-                    rt: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rt),
+                    rt: AArch64Architecture::get_register_by_mnemonic(ld_st_rt_hint(v), rt),
                 };
 
                 match (size, v, opc) {
@@ -2077,7 +2092,7 @@ fn parse_load_store_reg_imm_post_indexed(raw_instr: &[u8]) -> AArch64Inst {
                         AArch64MnemonicHint::X_SP,
                         rn,
                     ),
-                    rt: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rt),
+                    rt: AArch64Architecture::get_register_by_mnemonic(ld_st_rt_hint(v), rt),
                 };
 
                 match (size, v, opc) {
@@ -2165,12 +2180,19 @@ fn parse_advanced_simd_copy(raw_instr: &[u8]) -> AArch64Inst {
              Extract(imm4): Extract<u8, 11, 15>,
              Extract(rn): Extract<u8, 5, 10>,
              Extract(rd): Extract<u8, 0, 5>| {
+                // DUP (general) and INS (general) read a general register, SMOV and UMOV write
+                // one.
+                let (rn_hint, rd_hint) = match (op, imm4) {
+                    (0b0, 0b0001 | 0b0011) => (AArch64MnemonicHint::X, AArch64MnemonicHint::V),
+                    (0b0, 0b0101 | 0b0111) => (AArch64MnemonicHint::V, AArch64MnemonicHint::X),
+                    _ => (AArch64MnemonicHint::V, AArch64MnemonicHint::V),
+                };
                 let data = AdvancedSimdCopy {
                     q,
                     imm5,
                     imm4,
-                    rn: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rn),
-                    rd: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rd),
+                    rn: AArch64Architecture::get_register_by_mnemonic(rn_hint, rn),
+                    rd: AArch64Architecture::get_register_by_mnemonic(rd_hint, rd),
                 };
 
                 match (q, op, imm5, imm4) {
@@ -2441,9 +2463,14 @@ fn parse_conv_between_float_and_int(raw_instr: &[u8]) -> AArch64Inst {
              Extract(opcode): Extract<u8, 16, 19>,
              Extract(rn): Extract<u8, 5, 10>,
              Extract(rd): Extract<u8, 0, 5>| {
+                // SCVTF, UCVTF and the FMOVs into an FP register read a general register.
+                let (rn_hint, rd_hint) = match opcode {
+                    0b010 | 0b011 | 0b111 => (AArch64MnemonicHint::X, AArch64MnemonicHint::V),
+                    _ => (AArch64MnemonicHint::V, AArch64MnemonicHint::X),
+                };
                 let data = RnRd {
-                    rn: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rn),
-                    rd: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rd),
+                    rn: AArch64Architecture::get_register_by_mnemonic(rn_hint, rn),
+                    rd: AArch64Architecture::get_register_by_mnemonic(rd_hint, rd),
                 };
 
                 match (sf, s, ptype, rmode, opcode) {
@@ -2525,7 +2552,7 @@ fn parse_conv_between_float_and_int(raw_instr: &[u8]) -> AArch64Inst {
                         AArch64Inst::FcvtzsScalarIntDoublePrecisionTo32(data)
                     }
                     (0b0, 0b0, 0b01, 0b11, 0b001) => {
-                        AArch64Inst::FcvtzsScalarIntDoublePrecisionTo32(data)
+                        AArch64Inst::FcvtzuScalarIntDoublePrecisionTo32(data)
                     }
                     (0b0, 0b0, 0b01, 0b11, 0b110) => AArch64Inst::Fjcvtzs(data),
 
@@ -2607,7 +2634,7 @@ fn parse_conv_between_float_and_int(raw_instr: &[u8]) -> AArch64Inst {
                         AArch64Inst::FcvtzsScalarIntDoublePrecisionTo64(data)
                     }
                     (0b1, 0b0, 0b01, 0b11, 0b001) => {
-                        AArch64Inst::FcvtzsScalarIntDoublePrecisionTo64(data)
+                        AArch64Inst::FcvtzuScalarIntDoublePrecisionTo64(data)
                     }
 
                     (0b1, 0b0, 0b10, 0b01, 0b110) => AArch64Inst::FmovGeneralTopHalfOf128To64(data),
@@ -2902,7 +2929,7 @@ fn parse_advanced_simd_three_same(raw_instr: &[u8]) -> AArch64Inst {
                     (0b0, 0b00 | 0b01, 0b11111) => AArch64Inst::Frecps(data),
 
                     (0b0, 0b00, 0b00011) => AArch64Inst::AndVec(data),
-                    (0b0, 0b01, 0b00011) => AArch64Inst::AndVec(data),
+                    (0b0, 0b01, 0b00011) => AArch64Inst::BicVecReg(data),
 
                     (0b0, 0b10 | 0b11, 0b11000) => AArch64Inst::FminnmVec(data),
                     (0b0, 0b10 | 0b11, 0b11001) => AArch64Inst::FmlsVec(data),
@@ -2980,12 +3007,14 @@ fn parse_adv_simd_shift_by_imm(raw_instr: &[u8]) -> AArch64Inst {
             |raw_instr: &[u8],
              Extract(q): Extract<u8, 30, 31>,
              Extract(u): Extract<u8, 29, 30>,
+             Extract(immh): Extract<u8, 19, 23>,
              Extract(immb): Extract<u8, 16, 19>,
              Extract(opcode): Extract<u8, 11, 16>,
              Extract(rn): Extract<u8, 5, 10>,
              Extract(rd): Extract<u8, 0, 5>| {
                 let data = AdvSimdShiftByImm {
                     q,
+                    immh,
                     immb,
                     rn: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rn),
                     rd: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rd),
@@ -3090,6 +3119,22 @@ fn parse_float_data_proc_1src(raw_instr: &[u8]) -> AArch64Inst {
                     (0b0, 0b0, 0b01, 0b001110) => AArch64Inst::FrintxScalarDoublePrecisionVar(data),
                     (0b0, 0b0, 0b01, 0b001111) => AArch64Inst::FrintiScalarDoublePrecisionVar(data),
 
+                    (0b0, 0b0, 0b11, 0b000000) => AArch64Inst::FmovRegHalfPrecisionVar(data),
+                    (0b0, 0b0, 0b11, 0b000001) => AArch64Inst::FabsScalarHalfPrecisionVar(data),
+                    (0b0, 0b0, 0b11, 0b000010) => AArch64Inst::FnegScalarHalfPrecisionVar(data),
+                    (0b0, 0b0, 0b11, 0b000011) => AArch64Inst::FsqrtScalarHalfPrecisionVar(data),
+
+                    (0b0, 0b0, 0b11, 0b000100) => AArch64Inst::FcvtHalfToSinglePrecisionVar(data),
+                    (0b0, 0b0, 0b11, 0b000101) => AArch64Inst::FcvtHalfToDoublePrecisionVar(data),
+
+                    (0b0, 0b0, 0b11, 0b001000) => AArch64Inst::FrintnScalarHalfPrecisionVar(data),
+                    (0b0, 0b0, 0b11, 0b001001) => AArch64Inst::FrintpScalarHalfPrecisionVar(data),
+                    (0b0, 0b0, 0b11, 0b001010) => AArch64Inst::FrintmScalarHalfPrecisionVar(data),
+                    (0b0, 0b0, 0b11, 0b001011) => AArch64Inst::FrintzScalarHalfPrecisionVar(data),
+                    (0b0, 0b0, 0b11, 0b001100) => AArch64Inst::FrintaScalarHalfPrecisionVar(data),
+                    (0b0, 0b0, 0b11, 0b001110) => AArch64Inst::FrintxScalarHalfPrecisionVar(data),
+                    (0b0, 0b0, 0b11, 0b001111) => AArch64Inst::FrintiScalarHalfPrecisionVar(data),
+
                     _ => todo!("Unknown instruction {:?}", raw_instr),
                 }
             },
@@ -3124,11 +3169,11 @@ fn parse_adv_simd_scalar_pairwise(raw_instr: &[u8]) -> AArch64Inst {
 
                 match (u, size, opcode) {
                     (0b0, _, 0b11011) => AArch64Inst::AddpScalar(data),
-                    (0b0, 0b00 | 0b01, 0b01100) => AArch64Inst::FmaxnmpScalarEncoding(data),
-                    (0b0, 0b00 | 0b01, 0b01101) => AArch64Inst::FaddpScalarEncoding(data),
-                    (0b0, 0b00 | 0b01, 0b01111) => AArch64Inst::FmaxpScalarEncoding(data),
-                    (0b0, 0b10 | 0b11, 0b01100) => AArch64Inst::FminnmpScalarEncoding(data),
-                    (0b0, 0b10 | 0b11, 11) => AArch64Inst::FminpScalarEncoding(data),
+                    (0b1, 0b00 | 0b01, 0b01100) => AArch64Inst::FmaxnmpScalarEncoding(data),
+                    (0b1, 0b00 | 0b01, 0b01101) => AArch64Inst::FaddpScalarEncoding(data),
+                    (0b1, 0b00 | 0b01, 0b01111) => AArch64Inst::FmaxpScalarEncoding(data),
+                    (0b1, 0b10 | 0b11, 0b01100) => AArch64Inst::FminnmpScalarEncoding(data),
+                    (0b1, 0b10 | 0b11, 0b01111) => AArch64Inst::FminpScalarEncoding(data),
 
                     _ => todo!("Unknown instruction {:?}", raw_instr),
                 }
@@ -3218,7 +3263,293 @@ fn parse_adv_simd_ld_st_single_structure(raw_instr: &[u8]) -> AArch64Inst {
                     (0b1, 0b1, 0b101, 0b0, 0b01) => AArch64Inst::Ld4SingleStructureVar64(data),
 
                     (0b1, 0b1, 0b110, 0b0, _) => AArch64Inst::Ld2r(data),
-                    (0b1, 0b1, 0b111, 0b0, _) => AArch64Inst::Ld2r(data),
+                    (0b1, 0b1, 0b111, 0b0, _) => AArch64Inst::Ld4r(data),
+
+                    _ => todo!("Unknown instruction {:?}", raw_instr),
+                }
+            },
+        );
+
+        m
+    });
+
+    if let Some(instr) = MATCHER.try_match(raw_instr) {
+        instr
+    } else {
+        todo!("Unknown instruction {:?}", raw_instr);
+    }
+}
+
+fn parse_adv_simd_ld_st_single_structure_post_indexed(raw_instr: &[u8]) -> AArch64Inst {
+    pub static MATCHER: Lazy<BitPatternMatcher<AArch64Inst>> = Lazy::new(|| {
+        let mut m = BitPatternMatcher::new();
+        m.bind(
+            "0_x_0011011_x_x_xxxxx_xxx_x_xx_xxxxx_xxxxx",
+            |raw_instr: &[u8],
+             Extract(q): Extract<u8, 30, 31>,
+             Extract(l): Extract<u8, 22, 23>,
+             Extract(r): Extract<u8, 21, 22>,
+             Extract(rm): Extract<u8, 16, 21>,
+             Extract(opcode): Extract<u8, 13, 16>,
+             Extract(s): Extract<u8, 12, 13>,
+             Extract(size): Extract<u8, 10, 12>,
+             Extract(rn): Extract<u8, 5, 10>,
+             Extract(rt): Extract<u8, 0, 5>| {
+                let data = AdvSimdLdStSingleStructurePostIndexed {
+                    q,
+                    rm: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rm),
+                    s,
+                    size,
+                    rn: AArch64Architecture::get_register_by_mnemonic(
+                        AArch64MnemonicHint::X_SP,
+                        rn,
+                    ),
+                    rt,
+                };
+
+                match (l, r, opcode, s, size) {
+                    (0b0, 0b0, 0b000, _, _) => AArch64Inst::St1SingleStructurePostIndexedVar8(data),
+                    (0b0, 0b0, 0b001, _, _) => AArch64Inst::St3SingleStructurePostIndexedVar8(data),
+                    (0b0, 0b0, 0b010, _, 0b00 | 0b10) => {
+                        AArch64Inst::St1SingleStructurePostIndexedVar16(data)
+                    }
+                    (0b0, 0b0, 0b011, _, 0b00 | 0b10) => {
+                        AArch64Inst::St3SingleStructurePostIndexedVar16(data)
+                    }
+
+                    (0b0, 0b0, 0b100, _, 0b00) => {
+                        AArch64Inst::St1SingleStructurePostIndexedVar32(data)
+                    }
+                    (0b0, 0b0, 0b100, 0b0, 0b01) => {
+                        AArch64Inst::St1SingleStructurePostIndexedVar64(data)
+                    }
+                    (0b0, 0b0, 0b101, _, 0b00) => {
+                        AArch64Inst::St3SingleStructurePostIndexedVar32(data)
+                    }
+                    (0b0, 0b0, 0b101, 0b0, 0b01) => {
+                        AArch64Inst::St3SingleStructurePostIndexedVar64(data)
+                    }
+
+                    (0b0, 0b1, 0b000, _, _) => AArch64Inst::St2SingleStructurePostIndexedVar8(data),
+                    (0b0, 0b1, 0b001, _, _) => AArch64Inst::St4SingleStructurePostIndexedVar8(data),
+                    (0b0, 0b1, 0b010, _, 0b00 | 0b10) => {
+                        AArch64Inst::St2SingleStructurePostIndexedVar16(data)
+                    }
+
+                    (0b0, 0b1, 0b011, _, 0b00 | 0b10) => {
+                        AArch64Inst::St4SingleStructurePostIndexedVar16(data)
+                    }
+
+                    (0b0, 0b1, 0b100, _, 0b00) => {
+                        AArch64Inst::St2SingleStructurePostIndexedVar32(data)
+                    }
+                    (0b0, 0b1, 0b100, 0b0, 0b01) => {
+                        AArch64Inst::St2SingleStructurePostIndexedVar64(data)
+                    }
+                    (0b0, 0b1, 0b101, _, 0b00) => {
+                        AArch64Inst::St4SingleStructurePostIndexedVar32(data)
+                    }
+                    (0b0, 0b1, 0b101, 0b0, 0b01) => {
+                        AArch64Inst::St4SingleStructurePostIndexedVar64(data)
+                    }
+
+                    (0b1, 0b0, 0b000, _, _) => AArch64Inst::Ld1SingleStructurePostIndexedVar8(data),
+                    (0b1, 0b0, 0b001, _, _) => AArch64Inst::Ld3SingleStructurePostIndexedVar8(data),
+                    (0b1, 0b0, 0b010, _, 0b00 | 0b10) => {
+                        AArch64Inst::Ld1SingleStructurePostIndexedVar16(data)
+                    }
+
+                    (0b1, 0b0, 0b011, _, 0b00 | 0b10) => {
+                        AArch64Inst::Ld3SingleStructurePostIndexedVar16(data)
+                    }
+
+                    (0b1, 0b0, 0b100, _, 0b00) => {
+                        AArch64Inst::Ld1SingleStructurePostIndexedVar32(data)
+                    }
+                    (0b1, 0b0, 0b100, 0b0, 0b01) => {
+                        AArch64Inst::Ld1SingleStructurePostIndexedVar64(data)
+                    }
+                    (0b1, 0b0, 0b101, _, 0b00) => {
+                        AArch64Inst::Ld3SingleStructurePostIndexedVar32(data)
+                    }
+                    (0b1, 0b0, 0b101, 0b0, 0b01) => {
+                        AArch64Inst::Ld3SingleStructurePostIndexedVar64(data)
+                    }
+
+                    (0b1, 0b0, 0b110, 0b0, _) => AArch64Inst::Ld1rPostIndexed(data),
+                    (0b1, 0b0, 0b111, 0b0, _) => AArch64Inst::Ld3rPostIndexed(data),
+
+                    (0b1, 0b1, 0b000, _, _) => AArch64Inst::Ld2SingleStructurePostIndexedVar8(data),
+                    (0b1, 0b1, 0b001, _, _) => AArch64Inst::Ld4SingleStructurePostIndexedVar8(data),
+                    (0b1, 0b1, 0b010, _, 0b00 | 0b10) => {
+                        AArch64Inst::Ld2SingleStructurePostIndexedVar16(data)
+                    }
+
+                    (0b1, 0b1, 0b011, _, 0b00 | 0b10) => {
+                        AArch64Inst::Ld4SingleStructurePostIndexedVar16(data)
+                    }
+
+                    (0b1, 0b1, 0b100, _, 0b00) => {
+                        AArch64Inst::Ld2SingleStructurePostIndexedVar32(data)
+                    }
+                    (0b1, 0b1, 0b100, 0b0, 0b01) => {
+                        AArch64Inst::Ld2SingleStructurePostIndexedVar64(data)
+                    }
+                    (0b1, 0b1, 0b101, _, 0b00) => {
+                        AArch64Inst::Ld4SingleStructurePostIndexedVar32(data)
+                    }
+                    (0b1, 0b1, 0b101, 0b0, 0b01) => {
+                        AArch64Inst::Ld4SingleStructurePostIndexedVar64(data)
+                    }
+
+                    (0b1, 0b1, 0b110, 0b0, _) => AArch64Inst::Ld2rPostIndexed(data),
+                    (0b1, 0b1, 0b111, 0b0, _) => AArch64Inst::Ld4rPostIndexed(data),
+
+                    _ => todo!("Unknown instruction {:?}", raw_instr),
+                }
+            },
+        );
+
+        m
+    });
+
+    if let Some(instr) = MATCHER.try_match(raw_instr) {
+        instr
+    } else {
+        todo!("Unknown instruction {:?}", raw_instr);
+    }
+}
+
+fn parse_adv_simd_scalar_three_same(raw_instr: &[u8]) -> AArch64Inst {
+    pub static MATCHER: Lazy<BitPatternMatcher<AArch64Inst>> = Lazy::new(|| {
+        let mut m = BitPatternMatcher::new();
+        m.bind(
+            "01_x_11110_xx_1_xxxxx_xxxxx_1_xxxxx_xxxxx",
+            |raw_instr: &[u8],
+             Extract(u): Extract<u8, 29, 30>,
+             Extract(size): Extract<u8, 22, 24>,
+             Extract(rm): Extract<u8, 16, 21>,
+             Extract(opcode): Extract<u8, 11, 16>,
+             Extract(rn): Extract<u8, 5, 10>,
+             Extract(rd): Extract<u8, 0, 5>| {
+                let data = AdvSimdScalarThreeSame {
+                    size,
+                    rm: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rm),
+                    rn: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rn),
+                    rd: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rd),
+                };
+
+                match (u, size, opcode) {
+                    (0b0, _, 0b00001) => AArch64Inst::SqaddScalar(data),
+                    (0b0, _, 0b00101) => AArch64Inst::SqsubScalar(data),
+                    (0b0, _, 0b00110) => AArch64Inst::CmgtRegScalar(data),
+                    (0b0, _, 0b00111) => AArch64Inst::CmgeRegScalar(data),
+                    (0b0, _, 0b01000) => AArch64Inst::SshlScalar(data),
+                    (0b0, _, 0b01001) => AArch64Inst::SqshlRegScalar(data),
+                    (0b0, _, 0b01010) => AArch64Inst::SrshlScalar(data),
+                    (0b0, _, 0b01011) => AArch64Inst::SqrshlScalar(data),
+                    (0b0, _, 0b10000) => AArch64Inst::AddScalar(data),
+                    (0b0, _, 0b10001) => AArch64Inst::CmtstScalar(data),
+                    (0b0, _, 0b10110) => AArch64Inst::SqdmulhScalar(data),
+
+                    (0b0, 0b00 | 0b01, 0b11011) => AArch64Inst::FmulxScalar(data),
+                    (0b0, 0b00 | 0b01, 0b11100) => AArch64Inst::FcmeqRegScalar(data),
+                    (0b0, 0b00 | 0b01, 0b11111) => AArch64Inst::FrecpsScalar(data),
+
+                    (0b0, 0b10 | 0b11, 0b11111) => AArch64Inst::FrsqrtsScalar(data),
+
+                    (0b1, _, 0b00001) => AArch64Inst::UqaddScalar(data),
+                    (0b1, _, 0b00101) => AArch64Inst::UqsubScalar(data),
+                    (0b1, _, 0b00110) => AArch64Inst::CmhiRegScalar(data),
+                    (0b1, _, 0b00111) => AArch64Inst::CmhsRegScalar(data),
+                    (0b1, _, 0b01000) => AArch64Inst::UshlScalar(data),
+                    (0b1, _, 0b01001) => AArch64Inst::UqshlRegScalar(data),
+                    (0b1, _, 0b01010) => AArch64Inst::UrshlScalar(data),
+                    (0b1, _, 0b01011) => AArch64Inst::UqrshlScalar(data),
+                    (0b1, _, 0b10000) => AArch64Inst::SubScalar(data),
+                    (0b1, _, 0b10001) => AArch64Inst::CmeqRegScalar(data),
+                    (0b1, _, 0b10110) => AArch64Inst::SqrdmulhScalar(data),
+
+                    (0b1, 0b00 | 0b01, 0b11100) => AArch64Inst::FcmgeRegScalar(data),
+                    (0b1, 0b00 | 0b01, 0b11101) => AArch64Inst::FacgeScalar(data),
+
+                    (0b1, 0b10 | 0b11, 0b11010) => AArch64Inst::FabdScalar(data),
+                    (0b1, 0b10 | 0b11, 0b11100) => AArch64Inst::FcmgtRegScalar(data),
+                    (0b1, 0b10 | 0b11, 0b11101) => AArch64Inst::FacgtScalar(data),
+
+                    _ => todo!("Unknown instruction {:?}", raw_instr),
+                }
+            },
+        );
+
+        m
+    });
+
+    if let Some(instr) = MATCHER.try_match(raw_instr) {
+        instr
+    } else {
+        todo!("Unknown instruction {:?}", raw_instr);
+    }
+}
+
+fn parse_adv_simd_scalar_2reg_miscellaneous(raw_instr: &[u8]) -> AArch64Inst {
+    pub static MATCHER: Lazy<BitPatternMatcher<AArch64Inst>> = Lazy::new(|| {
+        let mut m = BitPatternMatcher::new();
+        m.bind(
+            "01_x_11110_xx_10000_xxxxx_10_xxxxx_xxxxx",
+            |raw_instr: &[u8],
+             Extract(u): Extract<u8, 29, 30>,
+             Extract(size): Extract<u8, 22, 24>,
+             Extract(opcode): Extract<u8, 12, 17>,
+             Extract(rn): Extract<u8, 5, 10>,
+             Extract(rd): Extract<u8, 0, 5>| {
+                let data = AdvSimdScalarTwoRegMisc {
+                    size,
+                    rn: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rn),
+                    rd: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rd),
+                };
+
+                match (u, size, opcode) {
+                    (0b0, _, 0b00011) => AArch64Inst::SuqaddScalar(data),
+                    (0b0, _, 0b00111) => AArch64Inst::SqabsScalar(data),
+                    (0b0, _, 0b01000) => AArch64Inst::CmgtZeroScalar(data),
+                    (0b0, _, 0b01001) => AArch64Inst::CmeqZeroScalar(data),
+                    (0b0, _, 0b01010) => AArch64Inst::CmltZeroScalar(data),
+                    (0b0, _, 0b01011) => AArch64Inst::AbsScalar(data),
+                    (0b0, _, 0b10100) => AArch64Inst::SqxtnScalar(data),
+
+                    (0b0, 0b00 | 0b01, 0b11010) => AArch64Inst::FcvtnsScalar(data),
+                    (0b0, 0b00 | 0b01, 0b11011) => AArch64Inst::FcvtmsScalar(data),
+                    (0b0, 0b00 | 0b01, 0b11100) => AArch64Inst::FcvtasScalar(data),
+                    (0b0, 0b00 | 0b01, 0b11101) => AArch64Inst::ScvtfScalarInt(data),
+
+                    (0b0, 0b10 | 0b11, 0b01100) => AArch64Inst::FcmgtZeroScalar(data),
+                    (0b0, 0b10 | 0b11, 0b01101) => AArch64Inst::FcmeqZeroScalar(data),
+                    (0b0, 0b10 | 0b11, 0b01110) => AArch64Inst::FcmltZeroScalar(data),
+                    (0b0, 0b10 | 0b11, 0b11010) => AArch64Inst::FcvtpsScalar(data),
+                    (0b0, 0b10 | 0b11, 0b11011) => AArch64Inst::FcvtzsScalarInt(data),
+                    (0b0, 0b10 | 0b11, 0b11101) => AArch64Inst::FrecpeScalar(data),
+                    (0b0, 0b10 | 0b11, 0b11111) => AArch64Inst::Frecpx(data),
+
+                    (0b1, _, 0b00011) => AArch64Inst::UsqaddScalar(data),
+                    (0b1, _, 0b00111) => AArch64Inst::SqnegScalar(data),
+                    (0b1, _, 0b01000) => AArch64Inst::CmgeZeroScalar(data),
+                    (0b1, _, 0b01001) => AArch64Inst::CmleZeroScalar(data),
+                    (0b1, _, 0b01011) => AArch64Inst::NegScalar(data),
+                    (0b1, _, 0b10010) => AArch64Inst::SqxtunScalar(data),
+                    (0b1, _, 0b10100) => AArch64Inst::UqxtnScalar(data),
+
+                    (0b1, 0b00 | 0b01, 0b10110) => AArch64Inst::FcvtxnScalar(data),
+                    (0b1, 0b00 | 0b01, 0b11010) => AArch64Inst::FcvtnuScalar(data),
+                    (0b1, 0b00 | 0b01, 0b11011) => AArch64Inst::FcvtmuScalar(data),
+                    (0b1, 0b00 | 0b01, 0b11100) => AArch64Inst::FcvtauScalar(data),
+                    (0b1, 0b00 | 0b01, 0b11101) => AArch64Inst::UcvtfScalarInt(data),
+
+                    (0b1, 0b10 | 0b11, 0b01100) => AArch64Inst::FcmgeZeroScalar(data),
+                    (0b1, 0b10 | 0b11, 0b01101) => AArch64Inst::FcmleZeroScalar(data),
+                    (0b1, 0b10 | 0b11, 0b11010) => AArch64Inst::FcvtpuScalar(data),
+                    (0b1, 0b10 | 0b11, 0b11011) => AArch64Inst::FcvtzuScalarInt(data),
+                    (0b1, 0b10 | 0b11, 0b11101) => AArch64Inst::FrsqrteScalar(data),
 
                     _ => todo!("Unknown instruction {:?}", raw_instr),
                 }
@@ -3365,7 +3696,7 @@ fn parse_adv_simd_across_lanes(raw_instr: &[u8]) -> AArch64Inst {
                     (0b0, _, 0b11011) => AArch64Inst::Addv(data),
 
                     (0b1, _, 0b00011) => AArch64Inst::Uaddlv(data),
-                    (0b1, _, 0b01010) => AArch64Inst::Uaddlv(data),
+                    (0b1, _, 0b01010) => AArch64Inst::Umaxv(data),
                     (0b1, _, 0b11010) => AArch64Inst::Uminv(data),
 
                     (0b1, 0b00 | 0b01, 0b01100) => AArch64Inst::FmaxnvmEncoding(data),
@@ -3721,13 +4052,13 @@ fn parse_floating_point_compare(raw_instr: &[u8]) -> AArch64Inst {
                 };
 
                 match (m, s, ptype, op, opcode2) {
-                    (0b0, 0b0, 0b00, 0b00, 0b00000 | 0b01000)
-                    | (0b0, 0b0, 0b01, 0b00, 0b00000 | 0b01000)
-                    | (0b0, 0b0, 0b11, 0b01, 0b00000 | 0b01000) => AArch64Inst::Fcmp(data),
+                    (0b0, 0b0, 0b00 | 0b01 | 0b11, 0b00, 0b00000 | 0b01000) => {
+                        AArch64Inst::Fcmp(data)
+                    }
 
-                    (0b0, 0b0, 0b00, 0b00, 0b10000 | 0b11000)
-                    | (0b0, 0b0, 0b01, 0b00, 0b10000 | 0b11000)
-                    | (0b0, 0b0, 0b11, 0b01, 0b10000 | 0b11000) => AArch64Inst::Fcmp(data),
+                    (0b0, 0b0, 0b00 | 0b01 | 0b11, 0b00, 0b10000 | 0b11000) => {
+                        AArch64Inst::Fcmpe(data)
+                    }
 
                     _ => todo!("Unknown instruction {:?}", raw_instr),
                 }
@@ -3788,6 +4119,111 @@ fn parse_advanced_simd_permute(raw_instr: &[u8]) -> AArch64Inst {
     }
 }
 
+fn parse_advanced_simd_table_lookup(raw_instr: &[u8]) -> AArch64Inst {
+    pub static MATCHER: Lazy<BitPatternMatcher<AArch64Inst>> = Lazy::new(|| {
+        let mut m = BitPatternMatcher::new();
+        m.bind(
+            "0_x_001110_xx_0_xxxxx_0_xx_x_00_xxxxx_xxxxx",
+            |raw_instr: &[u8],
+             Extract(q): Extract<u8, 30, 31>,
+             Extract(op2): Extract<u8, 22, 24>,
+             Extract(rm): Extract<u8, 16, 21>,
+             Extract(len): Extract<u8, 13, 15>,
+             Extract(op): Extract<u8, 12, 13>,
+             Extract(rn): Extract<u8, 5, 10>,
+             Extract(rd): Extract<u8, 0, 5>| {
+                let data = AdvSimdTableLookup {
+                    q,
+                    rm: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rm),
+                    len,
+                    rn: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rn),
+                    rd: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rd),
+                };
+
+                match (op2, op) {
+                    (0b00, 0b0) => AArch64Inst::Tbl(data),
+                    (0b00, 0b1) => AArch64Inst::Tbx(data),
+
+                    _ => todo!("Unknown instruction {:?}", raw_instr),
+                }
+            },
+        );
+
+        m
+    });
+
+    if let Some(instr) = MATCHER.try_match(raw_instr) {
+        instr
+    } else {
+        todo!("Unknown instruction {:?}", raw_instr);
+    }
+}
+
+fn parse_advanced_simd_three_different(raw_instr: &[u8]) -> AArch64Inst {
+    pub static MATCHER: Lazy<BitPatternMatcher<AArch64Inst>> = Lazy::new(|| {
+        let mut m = BitPatternMatcher::new();
+        m.bind(
+            "0_x_x_01110_xx_1_xxxxx_xxxx_00_xxxxx_xxxxx",
+            |raw_instr: &[u8],
+             Extract(q): Extract<u8, 30, 31>,
+             Extract(u): Extract<u8, 29, 30>,
+             Extract(size): Extract<u8, 22, 24>,
+             Extract(rm): Extract<u8, 16, 21>,
+             Extract(opcode): Extract<u8, 12, 16>,
+             Extract(rn): Extract<u8, 5, 10>,
+             Extract(rd): Extract<u8, 0, 5>| {
+                let data = QSizeRmRnRd {
+                    q,
+                    size,
+                    rm: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rm),
+                    rn: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rn),
+                    rd: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rd),
+                };
+
+                match (u, opcode) {
+                    (0b0, 0b0000) => AArch64Inst::Saddl(data),
+                    (0b0, 0b0001) => AArch64Inst::Saddw(data),
+                    (0b0, 0b0010) => AArch64Inst::Ssubl(data),
+                    (0b0, 0b0011) => AArch64Inst::Ssubw(data),
+                    (0b0, 0b0100) => AArch64Inst::Addhn(data),
+                    (0b0, 0b0101) => AArch64Inst::Sabal(data),
+                    (0b0, 0b0110) => AArch64Inst::Subhn(data),
+                    (0b0, 0b0111) => AArch64Inst::Sabdl(data),
+                    (0b0, 0b1000) => AArch64Inst::Smlal(data),
+                    (0b0, 0b1001) => AArch64Inst::Sqdmlal(data),
+                    (0b0, 0b1010) => AArch64Inst::Smlsl(data),
+                    (0b0, 0b1011) => AArch64Inst::Sqdmlsl(data),
+                    (0b0, 0b1100) => AArch64Inst::Smull(data),
+                    (0b0, 0b1101) => AArch64Inst::Sqdmull(data),
+                    (0b0, 0b1110) => AArch64Inst::Pmull(data),
+
+                    (0b1, 0b0000) => AArch64Inst::Uaddl(data),
+                    (0b1, 0b0001) => AArch64Inst::Uaddw(data),
+                    (0b1, 0b0010) => AArch64Inst::Usubl(data),
+                    (0b1, 0b0011) => AArch64Inst::Usubw(data),
+                    (0b1, 0b0100) => AArch64Inst::Raddhn(data),
+                    (0b1, 0b0101) => AArch64Inst::Uabal(data),
+                    (0b1, 0b0110) => AArch64Inst::Rsubhn(data),
+                    (0b1, 0b0111) => AArch64Inst::Uabdl(data),
+                    (0b1, 0b1000) => AArch64Inst::Umlal(data),
+                    (0b1, 0b1010) => AArch64Inst::Umlsl(data),
+                    (0b1, 0b1100) => AArch64Inst::Umull(data),
+
+                    _ => todo!("Unknown instruction {:?}", raw_instr),
+                }
+            },
+        );
+
+        m
+    });
+
+    if let Some(instr) = MATCHER.try_match(raw_instr) {
+        instr
+    } else {
+        todo!("Unknown instruction {:?}", raw_instr);
+    }
+}
+
 fn parse_float_data_proc_2src(raw_instr: &[u8]) -> AArch64Inst {
     pub static MATCHER: Lazy<BitPatternMatcher<AArch64Inst>> = Lazy::new(|| {
         let mut m = BitPatternMatcher::new();
@@ -3893,10 +4329,15 @@ fn parse_conv_between_float_and_fixed_point(raw_instr: &[u8]) -> AArch64Inst {
              Extract(scale): Extract<u8, 10, 16>,
              Extract(rn): Extract<u8, 5, 10>,
              Extract(rd): Extract<u8, 0, 5>| {
+                // FCVTZS and FCVTZU write a general register, SCVTF and UCVTF read one.
+                let (rn_hint, rd_hint) = match opcode {
+                    0b000 | 0b001 => (AArch64MnemonicHint::V, AArch64MnemonicHint::X),
+                    _ => (AArch64MnemonicHint::X, AArch64MnemonicHint::V),
+                };
                 let data = ConvBetweenFloatAndFixedPoint {
                     scale,
-                    rn: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rn),
-                    rd: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::V, rd),
+                    rn: AArch64Architecture::get_register_by_mnemonic(rn_hint, rn),
+                    rd: AArch64Architecture::get_register_by_mnemonic(rd_hint, rd),
                 };
 
                 match (sf, s, ptype, rmode, opcode, scale) {
@@ -4012,7 +4453,7 @@ fn parse_adv_simd_vec_x_indexed_elem(raw_instr: &[u8]) -> AArch64Inst {
         m.bind(
             "0_x_x_01111_xx_x_x_xxxx_xxxx_x_0_xxxxx_xxxxx",
             |raw_instr: &[u8],
-             Extract(q): Extract<u8, 31, 32>,
+             Extract(q): Extract<u8, 30, 31>,
              Extract(u): Extract<u8, 29, 30>,
              Extract(size): Extract<u8, 22, 24>,
              Extract(l): Extract<u8, 21, 22>,
@@ -4086,8 +4527,7 @@ fn parse_adv_simd_scalar_x_indexed_elem(raw_instr: &[u8]) -> AArch64Inst {
              Extract(h): Extract<u8, 11, 12>,
              Extract(rn): Extract<u8, 5, 10>,
              Extract(rd): Extract<u8, 0, 5>| {
-                let data = AdvSimdXIndexedElem {
-                    q: 0b1,
+                let data = AdvSimdScalarXIndexedElem {
                     size,
                     l,
                     m,
@@ -4098,17 +4538,17 @@ fn parse_adv_simd_scalar_x_indexed_elem(raw_instr: &[u8]) -> AArch64Inst {
                 };
 
                 match (u, size, opcode) {
-                    (0b0, _, 0b0011) => AArch64Inst::SqdmlalByElem(data),
-                    (0b0, _, 0b0111) => AArch64Inst::SqdmlslByElem(data),
-                    (0b0, _, 0b1011) => AArch64Inst::SqdmullByElem(data),
-                    (0b0, _, 0b1100) => AArch64Inst::SqdmulhByElem(data),
-                    (0b0, _, 0b1101) => AArch64Inst::SqrdmulhByElem(data),
+                    (0b0, _, 0b0011) => AArch64Inst::SqdmlalByElemScalar(data),
+                    (0b0, _, 0b0111) => AArch64Inst::SqdmlslByElemScalar(data),
+                    (0b0, _, 0b1011) => AArch64Inst::SqdmullByElemScalar(data),
+                    (0b0, _, 0b1100) => AArch64Inst::SqdmulhByElemScalar(data),
+                    (0b0, _, 0b1101) => AArch64Inst::SqrdmulhByElemScalar(data),
 
-                    (0b0, 0b10 | 0b11, 0b0001) => AArch64Inst::FmlaByElemEncoding(data),
-                    (0b0, 0b10 | 0b11, 0b0101) => AArch64Inst::FmlsByElemEncoding(data),
-                    (0b0, 0b10 | 0b11, 0b1001) => AArch64Inst::FmulByElemEncoding(data),
+                    (0b0, 0b10 | 0b11, 0b0001) => AArch64Inst::FmlaByElemScalarEncoding(data),
+                    (0b0, 0b10 | 0b11, 0b0101) => AArch64Inst::FmlsByElemScalarEncoding(data),
+                    (0b0, 0b10 | 0b11, 0b1001) => AArch64Inst::FmulByElemScalarEncoding(data),
 
-                    (0b1, 0b10 | 0b11, 0b1001) => AArch64Inst::FmulxByElemEncoding(data),
+                    (0b1, 0b10 | 0b11, 0b1001) => AArch64Inst::FmulxByElemScalarEncoding(data),
 
                     _ => todo!("Unknown instruction {:?}", raw_instr),
                 }
@@ -4228,7 +4668,7 @@ fn parse_sys_instr(raw_instr: &[u8]) -> AArch64Inst {
     pub static MATCHER: Lazy<BitPatternMatcher<AArch64Inst>> = Lazy::new(|| {
         let mut m = BitPatternMatcher::new();
         m.bind(
-            "1101010100000_xxx_0100_xxxx_xxx_xxxxx",
+            "1101010100_x_01_xxx_xxxx_xxxx_xxx_xxxxx",
             |raw_instr: &[u8],
              Extract(l): Extract<u8, 21, 22>,
              Extract(op1): Extract<u8, 16, 19>,
@@ -4345,7 +4785,7 @@ fn parse_load_register_literal(raw_instr: &[u8]) -> AArch64Inst {
              Extract(rt): Extract<u8, 0, 5>| {
                 let data = Imm19Rt {
                     imm19,
-                    rt: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rt),
+                    rt: AArch64Architecture::get_register_by_mnemonic(ld_st_rt_hint(v), rt),
                 };
 
                 match (opc, v) {
@@ -4387,7 +4827,10 @@ fn parse_compare_and_swap_pair(raw_instr: &[u8]) -> AArch64Inst {
              Extract(rt): Extract<u8, 0, 5>| {
                 let data = CompareAndSwapPair {
                     rs,
-                    rn: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rn),
+                    rn: AArch64Architecture::get_register_by_mnemonic(
+                        AArch64MnemonicHint::X_SP,
+                        rn,
+                    ),
                     rt,
                 };
 
@@ -4424,7 +4867,7 @@ fn parse_load_store_memory_tags(raw_instr: &[u8]) -> AArch64Inst {
             "11011001_xx_1_xxxxxxxxx_xx_xxxxx_xxxxx",
             |raw_instr: &[u8],
              Extract(opc): Extract<u8, 22, 24>,
-             Extract(imm9): Extract<u16, 12, 221>,
+             Extract(imm9): Extract<u16, 12, 21>,
              Extract(op2): Extract<u8, 10, 12>,
              Extract(rn): Extract<u8, 5, 10>,
              Extract(rt): Extract<u8, 0, 5>| {
@@ -4583,12 +5026,12 @@ fn parse_ld_st_no_alloc_pair_offset(raw_instr: &[u8]) -> AArch64Inst {
              Extract(rt): Extract<u8, 0, 5>| {
                 let data = LdStNoAllocPairOffset {
                     imm7,
-                    rt2: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rt2),
+                    rt2: AArch64Architecture::get_register_by_mnemonic(ld_st_rt_hint(v), rt2),
                     rn: AArch64Architecture::get_register_by_mnemonic(
                         AArch64MnemonicHint::X_SP,
                         rn,
                     ),
-                    rt: AArch64Architecture::get_register_by_mnemonic(AArch64MnemonicHint::X, rt),
+                    rt: AArch64Architecture::get_register_by_mnemonic(ld_st_rt_hint(v), rt),
                 };
 
                 match (opc, v, l) {
@@ -0,0 +1,1899 @@
+use crate::aarch64::inst::AArch64Inst;
+use crate::aarch64::inst_operand::*;
+
+/// Encode the instruction into its 32-bit word, the inverse of `decode_aarch64_inst`.
+///
+/// The variant fixes the opcode bits and the operand struct holds the raw fields, which are
+/// placed back where the decoder extracted them from. Encoding a decoded instruction gives back
+/// the same word.
+pub(crate) fn encode_aarch64_inst(inst: &AArch64Inst) -> u32 {
+    match inst {
+        AArch64Inst::AddImm32(operand) => 0x1100_0000 | operand.encode(),
+        AArch64Inst::AddsImm32(operand) => 0x3100_0000 | operand.encode(),
+        AArch64Inst::SubImm32(operand) => 0x5100_0000 | operand.encode(),
+        AArch64Inst::SubsImm32(operand) => 0x7100_0000 | operand.encode(),
+        AArch64Inst::AddImm64(operand) => 0x9100_0000 | operand.encode(),
+        AArch64Inst::AddsImm64(operand) => 0xb100_0000 | operand.encode(),
+        AArch64Inst::SubImm64(operand) => 0xd100_0000 | operand.encode(),
+        AArch64Inst::SubsImm64(operand) => 0xf100_0000 | operand.encode(),
+
+        AArch64Inst::AndImm32(operand) => 0x1200_0000 | operand.encode(),
+        AArch64Inst::OrrImm32(operand) => 0x3200_0000 | operand.encode(),
+        AArch64Inst::EorImm32(operand) => 0x5200_0000 | operand.encode(),
+        AArch64Inst::AndsImm32(operand) => 0x7200_0000 | operand.encode(),
+        AArch64Inst::AndImm64(operand) => 0x9200_0000 | operand.encode(),
+        AArch64Inst::OrrImm64(operand) => 0xb200_0000 | operand.encode(),
+        AArch64Inst::EorImm64(operand) => 0xd200_0000 | operand.encode(),
+        AArch64Inst::AndsImm64(operand) => 0xf200_0000 | operand.encode(),
+
+        AArch64Inst::Addg(operand) => 0x9180_0000 | operand.encode(),
+        AArch64Inst::Subg(operand) => 0xd180_0000 | operand.encode(),
+
+        AArch64Inst::Extr32(operand) => 0x1380_0000 | operand.encode(),
+        AArch64Inst::Extr64(operand) => 0x93c0_0000 | operand.encode(),
+
+        AArch64Inst::Clrex(operand) => 0xd503_305f | operand.encode(),
+        AArch64Inst::DsbEncoding(operand) => 0xd503_309f | operand.encode(),
+        AArch64Inst::Dmb(operand) => 0xd503_30bf | operand.encode(),
+        AArch64Inst::Isb(operand) => 0xd503_30df | operand.encode(),
+
+        AArch64Inst::Sbfm32(operand) => 0x1300_0000 | operand.encode(),
+        AArch64Inst::Bfm32(operand) => 0x3300_0000 | operand.encode(),
+        AArch64Inst::Ubfm32(operand) => 0x5300_0000 | operand.encode(),
+        AArch64Inst::Sbfm64(operand) => 0x9300_0000 | operand.encode(),
+        AArch64Inst::Bfm64(operand) => 0xb300_0000 | operand.encode(),
+        AArch64Inst::Ubfm64(operand) => 0xd300_0000 | operand.encode(),
+
+        AArch64Inst::AddShiftedReg32(operand) => 0x0b00_0000 | operand.encode(),
+        AArch64Inst::AddsShiftedReg32(operand) => 0x2b00_0000 | operand.encode(),
+        AArch64Inst::SubShiftedReg32(operand) => 0x4b00_0000 | operand.encode(),
+        AArch64Inst::SubsShiftedReg32(operand) => 0x6b00_0000 | operand.encode(),
+        AArch64Inst::AddShiftedReg64(operand) => 0x8b00_0000 | operand.encode(),
+        AArch64Inst::AddsShiftedReg64(operand) => 0xab00_0000 | operand.encode(),
+        AArch64Inst::SubShiftedReg64(operand) => 0xcb00_0000 | operand.encode(),
+        AArch64Inst::SubsShiftedReg64(operand) => 0xeb00_0000 | operand.encode(),
+
+        AArch64Inst::AddExtReg32(operand) => 0x0b20_0000 | operand.encode(),
+        AArch64Inst::AddsExtReg32(operand) => 0x2b20_0000 | operand.encode(),
+        AArch64Inst::SubExtReg32(operand) => 0x4b20_0000 | operand.encode(),
+        AArch64Inst::SubsExtReg32(operand) => 0x6b20_0000 | operand.encode(),
+        AArch64Inst::AddExtReg64(operand) => 0x8b20_0000 | operand.encode(),
+        AArch64Inst::AddsExtReg64(operand) => 0xab20_0000 | operand.encode(),
+        AArch64Inst::SubExtReg64(operand) => 0xcb20_0000 | operand.encode(),
+        AArch64Inst::SubsExtReg64(operand) => 0xeb20_0000 | operand.encode(),
+
+        AArch64Inst::AdcVar32(operand) => 0x1a00_0000 | operand.encode(),
+        AArch64Inst::AdcsVar32(operand) => 0x3a00_0000 | operand.encode(),
+        AArch64Inst::SbcVar32(operand) => 0x5a00_0000 | operand.encode(),
+        AArch64Inst::SbcsVar32(operand) => 0x7a00_0000 | operand.encode(),
+        AArch64Inst::AdcVar64(operand) => 0x9a00_0000 | operand.encode(),
+        AArch64Inst::AdcsVar64(operand) => 0xba00_0000 | operand.encode(),
+        AArch64Inst::SbcVar64(operand) => 0xda00_0000 | operand.encode(),
+        AArch64Inst::SbcsVar64(operand) => 0xfa00_0000 | operand.encode(),
+
+        AArch64Inst::FmAddSinglePrecision(operand) => 0x1f00_0000 | operand.encode(),
+        AArch64Inst::FmSubSinglePrecision(operand) => 0x1f00_8000 | operand.encode(),
+        AArch64Inst::FnmAddSinglePrecision(operand) => 0x1f20_0000 | operand.encode(),
+        AArch64Inst::FnmSubSinglePrecision(operand) => 0x1f20_8000 | operand.encode(),
+        AArch64Inst::FmAddDoublePrecision(operand) => 0x1f40_0000 | operand.encode(),
+        AArch64Inst::FmSubDoublePrecision(operand) => 0x1f40_8000 | operand.encode(),
+        AArch64Inst::FnmAddDoublePrecision(operand) => 0x1f60_0000 | operand.encode(),
+        AArch64Inst::FnmSubDoublePrecision(operand) => 0x1f60_8000 | operand.encode(),
+        AArch64Inst::FmAddHalfPrecision(operand) => 0x1fc0_0000 | operand.encode(),
+        AArch64Inst::FmSubHalfPrecision(operand) => 0x1fc0_8000 | operand.encode(),
+        AArch64Inst::FnmAddHalfPrecision(operand) => 0x1fe0_0000 | operand.encode(),
+        AArch64Inst::FnmSubHalfPrecision(operand) => 0x1fe0_8000 | operand.encode(),
+
+        AArch64Inst::CaspVar32(operand) => 0x0820_7c00 | operand.encode(),
+        AArch64Inst::CasplVar32(operand) => 0x0820_fc00 | operand.encode(),
+        AArch64Inst::CaspaVar32(operand) => 0x0860_7c00 | operand.encode(),
+        AArch64Inst::CaspalVar32(operand) => 0x0860_fc00 | operand.encode(),
+        AArch64Inst::CaspVar64(operand) => 0x4820_7c00 | operand.encode(),
+        AArch64Inst::CasplVar64(operand) => 0x4820_fc00 | operand.encode(),
+        AArch64Inst::CaspaVar64(operand) => 0x4860_7c00 | operand.encode(),
+        AArch64Inst::CaspalVar64(operand) => 0x4860_fc00 | operand.encode(),
+
+        AArch64Inst::StgEncoding(operand) => 0xd920_0000 | operand.encode(),
+        AArch64Inst::Stzgm(operand) => 0xd920_0000 | operand.encode(),
+        AArch64Inst::Ldg(operand) => 0xd960_0000 | operand.encode(),
+        AArch64Inst::StzgEncoding(operand) => 0xd960_0000 | operand.encode(),
+        AArch64Inst::St2gEncoding(operand) => 0xd9a0_0000 | operand.encode(),
+        AArch64Inst::Stgm(operand) => 0xd9a0_0000 | operand.encode(),
+        AArch64Inst::Stz2gEncoding(operand) => 0xd9e0_0000 | operand.encode(),
+        AArch64Inst::Ldgm(operand) => 0xd9e0_0000 | operand.encode(),
+
+        AArch64Inst::StxpVar32(operand) => 0x8820_0000 | operand.encode(),
+        AArch64Inst::StlxpVar32(operand) => 0x8820_8000 | operand.encode(),
+        AArch64Inst::LdxpVar32(operand) => 0x8860_0000 | operand.encode(),
+        AArch64Inst::LdaxpVar32(operand) => 0x8860_8000 | operand.encode(),
+        AArch64Inst::StxpVar64(operand) => 0xc820_0000 | operand.encode(),
+        AArch64Inst::StlxpVar64(operand) => 0xc820_8000 | operand.encode(),
+        AArch64Inst::LdxpVar64(operand) => 0xc860_0000 | operand.encode(),
+        AArch64Inst::LdaxpVar64(operand) => 0xc860_8000 | operand.encode(),
+
+        AArch64Inst::Stlurb(operand) => 0x1900_0000 | operand.encode(),
+        AArch64Inst::Ldapurb(operand) => 0x1940_0000 | operand.encode(),
+        AArch64Inst::LdapursbVar64(operand) => 0x1980_0000 | operand.encode(),
+        AArch64Inst::LdapursbVar32(operand) => 0x19c0_0000 | operand.encode(),
+        AArch64Inst::Stlurh(operand) => 0x5900_0000 | operand.encode(),
+        AArch64Inst::Ldapurh(operand) => 0x5940_0000 | operand.encode(),
+        AArch64Inst::LdapurshVar64(operand) => 0x5980_0000 | operand.encode(),
+        AArch64Inst::LdapurshVar32(operand) => 0x59c0_0000 | operand.encode(),
+        AArch64Inst::StlurVar32(operand) => 0x9900_0000 | operand.encode(),
+        AArch64Inst::LdapurVar32(operand) => 0x9940_0000 | operand.encode(),
+        AArch64Inst::Ldapursw(operand) => 0x9980_0000 | operand.encode(),
+        AArch64Inst::StlurVar64(operand) => 0xd900_0000 | operand.encode(),
+        AArch64Inst::LdapurVar64(operand) => 0xd940_0000 | operand.encode(),
+
+        AArch64Inst::StnpVar32(operand) => 0x2800_0000 | operand.encode(),
+        AArch64Inst::LdnpVar32(operand) => 0x2840_0000 | operand.encode(),
+        AArch64Inst::StnpSimdFPVar32(operand) => 0x2c00_0000 | operand.encode(),
+        AArch64Inst::LdnpSimdFPVar32(operand) => 0x2c40_0000 | operand.encode(),
+        AArch64Inst::StnpSimdFPVar64(operand) => 0x6c00_0000 | operand.encode(),
+        AArch64Inst::LdnpSimdFPVar64(operand) => 0x6c40_0000 | operand.encode(),
+        AArch64Inst::StnpVar64(operand) => 0xa800_0000 | operand.encode(),
+        AArch64Inst::LdnpVar64(operand) => 0xa840_0000 | operand.encode(),
+        AArch64Inst::StnpSimdFPVar128(operand) => 0xac00_0000 | operand.encode(),
+        AArch64Inst::LdnpSimdFPVar128(operand) => 0xac40_0000 | operand.encode(),
+
+        AArch64Inst::Sttrb(operand) => 0x3800_0800 | operand.encode(),
+        AArch64Inst::Ldtrb(operand) => 0x3840_0800 | operand.encode(),
+        AArch64Inst::LdtrsbVar64(operand) => 0x3880_0800 | operand.encode(),
+        AArch64Inst::LdtrsbVar32(operand) => 0x38c0_0800 | operand.encode(),
+        AArch64Inst::Sttrh(operand) => 0x7800_0800 | operand.encode(),
+        AArch64Inst::Ldtrh(operand) => 0x7840_0800 | operand.encode(),
+        AArch64Inst::LdtrshVar64(operand) => 0x7880_0800 | operand.encode(),
+        AArch64Inst::LdtrshVar32(operand) => 0x78c0_0800 | operand.encode(),
+        AArch64Inst::SttrVar32(operand) => 0xb800_0800 | operand.encode(),
+        AArch64Inst::LdtrVar32(operand) => 0xb840_0800 | operand.encode(),
+        AArch64Inst::Ldtrsw(operand) => 0xb880_0800 | operand.encode(),
+        AArch64Inst::SttrVar64(operand) => 0xf800_0800 | operand.encode(),
+        AArch64Inst::LdtrVar64(operand) => 0xf840_0800 | operand.encode(),
+
+        AArch64Inst::StrbImm(operand) => 0x3800_0000 | operand.encode(),
+        AArch64Inst::LdrbImm(operand) => 0x3800_0000 | operand.encode(),
+        AArch64Inst::LdrsbImm32(operand) => 0x3800_0000 | operand.encode(),
+        AArch64Inst::LdrsbImm64(operand) => 0x3800_0000 | operand.encode(),
+        AArch64Inst::StrImmSimdFP8(operand) => 0x3c00_0000 | operand.encode(),
+        AArch64Inst::LdrImmSimdFP8(operand) => 0x3c00_0000 | operand.encode(),
+        AArch64Inst::StrImmSimdFP128(operand) => 0x3c00_0000 | operand.encode(),
+        AArch64Inst::LdrImmSimdFP128(operand) => 0x3c00_0000 | operand.encode(),
+        AArch64Inst::StrhImm(operand) => 0x3800_0000 | operand.encode(),
+        AArch64Inst::LdrhImm(operand) => 0x3800_0000 | operand.encode(),
+        AArch64Inst::LdrshImm32(operand) => 0x3800_0000 | operand.encode(),
+        AArch64Inst::LdrshImm64(operand) => 0x3800_0000 | operand.encode(),
+        AArch64Inst::StrImmSimdFP16(operand) => 0x3c00_0000 | operand.encode(),
+        AArch64Inst::LdrImmSimdFP16(operand) => 0x3c00_0000 | operand.encode(),
+        AArch64Inst::StrImm32(operand) => 0x3800_0000 | operand.encode(),
+        AArch64Inst::LdrImm32(operand) => 0x3800_0000 | operand.encode(),
+        AArch64Inst::LdrswImm(operand) => 0x3800_0000 | operand.encode(),
+        AArch64Inst::StrImmSimdFP32(operand) => 0x3c00_0000 | operand.encode(),
+        AArch64Inst::LdrImmSimdFP32(operand) => 0x3c00_0000 | operand.encode(),
+        AArch64Inst::StrImm64(operand) => 0x3800_0000 | operand.encode(),
+        AArch64Inst::LdrImm64(operand) => 0x3800_0000 | operand.encode(),
+        AArch64Inst::PrfmImm(operand) => 0x3800_0000 | operand.encode(),
+        AArch64Inst::StrImmSimdFP64(operand) => 0x3c00_0000 | operand.encode(),
+        AArch64Inst::LdrImmSimdFP64(operand) => 0x3c00_0000 | operand.encode(),
+
+        AArch64Inst::StrbRegExtReg(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::StrbRegShiftedReg(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::LdrbRegExtReg(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::LdrbRegShiftedReg(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::LdrsbRegExtReg64(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::LdrsbRegShiftedReg64(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::LdrsbRegExtReg32(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::LdrsbRegShiftedReg32(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::StrRegSimdFP(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::LdrRegSimdFP(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::StrhReg(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::LdrhReg(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::LdrshReg64(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::LdrshReg32(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::StrReg32(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::LdrReg32(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::LdrswReg(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::StrReg64(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::LdrReg64(operand) => 0x3820_0800 | operand.encode(),
+        AArch64Inst::PrfmReg(operand) => 0x3820_0800 | operand.encode(),
+
+        AArch64Inst::Stgp(operand) => 0x2800_0000 | operand.encode(),
+        AArch64Inst::Ldpsw(operand) => 0x2840_0000 | operand.encode(),
+
+        AArch64Inst::Sturb(operand) => 0x3800_0000 | operand.encode(),
+        AArch64Inst::Ldurb(operand) => 0x3840_0000 | operand.encode(),
+        AArch64Inst::Ldursb64(operand) => 0x3880_0000 | operand.encode(),
+        AArch64Inst::Ldursb32(operand) => 0x38c0_0000 | operand.encode(),
+        AArch64Inst::SturSimdFP8(operand) => 0x3c00_0000 | operand.encode(),
+        AArch64Inst::LdurSimdFP8(operand) => 0x3c40_0000 | operand.encode(),
+        AArch64Inst::SturSimdFP128(operand) => 0x3c80_0000 | operand.encode(),
+        AArch64Inst::LdurSimdFP128(operand) => 0x3cc0_0000 | operand.encode(),
+        AArch64Inst::Sturh(operand) => 0x7800_0000 | operand.encode(),
+        AArch64Inst::Ldurh(operand) => 0x7840_0000 | operand.encode(),
+        AArch64Inst::Ldursh64(operand) => 0x7880_0000 | operand.encode(),
+        AArch64Inst::Ldursh32(operand) => 0x78c0_0000 | operand.encode(),
+        AArch64Inst::SturSimdFP16(operand) => 0x7c00_0000 | operand.encode(),
+        AArch64Inst::LdurSimdFP16(operand) => 0x7c40_0000 | operand.encode(),
+        AArch64Inst::Stur32(operand) => 0xb800_0000 | operand.encode(),
+        AArch64Inst::Ldur32(operand) => 0xb840_0000 | operand.encode(),
+        AArch64Inst::Ldursw(operand) => 0xb880_0000 | operand.encode(),
+        AArch64Inst::SturSimdFP32(operand) => 0xbc00_0000 | operand.encode(),
+        AArch64Inst::LdurSimdFP32(operand) => 0xbc40_0000 | operand.encode(),
+        AArch64Inst::Stur64(operand) => 0xf800_0000 | operand.encode(),
+        AArch64Inst::Ldur64(operand) => 0xf840_0000 | operand.encode(),
+        AArch64Inst::Prefum(operand) => 0xf880_0000 | operand.encode(),
+        AArch64Inst::SturSimdFP64(operand) => 0xfc00_0000 | operand.encode(),
+        AArch64Inst::LdurSimdFP64(operand) => 0xfc40_0000 | operand.encode(),
+
+        AArch64Inst::StpVar32(operand) => 0x2800_0000 | operand.encode(),
+        AArch64Inst::LdpVar32(operand) => 0x2840_0000 | operand.encode(),
+        AArch64Inst::StpSimdFPVar32(operand) => 0x2c00_0000 | operand.encode(),
+        AArch64Inst::LdpSimdFPVar32(operand) => 0x2c40_0000 | operand.encode(),
+        AArch64Inst::StpSimdFPVar64(operand) => 0x2c00_0000 | operand.encode(),
+        AArch64Inst::LdpSimdFPVar64(operand) => 0x2c40_0000 | operand.encode(),
+        AArch64Inst::StpVar64(operand) => 0x2800_0000 | operand.encode(),
+        AArch64Inst::LdpVar64(operand) => 0x2840_0000 | operand.encode(),
+        AArch64Inst::StpSimdFpVar128(operand) => 0x2c00_0000 | operand.encode(),
+        AArch64Inst::LdpSimdFpVar128(operand) => 0x2c40_0000 | operand.encode(),
+
+        AArch64Inst::Stxrb(operand) => 0x0800_0000 | operand.encode(),
+        AArch64Inst::Ldxrb(operand) => 0x0840_0000 | operand.encode(),
+        AArch64Inst::Stxrh(operand) => 0x4800_0000 | operand.encode(),
+        AArch64Inst::Ldxrh(operand) => 0x4840_0000 | operand.encode(),
+        AArch64Inst::StxrVar32(operand) => 0x8800_0000 | operand.encode(),
+        AArch64Inst::LdxrVar32(operand) => 0x8840_0000 | operand.encode(),
+        AArch64Inst::StxrVar64(operand) => 0xc800_0000 | operand.encode(),
+        AArch64Inst::LdxrVar64(operand) => 0xc840_0000 | operand.encode(),
+        AArch64Inst::Stlxrb(operand) => 0x0800_8000 | operand.encode(),
+        AArch64Inst::Ldaxrb(operand) => 0x0840_8000 | operand.encode(),
+        AArch64Inst::Stlxrh(operand) => 0x4800_8000 | operand.encode(),
+        AArch64Inst::Ldaxrh(operand) => 0x4840_8000 | operand.encode(),
+        AArch64Inst::StlxrVar32(operand) => 0x8800_8000 | operand.encode(),
+        AArch64Inst::LdaxrVar32(operand) => 0x8840_8000 | operand.encode(),
+        AArch64Inst::StlxrVar64(operand) => 0xc800_8000 | operand.encode(),
+        AArch64Inst::LdaxrVar64(operand) => 0xc840_8000 | operand.encode(),
+
+        AArch64Inst::Stlrb(operand) => 0x0880_8000 | operand.encode(),
+        AArch64Inst::Ldarb(operand) => 0x08c0_8000 | operand.encode(),
+        AArch64Inst::Stlrh(operand) => 0x4880_8000 | operand.encode(),
+        AArch64Inst::Ldarh(operand) => 0x48c0_8000 | operand.encode(),
+        AArch64Inst::StlrVar32(operand) => 0x8880_8000 | operand.encode(),
+        AArch64Inst::LdarVar32(operand) => 0x88c0_8000 | operand.encode(),
+        AArch64Inst::StlrVar64(operand) => 0xc880_8000 | operand.encode(),
+        AArch64Inst::LdarVar64(operand) => 0xc8c0_8000 | operand.encode(),
+
+        AArch64Inst::LdrLitVar32(operand) => 0x1800_0000 | operand.encode(),
+        AArch64Inst::LdrLitSimdFPVar32(operand) => 0x1c00_0000 | operand.encode(),
+        AArch64Inst::LdrLitVar64(operand) => 0x5800_0000 | operand.encode(),
+        AArch64Inst::LdrLitSimdFPVar64(operand) => 0x5c00_0000 | operand.encode(),
+        AArch64Inst::LdrswLit(operand) => 0x9800_0000 | operand.encode(),
+        AArch64Inst::LdrLitSimdFPVar128(operand) => 0x9c00_0000 | operand.encode(),
+        AArch64Inst::PrfmLit(operand) => 0xd800_0000 | operand.encode(),
+
+        AArch64Inst::BImm(operand) => 0x1400_0000 | operand.encode(),
+        AArch64Inst::BlImm(operand) => 0x9400_0000 | operand.encode(),
+
+        AArch64Inst::BCond(operand) => 0x5400_0000 | operand.encode(),
+        AArch64Inst::BcCond(operand) => 0x5400_0010 | operand.encode(),
+
+        AArch64Inst::Tbz(operand) => 0x3600_0000 | operand.encode(),
+        AArch64Inst::Tbnz(operand) => 0x3700_0000 | operand.encode(),
+
+        AArch64Inst::Cbz32(operand) => 0x3400_0000 | operand.encode(),
+        AArch64Inst::Cbnz32(operand) => 0x3500_0000 | operand.encode(),
+        AArch64Inst::Cbz64(operand) => 0xb400_0000 | operand.encode(),
+        AArch64Inst::Cbnz64(operand) => 0xb500_0000 | operand.encode(),
+
+        AArch64Inst::Wfet(operand) => 0xd503_1000 | operand.encode(),
+        AArch64Inst::Wfit(operand) => 0xd503_1020 | operand.encode(),
+
+        AArch64Inst::MsrImm(operand) => 0xd500_401f | operand.encode(),
+        AArch64Inst::Cfinv(operand) => 0xd500_401f | operand.encode(),
+        AArch64Inst::Xaflag(operand) => 0xd500_401f | operand.encode(),
+        AArch64Inst::Axflag(operand) => 0xd500_401f | operand.encode(),
+
+        AArch64Inst::Tstart(operand) => 0xd523_3060 | operand.encode(),
+        AArch64Inst::Ttest(operand) => 0xd523_3160 | operand.encode(),
+
+        AArch64Inst::Sys(operand) => 0xd508_0000 | operand.encode(),
+        AArch64Inst::Sysl(operand) => 0xd528_0000 | operand.encode(),
+
+        AArch64Inst::MsrReg(operand) => 0xd510_0000 | operand.encode(),
+        AArch64Inst::Mrs(operand) => 0xd530_0000 | operand.encode(),
+
+        AArch64Inst::Csel32(operand) => 0x1a80_0000 | operand.encode(),
+        AArch64Inst::Csinc32(operand) => 0x1a80_0400 | operand.encode(),
+        AArch64Inst::Csinv32(operand) => 0x5a80_0000 | operand.encode(),
+        AArch64Inst::Csneg32(operand) => 0x5a80_0400 | operand.encode(),
+        AArch64Inst::Csel64(operand) => 0x9a80_0000 | operand.encode(),
+        AArch64Inst::Csinc64(operand) => 0x9a80_0400 | operand.encode(),
+        AArch64Inst::Csinv64(operand) => 0xda80_0000 | operand.encode(),
+        AArch64Inst::Csneg64(operand) => 0xda80_0400 | operand.encode(),
+
+        AArch64Inst::MovnVar32(operand) => 0x1280_0000 | operand.encode(),
+        AArch64Inst::MovzVar32(operand) => 0x5280_0000 | operand.encode(),
+        AArch64Inst::MovkVar32(operand) => 0x7280_0000 | operand.encode(),
+        AArch64Inst::MovnVar64(operand) => 0x9280_0000 | operand.encode(),
+        AArch64Inst::MovzVar64(operand) => 0xd280_0000 | operand.encode(),
+        AArch64Inst::MovkVar64(operand) => 0xf280_0000 | operand.encode(),
+
+        AArch64Inst::AndShiftedReg32(operand) => 0x0a00_0000 | operand.encode(),
+        AArch64Inst::BicShiftedReg32(operand) => 0x0a20_0000 | operand.encode(),
+        AArch64Inst::OrrShiftedReg32(operand) => 0x2a00_0000 | operand.encode(),
+        AArch64Inst::OrnShiftedReg32(operand) => 0x2a20_0000 | operand.encode(),
+        AArch64Inst::EorShiftedReg32(operand) => 0x4a00_0000 | operand.encode(),
+        AArch64Inst::EonShiftedReg32(operand) => 0x4a20_0000 | operand.encode(),
+        AArch64Inst::AndsShiftedReg32(operand) => 0x6a00_0000 | operand.encode(),
+        AArch64Inst::BicsShiftedReg32(operand) => 0x6a20_0000 | operand.encode(),
+
+        AArch64Inst::AndShiftedReg64(operand) => 0x8a00_0000 | operand.encode(),
+        AArch64Inst::BicShiftedReg64(operand) => 0x8a20_0000 | operand.encode(),
+        AArch64Inst::OrrShiftedReg64(operand) => 0xaa00_0000 | operand.encode(),
+        AArch64Inst::OrnShiftedReg64(operand) => 0xaa20_0000 | operand.encode(),
+        AArch64Inst::EorShiftedReg64(operand) => 0xca00_0000 | operand.encode(),
+        AArch64Inst::EonShiftedReg64(operand) => 0xca20_0000 | operand.encode(),
+        AArch64Inst::AndsShiftedReg64(operand) => 0xea00_0000 | operand.encode(),
+        AArch64Inst::BicsShiftedReg64(operand) => 0xea20_0000 | operand.encode(),
+
+        AArch64Inst::Madd32(operand) => 0x1b00_0000 | operand.encode(),
+        AArch64Inst::Msub32(operand) => 0x1b00_8000 | operand.encode(),
+        AArch64Inst::Madd64(operand) => 0x9b00_0000 | operand.encode(),
+        AArch64Inst::Msub64(operand) => 0x9b00_8000 | operand.encode(),
+        AArch64Inst::Smaddl(operand) => 0x9b20_0000 | operand.encode(),
+        AArch64Inst::Smsubl(operand) => 0x9b20_8000 | operand.encode(),
+        AArch64Inst::Smulh(operand) => 0x9b40_0000 | operand.encode(),
+        AArch64Inst::Umaddl(operand) => 0x9ba0_0000 | operand.encode(),
+        AArch64Inst::Umsubl(operand) => 0x9ba0_8000 | operand.encode(),
+        AArch64Inst::Umulh(operand) => 0x9bc0_0000 | operand.encode(),
+
+        AArch64Inst::UdivVar32(operand) => 0x1ac0_0800 | operand.encode(),
+        AArch64Inst::SdivVar32(operand) => 0x1ac0_0c00 | operand.encode(),
+        AArch64Inst::LslvVar32(operand) => 0x1ac0_2000 | operand.encode(),
+        AArch64Inst::LsrvVar32(operand) => 0x1ac0_2400 | operand.encode(),
+        AArch64Inst::AsrvVar32(operand) => 0x1ac0_2800 | operand.encode(),
+        AArch64Inst::RorvVar32(operand) => 0x1ac0_2c00 | operand.encode(),
+        AArch64Inst::UdivVar64(operand) => 0x9ac0_0800 | operand.encode(),
+        AArch64Inst::SdivVar64(operand) => 0x9ac0_0c00 | operand.encode(),
+        AArch64Inst::LslvVar64(operand) => 0x9ac0_2000 | operand.encode(),
+        AArch64Inst::LsrvVar64(operand) => 0x9ac0_2400 | operand.encode(),
+        AArch64Inst::AsrvVar64(operand) => 0x9ac0_2800 | operand.encode(),
+        AArch64Inst::RorvVar64(operand) => 0x9ac0_2c00 | operand.encode(),
+        AArch64Inst::Pacga(operand) => 0x9ac0_3000 | operand.encode(),
+
+        AArch64Inst::Rmif(operand) => 0xba00_0400 | operand.encode(),
+
+        AArch64Inst::SetfVar8(operand) => 0x3a00_080d | operand.encode(),
+        AArch64Inst::SetfVar16(operand) => 0x3a00_480d | operand.encode(),
+
+        AArch64Inst::CcmnRegVar32(operand) => 0x3a40_0000 | operand.encode(),
+        AArch64Inst::CcmpRegVar32(operand) => 0x7a40_0000 | operand.encode(),
+        AArch64Inst::CcmnRegVar64(operand) => 0xba40_0000 | operand.encode(),
+        AArch64Inst::CcmpRegVar64(operand) => 0xfa40_0000 | operand.encode(),
+
+        AArch64Inst::CcmnImmVar32(operand) => 0x3a40_0800 | operand.encode(),
+        AArch64Inst::CcmpImmVar32(operand) => 0x7a40_0800 | operand.encode(),
+        AArch64Inst::CcmnImmVar64(operand) => 0xba40_0800 | operand.encode(),
+        AArch64Inst::CcmpImmVar64(operand) => 0xfa40_0800 | operand.encode(),
+
+        AArch64Inst::RbitVar32(operand) => 0x5ac0_0000 | operand.encode(),
+        AArch64Inst::Rev16Var32(operand) => 0x5ac0_0400 | operand.encode(),
+        AArch64Inst::RevVar32(operand) => 0x5ac0_0800 | operand.encode(),
+        AArch64Inst::ClzVar32(operand) => 0x5ac0_1000 | operand.encode(),
+        AArch64Inst::ClsVar32(operand) => 0x5ac0_1400 | operand.encode(),
+        AArch64Inst::RbitVar64(operand) => 0xdac0_0000 | operand.encode(),
+        AArch64Inst::Rev16Var64(operand) => 0xdac0_0400 | operand.encode(),
+        AArch64Inst::Rev32(operand) => 0xdac0_0800 | operand.encode(),
+        AArch64Inst::RevVar64(operand) => 0xdac0_0c00 | operand.encode(),
+        AArch64Inst::ClzVar64(operand) => 0xdac0_1000 | operand.encode(),
+        AArch64Inst::ClsVar64(operand) => 0xdac0_1400 | operand.encode(),
+
+        AArch64Inst::Br(operand) => 0xd61f_0000 | operand.encode(),
+        AArch64Inst::Blr(operand) => 0xd61f_0000 | operand.encode(),
+        AArch64Inst::Ret(operand) => 0xd61f_0000 | operand.encode(),
+        AArch64Inst::ERet(operand) => 0xd69f_0000 | operand.encode(),
+        AArch64Inst::Drps(operand) => 0xd69f_0000 | operand.encode(),
+
+        AArch64Inst::Hint(operand) => 0xd503_201f | operand.encode(),
+        AArch64Inst::Nop => 0xd503_201f,
+        AArch64Inst::Yield => 0xd503_203f,
+        AArch64Inst::Wfe => 0xd503_205f,
+        AArch64Inst::Wfi => 0xd503_207f,
+        AArch64Inst::Sev => 0xd503_209f,
+        AArch64Inst::Sevl => 0xd503_20bf,
+        AArch64Inst::Xpaclri => 0xd503_20ff,
+        AArch64Inst::Pacia1716Var => 0xd503_211f,
+        AArch64Inst::Pacib1716Var => 0xd503_215f,
+        AArch64Inst::Autia1716Var => 0xd503_219f,
+        AArch64Inst::Autib1716Var => 0xd503_21df,
+        AArch64Inst::PaciazVar => 0xd503_231f,
+        AArch64Inst::PaciaspVar => 0xd503_233f,
+        AArch64Inst::PacibzVar => 0xd503_235f,
+        AArch64Inst::PacibspVar => 0xd503_237f,
+        AArch64Inst::AutiazVar => 0xd503_239f,
+        AArch64Inst::AutiaspVar => 0xd503_23bf,
+        AArch64Inst::AutibzVar => 0xd503_23df,
+        AArch64Inst::AutibspVar => 0xd503_23ff,
+
+        AArch64Inst::Adr(operand) => 0x1000_0000 | operand.encode(),
+        AArch64Inst::Adrp(operand) => 0x9000_0000 | operand.encode(),
+
+        AArch64Inst::Svc(operand) => 0xd400_0000 | operand.encode(),
+        AArch64Inst::Hvc(operand) => 0xd400_0000 | operand.encode(),
+        AArch64Inst::Smc(operand) => 0xd400_0000 | operand.encode(),
+        AArch64Inst::Brk(operand) => 0xd400_0000 | operand.encode(),
+        AArch64Inst::Hlt(operand) => 0xd400_0000 | operand.encode(),
+        AArch64Inst::TCancle(operand) => 0xd400_0000 | operand.encode(),
+        AArch64Inst::DcpS1(operand) => 0xd400_0000 | operand.encode(),
+        AArch64Inst::DcpS2(operand) => 0xd400_0000 | operand.encode(),
+        AArch64Inst::DcpS3(operand) => 0xd400_0000 | operand.encode(),
+
+        AArch64Inst::DupElement(operand) => 0x0e00_0400 | operand.encode(),
+        AArch64Inst::DupGeneral(operand) => 0x0e00_0400 | operand.encode(),
+        AArch64Inst::Smov(operand) => 0x0e00_0400 | operand.encode(),
+        AArch64Inst::Umov(operand) => 0x0e00_0400 | operand.encode(),
+        AArch64Inst::InsGeneral(operand) => 0x0e00_0400 | operand.encode(),
+        AArch64Inst::InsElement(operand) => 0x2e00_0400 | operand.encode(),
+
+        AArch64Inst::St1SingleStructureVar8(operand) => 0x0d00_0000 | operand.encode(),
+        AArch64Inst::St3SingleStructureVar8(operand) => 0x0d00_2000 | operand.encode(),
+        AArch64Inst::St1SingleStructureVar16(operand) => 0x0d00_4000 | operand.encode(),
+        AArch64Inst::St3SingleStructureVar16(operand) => 0x0d00_6000 | operand.encode(),
+        AArch64Inst::St1SingleStructureVar32(operand) => 0x0d00_8000 | operand.encode(),
+        AArch64Inst::St1SingleStructureVar64(operand) => 0x0d00_8000 | operand.encode(),
+        AArch64Inst::St3SingleStructureVar32(operand) => 0x0d00_a000 | operand.encode(),
+        AArch64Inst::St3SingleStructureVar64(operand) => 0x0d00_a000 | operand.encode(),
+        AArch64Inst::St2SingleStructureVar8(operand) => 0x0d20_0000 | operand.encode(),
+        AArch64Inst::St4SingleStructureVar8(operand) => 0x0d20_2000 | operand.encode(),
+        AArch64Inst::St2SingleStructureVar16(operand) => 0x0d20_4000 | operand.encode(),
+        AArch64Inst::St4SingleStructureVar16(operand) => 0x0d20_6000 | operand.encode(),
+        AArch64Inst::St2SingleStructureVar32(operand) => 0x0d20_8000 | operand.encode(),
+        AArch64Inst::St2SingleStructureVar64(operand) => 0x0d20_8000 | operand.encode(),
+        AArch64Inst::St4SingleStructureVar32(operand) => 0x0d20_a000 | operand.encode(),
+        AArch64Inst::St4SingleStructureVar64(operand) => 0x0d20_a000 | operand.encode(),
+
+        AArch64Inst::Ld1SingleStructureVar8(operand) => 0x0d40_0000 | operand.encode(),
+        AArch64Inst::Ld3SingleStructureVar8(operand) => 0x0d40_2000 | operand.encode(),
+        AArch64Inst::Ld1SingleStructureVar16(operand) => 0x0d40_4000 | operand.encode(),
+        AArch64Inst::Ld3SingleStructureVar16(operand) => 0x0d40_6000 | operand.encode(),
+        AArch64Inst::Ld1SingleStructureVar32(operand) => 0x0d40_8000 | operand.encode(),
+        AArch64Inst::Ld1SingleStructureVar64(operand) => 0x0d40_8000 | operand.encode(),
+        AArch64Inst::Ld3SingleStructureVar32(operand) => 0x0d40_a000 | operand.encode(),
+        AArch64Inst::Ld3SingleStructureVar64(operand) => 0x0d40_a000 | operand.encode(),
+        AArch64Inst::Ld1r(operand) => 0x0d40_c000 | operand.encode(),
+        AArch64Inst::Ld3r(operand) => 0x0d40_e000 | operand.encode(),
+        AArch64Inst::Ld2SingleStructureVar8(operand) => 0x0d60_0000 | operand.encode(),
+        AArch64Inst::Ld4SingleStructureVar8(operand) => 0x0d60_2000 | operand.encode(),
+        AArch64Inst::Ld2SingleStructureVar16(operand) => 0x0d60_4000 | operand.encode(),
+        AArch64Inst::Ld4SingleStructureVar16(operand) => 0x0d60_6000 | operand.encode(),
+        AArch64Inst::Ld2SingleStructureVar32(operand) => 0x0d60_8000 | operand.encode(),
+        AArch64Inst::Ld2SingleStructureVar64(operand) => 0x0d60_8000 | operand.encode(),
+        AArch64Inst::Ld4SingleStructureVar32(operand) => 0x0d60_a000 | operand.encode(),
+        AArch64Inst::Ld4SingleStructureVar64(operand) => 0x0d60_a000 | operand.encode(),
+        AArch64Inst::Ld2r(operand) => 0x0d60_c000 | operand.encode(),
+        AArch64Inst::Ld4r(operand) => 0x0d60_e000 | operand.encode(),
+
+        AArch64Inst::St1SingleStructurePostIndexedVar8(operand) => 0x0d80_0000 | operand.encode(),
+        AArch64Inst::St3SingleStructurePostIndexedVar8(operand) => 0x0d80_2000 | operand.encode(),
+        AArch64Inst::St1SingleStructurePostIndexedVar16(operand) => 0x0d80_4000 | operand.encode(),
+        AArch64Inst::St3SingleStructurePostIndexedVar16(operand) => 0x0d80_6000 | operand.encode(),
+        AArch64Inst::St1SingleStructurePostIndexedVar32(operand) => 0x0d80_8000 | operand.encode(),
+        AArch64Inst::St1SingleStructurePostIndexedVar64(operand) => 0x0d80_8000 | operand.encode(),
+        AArch64Inst::St3SingleStructurePostIndexedVar32(operand) => 0x0d80_a000 | operand.encode(),
+        AArch64Inst::St3SingleStructurePostIndexedVar64(operand) => 0x0d80_a000 | operand.encode(),
+        AArch64Inst::St2SingleStructurePostIndexedVar8(operand) => 0x0da0_0000 | operand.encode(),
+        AArch64Inst::St4SingleStructurePostIndexedVar8(operand) => 0x0da0_2000 | operand.encode(),
+        AArch64Inst::St2SingleStructurePostIndexedVar16(operand) => 0x0da0_4000 | operand.encode(),
+        AArch64Inst::St4SingleStructurePostIndexedVar16(operand) => 0x0da0_6000 | operand.encode(),
+        AArch64Inst::St2SingleStructurePostIndexedVar32(operand) => 0x0da0_8000 | operand.encode(),
+        AArch64Inst::St2SingleStructurePostIndexedVar64(operand) => 0x0da0_8000 | operand.encode(),
+        AArch64Inst::St4SingleStructurePostIndexedVar32(operand) => 0x0da0_a000 | operand.encode(),
+        AArch64Inst::St4SingleStructurePostIndexedVar64(operand) => 0x0da0_a000 | operand.encode(),
+
+        AArch64Inst::Ld1SingleStructurePostIndexedVar8(operand) => 0x0dc0_0000 | operand.encode(),
+        AArch64Inst::Ld3SingleStructurePostIndexedVar8(operand) => 0x0dc0_2000 | operand.encode(),
+        AArch64Inst::Ld1SingleStructurePostIndexedVar16(operand) => 0x0dc0_4000 | operand.encode(),
+        AArch64Inst::Ld3SingleStructurePostIndexedVar16(operand) => 0x0dc0_6000 | operand.encode(),
+        AArch64Inst::Ld1SingleStructurePostIndexedVar32(operand) => 0x0dc0_8000 | operand.encode(),
+        AArch64Inst::Ld1SingleStructurePostIndexedVar64(operand) => 0x0dc0_8000 | operand.encode(),
+        AArch64Inst::Ld3SingleStructurePostIndexedVar32(operand) => 0x0dc0_a000 | operand.encode(),
+        AArch64Inst::Ld3SingleStructurePostIndexedVar64(operand) => 0x0dc0_a000 | operand.encode(),
+        AArch64Inst::Ld1rPostIndexed(operand) => 0x0dc0_c000 | operand.encode(),
+        AArch64Inst::Ld3rPostIndexed(operand) => 0x0dc0_e000 | operand.encode(),
+        AArch64Inst::Ld2SingleStructurePostIndexedVar8(operand) => 0x0de0_0000 | operand.encode(),
+        AArch64Inst::Ld4SingleStructurePostIndexedVar8(operand) => 0x0de0_2000 | operand.encode(),
+        AArch64Inst::Ld2SingleStructurePostIndexedVar16(operand) => 0x0de0_4000 | operand.encode(),
+        AArch64Inst::Ld4SingleStructurePostIndexedVar16(operand) => 0x0de0_6000 | operand.encode(),
+        AArch64Inst::Ld2SingleStructurePostIndexedVar32(operand) => 0x0de0_8000 | operand.encode(),
+        AArch64Inst::Ld2SingleStructurePostIndexedVar64(operand) => 0x0de0_8000 | operand.encode(),
+        AArch64Inst::Ld4SingleStructurePostIndexedVar32(operand) => 0x0de0_a000 | operand.encode(),
+        AArch64Inst::Ld4SingleStructurePostIndexedVar64(operand) => 0x0de0_a000 | operand.encode(),
+        AArch64Inst::Ld2rPostIndexed(operand) => 0x0de0_c000 | operand.encode(),
+        AArch64Inst::Ld4rPostIndexed(operand) => 0x0de0_e000 | operand.encode(),
+
+        AArch64Inst::St4MulStructures(operand) => 0x0c00_0000 | operand.encode(),
+        AArch64Inst::St1MulStructures4RegsVar(operand) => 0x0c00_2000 | operand.encode(),
+        AArch64Inst::St3MulStructures(operand) => 0x0c00_4000 | operand.encode(),
+        AArch64Inst::St1MulStructures3RegsVar(operand) => 0x0c00_6000 | operand.encode(),
+        AArch64Inst::St1MulStructures1RegsVar(operand) => 0x0c00_7000 | operand.encode(),
+        AArch64Inst::St2MulStructures(operand) => 0x0c00_8000 | operand.encode(),
+        AArch64Inst::St1MulStructures2RegsVar(operand) => 0x0c00_a000 | operand.encode(),
+        AArch64Inst::Ld4MulStructures(operand) => 0x0c40_0000 | operand.encode(),
+        AArch64Inst::Ld1MulStructures4RegsVar(operand) => 0x0c40_2000 | operand.encode(),
+        AArch64Inst::Ld3MulStructures(operand) => 0x0c40_4000 | operand.encode(),
+        AArch64Inst::Ld1MulStructures3RegsVar(operand) => 0x0c40_6000 | operand.encode(),
+        AArch64Inst::Ld1MulStructures1RegsVar(operand) => 0x0c40_7000 | operand.encode(),
+        AArch64Inst::Ld2MulStructures(operand) => 0x0c40_8000 | operand.encode(),
+        AArch64Inst::Ld1MulStructures2RegsVar(operand) => 0x0c40_a000 | operand.encode(),
+
+        AArch64Inst::St4MulStructuresRegOffsetVar(operand) => 0x0c80_0000 | operand.encode(),
+        AArch64Inst::St1MulStructures4RegRegOffsetVar(operand) => 0x0c80_2000 | operand.encode(),
+        AArch64Inst::St3MulStructuresRegOffsetVar(operand) => 0x0c80_4000 | operand.encode(),
+        AArch64Inst::St1MulStructures3RegRegOffsetVar(operand) => 0x0c80_6000 | operand.encode(),
+        AArch64Inst::St1MulStructures1RegRegOffsetVar(operand) => 0x0c80_7000 | operand.encode(),
+        AArch64Inst::St2MulStructuresRegOffsetVar(operand) => 0x0c80_8000 | operand.encode(),
+        AArch64Inst::St1MulStructures2RegRegOffsetVar(operand) => 0x0c80_a000 | operand.encode(),
+        AArch64Inst::St4MulStructuresImmOffsetVar(operand) => 0x0c80_0000 | operand.encode(),
+        AArch64Inst::St1MulStructures4RegImmOffsetVar(operand) => 0x0c80_2000 | operand.encode(),
+        AArch64Inst::St3MulStructuresImmOffsetVar(operand) => 0x0c80_4000 | operand.encode(),
+        AArch64Inst::St1MulStructures3RegImmOffsetVar(operand) => 0x0c80_6000 | operand.encode(),
+        AArch64Inst::St1MulStructures1RegImmOffsetVar(operand) => 0x0c80_7000 | operand.encode(),
+        AArch64Inst::St2MulStructuresImmOffsetVar(operand) => 0x0c80_8000 | operand.encode(),
+        AArch64Inst::St1MulStructures2RegImmOffsetVar(operand) => 0x0c80_a000 | operand.encode(),
+
+        AArch64Inst::Ld4MulStructuresRegOffsetVar(operand) => 0x0cc0_0000 | operand.encode(),
+        AArch64Inst::Ld1MulStructures4RegRegOffsetVar(operand) => 0x0cc0_2000 | operand.encode(),
+        AArch64Inst::Ld3MulStructuresRegOffsetVar(operand) => 0x0cc0_4000 | operand.encode(),
+        AArch64Inst::Ld1MulStructures3RegRegOffsetVar(operand) => 0x0cc0_6000 | operand.encode(),
+        AArch64Inst::Ld1MulStructures1RegRegOffsetVar(operand) => 0x0cc0_7000 | operand.encode(),
+        AArch64Inst::Ld2MulStructuresRegOffsetVar(operand) => 0x0cc0_8000 | operand.encode(),
+        AArch64Inst::Ld1MulStructures2RegRegOffsetVar(operand) => 0x0cc0_a000 | operand.encode(),
+        AArch64Inst::Ld4MulStructuresImmOffsetVar(operand) => 0x0cc0_0000 | operand.encode(),
+        AArch64Inst::Ld1MulStructures4RegImmOffsetVar(operand) => 0x0cc0_2000 | operand.encode(),
+        AArch64Inst::Ld3MulStructuresImmOffsetVar(operand) => 0x0cc0_4000 | operand.encode(),
+        AArch64Inst::Ld1MulStructures3RegImmOffsetVar(operand) => 0x0cc0_6000 | operand.encode(),
+        AArch64Inst::Ld1MulStructures1RegImmOffsetVar(operand) => 0x0cc0_7000 | operand.encode(),
+        AArch64Inst::Ld2MulStructuresImmOffsetVar(operand) => 0x0cc0_8000 | operand.encode(),
+        AArch64Inst::Ld1MulStructures2RegImmOffsetVar(operand) => 0x0cc0_a000 | operand.encode(),
+
+        AArch64Inst::FcvtnsScalarSinglePrecisionTo32(operand) => 0x1e20_0000 | operand.encode(),
+        AArch64Inst::FcvtnuScalarSinglePrecisionTo32(operand) => 0x1e21_0000 | operand.encode(),
+        AArch64Inst::ScvtfScalarInt32ToSinglePrecision(operand) => 0x1e22_0000 | operand.encode(),
+        AArch64Inst::UcvtfScalarInt32ToSinglePrecision(operand) => 0x1e23_0000 | operand.encode(),
+        AArch64Inst::FcvtasScalarSinglePrecisionTo32(operand) => 0x1e24_0000 | operand.encode(),
+        AArch64Inst::FcvtauScalarSinglePrecisionTo32(operand) => 0x1e25_0000 | operand.encode(),
+        AArch64Inst::FmovGeneralSinglePrecisionTo32(operand) => 0x1e26_0000 | operand.encode(),
+        AArch64Inst::FmovGeneral32ToSinglePrecision(operand) => 0x1e27_0000 | operand.encode(),
+        AArch64Inst::FcvtpsScalarSinglePrecisionTo32(operand) => 0x1e28_0000 | operand.encode(),
+        AArch64Inst::FcvtpuScalarSinglePrecisionTo32(operand) => 0x1e29_0000 | operand.encode(),
+        AArch64Inst::FcvtmsScalarSinglePrecisionTo32(operand) => 0x1e30_0000 | operand.encode(),
+        AArch64Inst::FcvtmuScalarSinglePrecisionTo32(operand) => 0x1e31_0000 | operand.encode(),
+        AArch64Inst::FcvtzsScalarIntSinglePrecisionTo32(operand) => 0x1e38_0000 | operand.encode(),
+        AArch64Inst::FcvtzuScalarIntSinglePrecisionTo32(operand) => 0x1e39_0000 | operand.encode(),
+        AArch64Inst::FcvtnsScalarDoublePrecisionTo32(operand) => 0x1e60_0000 | operand.encode(),
+        AArch64Inst::FcvtnuScalarDoublePrecisionTo32(operand) => 0x1e61_0000 | operand.encode(),
+        AArch64Inst::ScvtfScalarInt32ToDoublePrecision(operand) => 0x1e62_0000 | operand.encode(),
+        AArch64Inst::UcvtfScalarInt32ToDoublePrecision(operand) => 0x1e63_0000 | operand.encode(),
+        AArch64Inst::FcvtasScalarDoublePrecisionTo32(operand) => 0x1e64_0000 | operand.encode(),
+        AArch64Inst::FcvtauScalarDoublePrecisionTo32(operand) => 0x1e65_0000 | operand.encode(),
+        AArch64Inst::FcvtpsScalarDoublePrecisionTo32(operand) => 0x1e68_0000 | operand.encode(),
+        AArch64Inst::FcvtpuScalarDoublePrecisionTo32(operand) => 0x1e69_0000 | operand.encode(),
+        AArch64Inst::FcvtmsScalarDoublePrecisionTo32(operand) => 0x1e70_0000 | operand.encode(),
+        AArch64Inst::FcvtmuScalarDoublePrecisionTo32(operand) => 0x1e71_0000 | operand.encode(),
+        AArch64Inst::FcvtzsScalarIntDoublePrecisionTo32(operand) => 0x1e78_0000 | operand.encode(),
+        AArch64Inst::FcvtzuScalarIntDoublePrecisionTo32(operand) => 0x1e79_0000 | operand.encode(),
+        AArch64Inst::Fjcvtzs(operand) => 0x1e7e_0000 | operand.encode(),
+        AArch64Inst::FcvtnsScalarSinglePrecisionTo64(operand) => 0x9e20_0000 | operand.encode(),
+        AArch64Inst::FcvtnuScalarSinglePrecisionTo64(operand) => 0x9e21_0000 | operand.encode(),
+        AArch64Inst::ScvtfScalarInt64ToSinglePrecision(operand) => 0x9e22_0000 | operand.encode(),
+        AArch64Inst::UcvtfScalarInt64ToSinglePrecision(operand) => 0x9e23_0000 | operand.encode(),
+        AArch64Inst::FcvtasScalarSinglePrecisionTo64(operand) => 0x9e24_0000 | operand.encode(),
+        AArch64Inst::FcvtauScalarSinglePrecisionTo64(operand) => 0x9e25_0000 | operand.encode(),
+        AArch64Inst::FcvtpsScalarSinglePrecisionTo64(operand) => 0x9e28_0000 | operand.encode(),
+        AArch64Inst::FcvtpuScalarSinglePrecisionTo64(operand) => 0x9e29_0000 | operand.encode(),
+        AArch64Inst::FcvtmsScalarSinglePrecisionTo64(operand) => 0x9e30_0000 | operand.encode(),
+        AArch64Inst::FcvtmuScalarSinglePrecisionTo64(operand) => 0x9e31_0000 | operand.encode(),
+        AArch64Inst::FcvtzsScalarIntSinglePrecisionTo64(operand) => 0x9e38_0000 | operand.encode(),
+        AArch64Inst::FcvtzuScalarIntSinglePrecisionTo64(operand) => 0x9e39_0000 | operand.encode(),
+        AArch64Inst::FcvtnsScalarDoublePrecisionTo64(operand) => 0x9e60_0000 | operand.encode(),
+        AArch64Inst::FcvtnuScalarDoublePrecisionTo64(operand) => 0x9e61_0000 | operand.encode(),
+        AArch64Inst::ScvtfScalarInt64ToDoublePrecision(operand) => 0x9e62_0000 | operand.encode(),
+        AArch64Inst::UcvtfScalarInt64ToDoublePrecision(operand) => 0x9e63_0000 | operand.encode(),
+        AArch64Inst::FcvtasScalarDoublePrecisionTo64(operand) => 0x9e64_0000 | operand.encode(),
+        AArch64Inst::FcvtauScalarDoublePrecisionTo64(operand) => 0x9e65_0000 | operand.encode(),
+        AArch64Inst::FmovGeneralDoublePrecisionTo64(operand) => 0x9e66_0000 | operand.encode(),
+        AArch64Inst::FmovGeneral64ToDoublePrecision(operand) => 0x9e67_0000 | operand.encode(),
+        AArch64Inst::FcvtpsScalarDoublePrecisionTo64(operand) => 0x9e68_0000 | operand.encode(),
+        AArch64Inst::FcvtpuScalarDoublePrecisionTo64(operand) => 0x9e69_0000 | operand.encode(),
+        AArch64Inst::FcvtmsScalarDoublePrecisionTo64(operand) => 0x9e70_0000 | operand.encode(),
+        AArch64Inst::FcvtmuScalarDoublePrecisionTo64(operand) => 0x9e71_0000 | operand.encode(),
+        AArch64Inst::FcvtzsScalarIntDoublePrecisionTo64(operand) => 0x9e78_0000 | operand.encode(),
+        AArch64Inst::FcvtzuScalarIntDoublePrecisionTo64(operand) => 0x9e79_0000 | operand.encode(),
+        AArch64Inst::FmovGeneralTopHalfOf128To64(operand) => 0x9eae_0000 | operand.encode(),
+        AArch64Inst::FmovGeneral64toTopHalfOf128(operand) => 0x9eaf_0000 | operand.encode(),
+
+        AArch64Inst::MoviShiftedImmVar32(operand) => 0x0f00_0400 | operand.encode(),
+        AArch64Inst::OrrVecImmVar32(operand) => 0x0f00_0400 | operand.encode(),
+        AArch64Inst::MoviShiftedImmVar16(operand) => 0x0f00_0400 | operand.encode(),
+        AArch64Inst::OrrVecImmVar16(operand) => 0x0f00_0400 | operand.encode(),
+        AArch64Inst::MoviShiftingOnesVar32(operand) => 0x0f00_0400 | operand.encode(),
+        AArch64Inst::MoviVar8(operand) => 0x0f00_0400 | operand.encode(),
+        AArch64Inst::FmovVecImmSinglePrecisionVar(operand) => 0x0f00_0400 | operand.encode(),
+        AArch64Inst::MvniShiftedImmVar32(operand) => 0x0f00_0400 | operand.encode(),
+        AArch64Inst::BicVecImmVar32(operand) => 0x0f00_0400 | operand.encode(),
+        AArch64Inst::MvniShiftedImmVar16(operand) => 0x0f00_0400 | operand.encode(),
+        AArch64Inst::BicVecImmVar16(operand) => 0x0f00_0400 | operand.encode(),
+        AArch64Inst::MvniShiftingOnesVar32(operand) => 0x0f00_0400 | operand.encode(),
+        AArch64Inst::MoviScalarVar64(operand) => 0x0f00_0400 | operand.encode(),
+        AArch64Inst::MoviVectorVar64(operand) => 0x0f00_0400 | operand.encode(),
+        AArch64Inst::FmovVecImmDoublePrecisionVar(operand) => 0x0f00_0400 | operand.encode(),
+
+        AArch64Inst::Ext(operand) => 0x2e00_0000 | operand.encode(),
+
+        AArch64Inst::Shadd(operand) => 0x0e20_0400 | operand.encode(),
+        AArch64Inst::Sqadd(operand) => 0x0e20_0c00 | operand.encode(),
+        AArch64Inst::Srhadd(operand) => 0x0e20_1400 | operand.encode(),
+        AArch64Inst::Shsub(operand) => 0x0e20_2400 | operand.encode(),
+        AArch64Inst::Sqsub(operand) => 0x0e20_2c00 | operand.encode(),
+        AArch64Inst::CmgtReg(operand) => 0x0e20_3400 | operand.encode(),
+        AArch64Inst::CmgeReg(operand) => 0x0e20_3c00 | operand.encode(),
+        AArch64Inst::Sshl(operand) => 0x0e20_4400 | operand.encode(),
+        AArch64Inst::SqshlReg(operand) => 0x0e20_4c00 | operand.encode(),
+        AArch64Inst::Srshl(operand) => 0x0e20_5400 | operand.encode(),
+        AArch64Inst::Sqrshl(operand) => 0x0e20_5c00 | operand.encode(),
+        AArch64Inst::Smax(operand) => 0x0e20_6400 | operand.encode(),
+        AArch64Inst::Smin(operand) => 0x0e20_6c00 | operand.encode(),
+        AArch64Inst::Sabd(operand) => 0x0e20_7400 | operand.encode(),
+        AArch64Inst::Saba(operand) => 0x0e20_7c00 | operand.encode(),
+        AArch64Inst::AddVec(operand) => 0x0e20_8400 | operand.encode(),
+        AArch64Inst::Cmtst(operand) => 0x0e20_8c00 | operand.encode(),
+        AArch64Inst::MlaVec(operand) => 0x0e20_9400 | operand.encode(),
+        AArch64Inst::MulVec(operand) => 0x0e20_9c00 | operand.encode(),
+        AArch64Inst::Smaxp(operand) => 0x0e20_a400 | operand.encode(),
+        AArch64Inst::Sminp(operand) => 0x0e20_ac00 | operand.encode(),
+        AArch64Inst::SqdmulhVec(operand) => 0x0e20_b400 | operand.encode(),
+        AArch64Inst::AddpVec(operand) => 0x0e20_bc00 | operand.encode(),
+        AArch64Inst::FmaxnmVec(operand) => 0x0e20_c400 | operand.encode(),
+        AArch64Inst::FmlaVec(operand) => 0x0e20_cc00 | operand.encode(),
+        AArch64Inst::FaddVec(operand) => 0x0e20_d400 | operand.encode(),
+        AArch64Inst::Fmulx(operand) => 0x0e20_dc00 | operand.encode(),
+        AArch64Inst::FcmeqReg(operand) => 0x0e20_e400 | operand.encode(),
+        AArch64Inst::FmaxVec(operand) => 0x0e20_f400 | operand.encode(),
+        AArch64Inst::Frecps(operand) => 0x0e20_fc00 | operand.encode(),
+        AArch64Inst::AndVec(operand) => 0x0e20_1c00 | operand.encode(),
+        AArch64Inst::BicVecReg(operand) => 0x0e20_1c00 | operand.encode(),
+        AArch64Inst::FminnmVec(operand) => 0x0e20_c400 | operand.encode(),
+        AArch64Inst::FmlsVec(operand) => 0x0e20_cc00 | operand.encode(),
+        AArch64Inst::FsubVec(operand) => 0x0e20_d400 | operand.encode(),
+        AArch64Inst::FminVec(operand) => 0x0e20_f400 | operand.encode(),
+        AArch64Inst::Frsqrts(operand) => 0x0e20_fc00 | operand.encode(),
+        AArch64Inst::OrrVecReg(operand) => 0x0e20_1c00 | operand.encode(),
+        AArch64Inst::OrnVec(operand) => 0x0e20_1c00 | operand.encode(),
+        AArch64Inst::Uhadd(operand) => 0x2e20_0400 | operand.encode(),
+        AArch64Inst::Uqadd(operand) => 0x2e20_0c00 | operand.encode(),
+        AArch64Inst::Urhadd(operand) => 0x2e20_1400 | operand.encode(),
+        AArch64Inst::Uhsub(operand) => 0x2e20_2400 | operand.encode(),
+        AArch64Inst::Uqsub(operand) => 0x2e20_2c00 | operand.encode(),
+        AArch64Inst::CmhiReg(operand) => 0x2e20_3400 | operand.encode(),
+        AArch64Inst::CmhsReg(operand) => 0x2e20_3c00 | operand.encode(),
+        AArch64Inst::Ushl(operand) => 0x2e20_4400 | operand.encode(),
+        AArch64Inst::UqshlReg(operand) => 0x2e20_4c00 | operand.encode(),
+        AArch64Inst::Urshl(operand) => 0x2e20_5400 | operand.encode(),
+        AArch64Inst::Uqrshl(operand) => 0x2e20_5c00 | operand.encode(),
+        AArch64Inst::Umax(operand) => 0x2e20_6400 | operand.encode(),
+        AArch64Inst::Umin(operand) => 0x2e20_6c00 | operand.encode(),
+        AArch64Inst::Uabd(operand) => 0x2e20_7400 | operand.encode(),
+        AArch64Inst::Uaba(operand) => 0x2e20_7c00 | operand.encode(),
+        AArch64Inst::SubVec(operand) => 0x2e20_8400 | operand.encode(),
+        AArch64Inst::CmeqReg(operand) => 0x2e20_8c00 | operand.encode(),
+        AArch64Inst::MlsVec(operand) => 0x2e20_9400 | operand.encode(),
+        AArch64Inst::Pmul(operand) => 0x2e20_9c00 | operand.encode(),
+        AArch64Inst::Umaxp(operand) => 0x2e20_a400 | operand.encode(),
+        AArch64Inst::Uminp(operand) => 0x2e20_ac00 | operand.encode(),
+        AArch64Inst::SqrdmulhVec(operand) => 0x2e20_b400 | operand.encode(),
+        AArch64Inst::FmaxnmpVec(operand) => 0x2e20_c400 | operand.encode(),
+        AArch64Inst::FaddpVec(operand) => 0x2e20_d400 | operand.encode(),
+        AArch64Inst::FmulVec(operand) => 0x2e20_dc00 | operand.encode(),
+        AArch64Inst::FcmgeReg(operand) => 0x2e20_e400 | operand.encode(),
+        AArch64Inst::Facge(operand) => 0x2e20_ec00 | operand.encode(),
+        AArch64Inst::FmaxpVec(operand) => 0x2e20_f400 | operand.encode(),
+        AArch64Inst::FdivVec(operand) => 0x2e20_fc00 | operand.encode(),
+        AArch64Inst::EorVec(operand) => 0x2e20_1c00 | operand.encode(),
+        AArch64Inst::Bsl(operand) => 0x2e20_1c00 | operand.encode(),
+        AArch64Inst::FminnmpVec(operand) => 0x2e20_c400 | operand.encode(),
+        AArch64Inst::Fabd(operand) => 0x2e20_d400 | operand.encode(),
+        AArch64Inst::FcmgtReg(operand) => 0x2e20_e400 | operand.encode(),
+        AArch64Inst::Facgt(operand) => 0x2e20_ec00 | operand.encode(),
+        AArch64Inst::FminpVec(operand) => 0x2e20_f400 | operand.encode(),
+        AArch64Inst::Bit(operand) => 0x2e20_1c00 | operand.encode(),
+        AArch64Inst::Bif(operand) => 0x2e20_1c00 | operand.encode(),
+
+        AArch64Inst::SqaddScalar(operand) => 0x5e20_0c00 | operand.encode(),
+        AArch64Inst::SqsubScalar(operand) => 0x5e20_2c00 | operand.encode(),
+        AArch64Inst::CmgtRegScalar(operand) => 0x5e20_3400 | operand.encode(),
+        AArch64Inst::CmgeRegScalar(operand) => 0x5e20_3c00 | operand.encode(),
+        AArch64Inst::SshlScalar(operand) => 0x5e20_4400 | operand.encode(),
+        AArch64Inst::SqshlRegScalar(operand) => 0x5e20_4c00 | operand.encode(),
+        AArch64Inst::SrshlScalar(operand) => 0x5e20_5400 | operand.encode(),
+        AArch64Inst::SqrshlScalar(operand) => 0x5e20_5c00 | operand.encode(),
+        AArch64Inst::AddScalar(operand) => 0x5e20_8400 | operand.encode(),
+        AArch64Inst::CmtstScalar(operand) => 0x5e20_8c00 | operand.encode(),
+        AArch64Inst::SqdmulhScalar(operand) => 0x5e20_b400 | operand.encode(),
+        AArch64Inst::FmulxScalar(operand) => 0x5e20_dc00 | operand.encode(),
+        AArch64Inst::FcmeqRegScalar(operand) => 0x5e20_e400 | operand.encode(),
+        AArch64Inst::FrecpsScalar(operand) => 0x5e20_fc00 | operand.encode(),
+        AArch64Inst::FrsqrtsScalar(operand) => 0x5e20_fc00 | operand.encode(),
+        AArch64Inst::UqaddScalar(operand) => 0x7e20_0c00 | operand.encode(),
+        AArch64Inst::UqsubScalar(operand) => 0x7e20_2c00 | operand.encode(),
+        AArch64Inst::CmhiRegScalar(operand) => 0x7e20_3400 | operand.encode(),
+        AArch64Inst::CmhsRegScalar(operand) => 0x7e20_3c00 | operand.encode(),
+        AArch64Inst::UshlScalar(operand) => 0x7e20_4400 | operand.encode(),
+        AArch64Inst::UqshlRegScalar(operand) => 0x7e20_4c00 | operand.encode(),
+        AArch64Inst::UrshlScalar(operand) => 0x7e20_5400 | operand.encode(),
+        AArch64Inst::UqrshlScalar(operand) => 0x7e20_5c00 | operand.encode(),
+        AArch64Inst::SubScalar(operand) => 0x7e20_8400 | operand.encode(),
+        AArch64Inst::CmeqRegScalar(operand) => 0x7e20_8c00 | operand.encode(),
+        AArch64Inst::SqrdmulhScalar(operand) => 0x7e20_b400 | operand.encode(),
+        AArch64Inst::FcmgeRegScalar(operand) => 0x7e20_e400 | operand.encode(),
+        AArch64Inst::FacgeScalar(operand) => 0x7e20_ec00 | operand.encode(),
+        AArch64Inst::FabdScalar(operand) => 0x7e20_d400 | operand.encode(),
+        AArch64Inst::FcmgtRegScalar(operand) => 0x7e20_e400 | operand.encode(),
+        AArch64Inst::FacgtScalar(operand) => 0x7e20_ec00 | operand.encode(),
+
+        AArch64Inst::Sshr(operand) => 0x0f00_0400 | operand.encode(),
+        AArch64Inst::Ssra(operand) => 0x0f00_1400 | operand.encode(),
+        AArch64Inst::Srshr(operand) => 0x0f00_2400 | operand.encode(),
+        AArch64Inst::Srsra(operand) => 0x0f00_3400 | operand.encode(),
+        AArch64Inst::Shl(operand) => 0x0f00_5400 | operand.encode(),
+        AArch64Inst::SqshlImm(operand) => 0x0f00_7400 | operand.encode(),
+        AArch64Inst::Shrn(operand) => 0x0f00_8400 | operand.encode(),
+        AArch64Inst::Rshrn(operand) => 0x0f00_8c00 | operand.encode(),
+        AArch64Inst::Sqshrn(operand) => 0x0f00_9400 | operand.encode(),
+        AArch64Inst::Sqrshrn(operand) => 0x0f00_9c00 | operand.encode(),
+        AArch64Inst::Sshll(operand) => 0x0f00_a400 | operand.encode(),
+        AArch64Inst::ScvtfVecFixedPt(operand) => 0x0f00_e400 | operand.encode(),
+        AArch64Inst::FcvtzsVecFixedPt(operand) => 0x0f00_fc00 | operand.encode(),
+        AArch64Inst::Ushr(operand) => 0x2f00_0400 | operand.encode(),
+        AArch64Inst::Usra(operand) => 0x2f00_1400 | operand.encode(),
+        AArch64Inst::Urshr(operand) => 0x2f00_2400 | operand.encode(),
+        AArch64Inst::Ursra(operand) => 0x2f00_3400 | operand.encode(),
+        AArch64Inst::Sri(operand) => 0x2f00_4400 | operand.encode(),
+        AArch64Inst::Sli(operand) => 0x2f00_5400 | operand.encode(),
+        AArch64Inst::Sqshlu(operand) => 0x2f00_6400 | operand.encode(),
+        AArch64Inst::UqshlImm(operand) => 0x2f00_7400 | operand.encode(),
+        AArch64Inst::Sqshrun(operand) => 0x2f00_8400 | operand.encode(),
+        AArch64Inst::Sqrshrun(operand) => 0x2f00_8c00 | operand.encode(),
+        AArch64Inst::Uqshrn(operand) => 0x2f00_9400 | operand.encode(),
+        AArch64Inst::Uqrshrn(operand) => 0x2f00_9c00 | operand.encode(),
+        AArch64Inst::Ushll(operand) => 0x2f00_a400 | operand.encode(),
+        AArch64Inst::UcvtfVecFixedPt(operand) => 0x2f00_e400 | operand.encode(),
+        AArch64Inst::FcvtzuVecFixedPt(operand) => 0x2f00_fc00 | operand.encode(),
+
+        AArch64Inst::FmovRegSinglePrecisionVar(operand) => 0x1e20_4000 | operand.encode(),
+        AArch64Inst::FabsScalarSinglePrecisionVar(operand) => 0x1e20_c000 | operand.encode(),
+        AArch64Inst::FnegScalarSinglePrecisionVar(operand) => 0x1e21_4000 | operand.encode(),
+        AArch64Inst::FsqrtScalarSinglePrecisionVar(operand) => 0x1e21_c000 | operand.encode(),
+        AArch64Inst::FcvtSingleToDoublePrecisionVar(operand) => 0x1e22_c000 | operand.encode(),
+        AArch64Inst::FcvtSingleToHalfPrecisionVar(operand) => 0x1e23_c000 | operand.encode(),
+        AArch64Inst::FrintnScalarSinglePrecisionVar(operand) => 0x1e24_4000 | operand.encode(),
+        AArch64Inst::FrintpScalarSinglePrecisionVar(operand) => 0x1e24_c000 | operand.encode(),
+        AArch64Inst::FrintmScalarSinglePrecisionVar(operand) => 0x1e25_4000 | operand.encode(),
+        AArch64Inst::FrintzScalarSinglePrecisionVar(operand) => 0x1e25_c000 | operand.encode(),
+        AArch64Inst::FrintaScalarSinglePrecisionVar(operand) => 0x1e26_4000 | operand.encode(),
+        AArch64Inst::FrintxScalarSinglePrecisionVar(operand) => 0x1e27_4000 | operand.encode(),
+        AArch64Inst::FrintiScalarSinglePrecisionVar(operand) => 0x1e27_c000 | operand.encode(),
+        AArch64Inst::FmovRegDoublePrecisionVar(operand) => 0x1e60_4000 | operand.encode(),
+        AArch64Inst::FabsScalarDoublePrecisionVar(operand) => 0x1e60_c000 | operand.encode(),
+        AArch64Inst::FnegScalarDoublePrecisionVar(operand) => 0x1e61_4000 | operand.encode(),
+        AArch64Inst::FsqrtScalarDoublePrecisionVar(operand) => 0x1e61_c000 | operand.encode(),
+        AArch64Inst::FcvtDoubleToSinglePrecisionVar(operand) => 0x1e62_4000 | operand.encode(),
+        AArch64Inst::FcvtDoubleToHalfPrecisionVar(operand) => 0x1e63_c000 | operand.encode(),
+        AArch64Inst::FrintnScalarDoublePrecisionVar(operand) => 0x1e64_4000 | operand.encode(),
+        AArch64Inst::FrintpScalarDoublePrecisionVar(operand) => 0x1e64_c000 | operand.encode(),
+        AArch64Inst::FrintmScalarDoublePrecisionVar(operand) => 0x1e65_4000 | operand.encode(),
+        AArch64Inst::FrintzScalarDoublePrecisionVar(operand) => 0x1e65_c000 | operand.encode(),
+        AArch64Inst::FrintaScalarDoublePrecisionVar(operand) => 0x1e66_4000 | operand.encode(),
+        AArch64Inst::FrintxScalarDoublePrecisionVar(operand) => 0x1e67_4000 | operand.encode(),
+        AArch64Inst::FrintiScalarDoublePrecisionVar(operand) => 0x1e67_c000 | operand.encode(),
+        AArch64Inst::FmovRegHalfPrecisionVar(operand) => 0x1ee0_4000 | operand.encode(),
+        AArch64Inst::FabsScalarHalfPrecisionVar(operand) => 0x1ee0_c000 | operand.encode(),
+        AArch64Inst::FnegScalarHalfPrecisionVar(operand) => 0x1ee1_4000 | operand.encode(),
+        AArch64Inst::FsqrtScalarHalfPrecisionVar(operand) => 0x1ee1_c000 | operand.encode(),
+        AArch64Inst::FcvtHalfToSinglePrecisionVar(operand) => 0x1ee2_4000 | operand.encode(),
+        AArch64Inst::FcvtHalfToDoublePrecisionVar(operand) => 0x1ee2_c000 | operand.encode(),
+        AArch64Inst::FrintnScalarHalfPrecisionVar(operand) => 0x1ee4_4000 | operand.encode(),
+        AArch64Inst::FrintpScalarHalfPrecisionVar(operand) => 0x1ee4_c000 | operand.encode(),
+        AArch64Inst::FrintmScalarHalfPrecisionVar(operand) => 0x1ee5_4000 | operand.encode(),
+        AArch64Inst::FrintzScalarHalfPrecisionVar(operand) => 0x1ee5_c000 | operand.encode(),
+        AArch64Inst::FrintaScalarHalfPrecisionVar(operand) => 0x1ee6_4000 | operand.encode(),
+        AArch64Inst::FrintxScalarHalfPrecisionVar(operand) => 0x1ee7_4000 | operand.encode(),
+        AArch64Inst::FrintiScalarHalfPrecisionVar(operand) => 0x1ee7_c000 | operand.encode(),
+
+        AArch64Inst::AddpScalar(operand) => 0x5e31_b800 | operand.encode(),
+        AArch64Inst::FmaxnmpScalarEncoding(operand) => 0x7e30_c800 | operand.encode(),
+        AArch64Inst::FaddpScalarEncoding(operand) => 0x7e30_d800 | operand.encode(),
+        AArch64Inst::FmaxpScalarEncoding(operand) => 0x7e30_f800 | operand.encode(),
+        AArch64Inst::FminnmpScalarEncoding(operand) => 0x7e30_c800 | operand.encode(),
+        AArch64Inst::FminpScalarEncoding(operand) => 0x7e30_f800 | operand.encode(),
+
+        AArch64Inst::Rev64(operand) => 0x0e20_0800 | operand.encode(),
+        AArch64Inst::Rev16Vec(operand) => 0x0e20_1800 | operand.encode(),
+        AArch64Inst::Saddlp(operand) => 0x0e20_2800 | operand.encode(),
+        AArch64Inst::Suqadd(operand) => 0x0e20_3800 | operand.encode(),
+        AArch64Inst::ClsVec(operand) => 0x0e20_4800 | operand.encode(),
+        AArch64Inst::Cnt(operand) => 0x0e20_5800 | operand.encode(),
+        AArch64Inst::Sadalp(operand) => 0x0e20_6800 | operand.encode(),
+        AArch64Inst::Sqabs(operand) => 0x0e20_7800 | operand.encode(),
+        AArch64Inst::CmgtZero(operand) => 0x0e20_8800 | operand.encode(),
+        AArch64Inst::CmeqZero(operand) => 0x0e20_9800 | operand.encode(),
+        AArch64Inst::CmltZero(operand) => 0x0e20_a800 | operand.encode(),
+        AArch64Inst::Abs(operand) => 0x0e20_b800 | operand.encode(),
+        AArch64Inst::XtnXtn2(operand) => 0x0e21_2800 | operand.encode(),
+        AArch64Inst::Sqxtn(operand) => 0x0e21_4800 | operand.encode(),
+        AArch64Inst::Fcvtn(operand) => 0x0e21_6800 | operand.encode(),
+        AArch64Inst::Fcvtl(operand) => 0x0e21_7800 | operand.encode(),
+        AArch64Inst::FrintnVec(operand) => 0x0e21_8800 | operand.encode(),
+        AArch64Inst::FrintmVec(operand) => 0x0e21_9800 | operand.encode(),
+        AArch64Inst::FcvtnsVec(operand) => 0x0e21_a800 | operand.encode(),
+        AArch64Inst::FcvtmsVec(operand) => 0x0e21_b800 | operand.encode(),
+        AArch64Inst::FcvtasVec(operand) => 0x0e21_c800 | operand.encode(),
+        AArch64Inst::ScvtfVecInt(operand) => 0x0e21_d800 | operand.encode(),
+        AArch64Inst::FcmgtZero(operand) => 0x0e20_c800 | operand.encode(),
+        AArch64Inst::FcmeqZero(operand) => 0x0e20_d800 | operand.encode(),
+        AArch64Inst::FcmltZero(operand) => 0x0e20_e800 | operand.encode(),
+        AArch64Inst::FabsVec(operand) => 0x0e20_f800 | operand.encode(),
+        AArch64Inst::FrintpVec(operand) => 0x0e21_8800 | operand.encode(),
+        AArch64Inst::FrintzVec(operand) => 0x0e21_9800 | operand.encode(),
+        AArch64Inst::FcvtpsVec(operand) => 0x0e21_a800 | operand.encode(),
+        AArch64Inst::FcvtzsVecInt(operand) => 0x0e21_b800 | operand.encode(),
+        AArch64Inst::Urecpe(operand) => 0x0e21_c800 | operand.encode(),
+        AArch64Inst::Frecpe(operand) => 0x0e21_d800 | operand.encode(),
+        AArch64Inst::Rev32Vec(operand) => 0x2e20_0800 | operand.encode(),
+        AArch64Inst::Uaddlp(operand) => 0x2e20_2800 | operand.encode(),
+        AArch64Inst::Usqadd(operand) => 0x2e20_3800 | operand.encode(),
+        AArch64Inst::ClzVec(operand) => 0x2e20_4800 | operand.encode(),
+        AArch64Inst::Uadalp(operand) => 0x2e20_6800 | operand.encode(),
+        AArch64Inst::Sqneg(operand) => 0x2e20_7800 | operand.encode(),
+        AArch64Inst::CmgeZero(operand) => 0x2e20_8800 | operand.encode(),
+        AArch64Inst::CmleZero(operand) => 0x2e20_9800 | operand.encode(),
+        AArch64Inst::NegVec(operand) => 0x2e20_b800 | operand.encode(),
+        AArch64Inst::Sqxtun(operand) => 0x2e21_2800 | operand.encode(),
+        AArch64Inst::Shll(operand) => 0x2e21_3800 | operand.encode(),
+        AArch64Inst::Uqxtn(operand) => 0x2e21_4800 | operand.encode(),
+        AArch64Inst::Fcvtxn(operand) => 0x2e21_6800 | operand.encode(),
+        AArch64Inst::FrintaVec(operand) => 0x2e21_8800 | operand.encode(),
+        AArch64Inst::FrintxVec(operand) => 0x2e21_9800 | operand.encode(),
+        AArch64Inst::FcvtnuVec(operand) => 0x2e21_a800 | operand.encode(),
+        AArch64Inst::FcvtmuVec(operand) => 0x2e21_b800 | operand.encode(),
+        AArch64Inst::FcvtauVec(operand) => 0x2e21_c800 | operand.encode(),
+        AArch64Inst::UcvtfVecInt(operand) => 0x2e21_d800 | operand.encode(),
+        AArch64Inst::Not(operand) => 0x2e20_5800 | operand.encode(),
+        AArch64Inst::RbitVec(operand) => 0x2e20_5800 | operand.encode(),
+        AArch64Inst::FcmgeZero(operand) => 0x2e20_c800 | operand.encode(),
+        AArch64Inst::FcmleZero(operand) => 0x2e20_d800 | operand.encode(),
+        AArch64Inst::FnegVec(operand) => 0x2e20_f800 | operand.encode(),
+        AArch64Inst::FrintiVec(operand) => 0x2e21_9800 | operand.encode(),
+        AArch64Inst::FcvtpuVec(operand) => 0x2e21_a800 | operand.encode(),
+        AArch64Inst::FcvtzuVecInt(operand) => 0x2e21_b800 | operand.encode(),
+        AArch64Inst::Ursqrte(operand) => 0x2e21_c800 | operand.encode(),
+        AArch64Inst::Frsqrte(operand) => 0x2e21_d800 | operand.encode(),
+        AArch64Inst::FsqrtVec(operand) => 0x2e21_f800 | operand.encode(),
+
+        AArch64Inst::SuqaddScalar(operand) => 0x5e20_3800 | operand.encode(),
+        AArch64Inst::SqabsScalar(operand) => 0x5e20_7800 | operand.encode(),
+        AArch64Inst::CmgtZeroScalar(operand) => 0x5e20_8800 | operand.encode(),
+        AArch64Inst::CmeqZeroScalar(operand) => 0x5e20_9800 | operand.encode(),
+        AArch64Inst::CmltZeroScalar(operand) => 0x5e20_a800 | operand.encode(),
+        AArch64Inst::AbsScalar(operand) => 0x5e20_b800 | operand.encode(),
+        AArch64Inst::SqxtnScalar(operand) => 0x5e21_4800 | operand.encode(),
+        AArch64Inst::FcvtnsScalar(operand) => 0x5e21_a800 | operand.encode(),
+        AArch64Inst::FcvtmsScalar(operand) => 0x5e21_b800 | operand.encode(),
+        AArch64Inst::FcvtasScalar(operand) => 0x5e21_c800 | operand.encode(),
+        AArch64Inst::ScvtfScalarInt(operand) => 0x5e21_d800 | operand.encode(),
+        AArch64Inst::FcmgtZeroScalar(operand) => 0x5e20_c800 | operand.encode(),
+        AArch64Inst::FcmeqZeroScalar(operand) => 0x5e20_d800 | operand.encode(),
+        AArch64Inst::FcmltZeroScalar(operand) => 0x5e20_e800 | operand.encode(),
+        AArch64Inst::FcvtpsScalar(operand) => 0x5e21_a800 | operand.encode(),
+        AArch64Inst::FcvtzsScalarInt(operand) => 0x5e21_b800 | operand.encode(),
+        AArch64Inst::FrecpeScalar(operand) => 0x5e21_d800 | operand.encode(),
+        AArch64Inst::Frecpx(operand) => 0x5e21_f800 | operand.encode(),
+        AArch64Inst::UsqaddScalar(operand) => 0x7e20_3800 | operand.encode(),
+        AArch64Inst::SqnegScalar(operand) => 0x7e20_7800 | operand.encode(),
+        AArch64Inst::CmgeZeroScalar(operand) => 0x7e20_8800 | operand.encode(),
+        AArch64Inst::CmleZeroScalar(operand) => 0x7e20_9800 | operand.encode(),
+        AArch64Inst::NegScalar(operand) => 0x7e20_b800 | operand.encode(),
+        AArch64Inst::SqxtunScalar(operand) => 0x7e21_2800 | operand.encode(),
+        AArch64Inst::UqxtnScalar(operand) => 0x7e21_4800 | operand.encode(),
+        AArch64Inst::FcvtxnScalar(operand) => 0x7e21_6800 | operand.encode(),
+        AArch64Inst::FcvtnuScalar(operand) => 0x7e21_a800 | operand.encode(),
+        AArch64Inst::FcvtmuScalar(operand) => 0x7e21_b800 | operand.encode(),
+        AArch64Inst::FcvtauScalar(operand) => 0x7e21_c800 | operand.encode(),
+        AArch64Inst::UcvtfScalarInt(operand) => 0x7e21_d800 | operand.encode(),
+        AArch64Inst::FcmgeZeroScalar(operand) => 0x7e20_c800 | operand.encode(),
+        AArch64Inst::FcmleZeroScalar(operand) => 0x7e20_d800 | operand.encode(),
+        AArch64Inst::FcvtpuScalar(operand) => 0x7e21_a800 | operand.encode(),
+        AArch64Inst::FcvtzuScalarInt(operand) => 0x7e21_b800 | operand.encode(),
+        AArch64Inst::FrsqrteScalar(operand) => 0x7e21_d800 | operand.encode(),
+
+        AArch64Inst::Saddlv(operand) => 0x0e30_3800 | operand.encode(),
+        AArch64Inst::Smaxv(operand) => 0x0e30_a800 | operand.encode(),
+        AArch64Inst::Sminv(operand) => 0x0e31_a800 | operand.encode(),
+        AArch64Inst::Addv(operand) => 0x0e31_b800 | operand.encode(),
+        AArch64Inst::Uaddlv(operand) => 0x2e30_3800 | operand.encode(),
+        AArch64Inst::Umaxv(operand) => 0x2e30_a800 | operand.encode(),
+        AArch64Inst::Uminv(operand) => 0x2e31_a800 | operand.encode(),
+        AArch64Inst::FmaxnvmEncoding(operand) => 0x2e30_c800 | operand.encode(),
+        AArch64Inst::FmaxvEncoding(operand) => 0x2e30_f800 | operand.encode(),
+        AArch64Inst::FminnmvEncoding(operand) => 0x2e30_c800 | operand.encode(),
+        AArch64Inst::FminvEncoding(operand) => 0x2e30_f800 | operand.encode(),
+
+        AArch64Inst::Udf(operand) => operand.encode(),
+
+        AArch64Inst::Casb(operand) => 0x08a0_7c00 | operand.encode(),
+        AArch64Inst::Caslb(operand) => 0x08a0_fc00 | operand.encode(),
+        AArch64Inst::Casab(operand) => 0x08e0_7c00 | operand.encode(),
+        AArch64Inst::Casalb(operand) => 0x08e0_fc00 | operand.encode(),
+        AArch64Inst::Cash(operand) => 0x48a0_7c00 | operand.encode(),
+        AArch64Inst::Caslh(operand) => 0x48a0_fc00 | operand.encode(),
+        AArch64Inst::Casah(operand) => 0x48e0_7c00 | operand.encode(),
+        AArch64Inst::Casalh(operand) => 0x48e0_fc00 | operand.encode(),
+
+        AArch64Inst::CasVar32(operand) => 0x88a0_7c00 | operand.encode(),
+        AArch64Inst::CaslVar32(operand) => 0x88a0_fc00 | operand.encode(),
+        AArch64Inst::CasaVar32(operand) => 0x88e0_7c00 | operand.encode(),
+        AArch64Inst::CasalVar32(operand) => 0x88e0_fc00 | operand.encode(),
+        AArch64Inst::CasVar64(operand) => 0xc8a0_7c00 | operand.encode(),
+        AArch64Inst::CaslVar64(operand) => 0xc8a0_fc00 | operand.encode(),
+        AArch64Inst::CasaVar64(operand) => 0xc8e0_7c00 | operand.encode(),
+        AArch64Inst::CasalVar64(operand) => 0xc8e0_fc00 | operand.encode(),
+
+        AArch64Inst::LdaddbVar(operand) => 0x3820_0000 | operand.encode(),
+        AArch64Inst::LdclrbVar(operand) => 0x3820_1000 | operand.encode(),
+        AArch64Inst::LdeorbVar(operand) => 0x3820_2000 | operand.encode(),
+        AArch64Inst::LdsetbVar(operand) => 0x3820_3000 | operand.encode(),
+        AArch64Inst::LdsmaxbVar(operand) => 0x3820_4000 | operand.encode(),
+        AArch64Inst::LdsminbVar(operand) => 0x3820_5000 | operand.encode(),
+        AArch64Inst::LdumaxbVar(operand) => 0x3820_6000 | operand.encode(),
+        AArch64Inst::LduminbVar(operand) => 0x3820_7000 | operand.encode(),
+        AArch64Inst::SwpbVar(operand) => 0x3820_8000 | operand.encode(),
+
+        AArch64Inst::LdaddlbVar(operand) => 0x3860_0000 | operand.encode(),
+        AArch64Inst::LdclrlbVar(operand) => 0x3860_1000 | operand.encode(),
+        AArch64Inst::LdeorlbVar(operand) => 0x3860_2000 | operand.encode(),
+        AArch64Inst::LdsetlbVar(operand) => 0x3860_3000 | operand.encode(),
+        AArch64Inst::LdsmaxlbVar(operand) => 0x3860_4000 | operand.encode(),
+        AArch64Inst::LdsminlbVar(operand) => 0x3860_5000 | operand.encode(),
+        AArch64Inst::LdumaxlbVar(operand) => 0x3860_6000 | operand.encode(),
+        AArch64Inst::LduminlbVar(operand) => 0x3860_7000 | operand.encode(),
+        AArch64Inst::SwplbVar(operand) => 0x3860_8000 | operand.encode(),
+
+        AArch64Inst::LdaddabVar(operand) => 0x38a0_0000 | operand.encode(),
+        AArch64Inst::LdclrabVar(operand) => 0x38a0_1000 | operand.encode(),
+        AArch64Inst::LdeorabVar(operand) => 0x38a0_2000 | operand.encode(),
+        AArch64Inst::LdsetabVar(operand) => 0x38a0_3000 | operand.encode(),
+        AArch64Inst::LdsmaxabVar(operand) => 0x38a0_4000 | operand.encode(),
+        AArch64Inst::LdsminabVar(operand) => 0x38a0_5000 | operand.encode(),
+        AArch64Inst::LdumaxabVar(operand) => 0x38a0_6000 | operand.encode(),
+        AArch64Inst::LduminabVar(operand) => 0x38a0_7000 | operand.encode(),
+        AArch64Inst::SwpabVar(operand) => 0x38a0_8000 | operand.encode(),
+
+        AArch64Inst::Ldaprb(operand) => 0x38a0_c000 | operand.encode(),
+
+        AArch64Inst::LdaddalbVar(operand) => 0x38e0_0000 | operand.encode(),
+        AArch64Inst::LdclralbVar(operand) => 0x38e0_1000 | operand.encode(),
+        AArch64Inst::LdeoralbVar(operand) => 0x38e0_2000 | operand.encode(),
+        AArch64Inst::LdsetalbVar(operand) => 0x38e0_3000 | operand.encode(),
+        AArch64Inst::LdsmaxalbVar(operand) => 0x38e0_4000 | operand.encode(),
+        AArch64Inst::LdsminalbVar(operand) => 0x38e0_5000 | operand.encode(),
+        AArch64Inst::LdumaxalbVar(operand) => 0x38e0_6000 | operand.encode(),
+        AArch64Inst::LduminalbVar(operand) => 0x38e0_7000 | operand.encode(),
+        AArch64Inst::SwpalbVar(operand) => 0x38e0_8000 | operand.encode(),
+
+        AArch64Inst::LdaddhVar(operand) => 0x7820_0000 | operand.encode(),
+        AArch64Inst::LdclrhVar(operand) => 0x7820_1000 | operand.encode(),
+        AArch64Inst::LdeorhVar(operand) => 0x7820_2000 | operand.encode(),
+        AArch64Inst::LdsethVar(operand) => 0x7820_3000 | operand.encode(),
+        AArch64Inst::LdsmaxhVar(operand) => 0x7820_4000 | operand.encode(),
+        AArch64Inst::LdsminhVar(operand) => 0x7820_5000 | operand.encode(),
+        AArch64Inst::LdumaxhVar(operand) => 0x7820_6000 | operand.encode(),
+        AArch64Inst::LduminhVar(operand) => 0x7820_7000 | operand.encode(),
+        AArch64Inst::SwphVar(operand) => 0x7820_8000 | operand.encode(),
+
+        AArch64Inst::LdaddlhVar(operand) => 0x7860_0000 | operand.encode(),
+        AArch64Inst::LdclrlhVar(operand) => 0x7860_1000 | operand.encode(),
+        AArch64Inst::LdeorlhVar(operand) => 0x7860_2000 | operand.encode(),
+        AArch64Inst::LdsetlhVar(operand) => 0x7860_3000 | operand.encode(),
+        AArch64Inst::LdsmaxlhVar(operand) => 0x7860_4000 | operand.encode(),
+        AArch64Inst::LdsminlhVar(operand) => 0x7860_5000 | operand.encode(),
+        AArch64Inst::LdumaxlhVar(operand) => 0x7860_6000 | operand.encode(),
+        AArch64Inst::LduminlhVar(operand) => 0x7860_7000 | operand.encode(),
+        AArch64Inst::SwplhVar(operand) => 0x7860_8000 | operand.encode(),
+
+        AArch64Inst::LdaddahVar(operand) => 0x78a0_0000 | operand.encode(),
+        AArch64Inst::LdclrahVar(operand) => 0x78a0_1000 | operand.encode(),
+        AArch64Inst::LdeorahVar(operand) => 0x78a0_2000 | operand.encode(),
+        AArch64Inst::LdsetahVar(operand) => 0x78a0_3000 | operand.encode(),
+        AArch64Inst::LdsmaxahVar(operand) => 0x78a0_4000 | operand.encode(),
+        AArch64Inst::LdsminahVar(operand) => 0x78a0_5000 | operand.encode(),
+        AArch64Inst::LdumaxahVar(operand) => 0x78a0_6000 | operand.encode(),
+        AArch64Inst::LduminahVar(operand) => 0x78a0_7000 | operand.encode(),
+        AArch64Inst::SwpahVar(operand) => 0x78a0_8000 | operand.encode(),
+
+        AArch64Inst::Ldaprh(operand) => 0x78a0_c000 | operand.encode(),
+
+        AArch64Inst::LdaddalhVar(operand) => 0x78e0_0000 | operand.encode(),
+        AArch64Inst::LdclralhVar(operand) => 0x78e0_1000 | operand.encode(),
+        AArch64Inst::LdeoralhVar(operand) => 0x78e0_2000 | operand.encode(),
+        AArch64Inst::LdsetalhVar(operand) => 0x78e0_3000 | operand.encode(),
+        AArch64Inst::LdsmaxalhVar(operand) => 0x78e0_4000 | operand.encode(),
+        AArch64Inst::LdsminalhVar(operand) => 0x78e0_5000 | operand.encode(),
+        AArch64Inst::LdumaxalhVar(operand) => 0x78e0_6000 | operand.encode(),
+        AArch64Inst::LduminalhVar(operand) => 0x78e0_7000 | operand.encode(),
+        AArch64Inst::SwpalhVar(operand) => 0x78e0_8000 | operand.encode(),
+
+        AArch64Inst::LdaddVar32(operand) => 0xb820_0000 | operand.encode(),
+        AArch64Inst::LdclrVar32(operand) => 0xb820_1000 | operand.encode(),
+        AArch64Inst::LdeorVar32(operand) => 0xb820_2000 | operand.encode(),
+        AArch64Inst::LdsetVar32(operand) => 0xb820_3000 | operand.encode(),
+        AArch64Inst::LdsmaxVar32(operand) => 0xb820_4000 | operand.encode(),
+        AArch64Inst::LdsminVar32(operand) => 0xb820_5000 | operand.encode(),
+        AArch64Inst::LdumaxVar32(operand) => 0xb820_6000 | operand.encode(),
+        AArch64Inst::LduminVar32(operand) => 0xb820_7000 | operand.encode(),
+        AArch64Inst::SwpVar32(operand) => 0xb820_8000 | operand.encode(),
+
+        AArch64Inst::LdaddlVar32(operand) => 0xb860_0000 | operand.encode(),
+        AArch64Inst::LdclrlVar32(operand) => 0xb860_1000 | operand.encode(),
+        AArch64Inst::LdeorlVar32(operand) => 0xb860_2000 | operand.encode(),
+        AArch64Inst::LdsetlVar32(operand) => 0xb860_3000 | operand.encode(),
+        AArch64Inst::LdsmaxlVar32(operand) => 0xb860_4000 | operand.encode(),
+        AArch64Inst::LdsminlVar32(operand) => 0xb860_5000 | operand.encode(),
+        AArch64Inst::LdumaxlVar32(operand) => 0xb860_6000 | operand.encode(),
+        AArch64Inst::LduminlVar32(operand) => 0xb860_7000 | operand.encode(),
+        AArch64Inst::SwplVar32(operand) => 0xb860_8000 | operand.encode(),
+
+        AArch64Inst::LdaddaVar32(operand) => 0xb8a0_0000 | operand.encode(),
+        AArch64Inst::LdclraVar32(operand) => 0xb8a0_1000 | operand.encode(),
+        AArch64Inst::LdeoraVar32(operand) => 0xb8a0_2000 | operand.encode(),
+        AArch64Inst::LdsetaVar32(operand) => 0xb8a0_3000 | operand.encode(),
+        AArch64Inst::LdsmaxaVar32(operand) => 0xb8a0_4000 | operand.encode(),
+        AArch64Inst::LdsminaVar32(operand) => 0xb8a0_5000 | operand.encode(),
+        AArch64Inst::LdumaxaVar32(operand) => 0xb8a0_6000 | operand.encode(),
+        AArch64Inst::LduminaVar32(operand) => 0xb8a0_7000 | operand.encode(),
+        AArch64Inst::SwpaVar32(operand) => 0xb8a0_8000 | operand.encode(),
+
+        AArch64Inst::LdaprVar32(operand) => 0xb8a0_c000 | operand.encode(),
+
+        AArch64Inst::LdaddalVar32(operand) => 0xb8e0_0000 | operand.encode(),
+        AArch64Inst::LdclralVar32(operand) => 0xb8e0_1000 | operand.encode(),
+        AArch64Inst::LdeoralVar32(operand) => 0xb8e0_2000 | operand.encode(),
+        AArch64Inst::LdsetalVar32(operand) => 0xb8e0_3000 | operand.encode(),
+        AArch64Inst::LdsmaxalVar32(operand) => 0xb8e0_4000 | operand.encode(),
+        AArch64Inst::LdsminalVar32(operand) => 0xb8e0_5000 | operand.encode(),
+        AArch64Inst::LdumaxalVar32(operand) => 0xb8e0_6000 | operand.encode(),
+        AArch64Inst::LduminalVar32(operand) => 0xb8e0_7000 | operand.encode(),
+        AArch64Inst::SwpalVar32(operand) => 0xb8e0_8000 | operand.encode(),
+
+        AArch64Inst::LdaddVar64(operand) => 0xf820_0000 | operand.encode(),
+        AArch64Inst::LdclrVar64(operand) => 0xf820_1000 | operand.encode(),
+        AArch64Inst::LdeorVar64(operand) => 0xf820_2000 | operand.encode(),
+        AArch64Inst::LdsetVar64(operand) => 0xf820_3000 | operand.encode(),
+        AArch64Inst::LdsmaxVar64(operand) => 0xf820_4000 | operand.encode(),
+        AArch64Inst::LdsminVar64(operand) => 0xf820_5000 | operand.encode(),
+        AArch64Inst::LdumaxVar64(operand) => 0xf820_6000 | operand.encode(),
+        AArch64Inst::LduminVar64(operand) => 0xf820_7000 | operand.encode(),
+        AArch64Inst::SwpVar64(operand) => 0xf820_8000 | operand.encode(),
+
+        AArch64Inst::St64bv0(operand) => 0xf820_a000 | operand.encode(),
+        AArch64Inst::St64bv(operand) => 0xf820_b000 | operand.encode(),
+
+        AArch64Inst::St64b(operand) => 0xf820_9000 | operand.encode(),
+        AArch64Inst::Ld64b(operand) => 0xf820_d000 | operand.encode(),
+
+        AArch64Inst::LdaddlVar64(operand) => 0xf860_0000 | operand.encode(),
+        AArch64Inst::LdclrlVar64(operand) => 0xf860_1000 | operand.encode(),
+        AArch64Inst::LdeorlVar64(operand) => 0xf860_2000 | operand.encode(),
+        AArch64Inst::LdsetlVar64(operand) => 0xf860_3000 | operand.encode(),
+        AArch64Inst::LdsmaxlVar64(operand) => 0xf860_4000 | operand.encode(),
+        AArch64Inst::LdsminlVar64(operand) => 0xf860_5000 | operand.encode(),
+        AArch64Inst::LdumaxlVar64(operand) => 0xf860_6000 | operand.encode(),
+        AArch64Inst::LduminlVar64(operand) => 0xf860_7000 | operand.encode(),
+        AArch64Inst::SwplVar64(operand) => 0xf860_8000 | operand.encode(),
+
+        AArch64Inst::LdaddaVar64(operand) => 0xf8a0_0000 | operand.encode(),
+        AArch64Inst::LdclraVar64(operand) => 0xf8a0_1000 | operand.encode(),
+        AArch64Inst::LdeoraVar64(operand) => 0xf8a0_2000 | operand.encode(),
+        AArch64Inst::LdsetaVar64(operand) => 0xf8a0_3000 | operand.encode(),
+        AArch64Inst::LdsmaxaVar64(operand) => 0xf8a0_4000 | operand.encode(),
+        AArch64Inst::LdsminaVar64(operand) => 0xf8a0_5000 | operand.encode(),
+        AArch64Inst::LdumaxaVar64(operand) => 0xf8a0_6000 | operand.encode(),
+        AArch64Inst::LduminaVar64(operand) => 0xf8a0_7000 | operand.encode(),
+        AArch64Inst::SwpaVar64(operand) => 0xf8a0_8000 | operand.encode(),
+
+        AArch64Inst::LdaprVar64(operand) => 0xf8a0_c000 | operand.encode(),
+
+        AArch64Inst::LdaddalVar64(operand) => 0xf8e0_0000 | operand.encode(),
+        AArch64Inst::LdclralVar64(operand) => 0xf8e0_1000 | operand.encode(),
+        AArch64Inst::LdeoralVar64(operand) => 0xf8e0_2000 | operand.encode(),
+        AArch64Inst::LdsetalVar64(operand) => 0xf8e0_3000 | operand.encode(),
+        AArch64Inst::LdsmaxalVar64(operand) => 0xf8e0_4000 | operand.encode(),
+        AArch64Inst::LdsminalVar64(operand) => 0xf8e0_5000 | operand.encode(),
+        AArch64Inst::LdumaxalVar64(operand) => 0xf8e0_6000 | operand.encode(),
+        AArch64Inst::LduminalVar64(operand) => 0xf8e0_7000 | operand.encode(),
+        AArch64Inst::SwpalVar64(operand) => 0xf8e0_8000 | operand.encode(),
+
+        AArch64Inst::Fcmp(operand) => 0x1e20_2000 | operand.encode(),
+        AArch64Inst::Fcmpe(operand) => 0x1e20_2000 | operand.encode(),
+
+        AArch64Inst::Tbl(operand) => 0x0e00_0000 | operand.encode(),
+        AArch64Inst::Tbx(operand) => 0x0e00_1000 | operand.encode(),
+
+        AArch64Inst::Saddl(operand) => 0x0e20_0000 | operand.encode(),
+        AArch64Inst::Saddw(operand) => 0x0e20_1000 | operand.encode(),
+        AArch64Inst::Ssubl(operand) => 0x0e20_2000 | operand.encode(),
+        AArch64Inst::Ssubw(operand) => 0x0e20_3000 | operand.encode(),
+        AArch64Inst::Addhn(operand) => 0x0e20_4000 | operand.encode(),
+        AArch64Inst::Sabal(operand) => 0x0e20_5000 | operand.encode(),
+        AArch64Inst::Subhn(operand) => 0x0e20_6000 | operand.encode(),
+        AArch64Inst::Sabdl(operand) => 0x0e20_7000 | operand.encode(),
+        AArch64Inst::Smlal(operand) => 0x0e20_8000 | operand.encode(),
+        AArch64Inst::Sqdmlal(operand) => 0x0e20_9000 | operand.encode(),
+        AArch64Inst::Smlsl(operand) => 0x0e20_a000 | operand.encode(),
+        AArch64Inst::Sqdmlsl(operand) => 0x0e20_b000 | operand.encode(),
+        AArch64Inst::Smull(operand) => 0x0e20_c000 | operand.encode(),
+        AArch64Inst::Sqdmull(operand) => 0x0e20_d000 | operand.encode(),
+        AArch64Inst::Pmull(operand) => 0x0e20_e000 | operand.encode(),
+        AArch64Inst::Uaddl(operand) => 0x2e20_0000 | operand.encode(),
+        AArch64Inst::Uaddw(operand) => 0x2e20_1000 | operand.encode(),
+        AArch64Inst::Usubl(operand) => 0x2e20_2000 | operand.encode(),
+        AArch64Inst::Usubw(operand) => 0x2e20_3000 | operand.encode(),
+        AArch64Inst::Raddhn(operand) => 0x2e20_4000 | operand.encode(),
+        AArch64Inst::Uabal(operand) => 0x2e20_5000 | operand.encode(),
+        AArch64Inst::Rsubhn(operand) => 0x2e20_6000 | operand.encode(),
+        AArch64Inst::Uabdl(operand) => 0x2e20_7000 | operand.encode(),
+        AArch64Inst::Umlal(operand) => 0x2e20_8000 | operand.encode(),
+        AArch64Inst::Umlsl(operand) => 0x2e20_a000 | operand.encode(),
+        AArch64Inst::Umull(operand) => 0x2e20_c000 | operand.encode(),
+
+        AArch64Inst::Uzp1(operand) => 0x0e00_1800 | operand.encode(),
+        AArch64Inst::Trn1(operand) => 0x0e00_2800 | operand.encode(),
+        AArch64Inst::Zip1(operand) => 0x0e00_3800 | operand.encode(),
+        AArch64Inst::Uzp2(operand) => 0x0e00_5800 | operand.encode(),
+        AArch64Inst::Trn2(operand) => 0x0e00_6800 | operand.encode(),
+        AArch64Inst::Zip2(operand) => 0x0e00_7800 | operand.encode(),
+
+        AArch64Inst::FmulScalarSinglePrecisionVar(operand) => 0x1e20_0800 | operand.encode(),
+        AArch64Inst::FdivScalarSinglePrecisionVar(operand) => 0x1e20_1800 | operand.encode(),
+        AArch64Inst::FaddScalarSinglePrecisionVar(operand) => 0x1e20_2800 | operand.encode(),
+        AArch64Inst::FsubScalarSinglePrecisionVar(operand) => 0x1e20_3800 | operand.encode(),
+        AArch64Inst::FmaxScalarSinglePrecisionVar(operand) => 0x1e20_4800 | operand.encode(),
+        AArch64Inst::FminScalarSinglePrecisionVar(operand) => 0x1e20_5800 | operand.encode(),
+        AArch64Inst::FmaxnmScalarSinglePrecisionVar(operand) => 0x1e20_6800 | operand.encode(),
+        AArch64Inst::FminnmScalarSinglePrecisionVar(operand) => 0x1e20_7800 | operand.encode(),
+        AArch64Inst::FnmulScalarSinglePrecisionVar(operand) => 0x1e20_8800 | operand.encode(),
+
+        AArch64Inst::FmulScalarDoublePrecisionVar(operand) => 0x1e60_0800 | operand.encode(),
+        AArch64Inst::FdivScalarDoublePrecisionVar(operand) => 0x1e60_1800 | operand.encode(),
+        AArch64Inst::FaddScalarDoublePrecisionVar(operand) => 0x1e60_2800 | operand.encode(),
+        AArch64Inst::FsubScalarDoublePrecisionVar(operand) => 0x1e60_3800 | operand.encode(),
+        AArch64Inst::FmaxScalarDoublePrecisionVar(operand) => 0x1e60_4800 | operand.encode(),
+        AArch64Inst::FminScalarDoublePrecisionVar(operand) => 0x1e60_5800 | operand.encode(),
+        AArch64Inst::FmaxnmScalarDoublePrecisionVar(operand) => 0x1e60_6800 | operand.encode(),
+        AArch64Inst::FminnmScalarDoublePrecisionVar(operand) => 0x1e60_7800 | operand.encode(),
+        AArch64Inst::FnmulScalarDoublePrecisionVar(operand) => 0x1e60_8800 | operand.encode(),
+
+        AArch64Inst::FmovScalarImmSinglePrecisionVar(operand) => 0x1e20_1000 | operand.encode(),
+        AArch64Inst::FmovScalarImmDoublePrecisionVar(operand) => 0x1e60_1000 | operand.encode(),
+
+        AArch64Inst::ScvtfScalarFixedPt32ToSinglePrecision(operand) => {
+            0x1e02_0000 | operand.encode()
+        }
+        AArch64Inst::UcvtfScalarFixedPt32ToSinglePrecision(operand) => {
+            0x1e03_0000 | operand.encode()
+        }
+        AArch64Inst::FcvtzsScalarFixedPtSinglePrecisionTo32(operand) => {
+            0x1e18_0000 | operand.encode()
+        }
+        AArch64Inst::FcvtzuScalarFixedPtSinglePrecisionTo32(operand) => {
+            0x1e19_0000 | operand.encode()
+        }
+
+        AArch64Inst::ScvtfScalarFixedPt32ToDoublePrecision(operand) => {
+            0x1e42_0000 | operand.encode()
+        }
+        AArch64Inst::UcvtfScalarFixedPt32ToDoublePrecision(operand) => {
+            0x1e43_0000 | operand.encode()
+        }
+        AArch64Inst::FcvtzsScalarFixedPtDoublePrecisionTo32(operand) => {
+            0x1e58_0000 | operand.encode()
+        }
+        AArch64Inst::FcvtzuScalarFixedPtDoublePrecisionTo32(operand) => {
+            0x1e59_0000 | operand.encode()
+        }
+
+        AArch64Inst::ScvtfScalarFixedPt64ToSinglePrecision(operand) => {
+            0x9e02_0000 | operand.encode()
+        }
+        AArch64Inst::UcvtfScalarFixedPt64ToSinglePrecision(operand) => {
+            0x9e03_0000 | operand.encode()
+        }
+        AArch64Inst::FcvtzsScalarFixedPtSinglePrecisionTo64(operand) => {
+            0x9e18_0000 | operand.encode()
+        }
+        AArch64Inst::FcvtzuScalarFixedPtSinglePrecisionTo64(operand) => {
+            0x9e19_0000 | operand.encode()
+        }
+
+        AArch64Inst::ScvtfScalarFixedPt64ToDoublePrecision(operand) => {
+            0x9e42_0000 | operand.encode()
+        }
+        AArch64Inst::UcvtfScalarFixedPt64ToDoublePrecision(operand) => {
+            0x9e43_0000 | operand.encode()
+        }
+        AArch64Inst::FcvtzsScalarFixedPtDoublePrecisionTo64(operand) => {
+            0x9e58_0000 | operand.encode()
+        }
+        AArch64Inst::FcvtzuScalarFixedPtDoublePrecisionTo64(operand) => {
+            0x9e59_0000 | operand.encode()
+        }
+
+        AArch64Inst::FcselSinglePrecisionVar(operand) => 0x1e20_0c00 | operand.encode(),
+        AArch64Inst::FcselDoublePrecisionVar(operand) => 0x1e60_0c00 | operand.encode(),
+
+        AArch64Inst::SmlalByElem(operand) => 0x0f00_2000 | operand.encode(),
+        AArch64Inst::SqdmlalByElem(operand) => 0x0f00_3000 | operand.encode(),
+        AArch64Inst::SmlslByElem(operand) => 0x0f00_6000 | operand.encode(),
+        AArch64Inst::SqdmlslByElem(operand) => 0x0f00_7000 | operand.encode(),
+        AArch64Inst::MulByElem(operand) => 0x0f00_8000 | operand.encode(),
+        AArch64Inst::SmullByElem(operand) => 0x0f00_a000 | operand.encode(),
+        AArch64Inst::SqdmullByElem(operand) => 0x0f00_b000 | operand.encode(),
+        AArch64Inst::SqdmulhByElem(operand) => 0x0f00_c000 | operand.encode(),
+        AArch64Inst::SqrdmulhByElem(operand) => 0x0f00_d000 | operand.encode(),
+
+        AArch64Inst::FmlaByElemEncoding(operand) => 0x0f00_1000 | operand.encode(),
+        AArch64Inst::FmlsByElemEncoding(operand) => 0x0f00_5000 | operand.encode(),
+        AArch64Inst::FmulByElemEncoding(operand) => 0x0f00_9000 | operand.encode(),
+
+        AArch64Inst::MlaByElem(operand) => 0x2f00_0000 | operand.encode(),
+        AArch64Inst::UmlalByElem(operand) => 0x2f00_2000 | operand.encode(),
+        AArch64Inst::MlsByElem(operand) => 0x2f00_4000 | operand.encode(),
+        AArch64Inst::UmlslByElem(operand) => 0x2f00_6000 | operand.encode(),
+        AArch64Inst::UmullByElem(operand) => 0x2f00_a000 | operand.encode(),
+        AArch64Inst::FmulxByElemEncoding(operand) => 0x2f00_9000 | operand.encode(),
+
+        AArch64Inst::SqdmlalByElemScalar(operand) => 0x5f00_3000 | operand.encode(),
+        AArch64Inst::SqdmlslByElemScalar(operand) => 0x5f00_7000 | operand.encode(),
+        AArch64Inst::SqdmullByElemScalar(operand) => 0x5f00_b000 | operand.encode(),
+        AArch64Inst::SqdmulhByElemScalar(operand) => 0x5f00_c000 | operand.encode(),
+        AArch64Inst::SqrdmulhByElemScalar(operand) => 0x5f00_d000 | operand.encode(),
+        AArch64Inst::FmlaByElemScalarEncoding(operand) => 0x5f00_1000 | operand.encode(),
+        AArch64Inst::FmlsByElemScalarEncoding(operand) => 0x5f00_5000 | operand.encode(),
+        AArch64Inst::FmulByElemScalarEncoding(operand) => 0x5f00_9000 | operand.encode(),
+        AArch64Inst::FmulxByElemScalarEncoding(operand) => 0x7f00_9000 | operand.encode(),
+    }
+}
+
+/// `value` placed at bit `lsb` of the word, it must fit in `width` bits.
+fn field(value: impl Into<u32>, lsb: u32, width: u32) -> u32 {
+    let value = value.into();
+    debug_assert!(
+        value >> width == 0,
+        "{value:#x} doesn't fit in {width} bits"
+    );
+    value << lsb
+}
+
+impl RmRnRd {
+    fn encode(&self) -> u32 {
+        field(self.rm.number(), 16, 5)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl RmRaRnRd {
+    fn encode(&self) -> u32 {
+        field(self.rm.number(), 16, 5)
+            | field(self.ra.number(), 10, 5)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl RnRd {
+    fn encode(&self) -> u32 {
+        field(self.rn.number(), 5, 5) | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl ShImm12RnRd {
+    fn encode(&self) -> u32 {
+        field(self.sh, 22, 1)
+            | field(self.imm12, 10, 12)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl OpcSizeImm12RnRt {
+    fn encode(&self) -> u32 {
+        field(self.size, 30, 2)
+            | field(self.idxt, 24, 2)
+            | field(self.opc, 22, 2)
+            | field(self.imm12, 10, 12)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rt.number(), 0, 5)
+    }
+}
+
+impl Imm26 {
+    fn encode(&self) -> u32 {
+        field(self.imm26, 0, 26)
+    }
+}
+
+impl Imm19Cond {
+    fn encode(&self) -> u32 {
+        field(self.imm19, 5, 19) | field(self.cond, 0, 4)
+    }
+}
+
+impl RmCondRnRd {
+    fn encode(&self) -> u32 {
+        field(self.rm.number(), 16, 5)
+            | field(self.cond, 12, 4)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl HwImm16Rd {
+    fn encode(&self) -> u32 {
+        field(self.hw, 21, 2) | field(self.imm16, 5, 16) | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl B5B40Imm14Rt {
+    fn encode(&self) -> u32 {
+        field(self.b5, 31, 1)
+            | field(self.b40, 19, 5)
+            | field(self.imm14, 5, 14)
+            | field(self.rt.number(), 0, 5)
+    }
+}
+
+impl ShiftRmImm6RnRd {
+    fn encode(&self) -> u32 {
+        field(self.shift, 22, 2)
+            | field(self.rm.number(), 16, 5)
+            | field(self.imm6, 10, 6)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl UncondBranchReg {
+    fn encode(&self) -> u32 {
+        field(self.z, 24, 1)
+            | field(self.op, 21, 2)
+            | field(self.a, 11, 1)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rm.number(), 0, 5)
+    }
+}
+
+impl PcRelAddressing {
+    fn encode(&self) -> u32 {
+        field(self.immlo, 29, 2) | field(self.immhi, 5, 19) | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl ExceptionGen {
+    fn encode(&self) -> u32 {
+        field(self.opc, 21, 3)
+            | field(self.imm16, 5, 16)
+            | field(self.op2, 2, 3)
+            | field(self.ll, 0, 2)
+    }
+}
+
+impl LoadStoreRegRegOffset {
+    fn encode(&self) -> u32 {
+        field(self.size, 30, 2)
+            | field(self.v, 26, 1)
+            | field(self.opc, 22, 2)
+            | field(self.rm.number(), 16, 5)
+            | field(self.option, 13, 3)
+            | field(self.s, 12, 1)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rt.number(), 0, 5)
+    }
+}
+
+impl AddSubtractExtReg {
+    fn encode(&self) -> u32 {
+        field(self.rm.number(), 16, 5)
+            | field(self.option, 13, 3)
+            | field(self.imm3, 10, 3)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl Bitfield {
+    fn encode(&self) -> u32 {
+        field(self.n, 22, 1)
+            | field(self.immr, 16, 6)
+            | field(self.imms, 10, 6)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl LogicalImm {
+    fn encode(&self) -> u32 {
+        field(self.n, 22, 1)
+            | field(self.immr, 16, 6)
+            | field(self.imms, 10, 6)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl LoadStoreRegPair {
+    fn encode(&self) -> u32 {
+        field(self.opc, 30, 2)
+            | field(self.o, 23, 3)
+            | field(self.imm7, 15, 7)
+            | field(self.rt2.number(), 10, 5)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rt.number(), 0, 5)
+    }
+}
+
+impl AddSubImmWithTags {
+    fn encode(&self) -> u32 {
+        field(self.o2, 22, 1)
+            | field(self.uimm6, 16, 6)
+            | field(self.op3, 14, 2)
+            | field(self.uimm4, 10, 4)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl ExtractImm {
+    fn encode(&self) -> u32 {
+        field(self.rm.number(), 16, 5)
+            | field(self.imms, 10, 6)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl Imm19Rt {
+    fn encode(&self) -> u32 {
+        field(self.imm19, 5, 19) | field(self.rt.number(), 0, 5)
+    }
+}
+
+impl DataProc3Src {
+    fn encode(&self) -> u32 {
+        field(self.rm.number(), 16, 5)
+            | field(self.ra.number(), 10, 5)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl SysRegMov {
+    fn encode(&self) -> u32 {
+        field(self.o0, 19, 1)
+            | field(self.op1, 16, 3)
+            | field(self.crn, 12, 4)
+            | field(self.crm, 8, 4)
+            | field(self.op2, 5, 3)
+            | field(self.rt.number(), 0, 5)
+    }
+}
+
+impl DataProc2Src {
+    fn encode(&self) -> u32 {
+        field(self.rm.number(), 16, 5)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl Barriers {
+    fn encode(&self) -> u32 {
+        field(self.crm, 8, 4)
+    }
+}
+
+impl Hints {
+    fn encode(&self) -> u32 {
+        field(self.crm, 8, 4) | field(self.op2, 5, 3)
+    }
+}
+
+impl AdvancedSimdCopy {
+    fn encode(&self) -> u32 {
+        field(self.q, 30, 1)
+            | field(self.imm5, 16, 5)
+            | field(self.imm4, 11, 4)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl CondCmpReg {
+    fn encode(&self) -> u32 {
+        field(self.rm.number(), 16, 5)
+            | field(self.cond, 12, 4)
+            | field(self.rn.number(), 5, 5)
+            | field(self.nzcv, 0, 4)
+    }
+}
+
+impl AdvSimdLdStMultiStructures {
+    fn encode(&self) -> u32 {
+        field(self.q, 30, 1)
+            | field(self.size, 10, 2)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rt, 0, 5)
+    }
+}
+
+impl AdvancedSimdExtract {
+    fn encode(&self) -> u32 {
+        field(self.q, 30, 1)
+            | field(self.rm.number(), 16, 5)
+            | field(self.imm4, 11, 4)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl AdvSimdLdStMultiStructuresPostIndexed {
+    fn encode(&self) -> u32 {
+        field(self.q, 30, 1)
+            | field(self.rm.number(), 16, 5)
+            | field(self.size, 10, 2)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rt, 0, 5)
+    }
+}
+
+impl AdvSimdModifiedImm {
+    fn encode(&self) -> u32 {
+        field(self.q, 30, 1)
+            | field(self.op, 29, 1)
+            | field(self.a, 18, 1)
+            | field(self.b, 17, 1)
+            | field(self.c, 16, 1)
+            | field(self.cmode, 12, 4)
+            | field(self.d, 9, 1)
+            | field(self.e, 8, 1)
+            | field(self.f, 7, 1)
+            | field(self.g, 6, 1)
+            | field(self.h, 5, 1)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl CondCmpImm {
+    fn encode(&self) -> u32 {
+        field(self.imm5, 16, 5)
+            | field(self.cond, 12, 4)
+            | field(self.rn.number(), 5, 5)
+            | field(self.nzcv, 0, 4)
+    }
+}
+
+impl RsRt2RnRt {
+    fn encode(&self) -> u32 {
+        field(self.rs.number(), 16, 5)
+            | field(self.rt2, 10, 5)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rt.number(), 0, 5)
+    }
+}
+
+impl LoadStoreExclusivePair {
+    fn encode(&self) -> u32 {
+        field(self.rs.number(), 16, 5)
+            | field(self.rt2.number(), 10, 5)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rt.number(), 0, 5)
+    }
+}
+
+impl QSizeRmRnRd {
+    fn encode(&self) -> u32 {
+        field(self.q, 30, 1)
+            | field(self.size, 22, 2)
+            | field(self.rm.number(), 16, 5)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl AdvSimdScalarThreeSame {
+    fn encode(&self) -> u32 {
+        field(self.size, 22, 2)
+            | field(self.rm.number(), 16, 5)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl AdvSimdScalarTwoRegMisc {
+    fn encode(&self) -> u32 {
+        field(self.size, 22, 2) | field(self.rn.number(), 5, 5) | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl AdvSimdTableLookup {
+    fn encode(&self) -> u32 {
+        field(self.q, 30, 1)
+            | field(self.rm.number(), 16, 5)
+            | field(self.len, 13, 2)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl AdvSimdShiftByImm {
+    fn encode(&self) -> u32 {
+        field(self.q, 30, 1)
+            | field(self.immh, 19, 4)
+            | field(self.immb, 16, 3)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl AdvSimdScalarPairwise {
+    fn encode(&self) -> u32 {
+        field(self.size, 22, 2) | field(self.rn.number(), 5, 5) | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl AdvSimdLdStSingleStructure {
+    fn encode(&self) -> u32 {
+        field(self.q, 30, 1)
+            | field(self.s, 12, 1)
+            | field(self.size, 10, 2)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rt, 0, 5)
+    }
+}
+
+impl AdvSimdLdStSingleStructurePostIndexed {
+    fn encode(&self) -> u32 {
+        field(self.q, 30, 1)
+            | field(self.rm.number(), 16, 5)
+            | field(self.s, 12, 1)
+            | field(self.size, 10, 2)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rt, 0, 5)
+    }
+}
+
+impl QSizeRnRd {
+    fn encode(&self) -> u32 {
+        field(self.q, 30, 1)
+            | field(self.size, 22, 2)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl Imm16 {
+    fn encode(&self) -> u32 {
+        field(self.imm16, 0, 16)
+    }
+}
+
+impl RsRnRt {
+    fn encode(&self) -> u32 {
+        field(self.rs.number(), 16, 5)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rt.number(), 0, 5)
+    }
+}
+
+impl CompareAndSwapPair {
+    fn encode(&self) -> u32 {
+        field(self.rs, 16, 5) | field(self.rn.number(), 5, 5) | field(self.rt, 0, 5)
+    }
+}
+
+impl FloatingPointCompare {
+    fn encode(&self) -> u32 {
+        field(self.ptype, 22, 2)
+            | field(self.rm.number(), 16, 5)
+            | field(self.rn.number(), 5, 5)
+            | field(self.opcode2, 0, 5)
+    }
+}
+
+impl FloatingPointImmediate {
+    fn encode(&self) -> u32 {
+        field(self.imm8, 13, 8) | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl ConvBetweenFloatAndFixedPoint {
+    fn encode(&self) -> u32 {
+        field(self.scale, 10, 6) | field(self.rn.number(), 5, 5) | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl AdvSimdXIndexedElem {
+    fn encode(&self) -> u32 {
+        field(self.q, 30, 1)
+            | field(self.size, 22, 2)
+            | field(self.l, 21, 1)
+            | field(self.m, 20, 1)
+            | field(self.rm.number(), 16, 4)
+            | field(self.h, 11, 1)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl AdvSimdScalarXIndexedElem {
+    fn encode(&self) -> u32 {
+        field(self.size, 22, 2)
+            | field(self.l, 21, 1)
+            | field(self.m, 20, 1)
+            | field(self.rm.number(), 16, 4)
+            | field(self.h, 11, 1)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rd.number(), 0, 5)
+    }
+}
+
+impl Rt {
+    fn encode(&self) -> u32 {
+        field(self.rt.number(), 0, 5)
+    }
+}
+
+impl Rn {
+    fn encode(&self) -> u32 {
+        field(self.rn.number(), 5, 5)
+    }
+}
+
+impl PstateOp {
+    fn encode(&self) -> u32 {
+        field(self.op1, 16, 3) | field(self.crm, 8, 4) | field(self.op2, 5, 3)
+    }
+}
+
+impl SystemInstructions {
+    fn encode(&self) -> u32 {
+        field(self.op1, 16, 3)
+            | field(self.crn, 12, 4)
+            | field(self.crm, 8, 4)
+            | field(self.op2, 5, 3)
+            | field(self.rt.number(), 0, 5)
+    }
+}
+
+impl RotateRightIntoFlags {
+    fn encode(&self) -> u32 {
+        field(self.imm6, 15, 6) | field(self.rn.number(), 5, 5) | field(self.mask, 0, 4)
+    }
+}
+
+impl LoadStoreMemoryTags {
+    fn encode(&self) -> u32 {
+        field(self.imm9, 12, 9)
+            | field(self.op2, 10, 2)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rt.number(), 0, 5)
+    }
+}
+
+impl Imm9RnRt {
+    fn encode(&self) -> u32 {
+        field(self.imm9, 12, 9) | field(self.rn.number(), 5, 5) | field(self.rt.number(), 0, 5)
+    }
+}
+
+impl LdStNoAllocPairOffset {
+    fn encode(&self) -> u32 {
+        field(self.imm7, 15, 7)
+            | field(self.rt2.number(), 10, 5)
+            | field(self.rn.number(), 5, 5)
+            | field(self.rt.number(), 0, 5)
+    }
+}
+
+impl LdStRegUnscaledImm {
+    fn encode(&self) -> u32 {
+        field(self.imm9, 12, 9) | field(self.rn.number(), 5, 5) | field(self.rt.number(), 0, 5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::decode_aarch64_inst;
+    use super::*;
+    use crate::aarch64::AArch64RegisterId::{Sp, Xzr, V, W, X};
+    use utility::SplitMix64;
+
+    fn decode(word: u32) -> Option<AArch64Inst> {
+        std::panic::catch_unwind(|| decode_aarch64_inst(&word.to_le_bytes())).ok()
+    }
+
+    #[test]
+    fn encode_operands() {
+        let cases = [
+            (
+                AArch64Inst::AddImm64(ShImm12RnRd {
+                    sh: 0,
+                    imm12: 3,
+                    rn: X(1),
+                    rd: X(0),
+                }),
+                0x9100_0c20,
+            ),
+            (
+                AArch64Inst::SubsShiftedReg32(ShiftRmImm6RnRd {
+                    shift: 0,
+                    rm: W(2),
+                    imm6: 3,
+                    rn: W(1),
+                    rd: Xzr,
+                }),
+                0x6b02_0c3f,
+            ),
+            (
+                AArch64Inst::StpVar64(LoadStoreRegPair {
+                    opc: 0b10,
+                    imm7: 0x7e,
+                    o: 0b011,
+                    rt2: X(30),
+                    rn: Sp,
+                    rt: X(29),
+                }),
+                0xa9bf_7bfd,
+            ),
+            (AArch64Inst::BImm(Imm26 { imm26: 0x3ff_ffff }), 0x17ff_ffff),
+            (AArch64Inst::Dmb(Barriers { crm: 0b1011 }), 0xd503_3bbf),
+            (
+                AArch64Inst::Tbx(AdvSimdTableLookup {
+                    q: 0,
+                    rm: V(3),
+                    len: 2,
+                    rn: V(31),
+                    rd: V(0),
+                }),
+                0x0e03_53e0,
+            ),
+        ];
+        for (inst, word) in cases {
+            assert_eq!(inst.encode(), word, "{inst:?}");
+            assert_eq!(decode(word).unwrap().encode(), word);
+        }
+    }
+
+    /// Every decoded word must be encoded back to itself, the decoder keeps all the fields of
+    /// the instructions it accepts.
+    #[test]
+    fn decode_round_trip() {
+        let mut rng = SplitMix64::new(0x2545_f491_4f6c_dd1d);
+        let mut decoded = 0;
+        for _ in 0..50_000 {
+            let word = rng.next_u64() as u32;
+            let Some(inst) = decode(word) else {
+                continue;
+            };
+            decoded += 1;
+            assert_eq!(inst.encode(), word, "{inst:?}");
+        }
+        assert!(decoded > 10_000, "{decoded}");
+    }
+}
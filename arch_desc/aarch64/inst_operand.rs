@@ -31,9 +31,12 @@ pub struct ShImm12RnRd {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct OpcSizeImm12RnRt {
+    /// `0b01` for the unsigned offset form, `0b00` for the pre and post-indexed forms.
     pub idxt: u8,
     pub opc: u8,
     pub size: u8,
+    /// The unsigned offset, or for the indexed forms `imm9:0b11` when pre-indexed and
+    /// `imm9:0b01` when post-indexed.
     pub imm12: u16,
     pub rn: AArch64RegisterId,
     pub rt: AArch64RegisterId,
@@ -155,8 +158,9 @@ pub struct LogicalImm {
 pub struct LoadStoreRegPair {
     pub opc: u8,
     pub imm7: u8,
+    /// `0b010` for the signed offset form, `0b011` pre-indexed and `0b001` post-indexed.
     pub o: u8,
-    pub rt2: u8,
+    pub rt2: AArch64RegisterId,
     pub rn: AArch64RegisterId,
     pub rt: AArch64RegisterId,
 }
@@ -215,6 +219,13 @@ pub struct Barriers {
     pub crm: u8,
 }
 
+/// The hint number `crm:op2` of the hint space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hints {
+    pub crm: u8,
+    pub op2: u8,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct AdvancedSimdCopy {
     pub q: u8,
@@ -307,9 +318,20 @@ pub struct QSizeRmRnRd {
     pub rd: AArch64RegisterId,
 }
 
+/// TBL and TBX, the table is `len + 1` consecutive registers from rn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdvSimdTableLookup {
+    pub q: u8,
+    pub rm: AArch64RegisterId,
+    pub len: u8,
+    pub rn: AArch64RegisterId,
+    pub rd: AArch64RegisterId,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct AdvSimdShiftByImm {
     pub q: u8,
+    pub immh: u8,
     pub immb: u8,
     pub rn: AArch64RegisterId,
     pub rd: AArch64RegisterId,
@@ -322,6 +344,21 @@ pub struct AdvSimdScalarPairwise {
     pub rd: AArch64RegisterId,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdvSimdScalarThreeSame {
+    pub size: u8,
+    pub rm: AArch64RegisterId,
+    pub rn: AArch64RegisterId,
+    pub rd: AArch64RegisterId,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdvSimdScalarTwoRegMisc {
+    pub size: u8,
+    pub rn: AArch64RegisterId,
+    pub rd: AArch64RegisterId,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct AdvSimdLdStSingleStructure {
     pub q: u8,
@@ -331,6 +368,16 @@ pub struct AdvSimdLdStSingleStructure {
     pub rt: u8,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdvSimdLdStSingleStructurePostIndexed {
+    pub q: u8,
+    pub rm: AArch64RegisterId,
+    pub s: u8,
+    pub size: u8,
+    pub rn: AArch64RegisterId,
+    pub rt: u8,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct QSizeRnRd {
     pub q: u8,
@@ -0,0 +1,621 @@
+use core::{
+    Abort, AbortKind, AccessType, Architecture, PageTranslation, Permissions, RegisterId,
+    TranslationRegime,
+};
+
+use once_cell::sync::Lazy;
+
+use super::compiler_prelude::pstate;
+use super::{AArch64Architecture, AArch64RegisterId};
+
+/// Bits of SCTLR_EL1
+mod sctlr {
+    /// The stage 1 translation of EL1&0 is enabled
+    pub const M: u64 = 1 << 0;
+    /// Write permission implies execute never
+    pub const WXN: u64 = 1 << 19;
+}
+
+/// Fields of TCR_EL1, T1SZ and EPD1 of the TTBR1_EL1 region are 16 bits above T0SZ and EPD0.
+mod tcr {
+    /// The size offset of the region, six bits
+    pub const T0SZ: u64 = 0;
+    /// No walks in the region
+    pub const EPD0: u64 = 7;
+    /// The granule, two bits
+    pub const TG0: u64 = 14;
+    /// TTBR1_EL1 holds the ASID
+    pub const A1: u64 = 22;
+    pub const TG1: u64 = 30;
+    /// 16-bit ASIDs
+    pub const AS: u64 = 36;
+    /// The top byte of the addresses is ignored
+    pub const TBI0: u64 = 37;
+    pub const TBI1: u64 = 38;
+}
+
+/// Bits of the descriptors
+mod descriptor {
+    pub const VALID: u64 = 1 << 0;
+    /// A table, or a page at level 3
+    pub const TABLE: u64 = 1 << 1;
+    /// The index of the attributes in MAIR_EL1, three bits
+    pub const ATTR_INDEX: u64 = 2;
+    /// Accessible from EL0
+    pub const AP_EL0: u64 = 1 << 6;
+    pub const AP_READ_ONLY: u64 = 1 << 7;
+    /// Access flag
+    pub const AF: u64 = 1 << 10;
+    /// Not global
+    pub const NG: u64 = 1 << 11;
+    pub const PXN: u64 = 1 << 53;
+    pub const UXN: u64 = 1 << 54;
+    pub const PXN_TABLE: u64 = 1 << 59;
+    pub const UXN_TABLE: u64 = 1 << 60;
+    /// No EL0 access below the table
+    pub const AP_TABLE_NO_EL0: u64 = 1 << 61;
+    pub const AP_TABLE_READ_ONLY: u64 = 1 << 62;
+    /// The output address, 48 bits
+    pub const ADDRESS: u64 = 0x0000_ffff_ffff_f000;
+}
+
+/// The registers the translation depends on, by their offset in the register file.
+struct Offsets {
+    pstate: usize,
+    sctlr: usize,
+    tcr: usize,
+    ttbr0: usize,
+    ttbr1: usize,
+    mair: usize,
+}
+
+static OFFSETS: Lazy<Offsets> = Lazy::new(|| {
+    let desc = AArch64Architecture::get_register_file_desc();
+    let offset = |id: AArch64RegisterId| desc.register(id.raw()).offset;
+
+    Offsets {
+        pstate: offset(AArch64RegisterId::Pstate),
+        sctlr: offset(AArch64RegisterId::SctlrEl1),
+        tcr: offset(AArch64RegisterId::TcrEl1),
+        ttbr0: offset(AArch64RegisterId::Ttbr0El1),
+        ttbr1: offset(AArch64RegisterId::Ttbr1El1),
+        mair: offset(AArch64RegisterId::MairEl1),
+    }
+});
+
+fn read(registers: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(registers[offset..offset + 8].try_into().unwrap())
+}
+
+/// The EL1&0 regime, `None` at EL2 and EL3 or when the stage 1 translation is disabled. The
+/// privilege is the exception level, plus 2 with PSTATE.PAN.
+pub fn translation_regime(registers: &[u8]) -> Option<TranslationRegime> {
+    let offsets = &*OFFSETS;
+    let pstate = read(registers, offsets.pstate);
+    let el = (pstate >> pstate::EL) & 0b11;
+    if el >= 2 || read(registers, offsets.sctlr) & sctlr::M == 0 {
+        return None;
+    }
+
+    let tcr = read(registers, offsets.tcr);
+    let ttbr = match (tcr >> tcr::A1) & 1 {
+        0 => read(registers, offsets.ttbr0),
+        _ => read(registers, offsets.ttbr1),
+    };
+    let asid = match (tcr >> tcr::AS) & 1 {
+        0 => (ttbr >> 48) & 0xff,
+        _ => ttbr >> 48,
+    };
+
+    Some(TranslationRegime {
+        asid: asid as u16,
+        privilege: (el | ((pstate >> pstate::PAN) & 1) << 1) as u8,
+    })
+}
+
+/// The regime of LDTR and STTR, which have the permissions of EL0 whatever PSTATE.PAN.
+pub fn unprivileged_regime(regime: TranslationRegime) -> TranslationRegime {
+    TranslationRegime {
+        privilege: 0,
+        ..regime
+    }
+}
+
+/// Walk the VMSAv8-64 stage 1 translation tables of the EL1&0 regime for `address`, with the
+/// permissions of the privilege of `regime`.
+///
+/// Bit 55 selects the region of TTBR0_EL1 or TTBR1_EL1, the granule of each region is 4K, 16K
+/// or 64K. Only 48-bit addresses are supported, and the hardware doesn't update the access
+/// flag: its faults are left to the guest.
+pub fn translate(
+    registers: &[u8],
+    regime: TranslationRegime,
+    address: u64,
+    access: AccessType,
+    read_descriptor: &mut dyn FnMut(u64) -> Option<u64>,
+) -> Result<PageTranslation, Abort> {
+    let offsets = &*OFFSETS;
+    let (sctlr, tcr) = (read(registers, offsets.sctlr), read(registers, offsets.tcr));
+    let fault = |kind| Abort {
+        fault_address: address,
+        access,
+        kind,
+        syndrome: 0,
+    };
+
+    let upper = (address >> 55) & 1 != 0;
+    let (fields, ttbr, granule_bits) = match upper {
+        false => {
+            let granule_bits = match (tcr >> tcr::TG0) & 0b11 {
+                0b01 => 16,
+                0b10 => 14,
+                _ => 12,
+            };
+            (tcr, read(registers, offsets.ttbr0), granule_bits)
+        }
+        true => {
+            let granule_bits = match (tcr >> tcr::TG1) & 0b11 {
+                0b01 => 14,
+                0b11 => 16,
+                _ => 12,
+            };
+            (tcr >> 16, read(registers, offsets.ttbr1), granule_bits)
+        }
+    };
+    let tbi = match upper {
+        false => (tcr >> tcr::TBI0) & 1 != 0,
+        true => (tcr >> tcr::TBI1) & 1 != 0,
+    };
+
+    // The bits above the input address must all be equal to bit 55
+    let input_bits = 64 - ((fields >> tcr::T0SZ) & 0x3f).clamp(16, 39) as u32;
+    let top = match tbi {
+        true => ((address << 8) as i64) >> (input_bits + 8),
+        false => (address as i64) >> input_bits,
+    };
+    let disabled = (fields >> tcr::EPD0) & 1 != 0;
+    if top != -(upper as i64) || disabled {
+        return Err(fault(AbortKind::Translation { level: 0 }));
+    }
+
+    let stride = granule_bits - 3;
+    let input = address & ((1 << input_bits) - 1);
+    let mut level = 4 - (input_bits - granule_bits).div_ceil(stride);
+    let mut table = ttbr & 0x0000_ffff_ffff_fffe;
+    let mut hierarchy = 0;
+
+    let (descriptor, shift) = loop {
+        let shift = granule_bits + stride * (3 - level);
+        let index = (input >> shift) & ((1 << stride) - 1);
+        let level_u8 = level as u8;
+        let descriptor = read_descriptor(table + index * 8)
+            .ok_or_else(|| fault(AbortKind::TableWalk { level: level_u8 }))?;
+
+        let table_bit = descriptor & descriptor::TABLE != 0;
+        let block = match level {
+            3 => table_bit,
+            2 => !table_bit,
+            1 => !table_bit && granule_bits == 12,
+            _ => false,
+        };
+        if descriptor & descriptor::VALID == 0 || !(block || table_bit) {
+            return Err(fault(AbortKind::Translation { level: level_u8 }));
+        }
+        if block {
+            break (descriptor, shift);
+        }
+
+        hierarchy |= descriptor;
+        table = descriptor & descriptor::ADDRESS & !((1 << granule_bits) - 1);
+        level += 1;
+    };
+
+    let level = level as u8;
+    if descriptor & descriptor::AF == 0 {
+        return Err(fault(AbortKind::AccessFlag { level }));
+    }
+
+    let el0 = descriptor & descriptor::AP_EL0 != 0 && hierarchy & descriptor::AP_TABLE_NO_EL0 == 0;
+    let writable = descriptor & descriptor::AP_READ_ONLY == 0
+        && hierarchy & descriptor::AP_TABLE_READ_ONLY == 0;
+    let execute_never = sctlr & sctlr::WXN != 0 && writable;
+    let uxn = descriptor & descriptor::UXN != 0 || hierarchy & descriptor::UXN_TABLE != 0;
+    let pxn = descriptor & descriptor::PXN != 0 || hierarchy & descriptor::PXN_TABLE != 0;
+    let permissions = match regime.privilege & 1 {
+        0 => Permissions {
+            read: el0,
+            write: el0 && writable,
+            execute: !execute_never && !uxn,
+        },
+        _ => {
+            // PAN keeps EL1 from accessing the data of EL0, and EL1 never executes the code EL0
+            // can write
+            let data = regime.privilege & 2 == 0 || !el0;
+            let el0_writable = el0 && writable;
+            Permissions {
+                read: data,
+                write: data && writable,
+                execute: !execute_never && !el0_writable && !pxn,
+            }
+        }
+    };
+    if !permissions.allows(access) {
+        return Err(fault(AbortKind::Permission { level }));
+    }
+
+    let attr_index = (descriptor >> descriptor::ATTR_INDEX) & 0b111;
+    let attributes = (read(registers, offsets.mair) >> (attr_index * 8)) & 0xff;
+    let page_mask = (1 << shift) - 1;
+
+    Ok(PageTranslation {
+        virtual_address: address & !page_mask,
+        physical_address: descriptor & descriptor::ADDRESS & !page_mask,
+        page_shift: shift as u8,
+        permissions,
+        asid: (descriptor & descriptor::NG != 0).then_some(regime.asid),
+        device: attributes & 0xf0 == 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aarch64::compiler_prelude::{exception_class, syndrome};
+    use crate::aarch64::testing::TestCpu;
+    use core::Interrupt;
+    use AArch64RegisterId::*;
+
+    /// The translation tables, the one of each level is 64K above the one of the level before.
+    const TABLES: u64 = 0x10_0000;
+    const DATA: u64 = 0x4000_0000;
+
+    /// A valid page, or a valid block below level 3.
+    const PAGE: u64 = 0b11 | descriptor::AF;
+    const BLOCK: u64 = 0b01 | descriptor::AF;
+    /// MAIR_EL1 with normal memory at index 0 and device memory at index 1
+    const MAIR: u64 = 0x00ff;
+    /// T0SZ and T1SZ of 48-bit regions, the 4K granule in both
+    const TCR_4K: u64 = 16 | (16 << 16) | (0b10 << tcr::TG1);
+
+    fn start_level(granule_bits: u32) -> u32 {
+        4 - (48 - granule_bits).div_ceil(granule_bits - 3)
+    }
+
+    /// A cpu at EL1 with the translation enabled and the tables of `granule_bits`.
+    fn translating_cpu(tcr: u64, granule_bits: u32) -> TestCpu {
+        let mut cpu = TestCpu::new();
+        cpu.map_memory(TABLES, 0x10_0000);
+        cpu.map_memory(DATA, 0x40_0000);
+
+        let ttbr = TABLES + start_level(granule_bits) as u64 * 0x1_0000;
+        for (id, value) in [
+            (Pstate, 1 << pstate::EL),
+            (SctlrEl1, sctlr::M),
+            (TcrEl1, tcr),
+            (MairEl1, MAIR),
+            (Ttbr0El1, ttbr),
+            (Ttbr1El1, ttbr),
+        ] {
+            cpu.set(id, value);
+        }
+        cpu
+    }
+
+    /// Map `address` with the `leaf` descriptor at `level`.
+    fn map(cpu: &mut TestCpu, granule_bits: u32, address: u64, level: u32, leaf: u64) {
+        let stride = granule_bits - 3;
+        for current in start_level(granule_bits)..=level {
+            let table = TABLES + current as u64 * 0x1_0000;
+            let shift = granule_bits + stride * (3 - current);
+            let index = ((address & 0xffff_ffff_ffff) >> shift) & ((1 << stride) - 1);
+            let descriptor = match current == level {
+                true => leaf,
+                false => (table + 0x1_0000) | 0b11,
+            };
+            cpu.write_memory(table + index * 8, &descriptor.to_le_bytes());
+        }
+    }
+
+    fn walk(cpu: &TestCpu, address: u64, access: AccessType) -> Result<PageTranslation, Abort> {
+        let regime = translation_regime(cpu.registers()).unwrap();
+        translate(cpu.registers(), regime, address, access, &mut |address| {
+            let mut descriptor = [0; 8];
+            unsafe { cpu.mmu.try_read_all_at(address, &mut descriptor) }.ok()?;
+            Some(u64::from_le_bytes(descriptor))
+        })
+    }
+
+    fn fault(cpu: &TestCpu, address: u64, access: AccessType) -> AbortKind {
+        walk(cpu, address, access).unwrap_err().kind
+    }
+
+    #[test]
+    fn regime_follows_the_exception_level_and_asid() {
+        let mut cpu = translating_cpu(TCR_4K | (1 << tcr::AS), 12);
+        cpu.set(Ttbr0El1, (0x1234 << 48) | TABLES);
+        let regime = translation_regime(cpu.registers()).unwrap();
+        assert_eq!((regime.asid, regime.privilege), (0x1234, 1));
+
+        // 8-bit ASIDs from TTBR1_EL1
+        cpu.set(TcrEl1, TCR_4K | (1 << tcr::A1));
+        cpu.set(Ttbr1El1, (0x5678 << 48) | TABLES);
+        cpu.set(Pstate, 1 << pstate::PAN);
+        let regime = translation_regime(cpu.registers()).unwrap();
+        assert_eq!((regime.asid, regime.privilege), (0x78, 2));
+
+        cpu.set(Pstate, 2 << pstate::EL);
+        assert_eq!(translation_regime(cpu.registers()), None);
+        cpu.set(Pstate, 0);
+        cpu.set(SctlrEl1, 0);
+        assert_eq!(translation_regime(cpu.registers()), None);
+    }
+
+    #[test]
+    fn walks_pages_and_blocks_of_the_4k_granule() {
+        let mut cpu = translating_cpu(TCR_4K, 12);
+        map(&mut cpu, 12, 0x1234_5678_9000, 3, DATA | PAGE);
+        let translation = walk(&cpu, 0x1234_5678_9abc, AccessType::Read).unwrap();
+        assert_eq!(
+            translation,
+            PageTranslation {
+                virtual_address: 0x1234_5678_9000,
+                physical_address: DATA,
+                page_shift: 12,
+                permissions: Permissions {
+                    read: true,
+                    write: true,
+                    execute: true,
+                },
+                asid: None,
+                device: false,
+            }
+        );
+
+        // A 2M block of device memory in the TTBR1_EL1 region, with the top byte ignored
+        cpu.set(TcrEl1, TCR_4K | (1 << tcr::TBI1));
+        let address = 0xffff_8000_0020_0000;
+        map(
+            &mut cpu,
+            12,
+            address,
+            2,
+            DATA | BLOCK | (1 << descriptor::ATTR_INDEX),
+        );
+        let translation = walk(&cpu, 0xabff_8000_0031_2345, AccessType::Write).unwrap();
+        assert_eq!(translation.virtual_address, 0xabff_8000_0020_0000);
+        assert_eq!(
+            (translation.physical_address, translation.page_shift),
+            (DATA, 21)
+        );
+        assert!(translation.device);
+    }
+
+    #[test]
+    fn walks_the_16k_and_64k_granules() {
+        // Four levels of 16K tables
+        let mut cpu = translating_cpu(16 | (0b10 << tcr::TG0), 14);
+        map(&mut cpu, 14, 0x8000_0000_c000, 3, DATA | PAGE);
+        let translation = walk(&cpu, 0x8000_0000_c010, AccessType::Read).unwrap();
+        assert_eq!(
+            (translation.physical_address, translation.page_shift),
+            (DATA, 14)
+        );
+
+        // Three levels of 64K tables, a 512M block at level 2
+        let mut cpu = translating_cpu(16 | (0b01 << tcr::TG0), 16);
+        map(&mut cpu, 16, 0x2000_0000, 2, BLOCK);
+        let translation = walk(&cpu, 0x2123_4567, AccessType::Read).unwrap();
+        assert_eq!(
+            (translation.physical_address, translation.page_shift),
+            (0, 29)
+        );
+
+        // A 64K granule in the TTBR1_EL1 region
+        let mut cpu = translating_cpu((16 << 16) | (0b11 << tcr::TG1), 16);
+        map(&mut cpu, 16, 0xffff_0000_0001_0000, 3, DATA | PAGE);
+        let translation = walk(&cpu, 0xffff_0000_0001_8000, AccessType::Read).unwrap();
+        assert_eq!(
+            (translation.physical_address, translation.page_shift),
+            (DATA, 16)
+        );
+    }
+
+    #[test]
+    fn faults_carry_their_level() {
+        let mut cpu = translating_cpu(TCR_4K, 12);
+        map(&mut cpu, 12, 0x1000, 3, DATA | PAGE);
+        map(&mut cpu, 12, 0x2000, 3, DATA | (PAGE & !descriptor::AF));
+        map(
+            &mut cpu,
+            12,
+            0x3000,
+            3,
+            DATA | PAGE | descriptor::AP_READ_ONLY,
+        );
+        map(&mut cpu, 12, 0x4000, 3, DATA | BLOCK);
+
+        use AbortKind::*;
+        assert_eq!(
+            fault(&cpu, 0x5000, AccessType::Read),
+            Translation { level: 3 }
+        );
+        assert_eq!(
+            fault(&cpu, 0x2000, AccessType::Read),
+            AccessFlag { level: 3 }
+        );
+        assert_eq!(
+            fault(&cpu, 0x3000, AccessType::Write),
+            Permission { level: 3 }
+        );
+        assert!(walk(&cpu, 0x3000, AccessType::Read).is_ok());
+        // A block descriptor at level 3 is reserved
+        assert_eq!(
+            fault(&cpu, 0x4000, AccessType::Read),
+            Translation { level: 3 }
+        );
+        // Above the 48-bit region, with the top byte checked
+        assert_eq!(
+            fault(&cpu, 0x0001_0000_0000_1000, AccessType::Read),
+            Translation { level: 0 }
+        );
+        assert_eq!(
+            fault(&cpu, 0xff00_0000_0000_1000, AccessType::Read),
+            Translation { level: 0 }
+        );
+        // The level 1 table points to unmapped memory
+        cpu.write_memory(TABLES + 0x1_0000, &(0x8000_0000u64 | 0b11).to_le_bytes());
+        assert_eq!(
+            fault(&cpu, 0x1000, AccessType::Read),
+            TableWalk { level: 2 }
+        );
+
+        // No walks in a disabled region
+        cpu.set(TcrEl1, TCR_4K | (1 << tcr::EPD0));
+        assert_eq!(
+            fault(&cpu, 0x1000, AccessType::Read),
+            Translation { level: 0 }
+        );
+    }
+
+    #[test]
+    fn permissions_follow_the_exception_level() {
+        let mut cpu = translating_cpu(TCR_4K, 12);
+        let user = DATA | PAGE | descriptor::AP_EL0 | descriptor::PXN;
+        map(&mut cpu, 12, 0x1000, 3, DATA | PAGE | descriptor::UXN);
+        map(&mut cpu, 12, 0x2000, 3, user);
+        map(&mut cpu, 12, 0x3000, 3, user | descriptor::AP_READ_ONLY);
+        let permissions = |cpu: &TestCpu, address| {
+            let permissions = walk(cpu, address, AccessType::Read).map(|t| t.permissions);
+            let Permissions {
+                read,
+                write,
+                execute,
+            } = permissions.unwrap_or_default();
+            (read, write, execute)
+        };
+
+        assert_eq!(permissions(&cpu, 0x1000), (true, true, true));
+        assert_eq!(permissions(&cpu, 0x2000), (true, true, false));
+        cpu.set(Pstate, 0);
+        assert_eq!(permissions(&cpu, 0x1000), (false, false, false));
+        assert_eq!(permissions(&cpu, 0x2000), (true, true, true));
+        assert_eq!(permissions(&cpu, 0x3000), (true, false, true));
+        assert_eq!(
+            fault(&cpu, 0x1000, AccessType::Read),
+            AbortKind::Permission { level: 3 }
+        );
+
+        // PAN keeps EL1 from the data of EL0
+        cpu.set(Pstate, (1 << pstate::EL) | (1 << pstate::PAN));
+        assert_eq!(permissions(&cpu, 0x1000), (true, true, true));
+        assert_eq!(
+            fault(&cpu, 0x2000, AccessType::Read),
+            AbortKind::Permission { level: 3 }
+        );
+
+        // WXN
+        cpu.set(Pstate, 1 << pstate::EL);
+        cpu.set(SctlrEl1, sctlr::M | sctlr::WXN);
+        assert_eq!(permissions(&cpu, 0x1000), (true, true, false));
+    }
+
+    #[test]
+    fn unprivileged_accesses_have_the_el0_permissions() {
+        let mut cpu = translating_cpu(TCR_4K, 12);
+        let user = DATA | PAGE | descriptor::AP_EL0;
+        map(&mut cpu, 12, 0x1000, 3, DATA | PAGE);
+        map(&mut cpu, 12, 0x2000, 3, user);
+        map(&mut cpu, 12, 0x3000, 3, user | descriptor::AP_READ_ONLY);
+        cpu.write_memory(DATA, &7u64.to_le_bytes());
+        let fault = |cpu: &mut TestCpu, word| match cpu.run(&[word]) {
+            Some(Interrupt::Aborts(abort)) => Some(abort.kind),
+            _ => None,
+        };
+
+        // EL1 data is out of reach, even though the privileged accesses are allowed
+        cpu.set(X(1), 0x1000);
+        assert_eq!(fault(&mut cpu, 0xf940_0020), None); // ldr x0, [x1]
+        assert_eq!(
+            fault(&mut cpu, 0xf840_0820), // ldtr x0, [x1]
+            Some(AbortKind::Permission { level: 3 })
+        );
+        assert_eq!(
+            fault(&mut cpu, 0xf800_0820), // sttr x0, [x1]
+            Some(AbortKind::Permission { level: 3 })
+        );
+
+        // PAN doesn't apply to them
+        cpu.set(Pstate, (1 << pstate::EL) | (1 << pstate::PAN));
+        cpu.set(X(1), 0x2000);
+        assert!(fault(&mut cpu, 0xf940_0020).is_some()); // ldr x0, [x1]
+        assert_eq!(fault(&mut cpu, 0xf840_0820), None); // ldtr x0, [x1]
+        assert_eq!(cpu.get(X(0)), 7);
+        assert_eq!(fault(&mut cpu, 0xf800_0820), None); // sttr x0, [x1]
+        cpu.set(X(1), 0x3000);
+        assert_eq!(
+            fault(&mut cpu, 0xf800_0820), // sttr x0, [x1]
+            Some(AbortKind::Permission { level: 3 })
+        );
+    }
+
+    #[test]
+    fn non_global_translations_carry_the_asid() {
+        let mut cpu = translating_cpu(TCR_4K, 12);
+        cpu.set(Ttbr0El1, (0x42 << 48) | TABLES);
+        map(&mut cpu, 12, 0x1000, 3, DATA | PAGE | descriptor::NG);
+        map(&mut cpu, 12, 0x2000, 3, DATA | PAGE);
+
+        assert_eq!(
+            walk(&cpu, 0x1000, AccessType::Read).unwrap().asid,
+            Some(0x42)
+        );
+        assert_eq!(walk(&cpu, 0x2000, AccessType::Read).unwrap().asid, None);
+    }
+
+    #[test]
+    fn accesses_are_translated_and_invalidated_by_tlbi() {
+        let mut cpu = translating_cpu(TCR_4K, 12);
+        map(&mut cpu, 12, 0x1000, 3, DATA | PAGE);
+        cpu.write_memory(DATA, &1u64.to_le_bytes());
+        cpu.write_memory(DATA + 0x1000, &2u64.to_le_bytes());
+
+        cpu.set(X(1), 0x1000);
+        assert_eq!(cpu.run(&[0xf940_0020]), None); // ldr x0, [x1]
+        assert_eq!(cpu.get(X(0)), 1);
+
+        // The stale translation is used until it is invalidated
+        map(&mut cpu, 12, 0x1000, 3, (DATA + 0x1000) | PAGE);
+        assert_eq!(cpu.run(&[0xf940_0020]), None); // ldr x0, [x1]
+        assert_eq!(cpu.get(X(0)), 1);
+        cpu.set(X(2), 0x1);
+        assert_eq!(cpu.run(&[0xd508_8722, 0xf940_0020]), None); // tlbi vae1, x2; ldr x0, [x1]
+        assert_eq!(cpu.get(X(0)), 2);
+
+        map(&mut cpu, 12, 0x1000, 3, 0);
+        assert_eq!(cpu.run(&[0xd508_831f]), None); // tlbi vmalle1is
+        let Some(Interrupt::Aborts(abort)) = cpu.run(&[0xf900_0020]) else {
+            panic!("the store is not aborted"); // str x0, [x1]
+        };
+        assert_eq!(abort.kind, AbortKind::Translation { level: 3 });
+        assert_eq!(
+            (abort.fault_address, abort.access),
+            (0x1000, AccessType::Write)
+        );
+
+        // Taken with the fault status and the level
+        cpu.take(Interrupt::Aborts(abort));
+        let iss = 0b000111 | (1 << 6);
+        assert_eq!(cpu.get(EsrEl1), syndrome(exception_class::DATA_ABORT, iss));
+        assert_eq!(cpu.get(FarEl1), 0x1000);
+    }
+
+    #[test]
+    fn tlbi_is_privileged() {
+        let mut cpu = translating_cpu(TCR_4K, 12);
+        cpu.set(Pstate, 0);
+        cpu.set(SctlrEl1, 0);
+        assert!(cpu.run(&[0xd508_8742]).is_some()); // tlbi aside1, x2
+        assert_eq!(cpu.run(&[0xd50b_7e21]), None); // dc civac, x1
+        assert!(cpu.run(&[0xd508_751f]).is_some()); // ic iallu
+    }
+}
@@ -1,4 +1,5 @@
-use core::{RawRegisterId, Register, RegisterId};
+use core::{ir::IrType, RawRegisterId, Register, RegisterId};
+use std::fmt;
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum AArch64MnemonicHint {
@@ -8,51 +9,35 @@ pub enum AArch64MnemonicHint {
     V,
 }
 
-#[derive(Copy, Clone, PartialEq, Eq)]
-pub enum AArch64Register {
-    // Speical registers
-    Xzr,
-    Pstate([bool; 64]),
-    Sp(u64),
-    Pc(u64),
-
-    // Scalar registers
-    X(u64),
-    W(u32),
-
-    // Vector registers
-    V(Vector),
-    Q(u128),
-    D(u64),
-    S(u32),
-    H(u16),
-    B(u8),
-}
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct AArch64Register(pub AArch64RegisterId);
 
 #[derive(Copy, Clone, PartialEq, Eq, Default)]
 #[repr(transparent)]
 pub struct Vector([u8; 16]);
 
 impl Register for AArch64Register {
+    /// The zero registers and the virtual counter can't be written by the guest. TPIDRRO_EL0 is
+    /// only read-only at EL0, which the MSR permission checks enforce.
     fn is_read_only(&self) -> bool {
-        match self {
-            Self::Xzr => true,
-            _ => false,
-        }
+        matches!(
+            self.0,
+            AArch64RegisterId::Xzr | AArch64RegisterId::Wzr | AArch64RegisterId::CntvctEl0
+        )
     }
 
     type Id = AArch64RegisterId;
 
     fn parent(&self) -> Self::Id {
-        todo!()
+        self.0.parent()
     }
 
     fn id(&self) -> Self::Id {
-        todo!()
+        self.0
     }
 
     fn size(&self) -> usize {
-        todo!()
+        self.0.ty().size_in_bytes()
     }
 }
 
@@ -181,9 +166,221 @@ pub enum AArch64RegisterId {
 
     // Special registers
     Sp,
+    Wsp,
     Pc,
+    /// The PSTATE fields other than the condition flags, at their SPSR_ELx bit positions
+    /// (see `compiler_prelude::pstate`).
     Pstate,
+    /// The condition flags, in bits 31:28 like the NZCV system register.
+    Nzcv,
     Xzr,
+    Wzr,
+    Fpcr,
+    Fpsr,
+    TpidrEl0,
+    TpidrroEl0,
+    /// The virtual counter, it advances on every read (see `sysreg`).
+    CntvctEl0,
+    /// The stack pointer of EL0 while PSTATE.SP selects another stack, `Sp` holds it otherwise.
+    SpEl0,
+    /// The stack pointer of EL1 while PSTATE.SP selects another stack, `Sp` holds it otherwise.
+    SpEl1,
+    SctlrEl1,
+    CpacrEl1,
+    Ttbr0El1,
+    Ttbr1El1,
+    TcrEl1,
+    MairEl1,
+    VbarEl1,
+    ElrEl1,
+    SpsrEl1,
+    EsrEl1,
+    FarEl1,
+    ContextidrEl1,
+    TpidrEl1,
+    CntkctlEl1,
+    /// The stack pointer of EL2 while PSTATE.SP selects another stack, `Sp` holds it otherwise.
+    SpEl2,
+    HcrEl2,
+    VbarEl2,
+    ElrEl2,
+    SpsrEl2,
+    EsrEl2,
+    FarEl2,
+    /// The stack pointer of EL3 while PSTATE.SP selects another stack, `Sp` holds it otherwise.
+    SpEl3,
+    ScrEl3,
+    VbarEl3,
+    ElrEl3,
+    SpsrEl3,
+    EsrEl3,
+    FarEl3,
+}
+
+impl AArch64RegisterId {
+    /// The system registers with storage in the register file, all of them 64 bits wide.
+    pub const SYSTEM: [Self; 35] = [
+        Self::Fpcr,
+        Self::Fpsr,
+        Self::TpidrEl0,
+        Self::TpidrroEl0,
+        Self::CntvctEl0,
+        Self::SpEl0,
+        Self::SpEl1,
+        Self::SctlrEl1,
+        Self::CpacrEl1,
+        Self::Ttbr0El1,
+        Self::Ttbr1El1,
+        Self::TcrEl1,
+        Self::MairEl1,
+        Self::VbarEl1,
+        Self::ElrEl1,
+        Self::SpsrEl1,
+        Self::EsrEl1,
+        Self::FarEl1,
+        Self::ContextidrEl1,
+        Self::TpidrEl1,
+        Self::CntkctlEl1,
+        Self::SpEl2,
+        Self::HcrEl2,
+        Self::VbarEl2,
+        Self::ElrEl2,
+        Self::SpsrEl2,
+        Self::EsrEl2,
+        Self::FarEl2,
+        Self::SpEl3,
+        Self::ScrEl3,
+        Self::VbarEl3,
+        Self::ElrEl3,
+        Self::SpsrEl3,
+        Self::EsrEl3,
+        Self::FarEl3,
+    ];
+
+    /// The type of the whole register.
+    pub fn ty(&self) -> IrType {
+        match self {
+            Self::X(_) | Self::D(_) | Self::Sp | Self::Pc | Self::Xzr => IrType::U64,
+            Self::Pstate | Self::Nzcv | Self::Fpcr | Self::Fpsr | Self::TpidrEl0 => IrType::U64,
+            Self::TpidrroEl0 | Self::CntvctEl0 | Self::SpEl0 | Self::SpEl1 => IrType::U64,
+            Self::SctlrEl1 | Self::CpacrEl1 | Self::Ttbr0El1 | Self::Ttbr1El1 => IrType::U64,
+            Self::TcrEl1 | Self::MairEl1 | Self::VbarEl1 | Self::ElrEl1 => IrType::U64,
+            Self::SpsrEl1 | Self::EsrEl1 | Self::FarEl1 | Self::ContextidrEl1 => IrType::U64,
+            Self::TpidrEl1 | Self::CntkctlEl1 => IrType::U64,
+            Self::SpEl2 | Self::HcrEl2 | Self::VbarEl2 | Self::ElrEl2 => IrType::U64,
+            Self::SpsrEl2 | Self::EsrEl2 | Self::FarEl2 => IrType::U64,
+            Self::SpEl3 | Self::ScrEl3 | Self::VbarEl3 | Self::ElrEl3 => IrType::U64,
+            Self::SpsrEl3 | Self::EsrEl3 | Self::FarEl3 => IrType::U64,
+            Self::W(_) | Self::S(_) | Self::Wsp | Self::Wzr => IrType::U32,
+            Self::V(_) | Self::Q(_) => IrType::U128,
+            Self::H(_) => IrType::U16,
+            Self::B(_) => IrType::U8,
+        }
+    }
+
+    /// The register owning the storage, the register itself if it is not a view.
+    ///
+    /// Every view starts at the low bits of its parent and a write through it zeroes the rest of
+    /// the parent.
+    pub fn parent(&self) -> Self {
+        match *self {
+            Self::W(v) => Self::X(v),
+            Self::Wsp => Self::Sp,
+            Self::Wzr => Self::Xzr,
+            Self::Q(v) | Self::D(v) | Self::S(v) | Self::H(v) | Self::B(v) => Self::V(v),
+            id => id,
+        }
+    }
+
+    /// The register of the same family with the width of `ty`, e.g. `W3` for `X3` and `U32`.
+    ///
+    /// General purpose registers have 64 and 32-bit views, vector registers have a view for
+    /// every scalar width and the other registers only have their own width.
+    pub fn view(&self, ty: IrType) -> Self {
+        match (self.parent(), ty) {
+            (Self::X(v), IrType::U64) => Self::X(v),
+            (Self::X(v), IrType::U32) => Self::W(v),
+            (Self::Sp, IrType::U64) => Self::Sp,
+            (Self::Sp, IrType::U32) => Self::Wsp,
+            (Self::Xzr, IrType::U64) => Self::Xzr,
+            (Self::Xzr, IrType::U32) => Self::Wzr,
+            (Self::V(v), IrType::U128) => Self::V(v),
+            (Self::V(v), IrType::U64) => Self::D(v),
+            (Self::V(v), IrType::U32) => Self::S(v),
+            (Self::V(v), IrType::U16) => Self::H(v),
+            (Self::V(v), IrType::U8) => Self::B(v),
+            (id, ty) if id.ty() == ty => id,
+            (id, ty) => unreachable!("{id:?} has no {ty:?} view"),
+        }
+    }
+
+    /// The number of the register in an instruction encoding, 31 for `sp` and the zero register.
+    pub fn number(&self) -> u8 {
+        match self.parent() {
+            Self::X(v) | Self::V(v) => v,
+            _ => 31,
+        }
+    }
+}
+
+/// The name of the register in assembly, e.g. `x3`, `wzr` or `sctlr_el1`.
+impl fmt::Display for AArch64RegisterId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::X(v) => return write!(f, "x{v}"),
+            Self::W(v) => return write!(f, "w{v}"),
+            Self::V(v) => return write!(f, "v{v}"),
+            Self::Q(v) => return write!(f, "q{v}"),
+            Self::D(v) => return write!(f, "d{v}"),
+            Self::S(v) => return write!(f, "s{v}"),
+            Self::H(v) => return write!(f, "h{v}"),
+            Self::B(v) => return write!(f, "b{v}"),
+            Self::Sp => "sp",
+            Self::Wsp => "wsp",
+            Self::Pc => "pc",
+            Self::Pstate => "pstate",
+            Self::Nzcv => "nzcv",
+            Self::Xzr => "xzr",
+            Self::Wzr => "wzr",
+            Self::Fpcr => "fpcr",
+            Self::Fpsr => "fpsr",
+            Self::TpidrEl0 => "tpidr_el0",
+            Self::TpidrroEl0 => "tpidrro_el0",
+            Self::CntvctEl0 => "cntvct_el0",
+            Self::SpEl0 => "sp_el0",
+            Self::SpEl1 => "sp_el1",
+            Self::SctlrEl1 => "sctlr_el1",
+            Self::CpacrEl1 => "cpacr_el1",
+            Self::Ttbr0El1 => "ttbr0_el1",
+            Self::Ttbr1El1 => "ttbr1_el1",
+            Self::TcrEl1 => "tcr_el1",
+            Self::MairEl1 => "mair_el1",
+            Self::VbarEl1 => "vbar_el1",
+            Self::ElrEl1 => "elr_el1",
+            Self::SpsrEl1 => "spsr_el1",
+            Self::EsrEl1 => "esr_el1",
+            Self::FarEl1 => "far_el1",
+            Self::ContextidrEl1 => "contextidr_el1",
+            Self::TpidrEl1 => "tpidr_el1",
+            Self::CntkctlEl1 => "cntkctl_el1",
+            Self::SpEl2 => "sp_el2",
+            Self::HcrEl2 => "hcr_el2",
+            Self::VbarEl2 => "vbar_el2",
+            Self::ElrEl2 => "elr_el2",
+            Self::SpsrEl2 => "spsr_el2",
+            Self::EsrEl2 => "esr_el2",
+            Self::FarEl2 => "far_el2",
+            Self::SpEl3 => "sp_el3",
+            Self::ScrEl3 => "scr_el3",
+            Self::VbarEl3 => "vbar_el3",
+            Self::ElrEl3 => "elr_el3",
+            Self::SpsrEl3 => "spsr_el3",
+            Self::EsrEl3 => "esr_el3",
+            Self::FarEl3 => "far_el3",
+        };
+
+        f.write_str(name)
+    }
 }
 
 impl RegisterId for AArch64RegisterId {
@@ -202,8 +399,88 @@ impl RegisterId for AArch64RegisterId {
             Self::Pc => 0x0801,
             Self::Pstate => 0x0802,
             Self::Xzr => 0x0803,
+            Self::Wsp => 0x0804,
+            Self::Wzr => 0x0805,
+            Self::Nzcv => 0x0806,
+            Self::Fpcr => 0x0807,
+            Self::Fpsr => 0x0808,
+            Self::TpidrEl0 => 0x0809,
+            Self::TpidrroEl0 => 0x080A,
+            Self::CntvctEl0 => 0x080B,
+            Self::SpEl0 => 0x080C,
+            Self::SpEl1 => 0x080D,
+            Self::SctlrEl1 => 0x080E,
+            Self::CpacrEl1 => 0x080F,
+            Self::Ttbr0El1 => 0x0810,
+            Self::Ttbr1El1 => 0x0811,
+            Self::TcrEl1 => 0x0812,
+            Self::MairEl1 => 0x0813,
+            Self::VbarEl1 => 0x0814,
+            Self::ElrEl1 => 0x0815,
+            Self::SpsrEl1 => 0x0816,
+            Self::EsrEl1 => 0x0817,
+            Self::FarEl1 => 0x0818,
+            Self::ContextidrEl1 => 0x0819,
+            Self::TpidrEl1 => 0x081A,
+            Self::CntkctlEl1 => 0x081B,
+            Self::SpEl2 => 0x081C,
+            Self::HcrEl2 => 0x081D,
+            Self::VbarEl2 => 0x081E,
+            Self::ElrEl2 => 0x081F,
+            Self::SpsrEl2 => 0x0820,
+            Self::EsrEl2 => 0x0821,
+            Self::FarEl2 => 0x0822,
+            Self::SpEl3 => 0x0823,
+            Self::ScrEl3 => 0x0824,
+            Self::VbarEl3 => 0x0825,
+            Self::ElrEl3 => 0x0826,
+            Self::SpsrEl3 => 0x0827,
+            Self::EsrEl3 => 0x0828,
+            Self::FarEl3 => 0x0829,
         };
 
         RawRegisterId::new(raw)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vector_lanes_are_little_endian() {
+        let value = 0x0f0e_0d0c_0b0a_0908_0706_0504_0302_0100u128;
+        let vec = Vector::from(value);
+
+        assert_eq!(<[u8; 16]>::from(vec), std::array::from_fn(|i| i as u8));
+        assert_eq!(<[u16; 8]>::from(vec)[1], 0x0302);
+        assert_eq!(<[u32; 4]>::from(vec)[3], 0x0f0e_0d0c);
+        assert_eq!(
+            <[u64; 2]>::from(vec),
+            [0x0706_0504_0302_0100, 0x0f0e_0d0c_0b0a_0908]
+        );
+
+        assert_eq!(u128::from(Vector::from(<[u16; 8]>::from(vec))), value);
+        assert_eq!(u128::from(Vector::from(<[u32; 4]>::from(vec))), value);
+        assert_eq!(u128::from(Vector::from(<[u64; 2]>::from(vec))), value);
+        assert_eq!(u128::from(vec), value);
+    }
+
+    #[test]
+    fn read_only_registers() {
+        for id in [
+            AArch64RegisterId::Xzr,
+            AArch64RegisterId::Wzr,
+            AArch64RegisterId::CntvctEl0,
+        ] {
+            assert!(AArch64Register(id).is_read_only());
+        }
+        for id in [
+            AArch64RegisterId::X(0),
+            AArch64RegisterId::Sp,
+            AArch64RegisterId::TpidrroEl0,
+        ] {
+            assert!(!AArch64Register(id).is_read_only());
+        }
+    }
+}
@@ -0,0 +1,749 @@
+use core::ir::{BasicBlock, IrType, IrValue};
+
+use super::compiler_prelude::{
+    self, gen_add, gen_and, gen_assign, gen_current_el, gen_is_zero, gen_msb, gen_or, gen_select,
+    gen_shr, gen_sub, gen_xor, imm, pstate, reg,
+};
+use super::{AArch64RegisterId, SysRegMov};
+
+/// The frequency of the virtual counter in CNTFRQ_EL0, in Hz.
+pub const COUNTER_FREQUENCY: u64 = 1_000_000_000;
+
+/// How far the virtual counter advances on every read. It has no time source so that runs stay
+/// deterministic, a guest polling it still sees time pass.
+const COUNTER_STEP: u64 = 1000;
+
+/// The encoding of a system register in MRS and MSR, `op0` is 2 or 3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SysRegEncoding {
+    pub op0: u8,
+    pub op1: u8,
+    pub crn: u8,
+    pub crm: u8,
+    pub op2: u8,
+}
+
+impl SysRegEncoding {
+    pub const fn new(op0: u8, op1: u8, crn: u8, crm: u8, op2: u8) -> Self {
+        Self {
+            op0,
+            op1,
+            crn,
+            crm,
+            op2,
+        }
+    }
+}
+
+impl From<&SysRegMov> for SysRegEncoding {
+    fn from(operand: &SysRegMov) -> Self {
+        Self::new(
+            0b10 | operand.o0,
+            operand.op1,
+            operand.crn,
+            operand.crm,
+            operand.op2,
+        )
+    }
+}
+
+/// Where the value of a system register lives.
+#[derive(Clone, Copy)]
+pub enum SysRegValue {
+    /// The bits of `mask` in the register `id`, at the same positions. The other bits read as
+    /// zero and writes leave them untouched.
+    Field { id: AArch64RegisterId, mask: u64 },
+    /// A fixed value, like the identification registers have.
+    Constant(u64),
+    /// Accesses generated by hooks, for the registers with side effects.
+    Hooks {
+        read: fn(&mut BasicBlock) -> IrValue,
+        write: fn(&mut BasicBlock, IrValue),
+    },
+}
+
+/// A system register with the lowest exception levels allowed to access it.
+pub struct SysReg {
+    pub name: &'static str,
+    pub encoding: SysRegEncoding,
+    /// The lowest exception level that can read the register, `None` if it can't be read.
+    pub read: Option<u8>,
+    /// The lowest exception level that can write the register, `None` if it is read-only.
+    pub write: Option<u8>,
+    pub value: SysRegValue,
+}
+
+impl SysReg {
+    /// The system register with `encoding`, `None` if it isn't implemented.
+    pub fn lookup(encoding: SysRegEncoding) -> Option<&'static SysReg> {
+        SYSREGS.iter().find(|sysreg| sysreg.encoding == encoding)
+    }
+
+    /// Read the register as a U64, the exception level is checked by the caller.
+    pub fn gen_read(&self, bb: &mut BasicBlock) -> IrValue {
+        match self.value {
+            SysRegValue::Field { id, mask: u64::MAX } => reg(id, IrType::U64),
+            SysRegValue::Field { id, mask } => {
+                gen_and(bb, reg(id, IrType::U64), imm(IrType::U64, mask))
+            }
+            SysRegValue::Constant(value) => imm(IrType::U64, value),
+            SysRegValue::Hooks { read, .. } => read(bb),
+        }
+    }
+
+    /// Write the U64 `value` to the register, the exception level is checked by the caller.
+    pub fn gen_write(&self, bb: &mut BasicBlock, value: IrValue) {
+        match self.value {
+            SysRegValue::Field { id, mask } => gen_write_field(bb, id, mask, value),
+            SysRegValue::Constant(_) => unreachable!("{} is read-only", self.name),
+            SysRegValue::Hooks { write, .. } => write(bb, value),
+        }
+    }
+}
+
+/// Raise an undefined instruction exception if the current exception level is below `el`.
+pub fn gen_check_el(bb: &mut BasicBlock, el: u8) {
+    if el == 0 {
+        return;
+    }
+
+    let current = gen_current_el(bb);
+    let difference = gen_sub(bb, current, imm(IrType::U64, el as u64));
+    let below = gen_msb(bb, difference);
+    compiler_prelude::gen_undefined_if(bb, below);
+}
+
+/// Replace the bits of `mask` in the register `id` with the ones of `value`.
+fn gen_write_field(bb: &mut BasicBlock, id: AArch64RegisterId, mask: u64, value: IrValue) {
+    let dst = reg(id, IrType::U64);
+    if mask == u64::MAX {
+        return gen_assign(bb, dst, value);
+    }
+
+    let kept = gen_and(bb, dst, imm(IrType::U64, !mask));
+    let written = gen_and(bb, value, imm(IrType::U64, mask));
+    let result = gen_or(bb, kept, written);
+    gen_assign(bb, dst, result);
+}
+
+/// The banked stack pointers, indexed by the stack that PSTATE selects.
+const STACKS: [AArch64RegisterId; 4] = [
+    AArch64RegisterId::SpEl0,
+    AArch64RegisterId::SpEl1,
+    AArch64RegisterId::SpEl2,
+    AArch64RegisterId::SpEl3,
+];
+
+/// The stack selected by the U64 `pstate`, SP_EL0 while PSTATE.SP is clear and the one of the
+/// exception level otherwise.
+fn gen_stack_index(bb: &mut BasicBlock, pstate: IrValue) -> IrValue {
+    let select = gen_shr(bb, pstate, imm(IrType::U64, pstate::SP));
+    let select = gen_and(bb, select, imm(IrType::U64, 1));
+    let el = gen_shr(bb, pstate, imm(IrType::U64, pstate::EL));
+    let el = gen_and(bb, el, imm(IrType::U64, 0b11));
+    gen_select(bb, select, el, imm(IrType::U64, 0))
+}
+
+/// Bank the stack pointers for PSTATE changing to `new_pstate`. `Sp` always holds the selected
+/// stack pointer, the other ones are kept in `SpEl0` to `SpEl3`. PSTATE itself is left to the
+/// caller.
+pub fn gen_switch_stack(bb: &mut BasicBlock, new_pstate: IrValue) {
+    let pstate = reg(AArch64RegisterId::Pstate, IrType::U64);
+    let current = gen_stack_index(bb, pstate);
+    let selected = gen_stack_index(bb, new_pstate);
+
+    // Sp is saved to its bank before the selected one is loaded, so nothing changes if both
+    // are the same.
+    let sp = reg(AArch64RegisterId::Sp, IrType::U64);
+    let mut new_sp = sp;
+    for (index, id) in STACKS.into_iter().enumerate() {
+        let bank = reg(id, IrType::U64);
+        let index = imm(IrType::U64, index as u64);
+
+        let difference = gen_xor(bb, current, index);
+        let is_current = gen_is_zero(bb, difference);
+        let saved = gen_select(bb, is_current, sp, bank);
+
+        let difference = gen_xor(bb, selected, index);
+        let is_selected = gen_is_zero(bb, difference);
+        new_sp = gen_select(bb, is_selected, saved, new_sp);
+        gen_assign(bb, bank, saved);
+    }
+    gen_assign(bb, sp, new_sp);
+}
+
+fn read_spsel(bb: &mut BasicBlock) -> IrValue {
+    let pstate = reg(AArch64RegisterId::Pstate, IrType::U64);
+    gen_and(bb, pstate, imm(IrType::U64, 1 << pstate::SP))
+}
+
+fn write_spsel(bb: &mut BasicBlock, value: IrValue) {
+    let pstate = reg(AArch64RegisterId::Pstate, IrType::U64);
+    let select = gen_and(bb, value, imm(IrType::U64, 1));
+    let kept = gen_and(bb, pstate, imm(IrType::U64, !(1 << pstate::SP)));
+    let new_pstate = gen_or(bb, kept, select);
+    gen_switch_stack(bb, new_pstate);
+    gen_assign(bb, pstate, new_pstate);
+}
+
+/// SP_EL0 is only accessible while the stack of the exception level is selected, `Sp` holds
+/// it otherwise.
+fn gen_check_sp_el1_selected(bb: &mut BasicBlock) {
+    let spsel = read_spsel(bb);
+    let sp_el0_selected = gen_xor(bb, spsel, imm(IrType::U64, 1 << pstate::SP));
+    compiler_prelude::gen_undefined_if(bb, sp_el0_selected);
+}
+
+fn read_sp_el0(bb: &mut BasicBlock) -> IrValue {
+    gen_check_sp_el1_selected(bb);
+    reg(AArch64RegisterId::SpEl0, IrType::U64)
+}
+
+fn write_sp_el0(bb: &mut BasicBlock, value: IrValue) {
+    gen_check_sp_el1_selected(bb);
+    gen_assign(bb, reg(AArch64RegisterId::SpEl0, IrType::U64), value);
+}
+
+fn read_counter(bb: &mut BasicBlock) -> IrValue {
+    let counter = reg(AArch64RegisterId::CntvctEl0, IrType::U64);
+    let value = bb.new_variable(IrType::U64);
+    gen_assign(bb, value, counter);
+
+    let advanced = gen_add(bb, counter, imm(IrType::U64, COUNTER_STEP));
+    gen_assign(bb, counter, advanced);
+    value
+}
+
+fn read_only(_: &mut BasicBlock, _: IrValue) {
+    unreachable!("write to a read-only system register")
+}
+
+/// The implemented system registers.
+///
+/// The identification registers can be read from EL0 like Linux emulates them, and describe
+/// what is lifted: LSE atomics but no DC ZVA, and a cache type that makes the guest clean
+/// the caches of code it writes.
+pub static SYSREGS: &[SysReg] = &[
+    SysReg {
+        name: "NZCV",
+        encoding: SysRegEncoding::new(3, 3, 4, 2, 0),
+        read: Some(0),
+        write: Some(0),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::Nzcv,
+            mask: 0xf000_0000,
+        },
+    },
+    SysReg {
+        name: "DAIF",
+        encoding: SysRegEncoding::new(3, 3, 4, 2, 1),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::Pstate,
+            mask: 0b1111 << pstate::F,
+        },
+    },
+    SysReg {
+        name: "DIT",
+        encoding: SysRegEncoding::new(3, 3, 4, 2, 5),
+        read: Some(0),
+        write: Some(0),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::Pstate,
+            mask: 1 << pstate::DIT,
+        },
+    },
+    SysReg {
+        name: "SSBS",
+        encoding: SysRegEncoding::new(3, 3, 4, 2, 6),
+        read: Some(0),
+        write: Some(0),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::Pstate,
+            mask: 1 << pstate::SSBS,
+        },
+    },
+    SysReg {
+        name: "FPCR",
+        encoding: SysRegEncoding::new(3, 3, 4, 4, 0),
+        read: Some(0),
+        write: Some(0),
+        // AHP, DN, FZ, RMode and FZ16, the exceptions can't be trapped
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::Fpcr,
+            mask: 0x07c8_0000,
+        },
+    },
+    SysReg {
+        name: "FPSR",
+        encoding: SysRegEncoding::new(3, 3, 4, 4, 1),
+        read: Some(0),
+        write: Some(0),
+        // QC and the cumulative exception bits
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::Fpsr,
+            mask: 0x0800_009f,
+        },
+    },
+    SysReg {
+        name: "TPIDR_EL0",
+        encoding: SysRegEncoding::new(3, 3, 13, 0, 2),
+        read: Some(0),
+        write: Some(0),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::TpidrEl0,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "TPIDRRO_EL0",
+        encoding: SysRegEncoding::new(3, 3, 13, 0, 3),
+        read: Some(0),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::TpidrroEl0,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        // Cache lines of 64 bytes, a PIPT instruction cache that needs cleaning
+        name: "CTR_EL0",
+        encoding: SysRegEncoding::new(3, 3, 0, 0, 1),
+        read: Some(0),
+        write: None,
+        value: SysRegValue::Constant(0x8444_c004),
+    },
+    SysReg {
+        // DC ZVA is prohibited
+        name: "DCZID_EL0",
+        encoding: SysRegEncoding::new(3, 3, 0, 0, 7),
+        read: Some(0),
+        write: None,
+        value: SysRegValue::Constant(0x14),
+    },
+    SysReg {
+        name: "CNTFRQ_EL0",
+        encoding: SysRegEncoding::new(3, 3, 14, 0, 0),
+        read: Some(0),
+        write: None,
+        value: SysRegValue::Constant(COUNTER_FREQUENCY),
+    },
+    SysReg {
+        // The physical counter has no offset from the virtual one
+        name: "CNTPCT_EL0",
+        encoding: SysRegEncoding::new(3, 3, 14, 0, 1),
+        read: Some(0),
+        write: None,
+        value: SysRegValue::Hooks {
+            read: read_counter,
+            write: read_only,
+        },
+    },
+    SysReg {
+        name: "CNTVCT_EL0",
+        encoding: SysRegEncoding::new(3, 3, 14, 0, 2),
+        read: Some(0),
+        write: None,
+        value: SysRegValue::Hooks {
+            read: read_counter,
+            write: read_only,
+        },
+    },
+    SysReg {
+        // A Cortex-A72 r0p3
+        name: "MIDR_EL1",
+        encoding: SysRegEncoding::new(3, 0, 0, 0, 0),
+        read: Some(0),
+        write: None,
+        value: SysRegValue::Constant(0x410f_d083),
+    },
+    SysReg {
+        name: "MPIDR_EL1",
+        encoding: SysRegEncoding::new(3, 0, 0, 0, 5),
+        read: Some(1),
+        write: None,
+        value: SysRegValue::Constant(0x8000_0000),
+    },
+    SysReg {
+        // EL0 to EL3 in AArch64 only, FP and AdvSIMD without half precision
+        name: "ID_AA64PFR0_EL1",
+        encoding: SysRegEncoding::new(3, 0, 0, 4, 0),
+        read: Some(0),
+        write: None,
+        value: SysRegValue::Constant(0x1111),
+    },
+    SysReg {
+        // The LSE atomics
+        name: "ID_AA64ISAR0_EL1",
+        encoding: SysRegEncoding::new(3, 0, 0, 6, 0),
+        read: Some(0),
+        write: None,
+        value: SysRegValue::Constant(0x0020_0000),
+    },
+    SysReg {
+        // 48-bit physical addresses, 16-bit ASIDs and the 4K, 16K and 64K granules
+        name: "ID_AA64MMFR0_EL1",
+        encoding: SysRegEncoding::new(3, 0, 0, 7, 0),
+        read: Some(0),
+        write: None,
+        value: SysRegValue::Constant(0x0010_0025),
+    },
+    SysReg {
+        name: "SCTLR_EL1",
+        encoding: SysRegEncoding::new(3, 0, 1, 0, 0),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::SctlrEl1,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "CPACR_EL1",
+        encoding: SysRegEncoding::new(3, 0, 1, 0, 2),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::CpacrEl1,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "TTBR0_EL1",
+        encoding: SysRegEncoding::new(3, 0, 2, 0, 0),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::Ttbr0El1,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "TTBR1_EL1",
+        encoding: SysRegEncoding::new(3, 0, 2, 0, 1),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::Ttbr1El1,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "TCR_EL1",
+        encoding: SysRegEncoding::new(3, 0, 2, 0, 2),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::TcrEl1,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "SPSR_EL1",
+        encoding: SysRegEncoding::new(3, 0, 4, 0, 0),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::SpsrEl1,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "ELR_EL1",
+        encoding: SysRegEncoding::new(3, 0, 4, 0, 1),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::ElrEl1,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "SP_EL0",
+        encoding: SysRegEncoding::new(3, 0, 4, 1, 0),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Hooks {
+            read: read_sp_el0,
+            write: write_sp_el0,
+        },
+    },
+    SysReg {
+        name: "SPSel",
+        encoding: SysRegEncoding::new(3, 0, 4, 2, 0),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Hooks {
+            read: read_spsel,
+            write: write_spsel,
+        },
+    },
+    SysReg {
+        name: "CurrentEL",
+        encoding: SysRegEncoding::new(3, 0, 4, 2, 2),
+        read: Some(1),
+        write: None,
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::Pstate,
+            mask: 0b11 << pstate::EL,
+        },
+    },
+    SysReg {
+        name: "PAN",
+        encoding: SysRegEncoding::new(3, 0, 4, 2, 3),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::Pstate,
+            mask: 1 << pstate::PAN,
+        },
+    },
+    SysReg {
+        name: "UAO",
+        encoding: SysRegEncoding::new(3, 0, 4, 2, 4),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::Pstate,
+            mask: 1 << pstate::UAO,
+        },
+    },
+    SysReg {
+        name: "ESR_EL1",
+        encoding: SysRegEncoding::new(3, 0, 5, 2, 0),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::EsrEl1,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "FAR_EL1",
+        encoding: SysRegEncoding::new(3, 0, 6, 0, 0),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::FarEl1,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "MAIR_EL1",
+        encoding: SysRegEncoding::new(3, 0, 10, 2, 0),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::MairEl1,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        // The vector table is aligned to 2KB
+        name: "VBAR_EL1",
+        encoding: SysRegEncoding::new(3, 0, 12, 0, 0),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::VbarEl1,
+            mask: !0x7ff,
+        },
+    },
+    SysReg {
+        name: "CONTEXTIDR_EL1",
+        encoding: SysRegEncoding::new(3, 0, 13, 0, 1),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::ContextidrEl1,
+            mask: 0xffff_ffff,
+        },
+    },
+    SysReg {
+        name: "TPIDR_EL1",
+        encoding: SysRegEncoding::new(3, 0, 13, 0, 4),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::TpidrEl1,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "CNTKCTL_EL1",
+        encoding: SysRegEncoding::new(3, 0, 14, 1, 0),
+        read: Some(1),
+        write: Some(1),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::CntkctlEl1,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "HCR_EL2",
+        encoding: SysRegEncoding::new(3, 4, 1, 1, 0),
+        read: Some(2),
+        write: Some(2),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::HcrEl2,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "SPSR_EL2",
+        encoding: SysRegEncoding::new(3, 4, 4, 0, 0),
+        read: Some(2),
+        write: Some(2),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::SpsrEl2,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "ELR_EL2",
+        encoding: SysRegEncoding::new(3, 4, 4, 0, 1),
+        read: Some(2),
+        write: Some(2),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::ElrEl2,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        // Never the selected stack at EL2 and above, so always in its bank
+        name: "SP_EL1",
+        encoding: SysRegEncoding::new(3, 4, 4, 1, 0),
+        read: Some(2),
+        write: Some(2),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::SpEl1,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "ESR_EL2",
+        encoding: SysRegEncoding::new(3, 4, 5, 2, 0),
+        read: Some(2),
+        write: Some(2),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::EsrEl2,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "FAR_EL2",
+        encoding: SysRegEncoding::new(3, 4, 6, 0, 0),
+        read: Some(2),
+        write: Some(2),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::FarEl2,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "VBAR_EL2",
+        encoding: SysRegEncoding::new(3, 4, 12, 0, 0),
+        read: Some(2),
+        write: Some(2),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::VbarEl2,
+            mask: !0x7ff,
+        },
+    },
+    SysReg {
+        name: "SCR_EL3",
+        encoding: SysRegEncoding::new(3, 6, 1, 1, 0),
+        read: Some(3),
+        write: Some(3),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::ScrEl3,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "SPSR_EL3",
+        encoding: SysRegEncoding::new(3, 6, 4, 0, 0),
+        read: Some(3),
+        write: Some(3),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::SpsrEl3,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "ELR_EL3",
+        encoding: SysRegEncoding::new(3, 6, 4, 0, 1),
+        read: Some(3),
+        write: Some(3),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::ElrEl3,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "SP_EL2",
+        encoding: SysRegEncoding::new(3, 6, 4, 1, 0),
+        read: Some(3),
+        write: Some(3),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::SpEl2,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "ESR_EL3",
+        encoding: SysRegEncoding::new(3, 6, 5, 2, 0),
+        read: Some(3),
+        write: Some(3),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::EsrEl3,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "FAR_EL3",
+        encoding: SysRegEncoding::new(3, 6, 6, 0, 0),
+        read: Some(3),
+        write: Some(3),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::FarEl3,
+            mask: u64::MAX,
+        },
+    },
+    SysReg {
+        name: "VBAR_EL3",
+        encoding: SysRegEncoding::new(3, 6, 12, 0, 0),
+        read: Some(3),
+        write: Some(3),
+        value: SysRegValue::Field {
+            id: AArch64RegisterId::VbarEl3,
+            mask: !0x7ff,
+        },
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn encodings_and_names_are_unique() {
+        let encodings: HashSet<_> = SYSREGS.iter().map(|sysreg| sysreg.encoding).collect();
+        let names: HashSet<_> = SYSREGS.iter().map(|sysreg| sysreg.name).collect();
+
+        assert_eq!(encodings.len(), SYSREGS.len());
+        assert_eq!(names.len(), SYSREGS.len());
+    }
+
+    #[test]
+    fn constants_are_read_only() {
+        for sysreg in SYSREGS {
+            if matches!(sysreg.value, SysRegValue::Constant(_)) {
+                assert_eq!(sysreg.write, None, "{}", sysreg.name);
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! A cpu running lifted instructions on the rustjit backend, for the unit tests of the lifter.
+use core::{
+    ir::{BasicBlock, BasicBlockTerminator},
+    Architecture, Instruction, Interrupt, RegisterFileDesc, RegisterId,
+};
+
+use device::devices::{Memory, SoftMmu};
+use execution::codegen::{
+    rustjit::{RustjitCodegen, RustjitContext},
+    Codegen, ExecOutcome, Executable, ExecutionContext,
+};
+
+use super::{AArch64Architecture, AArch64Inst, AArch64RegisterId};
+
+pub struct TestCpu {
+    context: RustjitContext,
+    desc: RegisterFileDesc,
+    pub mmu: SoftMmu,
+}
+
+impl TestCpu {
+    pub fn new() -> Self {
+        Self {
+            context: RustjitCodegen::new_context::<AArch64Architecture>(),
+            desc: AArch64Architecture::get_register_file_desc(),
+            mmu: SoftMmu::new(),
+        }
+    }
+
+    /// Write the low bytes of `value` to the register, its view semantics are not applied.
+    pub fn set(&mut self, id: AArch64RegisterId, value: u64) {
+        self.set_u128(id, value as u128)
+    }
+
+    pub fn get(&self, id: AArch64RegisterId) -> u64 {
+        self.get_u128(id) as u64
+    }
+
+    pub fn set_u128(&mut self, id: AArch64RegisterId, value: u128) {
+        let desc = self.desc.register(id.raw());
+        let bytes = &value.to_le_bytes()[..desc.size];
+        self.context.registers_mut()[desc.offset..desc.offset + desc.size].copy_from_slice(bytes);
+    }
+
+    /// The raw register file.
+    pub fn registers(&self) -> &[u8] {
+        self.context.registers()
+    }
+
+    pub fn get_u128(&self, id: AArch64RegisterId) -> u128 {
+        let desc = self.desc.register(id.raw());
+        let mut bytes = [0; 16];
+        bytes[..desc.size]
+            .copy_from_slice(&self.context.registers()[desc.offset..desc.offset + desc.size]);
+        u128::from_le_bytes(bytes)
+    }
+
+    /// Map zeroed memory of `size` bytes at `addr`.
+    pub fn map_memory(&mut self, addr: u64, size: u64) {
+        self.mmu.map(Memory::allocate(size as usize), addr, size);
+    }
+
+    pub fn write_memory(&mut self, addr: u64, bytes: &[u8]) {
+        unsafe { self.mmu.try_write_all_at(addr, bytes) }.expect("unmapped test memory");
+    }
+
+    pub fn read_memory(&self, addr: u64, len: usize) -> Vec<u8> {
+        let mut bytes = vec![0; len];
+        unsafe { self.mmu.try_read_all_at(addr, &mut bytes) }.expect("unmapped test memory");
+        bytes
+    }
+
+    /// Lift the encoded instructions into a block and run it, returns the raised interrupt. A
+    /// branch ends the block, it can only be the last instruction.
+    pub fn run(&mut self, insts: &[u32]) -> Option<Interrupt> {
+        let mut bb = BasicBlock::new(self.context.pc());
+        for inst in insts {
+            AArch64Inst::decode(&inst.to_le_bytes()).compile_to_ir(&mut bb);
+        }
+        if bb.terminator() == BasicBlockTerminator::None {
+            bb.set_terminator(BasicBlockTerminator::Next);
+        }
+
+        let executable = RustjitCodegen.compile::<AArch64Architecture>(bb);
+        match unsafe { executable.execute(&mut self.context, &self.mmu) } {
+            ExecOutcome::Completed => None,
+            ExecOutcome::Interrupted { interrupt, .. } => Some(interrupt),
+        }
+    }
+
+    /// Take the interrupt like a vcpu does, running the block of
+    /// [`AArch64Architecture::interrupt`].
+    pub fn take(&mut self, interrupt: Interrupt) {
+        let bb = AArch64Architecture::interrupt(interrupt);
+        let executable = RustjitCodegen.compile::<AArch64Architecture>(bb);
+        let outcome = unsafe { executable.execute(&mut self.context, &self.mmu) };
+        assert!(matches!(outcome, ExecOutcome::Completed));
+    }
+}
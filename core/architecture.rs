@@ -1,7 +1,8 @@
 use std::fmt::Debug;
 
 use crate::{
-    ir::BasicBlock, Instruction, Interrupt, Primitive, Register, RegisterFileDesc, RegisterId,
+    ir::BasicBlock, Abort, AccessType, Instruction, Interrupt, PageTranslation, Primitive,
+    Register, RegisterFileDesc, RegisterId, TranslationRegime,
 };
 
 // The representation of an architecture
@@ -30,4 +31,24 @@ pub trait Architecture: Default + Clone + Copy + PartialEq + Eq {
     /// Get the basic block that represents the behavior of an interrupt.
     /// The executor will jump to this basic block when an interrupt is raised.
     fn interrupt(interrupt: Interrupt) -> BasicBlock;
+
+    /// Get the translation regime of the state in `registers`, the raw register file.
+    /// `None` if virtual addresses are physical addresses.
+    fn translation_regime(registers: &[u8]) -> Option<TranslationRegime>;
+
+    /// Get the regime of the unprivileged accesses made in `regime`, they have the permissions
+    /// of the least privileged state (e.g. LDTR and STTR on AArch64).
+    fn unprivileged_regime(regime: TranslationRegime) -> TranslationRegime;
+
+    /// Translate the virtual address of an access made in `regime`, the translation regime of
+    /// the state of `registers` or its unprivileged regime. The translation tables are read with
+    /// `read`, which returns the 64-bit descriptor at a physical address or `None` if nothing is
+    /// mapped there.
+    fn translate(
+        registers: &[u8],
+        regime: TranslationRegime,
+        address: u64,
+        access: AccessType,
+        read: &mut dyn FnMut(u64) -> Option<u64>,
+    ) -> Result<PageTranslation, Abort>;
 }
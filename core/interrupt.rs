@@ -1,9 +1,65 @@
 /// Interrupts that can be raised by the runtime.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Interrupt {
-    Aborts(u64),
+    Aborts(Abort),
     Reset(u64),
-    Exception(u64),
+    Exception(Exception),
     Interrupt(u64),
     Yield,
     WaitForInterrupt,
 }
+
+/// A memory access that could not be completed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Abort {
+    /// The address that caused the abort.
+    pub fault_address: u64,
+    pub access: AccessType,
+    pub kind: AbortKind,
+    /// Architecture specific syndrome of the abort.
+    pub syndrome: u64,
+}
+
+/// Why a memory access aborted.
+///
+/// The translation faults carry the level of the translation tables they were found at.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AbortKind {
+    /// Nothing is mapped at the address.
+    Unmapped,
+    /// The address of an atomic or device memory access is not aligned to its size.
+    Unaligned,
+    /// The virtual address has no translation.
+    Translation { level: u8 },
+    /// The translation has not been accessed yet, the guest tracks accesses with these faults.
+    AccessFlag { level: u8 },
+    /// The translation doesn't allow the access.
+    Permission { level: u8 },
+    /// A translation table could not be read.
+    TableWalk { level: u8 },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AccessType {
+    Read,
+    Write,
+    Execute,
+}
+
+/// A synchronous exception raised by an instruction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Exception {
+    pub kind: ExceptionKind,
+    /// Architecture specific syndrome of the exception.
+    pub syndrome: u64,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ExceptionKind {
+    /// A system call. Holds the syscall number encoded in the instruction.
+    SupervisorCall(u64),
+    HypervisorCall(u64),
+    SecureMonitorCall(u64),
+    Breakpoint(u64),
+    Undefined,
+}
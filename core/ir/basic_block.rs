@@ -21,6 +21,10 @@ impl BasicBlock {
         }
     }
 
+    pub fn addr(&self) -> u64 {
+        self.addr
+    }
+
     pub fn inst(&self) -> &[IrInst] {
         &self.statements
     }
@@ -29,6 +33,11 @@ impl BasicBlock {
         self.statements.push(statement);
     }
 
+    /// Remove the instruction at `idx`, the variables of the block stay allocated.
+    pub fn remove_inst(&mut self, idx: usize) -> IrInst {
+        self.statements.remove(idx)
+    }
+
     pub fn terminator(&self) -> BasicBlockTerminator {
         self.terminator
     }
@@ -37,6 +46,11 @@ impl BasicBlock {
         self.terminator = terminator;
     }
 
+    /// The number of variables allocated in the block, their ids are `0..variable_count`.
+    pub fn variable_count(&self) -> usize {
+        self.variable_count
+    }
+
     pub fn new_variable(&mut self, ty: IrType) -> IrValue {
         let variable = IrValue::Variable(ty, self.variable_count);
         self.variable_count += 1;
@@ -1,3 +1,5 @@
+use crate::Interrupt;
+
 use super::{IrType, IrValue, TypeOf};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -72,6 +74,17 @@ pub enum IrInst {
         dst: IrValue,
         src: IrValue,
     },
+    /// Load from the address `src` with the permissions of the least privileged state (see
+    /// [`Architecture::unprivileged_regime`](crate::Architecture::unprivileged_regime)).
+    LoadUnprivileged {
+        dst: IrValue,
+        src: IrValue,
+    },
+    /// Store `src` to the address `dst` with the permissions of the least privileged state.
+    StoreUnprivileged {
+        dst: IrValue,
+        src: IrValue,
+    },
     ZextCast {
         dst: IrValue,
         src: IrValue,
@@ -80,9 +93,90 @@ pub enum IrInst {
         dst: IrValue,
         src: IrValue,
     },
+    /// Raise the interrupt if `cond` is true
+    Interrupt {
+        cond: IrValue,
+        interrupt: Interrupt,
+    },
+    /// Order the memory accesses before and after the fence as seen by other cpus
+    Fence {
+        ordering: IrOrdering,
+    },
+    /// Atomically replace the value at the address `addr` with `op(value, src)`, `dst`
+    /// receives the old value.
+    ///
+    /// The atomic accesses must be aligned to their size, they abort otherwise.
+    AtomicRmw {
+        op: AtomicOp,
+        dst: IrValue,
+        addr: IrValue,
+        src: IrValue,
+    },
+    /// Atomically replace the value at the address `addr` with `src` if it is equal to
+    /// `expected`, `dst` receives the old value.
+    CompareExchange {
+        dst: IrValue,
+        addr: IrValue,
+        expected: IrValue,
+        src: IrValue,
+    },
+    /// Load from the address `src` and reserve it for a `StoreExclusive` of the same size.
+    ///
+    /// A cpu holds a single reservation, which is lost on a store to its address by any cpu.
+    /// Exclusive accesses are atomic accesses.
+    LoadExclusive {
+        dst: IrValue,
+        src: IrValue,
+    },
+    /// Store `src` to the address `dst` if the reservation of the cpu is still held on it,
+    /// the Bool `status` is true if the store has been made. The reservation is released.
+    StoreExclusive {
+        dst: IrValue,
+        src: IrValue,
+        status: IrValue,
+    },
+    /// Release the reservation of the cpu.
+    ClearExclusive,
+    /// Drop the cached translations of the U64 virtual address `addr` in the address space of
+    /// the U64 `asid`, `None` stands for every address or every address space. The global
+    /// translations of an address are dropped whatever the address space, the invalidation of
+    /// a whole address space leaves them.
+    ///
+    /// A `broadcast` invalidation also drops the cached translations of the other cpus.
+    InvalidateTlb {
+        addr: Option<IrValue>,
+        asid: Option<IrValue>,
+        broadcast: bool,
+    },
     Intrinsic(IrIntrinsic),
 }
 
+/// The operations of [`IrInst::AtomicRmw`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AtomicOp {
+    /// Replace the value with the source.
+    Swap,
+    Add,
+    And,
+    Or,
+    Xor,
+    /// The larger of the value and the source, signed if their type is.
+    Max,
+    /// The smaller of the value and the source, signed if their type is.
+    Min,
+}
+
+/// The memory ordering of a fence.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum IrOrdering {
+    /// Later accesses can't be reordered before the preceding loads.
+    Acquire,
+    /// Earlier accesses can't be reordered after the following stores.
+    Release,
+    /// Full barrier.
+    SeqCst,
+}
+
 impl TypeOf for IrInst {
     fn ty(&self) -> IrType {
         match self {
@@ -101,18 +195,174 @@ impl TypeOf for IrInst {
             Self::Assign { dst, .. } => dst.ty(),
             Self::Load { dst, .. } => dst.ty(),
             Self::Store { dst, .. } => dst.ty(),
+            Self::LoadUnprivileged { dst, .. } => dst.ty(),
+            Self::StoreUnprivileged { dst, .. } => dst.ty(),
             Self::ZextCast { dst, .. } => dst.ty(),
             Self::SextCast { dst, .. } => dst.ty(),
+            Self::Interrupt { .. } => IrType::Void,
+            Self::Fence { .. } => IrType::Void,
+            Self::AtomicRmw { dst, .. } => dst.ty(),
+            Self::CompareExchange { dst, .. } => dst.ty(),
+            Self::LoadExclusive { dst, .. } => dst.ty(),
+            Self::StoreExclusive { status, .. } => status.ty(),
+            Self::ClearExclusive => IrType::Void,
+            Self::InvalidateTlb { .. } => IrType::Void,
             Self::Intrinsic(intrinsic) => intrinsic.ty(),
         }
     }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub enum IrIntrinsic {}
+pub enum IrIntrinsic {
+    /// An IEEE 754 operation, see [`FloatOp`].
+    ///
+    /// Floats are carried as their raw bits in unsigned integers: U16 for half, U32 for single
+    /// and U64 for double precision. `control` is a U64 of [`float_control`] bits and the
+    /// exceptions raised by the operation are written to the U64 `status` as [`float_status`]
+    /// bits.
+    Float {
+        op: FloatOp,
+        dst: IrValue,
+        srcs: Vec<IrValue>,
+        control: IrValue,
+        status: IrValue,
+    },
+}
 
 impl TypeOf for IrIntrinsic {
     fn ty(&self) -> IrType {
-        todo!()
+        match self {
+            Self::Float { dst, .. } => dst.ty(),
+        }
+    }
+}
+
+/// The operations of [`IrIntrinsic::Float`].
+///
+/// The sources and the destination share their format unless stated otherwise. Subnormal
+/// inputs are flushed before the operation and subnormal results after it when
+/// [`float_control::FLUSH_TO_ZERO`] is set, tininess is detected before rounding.
+///
+/// A NaN result is the default NaN (positive, quiet, with an empty payload) when
+/// [`float_control::DEFAULT_NAN`] is set or the NaN is created by the operation. Otherwise it
+/// is the first signaling NaN of the sources made quiet, or else their first quiet NaN.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FloatOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    /// `srcs[0] + srcs[1] * srcs[2]` rounded once.
+    MulAdd,
+    Sqrt,
+    /// The larger source, NaNs propagate and -0 is smaller than +0.
+    Max,
+    /// The smaller source, NaNs propagate and -0 is smaller than +0.
+    Min,
+    /// Like `Max` but a quiet NaN loses against a number.
+    MaxNum,
+    /// Like `Min` but a quiet NaN loses against a number.
+    MinNum,
+    /// Round to an integral value, inexact is only raised when `exact` is set.
+    RoundInt {
+        rounding: Option<FloatRounding>,
+        exact: bool,
+    },
+    /// Compare two sources, the integer result is 0 when they are equal, 1 when the first
+    /// is less, 2 when it is greater and 3 when they are unordered. Quiet NaNs only raise
+    /// invalid when `signaling` is set.
+    Compare {
+        signaling: bool,
+    },
+    /// Convert to the format of the destination, with `rounding` or else the mode of
+    /// `control`.
+    Convert {
+        rounding: Option<FloatRounding>,
+    },
+    /// Convert to an integer with `fbits` fraction bits, the width of the destination.
+    /// Out of range values and NaNs raise invalid and saturate, NaNs to zero.
+    ToInt {
+        signed: bool,
+        fbits: u8,
+        rounding: Option<FloatRounding>,
+    },
+    /// Convert from an integer with `fbits` fraction bits, the width of the source.
+    FromInt {
+        signed: bool,
+        fbits: u8,
+    },
+    /// Like `Mul` but an infinity times a zero is 2 with the sign of the product and raises
+    /// nothing.
+    MulExtended,
+    /// `2 + srcs[0] * srcs[1]` rounded once, an infinity times a zero is 2 and raises nothing.
+    RecipStep,
+    /// `(3 + srcs[0] * srcs[1]) / 2` rounded once, an infinity times a zero is 1.5 and raises
+    /// nothing.
+    RSqrtStep,
+    /// An estimate of `1 / srcs[0]` with 8 significant bits, the FRECPE of Arm. Results too
+    /// small for a normal number are zero when flushing to zero.
+    RecipEstimate,
+    /// An estimate of `1 / sqrt(srcs[0])` with 8 significant bits, the FRSQRTE of Arm.
+    RSqrtEstimate,
+}
+
+impl FloatOp {
+    /// The number of sources of the operation.
+    pub fn arity(self) -> usize {
+        match self {
+            Self::MulAdd => 3,
+            Self::Add
+            | Self::Sub
+            | Self::Mul
+            | Self::Div
+            | Self::Max
+            | Self::Min
+            | Self::MaxNum
+            | Self::MinNum
+            | Self::Compare { .. }
+            | Self::MulExtended
+            | Self::RecipStep
+            | Self::RSqrtStep => 2,
+            Self::Sqrt
+            | Self::RoundInt { .. }
+            | Self::Convert { .. }
+            | Self::ToInt { .. }
+            | Self::FromInt { .. }
+            | Self::RecipEstimate
+            | Self::RSqrtEstimate => 1,
+        }
     }
 }
+
+/// A rounding mode, the modes that fit in [`float_control::ROUNDING`] come first.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FloatRounding {
+    TiesToEven = 0,
+    TowardPositive = 1,
+    TowardNegative = 2,
+    TowardZero = 3,
+    TiesToAway = 4,
+    /// Truncate and set the lowest bit when inexact, overflows to the largest finite number.
+    ToOdd = 5,
+}
+
+/// Bit positions in the `control` operand of float intrinsics.
+pub mod float_control {
+    /// The rounding mode of the operations without their own, two bits
+    pub const ROUNDING: u64 = 0;
+    /// Flush subnormal inputs and results to zero
+    pub const FLUSH_TO_ZERO: u64 = 2;
+    /// Replace every NaN result by the default NaN
+    pub const DEFAULT_NAN: u64 = 3;
+}
+
+/// Bit positions in the `status` result of float intrinsics.
+pub mod float_status {
+    pub const INVALID: u64 = 0;
+    pub const DIVIDE_BY_ZERO: u64 = 1;
+    pub const OVERFLOW: u64 = 2;
+    pub const UNDERFLOW: u64 = 3;
+    pub const INEXACT: u64 = 4;
+    /// A subnormal input was flushed to zero
+    pub const INPUT_DENORMAL: u64 = 7;
+}
@@ -35,9 +35,22 @@ impl IrType {
             IrType::F32 => 4,
             IrType::F64 => 8,
 
-            IrType::Vector(_, _) => 0,
+            IrType::Vector(elem, len) => elem.element_type().size_in_bytes() * len as usize,
         }
     }
+
+    /// Returns true for the signed integer types.
+    pub const fn is_signed(self) -> bool {
+        matches!(
+            self,
+            IrType::I8 | IrType::I16 | IrType::I32 | IrType::I64 | IrType::I128
+        )
+    }
+
+    /// Returns true for the floating point types.
+    pub const fn is_float(self) -> bool {
+        matches!(self, IrType::F32 | IrType::F64)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -56,6 +69,26 @@ pub enum VecTy {
     F64,
 }
 
+impl VecTy {
+    /// The type of a single lane.
+    pub const fn element_type(self) -> IrType {
+        match self {
+            VecTy::I8 => IrType::I8,
+            VecTy::I16 => IrType::I16,
+            VecTy::I32 => IrType::I32,
+            VecTy::I64 => IrType::I64,
+            VecTy::I128 => IrType::I128,
+            VecTy::U8 => IrType::U8,
+            VecTy::U16 => IrType::U16,
+            VecTy::U32 => IrType::U32,
+            VecTy::U64 => IrType::U64,
+            VecTy::U128 => IrType::U128,
+            VecTy::F32 => IrType::F32,
+            VecTy::F64 => IrType::F64,
+        }
+    }
+}
+
 pub trait TypeOf {
     fn ty(&self) -> IrType;
 }
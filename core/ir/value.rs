@@ -30,6 +30,7 @@ pub enum IrConstant {
     I16(i16),
     I32(i32),
     I64(i64),
+    Bool(bool),
 }
 
 impl IrConstant {
@@ -43,9 +44,41 @@ impl IrConstant {
             IrType::I16 => IrConstant::I16(value.to_i16().unwrap()),
             IrType::I32 => IrConstant::I32(value.to_i32().unwrap()),
             IrType::I64 => IrConstant::I64(value.to_i64().unwrap()),
+            IrType::Bool => IrConstant::Bool(!value.is_zero()),
             _ => unreachable!(),
         }
     }
+
+    /// Create a constant from its raw bits, the bits above the type are ignored.
+    pub fn from_bits(ty: IrType, bits: u128) -> Self {
+        match ty {
+            IrType::U8 => IrConstant::U8(bits as u8),
+            IrType::U16 => IrConstant::U16(bits as u16),
+            IrType::U32 => IrConstant::U32(bits as u32),
+            IrType::U64 => IrConstant::U64(bits as u64),
+            IrType::I8 => IrConstant::I8(bits as i8),
+            IrType::I16 => IrConstant::I16(bits as i16),
+            IrType::I32 => IrConstant::I32(bits as i32),
+            IrType::I64 => IrConstant::I64(bits as i64),
+            IrType::Bool => IrConstant::Bool(bits & 1 != 0),
+            _ => unreachable!(),
+        }
+    }
+
+    /// The raw bits of the constant, zero extended.
+    pub fn to_bits(&self) -> u128 {
+        match *self {
+            IrConstant::U8(value) => value as u128,
+            IrConstant::U16(value) => value as u128,
+            IrConstant::U32(value) => value as u128,
+            IrConstant::U64(value) => value as u128,
+            IrConstant::I8(value) => value as u8 as u128,
+            IrConstant::I16(value) => value as u16 as u128,
+            IrConstant::I32(value) => value as u32 as u128,
+            IrConstant::I64(value) => value as u64 as u128,
+            IrConstant::Bool(value) => value as u128,
+        }
+    }
 }
 
 impl TypeOf for IrConstant {
@@ -59,6 +92,7 @@ impl TypeOf for IrConstant {
             IrConstant::I16(_) => IrType::I16,
             IrConstant::I32(_) => IrType::I32,
             IrConstant::I64(_) => IrType::I64,
+            IrConstant::Bool(_) => IrType::Bool,
         }
     }
 }
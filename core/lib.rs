@@ -10,4 +10,6 @@ mod register_file;
 pub use register_file::*;
 mod interrupt;
 pub use interrupt::*;
+mod translation;
+pub use translation::*;
 pub mod ir;
@@ -16,11 +16,11 @@ pub trait RegisterId: Copy + Clone + PartialEq + Eq + Sized + Hash {
     fn raw(&self) -> RawRegisterId;
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct RawRegisterId(usize);
 
 impl RawRegisterId {
-    pub fn new(id: usize) -> Self {
+    pub const fn new(id: usize) -> Self {
         Self(id)
     }
 }
@@ -1,29 +1,141 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::Range};
 
 use crate::RawRegisterId;
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Default)]
 pub struct RegisterFileDesc {
     register: HashMap<RawRegisterId, RegisterDesc>,
 }
 
 impl RegisterFileDesc {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a register right after the registers added so far.
+    pub fn add_register(&mut self, id: RawRegisterId, size: usize, is_read_only: bool) {
+        let offset = self.total_size();
+        self.register.insert(
+            id,
+            RegisterDesc {
+                is_read_only,
+                size,
+                offset,
+                parent: None,
+                write: ViewWrite::Merge,
+                is_zero: false,
+            },
+        );
+    }
+
+    /// Add a register without storage, it reads as zero and ignores writes.
+    pub fn add_zero_register(&mut self, id: RawRegisterId, size: usize) {
+        self.register.insert(
+            id,
+            RegisterDesc {
+                is_read_only: false,
+                size,
+                offset: 0,
+                parent: None,
+                write: ViewWrite::Merge,
+                is_zero: true,
+            },
+        );
+    }
+
+    /// Add a register that aliases `size` bytes of `parent` starting at `offset`.
+    ///
+    /// A view of a view aliases the storage of the outermost register, the view is read only if
+    /// its parent is and a view of a zero register is a zero register.
+    pub fn add_view(
+        &mut self,
+        id: RawRegisterId,
+        parent: RawRegisterId,
+        offset: usize,
+        size: usize,
+        write: ViewWrite,
+    ) {
+        let parent_desc = self.register(parent);
+        assert!(
+            offset + size <= parent_desc.size,
+            "view is out of its parent"
+        );
+
+        let desc = RegisterDesc {
+            is_read_only: parent_desc.is_read_only,
+            size,
+            offset: parent_desc.offset + offset,
+            parent: Some(parent_desc.parent.unwrap_or(parent)),
+            write,
+            is_zero: parent_desc.is_zero,
+        };
+        self.register.insert(id, desc);
+    }
+
+    /// Iterate over every register, in no particular order.
+    pub fn registers(&self) -> impl Iterator<Item = (RawRegisterId, &RegisterDesc)> {
+        self.register.iter().map(|(id, desc)| (*id, desc))
+    }
+
+    /// Iterate over the registers that own their storage, in no particular order.
+    ///
+    /// They cover the register file without overlapping.
+    pub fn storage_registers(&self) -> impl Iterator<Item = (RawRegisterId, &RegisterDesc)> {
+        self.registers()
+            .filter(|(_, desc)| desc.parent.is_none() && !desc.is_zero)
+    }
+
     pub fn register(&self, id: RawRegisterId) -> &RegisterDesc {
         self.register.get(&id).unwrap()
     }
 
+    /// The bytes of the register file changed by a write to the register.
+    ///
+    /// The bytes outside of the register itself are zeroed by the write.
+    pub fn write_extent(&self, id: RawRegisterId) -> Range<usize> {
+        let desc = self.register(id);
+        if desc.is_zero {
+            return 0..0;
+        }
+
+        match (desc.parent, desc.write) {
+            (Some(parent), ViewWrite::ZeroExtend) => {
+                let parent = self.register(parent);
+                parent.offset..parent.offset + parent.size
+            }
+            _ => desc.offset..desc.offset + desc.size,
+        }
+    }
+
     pub fn total_size(&self) -> usize {
         self.register
             .values()
+            .filter(|r| !r.is_zero)
             .map(|r| r.offset + r.size)
             .max()
             .unwrap_or(0)
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct RegisterDesc {
     pub is_read_only: bool,
     pub size: usize,
+    /// The offset in the register file, a view points into the storage of its parent.
     pub offset: usize,
+    /// The register owning the storage of a view, `None` if the register has its own storage.
+    pub parent: Option<RawRegisterId>,
+    /// How a write through a view changes the rest of its parent.
+    pub write: ViewWrite,
+    /// The register has no storage, it reads as zero and ignores writes.
+    pub is_zero: bool,
+}
+
+/// How a write through a view changes the rest of its parent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ViewWrite {
+    /// The rest of the parent is unchanged.
+    Merge,
+    /// The rest of the parent is zeroed, like writing `W0` zero extends into `X0` on AArch64.
+    ZeroExtend,
 }
@@ -0,0 +1,48 @@
+use crate::AccessType;
+
+/// The state the translations of virtual addresses depend on, cached translations are only
+/// used in the regime they were made in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TranslationRegime {
+    /// The address space of the non-global translations.
+    pub asid: u16,
+    /// Tells apart the states that have different permissions on the same translations, e.g.
+    /// the privilege levels.
+    pub privilege: u8,
+}
+
+/// What a memory access may do with a page.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Permissions {
+    pub read: bool,
+    pub write: bool,
+    pub execute: bool,
+}
+
+impl Permissions {
+    /// Whether `access` is allowed.
+    pub fn allows(&self, access: AccessType) -> bool {
+        match access {
+            AccessType::Read => self.read,
+            AccessType::Write => self.write,
+            AccessType::Execute => self.execute,
+        }
+    }
+}
+
+/// The translation of the page holding a virtual address.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PageTranslation {
+    /// The virtual address of the page, aligned to its size.
+    pub virtual_address: u64,
+    /// The physical address of the page, aligned to its size.
+    pub physical_address: u64,
+    /// The log2 of the page size.
+    pub page_shift: u8,
+    /// The permissions in the regime the translation was made in.
+    pub permissions: Permissions,
+    /// The address space of the translation, `None` if it is global.
+    pub asid: Option<u16>,
+    /// Device memory, its accesses must be aligned to their size.
+    pub device: bool,
+}
@@ -1,3 +1,5 @@
+mod exclusive_monitor;
+pub use exclusive_monitor::*;
 mod memory;
 pub use memory::*;
 mod soft_mmu;
@@ -0,0 +1,264 @@
+use std::hint;
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The log2 of the size of a reservation granule.
+const GRANULE_BITS: u32 = 6;
+/// The number of slots, the granules are spread over them by their index.
+const SLOT_COUNT: usize = 4096;
+
+/// The slot is held by an access.
+const LOCKED: u64 = 1 << 0;
+/// A reservation is held on the slot.
+const RESERVED: u64 = 1 << 1;
+/// The generation of a slot is counted above the flags.
+const GENERATION: u64 = 1 << 2;
+
+/// The global exclusive monitor, shared by every cpu of a machine.
+///
+/// The memory is split into granules that are spread over a table of slots. A slot holds a
+/// generation that changes on every store to its granules while they are reserved. An
+/// exclusive load reserves its granule and keeps the generation, the exclusive store only
+/// succeeds if the generation hasn't changed. Granules sharing a slot break each other's
+/// reservations, the architectures allow exclusive stores to fail spuriously.
+///
+/// Every access takes the locks of its slots, so stores and atomic accesses to a granule are
+/// serialized.
+pub struct ExclusiveMonitor {
+    slots: Box<[AtomicU64]>,
+}
+
+/// A reservation made by an exclusive load, the local monitor of a cpu keeps it until the
+/// exclusive store.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Reservation {
+    addr: u64,
+    size: usize,
+    generation: u64,
+}
+
+impl Default for ExclusiveMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExclusiveMonitor {
+    pub fn new() -> Self {
+        Self {
+            slots: (0..SLOT_COUNT).map(|_| AtomicU64::new(0)).collect(),
+        }
+    }
+
+    /// Run `access` on the `size` bytes at `addr` while they are locked.
+    ///
+    /// `access` returns its result and whether it wrote to the memory, a write breaks the
+    /// reservations of the granules.
+    pub fn access<R>(&self, addr: u64, size: usize, access: impl FnOnce() -> (R, bool)) -> R {
+        let ranges = slot_ranges(addr, size);
+        for index in ranges.iter().cloned().flatten() {
+            self.lock(index);
+        }
+
+        let (result, written) = access();
+        for index in ranges.iter().cloned().flatten() {
+            self.unlock(index, written);
+        }
+
+        result
+    }
+
+    /// Run the exclusive load `load` of the `size` bytes at `addr` and reserve them.
+    ///
+    /// The access must not cross a granule, which aligned accesses of up to 64 bytes never do.
+    pub fn load_exclusive<R>(
+        &self,
+        addr: u64,
+        size: usize,
+        load: impl FnOnce() -> R,
+    ) -> (R, Reservation) {
+        let index = slot_index(addr);
+        let generation = self.lock(index) | RESERVED;
+
+        let result = load();
+        self.slots[index].store(generation, Ordering::Release);
+
+        let reservation = Reservation {
+            addr,
+            size,
+            generation,
+        };
+        (result, reservation)
+    }
+
+    /// Run the exclusive store `store` of the `size` bytes at `addr` if `reservation` is still
+    /// held on them. Returns `None` if the reservation has been lost, nothing is stored then.
+    pub fn store_exclusive<R>(
+        &self,
+        reservation: Reservation,
+        addr: u64,
+        size: usize,
+        store: impl FnOnce() -> R,
+    ) -> Option<R> {
+        if reservation.addr != addr || reservation.size != size {
+            return None;
+        }
+
+        let index = slot_index(addr);
+        let generation = self.lock(index);
+        if generation != reservation.generation {
+            self.unlock(index, false);
+            return None;
+        }
+
+        let result = store();
+        self.unlock(index, true);
+        Some(result)
+    }
+
+    /// Spin until the slot is unlocked and lock it, returns its value.
+    fn lock(&self, index: usize) -> u64 {
+        let slot = &self.slots[index];
+
+        loop {
+            let value = slot.load(Ordering::Relaxed);
+            if value & LOCKED == 0
+                && slot
+                    .compare_exchange_weak(
+                        value,
+                        value | LOCKED,
+                        Ordering::Acquire,
+                        Ordering::Relaxed,
+                    )
+                    .is_ok()
+            {
+                return value;
+            }
+            hint::spin_loop();
+        }
+    }
+
+    /// Unlock a slot held by the caller, starting a new generation if a reserved granule has
+    /// been written.
+    fn unlock(&self, index: usize, written: bool) {
+        let value = self.slots[index].load(Ordering::Relaxed) & !LOCKED;
+        let value = match written && value & RESERVED != 0 {
+            true => (value & !RESERVED).wrapping_add(GENERATION),
+            false => value,
+        };
+
+        self.slots[index].store(value, Ordering::Release);
+    }
+}
+
+fn slot_index(addr: u64) -> usize {
+    (addr >> GRANULE_BITS) as usize % SLOT_COUNT
+}
+
+/// The slots of the `size` bytes at `addr` as two ranges in ascending order, so that the
+/// locks are always taken in the same order.
+fn slot_ranges(addr: u64, size: usize) -> [Range<usize>; 2] {
+    let first = addr >> GRANULE_BITS;
+    let last = addr.wrapping_add(size.max(1) as u64 - 1) >> GRANULE_BITS;
+
+    let (start, end) = (slot_index(addr), slot_index(last << GRANULE_BITS));
+    if last.wrapping_sub(first) >= SLOT_COUNT as u64 - 1 {
+        [0..SLOT_COUNT, 0..0]
+    } else if start <= end {
+        [start..end + 1, 0..0]
+    } else {
+        [0..end + 1, start..SLOT_COUNT]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use super::*;
+
+    const ADDR: u64 = 0x1000;
+
+    fn reserve(monitor: &ExclusiveMonitor, addr: u64, size: usize) -> Reservation {
+        monitor.load_exclusive(addr, size, || ()).1
+    }
+
+    #[test]
+    fn stores_to_the_granule_break_the_reservation() {
+        let monitor = ExclusiveMonitor::new();
+
+        // Loads and the stores to other granules leave it
+        let reservation = reserve(&monitor, ADDR, 8);
+        monitor.access(ADDR, 8, || ((), false));
+        monitor.access(ADDR + 64, 8, || ((), true));
+        assert_eq!(monitor.store_exclusive(reservation, ADDR, 8, || 1), Some(1));
+
+        // A store of another cpu to any byte of the granule
+        let reservation = reserve(&monitor, ADDR, 8);
+        monitor.access(ADDR + 63, 1, || ((), true));
+        assert_eq!(monitor.store_exclusive(reservation, ADDR, 8, || 1), None);
+
+        // A store crossing into the granule
+        let reservation = reserve(&monitor, ADDR, 8);
+        monitor.access(ADDR - 4, 8, || ((), true));
+        assert_eq!(monitor.store_exclusive(reservation, ADDR, 8, || 1), None);
+
+        // An exclusive store of another cpu
+        let reservation = reserve(&monitor, ADDR, 8);
+        let other = reserve(&monitor, ADDR, 8);
+        assert_eq!(monitor.store_exclusive(other, ADDR, 8, || 2), Some(2));
+        assert_eq!(monitor.store_exclusive(reservation, ADDR, 8, || 1), None);
+    }
+
+    #[test]
+    fn exclusive_stores_need_the_reserved_address_and_size() {
+        let monitor = ExclusiveMonitor::new();
+
+        let reservation = reserve(&monitor, ADDR, 8);
+        assert_eq!(
+            monitor.store_exclusive(reservation, ADDR + 8, 8, || 1),
+            None
+        );
+        assert_eq!(monitor.store_exclusive(reservation, ADDR, 4, || 1), None);
+        assert_eq!(monitor.store_exclusive(reservation, ADDR, 16, || 1), None);
+
+        // The failed stores leave the reservation, the successful one uses it up
+        assert_eq!(monitor.store_exclusive(reservation, ADDR, 8, || 1), Some(1));
+        assert_eq!(monitor.store_exclusive(reservation, ADDR, 8, || 1), None);
+    }
+
+    #[test]
+    fn exclusive_increments_of_two_cpus_are_exact() {
+        const INCREMENTS: u64 = 20_000;
+        let monitor = Arc::new(ExclusiveMonitor::new());
+        let value = Arc::new(AtomicU64::new(0));
+
+        let threads: Vec<_> = (0..2)
+            .map(|_| {
+                let (monitor, value) = (monitor.clone(), value.clone());
+                thread::spawn(move || {
+                    for _ in 0..INCREMENTS {
+                        // The load and the store are separate, only the monitor keeps the
+                        // increments from being lost
+                        loop {
+                            let (old, reservation) =
+                                monitor.load_exclusive(ADDR, 8, || value.load(Ordering::Relaxed));
+                            let stored = monitor.store_exclusive(reservation, ADDR, 8, || {
+                                value.store(old + 1, Ordering::Relaxed)
+                            });
+                            if stored.is_some() {
+                                break;
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(value.load(Ordering::Relaxed), 2 * INCREMENTS);
+    }
+}
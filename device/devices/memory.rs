@@ -1,37 +1,120 @@
-use std::cell::UnsafeCell;
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use crate::IoDevice;
 
 /// A simple memory device.
+///
+/// The memory is shared by every cpu of a machine like a real RAM, so it is kept in atomic
+/// little-endian 64-bit words. An access within a word is single-copy atomic, larger accesses
+/// are split at the word boundaries. Ordering between cpus is up to the guest (barriers and
+/// atomic instructions), the words are accessed with relaxed ordering.
 pub struct Memory {
-    mem: Arc<UnsafeCell<Box<[u8]>>>,
+    words: Arc<[AtomicU64]>,
+    size: usize,
 }
 
 impl Memory {
     pub fn allocate(size: usize) -> Self {
         Self {
-            mem: Arc::new(UnsafeCell::new(vec![0; size].into_boxed_slice())),
+            words: (0..size.div_ceil(8)).map(|_| AtomicU64::new(0)).collect(),
+            size,
         }
     }
 
-    unsafe fn view(&self) -> &mut [u8] {
-        &mut *self.mem.get()
+    /// Call `f` with each word overlapping the `len` bytes at `offset`, the index of its first
+    /// byte in those and the range of its bytes in the word.
+    fn for_each_word(
+        &self,
+        offset: usize,
+        len: usize,
+        mut f: impl FnMut(&AtomicU64, usize, Range<usize>),
+    ) {
+        let mut done = 0;
+        while done < len {
+            let address = offset + done;
+            let start = address % 8;
+            let end = (start + len - done).min(8);
+            f(&self.words[address / 8], done, start..end);
+            done += end - start;
+        }
     }
 }
 
 impl IoDevice for Memory {
     unsafe fn read_at(&self, offset: u64, buf: &mut [u8]) -> usize {
-        let mem = self.view();
-        let len = buf.len().min(mem.len() - offset as usize);
-        buf[..len].copy_from_slice(&mem[offset as usize..offset as usize + len]);
+        let len = buf.len().min(self.size - offset as usize);
+        self.for_each_word(offset as usize, len, |word, done, bytes| {
+            let value = word.load(Ordering::Relaxed).to_le_bytes();
+            buf[done..done + bytes.len()].copy_from_slice(&value[bytes]);
+        });
         len
     }
 
     unsafe fn write_at(&self, offset: u64, buf: &[u8]) -> usize {
-        let mem = self.view();
-        let len = buf.len().min(mem.len() - offset as usize);
-        mem[offset as usize..offset as usize + len].copy_from_slice(&buf[..len]);
+        let len = buf.len().min(self.size - offset as usize);
+        self.for_each_word(offset as usize, len, |word, done, bytes| {
+            let data = &buf[done..done + bytes.len()];
+            if let Ok(data) = data.try_into() {
+                word.store(u64::from_le_bytes(data), Ordering::Relaxed);
+                return;
+            }
+            // The other bytes of the word are kept even if another cpu writes them meanwhile
+            let _ = word.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |value| {
+                let mut value = value.to_le_bytes();
+                value[bytes.clone()].copy_from_slice(data);
+                Some(u64::from_le_bytes(value))
+            });
+        });
         len
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn accesses_split_at_word_boundaries() {
+        let memory = Memory::allocate(20);
+        let data: Vec<u8> = (1..=13).collect();
+        assert_eq!(unsafe { memory.write_at(3, &data) }, 13);
+
+        let mut buf = [0xff; 20];
+        assert_eq!(unsafe { memory.read_at(0, &mut buf) }, 20);
+        assert_eq!(buf[..3], [0; 3]);
+        assert_eq!(buf[3..16], data[..]);
+        assert_eq!(buf[16..], [0; 4]);
+
+        // The accesses stop at the end of the memory
+        assert_eq!(unsafe { memory.write_at(18, &[7; 4]) }, 2);
+        let mut buf = [0; 4];
+        assert_eq!(unsafe { memory.read_at(17, &mut buf) }, 3);
+        assert_eq!(buf, [0, 7, 7, 0]);
+    }
+
+    #[test]
+    fn byte_writes_of_cpus_keep_the_rest_of_the_word() {
+        let memory = Arc::new(Memory::allocate(8));
+        let threads: Vec<_> = (0..8u8)
+            .map(|i| {
+                let memory = memory.clone();
+                thread::spawn(move || {
+                    for n in 0..1000u32 {
+                        unsafe { memory.write_at(i as u64, &[(n as u8) | 1]) };
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let mut buf = [0; 8];
+        unsafe { memory.read_at(0, &mut buf) };
+        assert_eq!(buf, [999u32 as u8 | 1; 8]);
+    }
+}
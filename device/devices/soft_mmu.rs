@@ -1,14 +1,36 @@
 use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
 use thread_local::ThreadLocal;
 
 use crate::io_device::IoDevice;
 
+use super::{ExclusiveMonitor, Reservation};
+
 pub struct SoftMmu {
     map: Vec<Mapping>,
     last_access: ThreadLocal<RefCell<LastAccess>>,
+    monitor: ExclusiveMonitor,
+    /// Bumped by the broadcast TLB invalidations, a cpu that sees it change drops its cached
+    /// translations.
+    tlb_generation: AtomicU64,
+}
+
+impl Default for SoftMmu {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SoftMmu {
+    pub fn new() -> Self {
+        Self {
+            map: Vec::new(),
+            last_access: ThreadLocal::new(),
+            monitor: ExclusiveMonitor::new(),
+            tlb_generation: AtomicU64::new(0),
+        }
+    }
+
     pub fn get_mapping_index(&self, addr: u64) -> Option<usize> {
         // We use a thread local to cache the last access to speed up the common case of
         // sequential accesses.
@@ -52,23 +74,165 @@ impl SoftMmu {
     }
 }
 
-impl IoDevice for SoftMmu {
-    unsafe fn read_at(&self, offset: u64, buf: &mut [u8]) -> usize {
-        let idx = self
-            .get_mapping_index(offset)
-            .expect("Tried to read from unmapped memory");
+impl SoftMmu {
+    /// Read from the given address into the buffer.
+    ///
+    /// Returns `None` if the address is not mapped.
+    ///
+    /// # Safety
+    ///
+    /// See [`IoDevice::read_at`].
+    pub unsafe fn try_read_at(&self, addr: u64, buf: &mut [u8]) -> Option<usize> {
+        let idx = self.get_mapping_index(addr)?;
 
         let mapping = &self.map[idx];
-        mapping.io.read_at(offset - mapping.addr, buf)
+        Some(mapping.io.read_at(addr - mapping.addr, buf))
     }
 
-    unsafe fn write_at(&self, offset: u64, buf: &[u8]) -> usize {
-        let idx = self
-            .get_mapping_index(offset)
-            .expect("Tried to read from unmapped memory");
+    /// Write to the given address from the buffer.
+    ///
+    /// Returns `None` if the address is not mapped.
+    ///
+    /// # Safety
+    ///
+    /// See [`IoDevice::write_at`].
+    pub unsafe fn try_write_at(&self, addr: u64, buf: &[u8]) -> Option<usize> {
+        let idx = self.get_mapping_index(addr)?;
 
         let mapping = &self.map[idx];
-        mapping.io.write_at(offset - mapping.addr, buf)
+        Some(mapping.io.write_at(addr - mapping.addr, buf))
+    }
+
+    /// Fill the whole buffer from the given address.
+    ///
+    /// On failure, returns the first address that is not mapped.
+    ///
+    /// # Safety
+    ///
+    /// See [`IoDevice::read_at`].
+    pub unsafe fn try_read_all_at(&self, addr: u64, buf: &mut [u8]) -> Result<(), u64> {
+        let mut read = 0;
+        while read < buf.len() {
+            let at = addr.wrapping_add(read as u64);
+            match self.try_read_at(at, &mut buf[read..]) {
+                Some(len) if len > 0 => read += len,
+                _ => return Err(at),
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the whole buffer to the given address.
+    ///
+    /// The write breaks the reservations of the exclusive monitor on the written bytes.
+    /// On failure, returns the first address that is not mapped.
+    ///
+    /// # Safety
+    ///
+    /// See [`IoDevice::write_at`].
+    pub unsafe fn try_write_all_at(&self, addr: u64, buf: &[u8]) -> Result<(), u64> {
+        self.monitor.access(addr, buf.len(), || {
+            (self.write_all_at_unmonitored(addr, buf), true)
+        })
+    }
+
+    /// Atomically read the given address into the buffer and write back the bytes `update`
+    /// leaves in it, `update` returns false to leave the memory untouched.
+    ///
+    /// On failure, returns the first address that is not mapped.
+    ///
+    /// # Safety
+    ///
+    /// See [`IoDevice::read_at`] and [`IoDevice::write_at`].
+    pub unsafe fn try_update_at(
+        &self,
+        addr: u64,
+        buf: &mut [u8],
+        update: impl FnOnce(&mut [u8]) -> bool,
+    ) -> Result<(), u64> {
+        self.monitor.access(addr, buf.len(), || {
+            if let Err(fault_address) = self.try_read_all_at(addr, buf) {
+                return (Err(fault_address), false);
+            }
+            match update(buf) {
+                true => (self.write_all_at_unmonitored(addr, buf), true),
+                false => (Ok(()), false),
+            }
+        })
+    }
+
+    /// Fill the whole buffer from the given address and reserve it in the exclusive monitor.
+    ///
+    /// On failure, returns the first address that is not mapped.
+    ///
+    /// # Safety
+    ///
+    /// See [`IoDevice::read_at`].
+    pub unsafe fn try_read_exclusive_at(
+        &self,
+        addr: u64,
+        buf: &mut [u8],
+    ) -> Result<Reservation, u64> {
+        let (read, reservation) = self
+            .monitor
+            .load_exclusive(addr, buf.len(), || self.try_read_all_at(addr, buf));
+        read.map(|_| reservation)
+    }
+
+    /// Write the whole buffer to the given address if `reservation` is still held on it.
+    /// Returns whether the buffer has been written.
+    ///
+    /// On failure, returns the first address that is not mapped.
+    ///
+    /// # Safety
+    ///
+    /// See [`IoDevice::write_at`].
+    pub unsafe fn try_write_exclusive_at(
+        &self,
+        reservation: Reservation,
+        addr: u64,
+        buf: &[u8],
+    ) -> Result<bool, u64> {
+        self.monitor
+            .store_exclusive(reservation, addr, buf.len(), || {
+                self.write_all_at_unmonitored(addr, buf)
+            })
+            .map_or(Ok(false), |written| written.map(|_| true))
+    }
+
+    /// Make every cpu drop its cached translations, see [`tlb_generation`](Self::tlb_generation).
+    pub fn invalidate_tlbs(&self) {
+        self.tlb_generation.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// The number of broadcast TLB invalidations so far, a cpu drops its cached translations
+    /// when it changes.
+    pub fn tlb_generation(&self) -> u64 {
+        self.tlb_generation.load(Ordering::Acquire)
+    }
+
+    unsafe fn write_all_at_unmonitored(&self, addr: u64, buf: &[u8]) -> Result<(), u64> {
+        let mut written = 0;
+        while written < buf.len() {
+            let at = addr.wrapping_add(written as u64);
+            match self.try_write_at(at, &buf[written..]) {
+                Some(len) if len > 0 => written += len,
+                _ => return Err(at),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl IoDevice for SoftMmu {
+    unsafe fn read_at(&self, offset: u64, buf: &mut [u8]) -> usize {
+        self.try_read_at(offset, buf)
+            .expect("Tried to read from unmapped memory")
+    }
+
+    unsafe fn write_at(&self, offset: u64, buf: &[u8]) -> usize {
+        self.try_write_at(offset, buf)
+            .expect("Tried to write to unmapped memory")
     }
 }
 
@@ -1,7 +1,8 @@
 /// A trait for IO operations.
 ///
 /// This can represent any kind of IO, including disk IO, network IO, MMIO, etc.
-pub trait IoDevice {
+/// Devices are shared by every cpu of a machine, so they must be thread safe.
+pub trait IoDevice: Send + Sync {
     /// Read from the given offset into the buffer.
     unsafe fn read_at(&self, offset: u64, buf: &mut [u8]) -> usize;
 
@@ -1,16 +1,19 @@
 pub mod cranelift;
 mod executable;
+mod tlb;
 
 pub mod analysis;
 pub mod rustjit;
+pub mod soft_float;
 
 use core::{ir::BasicBlock, Architecture};
 pub use executable::*;
+pub use tlb::*;
 
 pub trait Codegen {
-    type Context;
+    type Context: ExecutionContext;
     type Executable: Executable<Context = Self::Context>;
 
     fn new_context<A: Architecture>() -> Self::Context;
-    fn compile(&self, bb: BasicBlock) -> Self::Executable;
+    fn compile<A: Architecture>(&self, bb: BasicBlock) -> Self::Executable;
 }
@@ -62,7 +62,18 @@ impl Analysis for VariableLivenessAnalysis<'_> {
                 | &IrInst::BitOr { dst, lhs, rhs }
                 | &IrInst::BitXor { dst, lhs, rhs }
                 | &IrInst::Shl { dst, lhs, rhs }
-                | &IrInst::Shr { dst, lhs, rhs } => {
+                | &IrInst::Shr { dst, lhs, rhs }
+                | &IrInst::AtomicRmw {
+                    dst,
+                    addr: lhs,
+                    src: rhs,
+                    ..
+                }
+                | &IrInst::StoreExclusive {
+                    dst,
+                    src: lhs,
+                    status: rhs,
+                } => {
                     try_mark_as_dead(idx, dst);
                     try_mark_as_dead(idx, lhs);
                     try_mark_as_dead(idx, rhs);
@@ -72,11 +83,34 @@ impl Analysis for VariableLivenessAnalysis<'_> {
                 | &IrInst::Assign { dst, src }
                 | &IrInst::Load { dst, src }
                 | &IrInst::Store { dst, src }
+                | &IrInst::LoadUnprivileged { dst, src }
+                | &IrInst::StoreUnprivileged { dst, src }
+                | &IrInst::LoadExclusive { dst, src }
                 | &IrInst::ZextCast { dst, src }
                 | &IrInst::SextCast { dst, src } => {
                     try_mark_as_dead(idx, dst);
                     try_mark_as_dead(idx, src);
                 }
+                &IrInst::CompareExchange {
+                    dst,
+                    addr,
+                    expected,
+                    src,
+                } => {
+                    try_mark_as_dead(idx, dst);
+                    try_mark_as_dead(idx, addr);
+                    try_mark_as_dead(idx, expected);
+                    try_mark_as_dead(idx, src);
+                }
+                &IrInst::Interrupt { cond, .. } => {
+                    try_mark_as_dead(idx, cond);
+                }
+                IrInst::Fence { .. } | IrInst::ClearExclusive => {}
+                &IrInst::InvalidateTlb { addr, asid, .. } => {
+                    for value in addr.into_iter().chain(asid) {
+                        try_mark_as_dead(idx, value);
+                    }
+                }
                 IrInst::Intrinsic(_) => todo!(),
             }
         }
@@ -103,7 +137,18 @@ impl Analysis for VariableLivenessAnalysis<'_> {
                 | &IrInst::BitOr { dst, lhs, rhs }
                 | &IrInst::BitXor { dst, lhs, rhs }
                 | &IrInst::Shl { dst, lhs, rhs }
-                | &IrInst::Shr { dst, lhs, rhs } => {
+                | &IrInst::Shr { dst, lhs, rhs }
+                | &IrInst::AtomicRmw {
+                    dst,
+                    addr: lhs,
+                    src: rhs,
+                    ..
+                }
+                | &IrInst::StoreExclusive {
+                    dst,
+                    src: lhs,
+                    status: rhs,
+                } => {
                     try_mark_as_live(dst, &mut variable_live);
                     try_mark_as_live(lhs, &mut variable_live);
                     try_mark_as_live(rhs, &mut variable_live);
@@ -120,6 +165,9 @@ impl Analysis for VariableLivenessAnalysis<'_> {
                 | &IrInst::Assign { dst, src }
                 | &IrInst::Load { dst, src }
                 | &IrInst::Store { dst, src }
+                | &IrInst::LoadUnprivileged { dst, src }
+                | &IrInst::StoreUnprivileged { dst, src }
+                | &IrInst::LoadExclusive { dst, src }
                 | &IrInst::ZextCast { dst, src }
                 | &IrInst::SextCast { dst, src } => {
                     try_mark_as_live(dst, &mut variable_live);
@@ -132,6 +180,49 @@ impl Analysis for VariableLivenessAnalysis<'_> {
 
                     maximum_variable_live = maximum_variable_live.max(variable_live.len());
                 }
+                &IrInst::CompareExchange {
+                    dst,
+                    addr,
+                    expected,
+                    src,
+                } => {
+                    try_mark_as_live(dst, &mut variable_live);
+                    try_mark_as_live(addr, &mut variable_live);
+                    try_mark_as_live(expected, &mut variable_live);
+                    try_mark_as_live(src, &mut variable_live);
+
+                    // Remove dead variables
+                    for value in &killed[idx] {
+                        variable_live.remove(value);
+                    }
+
+                    maximum_variable_live = maximum_variable_live.max(variable_live.len());
+                }
+                &IrInst::Interrupt { cond, .. } => {
+                    try_mark_as_live(cond, &mut variable_live);
+
+                    // Remove dead variables
+                    for value in &killed[idx] {
+                        variable_live.remove(value);
+                    }
+
+                    maximum_variable_live = maximum_variable_live.max(variable_live.len());
+                }
+                IrInst::Fence { .. } | IrInst::ClearExclusive => {
+                    maximum_variable_live = maximum_variable_live.max(variable_live.len());
+                }
+                &IrInst::InvalidateTlb { addr, asid, .. } => {
+                    for value in addr.into_iter().chain(asid) {
+                        try_mark_as_live(value, &mut variable_live);
+                    }
+
+                    // Remove dead variables
+                    for value in &killed[idx] {
+                        variable_live.remove(value);
+                    }
+
+                    maximum_variable_live = maximum_variable_live.max(variable_live.len());
+                }
                 IrInst::Intrinsic(_) => todo!(),
             }
         }
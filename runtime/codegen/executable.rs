@@ -1,18 +1,96 @@
-use core::Interrupt;
-use std::ops::Generator;
+use core::{AccessType, Interrupt};
 
 use device::devices::SoftMmu;
 
+/// The result of running an executable object.
+pub enum ExecOutcome {
+    /// The executable object ran to the end.
+    Completed,
+    /// An interrupt was raised in the middle of the execution.
+    ///
+    /// The execution can be continued later by passing `resume` to [`Executable::resume`].
+    Interrupted {
+        interrupt: Interrupt,
+        resume: ResumeToken,
+    },
+}
+
+/// A position inside an executable object where the execution can be resumed.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ResumeToken {
+    position: usize,
+}
+
+impl ResumeToken {
+    pub fn new(position: usize) -> Self {
+        Self { position }
+    }
+
+    /// Index of the next operation to execute.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Get the token that executes the interrupted operation again.
+    pub fn retry(self) -> Self {
+        Self {
+            position: self.position.saturating_sub(1),
+        }
+    }
+}
+
+/// The part of a cpu state that the runtime needs to drive the execution.
+pub trait ExecutionContext {
+    /// Get the program counter.
+    fn pc(&self) -> u64;
+
+    /// Set the program counter.
+    fn set_pc(&mut self, pc: u64);
+
+    /// The raw register file, laid out as described by the architecture's
+    /// [`RegisterFileDesc`](core::RegisterFileDesc).
+    fn registers(&self) -> &[u8];
+
+    /// The raw register file, see [`ExecutionContext::registers`].
+    fn registers_mut(&mut self) -> &mut [u8];
+
+    /// Translate the virtual address of an access to a physical address, with the
+    /// translations the context has cached.
+    ///
+    /// # Safety
+    ///
+    /// The translation tables are read through `mmu` without any checks.
+    unsafe fn translate(
+        &mut self,
+        address: u64,
+        access: AccessType,
+        mmu: &SoftMmu,
+    ) -> Result<u64, Interrupt>;
+}
+
 /// An executable object that can be executed on a context.
 pub trait Executable {
     type Context;
-    type Generator<'a>: Generator<Yield = Interrupt> + 'a
-    where
-        Self: 'a;
-
-    unsafe fn execute<'a>(
-        &'a self,
-        context: &'a mut Self::Context,
-        io_device: &'a SoftMmu,
-    ) -> Self::Generator<'a>;
+
+    /// Execute the object from the beginning.
+    ///
+    /// # Safety
+    ///
+    /// The executable accesses the guest memory through `io_device` without any checks.
+    unsafe fn execute(&self, context: &mut Self::Context, io_device: &SoftMmu) -> ExecOutcome {
+        self.resume(context, io_device, ResumeToken::default())
+    }
+
+    /// Continue the execution from the given position.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`Executable::execute`]. The token must come from an
+    /// [`ExecOutcome::Interrupted`] returned by this object.
+    unsafe fn resume(
+        &self,
+        context: &mut Self::Context,
+        io_device: &SoftMmu,
+        token: ResumeToken,
+    ) -> ExecOutcome;
 }
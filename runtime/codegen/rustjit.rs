@@ -2,65 +2,372 @@ mod register_file;
 mod value;
 
 use core::{
-    ir::{BasicBlock, IrInst, IrValue},
-    Architecture, Interrupt,
+    ir::{BasicBlock, FloatOp, IrInst, IrIntrinsic, IrOrdering, IrType, IrValue, TypeOf},
+    Abort, AbortKind, AccessType, Architecture, Interrupt, RegisterFileDesc, RegisterId,
 };
-use std::{collections::HashMap, ops::Generator};
+use std::sync::atomic::{fence, Ordering};
 
-use device::devices::SoftMmu;
+use device::devices::{Reservation, SoftMmu};
 pub use register_file::*;
-use value::RustjitValue;
+pub use value::*;
 
-use super::{Codegen, Executable};
+use super::{soft_float, Codegen, ExecOutcome, Executable, ExecutionContext, ResumeToken, Tlb};
 pub struct RustjitContext {
     registers: RegisterFile,
-    variables: HashMap<usize, RustjitValue>,
+    /// The variables of the running block, indexed by their id.
+    variables: Vec<RustjitValue>,
+    /// The offset of the pc in the register file.
+    pc: usize,
+    /// The local exclusive monitor, the reservation of the last exclusive load.
+    exclusive: Option<Reservation>,
+    tlb: Tlb,
+}
+
+impl ExecutionContext for RustjitContext {
+    fn pc(&self) -> u64 {
+        self.registers.read(self.pc, IrType::U64).to_u64()
+    }
+
+    fn set_pc(&mut self, pc: u64) {
+        self.registers.write(self.pc, &pc.into());
+    }
+
+    fn registers(&self) -> &[u8] {
+        self.registers.as_bytes()
+    }
+
+    fn registers_mut(&mut self) -> &mut [u8] {
+        self.registers.as_bytes_mut()
+    }
+
+    unsafe fn translate(
+        &mut self,
+        address: u64,
+        access: AccessType,
+        mmu: &SoftMmu,
+    ) -> Result<u64, Interrupt> {
+        self.tlb
+            .translate(self.registers.as_bytes(), mmu, address, 1, access)
+    }
+}
+
+/// An operand resolved at compile time.
+#[derive(Clone, Copy)]
+enum Operand {
+    Variable(usize),
+    Register { offset: usize, ty: IrType },
+    Constant(RustjitValue),
+}
+
+/// A destination resolved at compile time.
+#[derive(Clone, Copy)]
+enum Place {
+    Variable(usize),
+    Register {
+        offset: usize,
+        /// The bytes zeroed before a write through a zero extending view.
+        zeroed: Option<(usize, usize)>,
+    },
+    /// A zero register, the write is dropped.
+    Discard,
 }
 
 impl RustjitContext {
-    fn get(&self, value: IrValue) -> RustjitValue {
-        match value {
-            IrValue::Variable(_ty, id) => *self.variables.get(&id).unwrap(),
-            IrValue::Register(ty, id) => self.registers.get_value(id, ty),
-            IrValue::Constant(constant) => constant.into(),
+    fn get(&self, operand: Operand) -> RustjitValue {
+        match operand {
+            Operand::Variable(slot) => self.variables[slot],
+            Operand::Register { offset, ty } => self.registers.read(offset, ty),
+            Operand::Constant(value) => value,
         }
     }
 
-    fn set(&mut self, value: IrValue, data: RustjitValue) {
+    fn set(&mut self, place: Place, value: RustjitValue) {
+        match place {
+            Place::Variable(slot) => self.variables[slot] = value,
+            Place::Register { offset, zeroed } => {
+                if let Some((start, end)) = zeroed {
+                    self.registers.as_bytes_mut()[start..end].fill(0);
+                }
+                self.registers.write(offset, &value)
+            }
+            Place::Discard => {}
+        }
+    }
+}
+
+/// Resolves the variables and registers of a block to slots and offsets.
+struct Resolver {
+    desc: RegisterFileDesc,
+    variable_count: usize,
+}
+
+impl Resolver {
+    fn operand(&self, value: IrValue) -> Operand {
         match value {
-            IrValue::Variable(_, id) => {
-                self.variables.insert(id, data);
+            IrValue::Variable(_, id) => Operand::Variable(self.slot(id)),
+            IrValue::Register(ty, id) => {
+                let reg = self.desc.register(id);
+                assert_eq!(reg.size, ty.size_in_bytes(), "register size mismatch");
+
+                if reg.is_zero {
+                    return Operand::Constant(RustjitValue::new(ty, 0));
+                }
+                Operand::Register {
+                    offset: reg.offset,
+                    ty,
+                }
             }
-            IrValue::Register(_, id) => {
-                self.registers.set_value(id, &data);
+            IrValue::Constant(constant) => Operand::Constant(constant.into()),
+        }
+    }
+
+    fn place(&self, value: IrValue) -> Place {
+        match value {
+            IrValue::Variable(_, id) => Place::Variable(self.slot(id)),
+            IrValue::Register(ty, id) => {
+                let reg = self.desc.register(id);
+                assert!(!reg.is_read_only, "register is read only");
+                assert_eq!(reg.size, ty.size_in_bytes(), "register size mismatch");
+
+                if reg.is_zero {
+                    return Place::Discard;
+                }
+                let extent = self.desc.write_extent(id);
+                let zeroed = (extent.len() != reg.size).then_some((extent.start, extent.end));
+
+                Place::Register {
+                    offset: reg.offset,
+                    zeroed,
+                }
             }
             IrValue::Constant(_) => panic!("Constant cannot be set"),
         }
     }
+
+    /// The slot of a variable is its id. The ids of a block are already dense (`0..variable_count`),
+    /// so there's nothing to renumber; the slots are reused by the next block, see
+    /// [`RustjitExectuable::resume`].
+    fn slot(&self, id: usize) -> usize {
+        assert!(
+            id < self.variable_count,
+            "variable {id} is not in the block"
+        );
+        id
+    }
 }
 
+type RustjitInst = Box<dyn Fn(&mut RustjitContext, &SoftMmu) -> Option<Interrupt> + Send + Sync>;
+
 pub struct RustjitExectuable {
-    inst: Vec<Box<dyn Fn(&mut RustjitContext, &SoftMmu) -> Option<Interrupt>>>,
+    inst: Vec<RustjitInst>,
+    variable_count: usize,
 }
 
 impl Executable for RustjitExectuable {
     type Context = RustjitContext;
-    type Generator<'a> = impl Generator<Yield = Interrupt, Return = ()> + 'a;
-
-    unsafe fn execute<'a>(
-        &'a self,
-        context: &'a mut Self::Context,
-        io_device: &'a SoftMmu,
-    ) -> Self::Generator<'a> {
-        || {
-            for inst in &self.inst {
-                let Some(interrput) = inst(context, io_device) else {
-                    continue;
-                };
-
-                yield interrput;
-            }
+
+    unsafe fn resume(
+        &self,
+        context: &mut Self::Context,
+        io_device: &SoftMmu,
+        token: ResumeToken,
+    ) -> ExecOutcome {
+        // The slots are shared by every block, a variable is written before it is read.
+        if context.variables.len() < self.variable_count {
+            let unset = RustjitValue::new(IrType::Void, 0);
+            context.variables.resize(self.variable_count, unset);
+        }
+
+        for (idx, inst) in self.inst.iter().enumerate().skip(token.position()) {
+            let Some(interrupt) = inst(context, io_device) else {
+                continue;
+            };
+
+            return ExecOutcome::Interrupted {
+                interrupt,
+                resume: ResumeToken::new(idx + 1),
+            };
         }
+
+        ExecOutcome::Completed
+    }
+}
+
+fn binary(
+    resolver: &Resolver,
+    dst: IrValue,
+    lhs: IrValue,
+    rhs: IrValue,
+    op: BinaryOp,
+) -> RustjitInst {
+    check(op.name(), op.check(lhs.ty(), rhs.ty()), dst);
+    let (dst, lhs, rhs) = (
+        resolver.place(dst),
+        resolver.operand(lhs),
+        resolver.operand(rhs),
+    );
+
+    Box::new(move |ctx: &mut RustjitContext, _: &SoftMmu| {
+        let lhs = ctx.get(lhs);
+        let rhs = ctx.get(rhs);
+        ctx.set(
+            dst,
+            lhs.binary(op, rhs).unwrap_or_else(|err| panic!("{err}")),
+        );
+
+        None
+    })
+}
+
+fn unary(resolver: &Resolver, dst: IrValue, src: IrValue, op: UnaryOp) -> RustjitInst {
+    check(op.name(), op.check(src.ty()), dst);
+    let (dst, src) = (resolver.place(dst), resolver.operand(src));
+
+    Box::new(move |ctx: &mut RustjitContext, _: &SoftMmu| {
+        let src = ctx.get(src);
+        ctx.set(dst, src.unary(op).unwrap_or_else(|err| panic!("{err}")));
+
+        None
+    })
+}
+
+fn cast(resolver: &Resolver, dst: IrValue, src: IrValue, op: CastOp) -> RustjitInst {
+    let ty = dst.ty();
+    check(op.name(), op.check(src.ty(), ty), dst);
+    let (dst, src) = (resolver.place(dst), resolver.operand(src));
+
+    Box::new(move |ctx: &mut RustjitContext, _: &SoftMmu| {
+        let src = ctx.get(src);
+        ctx.set(dst, src.cast(op, ty).unwrap_or_else(|err| panic!("{err}")));
+
+        None
+    })
+}
+
+fn float(
+    resolver: &Resolver,
+    op: FloatOp,
+    dst: IrValue,
+    srcs: &[IrValue],
+    control: IrValue,
+    status: IrValue,
+) -> RustjitInst {
+    let src_tys: Vec<_> = srcs.iter().map(|src| src.ty()).collect();
+    if let Err(err) = soft_float::check(op, &src_tys, dst.ty()) {
+        panic!("Invalid instruction: {err}");
+    }
+    check("float control", Ok(IrType::U64), control);
+    check("float status", Ok(IrType::U64), status);
+
+    let width = |ty: IrType| ty.size_in_bytes() as u32 * 8;
+    let (src_width, dst_width, ty) = (width(src_tys[0]), width(dst.ty()), dst.ty());
+    let srcs: Vec<_> = srcs.iter().map(|&src| resolver.operand(src)).collect();
+    let (dst, control, status) = (
+        resolver.place(dst),
+        resolver.operand(control),
+        resolver.place(status),
+    );
+
+    Box::new(move |ctx: &mut RustjitContext, _: &SoftMmu| {
+        let mut bits = [0; 3];
+        for (bits, &src) in bits.iter_mut().zip(&srcs) {
+            *bits = ctx.get(src).to_bits();
+        }
+
+        let control = ctx.get(control).to_u64();
+        let (result, raised) =
+            soft_float::evaluate(op, &bits[..srcs.len()], src_width, dst_width, control);
+        ctx.set(dst, RustjitValue::new(ty, result));
+        ctx.set(status, raised.into());
+
+        None
+    })
+}
+
+fn abort(fault_address: u64, access: AccessType, kind: AbortKind) -> Interrupt {
+    Interrupt::Aborts(Abort {
+        fault_address,
+        access,
+        kind,
+        syndrome: 0,
+    })
+}
+
+/// The abort of an atomic access of `ty` at an address not aligned to its size.
+fn check_aligned(addr: u64, ty: IrType, access: AccessType) -> Option<Interrupt> {
+    let misaligned = addr & (ty.size_in_bytes() as u64 - 1) != 0;
+    misaligned.then(|| abort(addr, access, AbortKind::Unaligned))
+}
+
+/// Translate the address of an aligned access, which stays in a page.
+fn translate(
+    ctx: &mut RustjitContext,
+    mmu: &SoftMmu,
+    addr: u64,
+    ty: IrType,
+    access: AccessType,
+) -> Result<u64, Interrupt> {
+    if let Some(interrupt) = check_aligned(addr, ty, access) {
+        return Err(interrupt);
+    }
+
+    unsafe {
+        ctx.tlb.translate(
+            ctx.registers.as_bytes(),
+            mmu,
+            addr,
+            ty.size_in_bytes(),
+            access,
+        )
+    }
+}
+
+/// Atomically replace the value of `ty` at `addr` with the one `op` computes from it, unless
+/// it returns `None`. Returns the old value.
+fn update(
+    ctx: &mut RustjitContext,
+    mmu: &SoftMmu,
+    addr: u64,
+    ty: IrType,
+    op: impl FnOnce(RustjitValue) -> Option<RustjitValue>,
+) -> Result<RustjitValue, Interrupt> {
+    // The value is read even if it isn't replaced, the page must allow both
+    let physical = translate(ctx, mmu, addr, ty, AccessType::Write)?;
+    translate(ctx, mmu, addr, ty, AccessType::Read)?;
+
+    let mut buf = [0; 16];
+    let buf = &mut buf[..ty.size_in_bytes()];
+    let mut old = RustjitValue::new(ty, 0);
+    let updated = unsafe {
+        mmu.try_update_at(physical, buf, |bytes| {
+            old = RustjitValue::from_bytes(bytes, ty);
+            let new = op(old);
+            if let Some(new) = new {
+                bytes.copy_from_slice(new.as_bytes());
+            }
+            new.is_some()
+        })
+    };
+
+    match updated {
+        Ok(()) => Ok(old),
+        Err(_) => Err(abort(addr, AccessType::Write, AbortKind::Unmapped)),
+    }
+}
+
+/// Reject an ill-typed instruction when it is compiled rather than when it is executed.
+fn check(op: &'static str, result: Result<IrType, ValueError>, dst: IrValue) {
+    let result = result.and_then(|result| match result == dst.ty() {
+        true => Ok(result),
+        false => Err(ValueError::DestinationMismatch {
+            op,
+            result,
+            dst: dst.ty(),
+        }),
+    });
+
+    if let Err(err) = result {
+        panic!("Invalid instruction: {err}");
     }
 }
 
@@ -71,36 +378,275 @@ impl Codegen for RustjitCodegen {
     type Executable = RustjitExectuable;
 
     fn new_context<A: Architecture>() -> Self::Context {
+        let desc = A::get_register_file_desc();
+
         RustjitContext {
-            registers: RegisterFile::new(&A::get_register_file_desc()),
-            variables: HashMap::new(),
+            registers: RegisterFile::new(&desc),
+            variables: Vec::new(),
+            pc: desc.register(A::get_pc_register().raw()).offset,
+            exclusive: None,
+            tlb: Tlb::new::<A>(),
         }
     }
 
-    fn compile(&self, bb: BasicBlock) -> Self::Executable {
-        let mut executable = RustjitExectuable { inst: Vec::new() };
+    fn compile<A: Architecture>(&self, bb: BasicBlock) -> Self::Executable {
+        let resolver = Resolver {
+            desc: A::get_register_file_desc(),
+            variable_count: bb.variable_count(),
+        };
+        let mut executable = RustjitExectuable {
+            inst: Vec::new(),
+            variable_count: bb.variable_count(),
+        };
 
         for inst in bb.inst() {
             let inst = match inst {
-                &IrInst::Add { dst, lhs, rhs } => {
+                &IrInst::Add { dst, lhs, rhs } => binary(&resolver, dst, lhs, rhs, BinaryOp::Add),
+                &IrInst::Sub { dst, lhs, rhs } => binary(&resolver, dst, lhs, rhs, BinaryOp::Sub),
+                &IrInst::Mul { dst, lhs, rhs } => binary(&resolver, dst, lhs, rhs, BinaryOp::Mul),
+                &IrInst::Div { dst, lhs, rhs } => binary(&resolver, dst, lhs, rhs, BinaryOp::Div),
+                &IrInst::Rem { dst, lhs, rhs } => binary(&resolver, dst, lhs, rhs, BinaryOp::Rem),
+                &IrInst::BitAnd { dst, lhs, rhs } => {
+                    binary(&resolver, dst, lhs, rhs, BinaryOp::BitAnd)
+                }
+                &IrInst::BitOr { dst, lhs, rhs } => {
+                    binary(&resolver, dst, lhs, rhs, BinaryOp::BitOr)
+                }
+                &IrInst::BitXor { dst, lhs, rhs } => {
+                    binary(&resolver, dst, lhs, rhs, BinaryOp::BitXor)
+                }
+                &IrInst::Shl { dst, lhs, rhs } => binary(&resolver, dst, lhs, rhs, BinaryOp::Shl),
+                &IrInst::Shr { dst, lhs, rhs } => binary(&resolver, dst, lhs, rhs, BinaryOp::Shr),
+                &IrInst::Neg { dst, src } => unary(&resolver, dst, src, UnaryOp::Neg),
+                &IrInst::BitNot { dst, src } => unary(&resolver, dst, src, UnaryOp::BitNot),
+                &IrInst::ZextCast { dst, src } => cast(&resolver, dst, src, CastOp::Zext),
+                &IrInst::SextCast { dst, src } => cast(&resolver, dst, src, CastOp::Sext),
+                &IrInst::Assign { dst, src } => {
+                    check("assign", Ok(src.ty()), dst);
+                    let (dst, src) = (resolver.place(dst), resolver.operand(src));
+
                     Box::new(move |ctx: &mut RustjitContext, _: &SoftMmu| {
-                        let lhs = ctx.get(lhs);
-                        let rhs = ctx.get(rhs);
-                        ctx.set(dst, lhs + rhs);
+                        let src = ctx.get(src);
+                        ctx.set(dst, src);
+
+                        None
+                    }) as Box<_>
+                }
+                &(IrInst::Load { dst, src } | IrInst::LoadUnprivileged { dst, src }) => {
+                    let unprivileged = matches!(inst, IrInst::LoadUnprivileged { .. });
+                    let ty = dst.ty();
+                    let (dst, src) = (resolver.place(dst), resolver.operand(src));
+
+                    Box::new(move |ctx: &mut RustjitContext, mmu: &SoftMmu| {
+                        let addr = ctx.get(src).to_u64();
+                        let mut buf = [0; 16];
+                        let buf = &mut buf[..ty.size_in_bytes()];
+
+                        let registers = ctx.registers.as_bytes();
+                        let read = unsafe { ctx.tlb.read(registers, mmu, addr, buf, unprivileged) };
+                        if let Err(interrupt) = read {
+                            return Some(interrupt);
+                        }
+                        ctx.set(dst, RustjitValue::from_bytes(buf, ty));
+
+                        None
+                    }) as Box<_>
+                }
+                &(IrInst::Store { dst, src } | IrInst::StoreUnprivileged { dst, src }) => {
+                    let unprivileged = matches!(inst, IrInst::StoreUnprivileged { .. });
+                    let (dst, src) = (resolver.operand(dst), resolver.operand(src));
+
+                    Box::new(move |ctx: &mut RustjitContext, mmu: &SoftMmu| {
+                        let addr = ctx.get(dst).to_u64();
+                        let value = ctx.get(src);
+
+                        let registers = ctx.registers.as_bytes();
+                        let bytes = value.as_bytes();
+                        let written =
+                            unsafe { ctx.tlb.write(registers, mmu, addr, bytes, unprivileged) };
+                        if let Err(interrupt) = written {
+                            return Some(interrupt);
+                        }
 
                         None
                     }) as Box<_>
                 }
-                &IrInst::Sub { dst, lhs, rhs } => {
+                &IrInst::Interrupt { cond, interrupt } => {
+                    let cond = resolver.operand(cond);
+
                     Box::new(move |ctx: &mut RustjitContext, _: &SoftMmu| {
-                        let lhs = ctx.get(lhs);
-                        let rhs = ctx.get(rhs);
-                        ctx.set(dst, lhs - rhs);
+                        ctx.get(cond).to_bool().then_some(interrupt)
+                    }) as Box<_>
+                }
+                &IrInst::Fence { ordering } => {
+                    let ordering = match ordering {
+                        IrOrdering::Acquire => Ordering::Acquire,
+                        IrOrdering::Release => Ordering::Release,
+                        IrOrdering::SeqCst => Ordering::SeqCst,
+                    };
+
+                    Box::new(move |_: &mut RustjitContext, _: &SoftMmu| {
+                        fence(ordering);
+
+                        None
+                    }) as Box<_>
+                }
+                &IrInst::AtomicRmw { op, dst, addr, src } => {
+                    check("atomic", check_atomic(dst.ty(), src.ty()), dst);
+                    let ty = dst.ty();
+                    let (dst, addr, src) = (
+                        resolver.place(dst),
+                        resolver.operand(addr),
+                        resolver.operand(src),
+                    );
+
+                    Box::new(move |ctx: &mut RustjitContext, mmu: &SoftMmu| {
+                        let addr = ctx.get(addr).to_u64();
+                        let src = ctx.get(src);
+
+                        let old = update(ctx, mmu, addr, ty, |old| {
+                            Some(old.atomic(op, src).unwrap_or_else(|err| panic!("{err}")))
+                        });
+                        match old {
+                            Ok(old) => ctx.set(dst, old),
+                            Err(interrupt) => return Some(interrupt),
+                        }
+
+                        None
+                    }) as Box<_>
+                }
+                &IrInst::CompareExchange {
+                    dst,
+                    addr,
+                    expected,
+                    src,
+                } => {
+                    check(
+                        "compare exchange",
+                        check_atomic(expected.ty(), src.ty()),
+                        dst,
+                    );
+                    let ty = dst.ty();
+                    let (dst, addr, expected, src) = (
+                        resolver.place(dst),
+                        resolver.operand(addr),
+                        resolver.operand(expected),
+                        resolver.operand(src),
+                    );
+
+                    Box::new(move |ctx: &mut RustjitContext, mmu: &SoftMmu| {
+                        let addr = ctx.get(addr).to_u64();
+                        let (expected, src) = (ctx.get(expected), ctx.get(src));
+
+                        let old = update(ctx, mmu, addr, ty, |old| {
+                            (old.to_bits() == expected.to_bits()).then_some(src)
+                        });
+                        match old {
+                            Ok(old) => ctx.set(dst, old),
+                            Err(interrupt) => return Some(interrupt),
+                        }
+
+                        None
+                    }) as Box<_>
+                }
+                &IrInst::LoadExclusive { dst, src } => {
+                    let ty = dst.ty();
+                    let (dst, src) = (resolver.place(dst), resolver.operand(src));
+
+                    Box::new(move |ctx: &mut RustjitContext, mmu: &SoftMmu| {
+                        let addr = ctx.get(src).to_u64();
+                        let physical = match translate(ctx, mmu, addr, ty, AccessType::Read) {
+                            Ok(physical) => physical,
+                            Err(interrupt) => return Some(interrupt),
+                        };
+
+                        let mut buf = [0; 16];
+                        let buf = &mut buf[..ty.size_in_bytes()];
+                        match unsafe { mmu.try_read_exclusive_at(physical, buf) } {
+                            Ok(reservation) => ctx.exclusive = Some(reservation),
+                            Err(_) => {
+                                return Some(abort(addr, AccessType::Read, AbortKind::Unmapped))
+                            }
+                        }
+                        ctx.set(dst, RustjitValue::from_bytes(buf, ty));
+
+                        None
+                    }) as Box<_>
+                }
+                &IrInst::StoreExclusive { dst, src, status } => {
+                    check("store exclusive", Ok(IrType::Bool), status);
+                    let ty = src.ty();
+                    let (dst, src, status) = (
+                        resolver.operand(dst),
+                        resolver.operand(src),
+                        resolver.place(status),
+                    );
+
+                    Box::new(move |ctx: &mut RustjitContext, mmu: &SoftMmu| {
+                        let addr = ctx.get(dst).to_u64();
+                        let value = ctx.get(src);
+                        let physical = match translate(ctx, mmu, addr, ty, AccessType::Write) {
+                            Ok(physical) => physical,
+                            Err(interrupt) => return Some(interrupt),
+                        };
+
+                        let stored = match ctx.exclusive.take() {
+                            Some(reservation) => {
+                                match unsafe {
+                                    mmu.try_write_exclusive_at(
+                                        reservation,
+                                        physical,
+                                        value.as_bytes(),
+                                    )
+                                } {
+                                    Ok(stored) => stored,
+                                    Err(_) => {
+                                        return Some(abort(
+                                            addr,
+                                            AccessType::Write,
+                                            AbortKind::Unmapped,
+                                        ))
+                                    }
+                                }
+                            }
+                            None => false,
+                        };
+                        ctx.set(status, RustjitValue::new(IrType::Bool, stored as u128));
 
                         None
                     }) as Box<_>
                 }
-                _ => todo!(),
+                IrInst::ClearExclusive => Box::new(|ctx: &mut RustjitContext, _: &SoftMmu| {
+                    ctx.exclusive = None;
+
+                    None
+                }) as Box<_>,
+                &IrInst::InvalidateTlb {
+                    addr,
+                    asid,
+                    broadcast,
+                } => {
+                    let addr = addr.map(|addr| resolver.operand(addr));
+                    let asid = asid.map(|asid| resolver.operand(asid));
+
+                    Box::new(move |ctx: &mut RustjitContext, mmu: &SoftMmu| {
+                        let addr = addr.map(|addr| ctx.get(addr).to_u64());
+                        let asid = asid.map(|asid| ctx.get(asid).to_u64() as u16);
+                        ctx.tlb.invalidate(addr, asid);
+                        if broadcast {
+                            mmu.invalidate_tlbs();
+                        }
+
+                        None
+                    }) as Box<_>
+                }
+                IrInst::Intrinsic(IrIntrinsic::Float {
+                    op,
+                    dst,
+                    srcs,
+                    control,
+                    status,
+                }) => float(&resolver, *op, *dst, srcs, *control, *status),
             };
 
             executable.inst.push(inst);
@@ -109,3 +655,184 @@ impl Codegen for RustjitCodegen {
         executable
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        ir::{IrConstant, IrInst},
+        RawRegisterId, ViewWrite,
+    };
+
+    use super::*;
+    use crate::testing::{TestArchitecture, TestRegisterId};
+
+    const X0: RawRegisterId = RawRegisterId::new(0);
+    const W0: RawRegisterId = RawRegisterId::new(1);
+    const V0: RawRegisterId = RawRegisterId::new(2);
+    const D0: RawRegisterId = RawRegisterId::new(3);
+    const S0: RawRegisterId = RawRegisterId::new(4);
+    const XZR: RawRegisterId = RawRegisterId::new(5);
+    const WZR: RawRegisterId = RawRegisterId::new(6);
+
+    fn resolver() -> Resolver {
+        let mut desc = RegisterFileDesc::new();
+        desc.add_register(X0, 8, false);
+        desc.add_view(W0, X0, 0, 4, ViewWrite::ZeroExtend);
+        desc.add_register(V0, 16, false);
+        desc.add_view(D0, V0, 0, 8, ViewWrite::Merge);
+        desc.add_view(S0, D0, 4, 4, ViewWrite::ZeroExtend);
+        desc.add_zero_register(XZR, 8);
+        desc.add_view(WZR, XZR, 0, 4, ViewWrite::ZeroExtend);
+
+        Resolver {
+            desc,
+            variable_count: 2,
+        }
+    }
+
+    #[test]
+    fn views_resolve_to_the_storage_of_their_parent() {
+        let resolver = resolver();
+
+        let w0 = IrValue::Register(IrType::U32, W0);
+        assert!(matches!(
+            resolver.operand(w0),
+            Operand::Register {
+                offset: 0,
+                ty: IrType::U32
+            }
+        ));
+        assert!(matches!(
+            resolver.place(w0),
+            Place::Register {
+                offset: 0,
+                zeroed: Some((0, 8))
+            }
+        ));
+
+        // A merging view only writes its own bytes
+        let d0 = IrValue::Register(IrType::U64, D0);
+        assert!(matches!(
+            resolver.place(d0),
+            Place::Register {
+                offset: 8,
+                zeroed: None
+            }
+        ));
+
+        // A view of a view zero extends to the outermost register
+        let s0 = IrValue::Register(IrType::U32, S0);
+        assert!(matches!(
+            resolver.operand(s0),
+            Operand::Register {
+                offset: 12,
+                ty: IrType::U32
+            }
+        ));
+        assert!(matches!(
+            resolver.place(s0),
+            Place::Register {
+                offset: 12,
+                zeroed: Some((8, 24))
+            }
+        ));
+    }
+
+    #[test]
+    fn zero_registers_read_zero_and_drop_writes() {
+        let resolver = resolver();
+
+        for (ty, id) in [(IrType::U64, XZR), (IrType::U32, WZR)] {
+            let zero = IrValue::Register(ty, id);
+            let Operand::Constant(value) = resolver.operand(zero) else {
+                panic!("zero register is not a constant");
+            };
+            assert_eq!(value.to_bits(), 0);
+            assert!(matches!(resolver.place(zero), Place::Discard));
+        }
+    }
+
+    #[test]
+    fn variables_are_their_slots() {
+        let resolver = resolver();
+
+        for id in 0..2 {
+            let variable = IrValue::Variable(IrType::U64, id);
+            assert!(matches!(resolver.operand(variable), Operand::Variable(slot) if slot == id));
+            assert!(matches!(resolver.place(variable), Place::Variable(slot) if slot == id));
+        }
+    }
+
+    #[test]
+    fn variables_share_the_slots_across_blocks() {
+        let r = |v| TestRegisterId::R(v).value();
+        let one = IrValue::Constant(IrConstant::U64(1));
+
+        // r1 = (r0 + 1) * 2 + r0, in three variables
+        let mut large = BasicBlock::new(0);
+        let (a, b, c) = (
+            large.new_variable(IrType::U64),
+            large.new_variable(IrType::U64),
+            large.new_variable(IrType::U64),
+        );
+        large.push_inst(IrInst::Add {
+            dst: a,
+            lhs: r(0),
+            rhs: one,
+        });
+        large.push_inst(IrInst::Add {
+            dst: b,
+            lhs: a,
+            rhs: a,
+        });
+        large.push_inst(IrInst::Add {
+            dst: c,
+            lhs: b,
+            rhs: r(0),
+        });
+        large.push_inst(IrInst::Assign { dst: r(1), src: c });
+
+        // r2 = r1 + 1, in the first slot
+        let mut small = BasicBlock::new(0);
+        let d = small.new_variable(IrType::U64);
+        small.push_inst(IrInst::Add {
+            dst: d,
+            lhs: r(1),
+            rhs: one,
+        });
+        small.push_inst(IrInst::Assign { dst: r(2), src: d });
+
+        let large = RustjitCodegen.compile::<TestArchitecture>(large);
+        let small = RustjitCodegen.compile::<TestArchitecture>(small);
+        let mut context = RustjitCodegen::new_context::<TestArchitecture>();
+        let mmu = SoftMmu::new();
+        let get = |context: &RustjitContext, v| {
+            let offset = context
+                .registers
+                .desc()
+                .register(TestRegisterId::R(v).raw())
+                .offset;
+            context.registers.read(offset, IrType::U64).to_u64()
+        };
+
+        // The small block runs first, then the large one grows the slots and the small one reuses
+        // the first of them
+        for r0 in [3u64, 5] {
+            let offset = context
+                .registers
+                .desc()
+                .register(TestRegisterId::R(0).raw())
+                .offset;
+            context.registers.write(offset, &r0.into());
+            unsafe {
+                small.execute(&mut context, &mmu);
+                large.execute(&mut context, &mmu);
+                small.execute(&mut context, &mmu);
+            }
+
+            assert_eq!(get(&context, 1), (r0 + 1) * 2 + r0);
+            assert_eq!(get(&context, 2), (r0 + 1) * 2 + r0 + 1);
+        }
+        assert_eq!(context.variables.len(), 3);
+    }
+}
@@ -34,23 +34,35 @@ impl<const LEN: usize> RegisterProjection for [f64; LEN] {}
 
 impl RegisterFile {
     pub fn new(desc: &RegisterFileDesc) -> Self {
-        let mut file = Vec::new();
-        file.resize(desc.total_size(), 0);
         Self {
             desc: desc.clone(),
-            file: file.into_boxed_slice(),
+            file: vec![0; desc.total_size()].into_boxed_slice(),
         }
     }
 
+    pub fn desc(&self) -> &RegisterFileDesc {
+        &self.desc
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.file
+    }
+
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.file
+    }
+
     /// Get reference of the the register as T
     ///
-    /// This function will panic if the size of T and the register size does not match.
+    /// This function will panic if the size of T and the register size does not match, or if the
+    /// register is a zero register.
     pub fn get<T>(&self, reg: RawRegisterId) -> T
     where
         T: RegisterProjection,
     {
         unsafe {
             let reg = self.desc.register(reg);
+            assert!(!reg.is_zero, "zero register has no storage");
             let ptr = self.file.as_ptr().add(reg.offset);
 
             assert!(reg.offset + std::mem::size_of::<T>() <= self.file.len());
@@ -61,14 +73,17 @@ impl RegisterFile {
 
     /// Get mutable reference of the the register as T
     ///
-    /// This function will panic if the size of T and the register size does not match.
+    /// Writes through the reference ignore the [`ViewWrite`](core::ViewWrite) of a view.
+    /// This function will panic if the size of T and the register size does not match, or if the
+    /// register is a zero register.
     pub fn get_mut<T>(&mut self, reg: RawRegisterId) -> &mut T
     where
         T: RegisterProjection,
     {
         unsafe {
             let reg = self.desc.register(reg);
-            assert!(reg.is_read_only == false);
+            assert!(!reg.is_read_only);
+            assert!(!reg.is_zero, "zero register has no storage");
             let ptr = self.file.as_mut_ptr().add(reg.offset);
 
             assert!(reg.offset + std::mem::size_of::<T>() <= self.file.len());
@@ -83,6 +98,10 @@ impl RegisterFile {
     pub fn get_value(&self, reg: RawRegisterId, ty: IrType) -> RustjitValue {
         unsafe {
             let reg = self.desc.register(reg);
+            if reg.is_zero {
+                assert_eq!(reg.size, ty.size_in_bytes());
+                return RustjitValue::new(ty, 0);
+            }
             let ptr = self.file.as_ptr().add(reg.offset);
 
             assert!(reg.offset + reg.size <= self.file.len());
@@ -94,19 +113,115 @@ impl RegisterFile {
 
     /// Set register value to RustjitValue
     ///
+    /// A write through a view follows its [`ViewWrite`](core::ViewWrite) semantics, a write to a
+    /// zero register is ignored.
     /// This function will panic if the size of `ty` and the register size does not match.
     pub fn set_value(&mut self, reg: RawRegisterId, value: &RustjitValue) {
-        unsafe {
-            let reg = self.desc.register(reg);
-            assert!(reg.is_read_only == false);
-            let ptr = self.file.as_mut_ptr().add(reg.offset);
+        let extent = self.desc.write_extent(reg);
+        let reg = self.desc.register(reg);
+        assert!(!reg.is_read_only);
+        assert_eq!(reg.size, value.as_bytes().len());
+        if reg.is_zero {
+            return;
+        }
 
-            let src = value.as_bytes();
+        let offset = reg.offset;
+        self.file[extent].fill(0);
+        self.write(offset, value);
+    }
 
-            assert!(reg.offset + reg.size <= self.file.len());
-            assert_eq!(reg.size, src.len());
+    /// Read a value of type `ty` at `offset` in the file.
+    ///
+    /// The offset is resolved ahead of time from [`RegisterFileDesc::register`], no check is done
+    /// against the description.
+    pub fn read(&self, offset: usize, ty: IrType) -> RustjitValue {
+        RustjitValue::from_bytes(&self.file[offset..offset + ty.size_in_bytes()], ty)
+    }
 
-            std::ptr::copy_nonoverlapping(src.as_ptr(), ptr, reg.size);
-        }
+    /// Write `value` at `offset` in the file, see [`read`](Self::read).
+    ///
+    /// The write semantics of views are left to the caller, see
+    /// [`RegisterFileDesc::write_extent`].
+    pub fn write(&mut self, offset: usize, value: &RustjitValue) {
+        let src = value.as_bytes();
+        self.file[offset..offset + src.len()].copy_from_slice(src);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ViewWrite;
+
+    use super::*;
+
+    const X0: RawRegisterId = RawRegisterId::new(0);
+    const W0: RawRegisterId = RawRegisterId::new(1);
+    const H0: RawRegisterId = RawRegisterId::new(2);
+    const V0: RawRegisterId = RawRegisterId::new(3);
+    const D0: RawRegisterId = RawRegisterId::new(4);
+    const S0: RawRegisterId = RawRegisterId::new(5);
+    const XZR: RawRegisterId = RawRegisterId::new(6);
+    const WZR: RawRegisterId = RawRegisterId::new(7);
+
+    fn desc() -> RegisterFileDesc {
+        let mut desc = RegisterFileDesc::new();
+        desc.add_register(X0, 8, false);
+        desc.add_view(W0, X0, 0, 4, ViewWrite::ZeroExtend);
+        desc.add_view(H0, W0, 2, 2, ViewWrite::Merge);
+        desc.add_register(V0, 16, false);
+        desc.add_view(D0, V0, 0, 8, ViewWrite::Merge);
+        desc.add_view(S0, D0, 4, 4, ViewWrite::ZeroExtend);
+        desc.add_zero_register(XZR, 8);
+        desc.add_view(WZR, XZR, 0, 4, ViewWrite::ZeroExtend);
+        desc
+    }
+
+    #[test]
+    fn views_read_the_storage_of_their_parent() {
+        let mut file = RegisterFile::new(&desc());
+        file.set_value(X0, &RustjitValue::new(IrType::U64, 0x1122_3344_5566_7788));
+
+        assert_eq!(file.get_value(W0, IrType::U32).to_bits(), 0x5566_7788);
+        assert_eq!(file.get_value(H0, IrType::U16).to_bits(), 0x5566);
+        assert_eq!(file.desc().total_size(), 24);
+    }
+
+    #[test]
+    fn views_write_with_their_semantics() {
+        let mut file = RegisterFile::new(&desc());
+        file.set_value(X0, &RustjitValue::new(IrType::U64, u64::MAX as u128));
+        file.set_value(V0, &RustjitValue::new(IrType::U128, u128::MAX));
+
+        file.set_value(H0, &RustjitValue::new(IrType::U16, 0));
+        assert_eq!(
+            file.get_value(X0, IrType::U64).to_bits(),
+            0xffff_ffff_0000_ffff
+        );
+
+        file.set_value(W0, &RustjitValue::new(IrType::U32, 0x1234));
+        assert_eq!(file.get_value(X0, IrType::U64).to_bits(), 0x1234);
+
+        file.set_value(D0, &RustjitValue::new(IrType::U64, 0x5678));
+        assert_eq!(
+            file.get_value(V0, IrType::U128).to_bits(),
+            u128::MAX << 64 | 0x5678
+        );
+
+        // A view of a view extends into the outermost register.
+        file.set_value(S0, &RustjitValue::new(IrType::U32, 0x9abc));
+        assert_eq!(file.get_value(V0, IrType::U128).to_bits(), 0x9abc << 32);
+    }
+
+    #[test]
+    fn zero_registers_read_zero_and_ignore_writes() {
+        let mut file = RegisterFile::new(&desc());
+        file.as_bytes_mut().fill(0xff);
+
+        file.set_value(XZR, &RustjitValue::new(IrType::U64, 0x1234));
+        file.set_value(WZR, &RustjitValue::new(IrType::U32, 0x1234));
+
+        assert_eq!(file.get_value(XZR, IrType::U64).to_bits(), 0);
+        assert_eq!(file.get_value(WZR, IrType::U32).to_bits(), 0);
+        assert!(file.as_bytes().iter().all(|&b| b == 0xff));
     }
 }
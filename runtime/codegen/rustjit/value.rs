@@ -1,191 +1,472 @@
-use core::ir::{IrConstant, IrType};
+use core::ir::{AtomicOp, IrConstant, IrType};
 
+use std::fmt;
 use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Rem, Shl, Shr, Sub};
 
 const VALUE_SIZE: usize = 16;
 
+/// A value of any [`IrType`] up to 128 bits.
+///
+/// Integers wrap at the width of their type and `Bool` is a 1-bit unsigned integer. Division
+/// by zero gives zero, remainder by zero gives the dividend and shift amounts are taken modulo
+/// the width. Floats follow IEEE 754 and vectors apply the operation to each lane.
 #[derive(Clone, Copy, Debug)]
 pub struct RustjitValue {
     raw: [u8; VALUE_SIZE],
     ty: IrType,
 }
 
+/// The error of an operation on values of the wrong types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueError {
+    /// The operands have different types.
+    TypeMismatch {
+        op: &'static str,
+        lhs: IrType,
+        rhs: IrType,
+    },
+    /// The result doesn't have the type of the destination.
+    DestinationMismatch {
+        op: &'static str,
+        result: IrType,
+        dst: IrType,
+    },
+    /// The operation is not defined on the type.
+    Unsupported { op: &'static str, ty: IrType },
+}
+
+impl fmt::Display for ValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueError::TypeMismatch { op, lhs, rhs } => {
+                write!(f, "mismatched operand types for {op}: {lhs:?} and {rhs:?}")
+            }
+            ValueError::DestinationMismatch { op, result, dst } => {
+                write!(
+                    f,
+                    "result of {op} is {result:?} but the destination is {dst:?}"
+                )
+            }
+            ValueError::Unsupported { op, ty } => write!(f, "{op} is not defined on {ty:?}"),
+        }
+    }
+}
+
+impl std::error::Error for ValueError {}
+
+pub type ValueResult = Result<RustjitValue, ValueError>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnaryOp {
+    Neg,
+    BitNot,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CastOp {
+    Zext,
+    Sext,
+}
+
+impl BinaryOp {
+    pub fn name(self) -> &'static str {
+        match self {
+            BinaryOp::Add => "add",
+            BinaryOp::Sub => "sub",
+            BinaryOp::Mul => "mul",
+            BinaryOp::Div => "div",
+            BinaryOp::Rem => "rem",
+            BinaryOp::BitAnd => "bitand",
+            BinaryOp::BitOr => "bitor",
+            BinaryOp::BitXor => "bitxor",
+            BinaryOp::Shl => "shl",
+            BinaryOp::Shr => "shr",
+        }
+    }
+
+    /// Returns the type of the result, or why the operation is not defined on the operands.
+    pub fn check(self, lhs: IrType, rhs: IrType) -> Result<IrType, ValueError> {
+        if lhs != rhs {
+            return Err(ValueError::TypeMismatch {
+                op: self.name(),
+                lhs,
+                rhs,
+            });
+        }
+
+        let (elem, _) = lanes(self.name(), lhs)?;
+        if elem.is_float() && matches!(self, BinaryOp::Shl | BinaryOp::Shr) {
+            return Err(ValueError::Unsupported {
+                op: self.name(),
+                ty: lhs,
+            });
+        }
+
+        Ok(lhs)
+    }
+
+    fn apply_int(self, ty: IrType, lhs: u128, rhs: u128) -> u128 {
+        let width = width(ty);
+
+        match self {
+            BinaryOp::Add => lhs.wrapping_add(rhs),
+            BinaryOp::Sub => lhs.wrapping_sub(rhs),
+            BinaryOp::Mul => lhs.wrapping_mul(rhs),
+            BinaryOp::Div => match rhs {
+                0 => 0,
+                _ if ty.is_signed() => {
+                    sign_extend(lhs, width).wrapping_div(sign_extend(rhs, width)) as u128
+                }
+                _ => lhs / rhs,
+            },
+            BinaryOp::Rem => match rhs {
+                0 => lhs,
+                _ if ty.is_signed() => {
+                    sign_extend(lhs, width).wrapping_rem(sign_extend(rhs, width)) as u128
+                }
+                _ => lhs % rhs,
+            },
+            BinaryOp::BitAnd => lhs & rhs,
+            BinaryOp::BitOr => lhs | rhs,
+            BinaryOp::BitXor => lhs ^ rhs,
+            BinaryOp::Shl => lhs << (rhs % width as u128),
+            BinaryOp::Shr => {
+                let amount = (rhs % width as u128) as u32;
+                match ty.is_signed() {
+                    true => (sign_extend(lhs, width) >> amount) as u128,
+                    false => lhs >> amount,
+                }
+            }
+        }
+    }
+
+    fn apply_float<F: Float>(self, lhs: u128, rhs: u128) -> u128 {
+        let (l, r) = (F::from_bits(lhs), F::from_bits(rhs));
+
+        match self {
+            BinaryOp::Add => (l + r).to_bits(),
+            BinaryOp::Sub => (l - r).to_bits(),
+            BinaryOp::Mul => (l * r).to_bits(),
+            BinaryOp::Div => (l / r).to_bits(),
+            BinaryOp::Rem => (l % r).to_bits(),
+            BinaryOp::BitAnd => lhs & rhs,
+            BinaryOp::BitOr => lhs | rhs,
+            BinaryOp::BitXor => lhs ^ rhs,
+            BinaryOp::Shl | BinaryOp::Shr => unreachable!("shifts are not defined on floats"),
+        }
+    }
+
+    /// Apply the operation to a single lane of type `ty`.
+    fn apply(self, ty: IrType, lhs: u128, rhs: u128) -> u128 {
+        match ty {
+            IrType::F32 => self.apply_float::<f32>(lhs, rhs),
+            IrType::F64 => self.apply_float::<f64>(lhs, rhs),
+            _ => self.apply_int(ty, lhs, rhs),
+        }
+    }
+}
+
+impl UnaryOp {
+    pub fn name(self) -> &'static str {
+        match self {
+            UnaryOp::Neg => "neg",
+            UnaryOp::BitNot => "bitnot",
+        }
+    }
+
+    /// Returns the type of the result, or why the operation is not defined on the operand.
+    pub fn check(self, ty: IrType) -> Result<IrType, ValueError> {
+        lanes(self.name(), ty).map(|_| ty)
+    }
+
+    fn apply(self, ty: IrType, src: u128) -> u128 {
+        match (self, ty) {
+            (UnaryOp::Neg, IrType::F32) => (-f32::from_bits(src as u32)).to_bits() as u128,
+            (UnaryOp::Neg, IrType::F64) => (-f64::from_bits(src as u64)).to_bits() as u128,
+            (UnaryOp::Neg, _) => src.wrapping_neg(),
+            (UnaryOp::BitNot, _) => !src,
+        }
+    }
+}
+
+impl CastOp {
+    pub fn name(self) -> &'static str {
+        match self {
+            CastOp::Zext => "zext",
+            CastOp::Sext => "sext",
+        }
+    }
+
+    /// Returns the type of the result, or why the cast is not defined between the types.
+    ///
+    /// Casts work on the raw bits of the whole value, a narrower destination truncates it.
+    pub fn check(self, from: IrType, to: IrType) -> Result<IrType, ValueError> {
+        lanes(self.name(), from)?;
+        lanes(self.name(), to)?;
+
+        Ok(to)
+    }
+}
+
+/// Floats as seen by the lanes of a value.
+trait Float:
+    Copy
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    fn from_bits(bits: u128) -> Self;
+    fn to_bits(self) -> u128;
+}
+
+impl Float for f32 {
+    fn from_bits(bits: u128) -> Self {
+        f32::from_bits(bits as u32)
+    }
+
+    fn to_bits(self) -> u128 {
+        f32::to_bits(self) as u128
+    }
+}
+
+impl Float for f64 {
+    fn from_bits(bits: u128) -> Self {
+        f64::from_bits(bits as u64)
+    }
+
+    fn to_bits(self) -> u128 {
+        f64::to_bits(self) as u128
+    }
+}
+
+/// The width of `ty` in bits.
+fn width(ty: IrType) -> u32 {
+    match ty {
+        IrType::Bool => 1,
+        ty => ty.size_in_bytes() as u32 * 8,
+    }
+}
+
+fn mask(width: u32) -> u128 {
+    match width {
+        128 => u128::MAX,
+        width => (1 << width) - 1,
+    }
+}
+
+fn sign_extend(bits: u128, width: u32) -> i128 {
+    let shift = 128 - width;
+    ((bits as i128) << shift) >> shift
+}
+
+/// Returns the type of an atomic operation on a value of `ty` with a source of `src`, or why it
+/// is not defined on them. Atomics are defined on the integers.
+pub fn check_atomic(ty: IrType, src: IrType) -> Result<IrType, ValueError> {
+    let op = "atomic";
+    if ty != src {
+        return Err(ValueError::TypeMismatch {
+            op,
+            lhs: ty,
+            rhs: src,
+        });
+    }
+
+    match lanes(op, ty)? {
+        (elem, 1) if !elem.is_float() => Ok(ty),
+        _ => Err(ValueError::Unsupported { op, ty }),
+    }
+}
+
+/// The type and number of the lanes of `ty`, scalars have a single lane.
+fn lanes(op: &'static str, ty: IrType) -> Result<(IrType, usize), ValueError> {
+    let size = ty.size_in_bytes();
+    if size == 0 || size > VALUE_SIZE {
+        return Err(ValueError::Unsupported { op, ty });
+    }
+
+    match ty {
+        IrType::Vector(elem, len) => Ok((elem.element_type(), len as usize)),
+        ty => Ok((ty, 1)),
+    }
+}
+
 impl RustjitValue {
+    /// Create a value from its raw bits, the bits above the type are ignored.
+    pub fn new(ty: IrType, bits: u128) -> Self {
+        assert!(ty.size_in_bytes() <= VALUE_SIZE, "{ty:?} is too large");
+
+        Self {
+            raw: (bits & mask(width(ty))).to_le_bytes(),
+            ty,
+        }
+    }
+
+    pub fn ty(&self) -> IrType {
+        self.ty
+    }
+
     pub fn as_bytes(&self) -> &[u8] {
         &self.raw[..self.ty.size_in_bytes()]
     }
 
     pub fn from_bytes(bytes: &[u8], ty: IrType) -> Self {
         let mut raw = [0; VALUE_SIZE];
-        raw[..ty.size_in_bytes()].copy_from_slice(bytes[..ty.size_in_bytes()].try_into().unwrap());
-        Self { raw, ty }
+        raw[..ty.size_in_bytes()].copy_from_slice(&bytes[..ty.size_in_bytes()]);
+        Self::new(ty, u128::from_le_bytes(raw))
     }
-}
 
-impl From<IrConstant> for RustjitValue {
-    fn from(value: IrConstant) -> Self {
+    /// The raw bits of the value, zero extended to u128.
+    pub fn to_bits(self) -> u128 {
+        u128::from_le_bytes(self.raw)
+    }
+
+    /// Get the value zero extended to u64.
+    pub fn to_u64(self) -> u64 {
+        self.to_bits() as u64
+    }
+
+    /// Returns true if the value is not zero.
+    pub fn to_bool(self) -> bool {
+        self.to_bits() != 0
+    }
+
+    fn lane(&self, elem: IrType, idx: usize) -> u128 {
+        let size = elem.size_in_bytes();
         let mut raw = [0; VALUE_SIZE];
-        let ty = match value {
-            IrConstant::U8(value) => {
-                raw[0] = value;
-                IrType::U8
-            }
-            IrConstant::U16(value) => {
-                raw[..2].copy_from_slice(&value.to_le_bytes());
-                IrType::U16
-            }
-            IrConstant::U32(value) => {
-                raw[..4].copy_from_slice(&value.to_le_bytes());
-                IrType::U32
-            }
-            IrConstant::U64(value) => {
-                raw[..8].copy_from_slice(&value.to_le_bytes());
-                IrType::U64
-            }
-            IrConstant::I8(value) => {
-                raw[0] = value as u8;
-                IrType::I8
-            }
-            IrConstant::I16(value) => {
-                raw[..2].copy_from_slice(&value.to_le_bytes());
-                IrType::I16
-            }
-            IrConstant::I32(value) => {
-                raw[..4].copy_from_slice(&value.to_le_bytes());
-                IrType::I32
-            }
-            IrConstant::I64(value) => {
-                raw[..8].copy_from_slice(&value.to_le_bytes());
-                IrType::I64
-            }
+        raw[..size].copy_from_slice(&self.raw[idx * size..(idx + 1) * size]);
+        u128::from_le_bytes(raw)
+    }
+
+    fn set_lane(&mut self, elem: IrType, idx: usize, bits: u128) {
+        let size = elem.size_in_bytes();
+        let bits = bits & mask(width(elem));
+        self.raw[idx * size..(idx + 1) * size].copy_from_slice(&bits.to_le_bytes()[..size]);
+    }
+
+    pub fn binary(self, op: BinaryOp, rhs: Self) -> ValueResult {
+        let ty = op.check(self.ty, rhs.ty)?;
+        let (elem, len) = lanes(op.name(), ty)?;
+
+        let mut result = Self::new(ty, 0);
+        for idx in 0..len {
+            let bits = op.apply(elem, self.lane(elem, idx), rhs.lane(elem, idx));
+            result.set_lane(elem, idx, bits);
+        }
+
+        Ok(result)
+    }
+
+    pub fn unary(self, op: UnaryOp) -> ValueResult {
+        let ty = op.check(self.ty)?;
+        let (elem, len) = lanes(op.name(), ty)?;
+
+        let mut result = Self::new(ty, 0);
+        for idx in 0..len {
+            result.set_lane(elem, idx, op.apply(elem, self.lane(elem, idx)));
+        }
+
+        Ok(result)
+    }
+
+    pub fn cast(self, op: CastOp, ty: IrType) -> ValueResult {
+        let ty = op.check(self.ty, ty)?;
+
+        let bits = match op {
+            CastOp::Zext => self.to_bits(),
+            CastOp::Sext => sign_extend(self.to_bits(), width(self.ty)) as u128,
         };
 
-        RustjitValue { raw, ty }
+        Ok(Self::new(ty, bits))
+    }
+
+    /// Zero extend the value to `ty`.
+    pub fn zext(self, ty: IrType) -> ValueResult {
+        self.cast(CastOp::Zext, ty)
+    }
+
+    /// Sign extend the value to `ty`.
+    pub fn sext(self, ty: IrType) -> ValueResult {
+        self.cast(CastOp::Sext, ty)
+    }
+
+    /// The value an atomic `op` stores over this value with the source `src`.
+    pub fn atomic(self, op: AtomicOp, src: Self) -> ValueResult {
+        let ty = check_atomic(self.ty, src.ty)?;
+        let (old, src) = (self.to_bits(), src.to_bits());
+        let less = match ty.is_signed() {
+            true => sign_extend(old, width(ty)) < sign_extend(src, width(ty)),
+            false => old < src,
+        };
+
+        let bits = match op {
+            AtomicOp::Swap => src,
+            AtomicOp::Add => old.wrapping_add(src),
+            AtomicOp::And => old & src,
+            AtomicOp::Or => old | src,
+            AtomicOp::Xor => old ^ src,
+            AtomicOp::Max if less => src,
+            AtomicOp::Min if !less => src,
+            AtomicOp::Max | AtomicOp::Min => old,
+        };
+
+        Ok(Self::new(ty, bits))
+    }
+}
+
+impl From<IrConstant> for RustjitValue {
+    fn from(value: IrConstant) -> Self {
+        Self::new(core::ir::TypeOf::ty(&value), value.to_bits())
     }
 }
 
 impl From<u64> for RustjitValue {
     fn from(value: u64) -> Self {
-        let mut raw = [0; VALUE_SIZE];
-        raw.copy_from_slice(&value.to_le_bytes()[..8]);
-
-        Self {
-            raw,
-            ty: IrType::U64,
-        }
+        Self::new(IrType::U64, value as u128)
     }
 }
 
 impl Neg for RustjitValue {
-    type Output = Self;
-
-    fn neg(mut self) -> Self::Output {
-        match self.ty {
-            IrType::Bool => {
-                let value = self.raw[0] != 0;
-                self.raw[0] = !value as u8;
-                self
-            }
-            other => {
-                let high = self.raw[other.size_in_bytes()];
-                self.raw[other.size_in_bytes()] = high ^ 0x80;
-                self
-            }
-        }
+    type Output = ValueResult;
+
+    fn neg(self) -> Self::Output {
+        self.unary(UnaryOp::Neg)
     }
 }
 
 impl Not for RustjitValue {
-    type Output = Self;
-
-    fn not(mut self) -> Self::Output {
-        match self.ty {
-            IrType::Bool => {
-                let value = self.raw[0] != 0;
-                self.raw[0] = !value as u8;
-                self
-            }
-            other => {
-                self.raw[..other.size_in_bytes()]
-                    .as_mut()
-                    .iter_mut()
-                    .for_each(|b| *b = !*b);
-                self
-            }
-        }
+    type Output = ValueResult;
+
+    fn not(self) -> Self::Output {
+        self.unary(UnaryOp::BitNot)
     }
 }
 
 macro_rules! impl_traits {
     ($(
-        [$trait_name:ident, $trait_fn:ident, $proc:ident $(, $target_ty:ty)?]
+        [$trait_name:ident, $trait_fn:ident, $op:ident]
     )*) => {
         $(
             impl $trait_name for RustjitValue {
-                type Output = Self;
-
-                fn $trait_fn(mut self, rhs: Self) -> Self::Output {
-                    match (self.ty, rhs.ty) {
-                        (IrType::I8 | IrType::I16 | IrType::I32 | IrType::I64, IrType::I8 | IrType::I16 | IrType::I32 | IrType::I64) => {
-                            let lhs = i64::from_le_bytes([
-                                self.raw[0], self.raw[1], self.raw[2], self.raw[3],
-                                self.raw[4], self.raw[5], self.raw[6], self.raw[7],
-                            ]);
-                            let rhs = i64::from_le_bytes([
-                                rhs.raw[0], rhs.raw[1], rhs.raw[2], rhs.raw[3],
-                                rhs.raw[4], rhs.raw[5], rhs.raw[6], rhs.raw[7],
-                            ]);
-                            let result = lhs.$proc(rhs $( as $target_ty)?);
-                            self.raw[..8].copy_from_slice(&result.to_le_bytes());
-                        }
-                        (IrType::I128, IrType::I8 | IrType::I16 | IrType::I32 | IrType::I64 | IrType::I128) => {
-                            let lhs = i128::from_le_bytes([
-                                self.raw[0], self.raw[1], self.raw[2], self.raw[3],
-                                self.raw[4], self.raw[5], self.raw[6], self.raw[7],
-                                self.raw[8], self.raw[9], self.raw[10], self.raw[11],
-                                self.raw[12], self.raw[13], self.raw[14], self.raw[15],
-                            ]);
-                            let rhs = i128::from_le_bytes([
-                                rhs.raw[0], rhs.raw[1], rhs.raw[2], rhs.raw[3],
-                                rhs.raw[4], rhs.raw[5], rhs.raw[6], rhs.raw[7],
-                                rhs.raw[8], rhs.raw[9], rhs.raw[10], rhs.raw[11],
-                                rhs.raw[12], rhs.raw[13], rhs.raw[14], rhs.raw[15],
-                            ]);
-                            let result = lhs.$proc(rhs $( as $target_ty)?);
-                            self.raw[..16].copy_from_slice(&result.to_le_bytes());
-                        }
-                        (IrType::U8 | IrType::U16 | IrType::U32 | IrType::U64, IrType::U8 | IrType::U16 | IrType::U32 | IrType::U64) => {
-                            let lhs = u64::from_le_bytes([
-                                self.raw[0], self.raw[1], self.raw[2], self.raw[3],
-                                self.raw[4], self.raw[5], self.raw[6], self.raw[7],
-                            ]);
-                            let rhs = u64::from_le_bytes([
-                                rhs.raw[0], rhs.raw[1], rhs.raw[2], rhs.raw[3],
-                                rhs.raw[4], rhs.raw[5], rhs.raw[6], rhs.raw[7],
-                            ]);
-                            let result = lhs.$proc(rhs $( as $target_ty)?);
-                            self.raw[..8].copy_from_slice(&result.to_le_bytes());
-                        }
-                        (IrType::U128, IrType::U8 | IrType::U16 | IrType::U32 | IrType::U64 | IrType::U128) => {
-                            let lhs = u128::from_le_bytes([
-                                self.raw[0], self.raw[1], self.raw[2], self.raw[3],
-                                self.raw[4], self.raw[5], self.raw[6], self.raw[7],
-                                self.raw[8], self.raw[9], self.raw[10], self.raw[11],
-                                self.raw[12], self.raw[13], self.raw[14], self.raw[15],
-                            ]);
-                            let rhs = u128::from_le_bytes([
-                                rhs.raw[0], rhs.raw[1], rhs.raw[2], rhs.raw[3],
-                                rhs.raw[4], rhs.raw[5], rhs.raw[6], rhs.raw[7],
-                                rhs.raw[8], rhs.raw[9], rhs.raw[10], rhs.raw[11],
-                                rhs.raw[12], rhs.raw[13], rhs.raw[14], rhs.raw[15],
-                            ]);
-                            let result = lhs.$proc(rhs $( as $target_ty)?);
-                            self.raw[..16].copy_from_slice(&result.to_le_bytes());
-                        }
-                        _ => panic!("Unsupported type for {}: {:?} and {:?}", stringify!($trait_name), self.ty, rhs.ty),
-                    }
+                type Output = ValueResult;
 
-                    self
+                fn $trait_fn(self, rhs: Self) -> Self::Output {
+                    self.binary(BinaryOp::$op, rhs)
                 }
             }
         )*
@@ -193,14 +474,264 @@ macro_rules! impl_traits {
 }
 
 impl_traits! {
-    [Add, add, wrapping_add]
-    [Sub, sub, wrapping_sub]
-    [Mul, mul, wrapping_mul]
-    [Div, div, wrapping_div]
-    [Rem, rem, wrapping_rem]
-    [BitAnd, bitand, bitand]
-    [BitOr, bitor, bitor]
-    [BitXor, bitxor, bitxor]
-    [Shl, shl, wrapping_shl, u32]
-    [Shr, shr, wrapping_shr, u32]
+    [Add, add, Add]
+    [Sub, sub, Sub]
+    [Mul, mul, Mul]
+    [Div, div, Div]
+    [Rem, rem, Rem]
+    [BitAnd, bitand, BitAnd]
+    [BitOr, bitor, BitOr]
+    [BitXor, bitxor, BitXor]
+    [Shl, shl, Shl]
+    [Shr, shr, Shr]
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ir::VecTy;
+    use utility::SplitMix64;
+
+    use super::*;
+
+    const ITERATIONS: usize = 2000;
+
+    fn random_bits(rng: &mut SplitMix64) -> u128 {
+        (rng.next_u64() as u128) << 64 | rng.next_u64() as u128
+    }
+
+    macro_rules! int_properties {
+        ($($name:ident: $native:ty, $unsigned:ty, $signed:ty => $ty:expr;)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let edges = [0, 1, <$native>::MAX, <$native>::MIN, <$native>::MAX / 2];
+                    let mut rng = SplitMix64::new(0x1234);
+                    let sample = |rng: &mut SplitMix64| match rng.below(4) {
+                        0 => edges[rng.below(edges.len() as u64) as usize],
+                        _ => random_bits(rng) as $native,
+                    };
+                    let value = |v: $native| RustjitValue::new($ty, v as u128);
+
+                    for _ in 0..ITERATIONS {
+                        let (a, b) = (sample(&mut rng), sample(&mut rng));
+                        let (l, r) = (value(a), value(b));
+                        let check = |result: ValueResult, expected: $native, op: &str| {
+                            assert_eq!(
+                                result.unwrap().to_bits(),
+                                value(expected).to_bits(),
+                                "{op} of {a} and {b}",
+                            );
+                        };
+
+                        check(l + r, a.wrapping_add(b), "add");
+                        check(l - r, a.wrapping_sub(b), "sub");
+                        check(l * r, a.wrapping_mul(b), "mul");
+                        check(l / r, if b == 0 { 0 } else { a.wrapping_div(b) }, "div");
+                        check(l % r, if b == 0 { a } else { a.wrapping_rem(b) }, "rem");
+                        check(l & r, a & b, "bitand");
+                        check(l | r, a | b, "bitor");
+                        check(l ^ r, a ^ b, "bitxor");
+                        check(l << r, a.wrapping_shl(b as u32), "shl");
+                        check(l >> r, a.wrapping_shr(b as u32), "shr");
+                        check(-l, a.wrapping_neg(), "neg");
+                        check(!l, !a, "bitnot");
+
+                        assert_eq!(l.zext(IrType::U128).unwrap().to_bits(), a as $unsigned as u128);
+                        assert_eq!(
+                            l.sext(IrType::I128).unwrap().to_bits(),
+                            a as $signed as i128 as u128,
+                        );
+                        assert_eq!(l.zext(IrType::U8).unwrap().to_bits(), a as u8 as u128);
+                    }
+                }
+            )*
+        };
+    }
+
+    int_properties! {
+        u8_matches_native: u8, u8, i8 => IrType::U8;
+        u16_matches_native: u16, u16, i16 => IrType::U16;
+        u32_matches_native: u32, u32, i32 => IrType::U32;
+        u64_matches_native: u64, u64, i64 => IrType::U64;
+        u128_matches_native: u128, u128, i128 => IrType::U128;
+        i8_matches_native: i8, u8, i8 => IrType::I8;
+        i16_matches_native: i16, u16, i16 => IrType::I16;
+        i32_matches_native: i32, u32, i32 => IrType::I32;
+        i64_matches_native: i64, u64, i64 => IrType::I64;
+        i128_matches_native: i128, u128, i128 => IrType::I128;
+    }
+
+    macro_rules! float_properties {
+        ($($name:ident: $native:ty => $ty:expr;)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let edges = [0.0, -0.0, 1.0, <$native>::INFINITY, <$native>::NAN, <$native>::MIN_POSITIVE];
+                    let mut rng = SplitMix64::new(0x5678);
+                    let sample = |rng: &mut SplitMix64| match rng.below(4) {
+                        0 => edges[rng.below(edges.len() as u64) as usize],
+                        _ => <$native>::from_bits(random_bits(rng) as _),
+                    };
+                    let value = |v: $native| RustjitValue::new($ty, v.to_bits() as u128);
+
+                    for _ in 0..ITERATIONS {
+                        let (a, b) = (sample(&mut rng), sample(&mut rng));
+                        let (l, r) = (value(a), value(b));
+                        let check = |result: ValueResult, expected: $native, op: &str| {
+                            let result = <$native>::from_bits(result.unwrap().to_bits() as _);
+                            assert!(
+                                result.to_bits() == expected.to_bits()
+                                    || result.is_nan() && expected.is_nan(),
+                                "{op} of {a} and {b}: {result} != {expected}",
+                            );
+                        };
+
+                        check(l + r, a + b, "add");
+                        check(l - r, a - b, "sub");
+                        check(l * r, a * b, "mul");
+                        check(l / r, a / b, "div");
+                        check(l % r, a % b, "rem");
+                        check(-l, -a, "neg");
+                    }
+                }
+            )*
+        };
+    }
+
+    float_properties! {
+        f32_matches_native: f32 => IrType::F32;
+        f64_matches_native: f64 => IrType::F64;
+    }
+
+    #[test]
+    fn bool_is_a_one_bit_integer() {
+        let value = |v: bool| RustjitValue::new(IrType::Bool, v as u128);
+
+        for a in [false, true] {
+            for b in [false, true] {
+                let (l, r) = (value(a), value(b));
+
+                assert_eq!((l + r).unwrap().to_bool(), a ^ b);
+                assert_eq!((l - r).unwrap().to_bool(), a ^ b);
+                assert_eq!((l * r).unwrap().to_bool(), a & b);
+                assert_eq!((l / r).unwrap().to_bool(), a & b);
+                assert_eq!((l % r).unwrap().to_bool(), a & !b);
+                assert_eq!((l & r).unwrap().to_bool(), a & b);
+                assert_eq!((l | r).unwrap().to_bool(), a | b);
+                assert_eq!((l ^ r).unwrap().to_bool(), a ^ b);
+                assert_eq!((l << r).unwrap().to_bool(), a);
+            }
+
+            assert_eq!((-value(a)).unwrap().to_bool(), a);
+            assert_eq!((!value(a)).unwrap().to_bool(), !a);
+            assert_eq!(
+                value(a).sext(IrType::U8).unwrap().to_bits(),
+                a as u128 * 0xff
+            );
+        }
+    }
+
+    #[test]
+    fn vectors_apply_the_operation_to_each_lane() {
+        let types = [
+            VecTy::U8,
+            VecTy::I8,
+            VecTy::U16,
+            VecTy::I16,
+            VecTy::U32,
+            VecTy::I32,
+            VecTy::U64,
+            VecTy::I64,
+            VecTy::U128,
+            VecTy::F32,
+            VecTy::F64,
+        ];
+        let mut rng = SplitMix64::new(0x9abc);
+
+        for elem in types {
+            let elem_ty = elem.element_type();
+            let elem_size = elem_ty.size_in_bytes();
+
+            for len in [VALUE_SIZE / elem_size, 1] {
+                let ty = IrType::Vector(elem, len as u32);
+                let lane = |value: RustjitValue, idx: usize| {
+                    let bytes = &value.as_bytes()[idx * elem_size..(idx + 1) * elem_size];
+                    RustjitValue::from_bytes(bytes, elem_ty)
+                };
+
+                for _ in 0..ITERATIONS / 10 {
+                    let l = RustjitValue::new(ty, random_bits(&mut rng));
+                    let r = RustjitValue::new(ty, random_bits(&mut rng));
+
+                    for op in [
+                        BinaryOp::Add,
+                        BinaryOp::Sub,
+                        BinaryOp::Mul,
+                        BinaryOp::Div,
+                        BinaryOp::Rem,
+                        BinaryOp::BitAnd,
+                        BinaryOp::BitOr,
+                        BinaryOp::BitXor,
+                        BinaryOp::Shl,
+                        BinaryOp::Shr,
+                    ] {
+                        if elem_ty.is_float() && matches!(op, BinaryOp::Shl | BinaryOp::Shr) {
+                            continue;
+                        }
+
+                        let result = l.binary(op, r).unwrap();
+                        for idx in 0..len {
+                            let expected = lane(l, idx).binary(op, lane(r, idx)).unwrap();
+                            assert_eq!(lane(result, idx).to_bits(), expected.to_bits());
+                        }
+                    }
+
+                    for op in [UnaryOp::Neg, UnaryOp::BitNot] {
+                        let result = l.unary(op).unwrap();
+                        for idx in 0..len {
+                            let expected = lane(l, idx).unary(op).unwrap();
+                            assert_eq!(lane(result, idx).to_bits(), expected.to_bits());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn type_mismatches_are_errors() {
+        let u32 = RustjitValue::new(IrType::U32, 1);
+        let u64 = RustjitValue::new(IrType::U64, 1);
+        let f32 = RustjitValue::new(IrType::F32, 1.0f32.to_bits() as u128);
+
+        assert_eq!(
+            (u32 + u64).unwrap_err(),
+            ValueError::TypeMismatch {
+                op: "add",
+                lhs: IrType::U32,
+                rhs: IrType::U64,
+            }
+        );
+        assert_eq!(
+            (f32 << f32).unwrap_err(),
+            ValueError::Unsupported {
+                op: "shl",
+                ty: IrType::F32,
+            }
+        );
+        assert_eq!(
+            u32.zext(IrType::Void).unwrap_err(),
+            ValueError::Unsupported {
+                op: "zext",
+                ty: IrType::Void,
+            }
+        );
+        assert_eq!(
+            BinaryOp::Add.check(IrType::Vector(VecTy::U64, 4), IrType::Vector(VecTy::U64, 4)),
+            Err(ValueError::Unsupported {
+                op: "add",
+                ty: IrType::Vector(VecTy::U64, 4),
+            })
+        );
+    }
 }
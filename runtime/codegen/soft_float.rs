@@ -0,0 +1,1325 @@
+//! IEEE 754 arithmetic in software for [`IrIntrinsic::Float`](core::ir::IrIntrinsic::Float),
+//! so every backend rounds and raises exceptions the same way whatever the host does.
+
+use core::ir::{float_control, float_status, FloatOp, FloatRounding, IrType};
+use std::cmp::Ordering;
+
+/// A binary interchange format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Format {
+    exponent_bits: u32,
+    fraction_bits: u32,
+}
+
+impl Format {
+    pub const HALF: Format = Format::new(5, 10);
+    pub const SINGLE: Format = Format::new(8, 23);
+    pub const DOUBLE: Format = Format::new(11, 52);
+
+    const fn new(exponent_bits: u32, fraction_bits: u32) -> Self {
+        Self {
+            exponent_bits,
+            fraction_bits,
+        }
+    }
+
+    /// The format of the floats carried in integers of `width` bits.
+    pub fn of_width(width: u32) -> Option<Self> {
+        match width {
+            16 => Some(Self::HALF),
+            32 => Some(Self::SINGLE),
+            64 => Some(Self::DOUBLE),
+            _ => None,
+        }
+    }
+
+    fn precision(self) -> i32 {
+        self.fraction_bits as i32 + 1
+    }
+
+    fn bias(self) -> i32 {
+        (1 << (self.exponent_bits - 1)) - 1
+    }
+
+    /// The exponent of the smallest normal number.
+    fn min_exponent(self) -> i32 {
+        1 - self.bias()
+    }
+
+    fn max_biased(self) -> u128 {
+        (1 << self.exponent_bits) - 1
+    }
+
+    fn fraction_mask(self) -> u128 {
+        (1 << self.fraction_bits) - 1
+    }
+
+    fn quiet_bit(self) -> u128 {
+        1 << (self.fraction_bits - 1)
+    }
+
+    fn sign(self, sign: bool) -> u128 {
+        (sign as u128) << (self.exponent_bits + self.fraction_bits)
+    }
+
+    fn zero(self, sign: bool) -> u128 {
+        self.sign(sign)
+    }
+
+    fn infinity(self, sign: bool) -> u128 {
+        self.sign(sign) | self.max_biased() << self.fraction_bits
+    }
+
+    fn max_finite(self, sign: bool) -> u128 {
+        self.sign(sign) | (self.max_biased() - 1) << self.fraction_bits | self.fraction_mask()
+    }
+
+    pub fn default_nan(self) -> u128 {
+        self.max_biased() << self.fraction_bits | self.quiet_bit()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Class {
+    Zero,
+    Finite,
+    Infinity,
+    QuietNaN,
+    SignalingNaN,
+}
+
+/// A float taken apart, its value is `sig * 2^exp` when it is finite.
+#[derive(Clone, Copy, Debug)]
+struct Unpacked {
+    bits: u128,
+    class: Class,
+    sign: bool,
+    exp: i32,
+    sig: u128,
+}
+
+impl Unpacked {
+    fn is_nan(&self) -> bool {
+        matches!(self.class, Class::QuietNaN | Class::SignalingNaN)
+    }
+
+    /// The exponent of the most significant bit of a finite value.
+    fn top(&self) -> i32 {
+        self.exp + bit_length(self.sig) - 1
+    }
+
+    fn with_sign(self, sign: bool) -> Self {
+        Self { sign, ..self }
+    }
+
+    fn infinity(sign: bool) -> Self {
+        Self {
+            bits: 0,
+            class: Class::Infinity,
+            sign,
+            exp: 0,
+            sig: 0,
+        }
+    }
+}
+
+/// What was shifted out below the kept bits, relative to half of their last bit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Tail {
+    Zero,
+    BelowHalf,
+    Half,
+    AboveHalf,
+}
+
+fn bit_length(value: u128) -> i32 {
+    128 - value.leading_zeros() as i32
+}
+
+/// `sig >> shift` and the tail shifted out, a negative shift moves left.
+fn shift_right(sig: u128, shift: i32) -> (u128, Tail) {
+    if shift <= 0 {
+        return (sig << -shift, Tail::Zero);
+    }
+    if shift > 128 {
+        let tail = if sig == 0 {
+            Tail::Zero
+        } else {
+            Tail::BelowHalf
+        };
+        return (0, tail);
+    }
+
+    let kept = sig.checked_shr(shift as u32).unwrap_or(0);
+    let rest = sig & (u128::MAX >> (128 - shift));
+    let half = 1 << (shift - 1);
+    let tail = match rest.cmp(&half) {
+        _ if rest == 0 => Tail::Zero,
+        Ordering::Less => Tail::BelowHalf,
+        Ordering::Equal => Tail::Half,
+        Ordering::Greater => Tail::AboveHalf,
+    };
+
+    (kept, tail)
+}
+
+/// `sig >> shift` with the bits shifted out ORed into the lowest bit.
+fn shift_right_jamming(sig: u128, shift: i32) -> u128 {
+    let (kept, tail) = shift_right(sig, shift);
+    kept | (tail != Tail::Zero) as u128
+}
+
+fn rounds_up(rounding: FloatRounding, sign: bool, odd: bool, tail: Tail) -> bool {
+    match rounding {
+        FloatRounding::TiesToEven => tail > Tail::Half || (tail == Tail::Half && odd),
+        FloatRounding::TiesToAway => tail >= Tail::Half,
+        FloatRounding::TowardPositive => tail != Tail::Zero && !sign,
+        FloatRounding::TowardNegative => tail != Tail::Zero && sign,
+        FloatRounding::TowardZero => false,
+        FloatRounding::ToOdd => tail != Tail::Zero && !odd,
+    }
+}
+
+fn isqrt(value: u128) -> u128 {
+    let mut root = 0;
+    let mut rest = value;
+    let mut bit = 1 << ((bit_length(value) - 1) & !1);
+
+    while bit != 0 {
+        if rest >= root + bit {
+            rest -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+
+    root
+}
+
+/// The state of one operation.
+struct Env {
+    rounding: FloatRounding,
+    flush: bool,
+    default_nan: bool,
+    status: u64,
+}
+
+impl Env {
+    fn new(control: u64) -> Self {
+        let rounding = match (control >> float_control::ROUNDING) & 0b11 {
+            0 => FloatRounding::TiesToEven,
+            1 => FloatRounding::TowardPositive,
+            2 => FloatRounding::TowardNegative,
+            _ => FloatRounding::TowardZero,
+        };
+
+        Self {
+            rounding,
+            flush: control >> float_control::FLUSH_TO_ZERO & 1 != 0,
+            default_nan: control >> float_control::DEFAULT_NAN & 1 != 0,
+            status: 0,
+        }
+    }
+
+    fn raise(&mut self, flag: u64) {
+        self.status |= 1 << flag;
+    }
+
+    fn unpack(&mut self, bits: u128, format: Format) -> Unpacked {
+        let sign = bits & format.sign(true) != 0;
+        let biased = (bits >> format.fraction_bits) & format.max_biased();
+        let fraction = bits & format.fraction_mask();
+
+        let (class, exp, sig) = match (biased, fraction) {
+            (0, 0) => (Class::Zero, 0, 0),
+            (0, _) if self.flush => {
+                self.raise(float_status::INPUT_DENORMAL);
+                (Class::Zero, 0, 0)
+            }
+            (0, _) => (
+                Class::Finite,
+                format.min_exponent() - format.fraction_bits as i32,
+                fraction,
+            ),
+            (biased, 0) if biased == format.max_biased() => (Class::Infinity, 0, 0),
+            (biased, _) if biased == format.max_biased() => match fraction & format.quiet_bit() {
+                0 => (Class::SignalingNaN, 0, 0),
+                _ => (Class::QuietNaN, 0, 0),
+            },
+            (biased, _) => (
+                Class::Finite,
+                biased as i32 - format.bias() - format.fraction_bits as i32,
+                fraction | 1 << format.fraction_bits,
+            ),
+        };
+
+        let bits = match class {
+            Class::Zero => format.zero(sign),
+            _ => bits,
+        };
+
+        Unpacked {
+            bits,
+            class,
+            sign,
+            exp,
+            sig,
+        }
+    }
+
+    /// Round `sig * 2^exp` to `format` with the rounding mode of the operation.
+    fn round(&mut self, format: Format, sign: bool, exp: i32, sig: u128) -> u128 {
+        self.round_with(format, self.rounding, sign, exp, sig)
+    }
+
+    fn round_with(
+        &mut self,
+        format: Format,
+        rounding: FloatRounding,
+        sign: bool,
+        exp: i32,
+        sig: u128,
+    ) -> u128 {
+        if sig == 0 {
+            return format.zero(sign);
+        }
+
+        let top = exp + bit_length(sig) - 1;
+        let tiny = top < format.min_exponent();
+        if tiny && self.flush {
+            self.raise(float_status::UNDERFLOW);
+            return format.zero(sign);
+        }
+
+        let mut lsb = top.max(format.min_exponent()) - (format.precision() - 1);
+        let (mut kept, tail) = shift_right(sig, lsb - exp);
+        if rounds_up(rounding, sign, kept & 1 != 0, tail) {
+            kept += 1;
+        }
+        if kept >> format.precision() != 0 {
+            kept >>= 1;
+            lsb += 1;
+        }
+
+        if tail != Tail::Zero {
+            self.raise(float_status::INEXACT);
+            if tiny {
+                self.raise(float_status::UNDERFLOW);
+            }
+        }
+
+        let biased = match kept >> (format.precision() - 1) {
+            0 => 0,
+            _ => (lsb + format.precision() - 1 + format.bias()) as u128,
+        };
+        if biased >= format.max_biased() {
+            self.raise(float_status::OVERFLOW);
+            self.raise(float_status::INEXACT);
+
+            let to_infinity = match rounding {
+                FloatRounding::TiesToEven | FloatRounding::TiesToAway => true,
+                FloatRounding::TowardPositive => !sign,
+                FloatRounding::TowardNegative => sign,
+                FloatRounding::TowardZero | FloatRounding::ToOdd => false,
+            };
+            return match to_infinity {
+                true => format.infinity(sign),
+                false => format.max_finite(sign),
+            };
+        }
+
+        format.sign(sign) | biased << format.fraction_bits | (kept & format.fraction_mask())
+    }
+
+    /// The exact zero of a sum that cancelled out.
+    fn cancelled_zero(&self, format: Format) -> u128 {
+        format.zero(self.rounding == FloatRounding::TowardNegative)
+    }
+
+    fn invalid(&mut self, format: Format) -> u128 {
+        self.raise(float_status::INVALID);
+        format.default_nan()
+    }
+
+    fn process_nan(&mut self, op: Unpacked, format: Format) -> u128 {
+        let bits = match op.class {
+            Class::SignalingNaN => {
+                self.raise(float_status::INVALID);
+                op.bits | format.quiet_bit()
+            }
+            _ => op.bits,
+        };
+
+        match self.default_nan {
+            true => format.default_nan(),
+            false => bits,
+        }
+    }
+
+    /// The NaN result of an operation on `ops`, if one of them is a NaN.
+    fn process_nans(&mut self, ops: &[Unpacked], format: Format) -> Option<u128> {
+        let nan = ops
+            .iter()
+            .find(|op| op.class == Class::SignalingNaN)
+            .or_else(|| ops.iter().find(|op| op.class == Class::QuietNaN))?;
+
+        Some(self.process_nan(*nan, format))
+    }
+
+    fn add(&mut self, format: Format, a: Unpacked, b: Unpacked) -> u128 {
+        if let Some(nan) = self.process_nans(&[a, b], format) {
+            return nan;
+        }
+
+        match (a.class, b.class) {
+            (Class::Infinity, Class::Infinity) if a.sign != b.sign => self.invalid(format),
+            (Class::Infinity, _) => format.infinity(a.sign),
+            (_, Class::Infinity) => format.infinity(b.sign),
+            (Class::Zero, Class::Zero) if a.sign == b.sign => format.zero(a.sign),
+            _ => self.sum(format, a, b),
+        }
+    }
+
+    /// Round the exact sum of two finite or zero values.
+    fn sum(&mut self, format: Format, a: Unpacked, b: Unpacked) -> u128 {
+        if a.sig == 0 && b.sig == 0 {
+            return self.cancelled_zero(format);
+        }
+        if b.sig == 0 {
+            return self.round(format, a.sign, a.exp, a.sig);
+        }
+        if a.sig == 0 {
+            return self.round(format, b.sign, b.exp, b.sig);
+        }
+
+        // Move the larger value to the top of the significand, the smaller one can only
+        // fall off the bottom when the result has far more bits than any format keeps, so
+        // the bits lost are jammed into the lowest one.
+        let (big, small) = match a.top() >= b.top() {
+            true => (a, b),
+            false => (b, a),
+        };
+        let shift = 124 - (bit_length(big.sig) - 1);
+        let exp = big.exp - shift;
+        let big_sig = big.sig << shift;
+        let small_sig = shift_right_jamming(small.sig, exp - small.exp);
+
+        let (sign, sig) = match (big.sign == small.sign, big_sig >= small_sig) {
+            (true, _) => (big.sign, big_sig + small_sig),
+            (false, true) => (big.sign, big_sig - small_sig),
+            (false, false) => (small.sign, small_sig - big_sig),
+        };
+
+        match sig {
+            0 => self.cancelled_zero(format),
+            _ => self.round(format, sign, exp, sig),
+        }
+    }
+
+    fn mul(&mut self, format: Format, a: Unpacked, b: Unpacked) -> u128 {
+        if let Some(nan) = self.process_nans(&[a, b], format) {
+            return nan;
+        }
+
+        let sign = a.sign != b.sign;
+        match (a.class, b.class) {
+            (Class::Infinity, Class::Zero) | (Class::Zero, Class::Infinity) => self.invalid(format),
+            (Class::Infinity, _) | (_, Class::Infinity) => format.infinity(sign),
+            (Class::Zero, _) | (_, Class::Zero) => format.zero(sign),
+            _ => self.round(format, sign, a.exp + b.exp, a.sig * b.sig),
+        }
+    }
+
+    fn mul_add(&mut self, format: Format, addend: Unpacked, a: Unpacked, b: Unpacked) -> u128 {
+        let invalid_product = matches!(
+            (a.class, b.class),
+            (Class::Infinity, Class::Zero) | (Class::Zero, Class::Infinity)
+        );
+
+        if let Some(nan) = self.process_nans(&[addend, a, b], format) {
+            // A quiet NaN addend doesn't hide the invalid product
+            if addend.class == Class::QuietNaN && invalid_product {
+                return self.invalid(format);
+            }
+            return nan;
+        }
+
+        let product_sign = a.sign != b.sign;
+        let product_infinite = a.class == Class::Infinity || b.class == Class::Infinity;
+        let product_zero = a.class == Class::Zero || b.class == Class::Zero;
+        let addend_infinite = addend.class == Class::Infinity;
+
+        if invalid_product || (addend_infinite && product_infinite && addend.sign != product_sign) {
+            return self.invalid(format);
+        }
+        if addend_infinite {
+            return format.infinity(addend.sign);
+        }
+        if product_infinite {
+            return format.infinity(product_sign);
+        }
+        if addend.class == Class::Zero && product_zero && addend.sign == product_sign {
+            return format.zero(addend.sign);
+        }
+
+        let product = Unpacked {
+            bits: 0,
+            class: Class::Finite,
+            sign: product_sign,
+            exp: a.exp + b.exp,
+            sig: a.sig * b.sig,
+        };
+        self.sum(format, addend, product)
+    }
+
+    fn div(&mut self, format: Format, a: Unpacked, b: Unpacked) -> u128 {
+        if let Some(nan) = self.process_nans(&[a, b], format) {
+            return nan;
+        }
+
+        let sign = a.sign != b.sign;
+        match (a.class, b.class) {
+            (Class::Infinity, Class::Infinity) | (Class::Zero, Class::Zero) => self.invalid(format),
+            (Class::Infinity, _) => format.infinity(sign),
+            (_, Class::Zero) => {
+                self.raise(float_status::DIVIDE_BY_ZERO);
+                format.infinity(sign)
+            }
+            (Class::Zero, _) | (_, Class::Infinity) => format.zero(sign),
+            _ => {
+                // The quotient keeps at least 70 bits, the remainder is jammed below them
+                let shift = 125 - (bit_length(a.sig) - 1);
+                let dividend = a.sig << shift;
+                let quotient = dividend / b.sig;
+                let sig = quotient | !dividend.is_multiple_of(b.sig) as u128;
+
+                self.round(format, sign, a.exp - shift - b.exp, sig)
+            }
+        }
+    }
+
+    fn sqrt(&mut self, format: Format, a: Unpacked) -> u128 {
+        match a.class {
+            Class::QuietNaN | Class::SignalingNaN => self.process_nan(a, format),
+            Class::Zero => format.zero(a.sign),
+            _ if a.sign => self.invalid(format),
+            Class::Infinity => format.infinity(false),
+            Class::Finite => {
+                let shift = 124 - (bit_length(a.sig) - 1);
+                let (mut exp, mut sig) = (a.exp - shift, a.sig << shift);
+                if exp & 1 != 0 {
+                    exp -= 1;
+                    sig <<= 1;
+                }
+
+                let root = isqrt(sig);
+                let root = root | (root * root != sig) as u128;
+                self.round(format, false, exp >> 1, root)
+            }
+        }
+    }
+
+    /// How `a` compares to `b`, neither of them is a NaN.
+    fn compare_values(a: Unpacked, b: Unpacked) -> Ordering {
+        let magnitude = |op: &Unpacked| match op.class {
+            Class::Zero => (0, 0, 0),
+            Class::Infinity => (2, 0, 0),
+            _ => (1, op.top(), op.sig << (128 - bit_length(op.sig))),
+        };
+
+        match (a.class, b.class) {
+            (Class::Zero, Class::Zero) => Ordering::Equal,
+            _ if a.sign != b.sign && a.class == Class::Zero => match b.sign {
+                true => Ordering::Greater,
+                false => Ordering::Less,
+            },
+            _ if a.sign != b.sign && b.class == Class::Zero => match a.sign {
+                true => Ordering::Less,
+                false => Ordering::Greater,
+            },
+            _ if a.sign != b.sign => match a.sign {
+                true => Ordering::Less,
+                false => Ordering::Greater,
+            },
+            _ => {
+                let ordering = magnitude(&a).cmp(&magnitude(&b));
+                match a.sign {
+                    true => ordering.reverse(),
+                    false => ordering,
+                }
+            }
+        }
+    }
+
+    fn compare(&mut self, a: Unpacked, b: Unpacked, signaling: bool) -> u128 {
+        if a.is_nan() || b.is_nan() {
+            let signaling_nan = a.class == Class::SignalingNaN || b.class == Class::SignalingNaN;
+            if signaling || signaling_nan {
+                self.raise(float_status::INVALID);
+            }
+            return 3;
+        }
+
+        match Self::compare_values(a, b) {
+            Ordering::Equal => 0,
+            Ordering::Less => 1,
+            Ordering::Greater => 2,
+        }
+    }
+
+    fn max_min(&mut self, format: Format, a: Unpacked, b: Unpacked, max: bool, num: bool) -> u128 {
+        let (mut a, mut b) = (a, b);
+        if num {
+            // A quiet NaN against a number acts as the infinity that loses
+            match (a.class, b.class) {
+                (Class::QuietNaN, class) if !matches!(class, Class::QuietNaN) => {
+                    a = Unpacked::infinity(max)
+                }
+                (class, Class::QuietNaN) if !matches!(class, Class::QuietNaN) => {
+                    b = Unpacked::infinity(max)
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(nan) = self.process_nans(&[a, b], format) {
+            return nan;
+        }
+
+        let first = match max {
+            true => Self::compare_values(a, b) == Ordering::Greater,
+            false => Self::compare_values(a, b) == Ordering::Less,
+        };
+        let chosen = if first { a } else { b };
+
+        match chosen.class {
+            Class::Infinity => format.infinity(chosen.sign),
+            Class::Zero => match max {
+                true => format.zero(a.sign && b.sign),
+                false => format.zero(a.sign || b.sign),
+            },
+            _ => self.round(format, chosen.sign, chosen.exp, chosen.sig),
+        }
+    }
+
+    fn round_int(
+        &mut self,
+        format: Format,
+        a: Unpacked,
+        rounding: FloatRounding,
+        exact: bool,
+    ) -> u128 {
+        match a.class {
+            Class::QuietNaN | Class::SignalingNaN => return self.process_nan(a, format),
+            Class::Zero | Class::Infinity => return a.bits,
+            Class::Finite if a.exp >= 0 => return a.bits,
+            Class::Finite => {}
+        }
+
+        let (mut integer, tail) = shift_right(a.sig, -a.exp);
+        if rounds_up(rounding, a.sign, integer & 1 != 0, tail) {
+            integer += 1;
+        }
+        if exact && tail != Tail::Zero {
+            self.raise(float_status::INEXACT);
+        }
+
+        self.round(format, a.sign, 0, integer)
+    }
+
+    fn convert_to_int(
+        &mut self,
+        a: Unpacked,
+        signed: bool,
+        fbits: u8,
+        rounding: FloatRounding,
+        width: u32,
+    ) -> u128 {
+        let (min, max) = match signed {
+            true => (-(1i128 << (width - 1)), (1i128 << (width - 1)) - 1),
+            false => (0, (1i128 << width) - 1),
+        };
+        let saturated = |sign: bool| if sign { min } else { max };
+
+        let (value, tail) = match a.class {
+            Class::QuietNaN | Class::SignalingNaN => (None, Tail::Zero),
+            Class::Infinity => (Some(if a.sign { min - 1 } else { max + 1 }), Tail::Zero),
+            Class::Zero => (Some(0), Tail::Zero),
+            Class::Finite => {
+                let exp = a.exp + fbits as i32;
+                let (mut integer, tail) = match exp + bit_length(a.sig) > 100 {
+                    // Saturates whatever the rounding
+                    true => (1 << 100, Tail::Zero),
+                    false => shift_right(a.sig, -exp),
+                };
+                if rounds_up(rounding, a.sign, integer & 1 != 0, tail) {
+                    integer += 1;
+                }
+
+                let integer = integer as i128;
+                (Some(if a.sign { -integer } else { integer }), tail)
+            }
+        };
+
+        let result = match value {
+            Some(value) if (min..=max).contains(&value) => {
+                if tail != Tail::Zero {
+                    self.raise(float_status::INEXACT);
+                }
+                value
+            }
+            Some(value) => {
+                self.raise(float_status::INVALID);
+                saturated(value < 0)
+            }
+            None => {
+                self.raise(float_status::INVALID);
+                0
+            }
+        };
+
+        result as u128 & (u128::MAX >> (128 - width))
+    }
+
+    fn convert_from_int(
+        &mut self,
+        format: Format,
+        bits: u128,
+        signed: bool,
+        fbits: u8,
+        width: u32,
+    ) -> u128 {
+        let negative = signed && bits >> (width - 1) & 1 != 0;
+        let magnitude = match negative {
+            true => (bits | u128::MAX << width).wrapping_neg(),
+            false => bits,
+        };
+
+        match magnitude {
+            0 => format.zero(false),
+            _ => self.round(format, negative, -(fbits as i32), magnitude),
+        }
+    }
+
+    fn convert(&mut self, a: Unpacked, from: Format, to: Format, rounding: FloatRounding) -> u128 {
+        match a.class {
+            Class::QuietNaN | Class::SignalingNaN => {
+                if a.class == Class::SignalingNaN {
+                    self.raise(float_status::INVALID);
+                }
+                if self.default_nan {
+                    return to.default_nan();
+                }
+
+                // The payload keeps its top bits
+                let fraction = (a.bits & from.fraction_mask()) << 64 >> from.fraction_bits;
+                let fraction = fraction << to.fraction_bits >> 64;
+                to.infinity(a.sign) | fraction | to.quiet_bit()
+            }
+            Class::Infinity => to.infinity(a.sign),
+            Class::Zero => to.zero(a.sign),
+            Class::Finite => self.round_with(to, rounding, a.sign, a.exp, a.sig),
+        }
+    }
+
+    /// `a * b`, an infinity times a zero is 2 with the sign of the product.
+    fn mul_extended(&mut self, format: Format, a: Unpacked, b: Unpacked) -> u128 {
+        match (a.class, b.class) {
+            (Class::Infinity, Class::Zero) | (Class::Zero, Class::Infinity) => {
+                self.round(format, a.sign != b.sign, 1, 1)
+            }
+            _ => self.mul(format, a, b),
+        }
+    }
+
+    /// `(constant + a * b) * 2^scale` rounded once, where an infinity times a zero is zero.
+    fn step(
+        &mut self,
+        format: Format,
+        a: Unpacked,
+        b: Unpacked,
+        constant: u128,
+        scale: i32,
+    ) -> u128 {
+        if let Some(nan) = self.process_nans(&[a, b], format) {
+            return nan;
+        }
+
+        let sign = a.sign != b.sign;
+        match (a.class, b.class) {
+            (Class::Infinity, Class::Zero) | (Class::Zero, Class::Infinity) => {
+                self.round(format, false, scale, constant)
+            }
+            (Class::Infinity, _) | (_, Class::Infinity) => format.infinity(sign),
+            (Class::Zero, _) | (_, Class::Zero) => self.round(format, false, scale, constant),
+            _ => {
+                let constant = Unpacked {
+                    bits: 0,
+                    class: Class::Finite,
+                    sign: false,
+                    exp: scale,
+                    sig: constant,
+                };
+                let product = Unpacked {
+                    bits: 0,
+                    class: Class::Finite,
+                    sign,
+                    exp: a.exp + b.exp + scale,
+                    sig: a.sig * b.sig,
+                };
+                self.sum(format, constant, product)
+            }
+        }
+    }
+
+    /// The fraction of a finite `a` widened to 52 bits and its biased exponent, as the
+    /// estimates of the architecture take them apart.
+    fn estimate_fields(a: Unpacked, format: Format) -> (u128, i32) {
+        let fraction = (a.bits & format.fraction_mask()) << (52 - format.fraction_bits);
+        let biased = (a.bits >> format.fraction_bits) & format.max_biased();
+        (fraction, biased as i32)
+    }
+
+    fn recip_estimate(&mut self, format: Format, a: Unpacked) -> u128 {
+        let fraction_mask = (1 << 52) - 1;
+        match a.class {
+            Class::QuietNaN | Class::SignalingNaN => return self.process_nan(a, format),
+            Class::Infinity => return format.zero(a.sign),
+            Class::Zero => {
+                self.raise(float_status::DIVIDE_BY_ZERO);
+                return format.infinity(a.sign);
+            }
+            // The reciprocal overflows
+            Class::Finite if a.top() < -format.bias() - 1 => {
+                self.raise(float_status::OVERFLOW);
+                self.raise(float_status::INEXACT);
+                let to_infinity = match self.rounding {
+                    FloatRounding::TowardPositive => !a.sign,
+                    FloatRounding::TowardNegative => a.sign,
+                    FloatRounding::TowardZero | FloatRounding::ToOdd => false,
+                    FloatRounding::TiesToEven | FloatRounding::TiesToAway => true,
+                };
+                return match to_infinity {
+                    true => format.infinity(a.sign),
+                    false => format.max_finite(a.sign),
+                };
+            }
+            // The reciprocal is subnormal, half precision doesn't flush it
+            Class::Finite
+                if self.flush && a.top() >= format.bias() - 1 && format != Format::HALF =>
+            {
+                self.raise(float_status::UNDERFLOW);
+                return format.zero(a.sign);
+            }
+            Class::Finite => {}
+        }
+
+        // Scale to a fixed point value in [0.5, 1) in steps of 1/512
+        let (mut fraction, exp) = Self::estimate_fields(a, format);
+        let mut exp = exp;
+        if exp == 0 {
+            match fraction >> 51 & 1 {
+                0 => {
+                    exp = -1;
+                    fraction = fraction << 2 & fraction_mask;
+                }
+                _ => fraction = fraction << 1 & fraction_mask,
+            }
+        }
+
+        let estimate = recip_estimate(1 << 8 | fraction >> 44);
+        let mut result_exp = 2 * format.bias() - 1 - exp;
+        let mut fraction = (estimate & 0xff) << 44;
+        if result_exp == 0 {
+            fraction = 1 << 51 | fraction >> 1;
+        } else if result_exp == -1 {
+            fraction = 1 << 50 | fraction >> 2;
+            result_exp = 0;
+        }
+
+        format.sign(a.sign)
+            | (result_exp as u128) << format.fraction_bits
+            | fraction >> (52 - format.fraction_bits)
+    }
+
+    fn rsqrt_estimate(&mut self, format: Format, a: Unpacked) -> u128 {
+        let fraction_mask = (1 << 52) - 1;
+        match a.class {
+            Class::QuietNaN | Class::SignalingNaN => return self.process_nan(a, format),
+            Class::Zero => {
+                self.raise(float_status::DIVIDE_BY_ZERO);
+                return format.infinity(a.sign);
+            }
+            _ if a.sign => return self.invalid(format),
+            Class::Infinity => return format.zero(false),
+            Class::Finite => {}
+        }
+
+        // Scale to a fixed point value in [0.25, 1) and make the exponent even
+        let (mut fraction, mut exp) = Self::estimate_fields(a, format);
+        if exp == 0 {
+            while fraction >> 51 & 1 == 0 {
+                fraction = fraction << 1 & fraction_mask;
+                exp -= 1;
+            }
+            fraction = fraction << 1 & fraction_mask;
+        }
+        let scaled = match exp & 1 {
+            0 => 1 << 8 | fraction >> 44,
+            _ => 1 << 7 | fraction >> 45,
+        };
+
+        let estimate = rsqrt_estimate(scaled);
+        let result_exp = (3 * format.bias() - 1 - exp).div_euclid(2);
+        (result_exp as u128) << format.fraction_bits
+            | (estimate & 0xff) << (format.fraction_bits - 8)
+    }
+}
+
+/// The reciprocal of the 9-bit fixed point `a` in [0.5, 1) as a 9-bit fixed point value in
+/// [1, 2), the RecipEstimate of Arm also behind URECPE.
+fn recip_estimate(a: u128) -> u128 {
+    let a = a * 2 + 1;
+    let b: u128 = (1 << 19) / a;
+    b.div_ceil(2)
+}
+
+/// The reciprocal square root of the 9-bit fixed point `a` in [0.25, 1) as a 9-bit fixed point
+/// value in [1, 2), the RecipSqrtEstimate of Arm also behind URSQRTE.
+fn rsqrt_estimate(a: u128) -> u128 {
+    let a = match a < 256 {
+        true => a * 2 + 1,
+        false => ((a >> 1) << 1) * 2 + 2,
+    };
+
+    let mut b = 512;
+    while a * (b + 1) * (b + 1) < 1 << 28 {
+        b += 1;
+    }
+    b.div_ceil(2)
+}
+
+/// Check the types of a float intrinsic, see [`IrIntrinsic::Float`](core::ir::IrIntrinsic::Float).
+pub fn check(op: FloatOp, srcs: &[IrType], dst: IrType) -> Result<(), String> {
+    let float = |ty: IrType| matches!(ty, IrType::U16 | IrType::U32 | IrType::U64);
+    let int = |ty: IrType| matches!(ty, IrType::U8 | IrType::U16 | IrType::U32 | IrType::U64);
+
+    if srcs.len() != op.arity() {
+        return Err(format!("{op:?} takes {} sources", op.arity()));
+    }
+    if srcs.windows(2).any(|pair| pair[0] != pair[1]) {
+        return Err(format!("the sources of {op:?} have different types"));
+    }
+
+    let (src_ok, dst_ok) = match op {
+        FloatOp::Compare { .. } | FloatOp::ToInt { .. } => (float(srcs[0]), int(dst)),
+        FloatOp::FromInt { .. } => (int(srcs[0]), float(dst)),
+        FloatOp::Convert { .. } => (float(srcs[0]), float(dst)),
+        _ => (float(srcs[0]), srcs[0] == dst),
+    };
+
+    match (src_ok, dst_ok) {
+        (true, true) => Ok(()),
+        _ => Err(format!(
+            "{op:?} is not defined from {:?} to {dst:?}",
+            srcs[0]
+        )),
+    }
+}
+
+/// Evaluate `op` on the raw bits of `srcs`, returning the raw bits of the result and the
+/// [`float_status`] bits of the exceptions raised.
+///
+/// The sources are `src_width` bits wide and the result `dst_width` bits, the widths select
+/// the formats or the integers of conversions.
+pub fn evaluate(
+    op: FloatOp,
+    srcs: &[u128],
+    src_width: u32,
+    dst_width: u32,
+    control: u64,
+) -> (u128, u64) {
+    assert_eq!(
+        srcs.len(),
+        op.arity(),
+        "{op:?} takes {} sources",
+        op.arity()
+    );
+
+    let mut env = Env::new(control);
+    let format = |width| {
+        Format::of_width(width).unwrap_or_else(|| panic!("no float format is {width} bits wide"))
+    };
+    let unpack = |env: &mut Env, idx: usize| env.unpack(srcs[idx], format(src_width));
+
+    let result = match op {
+        FloatOp::Add | FloatOp::Sub | FloatOp::Mul | FloatOp::Div => {
+            let (a, b) = (unpack(&mut env, 0), unpack(&mut env, 1));
+            let format = format(dst_width);
+
+            match op {
+                FloatOp::Add => env.add(format, a, b),
+                // The sign of a NaN subtrahend is kept
+                FloatOp::Sub if b.is_nan() => env.add(format, a, b),
+                FloatOp::Sub => env.add(format, a, b.with_sign(!b.sign)),
+                FloatOp::Mul => env.mul(format, a, b),
+                _ => env.div(format, a, b),
+            }
+        }
+        FloatOp::MulAdd => {
+            let addend = unpack(&mut env, 0);
+            let (a, b) = (unpack(&mut env, 1), unpack(&mut env, 2));
+            env.mul_add(format(dst_width), addend, a, b)
+        }
+        FloatOp::Sqrt => {
+            let a = unpack(&mut env, 0);
+            env.sqrt(format(dst_width), a)
+        }
+        FloatOp::Max | FloatOp::Min | FloatOp::MaxNum | FloatOp::MinNum => {
+            let (a, b) = (unpack(&mut env, 0), unpack(&mut env, 1));
+            let max = matches!(op, FloatOp::Max | FloatOp::MaxNum);
+            let num = matches!(op, FloatOp::MaxNum | FloatOp::MinNum);
+            env.max_min(format(dst_width), a, b, max, num)
+        }
+        FloatOp::RoundInt { rounding, exact } => {
+            let a = unpack(&mut env, 0);
+            let rounding = rounding.unwrap_or(env.rounding);
+            env.round_int(format(dst_width), a, rounding, exact)
+        }
+        FloatOp::Compare { signaling } => {
+            let (a, b) = (unpack(&mut env, 0), unpack(&mut env, 1));
+            env.compare(a, b, signaling)
+        }
+        FloatOp::Convert { rounding } => {
+            let a = unpack(&mut env, 0);
+            let rounding = rounding.unwrap_or(env.rounding);
+            env.convert(a, format(src_width), format(dst_width), rounding)
+        }
+        FloatOp::ToInt {
+            signed,
+            fbits,
+            rounding,
+        } => {
+            let a = unpack(&mut env, 0);
+            let rounding = rounding.unwrap_or(env.rounding);
+            env.convert_to_int(a, signed, fbits, rounding, dst_width)
+        }
+        FloatOp::FromInt { signed, fbits } => {
+            env.convert_from_int(format(dst_width), srcs[0], signed, fbits, src_width)
+        }
+        FloatOp::MulExtended | FloatOp::RecipStep | FloatOp::RSqrtStep => {
+            let (a, b) = (unpack(&mut env, 0), unpack(&mut env, 1));
+            let format = format(dst_width);
+
+            match op {
+                FloatOp::MulExtended => env.mul_extended(format, a, b),
+                FloatOp::RecipStep => env.step(format, a, b, 2, 0),
+                _ => env.step(format, a, b, 3, -1),
+            }
+        }
+        FloatOp::RecipEstimate => {
+            let a = unpack(&mut env, 0);
+            env.recip_estimate(format(dst_width), a)
+        }
+        FloatOp::RSqrtEstimate => {
+            let a = unpack(&mut env, 0);
+            env.rsqrt_estimate(format(dst_width), a)
+        }
+    };
+
+    (result, env.status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utility::SplitMix64;
+
+    const ITERATIONS: usize = 20000;
+
+    fn f32_op(op: FloatOp, srcs: &[f32]) -> (f32, u64) {
+        let srcs: Vec<_> = srcs.iter().map(|src| src.to_bits() as u128).collect();
+        let (bits, status) = evaluate(op, &srcs, 32, 32, 0);
+        (f32::from_bits(bits as u32), status)
+    }
+
+    fn f64_op(op: FloatOp, srcs: &[f64]) -> (f64, u64) {
+        let srcs: Vec<_> = srcs.iter().map(|src| src.to_bits() as u128).collect();
+        let (bits, status) = evaluate(op, &srcs, 64, 64, 0);
+        (f64::from_bits(bits as u64), status)
+    }
+
+    /// Random floats with every class and exponent equally likely.
+    fn random_f64(rng: &mut SplitMix64) -> f64 {
+        let bits = rng.next_u64();
+        match rng.next_u64() % 8 {
+            0 => f64::from_bits(bits & 0x800f_ffff_ffff_ffff),
+            1 => [0.0, -0.0, f64::INFINITY, f64::NEG_INFINITY][bits as usize % 4],
+            2 => f64::from_bits(bits | 0x7ff0_0000_0000_0000),
+            _ => f64::from_bits(bits),
+        }
+    }
+
+    fn same(a: f64, b: f64) -> bool {
+        a.to_bits() == b.to_bits() || (a.is_nan() && b.is_nan())
+    }
+
+    #[test]
+    fn arithmetic_matches_the_host() {
+        let mut rng = SplitMix64::new(0);
+
+        for _ in 0..ITERATIONS {
+            let (a, b, c) = (
+                random_f64(&mut rng),
+                random_f64(&mut rng),
+                random_f64(&mut rng),
+            );
+            let cases = [
+                (FloatOp::Add, a + b),
+                (FloatOp::Sub, a - b),
+                (FloatOp::Mul, a * b),
+                (FloatOp::Div, a / b),
+                (FloatOp::MulAdd, b.mul_add(c, a)),
+            ];
+
+            for (op, expected) in cases {
+                let (result, _) = f64_op(op, &[a, b, c][..op.arity()]);
+                assert!(
+                    same(result, expected),
+                    "{op:?} {a:e} {b:e} {c:e}: {result:e}"
+                );
+            }
+
+            let (result, _) = f64_op(FloatOp::Sqrt, &[a]);
+            assert!(same(result, a.sqrt()), "sqrt {a:e}: {result:e}");
+
+            let (result, _) = f32_op(FloatOp::Add, &[a as f32, b as f32]);
+            assert!(same(result as f64, (a as f32 + b as f32) as f64));
+            let (result, _) = f32_op(FloatOp::Div, &[a as f32, b as f32]);
+            assert!(same(result as f64, (a as f32 / b as f32) as f64));
+        }
+    }
+
+    #[test]
+    fn conversions_match_the_host() {
+        let mut rng = SplitMix64::new(1);
+
+        for _ in 0..ITERATIONS {
+            let a = random_f64(&mut rng);
+            let (bits, _) = evaluate(
+                FloatOp::Convert { rounding: None },
+                &[a.to_bits() as u128],
+                64,
+                32,
+                0,
+            );
+            assert!(same(f32::from_bits(bits as u32) as f64, a as f32 as f64));
+
+            let to_int = FloatOp::ToInt {
+                signed: true,
+                fbits: 0,
+                rounding: Some(FloatRounding::TowardZero),
+            };
+            let (bits, _) = evaluate(to_int, &[a.to_bits() as u128], 64, 64, 0);
+            assert_eq!(bits as i64, a as i64, "{a:e}");
+
+            let int = rng.next_u64();
+            let from_int = FloatOp::FromInt {
+                signed: false,
+                fbits: 0,
+            };
+            let (bits, _) = evaluate(from_int, &[int as u128], 64, 64, 0);
+            assert_eq!(f64::from_bits(bits as u64), int as f64);
+            let from_int = FloatOp::FromInt {
+                signed: true,
+                fbits: 0,
+            };
+            let (bits, _) = evaluate(from_int, &[int as u32 as u128], 32, 32, 0);
+            assert_eq!(f32::from_bits(bits as u32), int as u32 as i32 as f32);
+        }
+    }
+
+    #[test]
+    fn exceptions() {
+        use float_status::*;
+
+        assert_eq!(f64_op(FloatOp::Add, &[1.0, 2.0]), (3.0, 0));
+        assert_eq!(f64_op(FloatOp::Div, &[1.0, 3.0]).1, 1 << INEXACT);
+        assert_eq!(
+            f64_op(FloatOp::Div, &[1.0, 0.0]),
+            (f64::INFINITY, 1 << DIVIDE_BY_ZERO)
+        );
+        assert_eq!(f64_op(FloatOp::Sqrt, &[-1.0]).1, 1 << INVALID);
+        assert_eq!(
+            f64_op(FloatOp::Sub, &[f64::INFINITY, f64::INFINITY]).1,
+            1 << INVALID
+        );
+        assert_eq!(
+            f64_op(FloatOp::Mul, &[f64::MAX, 2.0]),
+            (f64::INFINITY, 1 << OVERFLOW | 1 << INEXACT)
+        );
+        assert_eq!(
+            f64_op(FloatOp::Mul, &[f64::from_bits(3), 0.5]).1,
+            1 << UNDERFLOW | 1 << INEXACT
+        );
+        // An exact subnormal result doesn't underflow
+        assert_eq!(f64_op(FloatOp::Mul, &[f64::MIN_POSITIVE, 0.5]).1, 0);
+
+        let compare = FloatOp::Compare { signaling: false };
+        assert_eq!(f64_op(compare, &[f64::NAN, 1.0]).1, 0);
+        let compare = FloatOp::Compare { signaling: true };
+        assert_eq!(f64_op(compare, &[f64::NAN, 1.0]).1, 1 << INVALID);
+    }
+
+    #[test]
+    fn rounding_modes() {
+        let third = |rounding: u64| {
+            let srcs = [1f32.to_bits() as u128, 3f32.to_bits() as u128];
+            let (bits, _) = evaluate(FloatOp::Div, &srcs, 32, 32, rounding);
+            bits as u32
+        };
+
+        let nearest = (1f32 / 3f32).to_bits();
+        assert_eq!(third(0), nearest);
+        assert_eq!(third(1), nearest);
+        assert_eq!(third(2), nearest - 1);
+        assert_eq!(third(3), nearest - 1);
+
+        let round = |value: f64, rounding| {
+            let op = FloatOp::RoundInt {
+                rounding: Some(rounding),
+                exact: false,
+            };
+            f64_op(op, &[value]).0
+        };
+        assert_eq!(round(2.5, FloatRounding::TiesToEven), 2.0);
+        assert_eq!(round(2.5, FloatRounding::TiesToAway), 3.0);
+        assert_eq!(round(-2.5, FloatRounding::TowardPositive), -2.0);
+        assert_eq!(round(-2.5, FloatRounding::TowardNegative), -3.0);
+        assert_eq!(
+            round(-0.5, FloatRounding::TowardZero).to_bits(),
+            (-0f64).to_bits()
+        );
+    }
+
+    #[test]
+    fn nans() {
+        let snan = f32::from_bits(0x7f80_0001);
+        let qnan = f32::from_bits(0xffc0_0002);
+
+        // The signaling NaN wins and is made quiet
+        let (result, status) = f32_op(FloatOp::Add, &[qnan, snan]);
+        assert_eq!(result.to_bits(), 0x7fc0_0001);
+        assert_eq!(status, 1 << float_status::INVALID);
+        assert_eq!(
+            f32_op(FloatOp::Sub, &[1.0, qnan]).0.to_bits(),
+            qnan.to_bits()
+        );
+
+        let control = 1 << float_control::DEFAULT_NAN;
+        let srcs = [1f32.to_bits() as u128, qnan.to_bits() as u128];
+        let (bits, _) = evaluate(FloatOp::Mul, &srcs, 32, 32, control);
+        assert_eq!(bits, 0x7fc0_0000);
+
+        assert_eq!(f32_op(FloatOp::MaxNum, &[qnan, -1.0]).0, -1.0);
+        assert!(f32_op(FloatOp::Max, &[qnan, -1.0]).0.is_nan());
+        assert_eq!(
+            f32_op(FloatOp::Min, &[0.0, -0.0]).0.to_bits(),
+            (-0f32).to_bits()
+        );
+    }
+
+    #[test]
+    fn flush_to_zero() {
+        let control = 1 << float_control::FLUSH_TO_ZERO;
+        let tiny = f32::from_bits(1).to_bits() as u128;
+
+        let (bits, status) = evaluate(FloatOp::Add, &[tiny, 0], 32, 32, control);
+        assert_eq!((bits, status), (0, 1 << float_status::INPUT_DENORMAL));
+
+        let small = f32::MIN_POSITIVE.to_bits() as u128;
+        let half = 0.5f32.to_bits() as u128;
+        let (bits, status) = evaluate(FloatOp::Mul, &[small, half], 32, 32, control);
+        assert_eq!((bits, status), (0, 1 << float_status::UNDERFLOW));
+    }
+
+    #[test]
+    fn half_precision() {
+        // 1.0 + 2^-11 is a tie in half precision, it rounds to even
+        let (bits, status) = evaluate(FloatOp::Add, &[0x3c00, 0x1000], 16, 16, 0);
+        assert_eq!((bits, status), (0x3c00, 1 << float_status::INEXACT));
+
+        let (bits, _) = evaluate(FloatOp::Convert { rounding: None }, &[0x3c01], 16, 32, 0);
+        assert_eq!(f32::from_bits(bits as u32), 1.0 + 2f32.powi(-10));
+        let (bits, status) = evaluate(
+            FloatOp::Convert { rounding: None },
+            &[1e6f32.to_bits() as u128],
+            32,
+            16,
+            0,
+        );
+        assert_eq!(bits, 0x7c00);
+        assert_eq!(
+            status,
+            1 << float_status::OVERFLOW | 1 << float_status::INEXACT
+        );
+    }
+
+    #[test]
+    fn round_to_odd() {
+        use float_status::INEXACT;
+
+        let convert = FloatOp::Convert {
+            rounding: Some(FloatRounding::ToOdd),
+        };
+        let srcs = [(1.0 + 2f64.powi(-30)).to_bits() as u128];
+        assert_eq!(
+            evaluate(convert, &srcs, 64, 32, 0),
+            (0x3f80_0001, 1 << INEXACT)
+        );
+        let srcs = [1.5f64.to_bits() as u128];
+        assert_eq!(evaluate(convert, &srcs, 64, 32, 0), (0x3fc0_0000, 0));
+        let srcs = [f64::MAX.to_bits() as u128];
+        let (bits, _) = evaluate(convert, &srcs, 64, 32, 0);
+        assert_eq!(f32::from_bits(bits as u32), f32::MAX);
+    }
+
+    #[test]
+    fn steps_and_estimates() {
+        use float_status::*;
+
+        assert_eq!(
+            f32_op(FloatOp::MulExtended, &[f32::INFINITY, -0.0]),
+            (-2.0, 0)
+        );
+        assert_eq!(f32_op(FloatOp::MulExtended, &[3.0, 0.5]), (1.5, 0));
+        assert_eq!(f64_op(FloatOp::RecipStep, &[-2.0, 0.75]), (0.5, 0));
+        assert_eq!(f64_op(FloatOp::RecipStep, &[0.0, f64::INFINITY]), (2.0, 0));
+        assert_eq!(f64_op(FloatOp::RSqrtStep, &[-1.0, 1.0]), (1.0, 0));
+        assert_eq!(f64_op(FloatOp::RSqrtStep, &[f64::INFINITY, 0.0]), (1.5, 0));
+
+        assert_eq!(f32_op(FloatOp::RecipEstimate, &[1.0]), (511.0 / 512.0, 0));
+        assert_eq!(
+            f32_op(FloatOp::RecipEstimate, &[-4.0]),
+            (-511.0 / 2048.0, 0)
+        );
+        assert_eq!(f64_op(FloatOp::RecipEstimate, &[1.0]), (0.998046875, 0));
+        assert_eq!(
+            f32_op(FloatOp::RecipEstimate, &[0.0]),
+            (f32::INFINITY, 1 << DIVIDE_BY_ZERO)
+        );
+        assert_eq!(
+            f32_op(FloatOp::RecipEstimate, &[f32::from_bits(0x200)]),
+            (f32::INFINITY, 1 << OVERFLOW | 1 << INEXACT)
+        );
+
+        assert_eq!(f32_op(FloatOp::RSqrtEstimate, &[4.0]), (511.0 / 1024.0, 0));
+        assert_eq!(f32_op(FloatOp::RSqrtEstimate, &[2.0]), (361.0 / 512.0, 0));
+        assert_eq!(f64_op(FloatOp::RSqrtEstimate, &[f64::INFINITY]), (0.0, 0));
+        let (result, status) = f32_op(FloatOp::RSqrtEstimate, &[-1.0]);
+        assert!(result.is_nan());
+        assert_eq!(status, 1 << INVALID);
+    }
+}
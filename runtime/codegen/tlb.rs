@@ -0,0 +1,465 @@
+use core::{
+    Abort, AbortKind, AccessType, Architecture, Interrupt, PageTranslation, TranslationRegime,
+};
+
+use device::devices::SoftMmu;
+
+/// The smallest page size, the translations are cached per page of this size.
+pub const PAGE_SIZE: u64 = 1 << PAGE_SHIFT;
+const PAGE_SHIFT: u32 = 12;
+
+/// The number of cached translations, the cache is direct mapped.
+const TLB_ENTRIES: usize = 256;
+
+type RegimeFn = fn(&[u8]) -> Option<TranslationRegime>;
+type UnprivilegedFn = fn(TranslationRegime) -> TranslationRegime;
+type WalkFn = fn(
+    &[u8],
+    TranslationRegime,
+    u64,
+    AccessType,
+    &mut dyn FnMut(u64) -> Option<u64>,
+) -> Result<PageTranslation, Abort>;
+
+#[derive(Copy, Clone)]
+struct TlbEntry {
+    /// The virtual address of the entry, shifted by [`PAGE_SHIFT`].
+    page: u64,
+    /// The physical address of the small page.
+    physical_address: u64,
+    privilege: u8,
+    translation: PageTranslation,
+}
+
+/// A software TLB, caching the translations of the virtual addresses of an architecture (see
+/// [`Architecture::translate`]).
+///
+/// The translations are only used in the regime they were made in, and are dropped when the
+/// guest invalidates them. A broadcast invalidation of any cpu drops all of them.
+pub struct Tlb {
+    entries: Vec<Option<TlbEntry>>,
+    /// The [`SoftMmu::tlb_generation`] the entries were made in.
+    generation: u64,
+    regime: RegimeFn,
+    unprivileged: UnprivilegedFn,
+    walk: WalkFn,
+}
+
+impl Tlb {
+    pub fn new<A: Architecture>() -> Self {
+        Self {
+            entries: vec![None; TLB_ENTRIES],
+            generation: 0,
+            regime: A::translation_regime,
+            unprivileged: A::unprivileged_regime,
+            walk: A::translate,
+        }
+    }
+
+    /// Translate the virtual address of an access of `size` bytes in the state of
+    /// `registers`, the raw register file. The access must not cross a page boundary after
+    /// `address`.
+    ///
+    /// # Safety
+    ///
+    /// The translation tables are read through `mmu` without any checks.
+    pub unsafe fn translate(
+        &mut self,
+        registers: &[u8],
+        mmu: &SoftMmu,
+        address: u64,
+        size: usize,
+        access: AccessType,
+    ) -> Result<u64, Interrupt> {
+        self.translate_in(registers, mmu, address, size, access, false)
+    }
+
+    /// [`Tlb::translate`], with the permissions of the unprivileged regime of the state if
+    /// `unprivileged`.
+    unsafe fn translate_in(
+        &mut self,
+        registers: &[u8],
+        mmu: &SoftMmu,
+        address: u64,
+        size: usize,
+        access: AccessType,
+        unprivileged: bool,
+    ) -> Result<u64, Interrupt> {
+        let Some(mut regime) = (self.regime)(registers) else {
+            return Ok(address);
+        };
+        if unprivileged {
+            regime = (self.unprivileged)(regime);
+        }
+
+        let generation = mmu.tlb_generation();
+        if generation != self.generation {
+            self.entries.fill(None);
+            self.generation = generation;
+        }
+
+        let page = address >> PAGE_SHIFT;
+        let slot = page as usize % TLB_ENTRIES;
+        let entry = match self.entries[slot] {
+            // A hit without the permission walks again to raise the right abort
+            Some(entry)
+                if entry.page == page
+                    && entry.privilege == regime.privilege
+                    && entry
+                        .translation
+                        .asid
+                        .is_none_or(|asid| asid == regime.asid)
+                    && entry.translation.permissions.allows(access) =>
+            {
+                entry
+            }
+            _ => {
+                let translation = (self.walk)(registers, regime, address, access, &mut |address| {
+                    let mut descriptor = [0; 8];
+                    mmu.try_read_all_at(address, &mut descriptor).ok()?;
+                    Some(u64::from_le_bytes(descriptor))
+                })
+                .map_err(Interrupt::Aborts)?;
+
+                let offset = (address - translation.virtual_address) & !(PAGE_SIZE - 1);
+                let entry = TlbEntry {
+                    page,
+                    physical_address: translation.physical_address + offset,
+                    privilege: regime.privilege,
+                    translation,
+                };
+                self.entries[slot] = Some(entry);
+                entry
+            }
+        };
+
+        if entry.translation.device && !address.is_multiple_of(size as u64) {
+            return Err(abort(address, access, AbortKind::Unaligned));
+        }
+
+        Ok(entry.physical_address | (address & (PAGE_SIZE - 1)))
+    }
+
+    /// Fill the whole buffer from the virtual address, translated in the state of `registers`
+    /// or in its unprivileged regime if `unprivileged`.
+    ///
+    /// # Safety
+    ///
+    /// See [`SoftMmu::try_read_all_at`].
+    pub unsafe fn read(
+        &mut self,
+        registers: &[u8],
+        mmu: &SoftMmu,
+        address: u64,
+        buf: &mut [u8],
+        unprivileged: bool,
+    ) -> Result<(), Interrupt> {
+        let chunks = self.translate_range(
+            registers,
+            mmu,
+            address,
+            buf.len(),
+            AccessType::Read,
+            unprivileged,
+        )?;
+
+        let mut done = 0;
+        for (physical_address, len) in chunks.into_iter().flatten() {
+            mmu.try_read_all_at(physical_address, &mut buf[done..done + len])
+                .map_err(|fault| {
+                    let fault_address =
+                        address.wrapping_add((done as u64) + fault - physical_address);
+                    abort(fault_address, AccessType::Read, AbortKind::Unmapped)
+                })?;
+            done += len;
+        }
+        Ok(())
+    }
+
+    /// Write the whole buffer to the virtual address, translated in the state of `registers`
+    /// or in its unprivileged regime if `unprivileged`. Nothing is written if a page of the
+    /// buffer has no translation.
+    ///
+    /// # Safety
+    ///
+    /// See [`SoftMmu::try_write_all_at`].
+    pub unsafe fn write(
+        &mut self,
+        registers: &[u8],
+        mmu: &SoftMmu,
+        address: u64,
+        buf: &[u8],
+        unprivileged: bool,
+    ) -> Result<(), Interrupt> {
+        let chunks = self.translate_range(
+            registers,
+            mmu,
+            address,
+            buf.len(),
+            AccessType::Write,
+            unprivileged,
+        )?;
+
+        let mut done = 0;
+        for (physical_address, len) in chunks.into_iter().flatten() {
+            mmu.try_write_all_at(physical_address, &buf[done..done + len])
+                .map_err(|fault| {
+                    let fault_address =
+                        address.wrapping_add((done as u64) + fault - physical_address);
+                    abort(fault_address, AccessType::Write, AbortKind::Unmapped)
+                })?;
+            done += len;
+        }
+        Ok(())
+    }
+
+    /// Translate the pages of an access of `size` bytes at `address`, returns the physical
+    /// address and the length of its part in each of them.
+    unsafe fn translate_range(
+        &mut self,
+        registers: &[u8],
+        mmu: &SoftMmu,
+        address: u64,
+        size: usize,
+        access: AccessType,
+        unprivileged: bool,
+    ) -> Result<[Option<(u64, usize)>; 2], Interrupt> {
+        let in_page = (PAGE_SIZE - (address & (PAGE_SIZE - 1))) as usize;
+        let first = self.translate_in(registers, mmu, address, size, access, unprivileged)?;
+        if size <= in_page {
+            return Ok([Some((first, size)), None]);
+        }
+
+        let next = address.wrapping_add(in_page as u64);
+        let second = self.translate_in(registers, mmu, next, size, access, unprivileged)?;
+        Ok([Some((first, in_page)), Some((second, size - in_page))])
+    }
+
+    /// Drop the cached translations of the virtual `address` in the address space `asid`,
+    /// see [`IrInst::InvalidateTlb`](core::ir::IrInst::InvalidateTlb). The top byte of the
+    /// addresses is ignored, it can hold a tag.
+    pub fn invalidate(&mut self, address: Option<u64>, asid: Option<u16>) {
+        for slot in &mut self.entries {
+            let Some(entry) = slot else {
+                continue;
+            };
+            let translation = &entry.translation;
+
+            let dropped = match (address, asid) {
+                (None, None) => true,
+                (None, Some(asid)) => translation.asid == Some(asid),
+                (Some(address), asid) => {
+                    let difference = (address ^ translation.virtual_address) << 8;
+                    let contained = difference >> (translation.page_shift + 8) == 0;
+                    let in_space =
+                        translation.asid.is_none() || asid.is_none() || translation.asid == asid;
+                    contained && in_space
+                }
+            };
+
+            if dropped {
+                *slot = None;
+            }
+        }
+    }
+}
+
+fn abort(fault_address: u64, access: AccessType, kind: AbortKind) -> Interrupt {
+    Interrupt::Aborts(Abort {
+        fault_address,
+        access,
+        kind,
+        syndrome: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use core::Permissions;
+
+    use super::*;
+
+    thread_local! {
+        /// The number of table walks and the physical address the pages are mapped at.
+        static WALKS: Cell<usize> = const { Cell::new(0) };
+        static PHYSICAL: Cell<u64> = const { Cell::new(0x10_0000) };
+    }
+
+    /// The regime is in the registers: the ASID in the first two bytes, then the privilege.
+    fn regime(registers: &[u8]) -> Option<TranslationRegime> {
+        Some(TranslationRegime {
+            asid: u16::from_le_bytes([registers[0], registers[1]]),
+            privilege: registers[2],
+        })
+    }
+
+    /// Every page is mapped. Page 1 is read-only, page 2 write-only and page 3 is device
+    /// memory. The pages from 0x100 are not global, and only readable at privilege 0.
+    fn walk(
+        _registers: &[u8],
+        regime: TranslationRegime,
+        address: u64,
+        access: AccessType,
+        _read: &mut dyn FnMut(u64) -> Option<u64>,
+    ) -> Result<PageTranslation, Abort> {
+        WALKS.set(WALKS.get() + 1);
+        let page = address >> PAGE_SHIFT;
+        let global = page < 0x100;
+        let permissions = Permissions {
+            read: page != 2 && (global || regime.privilege == 0),
+            write: page != 1 && (global || regime.privilege != 0),
+            execute: false,
+        };
+        if !permissions.allows(access) {
+            return Err(Abort {
+                fault_address: address,
+                access,
+                kind: AbortKind::Permission { level: 3 },
+                syndrome: 0,
+            });
+        }
+
+        Ok(PageTranslation {
+            virtual_address: page << PAGE_SHIFT,
+            physical_address: PHYSICAL.get() + (page << PAGE_SHIFT),
+            page_shift: PAGE_SHIFT as u8,
+            permissions,
+            asid: (!global).then_some(regime.asid),
+            device: page == 3,
+        })
+    }
+
+    fn tlb() -> Tlb {
+        WALKS.set(0);
+        Tlb {
+            entries: vec![None; TLB_ENTRIES],
+            generation: 0,
+            regime,
+            unprivileged: |regime| regime,
+            walk,
+        }
+    }
+
+    fn registers(asid: u16, privilege: u8) -> [u8; 3] {
+        let [asid_low, asid_high] = asid.to_le_bytes();
+        [asid_low, asid_high, privilege]
+    }
+
+    fn translate(
+        tlb: &mut Tlb,
+        mmu: &SoftMmu,
+        registers: [u8; 3],
+        address: u64,
+        size: usize,
+        access: AccessType,
+    ) -> Result<u64, Option<AbortKind>> {
+        unsafe { tlb.translate(&registers, mmu, address, size, access) }.map_err(|interrupt| {
+            match interrupt {
+                Interrupt::Aborts(abort) => Some(abort.kind),
+                _ => None,
+            }
+        })
+    }
+
+    #[test]
+    fn hits_and_misses() {
+        let (mut tlb, mmu, state) = (tlb(), SoftMmu::new(), registers(0, 0));
+        let read =
+            |tlb: &mut Tlb, address| translate(tlb, &mmu, state, address, 8, AccessType::Read);
+
+        assert_eq!(read(&mut tlb, 0x4008), Ok(0x10_4008));
+        assert_eq!(read(&mut tlb, 0x4ff0), Ok(0x10_4ff0));
+        assert_eq!(WALKS.get(), 1);
+
+        // The page in the same slot replaces it
+        let other = 0x4000 + ((TLB_ENTRIES as u64) << PAGE_SHIFT);
+        assert_eq!(read(&mut tlb, other), Ok(0x10_0000 + other));
+        assert_eq!(read(&mut tlb, 0x4000), Ok(0x10_4000));
+        assert_eq!(WALKS.get(), 3);
+
+        // Another privilege walks again
+        let registers = registers(0, 1);
+        assert_eq!(
+            translate(&mut tlb, &mmu, registers, 0x4000, 8, AccessType::Read),
+            Ok(0x10_4000)
+        );
+        assert_eq!(WALKS.get(), 4);
+    }
+
+    #[test]
+    fn hits_without_the_permission_walk_again() {
+        let (mut tlb, mmu, state) = (tlb(), SoftMmu::new(), registers(0, 1));
+        let access =
+            |tlb: &mut Tlb, address, access| translate(tlb, &mmu, state, address, 8, access);
+
+        assert_eq!(access(&mut tlb, 0x1000, AccessType::Read), Ok(0x10_1000));
+        assert_eq!(
+            access(&mut tlb, 0x1000, AccessType::Write),
+            Err(Some(AbortKind::Permission { level: 3 }))
+        );
+        assert_eq!(access(&mut tlb, 0x2000, AccessType::Write), Ok(0x10_2000));
+        assert_eq!(
+            access(&mut tlb, 0x2000, AccessType::Read),
+            Err(Some(AbortKind::Permission { level: 3 }))
+        );
+        assert_eq!(WALKS.get(), 4);
+    }
+
+    #[test]
+    fn non_global_translations_stay_in_their_address_space() {
+        let (mut tlb, mmu) = (tlb(), SoftMmu::new());
+        let read = |tlb: &mut Tlb, asid, address| {
+            translate(tlb, &mmu, registers(asid, 0), address, 8, AccessType::Read)
+        };
+
+        // The global translation is shared
+        assert_eq!(read(&mut tlb, 1, 0x5000), Ok(0x10_5000));
+        assert_eq!(read(&mut tlb, 2, 0x5000), Ok(0x10_5000));
+        assert_eq!(WALKS.get(), 1);
+
+        assert_eq!(read(&mut tlb, 1, 0x10_0000), Ok(0x20_0000));
+        assert_eq!(read(&mut tlb, 1, 0x10_0000), Ok(0x20_0000));
+        assert_eq!(WALKS.get(), 2);
+        assert_eq!(read(&mut tlb, 2, 0x10_0000), Ok(0x20_0000));
+        assert_eq!(WALKS.get(), 3);
+
+        // Invalidating an address space keeps the global translations
+        tlb.invalidate(None, Some(2));
+        assert_eq!(read(&mut tlb, 1, 0x5000), Ok(0x10_5000));
+        assert_eq!(WALKS.get(), 3);
+        assert_eq!(read(&mut tlb, 2, 0x10_0000), Ok(0x20_0000));
+        assert_eq!(WALKS.get(), 4);
+    }
+
+    #[test]
+    fn a_new_generation_drops_every_translation() {
+        let (mut tlb, mmu, state) = (tlb(), SoftMmu::new(), registers(0, 1));
+        let read =
+            |tlb: &mut Tlb, mmu: &SoftMmu| translate(tlb, mmu, state, 0x4000, 8, AccessType::Read);
+        assert_eq!(read(&mut tlb, &mmu), Ok(0x10_4000));
+
+        // The stale translation is used until another cpu invalidates them
+        PHYSICAL.set(0x80_0000);
+        assert_eq!(read(&mut tlb, &mmu), Ok(0x10_4000));
+        mmu.invalidate_tlbs();
+        assert_eq!(read(&mut tlb, &mmu), Ok(0x80_4000));
+        assert_eq!(WALKS.get(), 2);
+        PHYSICAL.set(0x10_0000);
+    }
+
+    #[test]
+    fn device_accesses_must_be_aligned() {
+        let (mut tlb, mmu, state) = (tlb(), SoftMmu::new(), registers(0, 1));
+        let read = |tlb: &mut Tlb, address, size| {
+            translate(tlb, &mmu, state, address, size, AccessType::Read)
+        };
+
+        assert_eq!(read(&mut tlb, 0x3004, 4), Ok(0x10_3004));
+        assert_eq!(read(&mut tlb, 0x3002, 4), Err(Some(AbortKind::Unaligned)));
+        assert_eq!(read(&mut tlb, 0x3003, 1), Ok(0x10_3003));
+        // Normal memory doesn't care
+        assert_eq!(read(&mut tlb, 0x4002, 4), Ok(0x10_4002));
+    }
+}
@@ -0,0 +1,13 @@
+#![no_main]
+
+use execution::codegen::rustjit::RustjitCodegen;
+use execution::testing::{fuzz_codegen, ByteEntropy, GeneratorConfig};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let config = GeneratorConfig::default();
+
+    if let Err(divergence) = fuzz_codegen(RustjitCodegen, &config, &mut ByteEntropy::new(data)) {
+        panic!("{divergence}");
+    }
+});
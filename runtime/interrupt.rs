@@ -0,0 +1,272 @@
+use core::Interrupt;
+
+use device::devices::SoftMmu;
+
+use crate::codegen::{ExecOutcome, Executable, ExecutionContext};
+
+/// The action the runtime takes after an interrupt has been handled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InterruptAction {
+    /// Continue right after the operation that raised the interrupt.
+    ///
+    /// An aborted access hasn't defined its destination, which the next operations may read,
+    /// so an [`Interrupt::Aborts`] can't be resumed: resuming it stops the execution like
+    /// [`InterruptAction::Stop`].
+    Resume,
+    /// Execute the operation that raised the interrupt again.
+    ///
+    /// This is useful when the handler fixed the cause of the interrupt, e.g. mapped the
+    /// memory of an abort.
+    Retry,
+    /// Abandon the rest of the block and continue at the given pc.
+    Redirect(u64),
+    /// Abandon the rest of the block and let the guest take the interrupt, continuing at its
+    /// exception vector (see
+    /// [`Architecture::interrupt`](core::Architecture::interrupt)).
+    Take,
+    /// Stop the execution.
+    Stop,
+}
+
+/// A handler that decides how to continue after an interrupt.
+pub trait InterruptHandler<C> {
+    fn handle(&mut self, context: &mut C, interrupt: Interrupt) -> InterruptAction;
+}
+
+impl<C, F> InterruptHandler<C> for F
+where
+    F: FnMut(&mut C, Interrupt) -> InterruptAction,
+{
+    fn handle(&mut self, context: &mut C, interrupt: Interrupt) -> InterruptAction {
+        self(context, interrupt)
+    }
+}
+
+/// How the execution of a block has ended.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BlockExit {
+    /// The block has been finished. The next block starts at the pc of the context.
+    Continue,
+    /// The handler stopped the execution on the interrupt.
+    Stop(Interrupt),
+    /// The handler wants the guest to take the interrupt, which the vcpu does before
+    /// continuing.
+    Take(Interrupt),
+}
+
+/// Execute the block, passing every raised interrupt to the handler and applying its action.
+///
+/// # Safety
+///
+/// See [`Executable::execute`].
+pub unsafe fn execute_with_handler<E>(
+    executable: &E,
+    context: &mut E::Context,
+    io_device: &SoftMmu,
+    handler: &mut impl InterruptHandler<E::Context>,
+) -> BlockExit
+where
+    E: Executable,
+    E::Context: ExecutionContext,
+{
+    let mut outcome = executable.execute(context, io_device);
+
+    loop {
+        let (interrupt, resume) = match outcome {
+            ExecOutcome::Completed => return BlockExit::Continue,
+            ExecOutcome::Interrupted { interrupt, resume } => (interrupt, resume),
+        };
+
+        let token = match handler.handle(context, interrupt) {
+            InterruptAction::Resume if matches!(interrupt, Interrupt::Aborts(_)) => {
+                return BlockExit::Stop(interrupt)
+            }
+            InterruptAction::Resume => resume,
+            InterruptAction::Retry => resume.retry(),
+            InterruptAction::Redirect(pc) => {
+                context.set_pc(pc);
+                return BlockExit::Continue;
+            }
+            InterruptAction::Take => return BlockExit::Take(interrupt),
+            InterruptAction::Stop => return BlockExit::Stop(interrupt),
+        };
+
+        outcome = executable.resume(context, io_device, token);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        ir::{BasicBlock, IrConstant, IrInst, IrType, IrValue},
+        Abort, AbortKind, AccessType, Architecture, RegisterId,
+    };
+
+    use super::*;
+    use crate::codegen::rustjit::{RustjitCodegen, RustjitContext};
+    use crate::codegen::Codegen;
+    use crate::testing::{MemoryImage, TestArchitecture, TestRegisterId};
+
+    const DATA: u64 = 0x2000;
+    const UNMAPPED: u64 = 0x3000;
+
+    fn r(v: u8) -> IrValue {
+        TestRegisterId::R(v).value()
+    }
+
+    fn constant(value: u64) -> IrValue {
+        IrValue::Constant(IrConstant::U64(value))
+    }
+
+    fn offset(id: TestRegisterId) -> usize {
+        let desc = TestArchitecture::get_register_file_desc();
+        desc.register(id.raw()).offset
+    }
+
+    fn get(context: &RustjitContext, v: u8) -> u64 {
+        let offset = offset(TestRegisterId::R(v));
+        u64::from_le_bytes(context.registers()[offset..offset + 8].try_into().unwrap())
+    }
+
+    fn set(context: &mut RustjitContext, v: u8, value: u64) {
+        let offset = offset(TestRegisterId::R(v));
+        context.registers_mut()[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn raise(bb: &mut BasicBlock, interrupt: Interrupt) {
+        bb.push_inst(IrInst::Interrupt {
+            cond: IrValue::Constant(IrConstant::Bool(true)),
+            interrupt,
+        });
+    }
+
+    /// Run `bb` on a fresh context, with `0x2a` stored at `DATA`.
+    fn run(
+        bb: BasicBlock,
+        setup: impl FnOnce(&mut RustjitContext),
+        mut handler: impl FnMut(&mut RustjitContext, Interrupt) -> InterruptAction,
+    ) -> (BlockExit, RustjitContext) {
+        let mut memory = MemoryImage::new();
+        memory.add_region(DATA, 0x2au64.to_le_bytes().to_vec());
+        let mmu = memory.to_mmu();
+
+        let executable = RustjitCodegen.compile::<TestArchitecture>(bb);
+        let mut context = RustjitCodegen::new_context::<TestArchitecture>();
+        setup(&mut context);
+
+        let exit = unsafe { execute_with_handler(&executable, &mut context, &mmu, &mut handler) };
+        (exit, context)
+    }
+
+    #[test]
+    fn resume_continues_after_the_interrupt() {
+        let mut bb = BasicBlock::new(0x1000);
+        bb.push_inst(IrInst::Assign {
+            dst: r(1),
+            src: constant(1),
+        });
+        raise(&mut bb, Interrupt::Yield);
+        bb.push_inst(IrInst::Assign {
+            dst: r(2),
+            src: constant(2),
+        });
+
+        let mut yields = 0;
+        let (exit, context) = run(
+            bb,
+            |_| {},
+            |_, interrupt| {
+                assert_eq!(interrupt, Interrupt::Yield);
+                yields += 1;
+                InterruptAction::Resume
+            },
+        );
+
+        assert_eq!(exit, BlockExit::Continue);
+        assert_eq!(yields, 1);
+        assert_eq!((get(&context, 1), get(&context, 2)), (1, 2));
+    }
+
+    #[test]
+    fn resuming_an_abort_stops() {
+        // The loaded variable is never defined, the addition must not run.
+        let mut bb = BasicBlock::new(0x1000);
+        let loaded = bb.new_variable(IrType::U64);
+        bb.push_inst(IrInst::Load {
+            dst: loaded,
+            src: constant(UNMAPPED),
+        });
+        bb.push_inst(IrInst::Add {
+            dst: r(1),
+            lhs: loaded,
+            rhs: constant(1),
+        });
+
+        let mut aborts = 0;
+        let (exit, context) = run(
+            bb,
+            |_| {},
+            |_, _| {
+                aborts += 1;
+                InterruptAction::Resume
+            },
+        );
+
+        let abort = Interrupt::Aborts(Abort {
+            fault_address: UNMAPPED,
+            access: AccessType::Read,
+            kind: AbortKind::Unmapped,
+            syndrome: 0,
+        });
+        assert_eq!(exit, BlockExit::Stop(abort));
+        assert_eq!(aborts, 1);
+        assert_eq!(get(&context, 1), 0);
+    }
+
+    #[test]
+    fn retry_executes_the_interrupted_operation_again() {
+        let mut bb = BasicBlock::new(0x1000);
+        bb.push_inst(IrInst::Load {
+            dst: r(1),
+            src: r(0),
+        });
+        bb.push_inst(IrInst::Add {
+            dst: r(2),
+            lhs: r(1),
+            rhs: constant(1),
+        });
+
+        // The handler fixes the address of the load before retrying it.
+        let mut aborts = 0;
+        let (exit, context) = run(
+            bb,
+            |context| set(context, 0, UNMAPPED),
+            |context, interrupt| {
+                assert!(matches!(interrupt, Interrupt::Aborts(_)));
+                aborts += 1;
+                set(context, 0, DATA);
+                InterruptAction::Retry
+            },
+        );
+
+        assert_eq!(exit, BlockExit::Continue);
+        assert_eq!(aborts, 1);
+        assert_eq!((get(&context, 1), get(&context, 2)), (0x2a, 0x2b));
+    }
+
+    #[test]
+    fn redirect_abandons_the_rest_of_the_block() {
+        let mut bb = BasicBlock::new(0x1000);
+        raise(&mut bb, Interrupt::Yield);
+        bb.push_inst(IrInst::Assign {
+            dst: r(1),
+            src: constant(1),
+        });
+
+        let (exit, context) = run(bb, |_| {}, |_, _| InterruptAction::Redirect(0x4000));
+
+        assert_eq!(exit, BlockExit::Continue);
+        assert_eq!(context.pc(), 0x4000);
+        assert_eq!(get(&context, 1), 0);
+    }
+}
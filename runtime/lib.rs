@@ -1,4 +1,6 @@
-#![feature(generators, generator_trait)]
-#![feature(impl_trait_in_assoc_type)]
-
 pub mod codegen;
+pub mod interrupt;
+pub mod machine;
+pub mod scheduler;
+pub mod testing;
+pub mod vcpu;
@@ -0,0 +1,488 @@
+use core::{Architecture, Interrupt};
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Condvar, Mutex, MutexGuard,
+    },
+    thread,
+};
+
+use device::devices::SoftMmu;
+
+use crate::{
+    codegen::{Codegen, ExecutionContext},
+    interrupt::{BlockExit, InterruptAction, InterruptHandler},
+    vcpu::Vcpu,
+};
+
+/// A machine that runs several vcpus on their own threads, sharing one memory.
+pub struct Machine<A: Architecture, C: Codegen> {
+    mmu: Arc<SoftMmu>,
+    control: Arc<MachineControl>,
+    vcpus: Vec<Vcpu<A, C>>,
+}
+
+/// How a vcpu has finished running.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VcpuExit {
+    /// The handler stopped the vcpu on the interrupt.
+    Interrupted(Interrupt),
+    /// The machine has been stopped.
+    Stopped,
+}
+
+impl<A: Architecture, C: Codegen> Machine<A, C> {
+    pub fn new(codegen: C, mmu: SoftMmu, cpu_count: usize) -> Self {
+        let codegen = Arc::new(codegen);
+
+        Self {
+            mmu: Arc::new(mmu),
+            control: Arc::new(MachineControl::new(cpu_count)),
+            vcpus: (0..cpu_count).map(|_| Vcpu::new(codegen.clone())).collect(),
+        }
+    }
+
+    pub fn mmu(&self) -> &Arc<SoftMmu> {
+        &self.mmu
+    }
+
+    /// The control of the machine, it can be used from other threads while the machine runs.
+    pub fn control(&self) -> &Arc<MachineControl> {
+        &self.control
+    }
+
+    pub fn vcpu(&self, id: usize) -> &Vcpu<A, C> {
+        &self.vcpus[id]
+    }
+
+    pub fn vcpu_mut(&mut self, id: usize) -> &mut Vcpu<A, C> {
+        &mut self.vcpus[id]
+    }
+
+    /// Run every vcpu on its own thread until all of them have finished.
+    ///
+    /// `handler` creates the interrupt handler of a vcpu from its id. Interrupts signaled
+    /// through [`MachineControl::signal`] are delivered to the handler as
+    /// [`Interrupt::Interrupt`] between blocks. [`Interrupt::WaitForInterrupt`] is handled by
+    /// the machine, the vcpu sleeps until it is signaled or the machine is stopped.
+    ///
+    /// # Safety
+    ///
+    /// See [`Executable::execute`](crate::codegen::Executable::execute).
+    pub unsafe fn run<H, F>(&mut self, handler: F) -> Vec<VcpuExit>
+    where
+        C: Send + Sync,
+        C::Context: Send,
+        C::Executable: Send,
+        H: InterruptHandler<C::Context>,
+        F: Fn(usize) -> H + Sync,
+    {
+        let mmu = &*self.mmu;
+        let control = &*self.control;
+        let handler = &handler;
+
+        thread::scope(|scope| {
+            let threads: Vec<_> = self
+                .vcpus
+                .iter_mut()
+                .enumerate()
+                .map(|(id, vcpu)| {
+                    scope.spawn(move || {
+                        let mut handler = WaitForInterruptHandler {
+                            id,
+                            control,
+                            inner: handler(id),
+                        };
+
+                        control.enter();
+                        let exit = run_vcpu(id, vcpu, mmu, control, &mut handler);
+                        control.leave();
+
+                        exit
+                    })
+                })
+                .collect();
+
+            threads
+                .into_iter()
+                .map(|thread| thread.join().expect("vcpu thread panicked"))
+                .collect()
+        })
+    }
+}
+
+unsafe fn run_vcpu<A: Architecture, C: Codegen>(
+    id: usize,
+    vcpu: &mut Vcpu<A, C>,
+    mmu: &SoftMmu,
+    control: &MachineControl,
+    handler: &mut impl InterruptHandler<C::Context>,
+) -> VcpuExit {
+    loop {
+        if control.attention.load(Ordering::Acquire) {
+            match control.checkpoint(id) {
+                Checkpoint::Continue => {}
+                Checkpoint::Stop => return VcpuExit::Stopped,
+                Checkpoint::Interrupt(irq) => {
+                    if let BlockExit::Stop(interrupt) =
+                        vcpu.deliver(Interrupt::Interrupt(irq), mmu, handler)
+                    {
+                        return VcpuExit::Interrupted(interrupt);
+                    }
+                }
+            }
+        }
+
+        if let BlockExit::Stop(interrupt) = vcpu.step(mmu, handler) {
+            return VcpuExit::Interrupted(interrupt);
+        }
+    }
+}
+
+/// Puts the vcpu to sleep on WFI and passes every other interrupt to the inner handler.
+struct WaitForInterruptHandler<'a, H> {
+    id: usize,
+    control: &'a MachineControl,
+    inner: H,
+}
+
+impl<C: ExecutionContext, H: InterruptHandler<C>> InterruptHandler<C>
+    for WaitForInterruptHandler<'_, H>
+{
+    fn handle(&mut self, context: &mut C, interrupt: Interrupt) -> InterruptAction {
+        match interrupt {
+            Interrupt::WaitForInterrupt => {
+                self.control.wait_for_interrupt(self.id);
+                // The pc already points to the next instruction, drop the rest of the block so
+                // the interrupt or the stop that woke the vcpu is seen before it continues.
+                InterruptAction::Redirect(context.pc())
+            }
+            interrupt => self.inner.handle(context, interrupt),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum RunState {
+    Running,
+    Paused,
+    Stopped,
+}
+
+struct ControlState {
+    run_state: RunState,
+    /// The number of vcpus that are currently executing guest code.
+    running: usize,
+    /// The pending interrupts of each vcpu.
+    pending: Vec<VecDeque<u64>>,
+}
+
+enum Checkpoint {
+    Continue,
+    Stop,
+    Interrupt(u64),
+}
+
+/// Pauses, resumes, stops and signals the vcpus of a running machine.
+///
+/// Vcpus only look at the control between blocks, so a request takes effect once every vcpu
+/// has finished its current block.
+pub struct MachineControl {
+    /// Set when the vcpus have to look at the state, keeps the common path lock free.
+    attention: AtomicBool,
+    cpu_count: usize,
+    state: Mutex<ControlState>,
+    changed: Condvar,
+}
+
+impl MachineControl {
+    fn new(cpu_count: usize) -> Self {
+        Self {
+            attention: AtomicBool::new(false),
+            cpu_count,
+            state: Mutex::new(ControlState {
+                run_state: RunState::Running,
+                running: 0,
+                pending: vec![VecDeque::new(); cpu_count],
+            }),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// Pause every vcpu, returns once none of them is executing guest code.
+    pub fn pause(&self) {
+        let mut state = self.lock();
+        if state.run_state == RunState::Running {
+            state.run_state = RunState::Paused;
+        }
+        self.notify(&state);
+
+        let _state = self
+            .changed
+            .wait_while(state, |state| {
+                state.run_state == RunState::Paused && state.running > 0
+            })
+            .unwrap();
+    }
+
+    /// Resume the vcpus after [`pause`](Self::pause).
+    pub fn resume(&self) {
+        let mut state = self.lock();
+        if state.run_state == RunState::Paused {
+            state.run_state = RunState::Running;
+        }
+        self.notify(&state);
+    }
+
+    /// Stop every vcpu, [`Machine::run`] returns once all of them have finished their block.
+    pub fn stop(&self) {
+        let mut state = self.lock();
+        state.run_state = RunState::Stopped;
+        self.notify(&state);
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.lock().run_state == RunState::Stopped
+    }
+
+    /// Send an interrupt to a vcpu, waking it up if it is waiting for one.
+    ///
+    /// # Panics
+    ///
+    /// If there is no vcpu `cpu`, the control is left usable.
+    pub fn signal(&self, cpu: usize, irq: u64) {
+        // Checked before locking so the panic doesn't poison the state
+        assert!(
+            cpu < self.cpu_count,
+            "signal to vcpu {cpu} of a machine with {} vcpus",
+            self.cpu_count
+        );
+        let mut state = self.lock();
+        state.pending[cpu].push_back(irq);
+        self.notify(&state);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ControlState> {
+        self.state.lock().unwrap()
+    }
+
+    fn notify(&self, state: &ControlState) {
+        let attention = state.run_state != RunState::Running
+            || state.pending.iter().any(|pending| !pending.is_empty());
+
+        self.attention.store(attention, Ordering::Release);
+        self.changed.notify_all();
+    }
+
+    fn enter(&self) {
+        self.lock().running += 1;
+    }
+
+    fn leave(&self) {
+        let mut state = self.lock();
+        state.running -= 1;
+        self.changed.notify_all();
+    }
+
+    /// Sleep while the machine is paused, then take the next pending interrupt.
+    fn checkpoint(&self, id: usize) -> Checkpoint {
+        let mut state = self.park_while(self.lock(), |state| state.run_state == RunState::Paused);
+
+        if state.run_state == RunState::Stopped {
+            return Checkpoint::Stop;
+        }
+
+        let irq = state.pending[id].pop_front();
+        self.notify(&state);
+
+        match irq {
+            Some(irq) => Checkpoint::Interrupt(irq),
+            None => Checkpoint::Continue,
+        }
+    }
+
+    /// Sleep until the vcpu has a pending interrupt or the machine is stopped.
+    fn wait_for_interrupt(&self, id: usize) {
+        let _state = self.park_while(self.lock(), |state| {
+            state.run_state != RunState::Stopped && state.pending[id].is_empty()
+                || state.run_state == RunState::Paused
+        });
+    }
+
+    /// Wait on the condition without counting as a running vcpu.
+    fn park_while<'a>(
+        &self,
+        mut state: MutexGuard<'a, ControlState>,
+        condition: impl FnMut(&mut ControlState) -> bool,
+    ) -> MutexGuard<'a, ControlState> {
+        state.running -= 1;
+        self.changed.notify_all();
+
+        let mut state = self.changed.wait_while(state, condition).unwrap();
+        state.running += 1;
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{Exception, ExceptionKind};
+    use std::panic::{self, AssertUnwindSafe};
+    use std::{sync::atomic::AtomicU64, time::Duration};
+
+    use super::*;
+    use crate::codegen::rustjit::RustjitCodegen;
+    use crate::testing::{MemoryImage, TestArchitecture, TestInst};
+
+    const CODE: u64 = 0x1000;
+    const DATA: u64 = 0x8000;
+
+    type Context = <RustjitCodegen as Codegen>::Context;
+
+    /// A machine running `programs[id]` on each vcpu, every program in its own page, with 64
+    /// bytes of zeroed data at [`DATA`].
+    fn machine(programs: &[&[TestInst]]) -> Machine<TestArchitecture, RustjitCodegen> {
+        let mut memory = MemoryImage::new();
+        for (id, program) in programs.iter().enumerate() {
+            memory.add_region(CODE * (id as u64 + 1), TestInst::assemble(program));
+        }
+        memory.add_region(DATA, vec![0; 64]);
+
+        let mut machine = Machine::new(RustjitCodegen, memory.to_mmu(), programs.len());
+        for id in 0..programs.len() {
+            let context = machine.vcpu_mut(id).context_mut();
+            context.set_pc(CODE * (id as u64 + 1));
+        }
+        machine
+    }
+
+    fn breakpoint(imm: u64) -> Interrupt {
+        Interrupt::Exception(Exception {
+            kind: ExceptionKind::Breakpoint(imm),
+            syndrome: 0,
+        })
+    }
+
+    #[test]
+    fn signals_wake_a_vcpu_waiting_for_an_interrupt() {
+        let mut machine = machine(&[&[TestInst::Wfi, TestInst::Halt { imm: 0 }]]);
+        let control = machine.control().clone();
+        let received = AtomicU64::new(0);
+
+        let exits = thread::scope(|scope| {
+            scope.spawn(|| {
+                thread::sleep(Duration::from_millis(20));
+                control.signal(0, 42);
+            });
+
+            unsafe {
+                machine.run(|_| {
+                    |_: &mut Context, interrupt| match interrupt {
+                        Interrupt::Interrupt(irq) => {
+                            received.store(irq, Ordering::Relaxed);
+                            InterruptAction::Resume
+                        }
+                        _ => InterruptAction::Stop,
+                    }
+                })
+            }
+        });
+
+        assert_eq!(exits, [VcpuExit::Interrupted(breakpoint(0))]);
+        assert_eq!(received.load(Ordering::Relaxed), 42);
+    }
+
+    #[test]
+    fn signals_to_a_missing_vcpu_leave_the_control_usable() {
+        let machine = machine(&[&[TestInst::Halt { imm: 0 }]]);
+        let control = machine.control();
+
+        let signal = panic::catch_unwind(AssertUnwindSafe(|| control.signal(1, 7)));
+        assert!(signal.is_err());
+
+        // The state isn't poisoned
+        control.signal(0, 42);
+        control.stop();
+        assert!(control.is_stopped());
+    }
+
+    #[test]
+    fn the_host_stops_running_and_waiting_vcpus() {
+        // The first vcpu spins on the breakpoint, the second one waits forever
+        let spinning: &[TestInst] = &[TestInst::Nop, TestInst::Halt { imm: 1 }];
+        let mut machine = machine(&[spinning, &[TestInst::Wfi, TestInst::Halt { imm: 0 }]]);
+        let control = machine.control().clone();
+
+        let exits = thread::scope(|scope| {
+            scope.spawn(|| {
+                thread::sleep(Duration::from_millis(20));
+                control.stop();
+            });
+
+            unsafe {
+                machine.run(|_| {
+                    |_: &mut Context, interrupt| match interrupt {
+                        _ if interrupt == breakpoint(1) => InterruptAction::Redirect(CODE),
+                        _ => InterruptAction::Stop,
+                    }
+                })
+            }
+        });
+
+        assert!(control.is_stopped());
+        assert_eq!(exits, [VcpuExit::Stopped; 2]);
+    }
+
+    #[test]
+    fn vcpus_see_the_stores_of_each_other() {
+        // Each vcpu stores its value to its slot, then copies the slot of the other one until
+        // the value of the other vcpu shows up in its copy
+        let program = |id: u16| {
+            [
+                TestInst::Mov {
+                    rd: 0,
+                    imm: DATA as u16 + 8 * id,
+                },
+                TestInst::Mov {
+                    rd: 1,
+                    imm: DATA as u16 + 8 * (1 - id),
+                },
+                TestInst::Mov {
+                    rd: 2,
+                    imm: DATA as u16 + 16 + 8 * id,
+                },
+                TestInst::Mov {
+                    rd: 3,
+                    imm: 0x11 * (id + 1),
+                },
+                TestInst::Store { rt: 3, rn: 0 },
+                TestInst::Load { rt: 4, rn: 1 },
+                TestInst::Store { rt: 4, rn: 2 },
+                TestInst::Halt { imm: 1 },
+            ]
+        };
+        let mut machine = machine(&[&program(0), &program(1)]);
+        let mmu = machine.mmu().clone();
+        let copy = |id: u64| {
+            let mut copy = [0; 8];
+            unsafe { mmu.try_read_all_at(DATA + 16 + 8 * id, &mut copy) }.unwrap();
+            u64::from_le_bytes(copy)
+        };
+
+        let exits = unsafe {
+            machine.run(|id| {
+                let id = id as u64;
+                move |_: &mut Context, interrupt| match interrupt {
+                    _ if interrupt != breakpoint(1) => InterruptAction::Stop,
+                    _ if copy(id) == 0x11 * (2 - id) => InterruptAction::Stop,
+                    // Load the slot of the other vcpu again
+                    _ => InterruptAction::Redirect(CODE * (id + 1) + 5 * 4),
+                }
+            })
+        };
+
+        assert_eq!(exits, [VcpuExit::Interrupted(breakpoint(1)); 2]);
+        assert_eq!((copy(0), copy(1)), (0x22, 0x11));
+    }
+}
@@ -0,0 +1,410 @@
+use core::{Architecture, Interrupt};
+use std::{cell::RefCell, collections::VecDeque, rc::Rc, sync::Arc};
+
+use device::devices::SoftMmu;
+use utility::SplitMix64;
+
+use crate::{
+    codegen::{Codegen, ExecutionContext},
+    interrupt::{BlockExit, InterruptAction, InterruptHandler},
+    machine::VcpuExit,
+    vcpu::Vcpu,
+};
+
+/// How long a vcpu runs before the scheduler switches to the next one.
+///
+/// The scheduler only switches at block boundaries, so an instruction quantum is rounded up
+/// to the end of the block that exhausts it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Quantum {
+    Blocks(u64),
+    Instructions(u64),
+}
+
+/// Runs many vcpus interleaved on the current thread.
+///
+/// Every round gives one quantum to each runnable vcpu, in an order permuted from the seed
+/// anew every round. A vcpu also gives up the rest of its quantum on [`Interrupt::Yield`], and
+/// sleeps on [`Interrupt::WaitForInterrupt`] until it is signaled. The same seed, program and
+/// signals always produce the same interleaving.
+pub struct Scheduler<A: Architecture, C: Codegen> {
+    mmu: Arc<SoftMmu>,
+    control: SchedulerControl,
+    vcpus: Vec<Vcpu<A, C>>,
+    quantum: Quantum,
+    /// Permutes the order of the vcpus of every round.
+    rng: SplitMix64,
+}
+
+enum SliceEnd {
+    Expired,
+    Yielded,
+    Waiting,
+    Exited(VcpuExit),
+}
+
+impl<A: Architecture, C: Codegen> Scheduler<A, C> {
+    pub fn new(codegen: C, mmu: SoftMmu, cpu_count: usize, quantum: Quantum, seed: u64) -> Self {
+        let codegen = Arc::new(codegen);
+
+        Self {
+            mmu: Arc::new(mmu),
+            control: SchedulerControl::new(cpu_count),
+            vcpus: (0..cpu_count).map(|_| Vcpu::new(codegen.clone())).collect(),
+            quantum,
+            rng: SplitMix64::new(seed),
+        }
+    }
+
+    pub fn mmu(&self) -> &Arc<SoftMmu> {
+        &self.mmu
+    }
+
+    /// The control of the scheduler, handlers can keep a clone to signal other vcpus.
+    pub fn control(&self) -> &SchedulerControl {
+        &self.control
+    }
+
+    pub fn vcpu(&self, id: usize) -> &Vcpu<A, C> {
+        &self.vcpus[id]
+    }
+
+    pub fn vcpu_mut(&mut self, id: usize) -> &mut Vcpu<A, C> {
+        &mut self.vcpus[id]
+    }
+
+    /// Run the vcpus until all of them have finished.
+    ///
+    /// `handler` creates the interrupt handler of a vcpu from its id. Pending signals are
+    /// delivered to the handler as [`Interrupt::Interrupt`] at the start of a quantum. When
+    /// every remaining vcpu waits for an interrupt nobody can send, they are reported as
+    /// [`VcpuExit::Stopped`].
+    ///
+    /// # Safety
+    ///
+    /// See [`Executable::execute`](crate::codegen::Executable::execute).
+    pub unsafe fn run<H>(&mut self, handler: impl FnMut(usize) -> H) -> Vec<VcpuExit>
+    where
+        H: InterruptHandler<C::Context>,
+    {
+        let count = self.vcpus.len();
+        let mut handlers: Vec<_> = (0..count).map(handler).collect();
+        let mut exits = vec![None; count];
+        let mut waiting = vec![false; count];
+        let mut order: Vec<usize> = (0..count).collect();
+
+        loop {
+            let mut progressed = false;
+            self.rng.shuffle(&mut order);
+
+            for &id in &order {
+                if exits[id].is_some() || waiting[id] && !self.control.has_pending(id) {
+                    continue;
+                }
+
+                waiting[id] = false;
+                progressed = true;
+
+                match self.run_slice(id, &mut handlers[id]) {
+                    SliceEnd::Expired | SliceEnd::Yielded => {}
+                    SliceEnd::Waiting => waiting[id] = true,
+                    SliceEnd::Exited(exit) => exits[id] = Some(exit),
+                }
+            }
+
+            if !progressed {
+                break;
+            }
+        }
+
+        exits
+            .into_iter()
+            .map(|exit| exit.unwrap_or(VcpuExit::Stopped))
+            .collect()
+    }
+
+    unsafe fn run_slice(
+        &mut self,
+        id: usize,
+        handler: &mut impl InterruptHandler<C::Context>,
+    ) -> SliceEnd {
+        let vcpu = &mut self.vcpus[id];
+
+        while let Some(irq) = self.control.take_pending(id) {
+            let interrupt = Interrupt::Interrupt(irq);
+            if let BlockExit::Stop(interrupt) = vcpu.deliver(interrupt, &self.mmu, handler) {
+                return SliceEnd::Exited(VcpuExit::Interrupted(interrupt));
+            }
+        }
+
+        let mut handler = SchedulingHandler {
+            inner: handler,
+            event: None,
+        };
+        let start = vcpu.retired();
+        let mut blocks = 0;
+
+        loop {
+            if self.control.is_stopped() {
+                return SliceEnd::Exited(VcpuExit::Stopped);
+            }
+
+            if let BlockExit::Stop(interrupt) = vcpu.step(&self.mmu, &mut handler) {
+                return SliceEnd::Exited(VcpuExit::Interrupted(interrupt));
+            }
+
+            if let Some(end) = handler.event.take() {
+                return end;
+            }
+
+            blocks += 1;
+            let expired = match self.quantum {
+                Quantum::Blocks(quantum) => blocks >= quantum,
+                Quantum::Instructions(quantum) => vcpu.retired() - start >= quantum,
+            };
+
+            if expired {
+                return SliceEnd::Expired;
+            }
+        }
+    }
+}
+
+/// Ends the slice on [`Interrupt::Yield`] and [`Interrupt::WaitForInterrupt`] and passes
+/// every other interrupt to the inner handler.
+struct SchedulingHandler<'a, H> {
+    inner: &'a mut H,
+    event: Option<SliceEnd>,
+}
+
+impl<C: ExecutionContext, H: InterruptHandler<C>> InterruptHandler<C> for SchedulingHandler<'_, H> {
+    fn handle(&mut self, context: &mut C, interrupt: Interrupt) -> InterruptAction {
+        self.event = match interrupt {
+            Interrupt::Yield => Some(SliceEnd::Yielded),
+            Interrupt::WaitForInterrupt => Some(SliceEnd::Waiting),
+            interrupt => return self.inner.handle(context, interrupt),
+        };
+
+        // The pc already points to the next instruction, drop the rest of the block so the
+        // vcpu continues from there in its next slice.
+        InterruptAction::Redirect(context.pc())
+    }
+}
+
+/// Signals and stops the vcpus of a [`Scheduler`].
+#[derive(Clone)]
+pub struct SchedulerControl {
+    state: Rc<RefCell<SchedulerState>>,
+}
+
+struct SchedulerState {
+    stopped: bool,
+    pending: Vec<VecDeque<u64>>,
+}
+
+impl SchedulerControl {
+    fn new(cpu_count: usize) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(SchedulerState {
+                stopped: false,
+                pending: vec![VecDeque::new(); cpu_count],
+            })),
+        }
+    }
+
+    /// Send an interrupt to a vcpu, waking it up if it is waiting for one.
+    pub fn signal(&self, cpu: usize, irq: u64) {
+        self.state.borrow_mut().pending[cpu].push_back(irq);
+    }
+
+    /// Stop every vcpu at its next block boundary.
+    pub fn stop(&self) {
+        self.state.borrow_mut().stopped = true;
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.state.borrow().stopped
+    }
+
+    fn has_pending(&self, cpu: usize) -> bool {
+        !self.state.borrow().pending[cpu].is_empty()
+    }
+
+    fn take_pending(&self, cpu: usize) -> Option<u64> {
+        self.state.borrow_mut().pending[cpu].pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{Exception, ExceptionKind};
+
+    use super::*;
+    use crate::codegen::rustjit::RustjitCodegen;
+    use crate::testing::{MemoryImage, TestArchitecture, TestInst};
+
+    const CODE: u64 = 0x1000;
+
+    type Trace = Rc<RefCell<Vec<usize>>>;
+    type Context = <RustjitCodegen as Codegen>::Context;
+
+    /// A scheduler running `programs[id]` on each vcpu, every program in its own page.
+    fn scheduler(
+        programs: &[&[TestInst]],
+        quantum: Quantum,
+        seed: u64,
+    ) -> Scheduler<TestArchitecture, RustjitCodegen> {
+        let mut memory = MemoryImage::new();
+        for (id, program) in programs.iter().enumerate() {
+            memory.add_region(CODE * (id as u64 + 1), TestInst::assemble(program));
+        }
+
+        let mut scheduler = Scheduler::new(
+            RustjitCodegen,
+            memory.to_mmu(),
+            programs.len(),
+            quantum,
+            seed,
+        );
+        for id in 0..programs.len() {
+            let context = scheduler.vcpu_mut(id).context_mut();
+            context.set_pc(CODE * (id as u64 + 1));
+        }
+        scheduler
+    }
+
+    fn breakpoint(imm: u64) -> Interrupt {
+        Interrupt::Exception(Exception {
+            kind: ExceptionKind::Breakpoint(imm),
+            syndrome: 0,
+        })
+    }
+
+    /// A handler that records the vcpu on `Halt { imm: 1 }` and ends the block there, and
+    /// stops the vcpu on `Halt { imm: 0 }`.
+    fn tracing(trace: &Trace, id: usize) -> impl FnMut(&mut Context, Interrupt) -> InterruptAction {
+        let trace = trace.clone();
+        move |context, interrupt| match interrupt {
+            _ if interrupt == breakpoint(1) => {
+                trace.borrow_mut().push(id);
+                InterruptAction::Redirect(context.pc())
+            }
+            Interrupt::Interrupt(_) => InterruptAction::Resume,
+            _ => InterruptAction::Stop,
+        }
+    }
+
+    fn run_traced(
+        programs: &[&[TestInst]],
+        quantum: Quantum,
+        seed: u64,
+    ) -> (Vec<usize>, Vec<VcpuExit>) {
+        let trace = Trace::default();
+        let mut scheduler = scheduler(programs, quantum, seed);
+        let exits = unsafe { scheduler.run(|id| tracing(&trace, id)) };
+        let trace = trace.borrow().clone();
+        (trace, exits)
+    }
+
+    /// Whether every vcpu of `trace` runs once in each round of `count` of them.
+    fn in_rounds(trace: &[usize], count: usize) -> bool {
+        trace.chunks(count).all(|round| {
+            let mut round = round.to_vec();
+            round.sort();
+            round.into_iter().eq(0..count)
+        })
+    }
+
+    const HALTS: &[TestInst] = &[
+        TestInst::Halt { imm: 1 },
+        TestInst::Halt { imm: 1 },
+        TestInst::Halt { imm: 1 },
+        TestInst::Halt { imm: 0 },
+    ];
+
+    #[test]
+    fn rounds_are_permuted_from_the_seed() {
+        let programs = [HALTS; 3];
+        let (trace, exits) = run_traced(&programs, Quantum::Blocks(1), 1);
+
+        assert_eq!(trace.len(), 9);
+        assert!(in_rounds(&trace, 3), "{trace:?}");
+        assert_eq!(exits, vec![VcpuExit::Interrupted(breakpoint(0)); 3]);
+
+        // The same seed gives the same interleaving, the order changes between the rounds and
+        // the seeds
+        assert_eq!(run_traced(&programs, Quantum::Blocks(1), 1).0, trace);
+        let traces: Vec<_> = (0..8)
+            .map(|seed| run_traced(&programs, Quantum::Blocks(1), seed).0)
+            .collect();
+        assert!(traces.iter().any(|other| other[..3] != trace[..3]));
+        assert!(
+            traces
+                .iter()
+                .any(|other| other[..3] != other[3..6] || other[3..6] != other[6..]),
+            "{traces:?}"
+        );
+    }
+
+    #[test]
+    fn vcpus_switch_when_the_quantum_expires() {
+        let programs = [HALTS; 2];
+        let (trace, _) = run_traced(&programs, Quantum::Blocks(2), 3);
+        let (a, b) = (trace[0], 1 - trace[0]);
+        assert_eq!(trace[..4], [a, a, b, b]);
+        assert!(in_rounds(&trace[4..], 2), "{trace:?}");
+
+        // The blocks of 4 and 3 instructions exhaust the quantum, the last one only has 2 of
+        // them before the end
+        assert_eq!(run_traced(&programs, Quantum::Instructions(6), 3).0, trace);
+        let (trace, _) = run_traced(&programs, Quantum::Instructions(1), 3);
+        assert_eq!(trace.len(), 6);
+        assert!(in_rounds(&trace, 2), "{trace:?}");
+    }
+
+    #[test]
+    fn vcpus_switch_on_yield_and_wait_for_interrupt() {
+        // Without the yields, each program would run in a single quantum
+        let yielding: &[TestInst] = &[
+            TestInst::Halt { imm: 1 },
+            TestInst::Yield,
+            TestInst::Halt { imm: 1 },
+            TestInst::Halt { imm: 0 },
+        ];
+        let (trace, _) = run_traced(&[yielding; 2], Quantum::Blocks(100), 5);
+        assert_eq!(trace.len(), 4);
+        assert!(in_rounds(&trace, 2), "{trace:?}");
+
+        // The waiting vcpu only runs again once the other one signals it
+        let trace = Trace::default();
+        let waiting: &[TestInst] = &[
+            TestInst::Wfi,
+            TestInst::Halt { imm: 1 },
+            TestInst::Halt { imm: 0 },
+        ];
+        let mut scheduler = scheduler(&[waiting, HALTS], Quantum::Blocks(1), 5);
+        let control = scheduler.control().clone();
+        let exits = unsafe {
+            scheduler.run(|id| {
+                let mut inner = tracing(&trace, id);
+                let control = control.clone();
+                let trace = trace.clone();
+                move |context: &mut Context, interrupt| {
+                    let action = inner(context, interrupt);
+                    if id == 1 && trace.borrow().len() == 3 {
+                        control.signal(0, 42);
+                    }
+                    action
+                }
+            })
+        };
+
+        assert_eq!(*trace.borrow(), [1, 1, 1, 0]);
+        assert_eq!(exits, vec![VcpuExit::Interrupted(breakpoint(0)); 2]);
+
+        // Nobody is left to signal the vcpus
+        let (trace, exits) = run_traced(&[waiting; 2], Quantum::Blocks(1), 5);
+        assert!(trace.is_empty());
+        assert_eq!(exits, vec![VcpuExit::Stopped; 2]);
+    }
+}
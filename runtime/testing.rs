@@ -0,0 +1,11 @@
+//! Helpers to test codegen backends against each other.
+mod architecture;
+pub use architecture::*;
+mod differential;
+pub use differential::*;
+mod ir_fuzz;
+pub use ir_fuzz::*;
+mod lockstep;
+pub use lockstep::*;
+mod reference;
+pub use reference::*;
@@ -0,0 +1,343 @@
+use core::{
+    ir::{BasicBlock, BasicBlockTerminator, IrConstant, IrInst, IrType, IrValue},
+    Abort, AccessType, Architecture, Exception, ExceptionKind, Instruction, Interrupt,
+    PageTranslation, Primitive, RawRegisterId, Register, RegisterFileDesc, RegisterId,
+    TranslationRegime, ViewWrite,
+};
+
+/// A tiny architecture to test the runtime without a real guest architecture.
+///
+/// It has 16 general purpose 64-bit registers, 4 128-bit vector registers, a pc and a flag
+/// register. `W` views the low half of a general purpose register and zero extends on write,
+/// `D` views the low half of a vector register and merges on write. Its instructions are
+/// [`TestInst`]. Taken interrupts continue at [`TEST_INTERRUPT_VECTOR`] with the pc they were
+/// raised at in `R15`.
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct TestArchitecture;
+
+pub const TEST_GPR_COUNT: u8 = 16;
+pub const TEST_VECTOR_COUNT: u8 = 4;
+pub const TEST_INTERRUPT_VECTOR: u64 = 0x100;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TestRegisterId {
+    R(u8),
+    W(u8),
+    V(u8),
+    D(u8),
+    Pc,
+    Flags,
+}
+
+impl RegisterId for TestRegisterId {
+    fn raw(&self) -> RawRegisterId {
+        match *self {
+            TestRegisterId::R(v) => RawRegisterId::new(v as usize),
+            TestRegisterId::V(v) => RawRegisterId::new(0x10 + v as usize),
+            TestRegisterId::Pc => RawRegisterId::new(0x20),
+            TestRegisterId::Flags => RawRegisterId::new(0x21),
+            TestRegisterId::W(v) => RawRegisterId::new(0x30 + v as usize),
+            TestRegisterId::D(v) => RawRegisterId::new(0x40 + v as usize),
+        }
+    }
+}
+
+impl TestRegisterId {
+    pub fn ty(&self) -> IrType {
+        match self {
+            TestRegisterId::V(_) => IrType::U128,
+            TestRegisterId::W(_) => IrType::U32,
+            _ => IrType::U64,
+        }
+    }
+
+    pub fn value(&self) -> IrValue {
+        IrValue::Register(self.ty(), self.raw())
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TestRegister(pub TestRegisterId);
+
+impl Register for TestRegister {
+    type Id = TestRegisterId;
+
+    fn parent(&self) -> Self::Id {
+        match self.0 {
+            TestRegisterId::W(v) => TestRegisterId::R(v),
+            TestRegisterId::D(v) => TestRegisterId::V(v),
+            id => id,
+        }
+    }
+
+    fn id(&self) -> Self::Id {
+        self.0
+    }
+
+    fn size(&self) -> usize {
+        self.0.ty().size_in_bytes()
+    }
+
+    fn is_read_only(&self) -> bool {
+        false
+    }
+}
+
+impl Architecture for TestArchitecture {
+    type Inst = TestInst;
+
+    type Reg = TestRegister;
+    type RegId = TestRegisterId;
+
+    fn get_register_by_name(name: impl AsRef<str>) -> Self::RegId {
+        let name = name.as_ref();
+
+        match name {
+            "pc" => return TestRegisterId::Pc,
+            "flags" => return TestRegisterId::Flags,
+            _ => {}
+        }
+
+        let reg_number: u8 = name[1..].parse().unwrap();
+        match &name[0..1] {
+            "r" if reg_number < TEST_GPR_COUNT => TestRegisterId::R(reg_number),
+            "w" if reg_number < TEST_GPR_COUNT => TestRegisterId::W(reg_number),
+            "v" if reg_number < TEST_VECTOR_COUNT => TestRegisterId::V(reg_number),
+            "d" if reg_number < TEST_VECTOR_COUNT => TestRegisterId::D(reg_number),
+            _ => unreachable!("invalid register name {}", name),
+        }
+    }
+
+    type MnemonicHint = ();
+    fn get_register_by_mnemonic(
+        _hint: Self::MnemonicHint,
+        mnemonic: impl Primitive,
+    ) -> Self::RegId {
+        TestRegisterId::R(mnemonic.to_u8().unwrap() % TEST_GPR_COUNT)
+    }
+
+    fn get_pc_register() -> Self::RegId {
+        TestRegisterId::Pc
+    }
+
+    fn get_flag_register() -> Self::RegId {
+        TestRegisterId::Flags
+    }
+
+    fn get_register_file_desc() -> RegisterFileDesc {
+        let mut desc = RegisterFileDesc::new();
+
+        let registers = (0..TEST_GPR_COUNT)
+            .map(TestRegisterId::R)
+            .chain((0..TEST_VECTOR_COUNT).map(TestRegisterId::V))
+            .chain([TestRegisterId::Pc, TestRegisterId::Flags]);
+
+        for reg in registers {
+            desc.add_register(reg.raw(), reg.ty().size_in_bytes(), false);
+        }
+
+        for v in 0..TEST_GPR_COUNT {
+            let (w, r) = (TestRegisterId::W(v), TestRegisterId::R(v));
+            desc.add_view(w.raw(), r.raw(), 0, 4, ViewWrite::ZeroExtend);
+        }
+        for v in 0..TEST_VECTOR_COUNT {
+            let (d, q) = (TestRegisterId::D(v), TestRegisterId::V(v));
+            desc.add_view(d.raw(), q.raw(), 0, 8, ViewWrite::Merge);
+        }
+
+        desc
+    }
+
+    fn interrupt(_interrupt: Interrupt) -> BasicBlock {
+        let vector = IrValue::Constant(IrConstant::U64(TEST_INTERRUPT_VECTOR));
+        let mut bb = BasicBlock::new(0);
+        bb.push_inst(IrInst::Assign {
+            dst: gpr(TEST_GPR_COUNT - 1),
+            src: TestRegisterId::Pc.value(),
+        });
+        bb.push_inst(IrInst::Assign {
+            dst: TestRegisterId::Pc.value(),
+            src: vector,
+        });
+        bb.set_terminator(BasicBlockTerminator::Branch(vector));
+        bb
+    }
+
+    fn translation_regime(_registers: &[u8]) -> Option<TranslationRegime> {
+        None
+    }
+
+    fn unprivileged_regime(regime: TranslationRegime) -> TranslationRegime {
+        regime
+    }
+
+    fn translate(
+        _registers: &[u8],
+        _regime: TranslationRegime,
+        _address: u64,
+        _access: AccessType,
+        _read: &mut dyn FnMut(u64) -> Option<u64>,
+    ) -> Result<PageTranslation, Abort> {
+        unreachable!("the test architecture has no virtual memory")
+    }
+}
+
+/// The instructions of [`TestArchitecture`].
+///
+/// Every instruction is 4 bytes: an opcode followed by three operand bytes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TestInst {
+    /// `0x00`
+    Nop,
+    /// `0x01 rd imm16`: `rd = imm16`
+    Mov { rd: u8, imm: u16 },
+    /// `0x02 rd rn rm`: `rd = rn + rm`
+    Add { rd: u8, rn: u8, rm: u8 },
+    /// `0x03 rd rn rm`: `rd = rn - rm`
+    Sub { rd: u8, rn: u8, rm: u8 },
+    /// `0x04 rt rn`: `rt = [rn]`
+    Load { rt: u8, rn: u8 },
+    /// `0x05 rt rn`: `[rn] = rt`
+    Store { rt: u8, rn: u8 },
+    /// `0x06`: raise [`Interrupt::Yield`]
+    Yield,
+    /// `0x07`: raise [`Interrupt::WaitForInterrupt`]
+    Wfi,
+    /// `0xff imm16`: raise a breakpoint exception
+    Halt { imm: u16 },
+    /// Any other opcode
+    Undefined,
+}
+
+impl TestInst {
+    pub fn encode(&self) -> [u8; 4] {
+        let [imm_lo, imm_hi] = match self {
+            TestInst::Mov { imm, .. } | TestInst::Halt { imm } => imm.to_le_bytes(),
+            _ => [0, 0],
+        };
+
+        match *self {
+            TestInst::Nop => [0x00, 0, 0, 0],
+            TestInst::Mov { rd, .. } => [0x01, rd, imm_lo, imm_hi],
+            TestInst::Add { rd, rn, rm } => [0x02, rd, rn, rm],
+            TestInst::Sub { rd, rn, rm } => [0x03, rd, rn, rm],
+            TestInst::Load { rt, rn } => [0x04, rt, rn, 0],
+            TestInst::Store { rt, rn } => [0x05, rt, rn, 0],
+            TestInst::Yield => [0x06, 0, 0, 0],
+            TestInst::Wfi => [0x07, 0, 0, 0],
+            TestInst::Halt { .. } => [0xff, imm_lo, imm_hi, 0],
+            TestInst::Undefined => [0xfe, 0, 0, 0],
+        }
+    }
+
+    /// Encode a whole program.
+    pub fn assemble(program: &[TestInst]) -> Vec<u8> {
+        program.iter().flat_map(TestInst::encode).collect()
+    }
+}
+
+fn gpr(v: u8) -> IrValue {
+    TestRegisterId::R(v % TEST_GPR_COUNT).value()
+}
+
+impl Instruction for TestInst {
+    fn size(&self) -> u64 {
+        4
+    }
+
+    fn decode(raw_inst: &[u8]) -> Self {
+        let Some(&[op, a, b, c]) = raw_inst.get(..4) else {
+            return TestInst::Undefined;
+        };
+
+        match op {
+            0x00 => TestInst::Nop,
+            0x01 => TestInst::Mov {
+                rd: a,
+                imm: u16::from_le_bytes([b, c]),
+            },
+            0x02 => TestInst::Add {
+                rd: a,
+                rn: b,
+                rm: c,
+            },
+            0x03 => TestInst::Sub {
+                rd: a,
+                rn: b,
+                rm: c,
+            },
+            0x04 => TestInst::Load { rt: a, rn: b },
+            0x05 => TestInst::Store { rt: a, rn: b },
+            0x06 => TestInst::Yield,
+            0x07 => TestInst::Wfi,
+            0xff => TestInst::Halt {
+                imm: u16::from_le_bytes([a, b]),
+            },
+            _ => TestInst::Undefined,
+        }
+    }
+
+    fn compile_to_ir(&self, bb: &mut BasicBlock) {
+        let pc = TestRegisterId::Pc.value();
+        let raise = |bb: &mut BasicBlock, interrupt| {
+            bb.push_inst(IrInst::Interrupt {
+                cond: IrValue::Constant(IrConstant::Bool(true)),
+                interrupt,
+            })
+        };
+
+        match *self {
+            TestInst::Nop => {}
+            TestInst::Mov { rd, imm } => bb.push_inst(IrInst::Assign {
+                dst: gpr(rd),
+                src: IrValue::Constant(IrConstant::U64(imm as u64)),
+            }),
+            TestInst::Add { rd, rn, rm } => bb.push_inst(IrInst::Add {
+                dst: gpr(rd),
+                lhs: gpr(rn),
+                rhs: gpr(rm),
+            }),
+            TestInst::Sub { rd, rn, rm } => bb.push_inst(IrInst::Sub {
+                dst: gpr(rd),
+                lhs: gpr(rn),
+                rhs: gpr(rm),
+            }),
+            TestInst::Load { rt, rn } => bb.push_inst(IrInst::Load {
+                dst: gpr(rt),
+                src: gpr(rn),
+            }),
+            TestInst::Store { rt, rn } => bb.push_inst(IrInst::Store {
+                dst: gpr(rn),
+                src: gpr(rt),
+            }),
+            TestInst::Yield | TestInst::Wfi | TestInst::Halt { .. } | TestInst::Undefined => {}
+        }
+
+        // Move to the next instruction before raising, so the interrupts resume after it.
+        bb.push_inst(IrInst::Add {
+            dst: pc,
+            lhs: pc,
+            rhs: IrValue::Constant(IrConstant::U64(4)),
+        });
+
+        match *self {
+            TestInst::Yield => raise(bb, Interrupt::Yield),
+            TestInst::Wfi => raise(bb, Interrupt::WaitForInterrupt),
+            TestInst::Halt { imm } => raise(
+                bb,
+                Interrupt::Exception(Exception {
+                    kind: ExceptionKind::Breakpoint(imm as u64),
+                    syndrome: 0,
+                }),
+            ),
+            TestInst::Undefined => raise(
+                bb,
+                Interrupt::Exception(Exception {
+                    kind: ExceptionKind::Undefined,
+                    syndrome: 0,
+                }),
+            ),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,590 @@
+use core::{ir::BasicBlock, Architecture, Interrupt, RawRegisterId, RegisterFileDesc};
+use std::{
+    fmt,
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
+};
+
+use device::{
+    devices::{Memory, SoftMmu},
+    IoDevice,
+};
+
+use crate::{
+    codegen::{Codegen, ExecutionContext},
+    interrupt::{execute_with_handler, InterruptAction},
+};
+
+/// Guest memory regions with their content.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryImage {
+    regions: Vec<MemoryRegion>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemoryRegion {
+    pub addr: u64,
+    pub data: Vec<u8>,
+}
+
+impl MemoryImage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_region(&mut self, addr: u64, data: Vec<u8>) {
+        self.regions.push(MemoryRegion { addr, data });
+    }
+
+    pub fn regions(&self) -> &[MemoryRegion] {
+        &self.regions
+    }
+
+    /// Create a memory that maps every region with its content.
+    pub fn to_mmu(&self) -> SoftMmu {
+        let mut mmu = SoftMmu::new();
+
+        for region in &self.regions {
+            let memory = Memory::allocate(region.data.len());
+            // SAFETY: the memory is not shared with anyone yet.
+            unsafe { memory.write_all_at(0, &region.data) };
+            mmu.map(memory, region.addr, region.data.len() as u64);
+        }
+
+        mmu
+    }
+
+    /// Read the current content of the regions of this image from `mmu`.
+    ///
+    /// # Safety
+    ///
+    /// See [`IoDevice::read_at`](device::IoDevice::read_at).
+    pub unsafe fn capture(&self, mmu: &SoftMmu) -> MemoryImage {
+        let regions = self
+            .regions
+            .iter()
+            .map(|region| {
+                let mut data = vec![0; region.data.len()];
+                mmu.try_read_all_at(region.addr, &mut data)
+                    .expect("memory image is not mapped");
+
+                MemoryRegion {
+                    addr: region.addr,
+                    data,
+                }
+            })
+            .collect();
+
+        MemoryImage { regions }
+    }
+}
+
+/// The initial state a block runs on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffInput {
+    /// The raw register file, see [`ExecutionContext::registers`].
+    pub registers: Vec<u8>,
+    pub memory: MemoryImage,
+}
+
+impl DiffInput {
+    /// Zeroed registers and no memory.
+    pub fn new(desc: &RegisterFileDesc) -> Self {
+        Self {
+            registers: vec![0; desc.total_size()],
+            memory: MemoryImage::new(),
+        }
+    }
+}
+
+/// The state observed after running a block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Observation {
+    pub registers: Vec<u8>,
+    pub memory: MemoryImage,
+    /// Every interrupt raised by the block, in order.
+    pub interrupts: Vec<Interrupt>,
+    /// The panic message if the backend panicked.
+    pub panic: Option<String>,
+}
+
+/// The first difference between the observations of two backends.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Difference {
+    Panic {
+        left: Option<String>,
+        right: Option<String>,
+    },
+    Interrupts {
+        left: Vec<Interrupt>,
+        right: Vec<Interrupt>,
+    },
+    Register {
+        id: RawRegisterId,
+        left: Vec<u8>,
+        right: Vec<u8>,
+    },
+    Memory {
+        addr: u64,
+        left: u8,
+        right: u8,
+    },
+}
+
+/// A block and an input that reproduce a difference.
+#[derive(Clone, Debug)]
+pub struct Reproducer {
+    pub block: BasicBlock,
+    pub input: DiffInput,
+    pub difference: Difference,
+}
+
+/// A divergence between two backends.
+#[derive(Clone, Debug)]
+pub struct Divergence {
+    pub block: BasicBlock,
+    pub input: DiffInput,
+    pub difference: Difference,
+    /// The smallest block and input found that still diverge, `None` if the divergence
+    /// can't be reproduced from the block alone.
+    pub reproducer: Option<Reproducer>,
+}
+
+/// Runs blocks on two backends and compares the results.
+pub struct Differential<A: Architecture, L: Codegen, R: Codegen> {
+    left: L,
+    right: R,
+    desc: RegisterFileDesc,
+    _arch: PhantomData<fn() -> A>,
+}
+
+impl<A: Architecture, L: Codegen, R: Codegen> Differential<A, L, R> {
+    pub fn new(left: L, right: R) -> Self {
+        Self {
+            left,
+            right,
+            desc: A::get_register_file_desc(),
+            _arch: PhantomData,
+        }
+    }
+
+    pub fn desc(&self) -> &RegisterFileDesc {
+        &self.desc
+    }
+
+    pub fn left(&self) -> &L {
+        &self.left
+    }
+
+    pub fn right(&self) -> &R {
+        &self.right
+    }
+
+    /// Run the block on both backends, returns the divergence with a minimized reproducer.
+    pub fn check_block(&self, bb: &BasicBlock, input: &DiffInput) -> Result<(), Box<Divergence>> {
+        let Some(difference) = self.difference(bb, input) else {
+            return Ok(());
+        };
+
+        Err(Box::new(Divergence {
+            block: bb.clone(),
+            input: input.clone(),
+            difference,
+            reproducer: self.minimize(bb, input),
+        }))
+    }
+
+    /// Same as [`check_block`](Self::check_block), but panics with the report.
+    pub fn assert_block(&self, bb: &BasicBlock, input: &DiffInput) {
+        if let Err(divergence) = self.check_block(bb, input) {
+            panic!("{}", divergence);
+        }
+    }
+
+    /// Run the block on both backends and compare the results.
+    pub fn difference(&self, bb: &BasicBlock, input: &DiffInput) -> Option<Difference> {
+        let left = observe::<A, L>(&self.left, bb, input);
+        let right = observe::<A, R>(&self.right, bb, input);
+
+        self.compare(&left, &right)
+    }
+
+    /// Find the first difference between two observations.
+    ///
+    /// A panic is only a difference if one backend did not panic, interrupts are compared
+    /// before registers (in register file order) and memory (in address order).
+    pub fn compare(&self, left: &Observation, right: &Observation) -> Option<Difference> {
+        if left.panic.is_some() != right.panic.is_some() {
+            return Some(Difference::Panic {
+                left: left.panic.clone(),
+                right: right.panic.clone(),
+            });
+        }
+
+        if left.interrupts != right.interrupts {
+            return Some(Difference::Interrupts {
+                left: left.interrupts.clone(),
+                right: right.interrupts.clone(),
+            });
+        }
+
+        let mut registers: Vec<_> = self.desc.storage_registers().collect();
+        registers.sort_by_key(|(id, reg)| (reg.offset, reg.size, *id));
+
+        for (id, reg) in registers {
+            let range = reg.offset..reg.offset + reg.size;
+            if left.registers[range.clone()] != right.registers[range.clone()] {
+                return Some(Difference::Register {
+                    id,
+                    left: left.registers[range.clone()].to_vec(),
+                    right: right.registers[range].to_vec(),
+                });
+            }
+        }
+
+        let mut regions: Vec<_> = left
+            .memory
+            .regions()
+            .iter()
+            .zip(right.memory.regions())
+            .collect();
+        regions.sort_by_key(|(region, _)| region.addr);
+
+        for (left, right) in regions {
+            let offset = left.data.iter().zip(&right.data).position(|(l, r)| l != r);
+
+            if let Some(offset) = offset {
+                return Some(Difference::Memory {
+                    addr: left.addr + offset as u64,
+                    left: left.data[offset],
+                    right: right.data[offset],
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Shrink the block and the input while they still diverge.
+    ///
+    /// Instructions are removed one by one, then registers and memory regions are zeroed.
+    pub fn minimize(&self, bb: &BasicBlock, input: &DiffInput) -> Option<Reproducer> {
+        self.difference(bb, input)?;
+
+        let mut bb = bb.clone();
+        let mut input = input.clone();
+
+        loop {
+            let mut changed = false;
+
+            for idx in (0..bb.inst().len()).rev() {
+                let mut candidate = bb.clone();
+                candidate.remove_inst(idx);
+
+                if self.difference(&candidate, &input).is_some() {
+                    bb = candidate;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        let mut registers: Vec<_> = self.desc.storage_registers().map(|(_, reg)| reg).collect();
+        registers.sort_by_key(|reg| reg.offset);
+
+        for reg in registers {
+            let range = reg.offset..reg.offset + reg.size;
+            if input.registers[range.clone()].iter().all(|&b| b == 0) {
+                continue;
+            }
+
+            let mut candidate = input.clone();
+            candidate.registers[range].fill(0);
+
+            if self.difference(&bb, &candidate).is_some() {
+                input = candidate;
+            }
+        }
+
+        for idx in 0..input.memory.regions.len() {
+            if input.memory.regions[idx].data.iter().all(|&b| b == 0) {
+                continue;
+            }
+
+            let mut candidate = input.clone();
+            candidate.memory.regions[idx].data.fill(0);
+
+            if self.difference(&bb, &candidate).is_some() {
+                input = candidate;
+            }
+        }
+
+        let difference = self.difference(&bb, &input)?;
+        Some(Reproducer {
+            block: bb,
+            input,
+            difference,
+        })
+    }
+}
+
+/// Run the block on a fresh context of `codegen`, every interrupt is recorded and resumed (an
+/// abort stops the block, see [`InterruptAction::Resume`]).
+pub fn observe<A: Architecture, C: Codegen>(
+    codegen: &C,
+    bb: &BasicBlock,
+    input: &DiffInput,
+) -> Observation {
+    let mmu = input.memory.to_mmu();
+    let mut interrupts = Vec::new();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let executable = codegen.compile::<A>(bb.clone());
+        let mut context = C::new_context::<A>();
+        context.registers_mut().copy_from_slice(&input.registers);
+
+        let mut handler = |_: &mut C::Context, interrupt| {
+            interrupts.push(interrupt);
+            InterruptAction::Resume
+        };
+        // SAFETY: the memory only contains the regions of the input.
+        unsafe { execute_with_handler(&executable, &mut context, &mmu, &mut handler) };
+
+        context.registers().to_vec()
+    }));
+
+    match result {
+        Ok(registers) => Observation {
+            registers,
+            // SAFETY: the memory only contains the regions of the input.
+            memory: unsafe { input.memory.capture(&mmu) },
+            interrupts,
+            panic: None,
+        },
+        Err(payload) => Observation {
+            registers: input.registers.clone(),
+            memory: input.memory.clone(),
+            interrupts,
+            panic: Some(panic_message(payload.as_ref())),
+        },
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+struct Bytes<'a>(&'a [u8]);
+
+impl fmt::Display for Bytes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difference::Panic { left, right } => {
+                write!(f, "panic: left {:?}, right {:?}", left, right)
+            }
+            Difference::Interrupts { left, right } => {
+                write!(f, "interrupts: left {:?}, right {:?}", left, right)
+            }
+            Difference::Register { id, left, right } => write!(
+                f,
+                "register {:?}: left {}, right {}",
+                id,
+                Bytes(left),
+                Bytes(right)
+            ),
+            Difference::Memory { addr, left, right } => {
+                write!(
+                    f,
+                    "memory {:#x}: left {:02x}, right {:02x}",
+                    addr, left, right
+                )
+            }
+        }
+    }
+}
+
+fn fmt_case(f: &mut fmt::Formatter<'_>, bb: &BasicBlock, input: &DiffInput) -> fmt::Result {
+    writeln!(f, "  block {:#x}:", bb.addr())?;
+    for (idx, inst) in bb.inst().iter().enumerate() {
+        writeln!(f, "    {}: {:?}", idx, inst)?;
+    }
+    writeln!(f, "    terminator: {:?}", bb.terminator())?;
+
+    writeln!(f, "  registers: {}", Bytes(&input.registers))?;
+    for region in input.memory.regions() {
+        writeln!(f, "  memory {:#x}: {}", region.addr, Bytes(&region.data))?;
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "backends diverged, {}", self.difference)?;
+        fmt_case(f, &self.block, &self.input)?;
+
+        match &self.reproducer {
+            Some(reproducer) => {
+                writeln!(f, "minimized reproducer, {}", reproducer.difference)?;
+                fmt_case(f, &reproducer.block, &reproducer.input)
+            }
+            None => writeln!(f, "not reproducible from the block alone"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        ir::{BasicBlock, IrConstant, IrInst, IrType, IrValue},
+        RegisterId,
+    };
+
+    use super::*;
+    use crate::codegen::rustjit::{RustjitCodegen, RustjitContext, RustjitExectuable};
+    use crate::testing::{TestArchitecture, TestRegisterId};
+
+    /// A backend that compiles `Add` as `Sub`.
+    struct AddAsSub;
+
+    impl Codegen for AddAsSub {
+        type Context = RustjitContext;
+        type Executable = RustjitExectuable;
+
+        fn new_context<A: Architecture>() -> Self::Context {
+            RustjitCodegen::new_context::<A>()
+        }
+
+        fn compile<A: Architecture>(&self, mut bb: BasicBlock) -> Self::Executable {
+            for _ in 0..bb.inst().len() {
+                let inst = match bb.remove_inst(0) {
+                    IrInst::Add { dst, lhs, rhs } => IrInst::Sub { dst, lhs, rhs },
+                    inst => inst,
+                };
+                bb.push_inst(inst);
+            }
+
+            RustjitCodegen.compile::<A>(bb)
+        }
+    }
+
+    fn r(v: u8) -> IrValue {
+        TestRegisterId::R(v).value()
+    }
+
+    fn sample_block() -> BasicBlock {
+        let mut bb = BasicBlock::new(0x1000);
+        let tmp = bb.new_variable(IrType::U64);
+
+        bb.push_inst(IrInst::Sub {
+            dst: tmp,
+            lhs: r(0),
+            rhs: IrValue::Constant(IrConstant::U64(1)),
+        });
+        bb.push_inst(IrInst::Assign {
+            dst: r(3),
+            src: tmp,
+        });
+        bb.push_inst(IrInst::Add {
+            dst: r(2),
+            lhs: r(0),
+            rhs: r(1),
+        });
+        bb.push_inst(IrInst::Store {
+            dst: IrValue::Constant(IrConstant::U64(0x2000)),
+            src: r(2),
+        });
+        bb
+    }
+
+    fn sample_input(desc: &RegisterFileDesc) -> DiffInput {
+        let mut input = DiffInput::new(desc);
+        let r0 = desc.register(TestRegisterId::R(0).raw()).offset;
+        let r1 = desc.register(TestRegisterId::R(1).raw()).offset;
+        input.registers[r0..r0 + 8].copy_from_slice(&7u64.to_le_bytes());
+        input.registers[r1..r1 + 8].copy_from_slice(&5u64.to_le_bytes());
+        input.memory.add_region(0x2000, vec![0xaa; 16]);
+        input
+    }
+
+    #[test]
+    fn same_backend_does_not_diverge() {
+        let diff = Differential::<TestArchitecture, _, _>::new(RustjitCodegen, RustjitCodegen);
+        let input = sample_input(diff.desc());
+
+        diff.assert_block(&sample_block(), &input);
+    }
+
+    #[test]
+    fn divergence_is_reported_and_minimized() {
+        let diff = Differential::<TestArchitecture, _, _>::new(RustjitCodegen, AddAsSub);
+        let input = sample_input(diff.desc());
+
+        let divergence = diff.check_block(&sample_block(), &input).unwrap_err();
+        assert_eq!(
+            divergence.difference,
+            Difference::Register {
+                id: TestRegisterId::R(2).raw(),
+                left: 12u64.to_le_bytes().to_vec(),
+                right: 2u64.to_le_bytes().to_vec(),
+            }
+        );
+
+        let reproducer = divergence.reproducer.as_ref().unwrap();
+        assert_eq!(reproducer.block.inst(), &sample_block().inst()[2..3]);
+        assert!(reproducer.input.memory.regions()[0]
+            .data
+            .iter()
+            .all(|&b| b == 0));
+        assert!(divergence.to_string().contains("minimized reproducer"));
+    }
+
+    #[test]
+    fn memory_divergence_is_reported() {
+        let diff = Differential::<TestArchitecture, _, _>::new(RustjitCodegen, AddAsSub);
+        let mut bb = BasicBlock::new(0);
+        bb.push_inst(IrInst::Add {
+            dst: r(2),
+            lhs: r(0),
+            rhs: r(1),
+        });
+        bb.push_inst(IrInst::Store {
+            dst: IrValue::Constant(IrConstant::U64(0x2000)),
+            src: r(2),
+        });
+        bb.push_inst(IrInst::Assign {
+            dst: r(2),
+            src: IrValue::Constant(IrConstant::U64(0)),
+        });
+
+        let divergence = diff
+            .check_block(&bb, &sample_input(diff.desc()))
+            .unwrap_err();
+        assert_eq!(
+            divergence.difference,
+            Difference::Memory {
+                addr: 0x2000,
+                left: 12,
+                right: 2,
+            }
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use core::{ir::BasicBlock, Architecture, Interrupt};
+use std::collections::HashMap;
+
+use device::devices::SoftMmu;
+
+use crate::{
+    codegen::{Codegen, Executable, ExecutionContext},
+    interrupt::{execute_with_handler, BlockExit, InterruptAction},
+    vcpu::translate,
+};
+
+use super::{DiffInput, Differential, Divergence, MemoryImage, Observation};
+
+/// Runs a guest program on two backends at once, comparing them after every block.
+///
+/// Each backend has its own copy of the memory image, only the regions of the image are
+/// compared.
+pub struct Lockstep<A: Architecture, L: Codegen, R: Codegen> {
+    differential: Differential<A, L, R>,
+    memory: MemoryImage,
+    left: Side<L>,
+    right: Side<R>,
+    blocks: HashMap<u64, CompiledPair<L, R>>,
+}
+
+struct Side<C: Codegen> {
+    context: C::Context,
+    mmu: SoftMmu,
+}
+
+struct CompiledPair<L: Codegen, R: Codegen> {
+    block: BasicBlock,
+    left: L::Executable,
+    right: R::Executable,
+}
+
+/// The handler of a lockstep run, it is called for both backends with the same interrupts.
+pub type LockstepHandler<'a> =
+    dyn FnMut(&mut dyn ExecutionContext, Interrupt) -> InterruptAction + 'a;
+
+impl<A: Architecture, L: Codegen, R: Codegen> Lockstep<A, L, R> {
+    pub fn new(left: L, right: R, memory: MemoryImage) -> Self {
+        Self {
+            differential: Differential::new(left, right),
+            left: Side {
+                context: L::new_context::<A>(),
+                mmu: memory.to_mmu(),
+            },
+            right: Side {
+                context: R::new_context::<A>(),
+                mmu: memory.to_mmu(),
+            },
+            memory,
+            blocks: HashMap::new(),
+        }
+    }
+
+    pub fn left(&self) -> &L::Context {
+        &self.left.context
+    }
+
+    pub fn right(&self) -> &R::Context {
+        &self.right.context
+    }
+
+    /// Set the registers of both backends.
+    pub fn set_registers(&mut self, registers: &[u8]) {
+        self.left.context.registers_mut().copy_from_slice(registers);
+        self.right
+            .context
+            .registers_mut()
+            .copy_from_slice(registers);
+    }
+
+    /// Set the pc of both backends.
+    pub fn set_pc(&mut self, pc: u64) {
+        self.left.context.set_pc(pc);
+        self.right.context.set_pc(pc);
+    }
+
+    /// Execute the next block on both backends and compare them.
+    ///
+    /// The block is translated from the memory of the left backend, in the state of its
+    /// context.
+    ///
+    /// # Safety
+    ///
+    /// See [`Executable::execute`](crate::codegen::Executable::execute).
+    pub unsafe fn step(
+        &mut self,
+        handler: &mut LockstepHandler<'_>,
+    ) -> Result<BlockExit, Box<Divergence>> {
+        let pc = self.left.context.pc();
+        let input = DiffInput {
+            registers: self.left.context.registers().to_vec(),
+            memory: self.memory.capture(&self.left.mmu),
+        };
+
+        if !self.blocks.contains_key(&pc) {
+            match translate::<A>(&mut self.left.context, &self.left.mmu) {
+                Ok((block, _)) => {
+                    let pair = CompiledPair {
+                        left: self.differential.left().compile::<A>(block.clone()),
+                        right: self.differential.right().compile::<A>(block.clone()),
+                        block,
+                    };
+                    self.blocks.insert(pc, pair);
+                }
+                Err(interrupt) => {
+                    let left = Self::deliver(&mut self.left.context, handler, interrupt);
+                    let right = Self::deliver(&mut self.right.context, handler, interrupt);
+                    self.compare(
+                        &BasicBlock::new(pc),
+                        input,
+                        vec![interrupt],
+                        vec![interrupt],
+                    )?;
+                    debug_assert_eq!(left, right);
+
+                    return self.settle(left);
+                }
+            }
+        }
+
+        let pair = &self.blocks[&pc];
+
+        let mut left_interrupts = Vec::new();
+        let left_exit = execute_with_handler(
+            &pair.left,
+            &mut self.left.context,
+            &self.left.mmu,
+            &mut |context: &mut L::Context, interrupt| {
+                left_interrupts.push(interrupt);
+                handler(context, interrupt)
+            },
+        );
+
+        let mut right_interrupts = Vec::new();
+        let right_exit = execute_with_handler(
+            &pair.right,
+            &mut self.right.context,
+            &self.right.mmu,
+            &mut |context: &mut R::Context, interrupt| {
+                right_interrupts.push(interrupt);
+                handler(context, interrupt)
+            },
+        );
+
+        let block = pair.block.clone();
+        self.compare(&block, input, left_interrupts, right_interrupts)?;
+        debug_assert_eq!(left_exit, right_exit);
+
+        self.settle(left_exit)
+    }
+
+    /// Let the guest take the interrupt of a [`BlockExit::Take`] on both backends and compare
+    /// them.
+    unsafe fn settle(&mut self, exit: BlockExit) -> Result<BlockExit, Box<Divergence>> {
+        let BlockExit::Take(interrupt) = exit else {
+            return Ok(exit);
+        };
+
+        let input = DiffInput {
+            registers: self.left.context.registers().to_vec(),
+            memory: self.memory.capture(&self.left.mmu),
+        };
+        let block = A::interrupt(interrupt);
+        let left = self.differential.left().compile::<A>(block.clone());
+        let right = self.differential.right().compile::<A>(block.clone());
+        left.execute(&mut self.left.context, &self.left.mmu);
+        right.execute(&mut self.right.context, &self.right.mmu);
+        self.compare(&block, input, Vec::new(), Vec::new())?;
+
+        Ok(BlockExit::Continue)
+    }
+
+    /// Step until a block stops, see [`step`](Self::step).
+    ///
+    /// # Safety
+    ///
+    /// See [`Executable::execute`](crate::codegen::Executable::execute).
+    pub unsafe fn run(
+        &mut self,
+        handler: &mut LockstepHandler<'_>,
+    ) -> Result<Interrupt, Box<Divergence>> {
+        loop {
+            if let BlockExit::Stop(interrupt) = self.step(handler)? {
+                return Ok(interrupt);
+            }
+        }
+    }
+
+    fn deliver(
+        context: &mut dyn ExecutionContext,
+        handler: &mut LockstepHandler<'_>,
+        interrupt: Interrupt,
+    ) -> BlockExit {
+        match handler(context, interrupt) {
+            InterruptAction::Resume | InterruptAction::Retry => BlockExit::Continue,
+            InterruptAction::Redirect(pc) => {
+                context.set_pc(pc);
+                BlockExit::Continue
+            }
+            InterruptAction::Take => BlockExit::Take(interrupt),
+            InterruptAction::Stop => BlockExit::Stop(interrupt),
+        }
+    }
+
+    unsafe fn compare(
+        &self,
+        block: &BasicBlock,
+        input: DiffInput,
+        left_interrupts: Vec<Interrupt>,
+        right_interrupts: Vec<Interrupt>,
+    ) -> Result<(), Box<Divergence>> {
+        let left = Observation {
+            registers: self.left.context.registers().to_vec(),
+            memory: self.memory.capture(&self.left.mmu),
+            interrupts: left_interrupts,
+            panic: None,
+        };
+        let right = Observation {
+            registers: self.right.context.registers().to_vec(),
+            memory: self.memory.capture(&self.right.mmu),
+            interrupts: right_interrupts,
+            panic: None,
+        };
+
+        let Some(difference) = self.differential.compare(&left, &right) else {
+            return Ok(());
+        };
+
+        Err(Box::new(Divergence {
+            block: block.clone(),
+            reproducer: self.differential.minimize(block, &input),
+            input,
+            difference,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{Exception, ExceptionKind, Interrupt};
+
+    use super::*;
+    use crate::codegen::rustjit::RustjitCodegen;
+    use crate::testing::{TestArchitecture, TestInst, TEST_INTERRUPT_VECTOR};
+
+    #[test]
+    fn lockstep_runs_a_program_to_the_end() {
+        let program = TestInst::assemble(&[
+            TestInst::Mov { rd: 0, imm: 0x2000 },
+            TestInst::Mov { rd: 1, imm: 40 },
+            TestInst::Mov { rd: 2, imm: 2 },
+            TestInst::Add {
+                rd: 3,
+                rn: 1,
+                rm: 2,
+            },
+            TestInst::Store { rt: 3, rn: 0 },
+            TestInst::Yield,
+            TestInst::Load { rt: 4, rn: 0 },
+            TestInst::Halt { imm: 7 },
+        ]);
+
+        let mut memory = MemoryImage::new();
+        memory.add_region(0x1000, program);
+        memory.add_region(0x2000, vec![0; 8]);
+
+        let mut lockstep =
+            Lockstep::<TestArchitecture, _, _>::new(RustjitCodegen, RustjitCodegen, memory);
+        lockstep.set_pc(0x1000);
+
+        // The handler is called once for each backend.
+        let mut yields = 0;
+        let interrupt = unsafe {
+            lockstep.run(&mut |_, interrupt| match interrupt {
+                Interrupt::Yield => {
+                    yields += 1;
+                    InterruptAction::Resume
+                }
+                _ => InterruptAction::Stop,
+            })
+        }
+        .unwrap();
+
+        assert_eq!(yields, 2);
+        assert_eq!(
+            interrupt,
+            Interrupt::Exception(Exception {
+                kind: ExceptionKind::Breakpoint(7),
+                syndrome: 0,
+            })
+        );
+        assert_eq!(lockstep.left().pc(), 0x1000 + 8 * 4);
+        assert_eq!(lockstep.left().registers(), lockstep.right().registers());
+    }
+
+    #[test]
+    fn lockstep_takes_interrupts() {
+        let program = TestInst::assemble(&[TestInst::Nop, TestInst::Halt { imm: 1 }]);
+        let handler = TestInst::assemble(&[TestInst::Halt { imm: 2 }]);
+
+        let mut memory = MemoryImage::new();
+        memory.add_region(0x1000, program);
+        memory.add_region(TEST_INTERRUPT_VECTOR, handler);
+
+        let mut lockstep =
+            Lockstep::<TestArchitecture, _, _>::new(RustjitCodegen, RustjitCodegen, memory);
+        lockstep.set_pc(0x1000);
+
+        let interrupt = unsafe {
+            lockstep.run(&mut |_, interrupt| match interrupt {
+                Interrupt::Exception(Exception {
+                    kind: ExceptionKind::Breakpoint(1),
+                    ..
+                }) => InterruptAction::Take,
+                _ => InterruptAction::Stop,
+            })
+        }
+        .unwrap();
+
+        assert_eq!(
+            interrupt,
+            Interrupt::Exception(Exception {
+                kind: ExceptionKind::Breakpoint(2),
+                syndrome: 0,
+            })
+        );
+        assert_eq!(lockstep.left().pc(), TEST_INTERRUPT_VECTOR + 4);
+        assert_eq!(lockstep.left().registers(), lockstep.right().registers());
+    }
+}
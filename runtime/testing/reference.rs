@@ -0,0 +1,487 @@
+use core::{
+    ir::{AtomicOp, BasicBlock, IrInst, IrIntrinsic, IrType, IrValue, TypeOf},
+    Abort, AbortKind, AccessType, Architecture, Interrupt, RawRegisterId, RegisterFileDesc,
+    RegisterId,
+};
+use std::collections::HashMap;
+
+use device::devices::{Reservation, SoftMmu};
+
+use crate::codegen::{
+    soft_float, Codegen, ExecOutcome, Executable, ExecutionContext, ResumeToken, Tlb,
+};
+
+/// A plain interpreter of the IR, the semantics every backend is checked against.
+///
+/// It is slow and written to be obviously correct:
+/// - Integers wrap at the width of their type, signed types use two's complement.
+/// - Division by zero gives zero, remainder by zero gives the dividend.
+/// - Shift amounts are taken modulo the width of the type.
+/// - Terminators don't change the state, the instructions of the block update the pc.
+/// - Float intrinsics are evaluated by the [`soft_float`] every backend shares.
+/// - Atomic accesses go through the exclusive monitor of the [`SoftMmu`].
+/// - Virtual addresses are translated by the same [`Tlb`] as the other backends.
+///
+/// Only integer and `Bool` values are supported.
+pub struct ReferenceCodegen;
+
+pub struct ReferenceContext {
+    desc: RegisterFileDesc,
+    registers: Vec<u8>,
+    variables: HashMap<usize, Value>,
+    pc: RawRegisterId,
+    exclusive: Option<Reservation>,
+    tlb: Tlb,
+}
+
+pub struct ReferenceExecutable {
+    block: BasicBlock,
+}
+
+/// A value with its raw bits, zero extended above the width of its type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Value {
+    bits: u128,
+    ty: IrType,
+}
+
+impl Value {
+    fn new(ty: IrType, bits: u128) -> Self {
+        assert!(
+            !ty.is_float() && !matches!(ty, IrType::Vector(..)),
+            "{ty:?} is not supported by the reference evaluator"
+        );
+
+        Self {
+            bits: bits & mask(ty),
+            ty,
+        }
+    }
+
+    /// The value sign extended to 128 bits.
+    fn signed(self) -> i128 {
+        let shift = 128 - width(self.ty);
+        ((self.bits as i128) << shift) >> shift
+    }
+
+    fn from_bytes(ty: IrType, bytes: &[u8]) -> Self {
+        let mut raw = [0; 16];
+        raw[..bytes.len()].copy_from_slice(bytes);
+        Self::new(ty, u128::from_le_bytes(raw))
+    }
+
+    fn to_bytes(self) -> Vec<u8> {
+        self.bits.to_le_bytes()[..self.ty.size_in_bytes()].to_vec()
+    }
+}
+
+fn width(ty: IrType) -> u32 {
+    match ty {
+        IrType::Bool => 1,
+        ty => ty.size_in_bytes() as u32 * 8,
+    }
+}
+
+fn mask(ty: IrType) -> u128 {
+    match width(ty) {
+        128 => u128::MAX,
+        width => (1 << width) - 1,
+    }
+}
+
+impl ExecutionContext for ReferenceContext {
+    fn pc(&self) -> u64 {
+        self.read_register(self.pc, IrType::U64).bits as u64
+    }
+
+    fn set_pc(&mut self, pc: u64) {
+        self.write_register(self.pc, Value::new(IrType::U64, pc as u128));
+    }
+
+    fn registers(&self) -> &[u8] {
+        &self.registers
+    }
+
+    fn registers_mut(&mut self) -> &mut [u8] {
+        &mut self.registers
+    }
+
+    unsafe fn translate(
+        &mut self,
+        address: u64,
+        access: AccessType,
+        mmu: &SoftMmu,
+    ) -> Result<u64, Interrupt> {
+        self.tlb.translate(&self.registers, mmu, address, 1, access)
+    }
+}
+
+impl ReferenceContext {
+    fn read_register(&self, id: RawRegisterId, ty: IrType) -> Value {
+        let reg = self.desc.register(id);
+        assert_eq!(reg.size, ty.size_in_bytes(), "register size mismatch");
+
+        if reg.is_zero {
+            return Value::new(ty, 0);
+        }
+        Value::from_bytes(ty, &self.registers[reg.offset..reg.offset + reg.size])
+    }
+
+    fn write_register(&mut self, id: RawRegisterId, value: Value) {
+        let reg = self.desc.register(id);
+        assert!(!reg.is_read_only, "register is read only");
+        assert_eq!(reg.size, value.ty.size_in_bytes(), "register size mismatch");
+
+        if reg.is_zero {
+            return;
+        }
+        let range = reg.offset..reg.offset + reg.size;
+        self.registers[self.desc.write_extent(id)].fill(0);
+        self.registers[range].copy_from_slice(&value.to_bytes());
+    }
+
+    fn get(&self, value: IrValue) -> Value {
+        match value {
+            IrValue::Variable(_, id) => self.variables[&id],
+            IrValue::Register(ty, id) => self.read_register(id, ty),
+            IrValue::Constant(constant) => Value::new(constant.ty(), constant.to_bits()),
+        }
+    }
+
+    fn set(&mut self, dst: IrValue, value: Value) {
+        // The result always has the type of the destination.
+        let value = Value::new(dst.ty(), value.bits);
+
+        match dst {
+            IrValue::Variable(_, id) => {
+                self.variables.insert(id, value);
+            }
+            IrValue::Register(_, id) => self.write_register(id, value),
+            IrValue::Constant(_) => panic!("Constant cannot be set"),
+        }
+    }
+
+    fn binary(&mut self, dst: IrValue, lhs: IrValue, rhs: IrValue, op: fn(Value, Value) -> u128) {
+        let lhs = self.get(lhs);
+        let rhs = self.get(rhs);
+        assert_eq!(lhs.ty, rhs.ty, "operand type mismatch");
+
+        self.set(dst, Value::new(lhs.ty, op(lhs, rhs)));
+    }
+
+    /// Translate the address of an aligned access of `ty`, which stays in a page.
+    unsafe fn translate_aligned(
+        &mut self,
+        mmu: &SoftMmu,
+        addr: u64,
+        ty: IrType,
+        access: AccessType,
+    ) -> Result<u64, Interrupt> {
+        if let Some(interrupt) = check_aligned(addr, ty, access) {
+            return Err(interrupt);
+        }
+
+        self.tlb
+            .translate(&self.registers, mmu, addr, ty.size_in_bytes(), access)
+    }
+
+    /// Atomically replace the value of `ty` at the address `addr` with the bits `op` computes
+    /// from it, unless it returns `None`. Returns the old value.
+    unsafe fn update(
+        &mut self,
+        mmu: &SoftMmu,
+        addr: IrValue,
+        ty: IrType,
+        op: impl FnOnce(Value) -> Option<u128>,
+    ) -> Result<Value, Interrupt> {
+        let addr = self.get(addr).bits as u64;
+        let physical = self.translate_aligned(mmu, addr, ty, AccessType::Write)?;
+
+        let mut buf = vec![0; ty.size_in_bytes()];
+        let mut old = Value::new(ty, 0);
+        let updated = mmu.try_update_at(physical, &mut buf, |bytes| {
+            old = Value::from_bytes(ty, bytes);
+            let new = op(old);
+            if let Some(new) = new {
+                bytes.copy_from_slice(&Value::new(ty, new).to_bytes());
+            }
+            new.is_some()
+        });
+
+        match updated {
+            Ok(()) => Ok(old),
+            Err(_) => Err(abort(addr, AccessType::Write, AbortKind::Unmapped)),
+        }
+    }
+
+    /// Evaluate a single instruction, returns the raised interrupt.
+    unsafe fn evaluate(&mut self, inst: &IrInst, mmu: &SoftMmu) -> Option<Interrupt> {
+        match *inst {
+            IrInst::Add { dst, lhs, rhs } => {
+                self.binary(dst, lhs, rhs, |l, r| l.bits.wrapping_add(r.bits))
+            }
+            IrInst::Sub { dst, lhs, rhs } => {
+                self.binary(dst, lhs, rhs, |l, r| l.bits.wrapping_sub(r.bits))
+            }
+            IrInst::Mul { dst, lhs, rhs } => {
+                self.binary(dst, lhs, rhs, |l, r| l.bits.wrapping_mul(r.bits))
+            }
+            IrInst::Div { dst, lhs, rhs } => self.binary(dst, lhs, rhs, |l, r| match r.bits {
+                0 => 0,
+                _ if l.ty.is_signed() => l.signed().wrapping_div(r.signed()) as u128,
+                _ => l.bits / r.bits,
+            }),
+            IrInst::Rem { dst, lhs, rhs } => self.binary(dst, lhs, rhs, |l, r| match r.bits {
+                0 => l.bits,
+                _ if l.ty.is_signed() => l.signed().wrapping_rem(r.signed()) as u128,
+                _ => l.bits % r.bits,
+            }),
+            IrInst::BitAnd { dst, lhs, rhs } => self.binary(dst, lhs, rhs, |l, r| l.bits & r.bits),
+            IrInst::BitOr { dst, lhs, rhs } => self.binary(dst, lhs, rhs, |l, r| l.bits | r.bits),
+            IrInst::BitXor { dst, lhs, rhs } => self.binary(dst, lhs, rhs, |l, r| l.bits ^ r.bits),
+            IrInst::Shl { dst, lhs, rhs } => self.binary(dst, lhs, rhs, |l, r| {
+                l.bits << (r.bits % width(l.ty) as u128)
+            }),
+            IrInst::Shr { dst, lhs, rhs } => self.binary(dst, lhs, rhs, |l, r| {
+                let amount = (r.bits % width(l.ty) as u128) as u32;
+                match l.ty.is_signed() {
+                    true => (l.signed() >> amount) as u128,
+                    false => l.bits >> amount,
+                }
+            }),
+            IrInst::Neg { dst, src } => {
+                let src = self.get(src);
+                self.set(dst, Value::new(src.ty, src.bits.wrapping_neg()));
+            }
+            IrInst::BitNot { dst, src } => {
+                let src = self.get(src);
+                self.set(dst, Value::new(src.ty, !src.bits));
+            }
+            IrInst::Assign { dst, src } => {
+                let src = self.get(src);
+                assert_eq!(src.ty, dst.ty(), "operand type mismatch");
+                self.set(dst, src);
+            }
+            IrInst::ZextCast { dst, src } => {
+                let src = self.get(src);
+                self.set(dst, Value::new(dst.ty(), src.bits));
+            }
+            IrInst::SextCast { dst, src } => {
+                let src = self.get(src);
+                self.set(dst, Value::new(dst.ty(), src.signed() as u128));
+            }
+            IrInst::Load { dst, src } | IrInst::LoadUnprivileged { dst, src } => {
+                let unprivileged = matches!(inst, IrInst::LoadUnprivileged { .. });
+                let addr = self.get(src).bits as u64;
+                let mut buf = vec![0; dst.ty().size_in_bytes()];
+
+                if let Err(interrupt) =
+                    self.tlb
+                        .read(&self.registers, mmu, addr, &mut buf, unprivileged)
+                {
+                    return Some(interrupt);
+                }
+                self.set(dst, Value::from_bytes(dst.ty(), &buf));
+            }
+            IrInst::Store { dst, src } | IrInst::StoreUnprivileged { dst, src } => {
+                let unprivileged = matches!(inst, IrInst::StoreUnprivileged { .. });
+                let addr = self.get(dst).bits as u64;
+                let bytes = self.get(src).to_bytes();
+
+                if let Err(interrupt) =
+                    self.tlb
+                        .write(&self.registers, mmu, addr, &bytes, unprivileged)
+                {
+                    return Some(interrupt);
+                }
+            }
+            IrInst::Interrupt { cond, interrupt } => {
+                if self.get(cond).bits != 0 {
+                    return Some(interrupt);
+                }
+            }
+            IrInst::Fence { .. } => {}
+            IrInst::AtomicRmw { op, dst, addr, src } => {
+                let src = self.get(src);
+                assert_eq!(src.ty, dst.ty(), "operand type mismatch");
+
+                let old = self.update(mmu, addr, dst.ty(), |old| {
+                    let less = match old.ty.is_signed() {
+                        true => old.signed() < src.signed(),
+                        false => old.bits < src.bits,
+                    };
+
+                    Some(match op {
+                        AtomicOp::Swap => src.bits,
+                        AtomicOp::Add => old.bits.wrapping_add(src.bits),
+                        AtomicOp::And => old.bits & src.bits,
+                        AtomicOp::Or => old.bits | src.bits,
+                        AtomicOp::Xor => old.bits ^ src.bits,
+                        AtomicOp::Max => match less {
+                            true => src.bits,
+                            false => old.bits,
+                        },
+                        AtomicOp::Min => match less {
+                            true => old.bits,
+                            false => src.bits,
+                        },
+                    })
+                });
+                match old {
+                    Ok(old) => self.set(dst, old),
+                    Err(interrupt) => return Some(interrupt),
+                }
+            }
+            IrInst::CompareExchange {
+                dst,
+                addr,
+                expected,
+                src,
+            } => {
+                let (expected, src) = (self.get(expected), self.get(src));
+                assert_eq!(expected.ty, dst.ty(), "operand type mismatch");
+                assert_eq!(src.ty, dst.ty(), "operand type mismatch");
+
+                let old = self.update(mmu, addr, dst.ty(), |old| {
+                    (old.bits == expected.bits).then_some(src.bits)
+                });
+                match old {
+                    Ok(old) => self.set(dst, old),
+                    Err(interrupt) => return Some(interrupt),
+                }
+            }
+            IrInst::LoadExclusive { dst, src } => {
+                let addr = self.get(src).bits as u64;
+                let physical = match self.translate_aligned(mmu, addr, dst.ty(), AccessType::Read) {
+                    Ok(physical) => physical,
+                    Err(interrupt) => return Some(interrupt),
+                };
+
+                let mut buf = vec![0; dst.ty().size_in_bytes()];
+                match mmu.try_read_exclusive_at(physical, &mut buf) {
+                    Ok(reservation) => self.exclusive = Some(reservation),
+                    Err(_) => return Some(abort(addr, AccessType::Read, AbortKind::Unmapped)),
+                }
+                self.set(dst, Value::from_bytes(dst.ty(), &buf));
+            }
+            IrInst::StoreExclusive { dst, src, status } => {
+                let addr = self.get(dst).bits as u64;
+                let value = self.get(src);
+                let physical = match self.translate_aligned(mmu, addr, value.ty, AccessType::Write)
+                {
+                    Ok(physical) => physical,
+                    Err(interrupt) => return Some(interrupt),
+                };
+
+                let stored = match self.exclusive.take() {
+                    None => false,
+                    Some(reservation) => {
+                        match mmu.try_write_exclusive_at(reservation, physical, &value.to_bytes()) {
+                            Ok(stored) => stored,
+                            Err(_) => {
+                                return Some(abort(addr, AccessType::Write, AbortKind::Unmapped))
+                            }
+                        }
+                    }
+                };
+                self.set(status, Value::new(IrType::Bool, stored as u128));
+            }
+            IrInst::ClearExclusive => self.exclusive = None,
+            IrInst::InvalidateTlb {
+                addr,
+                asid,
+                broadcast,
+            } => {
+                let addr = addr.map(|addr| self.get(addr).bits as u64);
+                let asid = asid.map(|asid| self.get(asid).bits as u16);
+                self.tlb.invalidate(addr, asid);
+                if broadcast {
+                    mmu.invalidate_tlbs();
+                }
+            }
+            IrInst::Intrinsic(IrIntrinsic::Float {
+                op,
+                dst,
+                ref srcs,
+                control,
+                status,
+            }) => {
+                let srcs: Vec<_> = srcs.iter().map(|&src| self.get(src)).collect();
+                let src_tys: Vec<_> = srcs.iter().map(|src| src.ty).collect();
+                if let Err(err) = soft_float::check(op, &src_tys, dst.ty()) {
+                    panic!("{err}");
+                }
+
+                let bits: Vec<_> = srcs.iter().map(|src| src.bits).collect();
+                let control = self.get(control).bits as u64;
+                let (result, raised) =
+                    soft_float::evaluate(op, &bits, width(srcs[0].ty), width(dst.ty()), control);
+                self.set(dst, Value::new(dst.ty(), result));
+                self.set(status, Value::new(IrType::U64, raised as u128));
+            }
+        }
+
+        None
+    }
+}
+
+fn abort(fault_address: u64, access: AccessType, kind: AbortKind) -> Interrupt {
+    Interrupt::Aborts(Abort {
+        fault_address,
+        access,
+        kind,
+        syndrome: 0,
+    })
+}
+
+/// The abort of an atomic access of `ty` at an address not aligned to its size.
+fn check_aligned(addr: u64, ty: IrType, access: AccessType) -> Option<Interrupt> {
+    match addr % ty.size_in_bytes() as u64 {
+        0 => None,
+        _ => Some(abort(addr, access, AbortKind::Unaligned)),
+    }
+}
+
+impl Executable for ReferenceExecutable {
+    type Context = ReferenceContext;
+
+    unsafe fn resume(
+        &self,
+        context: &mut Self::Context,
+        io_device: &SoftMmu,
+        token: ResumeToken,
+    ) -> ExecOutcome {
+        for (idx, inst) in self.block.inst().iter().enumerate().skip(token.position()) {
+            if let Some(interrupt) = context.evaluate(inst, io_device) {
+                return ExecOutcome::Interrupted {
+                    interrupt,
+                    resume: ResumeToken::new(idx + 1),
+                };
+            }
+        }
+
+        ExecOutcome::Completed
+    }
+}
+
+impl Codegen for ReferenceCodegen {
+    type Context = ReferenceContext;
+    type Executable = ReferenceExecutable;
+
+    fn new_context<A: Architecture>() -> Self::Context {
+        let desc = A::get_register_file_desc();
+
+        ReferenceContext {
+            registers: vec![0; desc.total_size()],
+            desc,
+            variables: HashMap::new(),
+            pc: A::get_pc_register().raw(),
+            exclusive: None,
+            tlb: Tlb::new::<A>(),
+        }
+    }
+
+    fn compile<A: Architecture>(&self, bb: BasicBlock) -> Self::Executable {
+        ReferenceExecutable { block: bb }
+    }
+}
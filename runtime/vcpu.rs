@@ -0,0 +1,236 @@
+use core::{
+    ir::{BasicBlock, BasicBlockTerminator},
+    Abort, AbortKind, AccessType, Architecture, Instruction, Interrupt,
+};
+use std::{collections::HashMap, marker::PhantomData, sync::Arc};
+
+use device::devices::SoftMmu;
+
+use crate::{
+    codegen::{Codegen, ExecOutcome, Executable, ExecutionContext, PAGE_SIZE},
+    interrupt::{execute_with_handler, BlockExit, InterruptAction, InterruptHandler},
+};
+
+/// The maximum number of bytes fetched to decode a single instruction.
+const MAX_INST_SIZE: usize = 16;
+
+/// The maximum number of instructions translated into a single block.
+const MAX_BLOCK_INSTS: usize = 64;
+
+struct CompiledBlock<E> {
+    executable: E,
+    inst_count: u64,
+}
+
+/// A virtual cpu.
+///
+/// Each vcpu owns its execution context and its cache of compiled blocks, the codegen and
+/// the memory are shared with the other vcpus of the machine.
+pub struct Vcpu<A: Architecture, C: Codegen> {
+    codegen: Arc<C>,
+    context: C::Context,
+    /// The compiled blocks by the physical address of their code, they don't depend on the
+    /// virtual address it is mapped at.
+    blocks: HashMap<u64, CompiledBlock<C::Executable>>,
+    retired: u64,
+    _arch: PhantomData<fn() -> A>,
+}
+
+impl<A: Architecture, C: Codegen> Vcpu<A, C> {
+    pub fn new(codegen: Arc<C>) -> Self {
+        Self {
+            codegen,
+            context: C::new_context::<A>(),
+            blocks: HashMap::new(),
+            retired: 0,
+            _arch: PhantomData,
+        }
+    }
+
+    pub fn context(&self) -> &C::Context {
+        &self.context
+    }
+
+    pub fn context_mut(&mut self) -> &mut C::Context {
+        &mut self.context
+    }
+
+    /// The number of guest instructions executed so far.
+    ///
+    /// It is counted per block, so a block left early by an interrupt is fully counted.
+    pub fn retired(&self) -> u64 {
+        self.retired
+    }
+
+    /// Drop every compiled block, e.g. after the guest modified its code.
+    pub fn flush_blocks(&mut self) {
+        self.blocks.clear();
+    }
+
+    /// Execute the block at the current pc, compiling it first if needed.
+    ///
+    /// # Safety
+    ///
+    /// See [`Executable::execute`].
+    pub unsafe fn step(
+        &mut self,
+        mmu: &SoftMmu,
+        handler: &mut impl InterruptHandler<C::Context>,
+    ) -> BlockExit {
+        let pc = self.context.pc();
+        let physical_pc = match self.context.translate(pc, AccessType::Execute, mmu) {
+            Ok(physical_pc) => physical_pc,
+            Err(interrupt) => return self.deliver(interrupt, mmu, handler),
+        };
+
+        if !self.blocks.contains_key(&physical_pc) {
+            let (bb, inst_count) = match translate::<A>(&mut self.context, mmu) {
+                Ok(translated) => translated,
+                Err(interrupt) => return self.deliver(interrupt, mmu, handler),
+            };
+
+            let executable = self.codegen.compile::<A>(bb);
+            self.blocks.insert(
+                physical_pc,
+                CompiledBlock {
+                    executable,
+                    inst_count,
+                },
+            );
+        }
+
+        let block = &self.blocks[&physical_pc];
+        self.retired += block.inst_count;
+        let exit = execute_with_handler(&block.executable, &mut self.context, mmu, handler);
+        self.settle(exit, mmu)
+    }
+
+    /// Pass an interrupt raised outside of a block to the handler, e.g. an external
+    /// interrupt delivered between blocks.
+    ///
+    /// # Safety
+    ///
+    /// See [`Executable::execute`].
+    pub unsafe fn deliver(
+        &mut self,
+        interrupt: Interrupt,
+        mmu: &SoftMmu,
+        handler: &mut impl InterruptHandler<C::Context>,
+    ) -> BlockExit {
+        let exit = match handler.handle(&mut self.context, interrupt) {
+            InterruptAction::Resume | InterruptAction::Retry => BlockExit::Continue,
+            InterruptAction::Redirect(pc) => {
+                self.context.set_pc(pc);
+                BlockExit::Continue
+            }
+            InterruptAction::Take => BlockExit::Take(interrupt),
+            InterruptAction::Stop => BlockExit::Stop(interrupt),
+        };
+        self.settle(exit, mmu)
+    }
+
+    /// Let the guest take `interrupt`, running the block of [`Architecture::interrupt`] that
+    /// moves it to its exception vector.
+    ///
+    /// # Safety
+    ///
+    /// See [`Executable::execute`].
+    pub unsafe fn take(&mut self, interrupt: Interrupt, mmu: &SoftMmu) {
+        let executable = self.codegen.compile::<A>(A::interrupt(interrupt));
+        let outcome = executable.execute(&mut self.context, mmu);
+        debug_assert!(matches!(outcome, ExecOutcome::Completed));
+    }
+
+    unsafe fn settle(&mut self, exit: BlockExit, mmu: &SoftMmu) -> BlockExit {
+        match exit {
+            BlockExit::Take(interrupt) => {
+                self.take(interrupt, mmu);
+                BlockExit::Continue
+            }
+            exit => exit,
+        }
+    }
+}
+
+/// Decode the guest code at the pc of `context` into a basic block, returns it with its
+/// instruction count. The code is fetched from the virtual addresses translated in the state
+/// of `context`.
+///
+/// The block ends at the first instruction that sets a terminator, at the first
+/// instruction that can't be fetched, at the end of the page, or after [`MAX_BLOCK_INSTS`]
+/// instructions. Fails with an abort only if the first instruction can't be fetched.
+///
+/// # Safety
+///
+/// The guest memory is read through `mmu` without any checks.
+pub unsafe fn translate<A: Architecture>(
+    context: &mut impl ExecutionContext,
+    mmu: &SoftMmu,
+) -> Result<(BasicBlock, u64), Interrupt> {
+    let pc = context.pc();
+    let mut bb = BasicBlock::new(pc);
+    let mut addr = pc;
+    let mut inst_count = 0;
+
+    while inst_count < MAX_BLOCK_INSTS as u64 {
+        if inst_count > 0 && addr.is_multiple_of(PAGE_SIZE) {
+            break;
+        }
+
+        let mut raw = [0; MAX_INST_SIZE];
+        let (fetched, stopped) = fetch(context, addr, mmu, &mut raw);
+
+        let inst = (fetched > 0)
+            .then(|| A::Inst::decode(&raw[..fetched]))
+            .filter(|inst| inst.size() as usize <= fetched);
+
+        let Some(inst) = inst else {
+            if inst_count == 0 {
+                return Err(stopped.unwrap_or(Interrupt::Aborts(Abort {
+                    fault_address: addr.wrapping_add(fetched as u64),
+                    access: AccessType::Execute,
+                    kind: AbortKind::Unmapped,
+                    syndrome: 0,
+                })));
+            }
+            break;
+        };
+
+        inst.compile_to_ir(&mut bb);
+        addr = addr.wrapping_add(inst.size());
+        inst_count += 1;
+
+        if bb.terminator() != BasicBlockTerminator::None {
+            return Ok((bb, inst_count));
+        }
+    }
+
+    bb.set_terminator(BasicBlockTerminator::Next);
+    Ok((bb, inst_count))
+}
+
+/// Read as many bytes as possible from the virtual `addr`, returns the number of bytes read
+/// and the abort of the translation that stopped the read, if any.
+unsafe fn fetch(
+    context: &mut impl ExecutionContext,
+    addr: u64,
+    mmu: &SoftMmu,
+    buf: &mut [u8],
+) -> (usize, Option<Interrupt>) {
+    let mut read = 0;
+    while read < buf.len() {
+        let virtual_addr = addr.wrapping_add(read as u64);
+        let physical_addr = match context.translate(virtual_addr, AccessType::Execute, mmu) {
+            Ok(physical_addr) => physical_addr,
+            Err(interrupt) => return (read, Some(interrupt)),
+        };
+
+        let in_page = (PAGE_SIZE - virtual_addr % PAGE_SIZE) as usize;
+        let end = buf.len().min(read + in_page);
+        match mmu.try_read_at(physical_addr, &mut buf[read..end]) {
+            Some(len) if len > 0 => read += len,
+            _ => break,
+        }
+    }
+    (read, None)
+}
@@ -188,6 +188,10 @@ impl_set_bit!(i64);
 impl_set_bit!(i128);
 
 #[derive(Clone, Copy)]
+/// Extracts the bits `L..R` of the matched bytes into `T`.
+///
+/// Bits are numbered the same way the patterns are written: the bytes are read
+/// most significant first, so bit 0 is the lowest bit of the last byte.
 pub struct Extract<T, const L: usize, const R: usize>(pub T);
 impl<T, const L: usize, const R: usize> ExtractFromBytes for Extract<T, L, R>
 where
@@ -195,8 +199,8 @@ where
 {
     fn extract(from: &[u8]) -> Self {
         let mut result = T::default();
-        for (i, v) in (L..=R).enumerate() {
-            result.set_bit(i, from[v / 8] & (1 << (v % 8)) != 0);
+        for (i, v) in (L..R).enumerate() {
+            result.set_bit(i, from[from.len() - 1 - v / 8] & (1 << (v % 8)) != 0);
         }
 
         Self(result)
@@ -846,6 +850,17 @@ mod tests {
         assert_eq!(tester.test(&vec![0b1110_1111, 0b1000_0000]), false);
     }
 
+    #[test]
+    fn extract_bit_ranges() {
+        let bytes = [0b1010_0000, 0b0000_0000, 0b0000_0011, 0b1110_0101];
+
+        assert_eq!(Extract::<u8, 0, 5>::extract(&bytes).0, 0b00101);
+        assert_eq!(Extract::<u8, 5, 8>::extract(&bytes).0, 0b111);
+        assert_eq!(Extract::<u16, 8, 10>::extract(&bytes).0, 0b11);
+        assert_eq!(Extract::<u8, 29, 32>::extract(&bytes).0, 0b101);
+        assert_eq!(Extract::<u32, 0, 32>::extract(&bytes).0, 0xa000_03e5);
+    }
+
     #[test]
     fn test_ones_u8() {
         assert_eq!(ones_u8(0), 0b0);
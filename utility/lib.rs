@@ -2,3 +2,5 @@ mod bit_patterns;
 pub use bit_patterns::*;
 mod array;
 pub use array::*;
+mod rng;
+pub use rng::*;
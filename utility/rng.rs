@@ -0,0 +1,41 @@
+/// A small, seedable pseudo random number generator (SplitMix64).
+///
+/// It is not suitable for cryptography, it exists to make randomized behaviors like
+/// scheduling and fuzzing reproducible from a single seed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Generate a number in `0..bound`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound != 0, "bound must not be zero");
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+
+    pub fn next_bool(&mut self) -> bool {
+        self.next_u64() & 1 != 0
+    }
+
+    /// Shuffle the slice in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            slice.swap(i, j);
+        }
+    }
+}